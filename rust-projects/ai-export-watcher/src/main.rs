@@ -1,11 +1,20 @@
+mod queue;
+
 use anyhow::{Context, Result};
+use chatgpt_to_continuum::{ConvertOptions, ExportFormat, ImportEvent, ImportSummary};
 use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher, Event, EventKind};
+use queue::{FileStatus, WorkQueue};
 use regex::Regex;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::mpsc::channel;
+use std::sync::Arc;
 use std::time::Duration;
 
+/// Converter runs at a time (override with AI_EXPORT_WATCHER_WORKERS).
+/// Sequential by default so converters never contend on output directories.
+const DEFAULT_WORKERS: usize = 1;
+
 fn main() -> Result<()> {
     let home = std::env::var("HOME").context("HOME not set")?;
     let downloads_dir = PathBuf::from(&home).join("Downloads");
@@ -32,6 +41,19 @@ fn main() -> Result<()> {
     // TM3 diary HTML exports (SingleFile captures)
     let tm3_pattern = Regex::new(r"(?i)TM3.*Diary.*\.html$")?;
 
+    let workers = std::env::var("AI_EXPORT_WATCHER_WORKERS")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .filter(|&n| n > 0)
+        .unwrap_or(DEFAULT_WORKERS);
+
+    let queue = Arc::new(WorkQueue::new());
+    for _ in 0..workers {
+        let queue = queue.clone();
+        let tm3_pattern = tm3_pattern.clone();
+        std::thread::spawn(move || run_worker(&queue, &tm3_pattern));
+    }
+
     println!("Watching for new exports...\n");
 
     for event in rx {
@@ -42,17 +64,8 @@ fn main() -> Result<()> {
                         continue;
                     }
 
-                    // Small delay to ensure file is fully written
-                    std::thread::sleep(Duration::from_millis(500));
-
-                    if export_pattern.is_match(filename) {
-                        if let Err(e) = process_export(&path) {
-                            eprintln!("Error processing {:?}: {}", path, e);
-                        }
-                    } else if tm3_pattern.is_match(filename) {
-                        if let Err(e) = process_tm3(&path) {
-                            eprintln!("Error processing {:?}: {}", path, e);
-                        }
+                    if export_pattern.is_match(filename) || tm3_pattern.is_match(filename) {
+                        queue.push(&path);
                     }
                 }
             }
@@ -62,48 +75,119 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn process_export(path: &Path) -> Result<()> {
-    println!("📥 Detected: {:?}", path.file_name().unwrap_or_default());
-
-    // Run chatgpt-to-continuum (handles ChatGPT, Grok, Gemini)
-    let output = Command::new("chatgpt-to-continuum")
-        .arg(path)
-        .output()
-        .context("Failed to run chatgpt-to-continuum")?;
+/// Process queued files one at a time, forever
+fn run_worker(queue: &WorkQueue, tm3_pattern: &Regex) {
+    loop {
+        let path = queue.pop();
 
-    if output.status.success() {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        println!("✅ Converted successfully");
-        for line in stdout.lines() {
-            if line.contains("Created:") || line.contains("Messages:") || line.contains("Assistant:") {
-                println!("   {}", line.trim());
-            }
+        queue.set_status(&path, FileStatus::WaitingStable);
+        if !wait_until_stable(&path) {
+            eprintln!("Error processing {:?}: file disappeared before it was complete", path);
+            queue.set_status(&path, FileStatus::Failed);
+            continue;
         }
 
-        // Rename to indicate it's been processed
-        let processed_name = path.with_extension("json.imported");
-        if let Err(e) = std::fs::rename(path, &processed_name) {
-            eprintln!("   Warning: couldn't rename file: {}", e);
+        queue.set_status(&path, FileStatus::Converting);
+        let filename = path.file_name().and_then(|f| f.to_str()).unwrap_or_default();
+        let result = if tm3_pattern.is_match(filename) {
+            process_tm3(&path)
         } else {
-            println!("   Renamed to {:?}", processed_name.file_name().unwrap_or_default());
+            process_export(&path)
+        };
+
+        match result {
+            Ok(()) => queue.set_status(&path, FileStatus::Done),
+            Err(e) => {
+                eprintln!("Error processing {:?}: {}", path, e);
+                queue.set_status(&path, FileStatus::Failed);
+            }
         }
+    }
+}
 
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
+/// Wait until the file size stops changing (download finished).
+/// Returns false if the file vanished.
+fn wait_until_stable(path: &Path) -> bool {
+    let mut last_size = None;
+    for _ in 0..120 {
+        std::thread::sleep(Duration::from_millis(500));
+        let Ok(metadata) = std::fs::metadata(path) else {
+            return false;
+        };
+        if last_size == Some(metadata.len()) {
+            return true;
+        }
+        last_size = Some(metadata.len());
+    }
+    // Still growing after a minute: try anyway rather than stall the queue
+    true
+}
 
-        // Notify failure via messageboard
-        let filename = path.file_name().unwrap_or_default().to_string_lossy();
-        let _ = Command::new("messageboard-edit")
-            .args(["insert", &format!("AI import FAILED: {}", filename)])
-            .output();
+fn process_export(path: &Path) -> Result<()> {
+    println!("📥 Detected: {:?}", path.file_name().unwrap_or_default());
 
-        anyhow::bail!("chatgpt-to-continuum failed: {}", stderr);
+    // Converted in-process (handles ChatGPT, Grok, Gemini)
+    let result = chatgpt_to_continuum::convert(path, &ConvertOptions::default(), |event| {
+        if let ImportEvent::Failed { index, error } = event {
+            eprintln!("   Conversation {} failed: {:#}", index + 1, error);
+        }
+    });
+
+    match result {
+        Ok(summary) => {
+            println!("✅ Converted successfully");
+            print_summary(&summary);
+
+            // Rename to indicate it's been processed
+            let processed_name = path.with_extension("json.imported");
+            if let Err(e) = std::fs::rename(path, &processed_name) {
+                eprintln!("   Warning: couldn't rename file: {}", e);
+            } else {
+                println!("   Renamed to {:?}", processed_name.file_name().unwrap_or_default());
+            }
+        }
+        Err(e) => {
+            // Notify failure via messageboard
+            let filename = path.file_name().unwrap_or_default().to_string_lossy();
+            let _ = Command::new("messageboard-edit")
+                .args(["insert", &format!("AI import FAILED: {}", filename)])
+                .output();
+
+            anyhow::bail!("chatgpt-to-continuum failed: {:#}", anyhow::Error::new(e));
+        }
     }
 
     println!();
     Ok(())
 }
 
+/// Created sessions are listed individually up to this many
+const MAX_LISTED_SESSIONS: usize = 5;
+
+fn print_summary(summary: &ImportSummary) {
+    for created in summary.created.iter().take(MAX_LISTED_SESSIONS) {
+        println!("   Created: {}", created.display());
+    }
+    if summary.created.len() > MAX_LISTED_SESSIONS {
+        println!("   Created: … and {} more", summary.created.len() - MAX_LISTED_SESSIONS);
+    }
+    if !summary.skipped.is_empty() || !summary.merged.is_empty() {
+        println!(
+            "   Already imported: {} skipped, {} merged",
+            summary.skipped.len(),
+            summary.merged.len()
+        );
+    }
+    println!("   Assistant: {}", summary.assistant);
+    println!("   Messages: {}", summary.messages);
+    if summary.format == ExportFormat::Official {
+        println!(
+            "   Conversations: {} ({} ok, {} failed)",
+            summary.conversations, summary.succeeded, summary.failed
+        );
+    }
+}
+
 fn process_tm3(path: &Path) -> Result<()> {
     println!("📋 TM3 diary detected: {:?}", path.file_name().unwrap_or_default());
 
@@ -0,0 +1,189 @@
+//! Deduplicating work queue for detected export files.
+//!
+//! Filesystem events arrive in bursts (several Create/Modify events per
+//! download), so the watcher pushes every match here and workers pop each
+//! path once, tracking it through detected → waiting-stable → converting →
+//! done/failed.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Condvar, Mutex};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileStatus {
+    Detected,
+    WaitingStable,
+    Converting,
+    Done,
+    Failed,
+}
+
+impl FileStatus {
+    /// Whether the file has left the pipeline (so a new event may re-queue it)
+    fn is_finished(self) -> bool {
+        matches!(self, FileStatus::Done | FileStatus::Failed)
+    }
+}
+
+impl fmt::Display for FileStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            FileStatus::Detected => "detected",
+            FileStatus::WaitingStable => "waiting-stable",
+            FileStatus::Converting => "converting",
+            FileStatus::Done => "done",
+            FileStatus::Failed => "failed",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Default)]
+struct QueueState {
+    pending: VecDeque<PathBuf>,
+    status: HashMap<PathBuf, FileStatus>,
+}
+
+#[derive(Default)]
+pub struct WorkQueue {
+    state: Mutex<QueueState>,
+    available: Condvar,
+}
+
+impl WorkQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a path unless it is already queued or being processed.
+    /// Returns true if the path was added.
+    pub fn push(&self, path: &Path) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if state.status.get(path).is_some_and(|s| !s.is_finished()) {
+            return false;
+        }
+        state.status.insert(path.to_path_buf(), FileStatus::Detected);
+        state.pending.push_back(path.to_path_buf());
+        drop(state);
+
+        log_transition(path, None, FileStatus::Detected);
+        self.available.notify_one();
+        true
+    }
+
+    /// Block until a path is available
+    pub fn pop(&self) -> PathBuf {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if let Some(path) = state.pending.pop_front() {
+                return path;
+            }
+            state = self.available.wait(state).unwrap();
+        }
+    }
+
+    /// Take the next path without blocking
+    #[cfg(test)]
+    pub fn try_pop(&self) -> Option<PathBuf> {
+        self.state.lock().unwrap().pending.pop_front()
+    }
+
+    pub fn set_status(&self, path: &Path, status: FileStatus) {
+        let previous = self
+            .state
+            .lock()
+            .unwrap()
+            .status
+            .insert(path.to_path_buf(), status);
+        log_transition(path, previous, status);
+    }
+
+    #[cfg(test)]
+    pub fn status(&self, path: &Path) -> Option<FileStatus> {
+        self.state.lock().unwrap().status.get(path).copied()
+    }
+}
+
+fn log_transition(path: &Path, from: Option<FileStatus>, to: FileStatus) {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    match from {
+        Some(from) if !from.is_finished() => println!("   [{}] {} → {}", name, from, to),
+        _ => println!("   [{}] {}", name, to),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn burst_of_duplicate_events_queues_once() {
+        let queue = WorkQueue::new();
+        let path = Path::new("/tmp/ChatGPT-burst.json");
+
+        let accepted = (0..5).filter(|_| queue.push(path)).count();
+
+        assert_eq!(accepted, 1);
+        assert_eq!(queue.try_pop().as_deref(), Some(path));
+        assert_eq!(queue.try_pop(), None);
+    }
+
+    #[test]
+    fn events_during_processing_are_ignored() {
+        let queue = WorkQueue::new();
+        let path = Path::new("/tmp/Grok-busy.json");
+
+        queue.push(path);
+        let popped = queue.pop();
+        queue.set_status(&popped, FileStatus::WaitingStable);
+        assert!(!queue.push(path));
+        queue.set_status(&popped, FileStatus::Converting);
+        assert!(!queue.push(path));
+
+        assert_eq!(queue.try_pop(), None);
+        assert_eq!(queue.status(path), Some(FileStatus::Converting));
+    }
+
+    #[test]
+    fn finished_files_can_be_requeued() {
+        let queue = WorkQueue::new();
+        let path = Path::new("/tmp/Gemini-again.json");
+
+        queue.push(path);
+        queue.pop();
+        queue.set_status(path, FileStatus::Failed);
+
+        assert!(queue.push(path));
+        assert_eq!(queue.status(path), Some(FileStatus::Detected));
+    }
+
+    #[test]
+    fn interleaved_bursts_keep_first_seen_order() {
+        let queue = WorkQueue::new();
+        let a = Path::new("/tmp/ChatGPT-a.json");
+        let b = Path::new("/tmp/ChatGPT-b.json");
+
+        for path in [a, b, a, a, b, a] {
+            queue.push(path);
+        }
+
+        assert_eq!(queue.try_pop().as_deref(), Some(a));
+        assert_eq!(queue.try_pop().as_deref(), Some(b));
+        assert_eq!(queue.try_pop(), None);
+    }
+
+    #[test]
+    fn pop_wakes_blocked_worker() {
+        let queue = std::sync::Arc::new(WorkQueue::new());
+        let worker = {
+            let queue = queue.clone();
+            std::thread::spawn(move || queue.pop())
+        };
+
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        queue.push(Path::new("/tmp/ChatGPT-late.json"));
+
+        assert_eq!(worker.join().unwrap(), PathBuf::from("/tmp/ChatGPT-late.json"));
+    }
+}
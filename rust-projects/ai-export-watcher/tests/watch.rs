@@ -0,0 +1,109 @@
+//! Runs the watcher binary against a scratch ~/Downloads and drops exports
+//! into it, checking what gets converted, renamed and reported.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command};
+use std::time::{Duration, Instant};
+
+struct Watcher {
+    child: Child,
+    stdout: PathBuf,
+    stderr: PathBuf,
+}
+
+impl Watcher {
+    fn start(home: &Path) -> Self {
+        fs::create_dir_all(home.join("Downloads")).unwrap();
+        let stdout = home.join("watcher.stdout");
+        let stderr = home.join("watcher.stderr");
+        let child = Command::new(env!("CARGO_BIN_EXE_ai-export-watcher"))
+            .env("HOME", home)
+            // Keep failure notifications (messageboard-edit) off the real system
+            .env("PATH", "")
+            .stdout(fs::File::create(&stdout).unwrap())
+            .stderr(fs::File::create(&stderr).unwrap())
+            .spawn()
+            .unwrap();
+        // Give the watcher time to register before files appear
+        std::thread::sleep(Duration::from_millis(500));
+        Watcher { child, stdout, stderr }
+    }
+
+    fn stdout(&self) -> String {
+        fs::read_to_string(&self.stdout).unwrap_or_default()
+    }
+
+    fn stderr(&self) -> String {
+        fs::read_to_string(&self.stderr).unwrap_or_default()
+    }
+
+    /// Wait for the file's final status line (done or failed)
+    fn wait_finished(&self, filename: &str) -> bool {
+        let done = format!("[{}] converting → done", filename);
+        let failed = format!("[{}] converting → failed", filename);
+        let deadline = Instant::now() + Duration::from_secs(30);
+        while Instant::now() < deadline {
+            let stdout = self.stdout();
+            if stdout.contains(&done) || stdout.contains(&failed) {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(200));
+        }
+        false
+    }
+}
+
+impl Drop for Watcher {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[test]
+fn converts_and_renames_export() {
+    let home = tempfile::tempdir().unwrap();
+    let watcher = Watcher::start(home.path());
+
+    let filename = "ChatGPT-Stoic_reading_list.json";
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(filename);
+    let download = home.path().join("Downloads").join(filename);
+    fs::copy(&fixture, &download).unwrap();
+
+    assert!(watcher.wait_finished(filename), "stdout:\n{}", watcher.stdout());
+    let stdout = watcher.stdout();
+
+    assert!(download.with_extension("json.imported").exists());
+    assert!(!download.exists());
+
+    let session_dir = home.path().join("Assistants/continuum-logs/chatgpt/2025-11-24/stoic-reading-list");
+    assert!(session_dir.join("messages.jsonl").exists());
+    assert!(session_dir.join("session.json").exists());
+
+    assert!(stdout.contains("✅ Converted successfully"));
+    assert!(stdout.contains("   Created: 2025-11-24/stoic-reading-list"));
+    assert!(stdout.contains("   Assistant: chatgpt"));
+    assert!(stdout.contains("   Messages: 3"));
+}
+
+#[test]
+fn unrecognized_export_is_left_in_place() {
+    let home = tempfile::tempdir().unwrap();
+    let watcher = Watcher::start(home.path());
+
+    let filename = "Grok-broken.json";
+    let download = home.path().join("Downloads").join(filename);
+    fs::write(&download, r#"{"not": "an export"}"#).unwrap();
+
+    assert!(watcher.wait_finished(filename), "stdout:\n{}", watcher.stdout());
+
+    assert!(download.exists());
+    assert!(!download.with_extension("json.imported").exists());
+    assert!(watcher.stdout().contains("[Grok-broken.json] converting → failed"));
+    assert!(
+        watcher.stderr().contains("chatgpt-to-continuum failed: Unrecognized JSON format"),
+        "stderr:\n{}",
+        watcher.stderr()
+    );
+}
@@ -30,32 +30,336 @@ pub fn create_deck(deck: &str) -> Result<()> {
     Ok(())
 }
 
-pub fn add_notes(deck: &str, cards: &[Card]) -> Result<usize> {
-    let notes: Vec<Value> = cards
-        .iter()
-        .map(|c| {
-            json!({
-                "deckName": deck,
-                "modelName": "Basic",
-                "fields": {
-                    "Front": c.front,
-                    "Back": c.back,
-                },
-                "options": {
-                    "allowDuplicate": false,
-                },
-            })
-        })
-        .collect();
+/// Add cards as notes, returning each card's new note id (None where Anki
+/// rejected it, e.g. as a duplicate), in card order
+pub fn add_notes(deck: &str, cards: &[Card]) -> Result<Vec<Option<u64>>> {
+    let notes: Vec<Value> = cards.iter().map(|c| note_json(deck, c)).collect();
 
     let response = anki_request("addNotes", json!({ "notes": notes }))?;
+    parse_note_ids(&response, cards.len())
+}
+
+/// addNotes entry for one card, tagged with its difficulty if it has one
+fn note_json(deck: &str, card: &Card) -> Value {
+    let tags: Vec<String> = card.difficulty.map(|d| d.tag()).into_iter().collect();
+    json!({
+        "deckName": deck,
+        "modelName": "Basic",
+        "fields": {
+            "Front": card.front,
+            "Back": card.back,
+        },
+        "tags": tags,
+        "options": {
+            "allowDuplicate": false,
+        },
+    })
+}
 
+fn parse_note_ids(response: &Value, expected: usize) -> Result<Vec<Option<u64>>> {
     // addNotes returns an array of note IDs (null for failures)
     let results = response
         .get("result")
         .and_then(|r| r.as_array())
         .context("Unexpected response format from addNotes")?;
 
-    let added = results.iter().filter(|r| !r.is_null()).count();
-    Ok(added)
+    if results.len() != expected {
+        anyhow::bail!("addNotes returned {} results for {} notes", results.len(), expected);
+    }
+
+    Ok(results.iter().map(|r| r.as_u64()).collect())
+}
+
+/// Subdeck for a card's topic: `<deck>::<topic>`, or `deck` itself when
+/// the topic is missing or blank. `::` inside a topic is flattened so a
+/// topic can't nest deeper than one level.
+pub fn subdeck(deck: &str, topic: Option<&str>) -> String {
+    let topic = topic
+        .map(|t| t.replace("::", " ").split_whitespace().collect::<Vec<_>>().join(" "))
+        .unwrap_or_default();
+    if topic.is_empty() {
+        deck.to_string()
+    } else {
+        format!("{}::{}", deck, topic)
+    }
+}
+
+/// Group card indices by target deck, decks in order of first appearance.
+/// Topics differing only in case share the first spelling seen.
+pub fn group_by_deck(deck: &str, cards: &[Card], flat: bool) -> Vec<(String, Vec<usize>)> {
+    let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+    for (i, card) in cards.iter().enumerate() {
+        let name = if flat {
+            deck.to_string()
+        } else {
+            subdeck(deck, card.topic.as_deref())
+        };
+        match groups.iter_mut().find(|(existing, _)| existing.eq_ignore_ascii_case(&name)) {
+            Some((_, indices)) => indices.push(i),
+            None => groups.push((name, vec![i])),
+        }
+    }
+    groups
+}
+
+/// One AnkiConnect action in, its whole response out. Tests stand in
+/// their own so the note queries can run without Anki.
+pub trait Transport {
+    fn request(&self, action: &str, params: Value) -> Result<Value>;
+}
+
+/// The local AnkiConnect add-on
+pub struct AnkiConnect;
+
+impl Transport for AnkiConnect {
+    fn request(&self, action: &str, params: Value) -> Result<Value> {
+        anki_request(action, params)
+    }
+}
+
+/// Ids of the notes matching an Anki search
+pub fn find_notes(anki: &impl Transport, query: &str) -> Result<Vec<u64>> {
+    let response = anki.request("findNotes", json!({ "query": query }))?;
+    Ok(response
+        .get("result")
+        .and_then(|r| r.as_array())
+        .context("Unexpected response format from findNotes")?
+        .iter()
+        .filter_map(|id| id.as_u64())
+        .collect())
+}
+
+/// Full note records (`noteId`, `fields`, `tags`, ...) for `ids`
+pub fn notes_info(anki: &impl Transport, ids: &[u64]) -> Result<Vec<Value>> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let response = anki.request("notesInfo", json!({ "notes": ids }))?;
+    response
+        .get("result")
+        .and_then(|r| r.as_array())
+        .cloned()
+        .context("Unexpected response format from notesInfo")
+}
+
+/// Most existing fronts passed on to the extraction prompt, newest first,
+/// so a large deck can't swamp it
+pub const MAX_EXISTING_FRONTS: usize = 300;
+
+/// Fronts of the notes already in `deck` or its subdecks, newest first
+/// (note ids are creation timestamps), at most `limit` of them
+pub fn deck_fronts(anki: &impl Transport, deck: &str, limit: Option<usize>) -> Result<Vec<String>> {
+    let mut ids = find_notes(anki, &deck_query(deck))?;
+    ids.sort_unstable_by(|a, b| b.cmp(a));
+    if let Some(limit) = limit {
+        ids.truncate(limit);
+    }
+    Ok(notes_info(anki, &ids)?.iter().filter_map(first_field).collect())
+}
+
+/// `deck_fronts` for the extraction prompt
+pub fn existing_fronts(deck: &str) -> Result<Vec<String>> {
+    deck_fronts(&AnkiConnect, deck, Some(MAX_EXISTING_FRONTS))
+}
+
+/// Search matching `deck` and its subdecks, quoted so spaces are safe
+fn deck_query(deck: &str) -> String {
+    format!("\"deck:{}\"", deck.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// A notesInfo record's first field (the one Anki checks for duplicates:
+/// Front on Basic notes), trimmed, or None when empty
+fn first_field(note: &Value) -> Option<String> {
+    let fields = note.get("fields")?.as_object()?;
+    let first = fields
+        .values()
+        .min_by_key(|field| field.get("order").and_then(|o| o.as_u64()).unwrap_or(u64::MAX))?;
+    let value = first.get("value")?.as_str()?.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+pub struct DeckStats {
+    pub total: u64,
+    pub new: u64,
+    pub due: u64,
+}
+
+/// Current card counts for a deck via `getDeckStats`
+pub fn deck_stats(deck: &str) -> Result<DeckStats> {
+    let response = anki_request("getDeckStats", json!({ "decks": [deck] }))?;
+
+    // getDeckStats returns an object keyed by deck ID
+    let stats = response
+        .get("result")
+        .and_then(|r| r.as_object())
+        .and_then(|m| m.values().find(|s| s.get("name").and_then(|n| n.as_str()) == Some(deck)))
+        .context("Deck not found in getDeckStats response")?;
+
+    let count = |key: &str| stats.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+
+    Ok(DeckStats {
+        total: count("total_in_deck"),
+        new: count("new_count"),
+        due: count("learn_count") + count("review_count"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Difficulty;
+
+    fn card(topic: Option<&str>) -> Card {
+        Card {
+            front: "Q".to_string(),
+            back: "A".to_string(),
+            topic: topic.map(str::to_string),
+            difficulty: None,
+        }
+    }
+
+    #[test]
+    fn difficulty_becomes_a_tag() {
+        let mut hard = card(None);
+        hard.difficulty = Some(Difficulty::Hard);
+        let note = note_json("Bio", &hard);
+        assert_eq!(note["tags"], json!(["difficulty::hard"]));
+        assert_eq!(note["deckName"], "Bio");
+        assert_eq!(note["fields"]["Front"], "Q");
+
+        assert_eq!(note_json("Bio", &card(None))["tags"], json!([]));
+    }
+
+    #[test]
+    fn subdeck_names() {
+        assert_eq!(subdeck("Continuum", Some("Memory")), "Continuum::Memory");
+        assert_eq!(subdeck("Continuum", Some("  Spaced   Repetition ")), "Continuum::Spaced Repetition");
+        assert_eq!(subdeck("Continuum", Some("A::B")), "Continuum::A B");
+        assert_eq!(subdeck("Continuum", Some(" ")), "Continuum");
+        assert_eq!(subdeck("Continuum", None), "Continuum");
+    }
+
+    #[test]
+    fn cards_grouped_by_topic_in_order() {
+        let cards = [card(Some("Memory")), card(None), card(Some("Sleep")), card(Some("memory"))];
+        let groups = group_by_deck("Bio", &cards, false);
+        assert_eq!(
+            groups,
+            vec![
+                ("Bio::Memory".to_string(), vec![0, 3]),
+                ("Bio".to_string(), vec![1]),
+                ("Bio::Sleep".to_string(), vec![2]),
+            ]
+        );
+    }
+
+    #[test]
+    fn flat_keeps_single_deck() {
+        let cards = [card(Some("Memory")), card(Some("Sleep"))];
+        assert_eq!(group_by_deck("Bio", &cards, true), vec![("Bio".to_string(), vec![0, 1])]);
+    }
+
+    #[test]
+    fn deck_query_is_quoted() {
+        assert_eq!(deck_query("Continuum::Memory"), r#""deck:Continuum::Memory""#);
+        assert_eq!(deck_query(r#"My "Deck""#), r#""deck:My \"Deck\"""#);
+    }
+
+    /// Canned AnkiConnect: answers each action from a table and records
+    /// the requests it saw
+    struct Fake {
+        responses: Vec<(&'static str, Value)>,
+        seen: std::cell::RefCell<Vec<(String, Value)>>,
+    }
+
+    impl Fake {
+        fn new(responses: Vec<(&'static str, Value)>) -> Self {
+            Self { responses, seen: Default::default() }
+        }
+    }
+
+    impl Transport for Fake {
+        fn request(&self, action: &str, params: Value) -> Result<Value> {
+            self.seen.borrow_mut().push((action.to_string(), params));
+            self.responses
+                .iter()
+                .find(|(name, _)| *name == action)
+                .map(|(_, response)| response.clone())
+                .context("unexpected action")
+        }
+    }
+
+    fn deck() -> Fake {
+        Fake::new(vec![
+            ("findNotes", json!({ "result": [100, 300, 200], "error": null })),
+            (
+                "notesInfo",
+                json!({
+                    "result": [
+                        { "noteId": 300, "fields": {
+                            "Back": { "value": "A", "order": 1 },
+                            "Front": { "value": " What is spacing? ", "order": 0 },
+                        } },
+                        { "noteId": 200, "fields": { "Text": { "value": "Cloze {{c1::x}}", "order": 0 } } },
+                        { "noteId": 100, "fields": { "Front": { "value": "", "order": 0 } } },
+                    ],
+                    "error": null,
+                }),
+            ),
+        ])
+    }
+
+    #[test]
+    fn deck_fronts_are_first_fields_newest_first() {
+        let anki = deck();
+        let fronts = deck_fronts(&anki, "Bio", None).unwrap();
+        assert_eq!(fronts, vec!["What is spacing?", "Cloze {{c1::x}}"]);
+
+        let seen = anki.seen.borrow();
+        assert_eq!(seen[0], ("findNotes".to_string(), json!({ "query": "\"deck:Bio\"" })));
+        assert_eq!(seen[1], ("notesInfo".to_string(), json!({ "notes": [300, 200, 100] })));
+    }
+
+    #[test]
+    fn deck_fronts_limit_keeps_newest() {
+        let anki = deck();
+        deck_fronts(&anki, "Bio", Some(2)).unwrap();
+        assert_eq!(anki.seen.borrow()[1].1, json!({ "notes": [300, 200] }));
+    }
+
+    #[test]
+    fn empty_deck_skips_notes_info() {
+        let anki = Fake::new(vec![("findNotes", json!({ "result": [], "error": null }))]);
+        assert!(deck_fronts(&anki, "Bio", None).unwrap().is_empty());
+        assert_eq!(anki.seen.borrow().len(), 1);
+    }
+
+    #[test]
+    fn malformed_responses_are_errors() {
+        let anki = Fake::new(vec![
+            ("findNotes", json!({ "result": null, "error": null })),
+            ("notesInfo", json!({ "result": null, "error": null })),
+        ]);
+        assert!(find_notes(&anki, "deck:Bio").is_err());
+        assert!(notes_info(&anki, &[1]).is_err());
+    }
+
+    #[test]
+    fn note_ids_follow_card_order() {
+        let response = json!({ "result": [1712345678901u64, null, 1712345678902u64], "error": null });
+        let ids = parse_note_ids(&response, 3).unwrap();
+        assert_eq!(ids, vec![Some(1712345678901), None, Some(1712345678902)]);
+    }
+
+    #[test]
+    fn note_ids_count_mismatch_is_an_error() {
+        let response = json!({ "result": [1], "error": null });
+        assert!(parse_note_ids(&response, 2).is_err());
+    }
+
+    #[test]
+    fn note_ids_missing_result_is_an_error() {
+        let response = json!({ "result": null, "error": null });
+        assert!(parse_note_ids(&response, 1).is_err());
+    }
 }
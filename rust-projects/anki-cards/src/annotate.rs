@@ -0,0 +1,258 @@
+//! Source annotation: an `## Anki` section recording what has been carded.
+//!
+//! ```markdown
+//! ## Anki
+//!
+//! <!-- anki-cards regions: 9c1b3f2a0e4d5c6b 1d22e0f39a8b7c6d -->
+//! - What does X guarantee? <!-- nid:1712345678901 -->
+//! ```
+//!
+//! `regions` are fingerprints of the paragraphs that were sent for
+//! extraction, so `--skip-existing` can leave them out next time. The section
+//! is owned by this tool and rewritten on each run; everything outside it is
+//! left byte-for-byte as it was.
+
+use anyhow::{Context, Result};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+const SECTION_HEADING: &str = "## Anki";
+const REGIONS_PREFIX: &str = "<!-- anki-cards regions:";
+
+#[derive(Debug, Default, PartialEq)]
+struct Annotation {
+    regions: BTreeSet<String>,
+    cards: Vec<(u64, String)>,
+}
+
+impl Annotation {
+    fn parse(section: &str) -> Self {
+        let mut annotation = Annotation::default();
+        for line in section.lines() {
+            let line = line.trim();
+            if let Some(rest) = line.strip_prefix(REGIONS_PREFIX) {
+                let rest = rest.trim_end_matches("-->");
+                annotation.regions.extend(rest.split_whitespace().map(String::from));
+            } else if let Some(item) = line.strip_prefix("- ") {
+                if let Some((front, id)) = item.rsplit_once("<!-- nid:") {
+                    if let Ok(id) = id.trim_end_matches("-->").trim().parse() {
+                        annotation.cards.push((id, front.trim().to_string()));
+                    }
+                }
+            }
+        }
+        annotation
+    }
+
+    fn render(&self) -> String {
+        let mut out = format!("{}\n\n", SECTION_HEADING);
+        out.push_str(REGIONS_PREFIX);
+        for region in &self.regions {
+            out.push(' ');
+            out.push_str(region);
+        }
+        out.push_str(" -->\n");
+        for (id, front) in &self.cards {
+            out.push_str(&format!("- {} <!-- nid:{} -->\n", front, id));
+        }
+        out
+    }
+}
+
+/// Byte range of the `## Anki` section: from its heading to the next
+/// heading of the same or higher level, or the end of the file.
+fn section_range(content: &str) -> Option<(usize, usize)> {
+    let mut offset = 0;
+    let mut start = None;
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim_end();
+        match start {
+            None if trimmed == SECTION_HEADING => start = Some(offset),
+            Some(s) if trimmed.starts_with("# ") || trimmed.starts_with("## ") => return Some((s, offset)),
+            _ => {}
+        }
+        offset += line.len();
+    }
+    start.map(|s| (s, content.len()))
+}
+
+/// Paragraphs (blank-line separated blocks) of a text, trimmed
+fn paragraphs(text: &str) -> Vec<&str> {
+    let mut paragraphs = Vec::new();
+    let mut current: Option<(usize, usize)> = None;
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            if let Some((s, e)) = current.take() {
+                paragraphs.push(text[s..e].trim());
+            }
+        } else {
+            let start = current.map_or(offset, |(s, _)| s);
+            current = Some((start, offset + line.len()));
+        }
+        offset += line.len();
+    }
+    if let Some((s, e)) = current {
+        paragraphs.push(text[s..e].trim());
+    }
+    paragraphs
+}
+
+/// Stable fingerprint of a paragraph, insensitive to whitespace reflow
+/// (FNV-1a over the whitespace-collapsed text).
+fn fingerprint(paragraph: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    let words: Vec<&str> = paragraph.split_whitespace().collect();
+    for byte in words.join(" ").bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+/// Fingerprints of every paragraph in `text`
+pub fn regions(text: &str) -> Vec<String> {
+    paragraphs(text).into_iter().map(fingerprint).collect()
+}
+
+/// The text to send for extraction: the source without its `## Anki`
+/// section and, with `skip_existing`, without paragraphs already recorded
+/// there.
+pub fn extraction_input(source: &str, skip_existing: bool) -> String {
+    let (body, annotation) = match section_range(source) {
+        Some((start, end)) => (
+            format!("{}{}", &source[..start], &source[end..]),
+            Annotation::parse(&source[start..end]),
+        ),
+        None => (source.to_string(), Annotation::default()),
+    };
+
+    if !skip_existing {
+        return body;
+    }
+    paragraphs(&body)
+        .into_iter()
+        .filter(|p| !annotation.regions.contains(&fingerprint(p)))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Merge `regions` and created `cards` (note id, front) into the source's
+/// `## Anki` section, adding the section at the end if there is none.
+/// Re-applying the same annotation leaves the content unchanged.
+pub fn annotate(source: &str, regions: &[String], cards: &[(u64, &str)]) -> String {
+    let range = section_range(source);
+    let mut annotation = range
+        .map(|(start, end)| Annotation::parse(&source[start..end]))
+        .unwrap_or_default();
+
+    annotation.regions.extend(regions.iter().cloned());
+    for (id, front) in cards {
+        if !annotation.cards.iter().any(|(existing, _)| existing == id) {
+            let front = front.split_whitespace().collect::<Vec<_>>().join(" ");
+            annotation.cards.push((*id, front));
+        }
+    }
+    let section = annotation.render();
+
+    match range {
+        Some((start, end)) => {
+            let separator = if end < source.len() { "\n" } else { "" };
+            format!("{}{}{}{}", &source[..start], section, separator, &source[end..])
+        }
+        None => {
+            let mut out = source.to_string();
+            if !out.is_empty() {
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push('\n');
+            }
+            out.push_str(&section);
+            out
+        }
+    }
+}
+
+pub fn annotate_file(path: &Path, regions: &[String], cards: &[(u64, &str)]) -> Result<()> {
+    // Re-read rather than reuse the input, in case it was edited meanwhile
+    let source = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read file: {}", path.display()))?;
+    let annotated = annotate(&source, regions, cards);
+    if annotated != source {
+        std::fs::write(path, annotated)
+            .with_context(|| format!("Failed to write file: {}", path.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOTE: &str = "# Ownership\n\nRust moves values by default.\n\n\
+                        Borrowing lends access\nwithout moving.\n";
+
+    #[test]
+    fn appends_section_preserving_source() {
+        let regions = regions(NOTE);
+        let out = annotate(NOTE, &regions, &[(1001, "What does a move do?")]);
+
+        assert!(out.starts_with(NOTE));
+        assert_eq!(
+            &out[NOTE.len()..],
+            format!(
+                "\n## Anki\n\n<!-- anki-cards regions: {} -->\n- What does a move do? <!-- nid:1001 -->\n",
+                {
+                    let mut sorted = regions.clone();
+                    sorted.sort();
+                    sorted.join(" ")
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn annotation_is_idempotent() {
+        let regions = regions(NOTE);
+        let cards = [(1001, "What does a move do?"), (1002, "What is borrowing?")];
+        let once = annotate(NOTE, &regions, &cards);
+        let twice = annotate(&once, &regions, &cards);
+        assert_eq!(once, twice);
+    }
+
+    #[test]
+    fn section_in_middle_is_replaced_in_place() {
+        let source = "Intro\n\n## Anki\n\n- Old card <!-- nid:7 -->\n\n## Later\nTail without newline";
+        let out = annotate(source, &[], &[(8, "New card")]);
+        assert_eq!(
+            out,
+            "Intro\n\n## Anki\n\n<!-- anki-cards regions: -->\n- Old card <!-- nid:7 -->\n\
+             - New card <!-- nid:8 -->\n\n## Later\nTail without newline"
+        );
+        assert_eq!(annotate(&out, &[], &[(8, "New card")]), out);
+    }
+
+    #[test]
+    fn skip_existing_drops_recorded_paragraphs() {
+        let first = extraction_input(NOTE, true);
+        let annotated = annotate(NOTE, &regions(&first), &[(1001, "Q")]);
+
+        // Unchanged note: nothing new; section itself never extracted
+        assert_eq!(extraction_input(&annotated, true), "");
+        assert!(!extraction_input(&annotated, false).contains("nid:"));
+
+        // A new paragraph (and a reflowed old one) → only the new one
+        let edited = annotated.replace(
+            "Borrowing lends access\nwithout moving.",
+            "Borrowing lends access without moving.\n\nLifetimes bound borrows.",
+        );
+        assert_eq!(extraction_input(&edited, true), "Lifetimes bound borrows.");
+    }
+
+    #[test]
+    fn without_skip_input_is_source_minus_section() {
+        let source = format!("{}\n## Anki\n\n- Q <!-- nid:1 -->\n", NOTE);
+        assert_eq!(extraction_input(&source, false), format!("{}\n", NOTE));
+    }
+}
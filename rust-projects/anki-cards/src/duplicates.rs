@@ -0,0 +1,84 @@
+use std::collections::HashSet;
+
+use crate::Card;
+
+/// The form fronts are compared in: case folded and whitespace collapsed,
+/// so "What is  Spacing?" and "what is spacing?" are the same card
+pub fn normalise(front: &str) -> String {
+    front.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+/// Fronts already in the target deck, for telling which extracted cards a
+/// push would skip. `--check` and the push itself both go through here, so
+/// the preview's counts match what gets added.
+pub struct Existing {
+    fronts: HashSet<String>,
+}
+
+impl Existing {
+    pub fn new<S: AsRef<str>>(fronts: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            fronts: fronts.into_iter().map(|f| normalise(f.as_ref())).collect(),
+        }
+    }
+
+    /// For each card, whether it duplicates a note in the deck or an
+    /// earlier card in the same batch
+    pub fn duplicates(&self, cards: &[Card]) -> Vec<bool> {
+        let mut seen: HashSet<String> = HashSet::new();
+        cards
+            .iter()
+            .map(|card| {
+                let key = normalise(&card.front);
+                self.fronts.contains(&key) || !seen.insert(key)
+            })
+            .collect()
+    }
+}
+
+/// "12 new, 3 existing"
+pub fn summary(duplicates: &[bool]) -> String {
+    let existing = duplicates.iter().filter(|&&d| d).count();
+    format!("{} new, {} existing", duplicates.len() - existing, existing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(front: &str) -> Card {
+        Card {
+            front: front.to_string(),
+            back: "A".to_string(),
+            topic: None,
+            difficulty: None,
+        }
+    }
+
+    #[test]
+    fn normalise_folds_case_and_whitespace() {
+        assert_eq!(normalise("  What is\n  Spacing? "), "what is spacing?");
+        assert_eq!(normalise("What is spacing?"), normalise("WHAT IS SPACING?"));
+    }
+
+    #[test]
+    fn marks_cards_already_in_deck_and_repeats() {
+        let existing = Existing::new(["What is spacing?", "Why  sleep?"]);
+        let cards = [
+            card("what is SPACING?"),
+            card("What is interleaving?"),
+            card("Why sleep?"),
+            card("What is  interleaving?"),
+        ];
+        let duplicates = existing.duplicates(&cards);
+        assert_eq!(duplicates, vec![true, false, true, true]);
+        assert_eq!(summary(&duplicates), "1 new, 3 existing");
+    }
+
+    #[test]
+    fn empty_deck_marks_nothing() {
+        let existing = Existing::new(Vec::<String>::new());
+        assert_eq!(existing.duplicates(&[card("Q1"), card("Q2")]), vec![false, false]);
+        assert_eq!(summary(&[]), "0 new, 0 existing");
+    }
+}
@@ -11,14 +11,37 @@ Rules:
 - Keep answers concise but complete
 - Skip trivial or obvious content
 - If the text contains no meaningful concepts to extract, return an empty array
+- Give each card a short topic (1-3 words, Title Case) naming the section or theme it belongs to; reuse the same topic for related cards so they group together
+- Rate each card's difficulty as "easy", "medium" or "hard": "hard" for foundational concepts that other ideas build on or that are conceptually demanding, "easy" for simple facts and definitions
 
 Output ONLY a JSON array, no other text:
-[{"front": "question", "back": "answer"}, ...]"#;
+[{"front": "question", "back": "answer", "topic": "topic", "difficulty": "medium"}, ...]"#;
 
-pub fn extract_cards(input: &str) -> Result<Vec<Card>> {
+/// The extraction prompt, listing `covered` (fronts already in the deck)
+/// as questions not to card again
+fn prompt(covered: &[String]) -> String {
+    if covered.is_empty() {
+        return EXTRACTION_PROMPT.to_string();
+    }
+    let mut prompt = EXTRACTION_PROMPT.to_string();
+    prompt.push_str(
+        "\n\nThese questions are already in the deck. Skip any concept they cover, \
+         even if you would word the question differently; only create genuinely new cards:\n",
+    );
+    for front in covered {
+        prompt.push_str("- ");
+        prompt.push_str(&front.split_whitespace().collect::<Vec<_>>().join(" "));
+        prompt.push('\n');
+    }
+    prompt
+}
+
+/// Extract cards from `input`, steering the LLM away from the questions in
+/// `covered`
+pub fn extract_cards(input: &str, covered: &[String]) -> Result<Vec<Card>> {
     let mut cmd = Command::new("claude");
     cmd.env_remove("ANTHROPIC_API_KEY");
-    cmd.args(["-p", EXTRACTION_PROMPT]);
+    cmd.args(["-p", &prompt(covered)]);
     cmd.stdin(std::process::Stdio::piped());
     cmd.stdout(std::process::Stdio::piped());
     cmd.stderr(std::process::Stdio::piped());
@@ -73,6 +96,7 @@ fn parse_cards(text: &str) -> Result<Vec<Card>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Difficulty;
 
     #[test]
     fn parse_clean_json() {
@@ -96,6 +120,43 @@ mod tests {
         assert_eq!(cards.len(), 1);
     }
 
+    #[test]
+    fn parse_topics() {
+        let input = r#"[{"front": "Q1", "back": "A1", "topic": "Memory"}, {"front": "Q2", "back": "A2"}]"#;
+        let cards = parse_cards(input).unwrap();
+        assert_eq!(cards[0].topic.as_deref(), Some("Memory"));
+        assert_eq!(cards[1].topic, None);
+    }
+
+    #[test]
+    fn parse_difficulty_leniently() {
+        let input = r#"[
+            {"front": "Q1", "back": "A1", "difficulty": "hard"},
+            {"front": "Q2", "back": "A2", "difficulty": " Easy "},
+            {"front": "Q3", "back": "A3", "difficulty": "very hard"},
+            {"front": "Q4", "back": "A4", "difficulty": null},
+            {"front": "Q5", "back": "A5"}
+        ]"#;
+        let cards = parse_cards(input).unwrap();
+        let difficulties: Vec<_> = cards.iter().map(|c| c.difficulty).collect();
+        assert_eq!(difficulties, vec![Some(Difficulty::Hard), Some(Difficulty::Easy), None, None, None]);
+
+        let json = serde_json::to_string(&cards[..2]).unwrap();
+        assert!(json.contains(r#""difficulty":"hard""#));
+        assert!(json.contains(r#""difficulty":"easy""#));
+        assert!(!serde_json::to_string(&cards[4]).unwrap().contains("difficulty"));
+    }
+
+    #[test]
+    fn covered_fronts_extend_the_prompt() {
+        assert_eq!(prompt(&[]), EXTRACTION_PROMPT);
+
+        let covered = vec!["What is\nspacing?".to_string(), "Why sleep?".to_string()];
+        let prompt = prompt(&covered);
+        assert!(prompt.starts_with(EXTRACTION_PROMPT));
+        assert!(prompt.ends_with("cards:\n- What is spacing?\n- Why sleep?\n"));
+    }
+
     #[test]
     fn parse_empty_array() {
         let input = "[]";
@@ -1,4 +1,6 @@
 mod anki;
+mod annotate;
+mod duplicates;
 mod extract;
 mod preview;
 
@@ -6,11 +8,58 @@ use anyhow::{Context, Result};
 use clap::Parser;
 use serde::{Deserialize, Serialize};
 use std::io::Read;
+use std::path::Path;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Card {
     pub front: String,
     pub back: String,
+    /// Short topic label from the LLM, used to pick a subdeck
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+    /// How hard or foundational the LLM judged the card; pushed as a
+    /// `difficulty::<level>` tag
+    #[serde(
+        default,
+        deserialize_with = "Difficulty::deserialize_lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub difficulty: Option<Difficulty>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+        }
+    }
+
+    /// Anki tag for this level
+    pub fn tag(self) -> String {
+        format!("difficulty::{}", self.as_str())
+    }
+
+    /// Accept any case and treat unknown values as missing, so one odd
+    /// answer from the LLM doesn't fail the whole extraction
+    fn deserialize_lenient<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<Self>, D::Error> {
+        let value: Option<String> = Option::deserialize(deserializer)?;
+        Ok(value.and_then(|v| match v.trim().to_lowercase().as_str() {
+            "easy" => Some(Difficulty::Easy),
+            "medium" => Some(Difficulty::Medium),
+            "hard" => Some(Difficulty::Hard),
+            _ => None,
+        }))
+    }
 }
 
 #[derive(Parser)]
@@ -24,6 +73,10 @@ struct Cli {
     #[arg(long, default_value = "Continuum")]
     deck: String,
 
+    /// Put every card in --deck instead of per-topic "<deck>::<topic>" subdecks
+    #[arg(long)]
+    flat: bool,
+
     /// Skip preview, push immediately
     #[arg(long)]
     yes: bool,
@@ -35,6 +88,26 @@ struct Cli {
     /// Output extracted cards as JSON (no Anki interaction)
     #[arg(long)]
     json: bool,
+
+    /// After pushing, record the created cards and their Anki note ids in
+    /// an "## Anki" section of the input file
+    #[arg(long, requires = "file")]
+    annotate_source: bool,
+
+    /// Leave out paragraphs already recorded in the input file's "## Anki" section
+    #[arg(long, requires = "file")]
+    skip_existing: bool,
+
+    /// Before extracting, fetch the fronts already in --deck (and its
+    /// subdecks) from Anki and tell the LLM to skip what they cover
+    #[arg(long)]
+    dedupe: bool,
+
+    /// Mark extracted cards already in --deck in the preview, with a count
+    /// of new and existing, and leave them out of the push (combine with
+    /// --dry-run to check without pushing)
+    #[arg(long)]
+    check: bool,
 }
 
 fn read_input(file: Option<&str>) -> Result<String> {
@@ -54,13 +127,39 @@ fn read_input(file: Option<&str>) -> Result<String> {
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
-    let input = read_input(cli.file.as_deref())?;
-    if input.trim().is_empty() {
+    let source = read_input(cli.file.as_deref())?;
+    if source.trim().is_empty() {
         anyhow::bail!("Input is empty — nothing to extract");
     }
 
+    let input = annotate::extraction_input(&source, cli.skip_existing);
+    if input.trim().is_empty() {
+        eprintln!("Everything in the input is already carded — nothing new to extract.");
+        return Ok(());
+    }
+
+    let covered = if cli.dedupe {
+        match anki::existing_fronts(&cli.deck) {
+            Ok(fronts) => {
+                eprintln!(
+                    "Telling claude to skip {} card{} already in \"{}\".",
+                    fronts.len(),
+                    if fronts.len() == 1 { "" } else { "s" },
+                    cli.deck
+                );
+                fronts
+            }
+            Err(e) => {
+                eprintln!("Warning: couldn't read existing cards, extracting without dedupe: {:#}", e);
+                Vec::new()
+            }
+        }
+    } else {
+        Vec::new()
+    };
+
     eprintln!("Extracting cards via claude...");
-    let cards = extract::extract_cards(&input)?;
+    let cards = extract::extract_cards(&input, &covered)?;
 
     if cards.is_empty() {
         eprintln!("No cards extracted from input.");
@@ -73,8 +172,25 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    // --check: compare against the live deck before showing anything
+    let existing = if cli.check {
+        match anki::deck_fronts(&anki::AnkiConnect, &cli.deck, None) {
+            Ok(fronts) => Some(duplicates::Existing::new(fronts)),
+            Err(e) => {
+                eprintln!("Warning: couldn't read existing cards, skipping the duplicate check: {:#}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+    let marks = existing.as_ref().map(|e| e.duplicates(&cards));
+
     // --dry-run or interactive: display cards
-    preview::display_cards(&cards);
+    preview::display_cards(&cards, marks.as_deref());
+    if let Some(marks) = &marks {
+        eprintln!("Against deck \"{}\": {}.\n", cli.deck, duplicates::summary(marks));
+    }
 
     if cli.dry_run {
         return Ok(());
@@ -99,9 +215,29 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
-    // Push to Anki
-    anki::create_deck(&cli.deck)?;
-    let added = anki::add_notes(&cli.deck, &cards_to_push)?;
+    // With --check, leave out the cards it marked, so the push agrees with
+    // its count; otherwise push everything and let Anki reject duplicates
+    let duplicate = match &existing {
+        Some(existing) => existing.duplicates(&cards_to_push),
+        None => vec![false; cards_to_push.len()],
+    };
+    let fresh: Vec<usize> = (0..cards_to_push.len()).filter(|&i| !duplicate[i]).collect();
+    let fresh_cards: Vec<Card> = fresh.iter().map(|&i| cards_to_push[i].clone()).collect();
+
+    // Push to Anki, one addNotes call per (sub)deck
+    let groups: Vec<(String, Vec<usize>)> = anki::group_by_deck(&cli.deck, &fresh_cards, cli.flat)
+        .into_iter()
+        .map(|(deck, indices)| (deck, indices.into_iter().map(|i| fresh[i]).collect()))
+        .collect();
+    let mut note_ids = vec![None; cards_to_push.len()];
+    for (deck, indices) in &groups {
+        anki::create_deck(deck)?;
+        let cards: Vec<Card> = indices.iter().map(|&i| cards_to_push[i].clone()).collect();
+        for (&i, id) in indices.iter().zip(anki::add_notes(deck, &cards)?) {
+            note_ids[i] = id;
+        }
+    }
+    let added = note_ids.iter().filter(|id| id.is_some()).count();
 
     let skipped = cards_to_push.len() - added;
     eprint!("Pushed {} card{} to deck \"{}\"", added, if added == 1 { "" } else { "s" }, cli.deck);
@@ -109,6 +245,40 @@ fn main() -> Result<()> {
         eprint!(" ({} duplicate{} skipped)", skipped, if skipped == 1 { "" } else { "s" });
     }
     eprintln!(".");
+    if groups.len() > 1 || groups.iter().any(|(deck, _)| *deck != cli.deck) {
+        for (deck, indices) in &groups {
+            eprintln!("  {}: {}", deck, indices.len());
+        }
+    }
+
+    if cli.annotate_source {
+        if let Some(path) = cli.file.as_deref() {
+            let created: Vec<(u64, &str)> = cards_to_push
+                .iter()
+                .zip(&note_ids)
+                .filter_map(|(card, id)| id.map(|id| (id, card.front.as_str())))
+                .collect();
+            annotate::annotate_file(Path::new(path), &annotate::regions(&input), &created)?;
+            eprintln!(
+                "Recorded {} card{} in the \"## Anki\" section of {}.",
+                created.len(),
+                if created.len() == 1 { "" } else { "s" },
+                path
+            );
+        }
+    }
+
+    // Best-effort: report the deck's resulting size
+    if let Ok(stats) = anki::deck_stats(&cli.deck) {
+        eprintln!(
+            "Deck \"{}\" now has {} card{} ({} new, {} due).",
+            cli.deck,
+            stats.total,
+            if stats.total == 1 { "" } else { "s" },
+            stats.new,
+            stats.due
+        );
+    }
 
     Ok(())
 }
@@ -9,11 +9,24 @@ pub enum Confirmation {
     Edit(Vec<Card>),
 }
 
-pub fn display_cards(cards: &[Card]) {
+/// Print the cards; with `duplicates` (from `--check`), flag those the
+/// deck already has
+pub fn display_cards(cards: &[Card], duplicates: Option<&[bool]>) {
     eprintln!("\nExtracted {} card{}:\n", cards.len(), if cards.len() == 1 { "" } else { "s" });
     for (i, card) in cards.iter().enumerate() {
-        eprintln!("  {}. Q: {}", i + 1, card.front);
-        eprintln!("     A: {}\n", card.back);
+        let mut labels: Vec<&str> = Vec::new();
+        labels.extend(card.topic.as_deref().filter(|t| !t.trim().is_empty()));
+        labels.extend(card.difficulty.map(|d| d.as_str()));
+        if labels.is_empty() {
+            eprintln!("  {}. Q: {}", i + 1, card.front);
+        } else {
+            eprintln!("  {}. [{}] Q: {}", i + 1, labels.join(" · "), card.front);
+        }
+        eprintln!("     A: {}", card.back);
+        if duplicates.is_some_and(|d| d.get(i) == Some(&true)) {
+            eprintln!("     ↺ already in deck");
+        }
+        eprintln!();
     }
 }
 
@@ -1,6 +1,8 @@
 use clap::Parser;
+use globset::{Glob, GlobMatcher};
 use rayon::prelude::*;
 use regex::Regex;
+use similar::TextDiff;
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -11,10 +13,18 @@ use walkdir::WalkDir;
 #[command(name = "backlinks-init")]
 #[command(about = "One-time bulk backlink population for markdown files")]
 struct Args {
-    /// Dry run - show what would be changed without modifying files
+    /// Dry run - print a diff of each Backlinks section change without modifying files
     #[arg(long)]
     dry_run: bool,
 
+    /// Process only the first N targets (ordered by path)
+    #[arg(long, value_name = "N")]
+    limit: Option<usize>,
+
+    /// Only update targets whose path, relative to its scan directory, matches this glob
+    #[arg(long, value_name = "GLOB")]
+    only: Option<String>,
+
     /// Directories to scan (defaults to ~/Forge, ~/Admin, ~/Archives, ~/Assistants)
     #[arg(short, long)]
     dirs: Vec<PathBuf>,
@@ -23,6 +33,15 @@ struct Args {
 fn main() {
     let args = Args::parse();
 
+    let only: Option<GlobMatcher> = args.only.as_deref().map(|pattern| {
+        Glob::new(pattern)
+            .unwrap_or_else(|e| {
+                eprintln!("Invalid --only pattern '{}': {}", pattern, e);
+                std::process::exit(2);
+            })
+            .compile_matcher()
+    });
+
     let home = std::env::var("HOME").expect("HOME not set");
     let dirs: Vec<PathBuf> = if args.dirs.is_empty() {
         vec!["Forge", "Admin", "Archives", "Assistants"]
@@ -47,6 +66,12 @@ fn main() {
     } else {
         println!("   Mode: LIVE (will modify files)");
     }
+    if let Some(pattern) = &args.only {
+        println!("   Only: {}", pattern);
+    }
+    if let Some(limit) = args.limit {
+        println!("   Limit: {} files", limit);
+    }
     println!();
 
     // Phase 1: Build file index (filename -> full path)
@@ -132,11 +157,23 @@ fn main() {
         return;
     }
 
-    // Phase 3: Update files
+    // Phase 3: Select targets deterministically, then update
+    let mut targets: Vec<(&PathBuf, &HashSet<PathBuf>)> = backlinks_map
+        .iter()
+        .filter(|(path, _)| {
+            only.as_ref()
+                .is_none_or(|matcher| matcher.is_match(relative_to_dirs(path, &dirs)))
+        })
+        .collect();
+    targets.sort_by(|a, b| a.0.cmp(b.0));
+    if let Some(limit) = args.limit {
+        targets.truncate(limit);
+    }
+
     let mut updated_count = 0;
     let mut skipped_count = 0;
 
-    for (target_path, sources) in &backlinks_map {
+    for (target_path, sources) in &targets {
         let mut backlink_lines: Vec<String> = sources
             .iter()
             .map(|p| {
@@ -149,11 +186,18 @@ fn main() {
         let backlinks_text = backlink_lines.join("\n");
 
         if args.dry_run {
-            println!(
-                "Would update: {} with {} backlinks",
-                target_path.file_name().unwrap_or_default().to_string_lossy(),
-                sources.len()
-            );
+            let Ok(content) = fs::read_to_string(target_path) else {
+                skipped_count += 1;
+                continue;
+            };
+            let updated = render_backlinks_section(&content, &backlinks_text);
+            if updated == content {
+                skipped_count += 1;
+            } else {
+                updated_count += 1;
+                let label = relative_to_dirs(target_path, &dirs).display().to_string();
+                print!("{}", section_diff(&label, &content, &updated));
+            }
             continue;
         }
 
@@ -172,8 +216,10 @@ fn main() {
     println!();
     if args.dry_run {
         println!(
-            "🔍 Dry run complete. Would update {} files.",
-            backlinks_map.len()
+            "🔍 Dry run complete. Would update {} of {} files ({} unchanged).",
+            updated_count,
+            targets.len(),
+            skipped_count
         );
     } else {
         println!("✅ Backlinks initialization complete");
@@ -182,11 +228,30 @@ fn main() {
     }
 }
 
+/// Path relative to the scan directory that contains it, for display and --only matching
+fn relative_to_dirs<'a>(path: &'a Path, dirs: &[PathBuf]) -> &'a Path {
+    dirs.iter()
+        .find_map(|dir| path.strip_prefix(dir).ok())
+        .unwrap_or(path)
+}
+
 fn update_backlinks_section(file_path: &Path, backlinks_text: &str) -> Result<bool, std::io::Error> {
     let content = fs::read_to_string(file_path)?;
+    let updated = render_backlinks_section(&content, backlinks_text);
+
+    if updated != content {
+        fs::write(file_path, updated)?;
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+/// The file content with its Backlinks section replaced, or appended if it has none
+fn render_backlinks_section(content: &str, backlinks_text: &str) -> String {
     let new_section = format!("## Backlinks\n\n{}\n", backlinks_text);
 
-    let updated = if let Some(start) = content.find("## Backlinks") {
+    if let Some(start) = content.find("## Backlinks") {
         // Find the end of the backlinks section (next ## heading or end of file)
         let after_header = start + "## Backlinks".len();
         let section_end = content[after_header..]
@@ -199,12 +264,81 @@ fn update_backlinks_section(file_path: &Path, backlinks_text: &str) -> Result<bo
     } else {
         // Add section at end
         format!("{}\n\n{}", content.trim_end(), new_section)
-    };
+    }
+}
 
-    if updated != content {
-        fs::write(file_path, updated)?;
-        Ok(true)
-    } else {
-        Ok(false)
+/// Unified diff between the current and prospective content of one target
+fn section_diff(label: &str, old: &str, new: &str) -> String {
+    TextDiff::from_lines(old, new)
+        .unified_diff()
+        .context_radius(2)
+        .header(&format!("a/{}", label), &format!("b/{}", label))
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replaces_existing_section() {
+        let content = "# Note\n\nBody\n\n## Backlinks\n\n- [[Old]]\n\n## Later\n\nMore\n";
+        let updated = render_backlinks_section(content, "- [[A]]\n- [[B]]");
+        assert_eq!(
+            updated,
+            "# Note\n\nBody\n\n## Backlinks\n\n- [[A]]\n- [[B]]\n\n## Later\n\nMore\n"
+        );
+
+        let diff = section_diff("Note.md", content, &updated);
+        let expected = [
+            "--- a/Note.md",
+            "+++ b/Note.md",
+            "@@ -5,5 +5,6 @@",
+            " ## Backlinks",
+            " ",
+            "-- [[Old]]",
+            "+- [[A]]",
+            "+- [[B]]",
+            " ",
+            " ## Later",
+        ];
+        assert_eq!(diff, format!("{}\n", expected.join("\n")));
+    }
+
+    #[test]
+    fn appends_missing_section() {
+        let content = "# Note\n\nBody\n";
+        let updated = render_backlinks_section(content, "- [[A]]");
+        assert_eq!(updated, "# Note\n\nBody\n\n## Backlinks\n\n- [[A]]\n");
+
+        let diff = section_diff("Note.md", content, &updated);
+        let expected = [
+            "--- a/Note.md",
+            "+++ b/Note.md",
+            "@@ -2,2 +2,6 @@",
+            " ",
+            " Body",
+            "+",
+            "+## Backlinks",
+            "+",
+            "+- [[A]]",
+        ];
+        assert_eq!(diff, format!("{}\n", expected.join("\n")));
+    }
+
+    #[test]
+    fn unchanged_section_renders_identically() {
+        let content = "# Note\n\n## Backlinks\n\n- [[A]]\n";
+        assert_eq!(render_backlinks_section(content, "- [[A]]"), content);
+    }
+
+    #[test]
+    fn relative_path_strips_containing_dir() {
+        let dirs = vec![PathBuf::from("/vault/Forge"), PathBuf::from("/vault/Admin")];
+        assert_eq!(
+            relative_to_dirs(Path::new("/vault/Admin/x/y.md"), &dirs),
+            Path::new("x/y.md")
+        );
+        assert_eq!(relative_to_dirs(Path::new("/other/z.md"), &dirs), Path::new("/other/z.md"));
     }
 }
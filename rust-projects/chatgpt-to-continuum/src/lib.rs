@@ -0,0 +1,1376 @@
+//! Conversion of ChatGPT, Grok and Gemini exports to continuum format.
+//!
+//! `detect_format` identifies which export layout a file uses; `convert`
+//! writes its conversations out and returns an `ImportSummary`. Progress is
+//! reported through `ImportEvent`s so callers (the CLI, ai-export-watcher)
+//! decide what to print. `split_groups` and `convert_merged` rejoin a
+//! conversation that was exported in several parts. Conversations whose
+//! message timestamps don't run forwards are reported, and with
+//! `ConvertOptions::fix_timestamps` spread out (see `timestamps`).
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use clap::ValueEnum;
+use continuum_core::{Note, Saved};
+use serde::de::{Deserializer, SeqAccess, Visitor};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+mod merge;
+mod timestamps;
+pub use merge::{convert_merged, split_groups};
+pub use timestamps::TimestampAnomaly;
+
+// ============================================================================
+// Skill matching
+// ============================================================================
+
+/// Read skill alias mappings from ~/.config/continuum/skill-aliases.json
+fn read_aliases() -> HashMap<String, String> {
+    let path = dirs::home_dir()
+        .map(|h| h.join(".config/continuum/skill-aliases.json"))
+        .unwrap_or_default();
+
+    if !path.exists() {
+        return HashMap::new();
+    }
+
+    match std::fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
+        Err(_) => HashMap::new(),
+    }
+}
+
+/// Read known skill names from ~/.claude/skills/ directory
+fn read_skill_dirs() -> Vec<String> {
+    let path = dirs::home_dir()
+        .map(|h| h.join(".claude/skills"))
+        .unwrap_or_default();
+
+    if !path.exists() {
+        return Vec::new();
+    }
+
+    std::fs::read_dir(&path)
+        .ok()
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| e.path().is_dir())
+                .filter_map(|e| {
+                    let name = e.file_name().to_str()?.to_string();
+                    if name.starts_with('.') { None } else { Some(name) }
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Match title and/or project against known skills and aliases
+fn match_skills(title: Option<&str>, project: Option<&str>) -> Vec<String> {
+    let known_skills = read_skill_dirs();
+    let aliases = read_aliases();
+    let mut skills = Vec::new();
+
+    for candidate in [project, title].into_iter().flatten() {
+        let candidate_lower = candidate.to_lowercase();
+
+        // Direct skill name match
+        for skill in &known_skills {
+            if candidate_lower.contains(skill) && !skills.contains(skill) {
+                skills.push(skill.clone());
+            }
+        }
+
+        // Alias match (case-insensitive)
+        for (alias, skill) in &aliases {
+            if candidate_lower.contains(&alias.to_lowercase()) && !skills.contains(skill) {
+                skills.push(skill.clone());
+            }
+        }
+    }
+
+    skills
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum OutputFormat {
+    /// messages.jsonl + session.json per conversation
+    #[default]
+    Continuum,
+    /// Readable markdown transcript per conversation
+    Markdown,
+}
+
+// ============================================================================
+// Browser Exporter format (ChatGPT Exporter / Grok Exporter)
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct ExporterConversation {
+    metadata: ExporterMetadata,
+    messages: Vec<ExporterMessage>,
+    /// Grok has title at root level
+    #[serde(default)]
+    title: Option<String>,
+    /// Project/folder name from browser extension
+    #[serde(default)]
+    project: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExporterMetadata {
+    /// ChatGPT has title in metadata
+    #[serde(default)]
+    title: Option<String>,
+    dates: ExporterDates,
+    #[serde(default)]
+    link: Option<String>,
+    #[serde(default)]
+    powered_by: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExporterDates {
+    created: String,
+    updated: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExporterMessage {
+    role: String,
+    say: String,
+}
+
+// ============================================================================
+// Browser Extension v2.4+ format (Grok Exporter, etc.)
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BrowserExtensionExport {
+    export_date: String,
+    #[serde(default)]
+    export_version: Option<String>,
+    platform: String,
+    #[serde(default)]
+    url: Option<String>,
+    conversation: Vec<BrowserExtensionMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BrowserExtensionMessage {
+    content: String,
+    #[serde(default)]
+    timestamp: Option<String>,
+}
+
+// ============================================================================
+// Official OpenAI export format
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct OfficialConversation {
+    title: String,
+    create_time: f64,
+    update_time: Option<f64>,
+    mapping: HashMap<String, Node>,
+    id: String,
+    #[serde(default)]
+    default_model_slug: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Node {
+    message: Option<NodeMessage>,
+    parent: Option<String>,
+    #[serde(default)]
+    children: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NodeMessage {
+    author: Author,
+    create_time: Option<f64>,
+    content: Content,
+    #[serde(default)]
+    metadata: Option<MessageMetadata>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct MessageMetadata {
+    #[serde(default)]
+    model_slug: Option<String>,
+    /// Set on the hidden message carrying the user's custom instructions
+    #[serde(default)]
+    is_user_system_message: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Author {
+    role: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Content {
+    #[serde(default)]
+    content_type: Option<String>,
+    parts: Option<Vec<serde_json::Value>>,
+    /// Custom instructions ("user_editable_context" content) carry these
+    /// instead of parts
+    #[serde(default)]
+    user_profile: Option<String>,
+    #[serde(default)]
+    user_instructions: Option<String>,
+}
+
+// ============================================================================
+// Continuum output structures
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+struct ContinuumMessage {
+    id: u32,
+    role: String,
+    content: String,
+    timestamp: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ContinuumSession {
+    id: String,
+    assistant: String,
+    start_time: Option<String>,
+    end_time: Option<String>,
+    status: Option<String>,
+    message_count: Option<u32>,
+    created_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_url: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    skills: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    model: Option<String>,
+    /// Custom instructions the conversation ran under
+    #[serde(skip_serializing_if = "Option::is_none")]
+    context: Option<String>,
+}
+
+/// A single conversation converted but not yet written
+#[derive(Debug)]
+struct Converted {
+    date_str: String,
+    id: String,
+    messages: Vec<ContinuumMessage>,
+    session: ContinuumSession,
+}
+
+impl Converted {
+    fn write(&self, output_dir: &Path, format: OutputFormat) -> Result<Saved> {
+        write_session(output_dir, &self.date_str, &self.id, &self.messages, &self.session, format)
+    }
+
+    /// Write the conversation as a vault note into `dir`
+    fn write_note(&self, dir: &Path) -> Result<PathBuf> {
+        let note = Note {
+            assistant: &self.session.assistant,
+            date: &self.date_str,
+            title: self.session.title.as_deref().unwrap_or(&self.id),
+            source_url: self.session.source_url.as_deref(),
+            skills: &self.session.skills,
+            messages: self.messages.iter().map(|m| (m.role.as_str(), m.content.as_str())).collect(),
+        };
+        note.write(dir)
+    }
+}
+
+// ============================================================================
+// Public API
+// ============================================================================
+
+/// Which export layout an input file uses
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportFormat {
+    /// ChatGPT Exporter / Grok Exporter browser extensions (`metadata.dates`)
+    Exporter,
+    /// Browser extension v2.4+ (`exportDate` / `platform`)
+    BrowserExtension { version: String, platform: String },
+    /// Official OpenAI bulk export (array of conversations)
+    Official,
+}
+
+#[derive(Error, Debug)]
+pub enum ImportError {
+    #[error("Failed to read input file")]
+    Read(#[source] std::io::Error),
+    #[error("Unrecognized JSON format. Expected ChatGPT/Grok Exporter or official OpenAI export.")]
+    UnrecognizedFormat,
+    #[error("Failed to parse official OpenAI export")]
+    Parse(#[source] serde_json::Error),
+    #[error("Trailing data after official OpenAI export")]
+    TrailingData(#[source] serde_json::Error),
+    /// Writing a single-conversation export failed
+    #[error("Failed to write conversation: {0:#}")]
+    Write(anyhow::Error),
+    /// Official exports hold many conversations and can't be merged into one
+    #[error("Cannot merge {0:?}: official OpenAI exports hold many conversations")]
+    NotMergeable(PathBuf),
+    #[error("No input files to merge")]
+    NoInput,
+}
+
+/// Settings for `convert`; the defaults match running the CLI with no flags
+#[derive(Debug, Clone, Default)]
+pub struct ConvertOptions {
+    /// Output directory (default: auto-detected based on source)
+    pub output: Option<PathBuf>,
+    /// Force assistant type instead of detecting it
+    pub assistant: Option<String>,
+    /// Renames for detected assistant names (e.g. "grok" → "grok-work").
+    /// A forced `assistant` is used as given.
+    pub assistant_map: HashMap<String, String>,
+    pub output_format: OutputFormat,
+    /// Also write each single-conversation export as a markdown note here.
+    /// Official exports are skipped.
+    pub note_dir: Option<PathBuf>,
+    /// Respace message timestamps that repeat or go backwards, evenly
+    /// between the ones around them
+    pub fix_timestamps: bool,
+}
+
+impl ConvertOptions {
+    /// The assistant name that picks the output directory and goes in
+    /// `session.assistant`
+    fn assistant_for(&self, parsed: &ParsedExport) -> String {
+        if let Some(assistant) = &self.assistant {
+            return assistant.clone();
+        }
+        let detected = match parsed {
+            ParsedExport::Exporter(conv) => detect_assistant(conv),
+            ParsedExport::BrowserExtension(export) => export.platform.to_lowercase(),
+            ParsedExport::Official => "chatgpt".to_string(),
+        };
+        self.assistant_map.get(&detected).cloned().unwrap_or(detected)
+    }
+}
+
+/// Read an `--assistant-map` file: a JSON object of detected → desired
+/// assistant names. Keys are matched case-insensitively.
+pub fn read_assistant_map(path: &Path) -> Result<HashMap<String, String>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read assistant map {}", path.display()))?;
+    let map: HashMap<String, String> = serde_json::from_str(&content)
+        .with_context(|| format!("Assistant map {} must be a JSON object of names", path.display()))?;
+    Ok(map.into_iter().map(|(detected, desired)| (detected.to_lowercase(), desired)).collect())
+}
+
+/// Progress reported while `convert` runs
+#[derive(Debug)]
+pub enum ImportEvent<'a> {
+    /// Format detected and output directory chosen; nothing written yet
+    Started { format: &'a ExportFormat, assistant: &'a str, output_dir: &'a Path },
+    /// A conversation was written (path relative to the output directory)
+    Created(&'a Path),
+    /// A conversation already held in full by this existing session
+    Skipped(&'a Path),
+    /// A conversation mostly held by this existing session; `added` new
+    /// messages were appended to it
+    Merged { into: &'a Path, added: usize },
+    /// One conversation of an official export failed; the rest carry on
+    Failed { index: usize, error: &'a anyhow::Error },
+    /// Conversations of an official export processed so far
+    Processed(usize),
+    /// A conversation's message timestamps don't run forwards; `fixed` when
+    /// `fix_timestamps` put them in order
+    Timestamps { id: &'a str, anomaly: &'a TimestampAnomaly, fixed: bool },
+}
+
+/// Outcome of converting one export file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportSummary {
+    pub format: ExportFormat,
+    pub assistant: String,
+    pub output_dir: PathBuf,
+    /// Conversations in the export
+    pub conversations: usize,
+    /// Conversations converted without error (including empty ones skipped)
+    pub succeeded: usize,
+    pub failed: usize,
+    /// Messages in the export (single-conversation formats) or written
+    /// (official export)
+    pub messages: usize,
+    /// Sessions or transcripts written, relative to `output_dir`
+    pub created: Vec<PathBuf>,
+    /// Existing sessions that already held a conversation in full
+    pub skipped: Vec<PathBuf>,
+    /// Existing sessions that had new messages appended
+    pub merged: Vec<PathBuf>,
+    /// Notes written to `ConvertOptions::note_dir`
+    pub notes: Vec<PathBuf>,
+    /// Conversations whose message timestamps didn't run forwards
+    pub timestamp_anomalies: usize,
+}
+
+impl ImportSummary {
+    /// Report a conversation whose timestamps don't run forwards, and
+    /// respace them if asked to
+    fn check_timestamps(
+        &mut self,
+        converted: &mut Converted,
+        options: &ConvertOptions,
+        on_event: &mut impl FnMut(ImportEvent),
+    ) {
+        let Some(anomaly) = timestamps::check(&converted.messages) else {
+            return;
+        };
+        let fixed = options.fix_timestamps && {
+            let bound = |time: &Option<String>| {
+                time.as_deref()
+                    .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+                    .map(|t| t.with_timezone(&Utc))
+            };
+            let (start, end) = (bound(&converted.session.start_time), bound(&converted.session.end_time));
+            timestamps::spread(&mut converted.messages, start, end);
+            timestamps::check(&converted.messages).is_none()
+        };
+        self.timestamp_anomalies += 1;
+        on_event(ImportEvent::Timestamps { id: &converted.id, anomaly: &anomaly, fixed });
+    }
+
+    /// Write the conversation's note if one was asked for
+    fn write_note(&mut self, converted: &Converted, options: &ConvertOptions) -> Result<(), ImportError> {
+        if let Some(dir) = &options.note_dir {
+            self.notes.push(converted.write_note(dir).map_err(ImportError::Write)?);
+        }
+        Ok(())
+    }
+
+    /// Report and record where a conversation ended up
+    fn record(&mut self, saved: &Saved, on_event: &mut impl FnMut(ImportEvent)) {
+        let relative = relative_to(saved.path(), &self.output_dir);
+        match saved {
+            Saved::Created(_) => {
+                on_event(ImportEvent::Created(&relative));
+                self.created.push(relative);
+            }
+            Saved::Skipped(_) => {
+                on_event(ImportEvent::Skipped(&relative));
+                self.skipped.push(relative);
+            }
+            Saved::Merged { added, .. } => {
+                on_event(ImportEvent::Merged { into: &relative, added: *added });
+                self.merged.push(relative);
+            }
+        }
+    }
+}
+
+/// A parsed single-conversation export, or a marker for the streamed official one
+enum ParsedExport {
+    Exporter(ExporterConversation),
+    BrowserExtension(BrowserExtensionExport),
+    Official,
+}
+
+impl ParsedExport {
+    fn format(&self) -> ExportFormat {
+        match self {
+            ParsedExport::Exporter(_) => ExportFormat::Exporter,
+            ParsedExport::BrowserExtension(export) => ExportFormat::BrowserExtension {
+                version: export.export_version.clone().unwrap_or_else(|| "unknown".to_string()),
+                platform: export.platform.clone(),
+            },
+            ParsedExport::Official => ExportFormat::Official,
+        }
+    }
+}
+
+fn parse_export(input: &Path) -> Result<ParsedExport, ImportError> {
+    // The official export is a top-level array and can run to gigabytes, so it
+    // is streamed one conversation at a time later. The other formats are
+    // single conversations and small enough to read whole.
+    if starts_with_array(input)? {
+        return Ok(ParsedExport::Official);
+    }
+
+    let json_content = fs::read_to_string(input).map_err(ImportError::Read)?;
+    if let Ok(exporter_conv) = serde_json::from_str::<ExporterConversation>(&json_content) {
+        Ok(ParsedExport::Exporter(exporter_conv))
+    } else if let Ok(browser_ext) = serde_json::from_str::<BrowserExtensionExport>(&json_content) {
+        Ok(ParsedExport::BrowserExtension(browser_ext))
+    } else {
+        Err(ImportError::UnrecognizedFormat)
+    }
+}
+
+/// Identify the export layout of `input` without converting anything
+pub fn detect_format(input: &Path) -> Result<ExportFormat, ImportError> {
+    parse_export(input).map(|parsed| parsed.format())
+}
+
+/// Convert every conversation in `input`, reporting progress to `on_event`.
+///
+/// A failing conversation inside an official export is reported as
+/// `ImportEvent::Failed` and counted in the summary; any other failure
+/// aborts with an `ImportError`.
+pub fn convert(
+    input: &Path,
+    options: &ConvertOptions,
+    mut on_event: impl FnMut(ImportEvent),
+) -> Result<ImportSummary, ImportError> {
+    let parsed = parse_export(input)?;
+    let format = parsed.format();
+
+    let assistant = options.assistant_for(&parsed);
+    let output_dir = options.output.clone()
+        .unwrap_or_else(|| default_output_dir(&assistant, options.output_format));
+
+    on_event(ImportEvent::Started { format: &format, assistant: &assistant, output_dir: &output_dir });
+
+    let mut summary = ImportSummary {
+        format: format.clone(),
+        assistant: assistant.clone(),
+        output_dir: output_dir.clone(),
+        conversations: 0,
+        succeeded: 0,
+        failed: 0,
+        messages: 0,
+        created: Vec::new(),
+        skipped: Vec::new(),
+        merged: Vec::new(),
+        notes: Vec::new(),
+        timestamp_anomalies: 0,
+    };
+
+    let converted = match parsed {
+        ParsedExport::Official => {
+            let file = fs::File::open(input).map_err(ImportError::Read)?;
+            summary.conversations = for_each_official_conversation(BufReader::new(file), |idx, conversation| {
+                let written = convert_official_conversation(&conversation, &assistant).and_then(|converted| {
+                    let Some(mut converted) = converted else {
+                        return Ok(None);
+                    };
+                    summary.check_timestamps(&mut converted, options, &mut on_event);
+                    let saved = converted.write(&output_dir, options.output_format)?;
+                    Ok(Some((saved, converted.messages.len())))
+                });
+                match written {
+                    Ok(written) => {
+                        summary.succeeded += 1;
+                        if let Some((saved, messages)) = written {
+                            summary.messages += match &saved {
+                                Saved::Created(_) => messages,
+                                Saved::Merged { added, .. } => *added,
+                                Saved::Skipped(_) => 0,
+                            };
+                            summary.record(&saved, &mut on_event);
+                        }
+                    }
+                    Err(error) => {
+                        on_event(ImportEvent::Failed { index: idx, error: &error });
+                        summary.failed += 1;
+                    }
+                }
+                on_event(ImportEvent::Processed(idx + 1));
+            })?;
+            return Ok(summary);
+        }
+        ParsedExport::Exporter(conv) => {
+            summary.messages = conv.messages.len();
+            convert_exporter_conversation(&conv, &assistant)
+        }
+        ParsedExport::BrowserExtension(export) => {
+            summary.messages = export.conversation.len();
+            convert_browser_extension_export(&export, &assistant)
+        }
+    };
+
+    summary.conversations = 1;
+    summary.succeeded = 1;
+    if let Some(mut converted) = converted {
+        summary.check_timestamps(&mut converted, options, &mut on_event);
+        let saved = converted.write(&output_dir, options.output_format).map_err(ImportError::Write)?;
+        summary.record(&saved, &mut on_event);
+        summary.write_note(&converted, options)?;
+    }
+    Ok(summary)
+}
+
+fn relative_to(path: &Path, base: &Path) -> PathBuf {
+    path.strip_prefix(base).unwrap_or(path).to_path_buf()
+}
+
+fn default_output_dir(assistant: &str, format: OutputFormat) -> PathBuf {
+    match format {
+        OutputFormat::Continuum => {
+            let home = std::env::var("HOME").expect("HOME not set");
+            PathBuf::from(home)
+                .join("Assistants")
+                .join("continuum-logs")
+                .join(assistant)
+        }
+        OutputFormat::Markdown => PathBuf::from("."),
+    }
+}
+
+fn detect_assistant(conv: &ExporterConversation) -> String {
+    if let Some(powered_by) = &conv.metadata.powered_by {
+        let lower = powered_by.to_lowercase();
+        if lower.contains("grok") {
+            return "grok".to_string();
+        }
+        if lower.contains("chatgpt") {
+            return "chatgpt".to_string();
+        }
+        if lower.contains("gemini") {
+            return "gemini".to_string();
+        }
+    }
+    // Default to chatgpt
+    "chatgpt".to_string()
+}
+
+// ============================================================================
+// Process Browser Exporter format (ChatGPT / Grok)
+// ============================================================================
+
+fn parse_exporter_date(date_str: &str) -> Option<DateTime<Utc>> {
+    // Try format with seconds: "11/24/2025 11:32:17"
+    if let Ok(dt) = NaiveDateTime::parse_from_str(date_str, "%m/%d/%Y %H:%M:%S") {
+        return Some(dt.and_utc());
+    }
+    // Try format without seconds: "9/11/2025 15:14"
+    if let Ok(dt) = NaiveDateTime::parse_from_str(date_str, "%m/%d/%Y %H:%M") {
+        return Some(dt.and_utc());
+    }
+    None
+}
+
+fn get_title(conv: &ExporterConversation) -> String {
+    // Try root-level title first (Grok), then metadata title (ChatGPT)
+    conv.title.clone()
+        .or_else(|| conv.metadata.title.clone())
+        .unwrap_or_else(|| "untitled".to_string())
+}
+
+/// None when no messages survived cleanup
+fn convert_exporter_conversation(conv: &ExporterConversation, assistant: &str) -> Option<Converted> {
+    let created = parse_exporter_date(&conv.metadata.dates.created)
+        .unwrap_or_else(Utc::now);
+    let updated = parse_exporter_date(&conv.metadata.dates.updated);
+
+    let date_str = created.format("%Y-%m-%d").to_string();
+    let title = get_title(conv);
+
+    // Generate ID from title (sanitized)
+    let id = sanitize_id(&title);
+
+    // Convert messages.
+    //
+    // The primary fix for the Gemini Exporter's known quirks (per-turn
+    // duplication, line-by-line paste fragmentation, stray "Gemini" label
+    // messages) lives in the browser extension itself, in
+    // ~/dotfiles/browser-extensions/gemini-exporter/content.js. After that
+    // fix, exports are clean at source.
+    //
+    // We keep two defensive layers here as belt-and-braces against
+    // regression and against similar bugs in other browser exporters:
+    //
+    //   1. clean_message_content strips any UI label prefixes/suffixes
+    //      that might leak through ("You said\n\n", "Gemini said\n\n",
+    //      trailing "Sources" footer)
+    //   2. Drop empty messages and stray model-name labels ("Gemini",
+    //      "ChatGPT", etc.)
+    //   3. Global (role, content) dedup — drops any message whose content
+    //      has already appeared in the conversation. Cheap protection
+    //      against any source-side regression that re-introduces dupes.
+
+    // Clean each message; drop empties and stray labels
+    let mut cleaned: Vec<ContinuumMessage> = Vec::new();
+    for msg in conv.messages.iter() {
+        let role = match msg.role.as_str() {
+            "Prompt" => "user".to_string(),
+            "Response" => "assistant".to_string(),
+            other => other.to_lowercase(),
+        };
+
+        let content = clean_message_content(&msg.say);
+
+        if content.trim().is_empty() {
+            continue;
+        }
+
+        let trimmed = content.trim();
+        if matches!(trimmed, "Gemini" | "ChatGPT" | "Claude" | "Grok") {
+            continue;
+        }
+
+        cleaned.push(ContinuumMessage {
+            id: 0, // renumbered at the end
+            role,
+            content,
+            timestamp: created.to_rfc3339(),
+        });
+    }
+
+    // Defensive global content dedup — drops any message whose (role, content)
+    // pair has already appeared. With the source-side fix in the browser
+    // extension this should never fire, but it costs almost nothing and
+    // catches regressions.
+    let mut seen: HashSet<(String, String)> = HashSet::new();
+    let mut continuum_messages: Vec<ContinuumMessage> = Vec::new();
+    for msg in cleaned.into_iter() {
+        let key = (msg.role.clone(), msg.content.clone());
+        if seen.insert(key) {
+            continuum_messages.push(msg);
+        }
+    }
+
+    // Renumber sequentially
+    for (i, msg) in continuum_messages.iter_mut().enumerate() {
+        msg.id = (i + 1) as u32;
+    }
+
+    if continuum_messages.is_empty() {
+        return None;
+    }
+
+    // Match skills from title and project
+    let skills = match_skills(
+        Some(&title),
+        conv.project.as_deref(),
+    );
+
+    // Write session.json
+    let session = ContinuumSession {
+        id: id.clone(),
+        assistant: assistant.to_string(),
+        start_time: Some(created.to_rfc3339()),
+        end_time: updated.map(|dt| dt.to_rfc3339()),
+        status: Some("imported".to_string()),
+        message_count: Some(continuum_messages.len() as u32),
+        created_at: Some(created.to_rfc3339()),
+        title: Some(title),
+        source_url: conv.metadata.link.clone(),
+        skills,
+        model: None,
+        context: None,
+    };
+
+    Some(Converted { date_str, id, messages: continuum_messages, session })
+}
+
+/// Write a converted conversation to `output_dir` in the requested format.
+/// Continuum sessions already held by another session on the same day are
+/// skipped or merged into it (see `continuum_core::dedup`); markdown
+/// transcripts are always written.
+fn write_session(
+    output_dir: &Path,
+    date_str: &str,
+    id: &str,
+    messages: &[ContinuumMessage],
+    session: &ContinuumSession,
+    format: OutputFormat,
+) -> Result<Saved> {
+    match format {
+        OutputFormat::Continuum => continuum_core::save_session(&output_dir.join(date_str), id, messages, session),
+        OutputFormat::Markdown => {
+            fs::create_dir_all(output_dir)
+                .with_context(|| format!("Failed to create {:?}", output_dir))?;
+            let path = output_dir.join(format!("{}-{}.md", date_str, id));
+            fs::write(&path, render_markdown(messages, session))?;
+            Ok(Saved::Created(path))
+        }
+    }
+}
+
+/// Render a conversation as a markdown transcript: title, metadata list,
+/// then alternating **User:** / **Assistant:** blocks
+fn render_markdown(messages: &[ContinuumMessage], session: &ContinuumSession) -> String {
+    let mut out = String::new();
+
+    let title = session.title.as_deref().unwrap_or(&session.id);
+    out.push_str(&format!("# {}\n\n", title));
+
+    out.push_str(&format!("- **Assistant:** {}\n", session.assistant));
+    if let Some(start) = &session.start_time {
+        out.push_str(&format!("- **Started:** {}\n", start));
+    }
+    if let Some(end) = &session.end_time {
+        out.push_str(&format!("- **Ended:** {}\n", end));
+    }
+    out.push_str(&format!("- **Messages:** {}\n", messages.len()));
+    if let Some(url) = &session.source_url {
+        out.push_str(&format!("- **Source:** {}\n", url));
+    }
+    if !session.skills.is_empty() {
+        out.push_str(&format!("- **Skills:** {}\n", session.skills.join(", ")));
+    }
+    if let Some(model) = &session.model {
+        out.push_str(&format!("- **Model:** {}\n", model));
+    }
+
+    for msg in messages {
+        out.push_str(&format!("\n---\n\n**{}:**\n\n{}\n", role_label(&msg.role), msg.content.trim()));
+    }
+
+    out
+}
+
+fn role_label(role: &str) -> String {
+    match role {
+        "user" => "User".to_string(),
+        "assistant" => "Assistant".to_string(),
+        other => {
+            let mut chars = other.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => "Unknown".to_string(),
+            }
+        }
+    }
+}
+
+fn sanitize_id(title: &str) -> String {
+    title
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c.to_ascii_lowercase()
+            } else if c.is_whitespace() {
+                '-'
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>()
+        .chars()
+        .take(64)
+        .collect()
+}
+
+fn clean_message_content(content: &str) -> String {
+    let mut result = content.to_string();
+
+    // Remove trailing timestamp patterns like "11:32 AM11:32" or "11:54 AM11:54"
+    let timestamp_re = regex::Regex::new(r"\n\n\d{1,2}:\d{2}\s*[AP]M\d{1,2}:\d{2}\s*$").unwrap();
+    result = timestamp_re.replace(&result, "").to_string();
+
+    // Strip Gemini browser-extension UI label prefixes from message bodies.
+    // The "Gemini Exporter (custom extension)" includes the rendered UI labels
+    // ("You said", "Gemini said") inline at the start of message content. Strip
+    // them so the message body is just the actual text.
+    if let Some(rest) = result.strip_prefix("You said\n\n") {
+        result = rest.to_string();
+    }
+    // "Show thinking" disclosure widget (Gemini's thinking indicator)
+    if let Some(rest) = result.strip_prefix("Show thinking\n") {
+        result = rest.to_string();
+    }
+    if let Some(rest) = result.strip_prefix("Gemini said\n\n") {
+        result = rest.to_string();
+    }
+
+    // Strip the trailing "Sources" footer label from Gemini assistant messages
+    // (the source-citation widget renders as a literal "Sources" line at the end).
+    let sources_re = regex::Regex::new(r"\n+Sources\s*$").unwrap();
+    result = sources_re.replace(&result, "").to_string();
+
+    // Remove Gemini UI artifacts
+    // "Edit" on its own line
+    let edit_re = regex::Regex::new(r"(?m)^Edit\s*$").unwrap();
+    result = edit_re.replace_all(&result, "").to_string();
+
+    // "Retry" and "WN" markers
+    let retry_re = regex::Regex::new(r"(?m)^Retry\s*$").unwrap();
+    result = retry_re.replace_all(&result, "").to_string();
+    let wn_re = regex::Regex::new(r"(?m)^WN\s*$").unwrap();
+    result = wn_re.replace_all(&result, "").to_string();
+
+    // Timestamp indicators like "9s", "0s", "4s", "25s" on their own line
+    let time_indicator_re = regex::Regex::new(r"(?m)^\d+s\s*$").unwrap();
+    result = time_indicator_re.replace_all(&result, "").to_string();
+
+    // "X results" search indicators
+    let results_re = regex::Regex::new(r"(?m)^\d+\s+results?\s*$").unwrap();
+    result = results_re.replace_all(&result, "").to_string();
+
+    // Collapse multiple newlines into at most two
+    let multi_newline_re = regex::Regex::new(r"\n{3,}").unwrap();
+    result = multi_newline_re.replace_all(&result, "\n\n").to_string();
+
+    result.trim().to_string()
+}
+
+// ============================================================================
+// Process Browser Extension v2.4+ format
+// ============================================================================
+
+/// None when the export has no messages
+fn convert_browser_extension_export(export: &BrowserExtensionExport, assistant: &str) -> Option<Converted> {
+    // Parse export date
+    let created = DateTime::parse_from_rfc3339(&export.export_date)
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now());
+
+    let date_str = created.format("%Y-%m-%d").to_string();
+
+    // Generate ID from URL or timestamp
+    let id = if let Some(url) = &export.url {
+        // Extract conversation ID from URL like "https://grok.com/c/b36eb0c0-..."
+        url.split('/').next_back()
+            .and_then(|s| s.split('?').next())
+            .unwrap_or("unknown")
+            .to_string()
+    } else {
+        format!("{}-{}", assistant, created.timestamp())
+    };
+
+    // Convert messages using alternating pattern (user starts, then alternates)
+    // The browser extension's debug scores are unreliable, so we use conversation flow
+    let mut continuum_messages = Vec::new();
+    for (idx, msg) in export.conversation.iter().enumerate() {
+        // Conversations typically start with user, then alternate
+        // This is more reliable than the extension's scoring heuristics
+        let role = if idx % 2 == 0 {
+            "user".to_string()
+        } else {
+            "assistant".to_string()
+        };
+
+        let timestamp = msg.timestamp.clone()
+            .unwrap_or_else(|| created.to_rfc3339());
+
+        if !msg.content.trim().is_empty() {
+            continuum_messages.push(ContinuumMessage {
+                id: (idx + 1) as u32,
+                role,
+                content: msg.content.clone(),
+                timestamp,
+            });
+        }
+    }
+
+    if continuum_messages.is_empty() {
+        return None;
+    }
+
+    // Get end time from last message
+    let end_time = continuum_messages.last()
+        .map(|msg| msg.timestamp.clone());
+
+    // Match skills (browser extension format has no title, but future versions may)
+    let skills = match_skills(None, None);
+
+    // Write session.json
+    let session = ContinuumSession {
+        id: id.clone(),
+        assistant: assistant.to_string(),
+        start_time: Some(created.to_rfc3339()),
+        end_time,
+        status: Some("imported".to_string()),
+        message_count: Some(continuum_messages.len() as u32),
+        created_at: Some(created.to_rfc3339()),
+        title: None, // Browser extension format doesn't include title
+        source_url: export.url.clone(),
+        skills,
+        model: None,
+        context: None,
+    };
+
+    Some(Converted { date_str, id, messages: continuum_messages, session })
+}
+
+// ============================================================================
+// Process Official OpenAI export format
+// ============================================================================
+
+/// Whether the file's first non-whitespace byte opens a JSON array.
+fn starts_with_array(path: &Path) -> Result<bool, ImportError> {
+    let file = fs::File::open(path).map_err(ImportError::Read)?;
+    let mut reader = BufReader::new(file);
+    loop {
+        let buf = reader.fill_buf().map_err(ImportError::Read)?;
+        if buf.is_empty() {
+            return Ok(false);
+        }
+        if let Some(&b) = buf.iter().find(|b| !b.is_ascii_whitespace()) {
+            return Ok(b == b'[');
+        }
+        let len = buf.len();
+        reader.consume(len);
+    }
+}
+
+/// Deserialize a top-level array of conversations element by element,
+/// handing each to `f` and dropping it before the next is read. Returns the
+/// number of conversations seen.
+fn for_each_official_conversation<R: Read>(
+    reader: R,
+    f: impl FnMut(usize, OfficialConversation),
+) -> Result<usize, ImportError> {
+    struct ConversationVisitor<F>(F);
+
+    impl<'de, F: FnMut(usize, OfficialConversation)> Visitor<'de> for ConversationVisitor<F> {
+        type Value = usize;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("an array of conversations")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(mut self, mut seq: A) -> Result<usize, A::Error> {
+            let mut count = 0;
+            while let Some(conversation) = seq.next_element::<OfficialConversation>()? {
+                (self.0)(count, conversation);
+                count += 1;
+            }
+            Ok(count)
+        }
+    }
+
+    let mut de = serde_json::Deserializer::from_reader(reader);
+    let count = (&mut de)
+        .deserialize_seq(ConversationVisitor(f))
+        .map_err(ImportError::Parse)?;
+    de.end().map_err(ImportError::TrailingData)?;
+    Ok(count)
+}
+
+/// None when the conversation has no messages
+fn convert_official_conversation(conv: &OfficialConversation, assistant: &str) -> Result<Option<Converted>> {
+    let datetime = DateTime::<Utc>::from_timestamp(conv.create_time as i64, 0)
+        .context("Invalid timestamp")?;
+    let date_str = datetime.format("%Y-%m-%d").to_string();
+
+    let messages = extract_messages_from_tree(conv)?;
+
+    if messages.is_empty() {
+        return Ok(None);
+    }
+
+    // Match skills from title
+    let skills = match_skills(Some(&conv.title), None);
+
+    // Write session.json
+    let session = ContinuumSession {
+        id: conv.id.clone(),
+        assistant: assistant.to_string(),
+        start_time: Some(datetime.to_rfc3339()),
+        end_time: conv.update_time.and_then(|t| {
+            DateTime::<Utc>::from_timestamp(t as i64, 0)
+                .map(|dt| dt.to_rfc3339())
+        }),
+        status: Some("imported".to_string()),
+        message_count: Some(messages.len() as u32),
+        created_at: Some(datetime.to_rfc3339()),
+        title: Some(conv.title.clone()),
+        source_url: None,
+        skills,
+        model: conversation_model(conv),
+        context: custom_instructions(conv),
+    };
+
+    Ok(Some(Converted { date_str, id: conv.id.clone(), messages, session }))
+}
+
+fn extract_text_from_part(part: &serde_json::Value) -> Option<String> {
+    if let Some(text) = part.as_str() {
+        return Some(text.to_string());
+    }
+
+    if let Some(obj) = part.as_object() {
+        if let Some(content_type) = obj.get("content_type").and_then(|v| v.as_str()) {
+            return Some(format!("[{}]", content_type));
+        }
+    }
+
+    None
+}
+
+/// Longest custom-instructions text kept in session.json
+const CONTEXT_MAX_CHARS: usize = 2000;
+
+fn is_custom_instructions(msg: &NodeMessage) -> bool {
+    msg.content.content_type.as_deref() == Some("user_editable_context")
+        || msg.metadata.as_ref().and_then(|m| m.is_user_system_message) == Some(true)
+}
+
+/// The user's custom instructions, if the conversation carries them,
+/// truncated to `CONTEXT_MAX_CHARS`
+fn custom_instructions(conv: &OfficialConversation) -> Option<String> {
+    let msg = conv.mapping.values()
+        .filter_map(|node| node.message.as_ref())
+        .find(|msg| is_custom_instructions(msg))?;
+
+    let content = &msg.content;
+    let sections: Vec<String> = match content.content_type.as_deref() {
+        Some("user_editable_context") => [&content.user_profile, &content.user_instructions]
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect(),
+        _ => content.parts.iter().flatten().filter_map(extract_text_from_part).collect(),
+    };
+    let text = sections.iter()
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    if text.is_empty() {
+        return None;
+    }
+    if text.chars().count() > CONTEXT_MAX_CHARS {
+        let truncated: String = text.chars().take(CONTEXT_MAX_CHARS).collect();
+        return Some(format!("{}…", truncated.trim_end()));
+    }
+    Some(text)
+}
+
+/// The model that answered most messages, ties going to the one used last.
+/// Falls back to the conversation's default model.
+fn conversation_model(conv: &OfficialConversation) -> Option<String> {
+    // slug -> (message count, latest create_time)
+    let mut usage: HashMap<&str, (usize, f64)> = HashMap::new();
+    for msg in conv.mapping.values().filter_map(|node| node.message.as_ref()) {
+        if let Some(slug) = msg.metadata.as_ref().and_then(|m| m.model_slug.as_deref()) {
+            let entry = usage.entry(slug).or_insert((0, f64::MIN));
+            entry.0 += 1;
+            entry.1 = entry.1.max(msg.create_time.unwrap_or(f64::MIN));
+        }
+    }
+
+    usage.into_iter()
+        .max_by(|a, b| a.1.0.cmp(&b.1.0).then(a.1.1.total_cmp(&b.1.1)))
+        .map(|(slug, _)| slug.to_string())
+        .or_else(|| conv.default_model_slug.clone())
+}
+
+fn extract_messages_from_tree(conv: &OfficialConversation) -> Result<Vec<ContinuumMessage>> {
+    let mut messages = Vec::new();
+
+    let root_id = conv.mapping.iter()
+        .find(|(_, node)| node.parent.is_none())
+        .map(|(id, _)| id.clone())
+        .context("No root node found")?;
+
+    let mut to_visit = vec![root_id];
+    let mut msg_id = 1u32;
+
+    while let Some(node_id) = to_visit.pop() {
+        if let Some(node) = conv.mapping.get(&node_id) {
+            // Custom instructions go into session.json as `context` instead
+            if let Some(msg) = node.message.as_ref().filter(|m| !is_custom_instructions(m)) {
+                if let Some(parts) = &msg.content.parts {
+                    let text_parts: Vec<String> = parts.iter()
+                        .filter_map(extract_text_from_part)
+                        .collect();
+
+                    if !text_parts.is_empty() {
+                        let content = text_parts.join("\n");
+                        if !content.trim().is_empty() {
+                            let timestamp = msg.create_time
+                                .and_then(|t| DateTime::<Utc>::from_timestamp(t as i64, 0))
+                                .map(|dt| dt.to_rfc3339())
+                                .unwrap_or_else(|| Utc::now().to_rfc3339());
+
+                            messages.push(ContinuumMessage {
+                                id: msg_id,
+                                role: msg.author.role.clone(),
+                                content,
+                                timestamp,
+                            });
+                            msg_id += 1;
+                        }
+                    }
+                }
+            }
+
+            for child_id in node.children.iter().rev() {
+                to_visit.push(child_id.clone());
+            }
+        }
+    }
+
+    Ok(messages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conversation_json(id: &str, title: &str) -> String {
+        format!(
+            r#"{{"id": "{id}", "title": "{title}", "create_time": 1700000000.0, "mapping": {{}}}}"#
+        )
+    }
+
+    #[test]
+    fn streams_official_conversations_in_order() {
+        let json = format!(
+            "[\n{},\n{}\n]\n",
+            conversation_json("a", "First"),
+            conversation_json("b", "Second")
+        );
+        let mut seen = Vec::new();
+        let count = for_each_official_conversation(json.as_bytes(), |idx, conv| {
+            seen.push((idx, conv.id, conv.title));
+        })
+        .unwrap();
+
+        assert_eq!(count, 2);
+        assert_eq!(
+            seen,
+            vec![(0, "a".to_string(), "First".to_string()), (1, "b".to_string(), "Second".to_string())]
+        );
+    }
+
+    #[test]
+    fn malformed_element_stops_the_stream() {
+        let json = format!("[{}, {{\"id\": 3}}]", conversation_json("a", "First"));
+        let mut seen = 0;
+        let result = for_each_official_conversation(json.as_bytes(), |_, _| seen += 1);
+
+        assert!(result.is_err());
+        assert_eq!(seen, 1);
+    }
+
+    fn fixture_conversation(name: &str) -> OfficialConversation {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name);
+        let mut conversations = Vec::new();
+        for_each_official_conversation(fs::File::open(path).unwrap(), |_, conv| conversations.push(conv)).unwrap();
+        conversations.remove(0)
+    }
+
+    #[test]
+    fn metadata_captured_when_present() {
+        let conv = fixture_conversation("official-with-metadata.json");
+
+        assert_eq!(conversation_model(&conv).as_deref(), Some("gpt-4o"));
+        let context = custom_instructions(&conv).unwrap();
+        assert!(context.starts_with("I'm a psychologist"));
+        assert!(context.ends_with("Be concise."));
+
+        let messages = extract_messages_from_tree(&conv).unwrap();
+        assert_eq!(messages.len(), 4);
+        assert!(messages.iter().all(|m| !m.content.contains("psychologist")));
+    }
+
+    #[test]
+    fn metadata_optional_for_older_exports() {
+        let conv = fixture_conversation("official-without-metadata.json");
+
+        assert_eq!(conversation_model(&conv), None);
+        assert_eq!(custom_instructions(&conv), None);
+        assert_eq!(extract_messages_from_tree(&conv).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn model_falls_back_to_conversation_default() {
+        let mut conv = fixture_conversation("official-without-metadata.json");
+        conv.default_model_slug = Some("text-davinci-002-render-sha".to_string());
+        assert_eq!(conversation_model(&conv).as_deref(), Some("text-davinci-002-render-sha"));
+    }
+
+    #[test]
+    fn long_custom_instructions_truncated() {
+        let mut conv = fixture_conversation("official-with-metadata.json");
+        for node in conv.mapping.values_mut() {
+            if let Some(msg) = node.message.as_mut().filter(|m| is_custom_instructions(m)) {
+                msg.content.user_instructions = Some("x".repeat(5000));
+            }
+        }
+        let context = custom_instructions(&conv).unwrap();
+        assert_eq!(context.chars().count(), CONTEXT_MAX_CHARS + 1);
+        assert!(context.ends_with('…'));
+    }
+
+    #[test]
+    fn detects_array_after_leading_whitespace() {
+        let dir = tempfile::tempdir().unwrap();
+        let array = dir.path().join("array.json");
+        let object = dir.path().join("object.json");
+        fs::write(&array, "  \n [ ]").unwrap();
+        fs::write(&object, "{}").unwrap();
+
+        assert!(starts_with_array(&array).unwrap());
+        assert!(!starts_with_array(&object).unwrap());
+    }
+
+    fn transcript() -> (Vec<ContinuumMessage>, ContinuumSession) {
+        let message = |id, role: &str, content: &str| ContinuumMessage {
+            id,
+            role: role.to_string(),
+            content: content.to_string(),
+            timestamp: "2025-11-24T11:32:17+00:00".to_string(),
+        };
+        let messages = vec![
+            message(1, "user", "Which Stoics should I start with?\n"),
+            message(2, "assistant", "  Epictetus, then Seneca's letters."),
+            message(3, "tool", "search results"),
+        ];
+        let session = ContinuumSession {
+            id: "stoic-reading-list".to_string(),
+            assistant: "chatgpt".to_string(),
+            start_time: Some("2025-11-24T11:32:17+00:00".to_string()),
+            end_time: None,
+            status: Some("imported".to_string()),
+            message_count: Some(3),
+            created_at: Some("2025-11-24T11:32:17+00:00".to_string()),
+            title: Some("Stoic reading list".to_string()),
+            source_url: Some("https://chatgpt.com/c/abc".to_string()),
+            skills: Vec::new(),
+            model: Some("gpt-4o".to_string()),
+            context: None,
+        };
+        (messages, session)
+    }
+
+    #[test]
+    fn markdown_transcript_layout() {
+        let (messages, session) = transcript();
+        assert_eq!(
+            render_markdown(&messages, &session),
+            "# Stoic reading list\n\n\
+             - **Assistant:** chatgpt\n\
+             - **Started:** 2025-11-24T11:32:17+00:00\n\
+             - **Messages:** 3\n\
+             - **Source:** https://chatgpt.com/c/abc\n\
+             - **Model:** gpt-4o\n\
+             \n---\n\n**User:**\n\nWhich Stoics should I start with?\n\
+             \n---\n\n**Assistant:**\n\nEpictetus, then Seneca's letters.\n\
+             \n---\n\n**Tool:**\n\nsearch results\n"
+        );
+    }
+
+    #[test]
+    fn markdown_title_falls_back_to_id() {
+        let (messages, mut session) = transcript();
+        session.title = None;
+        assert!(render_markdown(&messages, &session).starts_with("# stoic-reading-list\n\n"));
+    }
+
+    #[test]
+    fn write_session_by_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let (messages, session) = transcript();
+
+        let saved = write_session(dir.path(), "2025-11-24", &session.id, &messages, &session, OutputFormat::Markdown)
+            .unwrap();
+        let path = dir.path().join("2025-11-24-stoic-reading-list.md");
+        assert!(matches!(saved, Saved::Created(ref p) if *p == path));
+        assert_eq!(fs::read_to_string(&path).unwrap(), render_markdown(&messages, &session));
+
+        write_session(dir.path(), "2025-11-24", &session.id, &messages, &session, OutputFormat::Continuum).unwrap();
+        let session_dir = dir.path().join("2025-11-24/stoic-reading-list");
+        assert_eq!(fs::read_to_string(session_dir.join("messages.jsonl")).unwrap().lines().count(), 3);
+        assert!(session_dir.join("session.json").exists());
+    }
+}
@@ -1,89 +1,32 @@
-use anyhow::{Context, Result};
-use chrono::{DateTime, NaiveDateTime, Utc};
+use anyhow::Result;
+use anyhow::Context;
+use chatgpt_to_continuum::{
+    convert, convert_merged, read_assistant_map, split_groups, ConvertOptions, ExportFormat, ImportEvent,
+    ImportSummary, OutputFormat,
+};
 use clap::Parser;
-use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
-use std::fs;
+use continuum_core::wikilink;
 use std::path::PathBuf;
 
-// ============================================================================
-// Skill matching
-// ============================================================================
-
-/// Read skill alias mappings from ~/.config/continuum/skill-aliases.json
-fn read_aliases() -> HashMap<String, String> {
-    let path = dirs::home_dir()
-        .map(|h| h.join(".config/continuum/skill-aliases.json"))
-        .unwrap_or_default();
-
-    if !path.exists() {
-        return HashMap::new();
-    }
-
-    match std::fs::read_to_string(&path) {
-        Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
-        Err(_) => HashMap::new(),
-    }
-}
-
-/// Read known skill names from ~/.claude/skills/ directory
-fn read_skill_dirs() -> Vec<String> {
-    let path = dirs::home_dir()
-        .map(|h| h.join(".claude/skills"))
-        .unwrap_or_default();
-
-    if !path.exists() {
-        return Vec::new();
-    }
-
-    std::fs::read_dir(&path)
-        .ok()
-        .map(|entries| {
-            entries
-                .flatten()
-                .filter(|e| e.path().is_dir())
-                .filter_map(|e| {
-                    let name = e.file_name().to_str()?.to_string();
-                    if name.starts_with('.') { None } else { Some(name) }
-                })
-                .collect()
-        })
-        .unwrap_or_default()
-}
-
-/// Match title and/or project against known skills and aliases
-fn match_skills(title: Option<&str>, project: Option<&str>) -> Vec<String> {
-    let known_skills = read_skill_dirs();
-    let aliases = read_aliases();
-    let mut skills = Vec::new();
-
-    for candidate in [project, title].into_iter().flatten() {
-        let candidate_lower = candidate.to_lowercase();
-
-        // Direct skill name match
-        for skill in &known_skills {
-            if candidate_lower.contains(skill) && !skills.contains(skill) {
-                skills.push(skill.clone());
-            }
-        }
-
-        // Alias match (case-insensitive)
-        for (alias, skill) in &aliases {
-            if candidate_lower.contains(&alias.to_lowercase()) && !skills.contains(skill) {
-                skills.push(skill.clone());
-            }
-        }
-    }
-
-    skills
-}
-
 #[derive(Parser)]
 #[command(name = "chatgpt-to-continuum")]
 #[command(about = "Convert ChatGPT/Grok export to continuum format")]
 struct Cli {
-    /// Path to JSON file (ChatGPT or Grok export)
-    input: PathBuf,
+    /// Path(s) to JSON files (ChatGPT or Grok export). Files whose names
+    /// differ only by a part number ("X.json", "X part 2.json", "X-3.json")
+    /// are merged into one session
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+
+    /// Also merge browser-numbered repeat downloads ("X (1).json") into
+    /// "X.json", rather than treating them as different chats
+    #[arg(long, conflicts_with = "merge_into")]
+    numbered_copies: bool,
+
+    /// Merge all inputs, in the order given, into one session with this id,
+    /// appending to it if it already exists
+    #[arg(long, value_name = "ID")]
+    merge_into: Option<String>,
 
     /// Output directory (default: auto-detected based on source)
     #[arg(short, long)]
@@ -92,720 +35,163 @@ struct Cli {
     /// Force assistant type (chatgpt, grok) - auto-detected if not specified
     #[arg(short, long)]
     assistant: Option<String>,
-}
-
-// ============================================================================
-// Browser Exporter format (ChatGPT Exporter / Grok Exporter)
-// ============================================================================
-
-#[derive(Debug, Deserialize)]
-struct ExporterConversation {
-    metadata: ExporterMetadata,
-    messages: Vec<ExporterMessage>,
-    /// Grok has title at root level
-    #[serde(default)]
-    title: Option<String>,
-    /// Project/folder name from browser extension
-    #[serde(default)]
-    project: Option<String>,
-}
-
-#[derive(Debug, Deserialize)]
-struct ExporterMetadata {
-    /// ChatGPT has title in metadata
-    #[serde(default)]
-    title: Option<String>,
-    #[serde(default)]
-    user: Option<ExporterUser>,
-    dates: ExporterDates,
-    #[serde(default)]
-    link: Option<String>,
-    #[serde(default)]
-    powered_by: Option<String>,
-}
-
-#[derive(Debug, Deserialize)]
-struct ExporterUser {
-    #[serde(default)]
-    name: Option<String>,
-    #[serde(default)]
-    email: Option<String>,
-}
-
-#[derive(Debug, Deserialize)]
-struct ExporterDates {
-    created: String,
-    updated: String,
-    #[serde(default)]
-    exported: Option<String>,
-}
-
-#[derive(Debug, Deserialize)]
-struct ExporterMessage {
-    role: String,
-    say: String,
-}
-
-// ============================================================================
-// Browser Extension v2.4+ format (Grok Exporter, etc.)
-// ============================================================================
-
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct BrowserExtensionExport {
-    export_date: String,
-    #[serde(default)]
-    export_version: Option<String>,
-    platform: String,
-    #[serde(default)]
-    message_count: Option<u32>,
-    #[serde(default)]
-    url: Option<String>,
-    conversation: Vec<BrowserExtensionMessage>,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct BrowserExtensionMessage {
-    #[serde(default)]
-    id: Option<String>,
-    speaker: String,
-    content: String,
-    #[serde(default)]
-    timestamp: Option<String>,
-    #[serde(default)]
-    debug_info: Option<BrowserExtensionDebugInfo>,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct BrowserExtensionDebugInfo {
-    scores: Option<BrowserExtensionScores>,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct BrowserExtensionScores {
-    grok_score: Option<i32>,
-    human_score: Option<i32>,
-}
-
-// ============================================================================
-// Official OpenAI export format
-// ============================================================================
-
-#[derive(Debug, Deserialize)]
-struct OfficialConversation {
-    title: String,
-    create_time: f64,
-    update_time: Option<f64>,
-    mapping: HashMap<String, Node>,
-    #[serde(default)]
-    current_node: Option<String>,
-    id: String,
-}
-
-#[derive(Debug, Deserialize)]
-struct Node {
-    #[serde(default)]
-    id: String,
-    message: Option<NodeMessage>,
-    parent: Option<String>,
-    #[serde(default)]
-    children: Vec<String>,
-}
-
-#[derive(Debug, Deserialize)]
-struct NodeMessage {
-    #[serde(default)]
-    id: String,
-    author: Author,
-    create_time: Option<f64>,
-    content: Content,
-}
 
-#[derive(Debug, Deserialize)]
-struct Author {
-    role: String,
-}
+    /// JSON file mapping detected assistant names to the ones to use,
+    /// e.g. {"grok": "grok-work"}
+    #[arg(long, value_name = "FILE")]
+    assistant_map: Option<PathBuf>,
 
-#[derive(Debug, Deserialize)]
-struct Content {
-    parts: Option<Vec<serde_json::Value>>,
-}
+    /// Output format: continuum session directories, or one markdown
+    /// transcript per conversation (written to the current directory
+    /// unless --output is given)
+    #[arg(long, value_enum, default_value = "continuum")]
+    output_format: OutputFormat,
 
-// ============================================================================
-// Continuum output structures
-// ============================================================================
+    /// Also write each conversation as a markdown note for the vault and
+    /// print a wikilink to it
+    #[arg(long)]
+    note: bool,
 
-#[derive(Debug, Serialize)]
-struct ContinuumMessage {
-    id: u32,
-    role: String,
-    content: String,
-    timestamp: String,
-}
+    /// Where --note writes (default: ~/Forge/Captures/ai-conversations)
+    #[arg(long, value_name = "DIR", requires = "note")]
+    note_dir: Option<PathBuf>,
 
-#[derive(Debug, Serialize)]
-struct ContinuumSession {
-    id: String,
-    assistant: String,
-    start_time: Option<String>,
-    end_time: Option<String>,
-    status: Option<String>,
-    message_count: Option<u32>,
-    created_at: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    title: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    source_url: Option<String>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    skills: Vec<String>,
+    /// Respace message timestamps that repeat or go backwards evenly
+    /// between the ones around them (or the conversation's start and end)
+    #[arg(long)]
+    fix_timestamps: bool,
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
-    println!("Reading: {:?}", cli.input);
-
-    let json_content = fs::read_to_string(&cli.input)
-        .context("Failed to read input file")?;
-
-    // Try to detect format and process accordingly
-    if let Ok(exporter_conv) = serde_json::from_str::<ExporterConversation>(&json_content) {
-        // Detect source from powered_by or CLI flag
-        let assistant = cli.assistant.clone().unwrap_or_else(|| {
-            detect_assistant(&exporter_conv)
-        });
-
-        let output_dir = cli.output.unwrap_or_else(|| {
-            let home = std::env::var("HOME").expect("HOME not set");
-            PathBuf::from(home)
-                .join("Assistants")
-                .join("continuum-logs")
-                .join(&assistant)
-        });
-
-        println!("Detected: {} Exporter format", assistant);
-        println!("Output:  {:?}", output_dir);
-
-        process_exporter_conversation(&exporter_conv, &output_dir, &assistant)?;
-        println!("\nImport complete!");
-        println!("  Assistant:     {}", assistant);
-        println!("  Conversations: 1");
-        println!("  Messages:      {}", exporter_conv.messages.len());
-        println!("  Output:        {:?}", output_dir);
-    } else if let Ok(browser_ext) = serde_json::from_str::<BrowserExtensionExport>(&json_content) {
-        // Browser extension v2.4+ format (Grok Exporter, etc.)
-        let assistant = cli.assistant.clone().unwrap_or_else(|| browser_ext.platform.to_lowercase());
-
-        let output_dir = cli.output.unwrap_or_else(|| {
-            let home = std::env::var("HOME").expect("HOME not set");
-            PathBuf::from(home)
-                .join("Assistants")
-                .join("continuum-logs")
-                .join(&assistant)
-        });
-
-        let version = browser_ext.export_version.as_deref().unwrap_or("unknown");
-        println!("Detected: Browser Extension v{} format ({})", version, browser_ext.platform);
-        println!("Output:  {:?}", output_dir);
-
-        process_browser_extension_export(&browser_ext, &output_dir, &assistant)?;
-        println!("\nImport complete!");
-        println!("  Assistant:     {}", assistant);
-        println!("  Conversations: 1");
-        println!("  Messages:      {}", browser_ext.conversation.len());
-        println!("  Output:        {:?}", output_dir);
-    } else if let Ok(official_convs) = serde_json::from_str::<Vec<OfficialConversation>>(&json_content) {
-        let output_dir = cli.output.unwrap_or_else(|| {
-            let home = std::env::var("HOME").expect("HOME not set");
-            PathBuf::from(home)
-                .join("Assistants")
-                .join("continuum-logs")
-                .join("chatgpt")
-        });
-
-        println!("Detected: Official OpenAI export format");
-        println!("Output:  {:?}", output_dir);
-        println!("Found {} conversations", official_convs.len());
-
-        let mut success_count = 0;
-        let mut error_count = 0;
-
-        for (idx, conversation) in official_convs.iter().enumerate() {
-            match process_official_conversation(conversation, &output_dir) {
-                Ok(_) => success_count += 1,
-                Err(e) => {
-                    eprintln!("Error processing conversation {}: {}", idx + 1, e);
-                    error_count += 1;
-                }
-            }
-
-            if (idx + 1) % 100 == 0 {
-                println!("Processed {} conversations...", idx + 1);
-            }
-        }
-
-        println!("\nImport complete!");
-        println!("  Success: {}", success_count);
-        println!("  Errors:  {}", error_count);
-        println!("  Output:  {:?}", output_dir);
-    } else {
-        anyhow::bail!("Unrecognized JSON format. Expected ChatGPT/Grok Exporter or official OpenAI export.");
-    }
+    let options = ConvertOptions {
+        output: cli.output,
+        assistant: cli.assistant,
+        assistant_map: match &cli.assistant_map {
+            Some(path) => read_assistant_map(path)?,
+            None => Default::default(),
+        },
+        output_format: cli.output_format,
+        note_dir: match (cli.note, cli.note_dir) {
+            (_, Some(dir)) => Some(dir),
+            (true, None) => Some(default_note_dir()?),
+            (false, None) => None,
+        },
+        fix_timestamps: cli.fix_timestamps,
+    };
 
-    Ok(())
-}
+    let groups = match cli.merge_into {
+        Some(_) => vec![cli.inputs],
+        None => split_groups(&cli.inputs, cli.numbered_copies),
+    };
 
-fn detect_assistant(conv: &ExporterConversation) -> String {
-    if let Some(powered_by) = &conv.metadata.powered_by {
-        let lower = powered_by.to_lowercase();
-        if lower.contains("grok") {
-            return "grok".to_string();
-        }
-        if lower.contains("chatgpt") {
-            return "chatgpt".to_string();
+    let mut notes = Vec::new();
+    for (i, group) in groups.iter().enumerate() {
+        if i > 0 {
+            println!();
         }
-        if lower.contains("gemini") {
-            return "gemini".to_string();
+        for input in group {
+            println!("Reading: {:?}", input);
         }
-    }
-    // Default to chatgpt
-    "chatgpt".to_string()
-}
-
-// ============================================================================
-// Process Browser Exporter format (ChatGPT / Grok)
-// ============================================================================
-
-fn parse_exporter_date(date_str: &str) -> Option<DateTime<Utc>> {
-    // Try format with seconds: "11/24/2025 11:32:17"
-    if let Ok(dt) = NaiveDateTime::parse_from_str(date_str, "%m/%d/%Y %H:%M:%S") {
-        return Some(dt.and_utc());
-    }
-    // Try format without seconds: "9/11/2025 15:14"
-    if let Ok(dt) = NaiveDateTime::parse_from_str(date_str, "%m/%d/%Y %H:%M") {
-        return Some(dt.and_utc());
-    }
-    None
-}
-
-fn get_title(conv: &ExporterConversation) -> String {
-    // Try root-level title first (Grok), then metadata title (ChatGPT)
-    conv.title.clone()
-        .or_else(|| conv.metadata.title.clone())
-        .unwrap_or_else(|| "untitled".to_string())
-}
-
-fn process_exporter_conversation(conv: &ExporterConversation, output_dir: &PathBuf, assistant: &str) -> Result<()> {
-    let created = parse_exporter_date(&conv.metadata.dates.created)
-        .unwrap_or_else(Utc::now);
-    let updated = parse_exporter_date(&conv.metadata.dates.updated);
 
-    let date_str = created.format("%Y-%m-%d").to_string();
-    let title = get_title(conv);
-
-    // Generate ID from title (sanitized)
-    let id = sanitize_id(&title);
-
-    let session_dir = output_dir.join(&date_str).join(&id);
-    fs::create_dir_all(&session_dir)
-        .with_context(|| format!("Failed to create {:?}", session_dir))?;
-
-    // Convert messages.
-    //
-    // The primary fix for the Gemini Exporter's known quirks (per-turn
-    // duplication, line-by-line paste fragmentation, stray "Gemini" label
-    // messages) lives in the browser extension itself, in
-    // ~/dotfiles/browser-extensions/gemini-exporter/content.js. After that
-    // fix, exports are clean at source.
-    //
-    // We keep two defensive layers here as belt-and-braces against
-    // regression and against similar bugs in other browser exporters:
-    //
-    //   1. clean_message_content strips any UI label prefixes/suffixes
-    //      that might leak through ("You said\n\n", "Gemini said\n\n",
-    //      trailing "Sources" footer)
-    //   2. Drop empty messages and stray model-name labels ("Gemini",
-    //      "ChatGPT", etc.)
-    //   3. Global (role, content) dedup — drops any message whose content
-    //      has already appeared in the conversation. Cheap protection
-    //      against any source-side regression that re-introduces dupes.
-
-    // Clean each message; drop empties and stray labels
-    let mut cleaned: Vec<ContinuumMessage> = Vec::new();
-    for msg in conv.messages.iter() {
-        let role = match msg.role.as_str() {
-            "Prompt" => "user".to_string(),
-            "Response" => "assistant".to_string(),
-            other => other.to_lowercase(),
+        let mut official = false;
+        let summary = if group.len() > 1 || cli.merge_into.is_some() {
+            println!("Merging {} parts into one session", group.len());
+            convert_merged(group, cli.merge_into.as_deref(), &options, |event| {
+                print_event(event, &mut official)
+            })?
+        } else {
+            convert(&group[0], &options, |event| print_event(event, &mut official))?
         };
-
-        let content = clean_message_content(&msg.say);
-
-        if content.trim().is_empty() {
-            continue;
-        }
-
-        let trimmed = content.trim();
-        if matches!(trimmed, "Gemini" | "ChatGPT" | "Claude" | "Grok") {
-            continue;
+        print_summary(&summary, cli.fix_timestamps);
+        notes.extend(summary.notes);
+        if cli.note && official {
+            eprintln!("Note: skipped for official exports, which hold many conversations");
         }
-
-        cleaned.push(ContinuumMessage {
-            id: 0, // renumbered at the end
-            role,
-            content,
-            timestamp: created.to_rfc3339(),
-        });
     }
 
-    // Defensive global content dedup — drops any message whose (role, content)
-    // pair has already appeared. With the source-side fix in the browser
-    // extension this should never fire, but it costs almost nothing and
-    // catches regressions.
-    let mut seen: HashSet<(String, String)> = HashSet::new();
-    let mut continuum_messages: Vec<ContinuumMessage> = Vec::new();
-    for msg in cleaned.into_iter() {
-        let key = (msg.role.clone(), msg.content.clone());
-        if seen.insert(key) {
-            continuum_messages.push(msg);
+    // Last, so the links are easy to copy from the end of the output
+    if !notes.is_empty() {
+        println!();
+        for note in &notes {
+            println!("{}", wikilink(note));
         }
     }
 
-    // Renumber sequentially
-    for (i, msg) in continuum_messages.iter_mut().enumerate() {
-        msg.id = (i + 1) as u32;
-    }
-
-    if continuum_messages.is_empty() {
-        return Ok(());
-    }
-
-    // Write messages.jsonl
-    let messages_path = session_dir.join("messages.jsonl");
-    let mut jsonl_content = String::new();
-    for msg in &continuum_messages {
-        jsonl_content.push_str(&serde_json::to_string(msg)?);
-        jsonl_content.push('\n');
-    }
-    fs::write(&messages_path, jsonl_content)?;
-
-    // Match skills from title and project
-    let skills = match_skills(
-        Some(&title),
-        conv.project.as_deref(),
-    );
-
-    // Write session.json
-    let session = ContinuumSession {
-        id: id.clone(),
-        assistant: assistant.to_string(),
-        start_time: Some(created.to_rfc3339()),
-        end_time: updated.map(|dt| dt.to_rfc3339()),
-        status: Some("imported".to_string()),
-        message_count: Some(continuum_messages.len() as u32),
-        created_at: Some(created.to_rfc3339()),
-        title: Some(title),
-        source_url: conv.metadata.link.clone(),
-        skills,
-    };
-
-    let session_path = session_dir.join("session.json");
-    let session_json = serde_json::to_string_pretty(&session)?;
-    fs::write(&session_path, session_json)?;
-
-    println!("  Created: {}/{}", date_str, id);
     Ok(())
 }
 
-fn sanitize_id(title: &str) -> String {
-    title
-        .chars()
-        .map(|c| {
-            if c.is_alphanumeric() || c == '-' || c == '_' {
-                c.to_ascii_lowercase()
-            } else if c.is_whitespace() {
-                '-'
-            } else {
-                '_'
-            }
-        })
-        .collect::<String>()
-        .chars()
-        .take(64)
-        .collect()
-}
-
-fn clean_message_content(content: &str) -> String {
-    let mut result = content.to_string();
-
-    // Remove trailing timestamp patterns like "11:32 AM11:32" or "11:54 AM11:54"
-    let timestamp_re = regex::Regex::new(r"\n\n\d{1,2}:\d{2}\s*[AP]M\d{1,2}:\d{2}\s*$").unwrap();
-    result = timestamp_re.replace(&result, "").to_string();
-
-    // Strip Gemini browser-extension UI label prefixes from message bodies.
-    // The "Gemini Exporter (custom extension)" includes the rendered UI labels
-    // ("You said", "Gemini said") inline at the start of message content. Strip
-    // them so the message body is just the actual text.
-    if let Some(rest) = result.strip_prefix("You said\n\n") {
-        result = rest.to_string();
-    }
-    // "Show thinking" disclosure widget (Gemini's thinking indicator)
-    if let Some(rest) = result.strip_prefix("Show thinking\n") {
-        result = rest.to_string();
-    }
-    if let Some(rest) = result.strip_prefix("Gemini said\n\n") {
-        result = rest.to_string();
-    }
-
-    // Strip the trailing "Sources" footer label from Gemini assistant messages
-    // (the source-citation widget renders as a literal "Sources" line at the end).
-    let sources_re = regex::Regex::new(r"\n+Sources\s*$").unwrap();
-    result = sources_re.replace(&result, "").to_string();
-
-    // Remove Gemini UI artifacts
-    // "Edit" on its own line
-    let edit_re = regex::Regex::new(r"(?m)^Edit\s*$").unwrap();
-    result = edit_re.replace_all(&result, "").to_string();
-
-    // "Retry" and "WN" markers
-    let retry_re = regex::Regex::new(r"(?m)^Retry\s*$").unwrap();
-    result = retry_re.replace_all(&result, "").to_string();
-    let wn_re = regex::Regex::new(r"(?m)^WN\s*$").unwrap();
-    result = wn_re.replace_all(&result, "").to_string();
-
-    // Timestamp indicators like "9s", "0s", "4s", "25s" on their own line
-    let time_indicator_re = regex::Regex::new(r"(?m)^\d+s\s*$").unwrap();
-    result = time_indicator_re.replace_all(&result, "").to_string();
-
-    // "X results" search indicators
-    let results_re = regex::Regex::new(r"(?m)^\d+\s+results?\s*$").unwrap();
-    result = results_re.replace_all(&result, "").to_string();
-
-    // Collapse multiple newlines into at most two
-    let multi_newline_re = regex::Regex::new(r"\n{3,}").unwrap();
-    result = multi_newline_re.replace_all(&result, "\n\n").to_string();
-
-    result.trim().to_string()
+fn default_note_dir() -> Result<PathBuf> {
+    dirs::home_dir()
+        .map(|home| home.join("Forge/Captures/ai-conversations"))
+        .context("No home directory for --note; pass --note-dir")
 }
 
-// ============================================================================
-// Process Browser Extension v2.4+ format
-// ============================================================================
-
-fn process_browser_extension_export(export: &BrowserExtensionExport, output_dir: &PathBuf, assistant: &str) -> Result<()> {
-    // Parse export date
-    let created = DateTime::parse_from_rfc3339(&export.export_date)
-        .map(|dt| dt.with_timezone(&Utc))
-        .unwrap_or_else(|_| Utc::now());
-
-    let date_str = created.format("%Y-%m-%d").to_string();
-
-    // Generate ID from URL or timestamp
-    let id = if let Some(url) = &export.url {
-        // Extract conversation ID from URL like "https://grok.com/c/b36eb0c0-..."
-        url.split('/').last()
-            .and_then(|s| s.split('?').next())
-            .unwrap_or("unknown")
-            .to_string()
-    } else {
-        format!("{}-{}", assistant, created.timestamp())
-    };
-
-    let session_dir = output_dir.join(&date_str).join(&id);
-    fs::create_dir_all(&session_dir)
-        .with_context(|| format!("Failed to create {:?}", session_dir))?;
-
-    // Convert messages using alternating pattern (user starts, then alternates)
-    // The browser extension's debug scores are unreliable, so we use conversation flow
-    let mut continuum_messages = Vec::new();
-    for (idx, msg) in export.conversation.iter().enumerate() {
-        // Conversations typically start with user, then alternate
-        // This is more reliable than the extension's scoring heuristics
-        let role = if idx % 2 == 0 {
-            "user".to_string()
-        } else {
-            "assistant".to_string()
-        };
-
-        let timestamp = msg.timestamp.clone()
-            .unwrap_or_else(|| created.to_rfc3339());
-
-        if !msg.content.trim().is_empty() {
-            continuum_messages.push(ContinuumMessage {
-                id: (idx + 1) as u32,
-                role,
-                content: msg.content.clone(),
-                timestamp,
-            });
+fn print_event(event: ImportEvent, official: &mut bool) {
+    match event {
+        ImportEvent::Started { format, assistant, output_dir } => {
+            match format {
+                ExportFormat::Exporter => println!("Detected: {} Exporter format", assistant),
+                ExportFormat::BrowserExtension { version, platform } => {
+                    println!("Detected: Browser Extension v{} format ({})", version, platform)
+                }
+                ExportFormat::Official => {
+                    *official = true;
+                    println!("Detected: Official OpenAI export format");
+                }
+            }
+            println!("Output:  {:?}", output_dir);
         }
-    }
-
-    if continuum_messages.is_empty() {
-        return Ok(());
-    }
-
-    // Write messages.jsonl
-    let messages_path = session_dir.join("messages.jsonl");
-    let mut jsonl_content = String::new();
-    for msg in &continuum_messages {
-        jsonl_content.push_str(&serde_json::to_string(msg)?);
-        jsonl_content.push('\n');
-    }
-    fs::write(&messages_path, jsonl_content)?;
-
-    // Get end time from last message
-    let end_time = continuum_messages.last()
-        .map(|msg| msg.timestamp.clone());
-
-    // Match skills (browser extension format has no title, but future versions may)
-    let skills = match_skills(None, None);
-
-    // Write session.json
-    let session = ContinuumSession {
-        id: id.clone(),
-        assistant: assistant.to_string(),
-        start_time: Some(created.to_rfc3339()),
-        end_time,
-        status: Some("imported".to_string()),
-        message_count: Some(continuum_messages.len() as u32),
-        created_at: Some(created.to_rfc3339()),
-        title: None, // Browser extension format doesn't include title
-        source_url: export.url.clone(),
-        skills,
-    };
-
-    let session_path = session_dir.join("session.json");
-    let session_json = serde_json::to_string_pretty(&session)?;
-    fs::write(&session_path, session_json)?;
-
-    println!("  Created: {}/{}", date_str, id);
-    Ok(())
-}
-
-fn map_speaker_to_role(speaker: &str) -> String {
-    match speaker.to_lowercase().as_str() {
-        "grok" | "assistant" | "gemini" | "chatgpt" => "assistant".to_string(),
-        "human" | "user" | "you" => "user".to_string(),
-        _ => "user".to_string(), // Default to user for unknown speakers
-    }
-}
-
-// ============================================================================
-// Process Official OpenAI export format
-// ============================================================================
-
-fn process_official_conversation(conv: &OfficialConversation, output_dir: &PathBuf) -> Result<()> {
-    let datetime = DateTime::<Utc>::from_timestamp(conv.create_time as i64, 0)
-        .context("Invalid timestamp")?;
-    let date_str = datetime.format("%Y-%m-%d").to_string();
-
-    let session_dir = output_dir.join(&date_str).join(&conv.id);
-    fs::create_dir_all(&session_dir)
-        .with_context(|| format!("Failed to create {:?}", session_dir))?;
-
-    let messages = extract_messages_from_tree(conv)?;
-
-    if messages.is_empty() {
-        return Ok(());
-    }
-
-    // Write messages.jsonl
-    let messages_path = session_dir.join("messages.jsonl");
-    let mut jsonl_content = String::new();
-    for msg in &messages {
-        jsonl_content.push_str(&serde_json::to_string(msg)?);
-        jsonl_content.push('\n');
-    }
-    fs::write(&messages_path, jsonl_content)?;
-
-    // Match skills from title
-    let skills = match_skills(Some(&conv.title), None);
-
-    // Write session.json
-    let session = ContinuumSession {
-        id: conv.id.clone(),
-        assistant: "chatgpt".to_string(),
-        start_time: Some(datetime.to_rfc3339()),
-        end_time: conv.update_time.and_then(|t| {
-            DateTime::<Utc>::from_timestamp(t as i64, 0)
-                .map(|dt| dt.to_rfc3339())
-        }),
-        status: Some("imported".to_string()),
-        message_count: Some(messages.len() as u32),
-        created_at: Some(datetime.to_rfc3339()),
-        title: Some(conv.title.clone()),
-        source_url: None,
-        skills,
-    };
-
-    let session_path = session_dir.join("session.json");
-    let session_json = serde_json::to_string_pretty(&session)?;
-    fs::write(&session_path, session_json)?;
-
-    Ok(())
-}
-
-fn extract_text_from_part(part: &serde_json::Value) -> Option<String> {
-    if let Some(text) = part.as_str() {
-        return Some(text.to_string());
-    }
-
-    if let Some(obj) = part.as_object() {
-        if let Some(content_type) = obj.get("content_type").and_then(|v| v.as_str()) {
-            return Some(format!("[{}]", content_type));
+        // Official exports can hold thousands of conversations; only the
+        // periodic progress line is shown for them
+        ImportEvent::Created(path) if !*official => println!("  Created: {}", path.display()),
+        ImportEvent::Created(_) => {}
+        ImportEvent::Skipped(path) if !*official => println!("  Already imported: {}", path.display()),
+        ImportEvent::Merged { into, added } if !*official => {
+            println!("  Merged {} new messages into {}", added, into.display())
+        }
+        ImportEvent::Skipped(_) | ImportEvent::Merged { .. } => {}
+        ImportEvent::Failed { index, error } => {
+            eprintln!("Error processing conversation {}: {}", index + 1, error)
+        }
+        ImportEvent::Timestamps { id, anomaly, fixed } if !*official => {
+            let outcome = if fixed { "respaced" } else { "use --fix-timestamps to respace them" };
+            eprintln!("  ⚠️  Timestamps out of order in {}: {} ({})", id, anomaly, outcome)
+        }
+        ImportEvent::Timestamps { .. } => {}
+        ImportEvent::Processed(count) => {
+            if count % 100 == 0 {
+                println!("Processed {} conversations...", count);
+            }
         }
     }
-
-    None
 }
 
-fn extract_messages_from_tree(conv: &OfficialConversation) -> Result<Vec<ContinuumMessage>> {
-    let mut messages = Vec::new();
-
-    let root_id = conv.mapping.iter()
-        .find(|(_, node)| node.parent.is_none())
-        .map(|(id, _)| id.clone())
-        .context("No root node found")?;
-
-    let mut to_visit = vec![root_id];
-    let mut msg_id = 1u32;
-
-    while let Some(node_id) = to_visit.pop() {
-        if let Some(node) = conv.mapping.get(&node_id) {
-            if let Some(msg) = &node.message {
-                if let Some(parts) = &msg.content.parts {
-                    let text_parts: Vec<String> = parts.iter()
-                        .filter_map(extract_text_from_part)
-                        .collect();
-
-                    if !text_parts.is_empty() {
-                        let content = text_parts.join("\n");
-                        if !content.trim().is_empty() {
-                            let timestamp = msg.create_time
-                                .and_then(|t| DateTime::<Utc>::from_timestamp(t as i64, 0))
-                                .map(|dt| dt.to_rfc3339())
-                                .unwrap_or_else(|| Utc::now().to_rfc3339());
-
-                            messages.push(ContinuumMessage {
-                                id: msg_id,
-                                role: msg.author.role.clone(),
-                                content,
-                                timestamp,
-                            });
-                            msg_id += 1;
-                        }
-                    }
-                }
-            }
-
-            for child_id in node.children.iter().rev() {
-                to_visit.push(child_id.clone());
-            }
+fn print_summary(summary: &ImportSummary, fix_timestamps: bool) {
+    println!("\nImport complete!");
+    if summary.format == ExportFormat::Official {
+        println!("  Conversations: {}", summary.conversations);
+        println!("  Success: {}", summary.succeeded);
+        println!("  Errors:  {}", summary.failed);
+        if !summary.skipped.is_empty() || !summary.merged.is_empty() {
+            println!("  Duplicates: {} skipped, {} merged", summary.skipped.len(), summary.merged.len());
+        }
+        if summary.timestamp_anomalies > 0 {
+            println!(
+                "  Timestamps: {} conversations out of order{}",
+                summary.timestamp_anomalies,
+                if fix_timestamps { ", respaced" } else { " (--fix-timestamps respaces them)" }
+            );
         }
+        println!("  Output:  {:?}", summary.output_dir);
+    } else {
+        println!("  Assistant:     {}", summary.assistant);
+        println!("  Conversations: 1");
+        println!("  Messages:      {}", summary.messages);
+        println!("  Output:        {:?}", summary.output_dir);
+    }
+    for note in &summary.notes {
+        println!("  Note:          {:?}", note);
     }
-
-    Ok(messages)
 }
@@ -0,0 +1,328 @@
+//! Conversations split across several export files.
+//!
+//! Browser extensions export long conversations in pieces named `X part 2`,
+//! `X-2` and so on. The pieces are merged back into one session: messages
+//! appended in part order and renumbered contiguously. Browsers name repeat
+//! downloads `X (1).json`, which is just as likely a different chat with the
+//! same title, so those are only grouped when asked.
+
+use anyhow::Result;
+use continuum_core::Saved;
+use regex::Regex;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{
+    convert_browser_extension_export, convert_exporter_conversation, default_output_dir,
+    parse_export, ContinuumMessage, ConvertOptions, Converted, ImportError,
+    ImportEvent, ImportSummary, OutputFormat, ParsedExport,
+};
+
+/// Conversation name and part number from a file stem; unnumbered files are
+/// part 0. Recognises `Name part 2` / `Name-pt2`, and `Name-2` / `Name_2`
+/// with one or two digits (longer numbers are usually years). A browser's
+/// `Name (2)` counts only with `numbered_copies`.
+fn part_of(stem: &str, numbered_copies: bool) -> (String, u32) {
+    let part_re =
+        Regex::new(r"(?i)^(.+?)(?:\s*\((\d+)\)|[\s_-]*(?:part|pt)[\s_-]*(\d+)|[\s_-]+(\d{1,2}))$").unwrap();
+    part_re
+        .captures(stem)
+        .and_then(|cap| {
+            if cap.get(2).is_some() && !numbered_copies {
+                return None;
+            }
+            let number = cap.get(2).or(cap.get(3)).or(cap.get(4))?;
+            Some((cap[1].trim().to_string(), number.as_str().parse().ok()?))
+        })
+        .unwrap_or_else(|| (stem.to_string(), 0))
+}
+
+/// Group input files into conversations. Files in the same directory whose
+/// names differ only by a part number form one group, ordered by part;
+/// everything else is a group of one. Groups keep the order in which their
+/// first file was given. `numbered_copies` also takes `X (1).json` as a part
+/// of `X.json`.
+pub fn split_groups(inputs: &[PathBuf], numbered_copies: bool) -> Vec<Vec<PathBuf>> {
+    // (directory, conversation name) -> (part number, file)
+    let mut keys: Vec<(&Path, String)> = Vec::new();
+    let mut groups: Vec<Vec<(u32, PathBuf)>> = Vec::new();
+    for input in inputs {
+        let stem = input.file_stem().unwrap_or_default().to_string_lossy();
+        let (name, part) = part_of(&stem, numbered_copies);
+        let key = (input.parent().unwrap_or(Path::new("")), name);
+        match keys.iter().position(|k| *k == key) {
+            Some(i) => groups[i].push((part, input.clone())),
+            None => {
+                keys.push(key);
+                groups.push(vec![(part, input.clone())]);
+            }
+        }
+    }
+
+    let mut result = Vec::new();
+    for mut parts in groups {
+        parts.sort_by_key(|(part, _)| *part);
+        let distinct = parts.windows(2).all(|w| w[0].0 != w[1].0);
+        if distinct {
+            result.push(parts.into_iter().map(|(_, path)| path).collect());
+        } else {
+            // Two files claim the same part number: don't guess, keep them apart
+            result.extend(parts.into_iter().map(|(_, path)| vec![path]));
+        }
+    }
+    result
+}
+
+/// Concatenate converted parts into one conversation. Where a part starts
+/// by repeating the end of what came before (overlap at the split, or the
+/// same part downloaded twice) the repeat is dropped; messages repeated
+/// anywhere else are kept. Ids are renumbered from 1.
+fn merge_parts(parts: Vec<Converted>, id: Option<&str>) -> Option<Converted> {
+    let mut parts = parts.into_iter();
+    let mut merged = parts.next()?;
+
+    for part in parts {
+        let skip = boundary_overlap(&merged.messages, &part.messages);
+        merged.messages.extend(part.messages.into_iter().skip(skip));
+        if part.session.end_time.is_some() {
+            merged.session.end_time = part.session.end_time;
+        }
+    }
+
+    for (i, msg) in merged.messages.iter_mut().enumerate() {
+        msg.id = (i + 1) as u32;
+    }
+    merged.session.message_count = Some(merged.messages.len() as u32);
+    if let Some(id) = id {
+        merged.id = id.to_string();
+        merged.session.id = id.to_string();
+    }
+    Some(merged)
+}
+
+/// Length of the longest run of messages that ends `before` and starts
+/// `after`, compared by role and content
+fn boundary_overlap(before: &[ContinuumMessage], after: &[ContinuumMessage]) -> usize {
+    let same = |a: &ContinuumMessage, b: &ContinuumMessage| a.role == b.role && a.content == b.content;
+    (1..=before.len().min(after.len()))
+        .rev()
+        .find(|&n| before[before.len() - n..].iter().zip(&after[..n]).all(|(a, b)| same(a, b)))
+        .unwrap_or(0)
+}
+
+/// The existing session `id` under `output_dir`, filed under any date
+fn find_session(output_dir: &Path, id: &str) -> Option<PathBuf> {
+    let mut days: Vec<PathBuf> = fs::read_dir(output_dir).ok()?.flatten().map(|e| e.path()).collect();
+    days.sort();
+    days.into_iter()
+        .map(|day| day.join(id))
+        .find(|dir| dir.join("messages.jsonl").is_file())
+}
+
+/// Append `merged`'s messages to the existing session in `session_dir`,
+/// numbered on from its last one (see `continuum_core::dedup::merge_into`)
+fn append_to(session_dir: PathBuf, merged: &Converted) -> Result<Saved> {
+    let messages: Vec<Value> = merged.messages.iter().map(serde_json::to_value).collect::<Result<_, _>>()?;
+    let session = serde_json::to_value(&merged.session)?;
+    let added = continuum_core::dedup::merge_into(&session_dir, &messages, &session)?;
+    Ok(Saved::Merged { dir: session_dir, added })
+}
+
+/// Convert several single-conversation exports, in the order given, into one
+/// session. `id` overrides the session id taken from the first part; if a
+/// session with that id already exists, the messages are appended to it
+/// instead of replacing it.
+pub fn convert_merged(
+    inputs: &[PathBuf],
+    id: Option<&str>,
+    options: &ConvertOptions,
+    mut on_event: impl FnMut(ImportEvent),
+) -> Result<ImportSummary, ImportError> {
+    let mut parsed = Vec::new();
+    for input in inputs {
+        match parse_export(input)? {
+            ParsedExport::Official => return Err(ImportError::NotMergeable(input.clone())),
+            export => parsed.push(export),
+        }
+    }
+    let Some(first) = parsed.first() else {
+        return Err(ImportError::NoInput);
+    };
+
+    let format = first.format();
+    let assistant = options.assistant_for(first);
+    let output_dir = options.output.clone()
+        .unwrap_or_else(|| default_output_dir(&assistant, options.output_format));
+
+    on_event(ImportEvent::Started { format: &format, assistant: &assistant, output_dir: &output_dir });
+
+    let mut messages = 0;
+    let mut converted = Vec::new();
+    for export in &parsed {
+        let part = match export {
+            ParsedExport::Exporter(conv) => {
+                messages += conv.messages.len();
+                convert_exporter_conversation(conv, &assistant)
+            }
+            ParsedExport::BrowserExtension(export) => {
+                messages += export.conversation.len();
+                convert_browser_extension_export(export, &assistant)
+            }
+            ParsedExport::Official => None,
+        };
+        converted.extend(part);
+    }
+
+    let mut summary = ImportSummary {
+        format,
+        assistant,
+        output_dir,
+        conversations: 1,
+        succeeded: 1,
+        failed: 0,
+        messages,
+        created: Vec::new(),
+        skipped: Vec::new(),
+        merged: Vec::new(),
+        notes: Vec::new(),
+        timestamp_anomalies: 0,
+    };
+    if let Some(mut merged) = merge_parts(converted, id) {
+        summary.check_timestamps(&mut merged, options, &mut on_event);
+        let existing = id
+            .filter(|_| options.output_format == OutputFormat::Continuum)
+            .and_then(|id| find_session(&summary.output_dir, id));
+        let saved = match existing {
+            Some(session_dir) => append_to(session_dir, &merged),
+            None => merged.write(&summary.output_dir, options.output_format),
+        }
+        .map_err(ImportError::Write)?;
+        summary.record(&saved, &mut on_event);
+        summary.write_note(&merged, options)?;
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ContinuumSession;
+
+    fn paths(names: &[&str]) -> Vec<PathBuf> {
+        names.iter().map(PathBuf::from).collect()
+    }
+
+    #[test]
+    fn part_numbers_from_file_stems() {
+        assert_eq!(part_of("ChatGPT-Stoicism part 3", false), ("ChatGPT-Stoicism".to_string(), 3));
+        assert_eq!(part_of("Grok-Plans_pt2", false), ("Grok-Plans".to_string(), 2));
+        assert_eq!(part_of("Grok-Plans-2", false), ("Grok-Plans".to_string(), 2));
+        assert_eq!(part_of("ChatGPT-Stoicism", false), ("ChatGPT-Stoicism".to_string(), 0));
+        // Years and other long numbers aren't part numbers
+        assert_eq!(part_of("ChatGPT-Review 2024", false), ("ChatGPT-Review 2024".to_string(), 0));
+    }
+
+    #[test]
+    fn browser_copies_are_parts_only_when_asked() {
+        assert_eq!(part_of("ChatGPT-Stoicism (2)", false), ("ChatGPT-Stoicism (2)".to_string(), 0));
+        assert_eq!(part_of("ChatGPT-Stoicism (2)", true), ("ChatGPT-Stoicism".to_string(), 2));
+
+        let inputs = paths(&["dl/ChatGPT-Notes.json", "dl/ChatGPT-Notes (1).json"]);
+        assert_eq!(split_groups(&inputs, false), vec![paths(&["dl/ChatGPT-Notes.json"]), paths(&["dl/ChatGPT-Notes (1).json"])]);
+        assert_eq!(split_groups(&inputs, true), vec![inputs.clone()]);
+    }
+
+    #[test]
+    fn groups_sequential_parts_in_order() {
+        let groups = split_groups(&paths(&[
+            "dl/ChatGPT-Long part 2.json",
+            "dl/Grok-Other.json",
+            "dl/ChatGPT-Long.json",
+            "dl/ChatGPT-Long part 1.json",
+        ]), false);
+        assert_eq!(groups, vec![
+            paths(&["dl/ChatGPT-Long.json", "dl/ChatGPT-Long part 1.json", "dl/ChatGPT-Long part 2.json"]),
+            paths(&["dl/Grok-Other.json"]),
+        ]);
+    }
+
+    #[test]
+    fn ambiguous_part_numbers_stay_separate() {
+        let groups = split_groups(&paths(&["a/X-part1.json", "a/X (1).json"]), true);
+        assert_eq!(groups, vec![paths(&["a/X-part1.json"]), paths(&["a/X (1).json"])]);
+
+        let groups = split_groups(&paths(&["a/X-1.json", "b/X-2.json"]), false);
+        assert_eq!(groups.len(), 2);
+    }
+
+    fn part(id: &str, contents: &[(&str, &str)], end: Option<&str>) -> Converted {
+        let messages: Vec<ContinuumMessage> = contents.iter().enumerate()
+            .map(|(i, (role, content))| ContinuumMessage {
+                id: (i + 1) as u32,
+                role: role.to_string(),
+                content: content.to_string(),
+                timestamp: "2025-11-24T11:32:17+00:00".to_string(),
+            })
+            .collect();
+        Converted {
+            date_str: "2025-11-24".to_string(),
+            id: id.to_string(),
+            session: ContinuumSession {
+                id: id.to_string(),
+                assistant: "chatgpt".to_string(),
+                start_time: Some("2025-11-24T11:32:17+00:00".to_string()),
+                end_time: end.map(String::from),
+                status: Some("imported".to_string()),
+                message_count: Some(messages.len() as u32),
+                created_at: None,
+                title: Some(id.to_string()),
+                source_url: None,
+                skills: Vec::new(),
+                model: None,
+                context: None,
+            },
+            messages,
+        }
+    }
+
+    #[test]
+    fn merge_renumbers_and_drops_overlap() {
+        let merged = merge_parts(vec![
+            part("long-chat", &[("user", "q1"), ("assistant", "a1")], Some("2025-11-24T12:00:00+00:00")),
+            part("long-chat-2", &[("assistant", "a1"), ("user", "q2"), ("assistant", "a2")], Some("2025-11-24T13:00:00+00:00")),
+        ], None).unwrap();
+
+        let summary: Vec<(u32, &str)> = merged.messages.iter().map(|m| (m.id, m.content.as_str())).collect();
+        assert_eq!(summary, vec![(1, "q1"), (2, "a1"), (3, "q2"), (4, "a2")]);
+        assert_eq!(merged.id, "long-chat");
+        assert_eq!(merged.session.message_count, Some(4));
+        assert_eq!(merged.session.end_time.as_deref(), Some("2025-11-24T13:00:00+00:00"));
+    }
+
+    #[test]
+    fn merge_keeps_messages_repeated_away_from_the_split() {
+        let merged = merge_parts(vec![
+            part("chat", &[("user", "continue"), ("assistant", "a1"), ("user", "thanks")], None),
+            part("chat-2", &[("user", "thanks"), ("assistant", "a2"), ("user", "continue"), ("user", "thanks")], None),
+        ], None).unwrap();
+
+        let contents: Vec<&str> = merged.messages.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(contents, ["continue", "a1", "thanks", "a2", "continue", "thanks"]);
+    }
+
+    #[test]
+    fn merge_drops_a_part_downloaded_twice() {
+        let turns = [("user", "q1"), ("assistant", "a1")];
+        let merged = merge_parts(vec![part("chat", &turns, None), part("chat", &turns, None)], None).unwrap();
+        assert_eq!(merged.messages.len(), 2);
+    }
+
+    #[test]
+    fn merge_into_overrides_id() {
+        let merged = merge_parts(vec![part("a", &[("user", "q")], None)], Some("my-session")).unwrap();
+        assert_eq!(merged.id, "my-session");
+        assert_eq!(merged.session.id, "my-session");
+        assert!(merge_parts(Vec::new(), None).is_none());
+    }
+}
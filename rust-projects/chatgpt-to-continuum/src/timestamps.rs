@@ -0,0 +1,230 @@
+//! Message timestamps that go backwards.
+//!
+//! Official exports have per-message times that can go backwards, and
+//! continuum-activity can't sort those messages. `check` spots this, and
+//! `spread` (`--fix-timestamps`) spaces the offending messages evenly
+//! between the timestamps around them. Messages sharing a timestamp, as
+//! every message from the exporter formats does (they only carry the
+//! conversation's creation time), keep their order and aren't flagged.
+
+use chrono::{DateTime, Utc};
+use std::fmt;
+
+use crate::ContinuumMessage;
+
+/// Messages whose timestamp would sort them wrongly
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TimestampAnomaly {
+    /// Earlier than the message before
+    pub backwards: usize,
+    /// Not an RFC 3339 timestamp
+    pub unreadable: usize,
+}
+
+impl fmt::Display for TimestampAnomaly {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let parts: Vec<String> = [
+            (self.backwards, "earlier than the message before"),
+            (self.unreadable, "unreadable"),
+        ]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, what)| format!("{} {}", count, what))
+        .collect();
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+fn parse(timestamp: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(timestamp).ok().map(|dt| dt.with_timezone(&Utc))
+}
+
+/// None when no timestamp is earlier than the one before it
+pub(crate) fn check(messages: &[ContinuumMessage]) -> Option<TimestampAnomaly> {
+    let mut anomaly = TimestampAnomaly::default();
+    let mut previous = None;
+    for msg in messages {
+        let Some(time) = parse(&msg.timestamp) else {
+            anomaly.unreadable += 1;
+            continue;
+        };
+        if previous.is_some_and(|prev| time < prev) {
+            anomaly.backwards += 1;
+        }
+        previous = Some(time);
+    }
+    (anomaly != TimestampAnomaly::default()).then_some(anomaly)
+}
+
+/// Give every message a timestamp later than the one before. The longest
+/// run of timestamps that already increase (and lie within `start`..`end`,
+/// the conversation's known bounds) is kept; the other messages are spaced
+/// evenly between the kept ones either side, or the bounds at either end.
+pub(crate) fn spread(messages: &mut [ContinuumMessage], start: Option<DateTime<Utc>>, end: Option<DateTime<Utc>>) {
+    let end = end.filter(|end| start.is_none_or(|start| *end > start));
+    let times: Vec<Option<DateTime<Utc>>> = messages.iter()
+        .map(|msg| parse(&msg.timestamp))
+        .map(|time| time.filter(|t| start.is_none_or(|s| *t >= s) && end.is_none_or(|e| *t <= e)))
+        .collect();
+
+    let kept = longest_increasing(&times);
+    let mut points: Vec<(usize, DateTime<Utc>)> = kept.iter().map(|&i| (i, times[i].unwrap())).collect();
+    let last = messages.len().saturating_sub(1);
+    match (points.first(), start) {
+        (Some(&(0, _)), _) => {}
+        (_, Some(start)) => points.insert(0, (0, start)),
+        (Some(&(_, first)), None) => points.insert(0, (0, first)),
+        (None, None) => return,
+    }
+    match (points.last(), end) {
+        (Some(&(i, _)), _) if i == last => {}
+        (_, Some(end)) => points.push((last, end)),
+        (Some(&(_, latest)), None) => points.push((last, latest)),
+        (None, None) => return,
+    }
+
+    for pair in points.windows(2) {
+        let ((from, from_time), (to, to_time)) = (pair[0], pair[1]);
+        let step = (to_time - from_time) / (to - from).max(1) as i32;
+        for (i, msg) in messages.iter_mut().enumerate().take(to + 1).skip(from) {
+            if !kept.contains(&i) {
+                msg.timestamp = (from_time + step * (i - from) as i32).to_rfc3339();
+            }
+        }
+    }
+}
+
+/// Indices of the longest strictly increasing run of known times, earliest
+/// messages preferred on ties
+fn longest_increasing(times: &[Option<DateTime<Utc>>]) -> Vec<usize> {
+    // length of the longest run ending at i, and the index before i in it
+    let mut best: Vec<(usize, Option<usize>)> = Vec::with_capacity(times.len());
+    for (i, time) in times.iter().enumerate() {
+        let Some(time) = time else {
+            best.push((0, None));
+            continue;
+        };
+        let before = (0..i)
+            .filter(|&j| times[j].is_some_and(|t| t < *time))
+            .max_by(|&a, &b| best[a].0.cmp(&best[b].0).then(b.cmp(&a)));
+        best.push(match before {
+            Some(j) => (best[j].0 + 1, Some(j)),
+            None => (1, None),
+        });
+    }
+
+    let Some(end) = (0..times.len())
+        .filter(|&i| best[i].0 > 0)
+        .max_by(|&a, &b| best[a].0.cmp(&best[b].0).then(b.cmp(&a)))
+    else {
+        return Vec::new();
+    };
+    let mut run = vec![end];
+    while let Some(prev) = best[*run.last().unwrap()].1 {
+        run.push(prev);
+    }
+    run.reverse();
+    run
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(timestamps: &[&str]) -> Vec<ContinuumMessage> {
+        timestamps.iter().enumerate()
+            .map(|(i, ts)| ContinuumMessage {
+                id: i as u32 + 1,
+                role: if i % 2 == 0 { "user" } else { "assistant" }.to_string(),
+                content: format!("message {}", i + 1),
+                timestamp: ts.to_string(),
+            })
+            .collect()
+    }
+
+    fn time(s: &str) -> DateTime<Utc> {
+        parse(s).unwrap()
+    }
+
+    fn timestamps(messages: &[ContinuumMessage]) -> Vec<&str> {
+        messages.iter().map(|m| m.timestamp.as_str()).collect()
+    }
+
+    #[test]
+    fn increasing_timestamps_pass() {
+        let msgs = messages(&["2025-11-24T10:00:00+00:00", "2025-11-24T10:01:00+00:00"]);
+        assert_eq!(check(&msgs), None);
+        assert_eq!(check(&[]), None);
+    }
+
+    #[test]
+    fn repeated_timestamps_pass() {
+        let msgs = messages(&["2025-11-24T10:00:00+00:00"; 3]);
+        assert_eq!(check(&msgs), None);
+    }
+
+    #[test]
+    fn counts_each_kind_of_anomaly() {
+        let msgs = messages(&[
+            "2025-11-24T10:00:00+00:00",
+            "2025-11-24T10:00:00+00:00",
+            "2025-11-24T09:00:00+00:00",
+            "yesterday",
+            "2025-11-24T11:00:00+00:00",
+        ]);
+        let anomaly = check(&msgs).unwrap();
+        assert_eq!(anomaly, TimestampAnomaly { backwards: 1, unreadable: 1 });
+        assert_eq!(anomaly.to_string(), "1 earlier than the message before, 1 unreadable");
+    }
+
+    #[test]
+    fn spreads_creation_time_copies_up_to_end() {
+        // The exporter path: every message has the creation time
+        let created = "2025-11-24T10:00:00+00:00";
+        let mut msgs = messages(&[created; 4]);
+        spread(&mut msgs, Some(time(created)), Some(time("2025-11-24T10:30:00+00:00")));
+        assert_eq!(timestamps(&msgs), [
+            "2025-11-24T10:00:00+00:00",
+            "2025-11-24T10:10:00+00:00",
+            "2025-11-24T10:20:00+00:00",
+            "2025-11-24T10:30:00+00:00",
+        ]);
+        assert_eq!(check(&msgs), None);
+    }
+
+    #[test]
+    fn out_of_order_message_moves_between_neighbours() {
+        let mut msgs = messages(&[
+            "2025-11-24T10:00:00+00:00",
+            "2025-11-24T12:00:00+00:00",
+            "2025-11-24T10:10:00+00:00",
+            "2025-11-24T10:20:00+00:00",
+        ]);
+        spread(&mut msgs, None, None);
+        // 12:00 is the odd one out; the others are left alone
+        assert_eq!(timestamps(&msgs), [
+            "2025-11-24T10:00:00+00:00",
+            "2025-11-24T10:05:00+00:00",
+            "2025-11-24T10:10:00+00:00",
+            "2025-11-24T10:20:00+00:00",
+        ]);
+    }
+
+    #[test]
+    fn times_outside_the_bounds_are_not_kept() {
+        let mut msgs = messages(&["2025-11-23T08:00:00+00:00", "bad", "2025-11-24T10:40:00+00:00"]);
+        spread(&mut msgs, Some(time("2025-11-24T10:00:00+00:00")), Some(time("2025-11-24T11:00:00+00:00")));
+        assert_eq!(timestamps(&msgs), [
+            "2025-11-24T10:00:00+00:00",
+            "2025-11-24T10:20:00+00:00",
+            "2025-11-24T10:40:00+00:00",
+        ]);
+    }
+
+    #[test]
+    fn nothing_to_go_on_leaves_messages_alone() {
+        let mut msgs = messages(&["bad", "worse"]);
+        spread(&mut msgs, None, None);
+        assert_eq!(timestamps(&msgs), ["bad", "worse"]);
+    }
+}
@@ -0,0 +1,311 @@
+//! Runs the built binary against the fixtures to pin down CLI behaviour.
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+fn run(fixture: &str, home: &Path, out: &Path) -> Output {
+    let input = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(fixture);
+    Command::new(env!("CARGO_BIN_EXE_chatgpt-to-continuum"))
+        .arg(&input)
+        .arg("--output")
+        .arg(out)
+        .env("HOME", home)
+        .env_remove("RUST_BACKTRACE")
+        .output()
+        .unwrap()
+}
+
+fn stdout_lines(output: &Output) -> Vec<String> {
+    String::from_utf8_lossy(&output.stdout).lines().map(String::from).collect()
+}
+
+#[test]
+fn exporter_format() {
+    let home = tempfile::tempdir().unwrap();
+    let out = home.path().join("out");
+    let output = run("exporter.json", home.path(), &out);
+    assert!(output.status.success());
+
+    let lines = stdout_lines(&output);
+    assert_eq!(lines[1], "Detected: chatgpt Exporter format");
+    assert!(lines.contains(&"  Created: 2025-11-24/stoic-reading-list".to_string()));
+    assert!(lines.contains(&"  Assistant:     chatgpt".to_string()));
+    assert!(lines.contains(&"  Messages:      3".to_string()));
+
+    let session_dir = out.join("2025-11-24/stoic-reading-list");
+    let messages = fs::read_to_string(session_dir.join("messages.jsonl")).unwrap();
+    // The trailing "11:32 AM11:32" and the stray "ChatGPT" label are cleaned away
+    assert_eq!(messages.lines().count(), 2);
+    assert!(!messages.contains("AM11:32"));
+
+    let session: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(session_dir.join("session.json")).unwrap()).unwrap();
+    assert_eq!(session["title"], "Stoic reading list");
+    assert_eq!(session["message_count"], 2);
+}
+
+#[test]
+fn browser_extension_format() {
+    let home = tempfile::tempdir().unwrap();
+    let out = home.path().join("out");
+    let output = run("browser-extension.json", home.path(), &out);
+    assert!(output.status.success());
+
+    let lines = stdout_lines(&output);
+    assert_eq!(lines[1], "Detected: Browser Extension v2.4.1 format (Grok)");
+    assert!(lines.contains(&"  Created: 2025-12-02/b36eb0c0-1111-2222-3333-444455556666".to_string()));
+    assert!(lines.contains(&"  Assistant:     grok".to_string()));
+    assert!(out.join("2025-12-02/b36eb0c0-1111-2222-3333-444455556666/messages.jsonl").exists());
+}
+
+#[test]
+fn official_format() {
+    let home = tempfile::tempdir().unwrap();
+    let out = home.path().join("out");
+    let output = run("official-with-metadata.json", home.path(), &out);
+    assert!(output.status.success());
+
+    let lines = stdout_lines(&output);
+    assert_eq!(lines[1], "Detected: Official OpenAI export format");
+    assert!(!lines.iter().any(|l| l.contains("Created:")));
+    assert!(lines.contains(&"  Success: 1".to_string()));
+    assert!(lines.contains(&"  Errors:  0".to_string()));
+
+    let sessions: Vec<_> = fs::read_dir(&out).unwrap().collect();
+    assert_eq!(sessions.len(), 1);
+}
+
+#[test]
+fn unrecognized_format_fails() {
+    let home = tempfile::tempdir().unwrap();
+    let input = home.path().join("other.json");
+    fs::write(&input, r#"{"hello": "world"}"#).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_chatgpt-to-continuum"))
+        .arg(&input)
+        .env("HOME", home.path())
+        .env_remove("RUST_BACKTRACE")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unrecognized JSON format"));
+}
+
+#[test]
+fn numbered_parts_merge_into_one_session() {
+    let home = tempfile::tempdir().unwrap();
+    let out = home.path().join("out");
+    let split = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/split");
+
+    // Part 1 given first: grouping orders parts by their number, not argv
+    let output = Command::new(env!("CARGO_BIN_EXE_chatgpt-to-continuum"))
+        .arg("--numbered-copies")
+        .arg(split.join("ChatGPT-Marcus Aurelius (1).json"))
+        .arg(split.join("ChatGPT-Marcus Aurelius.json"))
+        .arg("--output")
+        .arg(&out)
+        .env("HOME", home.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(stdout_lines(&output).contains(&"Merging 2 parts into one session".to_string()));
+
+    let sessions: Vec<_> = fs::read_dir(out.join("2025-12-01")).unwrap().collect();
+    assert_eq!(sessions.len(), 1);
+
+    let session_dir = out.join("2025-12-01/marcus-aurelius");
+    let messages: Vec<serde_json::Value> = fs::read_to_string(session_dir.join("messages.jsonl"))
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    let ids: Vec<u64> = messages.iter().map(|m| m["id"].as_u64().unwrap()).collect();
+    assert_eq!(ids, vec![1, 2, 3, 4]);
+    assert_eq!(messages[0]["content"], "Which translation of the Meditations?");
+    assert_eq!(messages[3]["content"], "Book 2 opens with the morning reflection.");
+}
+
+#[test]
+fn merge_into_sets_session_id() {
+    let home = tempfile::tempdir().unwrap();
+    let out = home.path().join("out");
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_chatgpt-to-continuum"))
+        .arg(fixtures.join("exporter.json"))
+        .arg(fixtures.join("split/ChatGPT-Marcus Aurelius.json"))
+        .args(["--merge-into", "stoic-reading"])
+        .arg("--output")
+        .arg(&out)
+        .env("HOME", home.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let session_dir = out.join("2025-11-24/stoic-reading");
+    let session: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(session_dir.join("session.json")).unwrap()).unwrap();
+    assert_eq!(session["id"], "stoic-reading");
+    assert_eq!(session["message_count"], 4);
+}
+
+#[test]
+fn merge_into_appends_to_existing_session() {
+    let home = tempfile::tempdir().unwrap();
+    let out = home.path().join("out");
+    let split = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/split");
+    let merge_into = |file: &str| {
+        Command::new(env!("CARGO_BIN_EXE_chatgpt-to-continuum"))
+            .arg(split.join(file))
+            .args(["--merge-into", "marcus"])
+            .arg("--output")
+            .arg(&out)
+            .env("HOME", home.path())
+            .output()
+            .unwrap()
+    };
+
+    assert!(merge_into("ChatGPT-Marcus Aurelius.json").status.success());
+    let output = merge_into("ChatGPT-Marcus Aurelius (1).json");
+    assert!(output.status.success());
+    assert!(stdout_lines(&output).contains(&"  Merged 2 new messages into 2025-12-01/marcus".to_string()));
+
+    let session_dir = out.join("2025-12-01/marcus");
+    let messages: Vec<serde_json::Value> = fs::read_to_string(session_dir.join("messages.jsonl"))
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    let ids: Vec<u64> = messages.iter().map(|m| m["id"].as_u64().unwrap()).collect();
+    assert_eq!(ids, vec![1, 2, 3, 4]);
+    assert_eq!(messages[0]["content"], "Which translation of the Meditations?");
+    assert_eq!(messages[3]["content"], "Book 2 opens with the morning reflection.");
+
+    let session: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(session_dir.join("session.json")).unwrap()).unwrap();
+    assert_eq!(session["message_count"], 4);
+    assert_eq!(session["end_time"], "2025-12-01T11:05:00+00:00");
+}
+
+#[test]
+fn conversation_already_imported_is_skipped() {
+    let home = tempfile::tempdir().unwrap();
+    let out = home.path().join("out");
+    assert!(run("official-with-metadata.json", home.path(), &out).status.success());
+    assert!(out.join("2023-11-14/conv-meta/hashes.json").exists());
+
+    // A browser export of the same chat, taken before the last answer
+    let output = run("sleep-and-memory-partial.json", home.path(), &out);
+    assert!(output.status.success());
+    assert!(stdout_lines(&output).contains(&"  Already imported: 2023-11-14/conv-meta".to_string()));
+    let sessions: Vec<_> = fs::read_dir(out.join("2023-11-14")).unwrap().collect();
+    assert_eq!(sessions.len(), 1);
+
+    // Re-running the official export still overwrites its own session
+    let output = run("official-with-metadata.json", home.path(), &out);
+    assert!(stdout_lines(&output).contains(&"  Success: 1".to_string()));
+    assert!(!stdout_lines(&output).iter().any(|l| l.starts_with("  Duplicates:")));
+}
+
+#[test]
+fn assistant_map_renames_detected_platform() {
+    let home = tempfile::tempdir().unwrap();
+    let map = home.path().join("assistants.json");
+    fs::write(&map, r#"{"Grok": "grok-work"}"#).unwrap();
+
+    let input = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/browser-extension.json");
+    let output = Command::new(env!("CARGO_BIN_EXE_chatgpt-to-continuum"))
+        .arg(&input)
+        .arg("--assistant-map")
+        .arg(&map)
+        .env("HOME", home.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(stdout_lines(&output).contains(&"  Assistant:     grok-work".to_string()));
+
+    // The mapped name picks the output directory and is recorded in the session
+    let session_dir = home
+        .path()
+        .join("Assistants/continuum-logs/grok-work/2025-12-02/b36eb0c0-1111-2222-3333-444455556666");
+    let session: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(session_dir.join("session.json")).unwrap()).unwrap();
+    assert_eq!(session["assistant"], "grok-work");
+}
+
+#[test]
+fn note_written_to_vault_and_linked() {
+    let home = tempfile::tempdir().unwrap();
+    let out = home.path().join("out");
+    let input = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/exporter.json");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_chatgpt-to-continuum"))
+        .arg(&input)
+        .arg("--output")
+        .arg(&out)
+        .arg("--note")
+        .env("HOME", home.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(stdout_lines(&output).last().unwrap(), "[[2025-11-24 Stoic reading list]]");
+
+    let note = home.path().join("Forge/Captures/ai-conversations/2025-11-24 Stoic reading list.md");
+    let note = fs::read_to_string(note).unwrap();
+    assert!(note.starts_with("---\nassistant: chatgpt\ndate: 2025-11-24\n"));
+    assert!(note.contains("\n# Stoic reading list\n\n### You\n\n"));
+    assert!(note.contains("\n### Assistant\n\n"));
+}
+
+#[test]
+fn repeated_timestamps_are_not_warned() {
+    // Every exporter message carries the conversation's creation time
+    let home = tempfile::tempdir().unwrap();
+    let out = home.path().join("out");
+    let output = run("exporter.json", home.path(), &out);
+    assert!(output.status.success());
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("Timestamps out of order"));
+}
+
+#[test]
+fn backwards_timestamps_warned_and_fixed() {
+    let home = tempfile::tempdir().unwrap();
+    let out = home.path().join("out");
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/official-with-metadata.json");
+    // The last answer stamped before the question it answers
+    let export = fs::read_to_string(fixture).unwrap().replace("1700000030.0,\n          \"content\"", "1700000005.0,\n          \"content\"");
+    let input = home.path().join("conversations.json");
+    fs::write(&input, export).unwrap();
+
+    let convert = |fix: bool| {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_chatgpt-to-continuum"));
+        command.arg(&input).arg("--output").arg(&out).env("HOME", home.path());
+        if fix {
+            command.arg("--fix-timestamps");
+        }
+        command.output().unwrap()
+    };
+
+    let output = convert(false);
+    assert!(output.status.success());
+    assert!(stdout_lines(&output)
+        .contains(&"  Timestamps: 1 conversations out of order (--fix-timestamps respaces them)".to_string()));
+
+    let output = convert(true);
+    assert!(output.status.success());
+    assert!(stdout_lines(&output).contains(&"  Timestamps: 1 conversations out of order, respaced".to_string()));
+
+    // The stray answer moves up to the conversation's update time
+    let messages = fs::read_to_string(out.join("2023-11-14/conv-meta/messages.jsonl")).unwrap();
+    let timestamps: Vec<String> = messages
+        .lines()
+        .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap()["timestamp"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(timestamps, [
+        "2023-11-14T22:13:21+00:00",
+        "2023-11-14T22:13:30+00:00",
+        "2023-11-14T22:13:40+00:00",
+        "2023-11-14T22:13:50+00:00",
+    ]);
+}
@@ -1,10 +1,11 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use clap::Parser;
+use continuum_core::Saved;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 // ============================================================================
 // Skill matching
@@ -102,7 +103,6 @@ struct ClaudeConversation {
 
 #[derive(Debug, Deserialize)]
 struct ClaudeMessage {
-    uuid: String,
     text: String,
     sender: String,
     created_at: String,
@@ -156,10 +156,19 @@ fn main() -> Result<()> {
     // Process each conversation
     let mut success_count = 0;
     let mut error_count = 0;
+    let mut skipped_count = 0;
+    let mut merged_count = 0;
 
     for (idx, conversation) in conversations.iter().enumerate() {
         match process_conversation(conversation, &output_dir) {
-            Ok(_) => success_count += 1,
+            Ok(saved) => {
+                success_count += 1;
+                match saved {
+                    Some(Saved::Skipped(_)) => skipped_count += 1,
+                    Some(Saved::Merged { .. }) => merged_count += 1,
+                    _ => {}
+                }
+            }
             Err(e) => {
                 eprintln!("Error processing conversation {}: {}", idx + 1, e);
                 error_count += 1;
@@ -174,38 +183,30 @@ fn main() -> Result<()> {
     println!("\nImport complete!");
     println!("  Success: {}", success_count);
     println!("  Errors:  {}", error_count);
+    if skipped_count + merged_count > 0 {
+        println!("  Duplicates: {} skipped, {} merged", skipped_count, merged_count);
+    }
     println!("  Output:  {:?}", output_dir);
 
     Ok(())
 }
 
-fn process_conversation(conv: &ClaudeConversation, output_dir: &PathBuf) -> Result<()> {
+/// Write one conversation, or `None` if it has no messages. Conversations
+/// already held by another session on the same day are skipped or merged
+/// into it.
+fn process_conversation(conv: &ClaudeConversation, output_dir: &Path) -> Result<Option<Saved>> {
     // Parse the created_at timestamp
     let datetime: DateTime<Utc> = conv.created_at.parse()
         .context("Invalid timestamp")?;
     let date_str = datetime.format("%Y-%m-%d").to_string();
 
-    // Create session directory
-    let session_dir = output_dir.join(&date_str).join(&conv.uuid);
-    fs::create_dir_all(&session_dir)
-        .with_context(|| format!("Failed to create {:?}", session_dir))?;
-
     // Convert messages
     let messages = convert_messages(&conv.chat_messages)?;
 
     if messages.is_empty() {
-        return Ok(()); // Skip empty conversations
+        return Ok(None); // Skip empty conversations
     }
 
-    // Write messages.jsonl
-    let messages_path = session_dir.join("messages.jsonl");
-    let mut jsonl_content = String::new();
-    for msg in &messages {
-        jsonl_content.push_str(&serde_json::to_string(msg)?);
-        jsonl_content.push('\n');
-    }
-    fs::write(&messages_path, jsonl_content)?;
-
     // Parse update time
     let end_time: Option<DateTime<Utc>> = conv.updated_at.parse().ok();
 
@@ -225,11 +226,9 @@ fn process_conversation(conv: &ClaudeConversation, output_dir: &PathBuf) -> Resu
         skills,
     };
 
-    let session_path = session_dir.join("session.json");
-    let session_json = serde_json::to_string_pretty(&session)?;
-    fs::write(&session_path, session_json)?;
-
-    Ok(())
+    // Writes messages.jsonl, session.json and hashes.json
+    let saved = continuum_core::save_session(&output_dir.join(&date_str), &conv.uuid, &messages, &session)?;
+    Ok(Some(saved))
 }
 
 fn convert_messages(claude_messages: &[ClaudeMessage]) -> Result<Vec<ContinuumMessage>> {
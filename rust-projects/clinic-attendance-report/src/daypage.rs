@@ -0,0 +1,278 @@
+//! DayPage `clinic::` blocks.
+//!
+//! A block is the `clinic::` line followed by one entry per line up to the
+//! next blank line:
+//!
+//! ```text
+//! clinic::
+//! - ✓ EB88 07:50 insurer
+//! - [x] AB79+CD80 09:00
+//! - SZ84 10:30 DNA
+//! - JH91 12:45 deferred
+//! ```
+//!
+//! Days with more than one clinic have several blocks, either repeated
+//! `clinic::` keys or labelled ones like `clinic.am::` and `clinic.pm::`.
+//!
+//! Entries are hand-typed, so parsing is tolerant: status comes from a
+//! leading mark (`✓`, `✗`, `?`, or a `[x]`-style checkbox) or a status
+//! word anywhere in the line, and client code and time are picked out of
+//! whatever else is there.
+
+use crate::{Entry, Status};
+
+/// One clinic block: `label` is the `<suffix>` of a `clinic.<suffix>::` key
+#[derive(Debug)]
+pub struct Block {
+    pub label: Option<String>,
+    pub entries: Vec<Entry>,
+}
+
+/// Status words that look like client codes but aren't
+const STATUS_WORDS: &[&str] = &["DNA", "LC"];
+
+/// Entries of every clinic block in a DayPage, in page order.
+pub fn extract_and_parse(content: &str) -> Vec<Entry> {
+    extract_blocks(content).into_iter().flat_map(|b| b.entries).collect()
+}
+
+/// Clinic blocks in a DayPage, in page order. Each runs from its key line
+/// to the next blank line or block key; other `key::` fields inside it
+/// (like `clinic.summary::`) are not entries.
+pub fn extract_blocks(content: &str) -> Vec<Block> {
+    let mut blocks: Vec<Block> = Vec::new();
+    let mut in_block = false;
+
+    for line in content.lines() {
+        if let Some(label) = block_key(line) {
+            blocks.push(Block { label, entries: Vec::new() });
+            in_block = true;
+        } else if line.trim().is_empty() {
+            in_block = false;
+        } else if in_block && !line.contains("::") {
+            if let Some(block) = blocks.last_mut() {
+                block.entries.push(parse_entry(line));
+            }
+        }
+    }
+    blocks
+}
+
+/// `Some(None)` for a `clinic::` line, `Some(Some(label))` for
+/// `clinic.<label>::`; `clinic.summary::` is the tally, not a block.
+pub fn block_key(line: &str) -> Option<Option<String>> {
+    let rest = line.trim_start().strip_prefix("clinic")?;
+    if rest.starts_with("::") {
+        return Some(None);
+    }
+    let (label, _) = rest.strip_prefix('.')?.split_once("::")?;
+    let valid = !label.is_empty() && label.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_');
+    (valid && label != "summary").then(|| Some(label.to_string()))
+}
+
+fn parse_entry(line: &str) -> Entry {
+    let mut rest = line.trim();
+    rest = rest.strip_prefix("- ").or_else(|| rest.strip_prefix("* ")).unwrap_or(rest).trim_start();
+
+    let mut marked = None;
+    for (mark, status) in [
+        ("[x]", Status::Attended),
+        ("[X]", Status::Attended),
+        ("[ ]", Status::Pending),
+        ("[-]", Status::Cancelled),
+        ("[>]", Status::Deferred),
+        ("\u{2713}", Status::Attended),
+        ("\u{2714}", Status::Attended),
+        ("\u{2717}", Status::DnaLc),
+        ("\u{2718}", Status::DnaLc),
+        ("?", Status::Pending),
+    ] {
+        if let Some(after) = rest.strip_prefix(mark) {
+            rest = after.trim_start();
+            marked = Some(status);
+            break;
+        }
+    }
+
+    let status = status_word(rest).or(marked).unwrap_or(Status::Pending);
+    Entry { status, content: rest.to_string() }
+}
+
+fn status_word(content: &str) -> Option<Status> {
+    content.split_whitespace().find_map(|word| {
+        let word = word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase();
+        match word.as_str() {
+            "dna" | "lc" => Some(Status::DnaLc),
+            "cancelled" | "canceled" => Some(Status::Cancelled),
+            "deferred" => Some(Status::Deferred),
+            _ => None,
+        }
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rate {
+    Insurer,
+    SelfPay,
+    Reduced,
+}
+
+impl Rate {
+    pub fn label(self) -> &'static str {
+        match self {
+            Rate::Insurer => "insurer",
+            Rate::SelfPay => "self",
+            Rate::Reduced => "reduced",
+        }
+    }
+}
+
+/// Client code, time and rate picked out of an entry's content.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Fields {
+    /// `AB79`, or `AB79+CD80` for a joint session
+    pub client: Option<String>,
+    /// Start time as `HH:MM`
+    pub time: Option<String>,
+    pub rate: Rate,
+}
+
+pub fn parse_fields(content: &str) -> Fields {
+    // Let "AB79 & CD80" read as one joint token
+    let joined = content.replace(" & ", "&").replace(" + ", "+").replace(" / ", "/");
+    let tokens: Vec<&str> = joined
+        .split_whitespace()
+        .map(|t| t.trim_matches(|c: char| matches!(c, ',' | ';' | '(' | ')')))
+        .collect();
+
+    let client = tokens.iter().find_map(|t| client_code(t));
+    let time = tokens.iter().find_map(|t| start_time(t));
+
+    let lower = content.to_lowercase();
+    let rate = if lower.contains("insurer") {
+        Rate::Insurer
+    } else if lower.contains("reduced") {
+        Rate::Reduced
+    } else {
+        Rate::SelfPay
+    };
+
+    Fields { client, time, rate }
+}
+
+/// `AB79`, `AO`, or joint `AB79+CD80` / `AB79&CD80` / `AB79/CD80`
+fn client_code(token: &str) -> Option<String> {
+    let parts: Vec<&str> = token.split(['+', '&', '/']).collect();
+    parts.iter().all(|p| is_code(p)).then(|| parts.join("+"))
+}
+
+fn is_code(s: &str) -> bool {
+    let letters = s.chars().take_while(|c| c.is_ascii_uppercase()).count();
+    let digits = &s[letters..];
+    (2..=4).contains(&letters)
+        && digits.len() <= 3
+        && digits.chars().all(|c| c.is_ascii_digit())
+        && !STATUS_WORDS.contains(&s)
+}
+
+/// `07:50`, `7:50`, `7.50`, or the start of a range like `07:50-08:40`
+fn start_time(token: &str) -> Option<String> {
+    let start = token.split(['-', '\u{2013}']).next()?;
+    let (hours, minutes) = start.split_once([':', '.'])?;
+    let hours: u32 = hours.parse().ok()?;
+    if minutes.len() != 2 {
+        return None;
+    }
+    let minutes: u32 = minutes.parse().ok()?;
+    (hours < 24 && minutes < 60).then(|| format!("{:02}:{:02}", hours, minutes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_ends_at_blank_line_and_skips_fields() {
+        let page = "# 2026-03-02\n\nclinic::\n- \u{2713} EB88 07:50 insurer\n- SZ84 09:35 DNA\n\
+                    clinic.summary:: 1/2 attended\n\n- not an entry\n";
+        let entries = extract_and_parse(page);
+        assert_eq!(entries.len(), 2);
+        assert!(matches!(entries[0].status, Status::Attended));
+        assert_eq!(entries[0].content, "EB88 07:50 insurer");
+        assert!(matches!(entries[1].status, Status::DnaLc));
+    }
+
+    #[test]
+    fn every_block_in_page_order() {
+        let page = std::fs::read_to_string(
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/daypages/2026-05-04.md"),
+        )
+        .unwrap();
+        let blocks = extract_blocks(&page);
+        assert_eq!(blocks.len(), 2);
+        assert!(blocks.iter().all(|b| b.label.is_none()));
+        assert_eq!(blocks[0].entries.len(), 2);
+        assert_eq!(blocks[1].entries[0].content, "BA90 18:00 insurer");
+        assert_eq!(extract_and_parse(&page).len(), 4);
+    }
+
+    #[test]
+    fn labelled_blocks() {
+        // A block key ends the previous block even without a blank line
+        let page = "clinic.am::\n- \u{2713} EB88 07:50\nclinic.pm::\n- SZ84 18:30 DNA\n\
+                    clinic.summary:: 1/2 attended\n";
+        let blocks = extract_blocks(page);
+        let labels: Vec<Option<&str>> = blocks.iter().map(|b| b.label.as_deref()).collect();
+        assert_eq!(labels, vec![Some("am"), Some("pm")]);
+        assert_eq!(blocks[0].entries.len(), 1);
+        assert_eq!(blocks[1].entries.len(), 1);
+        assert!(matches!(blocks[1].entries[0].status, Status::DnaLc));
+    }
+
+    #[test]
+    fn block_keys() {
+        assert_eq!(block_key("clinic::"), Some(None));
+        assert_eq!(block_key("  clinic.am::"), Some(Some("am".to_string())));
+        assert_eq!(block_key("clinic.evening-2::"), Some(Some("evening-2".to_string())));
+        assert_eq!(block_key("clinic.summary:: 2/2 attended"), None);
+        assert_eq!(block_key("clinic.:: x"), None);
+        assert_eq!(block_key("clinical notes:: x"), None);
+        assert_eq!(block_key("- clinic:: x"), None);
+    }
+
+    #[test]
+    fn no_block_no_entries() {
+        assert!(extract_and_parse("# 2026-03-03\n\nJust notes\n").is_empty());
+    }
+
+    #[test]
+    fn status_marks_and_words() {
+        let page = "clinic::\n- [x] AB79 08:00\n- [ ] CC71 09:00\n- JH91 12:45 deferred\n\
+                    - \u{2717} AO 13:00 LC\n- BA90 14:00 cancelled\n";
+        let statuses: Vec<Status> = extract_and_parse(page).into_iter().map(|e| e.status).collect();
+        assert!(matches!(
+            statuses[..],
+            [Status::Attended, Status::Pending, Status::Deferred, Status::DnaLc, Status::Cancelled]
+        ));
+    }
+
+    #[test]
+    fn fields_tolerate_layout() {
+        assert_eq!(
+            parse_fields("EB88 07:50 insurer"),
+            Fields { client: Some("EB88".into()), time: Some("07:50".into()), rate: Rate::Insurer }
+        );
+        assert_eq!(
+            parse_fields("7.05 AB79 & CD80 reduced"),
+            Fields { client: Some("AB79+CD80".into()), time: Some("07:05".into()), rate: Rate::Reduced }
+        );
+        assert_eq!(
+            parse_fields("DNA AO 13:00-13:50"),
+            Fields { client: Some("AO".into()), time: Some("13:00".into()), rate: Rate::SelfPay }
+        );
+        assert_eq!(
+            parse_fields("phone call re: invoice"),
+            Fields { client: None, time: None, rate: Rate::SelfPay }
+        );
+    }
+}
@@ -4,6 +4,11 @@ use clap::Parser;
 use serde::Deserialize;
 use std::path::PathBuf;
 
+use daypage::Block;
+
+mod daypage;
+mod monthly;
+
 #[derive(Parser)]
 #[command(about = "Generate attendance report from PracticeForge session data")]
 struct Cli {
@@ -14,6 +19,24 @@ struct Cli {
     /// Print message but don't save or notify
     #[arg(long)]
     dry_run: bool,
+
+    /// Write the summary line into the DayPage as `clinic.summary::`
+    #[arg(long)]
+    append_summary: bool,
+
+    /// Export a month of DayPage clinic blocks (YYYY-MM); use with --csv
+    #[arg(long, value_parser = parse_month, requires = "csv")]
+    month: Option<NaiveDate>,
+
+    /// CSV file to write the --month export to
+    #[arg(long, requires = "month")]
+    csv: Option<PathBuf>,
+}
+
+/// `YYYY-MM` as the first day of that month.
+fn parse_month(s: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(&format!("{}-01", s), "%Y-%m-%d")
+        .map_err(|_| format!("expected YYYY-MM, got '{}'", s))
 }
 
 #[derive(Debug)]
@@ -22,6 +45,19 @@ enum Status {
     DnaLc,
     Cancelled,
     Pending,
+    Deferred,
+}
+
+impl Status {
+    fn label(&self) -> &'static str {
+        match self {
+            Status::Attended => "attended",
+            Status::DnaLc => "dna-lc",
+            Status::Cancelled => "cancelled",
+            Status::Pending => "pending",
+            Status::Deferred => "deferred",
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -43,6 +79,7 @@ struct SessionClient {
     #[serde(default)]
     time: String,
     #[serde(default)]
+    #[allow(dead_code)] // Part of the session format, not used in the report
     end_time: String,
     #[serde(default)]
     status: String,
@@ -53,22 +90,18 @@ struct SessionClient {
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
-    let date = cli.date.unwrap_or_else(|| Local::now().date_naive());
-    let session_path = get_session_path(&date);
-
-    let content = std::fs::read_to_string(&session_path)
-        .with_context(|| format!("No session file for {}: {}", date, session_path.display()))?;
-
-    let session: Session = serde_json::from_str(&content)
-        .with_context(|| format!("Failed to parse session file: {}", session_path.display()))?;
+    if let (Some(month), Some(csv_path)) = (cli.month, &cli.csv) {
+        return export_month(month, csv_path);
+    }
 
-    let entries = parse_session(&session);
+    let date = cli.date.unwrap_or_else(|| Local::now().date_naive());
+    let blocks = load_blocks(&date)?;
 
-    if entries.is_empty() {
+    if blocks.iter().all(|b| b.entries.is_empty()) {
         bail!("No clients in session for {}", date);
     }
 
-    let message = format_message(&date, &entries);
+    let message = format_report(&date, &blocks);
 
     println!("{}", message);
 
@@ -76,9 +109,46 @@ fn main() -> Result<()> {
         save_and_notify(&date, &message)?;
     }
 
+    if cli.append_summary {
+        let summary = summary_line(blocks.iter().flat_map(|b| &b.entries));
+        if cli.dry_run {
+            eprintln!("Would write to DayPage: {} {}", SUMMARY_KEY, summary);
+        } else {
+            append_summary(&date, &summary)?;
+        }
+    }
+
     Ok(())
 }
 
+/// The day's clinics: the PracticeForge session as one block, or, for days
+/// without a session file, the DayPage's `clinic::` blocks.
+fn load_blocks(date: &NaiveDate) -> Result<Vec<Block>> {
+    let session_path = get_session_path(date);
+    if session_path.exists() {
+        let content = std::fs::read_to_string(&session_path)
+            .with_context(|| format!("Failed to read session file: {}", session_path.display()))?;
+        let session: Session = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse session file: {}", session_path.display()))?;
+        return Ok(vec![Block { label: None, entries: parse_session(&session) }]);
+    }
+
+    let daypage_path = get_daypage_path(date);
+    let content = std::fs::read_to_string(&daypage_path).with_context(|| {
+        format!(
+            "No session file for {} ({}) and no DayPage at {}",
+            date,
+            session_path.display(),
+            daypage_path.display()
+        )
+    })?;
+    let blocks = daypage::extract_blocks(&content);
+    if blocks.is_empty() {
+        bail!("No session file for {} and no clinic block in {}", date, daypage_path.display());
+    }
+    Ok(blocks)
+}
+
 /// PracticeForge session file path.
 fn get_session_path(date: &NaiveDate) -> PathBuf {
     dirs::data_local_dir()
@@ -111,34 +181,87 @@ fn parse_session(session: &Session) -> Vec<Entry> {
 }
 
 fn format_message(date: &NaiveDate, entries: &[Entry]) -> String {
-    let day_str = date.format("%a %-e %b").to_string();
+    let mut lines = vec![title_line(date), String::new()];
+    lines.extend(entry_lines(entries));
+    lines.push(String::new());
+    lines.push(summary_line(entries));
 
-    let mut lines = vec![format!("{} — Attendance", day_str)];
+    lines.join("\n")
+}
+
+/// The day's report. One clinic renders as `format_message`; several get a
+/// `[label]` section each, with its own tally, and a combined total.
+fn format_report(date: &NaiveDate, blocks: &[Block]) -> String {
+    if let [block] = blocks {
+        return format_message(date, &block.entries);
+    }
+
+    let mut lines = vec![title_line(date)];
+    for (i, block) in blocks.iter().enumerate() {
+        let label = block.label.clone().unwrap_or_else(|| format!("clinic {}", i + 1));
+        lines.push(String::new());
+        lines.push(format!("[{}]", label));
+        lines.extend(entry_lines(&block.entries));
+        lines.push(summary_line(&block.entries));
+    }
     lines.push(String::new());
+    lines.push(format!("Total: {}", summary_line(blocks.iter().flat_map(|b| &b.entries))));
+
+    lines.join("\n")
+}
+
+fn title_line(date: &NaiveDate) -> String {
+    format!("{} — Attendance", date.format("%a %-e %b"))
+}
+
+/// Entries by start time, since DayPages aren't always typed in order.
+/// Entries without a time keep their relative order, after the timed ones.
+fn chronological(entries: &[Entry]) -> Vec<&Entry> {
+    let mut sorted: Vec<&Entry> = entries.iter().collect();
+    sorted.sort_by_cached_key(|entry| {
+        let time = daypage::parse_fields(&entry.content).time;
+        (time.is_none(), time)
+    });
+    sorted
+}
+
+/// `✓ EB88 07:50 insurer` per entry, in time order; cancelled and moved
+/// sessions are left out
+fn entry_lines(entries: &[Entry]) -> Vec<String> {
+    chronological(entries)
+        .into_iter()
+        .filter_map(|entry| {
+            let marker = match entry.status {
+                Status::Attended => "\u{2713}",
+                Status::DnaLc => "\u{2717}",
+                Status::Cancelled | Status::Deferred => return None,
+                Status::Pending => "?",
+            };
+            Some(format!("{} {}", marker, entry.content))
+        })
+        .collect()
+}
 
+/// The tally line, e.g. `3/4 attended · 1 DNA/LC · 2 insurer`.
+fn summary_line<'a>(entries: impl IntoIterator<Item = &'a Entry>) -> String {
     let mut attended = 0u32;
     let mut dna_lc = 0u32;
-    let mut cancelled = 0u32;
     let mut pending = 0u32;
     let mut insurer_count = 0u32;
 
     for entry in entries {
-        let marker = match entry.status {
-            Status::Attended => { attended += 1; "\u{2713}" }
-            Status::DnaLc => { dna_lc += 1; "\u{2717}" }
-            Status::Cancelled => { cancelled += 1; continue }  // Skip cancelled from report
-            Status::Pending => { pending += 1; "?" }
-        };
+        match entry.status {
+            Status::Attended => attended += 1,
+            Status::DnaLc => dna_lc += 1,
+            Status::Cancelled | Status::Deferred => continue,
+            Status::Pending => pending += 1,
+        }
 
         if entry.content.contains("insurer") {
             insurer_count += 1;
         }
-
-        lines.push(format!("{} {}", marker, entry.content));
     }
 
-    lines.push(String::new());
-
     let total = attended + dna_lc + pending;
     let mut summary = vec![format!("{}/{} attended", attended, total)];
 
@@ -146,9 +269,84 @@ fn format_message(date: &NaiveDate, entries: &[Entry]) -> String {
     if pending > 0 { summary.push(format!("{} unresolved", pending)); }
     if insurer_count > 0 { summary.push(format!("{} insurer", insurer_count)); }
 
-    lines.push(summary.join(" \u{00b7} "));
+    summary.join(" \u{00b7} ")
+}
 
-    lines.join("\n")
+const SUMMARY_KEY: &str = "clinic.summary::";
+
+fn get_daypages_dir() -> PathBuf {
+    dirs::home_dir()
+        .expect("Could not find home directory")
+        .join("Forge/NapierianLogs/DayPages")
+}
+
+/// DayPage path for a given date.
+fn get_daypage_path(date: &NaiveDate) -> PathBuf {
+    get_daypages_dir().join(format!("{}.md", date.format("%Y-%m-%d")))
+}
+
+/// Write every clinic entry in the month's DayPages to a CSV file.
+fn export_month(month: NaiveDate, csv_path: &std::path::Path) -> Result<()> {
+    let days = monthly::collect_month(&get_daypages_dir(), month)?;
+    if days.is_empty() {
+        bail!("No clinic blocks in DayPages for {}", month.format("%Y-%m"));
+    }
+
+    let file = std::fs::File::create(csv_path)
+        .with_context(|| format!("Failed to create {}", csv_path.display()))?;
+    monthly::write_csv(file, &days)?;
+
+    let count: usize = days.iter().map(|(_, entries)| entries.len()).sum();
+    eprintln!("Wrote {} entries from {} days to {}", count, days.len(), csv_path.display());
+    Ok(())
+}
+
+/// Write the summary line into the date's DayPage, replacing any earlier one.
+fn append_summary(date: &NaiveDate, summary: &str) -> Result<()> {
+    let path = get_daypage_path(date);
+
+    let content = if path.exists() {
+        std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read DayPage: {}", path.display()))?
+    } else {
+        format!("# {}\n\n## Backlinks\n", date.format("%Y-%m-%d"))
+    };
+
+    let new_content = upsert_summary(&content, summary);
+
+    std::fs::write(&path, new_content)
+        .with_context(|| format!("Failed to write DayPage: {}", path.display()))?;
+
+    eprintln!("Summary written to: {}", path.display());
+    Ok(())
+}
+
+/// Place `clinic.summary:: <summary>` in DayPage content. An existing summary
+/// line is replaced in place; otherwise it goes directly after the last
+/// clinic block, or before `## Backlinks`, or at the end.
+fn upsert_summary(content: &str, summary: &str) -> String {
+    let summary_line = format!("{} {}", SUMMARY_KEY, summary);
+    let mut lines: Vec<String> = content.lines().map(String::from).collect();
+
+    if let Some(i) = lines.iter().position(|l| l.trim_start().starts_with(SUMMARY_KEY)) {
+        lines[i] = summary_line;
+    } else if let Some(start) = lines.iter().rposition(|l| daypage::block_key(l).is_some()) {
+        // The block runs until the next blank line
+        let end = lines[start + 1..]
+            .iter()
+            .position(|l| l.trim().is_empty())
+            .map_or(lines.len(), |offset| start + 1 + offset);
+        lines.insert(end, summary_line);
+    } else if let Some(i) = lines.iter().position(|l| l.starts_with("## Backlinks")) {
+        lines.insert(i, String::new());
+        lines.insert(i, summary_line);
+    } else {
+        lines.push(summary_line);
+    }
+
+    let mut result = lines.join("\n");
+    result.push('\n');
+    result
 }
 
 fn save_and_notify(date: &NaiveDate, message: &str) -> Result<()> {
@@ -259,4 +457,109 @@ mod tests {
         assert!(msg.contains("1 DNA/LC"));
         assert!(msg.contains("1 insurer"));
     }
+
+    #[test]
+    fn test_entries_sorted_by_time() {
+        let date = NaiveDate::from_ymd_opt(2026, 4, 16).unwrap();
+        let entries = vec![
+            Entry { status: Status::Attended, content: "CC71 13:20".to_string() },
+            Entry { status: Status::Pending, content: "AO (time tbc)".to_string() },
+            Entry { status: Status::DnaLc, content: "SZ84 9:35 DNA".to_string() },
+            Entry { status: Status::Attended, content: "AB79 07:45-08:35 insurer".to_string() },
+            Entry { status: Status::Attended, content: "JH91".to_string() },
+            Entry { status: Status::Attended, content: "BA90 11:00".to_string() },
+        ];
+        assert_eq!(
+            format_message(&date, &entries),
+            "Thu 16 Apr — Attendance\n\n\u{2713} AB79 07:45-08:35 insurer\n\u{2717} SZ84 9:35 DNA\n\
+             \u{2713} BA90 11:00\n\u{2713} CC71 13:20\n? AO (time tbc)\n\u{2713} JH91\n\n\
+             4/6 attended \u{b7} 1 DNA/LC \u{b7} 1 unresolved \u{b7} 1 insurer"
+        );
+    }
+
+    fn fixture_blocks(day: &str) -> Vec<Block> {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/daypages")
+            .join(format!("{}.md", day));
+        daypage::extract_blocks(&std::fs::read_to_string(path).unwrap())
+    }
+
+    #[test]
+    fn test_single_block_report_unchanged() {
+        for day in ["2026-03-02", "2026-03-04", "2026-03-31", "2026-04-01"] {
+            let date = NaiveDate::parse_from_str(day, "%Y-%m-%d").unwrap();
+            let blocks = fixture_blocks(day);
+            assert_eq!(blocks.len(), 1);
+            assert_eq!(format_report(&date, &blocks), format_message(&date, &blocks[0].entries));
+        }
+        let date = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        assert_eq!(
+            format_report(&date, &fixture_blocks("2026-03-02")),
+            "Mon 2 Mar — Attendance\n\n\u{2713} EB88 07:50 insurer\n\u{2713} AB79+CD80 09:00 joint\n\
+             \u{2717} SZ84 10:30 DNA\n\n2/3 attended \u{b7} 1 DNA/LC \u{b7} 1 insurer"
+        );
+    }
+
+    #[test]
+    fn test_two_block_report() {
+        let date = NaiveDate::from_ymd_opt(2026, 5, 4).unwrap();
+        assert_eq!(
+            format_report(&date, &fixture_blocks("2026-05-04")),
+            "Mon 4 May — Attendance\n\
+             \n[clinic 1]\n\u{2713} EB88 07:50 insurer\n\u{2717} SZ84 09:35 DNA\n1/2 attended \u{b7} 1 DNA/LC \u{b7} 1 insurer\n\
+             \n[clinic 2]\n\u{2713} BA90 18:00 insurer\n? AO 19:00\n1/2 attended \u{b7} 1 unresolved \u{b7} 1 insurer\n\
+             \nTotal: 2/4 attended \u{b7} 1 DNA/LC \u{b7} 1 unresolved \u{b7} 2 insurer"
+        );
+    }
+
+    #[test]
+    fn test_labelled_block_report() {
+        let date = NaiveDate::from_ymd_opt(2026, 5, 5).unwrap();
+        assert_eq!(
+            format_report(&date, &fixture_blocks("2026-05-05")),
+            "Tue 5 May — Attendance\n\
+             \n[am]\n\u{2713} AB79 08:00\n\u{2713} CC71 09:00 insurer\n2/2 attended \u{b7} 1 insurer\n\
+             \n[pm]\n\u{2713} JH91 17:30 reduced\n1/1 attended\n\
+             \nTotal: 3/3 attended \u{b7} 1 insurer"
+        );
+    }
+
+    #[test]
+    fn test_upsert_summary_after_last_labelled_block() {
+        let content = "clinic.am::\n- AB79\n\nclinic.pm::\n- JH91\n\n## Backlinks\n";
+        assert_eq!(
+            upsert_summary(content, "2/2 attended"),
+            "clinic.am::\n- AB79\n\nclinic.pm::\n- JH91\nclinic.summary:: 2/2 attended\n\n## Backlinks\n"
+        );
+    }
+
+    #[test]
+    fn test_upsert_summary_after_clinic_block() {
+        let content = "# 2026-04-16\n\nclinic::\n- AB79 07:45\n- SZ84 09:35\n\nOther notes\n";
+        let result = upsert_summary(content, "2/2 attended");
+        assert_eq!(
+            result,
+            "# 2026-04-16\n\nclinic::\n- AB79 07:45\n- SZ84 09:35\nclinic.summary:: 2/2 attended\n\nOther notes\n"
+        );
+    }
+
+    #[test]
+    fn test_upsert_summary_replaces_existing() {
+        let content = "clinic::\n- AB79\nclinic.summary:: 1/2 attended\n\n## Backlinks\n";
+        let once = upsert_summary(content, "2/2 attended");
+        let twice = upsert_summary(&once, "2/2 attended");
+        assert_eq!(once, twice);
+        assert_eq!(once.matches(SUMMARY_KEY).count(), 1);
+        assert!(once.contains("clinic.summary:: 2/2 attended"));
+        assert!(!once.contains("1/2"));
+    }
+
+    #[test]
+    fn test_upsert_summary_without_clinic_block() {
+        let content = "# 2026-04-16\n\nNotes\n\n## Backlinks\n- [[Other]]\n";
+        let result = upsert_summary(content, "3/3 attended");
+        let summary_pos = result.find(SUMMARY_KEY).unwrap();
+        assert!(summary_pos < result.find("## Backlinks").unwrap());
+        assert!(result.contains("Notes\n\nclinic.summary:: 3/3 attended\n\n## Backlinks"));
+    }
 }
@@ -0,0 +1,106 @@
+//! Month-end attendance export for reconciling against invoices.
+
+use crate::daypage::{extract_and_parse, parse_fields};
+use crate::Entry;
+use anyhow::{Context, Result};
+use chrono::{Datelike, NaiveDate};
+use serde::Serialize;
+use std::path::Path;
+
+#[derive(Serialize)]
+struct CsvRow<'a> {
+    date: String,
+    client: &'a str,
+    time: &'a str,
+    status: &'a str,
+    rate: &'a str,
+    content: &'a str,
+}
+
+/// Clinic entries of every DayPage in `month` (any day within it), in date
+/// order. Days with no page or no `clinic::` block are left out.
+pub fn collect_month(daypages_dir: &Path, month: NaiveDate) -> Result<Vec<(NaiveDate, Vec<Entry>)>> {
+    let first = month.with_day(1).unwrap();
+    let mut days = Vec::new();
+
+    for date in first.iter_days().take_while(|d| d.month() == first.month()) {
+        let path = daypages_dir.join(format!("{}.md", date.format("%Y-%m-%d")));
+        if !path.exists() {
+            continue;
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read DayPage: {}", path.display()))?;
+        let entries = extract_and_parse(&content);
+        if !entries.is_empty() {
+            days.push((date, entries));
+        }
+    }
+
+    Ok(days)
+}
+
+/// Write one CSV row per entry: date, client, time, status, rate, content.
+pub fn write_csv<W: std::io::Write>(writer: W, days: &[(NaiveDate, Vec<Entry>)]) -> Result<()> {
+    let mut csv = csv::Writer::from_writer(writer);
+
+    for (date, entries) in days {
+        for entry in entries {
+            let fields = parse_fields(&entry.content);
+            csv.serialize(CsvRow {
+                date: date.format("%Y-%m-%d").to_string(),
+                client: fields.client.as_deref().unwrap_or(""),
+                time: fields.time.as_deref().unwrap_or(""),
+                status: entry.status.label(),
+                rate: fields.rate.label(),
+                content: &entry.content,
+            })?;
+        }
+    }
+
+    csv.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture_dir() -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/daypages")
+    }
+
+    #[test]
+    fn fixture_month_to_csv() {
+        let month = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let days = collect_month(&fixture_dir(), month).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("2026-03.csv");
+        write_csv(std::fs::File::create(&path).unwrap(), &days).unwrap();
+
+        let expected = "\
+date,client,time,status,rate,content
+2026-03-02,EB88,07:50,attended,insurer,EB88 07:50 insurer
+2026-03-02,AB79+CD80,09:00,attended,self,AB79+CD80 09:00 joint
+2026-03-02,SZ84,10:30,dna-lc,self,SZ84 10:30 DNA
+2026-03-04,JH91,12:45,deferred,reduced,\"JH91 12:45 reduced, deferred to 11th\"
+2026-03-04,BA90,13:20,attended,insurer,\"BA90 13:20 insurer \"\"pre-auth pending\"\"\"
+2026-03-31,AO,08:15,pending,self,AO 8.15
+";
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), expected);
+    }
+
+    #[test]
+    fn every_clinic_block_exported() {
+        let month = NaiveDate::from_ymd_opt(2026, 5, 1).unwrap();
+        let days = collect_month(&fixture_dir(), month).unwrap();
+        let counts: Vec<(u32, usize)> = days.iter().map(|(d, e)| (d.day(), e.len())).collect();
+        assert_eq!(counts, vec![(4, 4), (5, 4)]);
+    }
+
+    #[test]
+    fn empty_month_has_no_days() {
+        let month = NaiveDate::from_ymd_opt(2026, 2, 1).unwrap();
+        assert!(collect_month(&fixture_dir(), month).unwrap().is_empty());
+    }
+}
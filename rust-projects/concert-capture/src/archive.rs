@@ -65,6 +65,8 @@ mod tests {
             performers: vec!["The English Concert".to_string()],
             works: vec![],
             venue: crate::html::Venue::WigmoreHall,
+            venue_name: None,
+            is_gig: false,
         };
 
         let filename = generate_filename(&concert);
@@ -78,6 +80,8 @@ mod tests {
             performers: vec![],
             works: vec![],
             venue: crate::html::Venue::WigmoreHall,
+            venue_name: None,
+            is_gig: false,
         };
 
         let filename = generate_filename(&concert);
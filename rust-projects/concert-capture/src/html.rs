@@ -19,6 +19,10 @@ pub struct Concert {
     pub performers: Vec<String>,
     pub works: Vec<Work>,
     pub venue: Venue,
+    /// Venue as named on the page, when it isn't one of the known venues' own sites
+    pub venue_name: Option<String>,
+    /// Read from a gig listing's JSON-LD rather than a venue's programme
+    pub is_gig: bool,
 }
 
 #[derive(Debug)]
@@ -29,6 +33,13 @@ pub struct Work {
 
 pub fn parse_concert(html: &str) -> Result<Concert> {
     let venue = detect_venue(html);
+    // Not a venue's own page: a saved Songkick/Bandsintown listing carries the
+    // gig as JSON-LD
+    if venue == Venue::Unknown {
+        if let Some(gig) = crate::jsonld::parse_gig(html) {
+            return Ok(gig);
+        }
+    }
     let document = Html::parse_document(html);
 
     let date = extract_date(html, venue)?;
@@ -40,6 +51,8 @@ pub fn parse_concert(html: &str) -> Result<Concert> {
         performers,
         works,
         venue,
+        venue_name: None,
+        is_gig: false,
     })
 }
 
@@ -59,6 +72,28 @@ fn detect_venue(html: &str) -> Venue {
     }
 }
 
+/// Known venue from a listing's venue name ("Barbican Hall", "Wigmore Hall")
+pub fn venue_from_name(name: &str) -> Venue {
+    let lower = name.to_lowercase();
+    if lower.contains("wigmore") {
+        Venue::WigmoreHall
+    } else if lower.contains("southbank")
+        || lower.contains("royal festival hall")
+        || lower.contains("queen elizabeth hall")
+        || lower.contains("purcell room")
+    {
+        Venue::SouthbankCentre
+    } else if lower.contains("kings place") {
+        Venue::KingsPlace
+    } else if lower.contains("barbican") {
+        Venue::Barbican
+    } else if lower.contains("ilminster") {
+        Venue::IlminsterArts
+    } else {
+        Venue::Unknown
+    }
+}
+
 fn extract_date(html: &str, venue: Venue) -> Result<NaiveDate> {
     match venue {
         Venue::WigmoreHall => extract_date_wigmore(html),
@@ -617,6 +652,21 @@ mod tests {
         assert_eq!(date, NaiveDate::from_ymd_opt(2026, 1, 27).unwrap());
     }
 
+    #[test]
+    fn test_venue_from_name() {
+        assert_eq!(venue_from_name("Barbican Hall"), Venue::Barbican);
+        assert_eq!(venue_from_name("Royal Festival Hall, Southbank Centre"), Venue::SouthbankCentre);
+        assert_eq!(venue_from_name("O2 Academy Brixton"), Venue::Unknown);
+    }
+
+    #[test]
+    fn test_parse_concert_falls_back_to_json_ld() {
+        let html = include_str!("../tests/fixtures/songkick-event.html");
+        let concert = parse_concert(html).unwrap();
+        assert_eq!(concert.performers, vec!["Khruangbin", "Men I Trust"]);
+        assert!(concert.works.is_empty());
+    }
+
     #[test]
     fn test_month_to_num() {
         assert_eq!(month_to_num("January").unwrap(), 1);
@@ -0,0 +1,159 @@
+use chrono::NaiveDate;
+use scraper::{Html, Selector};
+use serde_json::Value;
+
+use crate::html::{venue_from_name, Concert};
+
+/// Parse a gig listing from schema.org `MusicEvent` JSON-LD, as embedded in
+/// saved Songkick and Bandsintown event pages. `content` may be the saved HTML
+/// or the JSON-LD itself. Returns `None` when there is no usable MusicEvent.
+pub fn parse_gig(content: &str) -> Option<Concert> {
+    json_ld_blocks(content).iter().find_map(find_music_event).and_then(event_to_concert)
+}
+
+/// True if `content` holds a MusicEvent, for picking gig pages out of Downloads
+pub fn has_music_event(content: &str) -> bool {
+    content.contains("MusicEvent") && json_ld_blocks(content).iter().any(|v| find_music_event(v).is_some())
+}
+
+/// Every JSON-LD block in the page; a bare JSON file is a single block
+fn json_ld_blocks(content: &str) -> Vec<Value> {
+    let trimmed = content.trim_start();
+    if trimmed.starts_with('{') || trimmed.starts_with('[') {
+        return serde_json::from_str(trimmed).into_iter().collect();
+    }
+
+    let document = Html::parse_document(content);
+    let selector = Selector::parse(r#"script[type="application/ld+json"]"#).unwrap();
+    document
+        .select(&selector)
+        // Malformed blocks are common (trailing commas, template leftovers); skip them
+        .filter_map(|script| serde_json::from_str(&script.text().collect::<String>()).ok())
+        .collect()
+}
+
+/// First MusicEvent in a block: the block itself, an array entry, or an `@graph` node
+fn find_music_event(value: &Value) -> Option<&Value> {
+    match value {
+        Value::Array(items) => items.iter().find_map(find_music_event),
+        Value::Object(map) => {
+            if is_type(value, "MusicEvent") {
+                Some(value)
+            } else {
+                map.get("@graph").and_then(find_music_event)
+            }
+        }
+        _ => None,
+    }
+}
+
+/// `@type` is either a string or a list of strings
+fn is_type(value: &Value, wanted: &str) -> bool {
+    match value.get("@type") {
+        Some(Value::String(t)) => t == wanted,
+        Some(Value::Array(types)) => types.iter().any(|t| t.as_str() == Some(wanted)),
+        _ => false,
+    }
+}
+
+fn event_to_concert(event: &Value) -> Option<Concert> {
+    // "2026-05-14T19:00:00+01:00" or a bare "2026-05-14"
+    let start = event.get("startDate")?.as_str()?;
+    let date = NaiveDate::parse_from_str(start.get(..10)?, "%Y-%m-%d").ok()?;
+
+    let venue_name = event
+        .get("location")
+        .and_then(|loc| match loc {
+            Value::Array(places) => places.first(),
+            place => Some(place),
+        })
+        .and_then(|place| place.get("name"))
+        .and_then(Value::as_str)
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty());
+
+    Some(Concert {
+        date,
+        performers: lineup(event),
+        works: Vec::new(),
+        venue: venue_name.as_deref().map(venue_from_name).unwrap_or(crate::html::Venue::Unknown),
+        venue_name,
+        is_gig: true,
+    })
+}
+
+/// Performer names in listing order (headliner first), without duplicates.
+/// Falls back to the event name when no performers are listed.
+fn lineup(event: &Value) -> Vec<String> {
+    let performers = match event.get("performer") {
+        Some(Value::Array(list)) => list.iter().collect(),
+        Some(single) => vec![single],
+        None => Vec::new(),
+    };
+
+    let mut names: Vec<String> = Vec::new();
+    for performer in performers {
+        let name = match performer {
+            Value::String(name) => Some(name.as_str()),
+            other => other.get("name").and_then(Value::as_str),
+        };
+        if let Some(name) = name.map(str::trim).filter(|n| !n.is_empty()) {
+            if !names.iter().any(|n| n == name) {
+                names.push(name.to_string());
+            }
+        }
+    }
+
+    if names.is_empty() {
+        if let Some(name) = event.get("name").and_then(Value::as_str) {
+            names.push(name.trim().to_string());
+        }
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::html::Venue;
+
+    const SONGKICK: &str = include_str!("../tests/fixtures/songkick-event.html");
+    const BANDSINTOWN: &str = include_str!("../tests/fixtures/bandsintown-event.html");
+
+    #[test]
+    fn test_songkick_lineup_and_venue() {
+        let gig = parse_gig(SONGKICK).unwrap();
+        assert_eq!(gig.date, NaiveDate::from_ymd_opt(2026, 5, 14).unwrap());
+        assert_eq!(gig.performers, vec!["Khruangbin", "Men I Trust"]);
+        assert!(gig.works.is_empty());
+        assert_eq!(gig.venue, Venue::Unknown);
+        assert_eq!(gig.venue_name.as_deref(), Some("O2 Academy Brixton"));
+    }
+
+    #[test]
+    fn test_bandsintown_known_venue() {
+        let gig = parse_gig(BANDSINTOWN).unwrap();
+        assert_eq!(gig.date, NaiveDate::from_ymd_opt(2026, 6, 2).unwrap());
+        assert_eq!(gig.performers, vec!["Nils Frahm"]);
+        assert_eq!(gig.venue, Venue::Barbican);
+    }
+
+    #[test]
+    fn test_bare_json_graph() {
+        let json = r#"{"@context":"https://schema.org","@graph":[
+            {"@type":"WebPage","name":"Tickets"},
+            {"@type":["Event","MusicEvent"],"name":"Late Night Jazz","startDate":"2026-07-01",
+             "location":{"@type":"Place","name":"Ronnie Scott's"}}]}"#;
+        let gig = parse_gig(json).unwrap();
+        assert_eq!(gig.performers, vec!["Late Night Jazz"]);
+        assert_eq!(gig.venue_name.as_deref(), Some("Ronnie Scott's"));
+    }
+
+    #[test]
+    fn test_no_music_event() {
+        let html = r#"<script type="application/ld+json">{"@type":"Organization","name":"Songkick"}</script>"#;
+        assert!(parse_gig(html).is_none());
+        assert!(!has_music_event(html));
+        assert!(has_music_event(SONGKICK));
+    }
+}
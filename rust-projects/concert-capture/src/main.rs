@@ -2,6 +2,7 @@ mod api;
 mod archive;
 mod daypage;
 mod html;
+mod jsonld;
 mod notation;
 
 use anyhow::{Context, Result};
@@ -19,7 +20,7 @@ struct Cli {
     #[arg(value_name = "FILE")]
     file: Option<PathBuf>,
 
-    /// Find latest Wigmore HTML in Downloads
+    /// Find latest concert or gig HTML in Downloads
     #[arg(long)]
     latest: bool,
 
@@ -102,7 +103,7 @@ const VENUE_MARKERS: &[&str] = &[
 
 fn is_concert_file(path: &PathBuf) -> bool {
     if let Ok(content) = std::fs::read_to_string(path) {
-        VENUE_MARKERS.iter().any(|marker| content.contains(marker))
+        VENUE_MARKERS.iter().any(|marker| content.contains(marker)) || jsonld::has_music_event(&content)
     } else {
         false
     }
@@ -144,8 +145,12 @@ fn process_concert(path: &PathBuf, dry_run: bool, no_api: bool, link_only: bool,
 
     let works_str = works_notation.join(" ");
 
-    let venue_tag = venue_to_tag(concert.venue);
-    let entry = format!("concert.{}:: {} {} {}", venue_tag, performers_str, works_str, wikilink);
+    let venue_tag = venue_to_tag(&concert);
+    let entry = if concert.is_gig {
+        gig_entry(&concert, &wikilink)
+    } else {
+        format!("concert.{}:: {} {} {}", venue_tag, performers_str, works_str, wikilink)
+    };
 
     if link_only {
         print!("{}", wikilink);
@@ -155,6 +160,9 @@ fn process_concert(path: &PathBuf, dry_run: bool, no_api: bool, link_only: bool,
     if dry_run {
         eprintln!("=== DRY RUN ===");
         eprintln!("Date: {}", concert.date);
+        if let Some(venue) = &concert.venue_name {
+            eprintln!("Venue: {}", venue);
+        }
         eprintln!("Performers: {:?}", concert.performers);
         eprintln!("Works: {:?}", concert.works);
         eprintln!();
@@ -218,13 +226,71 @@ fn list_archives() -> Result<()> {
     Ok(())
 }
 
-fn venue_to_tag(venue: html::Venue) -> &'static str {
-    match venue {
+/// DayPage entry for a gig from a listing site: the lineup as hashtags,
+/// then the venue's tag if the listing names one, then the archive link
+/// (gigs have no programme)
+fn gig_entry(concert: &html::Concert, wikilink: &str) -> String {
+    let mut tags: Vec<String> = concert
+        .performers
+        .iter()
+        .map(|p| format!("#{}", slug::slugify(p)))
+        .collect();
+    if concert.venue_name.is_some() {
+        tags.push(format!("#{}", venue_to_tag(concert)));
+    }
+    format!("concert.gig:: {} {}", tags.join(" "), wikilink)
+}
+
+/// Entry tag for the venue; venues outside the known list are tagged by
+/// the slug of their listed name (e.g. `#o2-academy-brixton` in a
+/// `concert.gig::` entry)
+fn venue_to_tag(concert: &html::Concert) -> String {
+    let tag = match concert.venue {
         html::Venue::WigmoreHall => "wigmore",
         html::Venue::SouthbankCentre => "southbank",
         html::Venue::KingsPlace => "kingsplace",
         html::Venue::Barbican => "barbican",
         html::Venue::IlminsterArts => "ilminster",
-        html::Venue::Unknown => "unknown",
+        html::Venue::Unknown => {
+            return concert
+                .venue_name
+                .as_deref()
+                .map(slug::slugify)
+                .filter(|s| !s.is_empty())
+                .unwrap_or_else(|| "unknown".to_string());
+        }
+    };
+    tag.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gig_entry() {
+        let html = include_str!("../tests/fixtures/songkick-event.html");
+        let gig = html::parse_concert(html).unwrap();
+        let wikilink = format!("[[captures/concerts/{}]]", archive::generate_filename(&gig));
+        assert_eq!(
+            gig_entry(&gig, &wikilink),
+            "concert.gig:: #khruangbin #men-i-trust #o2-academy-brixton \
+             [[captures/concerts/2026-05-14-khruangbin.html]]"
+        );
+    }
+
+    #[test]
+    fn test_gig_at_known_venue_uses_its_tag() {
+        let html = include_str!("../tests/fixtures/bandsintown-event.html");
+        let gig = html::parse_concert(html).unwrap();
+        assert_eq!(gig_entry(&gig, "[[x]]"), "concert.gig:: #nils-frahm #barbican [[x]]");
+    }
+
+    #[test]
+    fn test_gig_without_location() {
+        let json = r#"{"@type":"MusicEvent","startDate":"2026-08-09","performer":[{"name":"Floating Points"}]}"#;
+        let gig = jsonld::parse_gig(json).unwrap();
+        assert!(gig.is_gig);
+        assert_eq!(gig_entry(&gig, "[[x]]"), "concert.gig:: #floating-points [[x]]");
     }
 }
@@ -0,0 +1,321 @@
+//! Interactive session browser: a scrollable session list beside a preview
+//! of the selected conversation. Enter prints the full cleaned text to
+//! stdout after the terminal is restored, so the result can be piped on
+//! exactly like `load`.
+
+use anyhow::{bail, Context, Result};
+use ratatui::backend::{Backend, CrosstermBackend};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{Frame, Terminal};
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+use crate::load::{
+    build_cleaned_text, collect_sessions, estimate_tokens, format_time_range, resolve_filter,
+    SessionInfo,
+};
+
+/// Messages shown in the preview pane
+const PREVIEW_MESSAGES: usize = 6;
+
+struct App {
+    sessions: Vec<SessionInfo>,
+    state: ListState,
+    /// Cleaned text per session index, built the first time it is previewed
+    texts: HashMap<usize, String>,
+    preview_scroll: u16,
+    include_tool_output: bool,
+}
+
+enum Action {
+    Continue,
+    Quit,
+    Load(usize),
+}
+
+pub fn browse(assistant_filter: Option<&str>, include_tool_output: bool) -> Result<()> {
+    let base_dir = dirs::home_dir()
+        .context("No home directory")?
+        .join("Assistants/continuum-logs");
+    if !base_dir.exists() {
+        bail!("Continuum logs directory not found: {}", base_dir.display());
+    }
+
+    let assistant_filter = match assistant_filter {
+        Some(query) => {
+            let known: BTreeSet<String> = collect_sessions(&base_dir, None, None)?
+                .into_iter()
+                .map(|s| s.meta.assistant)
+                .collect();
+            Some(resolve_filter("assistant", query, &known)?)
+        }
+        None => None,
+    };
+
+    let mut app = App::new(sorted_sessions(&base_dir, assistant_filter.as_deref())?, include_tool_output);
+    if app.sessions.is_empty() {
+        bail!("No sessions found");
+    }
+
+    // Draw on stderr so stdout stays clean for the loaded text
+    enable_raw_mode()?;
+    execute!(std::io::stderr(), EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(std::io::stderr()))?;
+    let result = run(&mut terminal, &mut app);
+    disable_raw_mode()?;
+    execute!(std::io::stderr(), LeaveAlternateScreen)?;
+
+    if let Some(index) = result? {
+        let text = app.text(index).to_string();
+        let session = &app.sessions[index];
+        eprintln!(
+            "Session: {} | {} | approx {}k tokens",
+            session.meta.assistant,
+            format_time_range(&session.meta.start_time, &session.meta.end_time),
+            (estimate_tokens(&text) + 500) / 1000,
+        );
+        print!("{}", text);
+    }
+    Ok(())
+}
+
+/// All sessions, newest first
+fn sorted_sessions(base_dir: &Path, assistant_filter: Option<&str>) -> Result<Vec<SessionInfo>> {
+    let mut sessions = collect_sessions(base_dir, assistant_filter, None)?;
+    sessions.sort_by(|a, b| {
+        b.meta
+            .start_time
+            .cmp(&a.meta.start_time)
+            .then_with(|| a.meta.id.cmp(&b.meta.id))
+    });
+    Ok(sessions)
+}
+
+fn run<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<Option<usize>> {
+    loop {
+        terminal.draw(|frame| draw(frame, app))?;
+        if let Event::Key(key) = event::read()? {
+            match app.handle_key(key) {
+                Action::Continue => {}
+                Action::Quit => return Ok(None),
+                Action::Load(index) => return Ok(Some(index)),
+            }
+        }
+    }
+}
+
+impl App {
+    fn new(sessions: Vec<SessionInfo>, include_tool_output: bool) -> Self {
+        let mut state = ListState::default();
+        if !sessions.is_empty() {
+            state.select(Some(0));
+        }
+        App {
+            sessions,
+            state,
+            texts: HashMap::new(),
+            preview_scroll: 0,
+            include_tool_output,
+        }
+    }
+
+    fn selected(&self) -> usize {
+        self.state.selected().unwrap_or(0)
+    }
+
+    fn text(&mut self, index: usize) -> &str {
+        let include_tool_output = self.include_tool_output;
+        let session = &self.sessions[index];
+        self.texts.entry(index).or_insert_with(|| {
+            build_cleaned_text(session, include_tool_output)
+                .unwrap_or_else(|e| format!("(could not read messages: {})", e))
+        })
+    }
+
+    fn select(&mut self, index: usize) {
+        let last = self.sessions.len().saturating_sub(1);
+        self.state.select(Some(index.min(last)));
+        self.preview_scroll = 0;
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Action {
+        if key.kind != KeyEventKind::Press {
+            return Action::Continue;
+        }
+        let current = self.selected();
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Action::Quit,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Action::Quit,
+            KeyCode::Enter => return Action::Load(current),
+            KeyCode::Down | KeyCode::Char('j') => self.select(current + 1),
+            KeyCode::Up | KeyCode::Char('k') => self.select(current.saturating_sub(1)),
+            KeyCode::Home | KeyCode::Char('g') => self.select(0),
+            KeyCode::End | KeyCode::Char('G') => self.select(usize::MAX),
+            KeyCode::PageDown | KeyCode::Char('J') => self.preview_scroll = self.preview_scroll.saturating_add(10),
+            KeyCode::PageUp | KeyCode::Char('K') => self.preview_scroll = self.preview_scroll.saturating_sub(10),
+            _ => {}
+        }
+        Action::Continue
+    }
+}
+
+fn draw(frame: &mut Frame, app: &mut App) {
+    let [main, help] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(1)])
+        .areas(frame.area());
+    let [list_area, preview_area] = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+        .areas(main);
+
+    let items: Vec<ListItem> = app.sessions.iter().map(|s| ListItem::new(session_row(s))).collect();
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(format!(" Sessions ({}) ", app.sessions.len())))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    frame.render_stateful_widget(list, list_area, &mut app.state);
+
+    let index = app.selected();
+    let title = format!(" {} ", session_title(&app.sessions[index]));
+    let scroll = app.preview_scroll;
+    let preview = preview_text(app.text(index), PREVIEW_MESSAGES);
+    let paragraph = Paragraph::new(preview)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .wrap(Wrap { trim: false })
+        .scroll((scroll, 0));
+    frame.render_widget(paragraph, preview_area);
+
+    let keys = " ↑/↓ select · PgUp/PgDn scroll preview · Enter load · q quit";
+    frame.render_widget(Paragraph::new(keys).style(Style::default().fg(Color::DarkGray)), help);
+}
+
+/// List row: date, assistant, title
+fn session_row(session: &SessionInfo) -> Line<'static> {
+    let date = session
+        .meta
+        .start_time
+        .as_deref()
+        .and_then(|t| t.get(..10))
+        .unwrap_or("????-??-??")
+        .to_string();
+    Line::from(vec![
+        Span::styled(date, Style::default().fg(Color::DarkGray)),
+        Span::raw(" "),
+        Span::styled(format!("{:<12}", session.meta.assistant), Style::default().fg(Color::Cyan)),
+        Span::raw(" "),
+        Span::raw(session_title(session)),
+    ])
+}
+
+/// The session's title, or its id for sessions saved without one
+fn session_title(session: &SessionInfo) -> String {
+    session.meta.title.clone().unwrap_or_else(|| session.meta.id.clone())
+}
+
+/// The first `max_messages` messages of cleaned session text, with a note
+/// of how many more follow
+fn preview_text(text: &str, max_messages: usize) -> String {
+    // Messages are "[Role]\ncontent" separated by blank lines
+    let blocks: Vec<&str> = text.split("\n\n[").collect();
+    if blocks.len() <= max_messages {
+        return text.to_string();
+    }
+    let shown = blocks[..max_messages].join("\n\n[");
+    format!("{}\n\n… {} more messages (Enter to load all)", shown, blocks.len() - max_messages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+
+    /// The check fixture archive's sessions, read from a copy, since
+    /// scanning an archive writes its session cache. The copy lasts as long
+    /// as the returned directory.
+    fn fixture_sessions() -> (tempfile::TempDir, Vec<SessionInfo>) {
+        let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/check-archive");
+        let copy = tempfile::tempdir().unwrap();
+        for entry in walkdir::WalkDir::new(&fixture).into_iter().flatten() {
+            let dest = copy.path().join(entry.path().strip_prefix(&fixture).unwrap());
+            if entry.file_type().is_dir() {
+                std::fs::create_dir_all(&dest).unwrap();
+            } else {
+                std::fs::copy(entry.path(), &dest).unwrap();
+            }
+        }
+        let sessions = sorted_sessions(copy.path(), None).unwrap();
+        (copy, sessions)
+    }
+
+    fn press(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn preview_truncates_to_first_messages() {
+        let text = "[User]\nq1\n\n[Assistant]\na1\n\n[User]\nq2\n\n[Assistant]\na2\n\n";
+        assert_eq!(preview_text(text, 4), text);
+        assert_eq!(
+            preview_text(text, 2),
+            "[User]\nq1\n\n[Assistant]\na1\n\n… 2 more messages (Enter to load all)"
+        );
+    }
+
+    #[test]
+    fn sessions_newest_first_then_by_id() {
+        let (_archive, sessions) = fixture_sessions();
+        let keys: Vec<_> = sessions
+            .iter()
+            .map(|s| (std::cmp::Reverse(s.meta.start_time.clone()), s.meta.id.clone()))
+            .collect();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        assert_eq!(keys, sorted);
+        assert_eq!(keys[0].1, "clock-skew");
+    }
+
+    #[test]
+    fn keys_move_selection_and_enter_loads() {
+        let (_archive, sessions) = fixture_sessions();
+        let mut app = App::new(sessions, false);
+        let last = app.sessions.len() - 1;
+        assert!(matches!(app.handle_key(press(KeyCode::Up)), Action::Continue));
+        assert_eq!(app.selected(), 0);
+        app.handle_key(press(KeyCode::Char('j')));
+        app.handle_key(press(KeyCode::Down));
+        assert_eq!(app.selected(), 2);
+        app.handle_key(press(KeyCode::End));
+        app.handle_key(press(KeyCode::Down));
+        assert_eq!(app.selected(), last);
+        assert!(matches!(app.handle_key(press(KeyCode::Enter)), Action::Load(i) if i == last));
+        assert!(matches!(app.handle_key(press(KeyCode::Char('q'))), Action::Quit));
+    }
+
+    #[test]
+    fn renders_list_and_preview() {
+        let (_archive, sessions) = fixture_sessions();
+        let mut app = App::new(sessions, false);
+        let healthy = app.sessions.iter().position(|s| s.meta.id == "healthy").unwrap();
+        app.select(healthy);
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+        terminal.draw(|frame| draw(frame, &mut app)).unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("2026-03-01 chatgpt      healthy"));
+        assert!(screen.contains("[User]"));
+    }
+}
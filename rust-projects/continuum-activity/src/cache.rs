@@ -0,0 +1,204 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::load::SessionMeta;
+
+/// Index file kept at the top of the archive. Every scan skips plain files
+/// there, so it never looks like an assistant directory.
+pub(crate) const CACHE_FILE: &str = ".activity-cache.json";
+
+/// Bumped when `SessionMeta` changes shape, discarding older caches
+const CACHE_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct CacheFile {
+    version: u32,
+    /// Keyed by session.json path relative to the archive
+    entries: HashMap<String, Entry>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct Entry {
+    mtime_ns: u64,
+    len: u64,
+    meta: SessionMeta,
+}
+
+/// Parsed `session.json` files keyed by path, modification time and size,
+/// so a scan of thousands of sessions only re-reads the ones that changed.
+pub(crate) struct SessionCache {
+    base_dir: PathBuf,
+    file: CacheFile,
+    seen: HashSet<String>,
+    dirty: bool,
+}
+
+impl SessionCache {
+    /// The archive's cache, or an empty one when it's missing, unreadable or
+    /// from an older version
+    pub(crate) fn open(base_dir: &Path) -> Self {
+        let file = std::fs::read_to_string(base_dir.join(CACHE_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str::<CacheFile>(&content).ok())
+            .filter(|file| file.version == CACHE_VERSION)
+            .unwrap_or_else(|| CacheFile { version: CACHE_VERSION, entries: HashMap::new() });
+
+        Self {
+            base_dir: base_dir.to_path_buf(),
+            file,
+            seen: HashSet::new(),
+            dirty: false,
+        }
+    }
+
+    /// Metadata from `session_json`, parsed afresh only when the file's
+    /// mtime or size differs from the cached copy
+    pub(crate) fn meta(&mut self, session_json: &Path) -> Result<SessionMeta> {
+        let key = self.key(session_json);
+        self.seen.insert(key.clone());
+
+        let stat = std::fs::metadata(session_json)
+            .with_context(|| format!("Failed to read {}", session_json.display()))?;
+        let mtime_ns = stat
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_nanos() as u64);
+        let len = stat.len();
+
+        if let Some(entry) = self.file.entries.get(&key) {
+            if entry.mtime_ns == mtime_ns && entry.len == len {
+                return Ok(entry.meta.clone());
+            }
+        }
+
+        let parsed = std::fs::read_to_string(session_json)
+            .with_context(|| format!("Failed to read {}", session_json.display()))
+            .and_then(|content| {
+                serde_json::from_str::<SessionMeta>(&content)
+                    .with_context(|| format!("Failed to parse {}", session_json.display()))
+            });
+        self.dirty = true;
+        match parsed {
+            Ok(meta) => {
+                self.file.entries.insert(key, Entry { mtime_ns, len, meta: meta.clone() });
+                Ok(meta)
+            }
+            Err(e) => {
+                self.file.entries.remove(&key);
+                Err(e)
+            }
+        }
+    }
+
+    /// Write the cache back if anything changed, first dropping entries for
+    /// sessions that have since been deleted
+    pub(crate) fn save(&mut self) -> Result<()> {
+        let base_dir = &self.base_dir;
+        let seen = &self.seen;
+        let before = self.file.entries.len();
+        self.file.entries.retain(|key, _| seen.contains(key) || base_dir.join(key).exists());
+        if !self.dirty && self.file.entries.len() == before {
+            return Ok(());
+        }
+
+        // Write then rename, so a concurrent reader never sees half a file
+        let path = self.base_dir.join(CACHE_FILE);
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string(&self.file)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        std::fs::rename(&tmp, &path).with_context(|| format!("Failed to write {}", path.display()))?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Like `save`, but a cache that can't be written only warns
+    pub(crate) fn save_or_warn(&mut self) {
+        if let Err(e) = self.save() {
+            eprintln!("Warning: session cache not updated: {:#}", e);
+        }
+    }
+
+    fn key(&self, session_json: &Path) -> String {
+        session_json
+            .strip_prefix(&self.base_dir)
+            .unwrap_or(session_json)
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn write_session(base: &Path, id: &str, title: &str) -> PathBuf {
+        let dir = base.join("chatgpt/2026-03-01").join(id);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("session.json");
+        fs::write(&path, format!(r#"{{"id":"{id}","assistant":"chatgpt","title":"{title}"}}"#)).unwrap();
+        path
+    }
+
+    #[test]
+    fn unchanged_sessions_come_from_the_cache() {
+        let base = tempfile::tempdir().unwrap();
+        let path = write_session(base.path(), "a", "First");
+
+        let mut cache = SessionCache::open(base.path());
+        assert_eq!(cache.meta(&path).unwrap().title.as_deref(), Some("First"));
+        cache.save().unwrap();
+        assert!(base.path().join(CACHE_FILE).exists());
+
+        // Swap the cached copy for a marker; a hit returns the marker
+        let mut cache = SessionCache::open(base.path());
+        cache.file.entries.get_mut("chatgpt/2026-03-01/a/session.json").unwrap().meta.title = Some("cached".into());
+        assert_eq!(cache.meta(&path).unwrap().title.as_deref(), Some("cached"));
+        assert!(!cache.dirty);
+    }
+
+    #[test]
+    fn changed_file_is_reparsed() {
+        let base = tempfile::tempdir().unwrap();
+        let path = write_session(base.path(), "a", "First");
+        let mut cache = SessionCache::open(base.path());
+        cache.meta(&path).unwrap();
+        cache.save().unwrap();
+
+        write_session(base.path(), "a", "Renamed session");
+        let mut cache = SessionCache::open(base.path());
+        assert_eq!(cache.meta(&path).unwrap().title.as_deref(), Some("Renamed session"));
+        assert!(cache.dirty);
+    }
+
+    #[test]
+    fn deleted_and_broken_sessions_leave_the_cache() {
+        let base = tempfile::tempdir().unwrap();
+        let kept = write_session(base.path(), "kept", "Kept");
+        let gone = write_session(base.path(), "gone", "Gone");
+        let mut cache = SessionCache::open(base.path());
+        cache.meta(&kept).unwrap();
+        cache.meta(&gone).unwrap();
+        cache.save().unwrap();
+
+        fs::remove_dir_all(gone.parent().unwrap()).unwrap();
+        fs::write(&kept, "not json").unwrap();
+        let mut cache = SessionCache::open(base.path());
+        assert!(cache.meta(&kept).is_err());
+        cache.save().unwrap();
+        assert!(SessionCache::open(base.path()).file.entries.is_empty());
+    }
+
+    #[test]
+    fn corrupt_or_outdated_cache_starts_empty() {
+        let base = tempfile::tempdir().unwrap();
+        fs::write(base.path().join(CACHE_FILE), "{").unwrap();
+        assert!(SessionCache::open(base.path()).file.entries.is_empty());
+        fs::write(base.path().join(CACHE_FILE), r#"{"version":0,"entries":{}}"#).unwrap();
+        assert!(SessionCache::open(base.path()).file.entries.is_empty());
+    }
+}
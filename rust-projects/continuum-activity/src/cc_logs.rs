@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use std::io::{BufRead, BufReader};
 use std::path::Path;
 
@@ -6,6 +6,7 @@ use anyhow::{Context, Result};
 use chrono::{DateTime, NaiveDate, Utc};
 use serde::Deserialize;
 
+use crate::cost::{PriceTable, Usage};
 use crate::types::CcSession;
 
 /// Index file schema.
@@ -51,6 +52,7 @@ fn parse_session_jsonl(
     path: &Path,
     target_date: NaiveDate,
     verbose: bool,
+    prices: &mut PriceTable,
 ) -> Result<Option<CcSession>> {
     let file = std::fs::File::open(path)
         .with_context(|| format!("Failed to open {}", path.display()))?;
@@ -64,6 +66,9 @@ fn parse_session_jsonl(
     let mut files_modified: BTreeMap<String, u32> = BTreeMap::new();
     let mut tool_usage: BTreeMap<String, u32> = BTreeMap::new();
     let mut user_messages: Vec<(DateTime<Utc>, String)> = Vec::new();
+    let mut usage: Option<Usage> = None;
+    // A response split over several entries repeats its usage in each
+    let mut priced_messages: HashSet<String> = HashSet::new();
     let mut has_activity = false;
 
     for line in reader.lines() {
@@ -163,6 +168,14 @@ fn parse_session_jsonl(
                 }
             }
             "assistant" => {
+                if let Some(block) = entry.pointer("/message/usage").and_then(Usage::from_block) {
+                    let message_id = entry.pointer("/message/id").and_then(|v| v.as_str()).unwrap_or("");
+                    if message_id.is_empty() || priced_messages.insert(message_id.to_string()) {
+                        let model = entry.pointer("/message/model").and_then(|v| v.as_str()).unwrap_or("unknown");
+                        usage.get_or_insert_with(Usage::default).add(&prices.price(model, block));
+                    }
+                }
+
                 // Extract tool uses from assistant messages
                 if let Some(content) = entry.pointer("/message/content").and_then(|v| v.as_array())
                 {
@@ -219,8 +232,10 @@ fn parse_session_jsonl(
         end_time,
         skills,
         files_modified,
+        vault_files: Vec::new(), // Classified by the report against --vault-dirs
         tool_usage,
         user_messages,
+        usage,
     }))
 }
 
@@ -315,6 +330,7 @@ pub fn extract_cc_sessions(target_date: NaiveDate, verbose: bool) -> Result<Vec<
         .filter(|p| p.is_dir())
         .collect();
 
+    let mut prices = PriceTable::load()?;
     let mut sessions = Vec::new();
     let mut all_indexed_paths: Vec<String> = Vec::new();
 
@@ -335,7 +351,7 @@ pub fn extract_cc_sessions(target_date: NaiveDate, verbose: bool) -> Result<Vec<
             if !path.exists() {
                 continue;
             }
-            match parse_session_jsonl(path, target_date, verbose) {
+            match parse_session_jsonl(path, target_date, verbose, &mut prices) {
                 Ok(Some(session)) => sessions.push(session),
                 Ok(None) => {}
                 Err(e) => {
@@ -346,7 +362,7 @@ pub fn extract_cc_sessions(target_date: NaiveDate, verbose: bool) -> Result<Vec<
 
         // Also scan unindexed JSONL files (index can be stale)
         for path in unindexed_jsonl_files(cc_dir, &indexed_paths, target_date) {
-            match parse_session_jsonl(&path, target_date, verbose) {
+            match parse_session_jsonl(&path, target_date, verbose, &mut prices) {
                 Ok(Some(session)) => sessions.push(session),
                 Ok(None) => {}
                 Err(e) => {
@@ -397,6 +413,7 @@ pub fn extract_cc_sessions(target_date: NaiveDate, verbose: bool) -> Result<Vec<
             for (tool, count) in session.tool_usage {
                 *existing.tool_usage.entry(tool).or_insert(0) += count;
             }
+            existing.usage = Usage::total([existing.usage.as_ref(), session.usage.as_ref()]);
             // Merge user messages
             existing.user_messages.extend(session.user_messages);
             existing.user_messages.sort_by_key(|(ts, _)| *ts);
@@ -407,3 +424,52 @@ pub fn extract_cc_sessions(target_date: NaiveDate, verbose: bool) -> Result<Vec<
 
     Ok(merged)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assistant_entry(ts: &str, message_id: &str, model: &str, input: u64, output: u64) -> String {
+        serde_json::json!({
+            "type": "assistant",
+            "timestamp": ts,
+            "sessionId": "s1",
+            "message": {
+                "id": message_id,
+                "model": model,
+                "usage": {"input_tokens": input, "output_tokens": output, "cache_read_input_tokens": 0},
+                "content": [{"type": "tool_use", "name": "Read", "input": {}}],
+            },
+        })
+        .to_string()
+    }
+
+    fn parse(lines: &[String]) -> CcSession {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s1.jsonl");
+        std::fs::write(&path, lines.join("\n")).unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        parse_session_jsonl(&path, date, false, &mut PriceTable::default()).unwrap().unwrap()
+    }
+
+    #[test]
+    fn usage_is_counted_once_per_message() {
+        let session = parse(&[
+            // One response logged as two entries, each repeating its usage
+            assistant_entry("2026-03-01T09:00:00Z", "msg_1", "claude-sonnet-4-5", 1_000_000, 100_000),
+            assistant_entry("2026-03-01T09:00:01Z", "msg_1", "claude-sonnet-4-5", 1_000_000, 100_000),
+            assistant_entry("2026-03-01T09:01:00Z", "msg_2", "claude-opus-4-5", 1_000_000, 0),
+        ]);
+        let usage = session.usage.unwrap();
+        assert_eq!((usage.input_tokens, usage.output_tokens), (2_000_000, 100_000));
+        assert!((usage.cost_usd - (3.0 + 1.5 + 5.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn logs_without_usage_have_none() {
+        let mut entry: serde_json::Value =
+            serde_json::from_str(&assistant_entry("2026-03-01T09:00:00Z", "msg_1", "m", 0, 0)).unwrap();
+        entry["message"].as_object_mut().unwrap().remove("usage");
+        assert_eq!(parse(&[entry.to_string()]).usage, None);
+    }
+}
@@ -0,0 +1,431 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+
+/// One kind of structural problem in a session directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Violation {
+    SessionJson,
+    MessagesJsonl,
+    NonSequentialIds,
+    MessageCount,
+    TimeRange,
+    DateMismatch,
+    AssistantMismatch,
+    BadTimestamp,
+    TimestampOrder,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Violation {
+    /// Timestamps are advisory: older imports have gaps and clock skew that
+    /// don't stop the archive being read
+    pub fn severity(self) -> Severity {
+        match self {
+            Violation::BadTimestamp | Violation::TimestampOrder => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Violation::SessionJson => "session.json missing or invalid",
+            Violation::MessagesJsonl => "messages.jsonl line invalid",
+            Violation::NonSequentialIds => "message ids not sequential",
+            Violation::MessageCount => "message_count != line count",
+            Violation::TimeRange => "start_time after end_time",
+            Violation::DateMismatch => "date dir != start_time date",
+            Violation::AssistantMismatch => "assistant != parent dir",
+            Violation::BadTimestamp => "unparseable timestamp",
+            Violation::TimestampOrder => "timestamps go backwards",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Finding {
+    pub kind: Violation,
+    pub severity: Severity,
+    pub path: PathBuf,
+    pub detail: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct CheckReport {
+    pub sessions: usize,
+    pub errors: usize,
+    pub warnings: usize,
+    pub findings: Vec<Finding>,
+}
+
+impl CheckReport {
+    fn push(&mut self, kind: Violation, path: &Path, detail: String) {
+        match kind.severity() {
+            Severity::Error => self.errors += 1,
+            Severity::Warning => self.warnings += 1,
+        }
+        self.findings.push(Finding { kind, severity: kind.severity(), path: path.to_path_buf(), detail });
+    }
+
+    fn counts(&self) -> BTreeMap<Violation, usize> {
+        let mut counts = BTreeMap::new();
+        for finding in &self.findings {
+            *counts.entry(finding.kind).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SessionMeta {
+    assistant: String,
+    #[serde(default)]
+    start_time: Option<String>,
+    #[serde(default)]
+    end_time: Option<String>,
+    #[serde(default)]
+    message_count: Option<u32>,
+}
+
+/// The fields checked on each line. Ids and timestamps are optional: not
+/// every importer writes them
+#[derive(Debug, Deserialize)]
+struct Message {
+    #[serde(default)]
+    id: Option<u64>,
+    #[serde(default)]
+    timestamp: Option<String>,
+}
+
+/// Run the check command. Returns false if any errors (not warnings) were found.
+pub fn run(verbose: bool, json: bool) -> Result<bool> {
+    let base_dir = dirs::home_dir()
+        .context("No home directory")?
+        .join("Assistants/continuum-logs");
+
+    if !base_dir.exists() {
+        bail!("Continuum logs directory not found: {}", base_dir.display());
+    }
+
+    let report = check_archive(&base_dir)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{}", render_report(&report, &base_dir, verbose));
+    }
+    Ok(report.errors == 0)
+}
+
+/// Check every session directory under `<base>/<assistant>/<date>/`
+pub fn check_archive(base_dir: &Path) -> Result<CheckReport> {
+    let mut report = CheckReport::default();
+
+    for assistant_dir in sorted_dirs(base_dir)? {
+        let assistant = dir_name(&assistant_dir);
+        for date_dir in sorted_dirs(&assistant_dir)? {
+            let date = dir_name(&date_dir);
+            for session_dir in sorted_dirs(&date_dir)? {
+                report.sessions += 1;
+                check_session(&session_dir, &assistant, &date, &mut report);
+            }
+        }
+    }
+    Ok(report)
+}
+
+fn sorted_dirs(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut dirs: Vec<PathBuf> = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect();
+    dirs.sort();
+    Ok(dirs)
+}
+
+fn dir_name(path: &Path) -> String {
+    path.file_name().unwrap_or_default().to_string_lossy().to_string()
+}
+
+/// RFC 3339, or a naive `YYYY-MM-DDTHH:MM:SS[.f]` taken as UTC
+fn parse_time(s: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(s).ok().or_else(|| {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f")
+            .ok()
+            .map(|naive| naive.and_utc().fixed_offset())
+    })
+}
+
+fn check_session(session_dir: &Path, assistant: &str, date: &str, report: &mut CheckReport) {
+    let session_json = session_dir.join("session.json");
+    let meta = match std::fs::read_to_string(&session_json) {
+        Ok(content) => match serde_json::from_str::<SessionMeta>(&content) {
+            Ok(meta) => Some(meta),
+            Err(e) => {
+                report.push(Violation::SessionJson, &session_json, e.to_string());
+                None
+            }
+        },
+        Err(e) => {
+            report.push(Violation::SessionJson, &session_json, e.to_string());
+            None
+        }
+    };
+
+    let messages_path = session_dir.join("messages.jsonl");
+    let lines = check_messages(&messages_path, report);
+
+    let Some(meta) = meta else { return };
+
+    if meta.assistant != assistant {
+        report.push(
+            Violation::AssistantMismatch,
+            &session_json,
+            format!("assistant \"{}\" under {}/", meta.assistant, assistant),
+        );
+    }
+
+    if let (Some(expected), Some(lines)) = (meta.message_count, lines) {
+        if expected as usize != lines {
+            report.push(
+                Violation::MessageCount,
+                &session_json,
+                format!("message_count {} but messages.jsonl has {} lines", expected, lines),
+            );
+        }
+    }
+
+    let mut times = Vec::new();
+    for (field, value) in [("start_time", &meta.start_time), ("end_time", &meta.end_time)] {
+        let Some(value) = value else {
+            times.push(None);
+            continue;
+        };
+        let parsed = parse_time(value);
+        if parsed.is_none() {
+            report.push(Violation::BadTimestamp, &session_json, format!("{} \"{}\"", field, value));
+        }
+        times.push(parsed);
+    }
+    let (start, end) = (times[0], times[1]);
+
+    if let (Some(start), Some(end)) = (start, end) {
+        if start > end {
+            report.push(
+                Violation::TimeRange,
+                &session_json,
+                format!("start {} is after end {}", start.to_rfc3339(), end.to_rfc3339()),
+            );
+        }
+    }
+
+    if let Some(start) = start {
+        let matches = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map(|d| d == start.date_naive())
+            .unwrap_or(false);
+        if !matches {
+            report.push(
+                Violation::DateMismatch,
+                session_dir,
+                format!("in {}/ but starts {}", date, start.date_naive()),
+            );
+        }
+    }
+}
+
+/// Validate messages.jsonl line by line; returns the number of non-empty
+/// lines, or None if the file couldn't be read. Each kind of violation is
+/// reported once per session, at its first occurrence.
+fn check_messages(path: &Path, report: &mut CheckReport) -> Option<usize> {
+    let file = match std::fs::File::open(path) {
+        Ok(f) => f,
+        Err(e) => {
+            report.push(Violation::MessagesJsonl, path, e.to_string());
+            return None;
+        }
+    };
+
+    let mut lines = 0;
+    let mut last_id: Option<u64> = None;
+    let mut last_time: Option<DateTime<FixedOffset>> = None;
+    let (mut bad_line, mut bad_id, mut bad_time, mut backwards) = (false, false, false, false);
+
+    for (i, line) in std::io::BufReader::new(file).lines().enumerate() {
+        let line_no = i + 1;
+        let line = match line {
+            Ok(l) => l,
+            Err(e) => {
+                report.push(Violation::MessagesJsonl, path, format!("line {}: {}", line_no, e));
+                return None;
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        lines += 1;
+
+        let msg = match serde_json::from_str::<Message>(&line) {
+            Ok(m) => m,
+            Err(e) => {
+                if !bad_line {
+                    report.push(Violation::MessagesJsonl, path, format!("line {}: {}", line_no, e));
+                    bad_line = true;
+                }
+                // The unreadable line presumably held the next id
+                last_id = last_id.map(|id| id + 1);
+                continue;
+            }
+        };
+
+        if let Some(id) = msg.id {
+            let expected = last_id.map_or(1, |last| last + 1);
+            if id != expected && !bad_id {
+                report.push(
+                    Violation::NonSequentialIds,
+                    path,
+                    format!("line {}: id {} where {} expected", line_no, id, expected),
+                );
+                bad_id = true;
+            }
+            last_id = Some(id);
+        }
+
+        if let Some(ts) = &msg.timestamp {
+            match parse_time(ts) {
+                Some(time) => {
+                    if last_time.is_some_and(|last| time < last) && !backwards {
+                        report.push(
+                            Violation::TimestampOrder,
+                            path,
+                            format!("line {}: {} is earlier than the message before", line_no, ts),
+                        );
+                        backwards = true;
+                    }
+                    last_time = Some(time);
+                }
+                None if !bad_time => {
+                    report.push(Violation::BadTimestamp, path, format!("line {}: \"{}\"", line_no, ts));
+                    bad_time = true;
+                }
+                None => {}
+            }
+        }
+    }
+    Some(lines)
+}
+
+/// Summary table of violation counts, with offending paths under --verbose
+pub fn render_report(report: &CheckReport, base_dir: &Path, verbose: bool) -> String {
+    let mut out = format!(
+        "Checked {} sessions: {} errors, {} warnings\n",
+        report.sessions, report.errors, report.warnings
+    );
+    let counts = report.counts();
+    if counts.is_empty() {
+        return out;
+    }
+
+    out.push_str(&format!("\n{:<32} {:<8} {:>6}\n", "Violation", "Severity", "Count"));
+    for (kind, count) in &counts {
+        let severity = match kind.severity() {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        out.push_str(&format!("{:<32} {:<8} {:>6}\n", kind.label(), severity, count));
+    }
+
+    if verbose {
+        for kind in counts.keys() {
+            out.push_str(&format!("\n{}:\n", kind.label()));
+            for finding in report.findings.iter().filter(|f| f.kind == *kind) {
+                let path = finding.path.strip_prefix(base_dir).unwrap_or(&finding.path);
+                out.push_str(&format!("  {}  ({})\n", path.display(), finding.detail));
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture_archive() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/check-archive")
+    }
+
+    /// Violation kinds found for one session, by session dir name
+    fn kinds_for(report: &CheckReport, session: &str) -> Vec<Violation> {
+        let mut kinds: Vec<Violation> = report
+            .findings
+            .iter()
+            .filter(|f| f.path.components().any(|c| c.as_os_str() == session))
+            .map(|f| f.kind)
+            .collect();
+        kinds.sort();
+        kinds
+    }
+
+    #[test]
+    fn each_fixture_shows_its_violation() {
+        let report = check_archive(&fixture_archive()).unwrap();
+        assert_eq!(report.sessions, 10);
+
+        assert!(kinds_for(&report, "healthy").is_empty());
+        assert_eq!(kinds_for(&report, "broken-session-json"), vec![Violation::SessionJson]);
+        assert_eq!(kinds_for(&report, "garbled-line"), vec![Violation::MessagesJsonl]);
+        assert_eq!(kinds_for(&report, "skipped-id"), vec![Violation::NonSequentialIds]);
+        assert_eq!(kinds_for(&report, "wrong-count"), vec![Violation::MessageCount]);
+        assert_eq!(kinds_for(&report, "ends-before-start"), vec![Violation::TimeRange]);
+        assert_eq!(kinds_for(&report, "filed-next-day"), vec![Violation::DateMismatch]);
+        assert_eq!(kinds_for(&report, "misfiled-assistant"), vec![Violation::AssistantMismatch]);
+        assert_eq!(
+            kinds_for(&report, "clock-skew"),
+            vec![Violation::BadTimestamp, Violation::TimestampOrder]
+        );
+        // Messages without ids or timestamps aren't held to those checks
+        assert!(kinds_for(&report, "no-ids").is_empty());
+    }
+
+    #[test]
+    fn timestamp_problems_are_only_warnings() {
+        let report = check_archive(&fixture_archive()).unwrap();
+        assert_eq!(report.warnings, 2);
+        assert_eq!(report.errors, 7);
+    }
+
+    #[test]
+    fn clean_archive_has_no_table() {
+        let report = CheckReport { sessions: 3, ..Default::default() };
+        assert_eq!(render_report(&report, Path::new("/"), true), "Checked 3 sessions: 0 errors, 0 warnings\n");
+    }
+
+    #[test]
+    fn verbose_lists_paths_relative_to_archive() {
+        let base = fixture_archive();
+        let report = check_archive(&base).unwrap();
+        let out = render_report(&report, &base, true);
+        assert!(out.contains("message ids not sequential       error         1"));
+        assert!(out.contains("  chatgpt/2026-03-01/skipped-id/messages.jsonl  (line 3: id 4 where 3 expected)"));
+
+        let terse = render_report(&report, &base, false);
+        assert!(!terse.contains("skipped-id"));
+    }
+
+    #[test]
+    fn naive_timestamps_are_utc() {
+        assert_eq!(parse_time("2026-03-01T09:00:00"), parse_time("2026-03-01T09:00:00Z"));
+        assert!(parse_time("yesterday").is_none());
+    }
+}
@@ -1,23 +1,9 @@
 use anyhow::{Context, Result};
 use chrono::NaiveDate;
-use serde::Deserialize;
 
+use crate::cache::SessionCache;
 use crate::types::ContinuumSession;
 
-#[derive(Debug, Deserialize)]
-struct SessionMeta {
-    id: String,
-    assistant: String,
-    #[serde(default)]
-    title: Option<String>,
-    #[serde(default)]
-    start_time: Option<String>,
-    #[serde(default)]
-    end_time: Option<String>,
-    #[serde(default)]
-    message_count: Option<u32>,
-}
-
 /// Scan the Continuum archive for sessions on `target_date`.
 pub fn extract_continuum_sessions(target_date: NaiveDate) -> Result<Vec<ContinuumSession>> {
     let base_dir = dirs::home_dir()
@@ -30,6 +16,7 @@ pub fn extract_continuum_sessions(target_date: NaiveDate) -> Result<Vec<Continuu
 
     let date_str = target_date.format("%Y-%m-%d").to_string();
     let mut sessions = Vec::new();
+    let mut cache = SessionCache::open(&base_dir);
 
     // Iterate over each assistant directory
     let entries = std::fs::read_dir(&base_dir)
@@ -67,36 +54,22 @@ pub fn extract_continuum_sessions(target_date: NaiveDate) -> Result<Vec<Continuu
                 continue;
             }
 
-            match std::fs::read_to_string(&session_json) {
-                Ok(content) => match serde_json::from_str::<SessionMeta>(&content) {
-                    Ok(meta) => {
-                        sessions.push(ContinuumSession {
-                            assistant: meta.assistant,
-                            session_id: meta.id,
-                            title: meta.title,
-                            start_time: meta.start_time,
-                            end_time: meta.end_time,
-                            message_count: meta.message_count,
-                        });
-                    }
-                    Err(e) => {
-                        eprintln!(
-                            "Warning: failed to parse {}: {}",
-                            session_json.display(),
-                            e
-                        );
-                    }
-                },
-                Err(e) => {
-                    eprintln!(
-                        "Warning: failed to read {}: {}",
-                        session_json.display(),
-                        e
-                    );
+            match cache.meta(&session_json) {
+                Ok(meta) => {
+                    sessions.push(ContinuumSession {
+                        assistant: meta.assistant,
+                        session_id: meta.id,
+                        title: meta.title,
+                        start_time: meta.start_time,
+                        end_time: meta.end_time,
+                        message_count: meta.message_count,
+                    });
                 }
+                Err(e) => eprintln!("Warning: {:#}", e),
             }
         }
     }
+    cache.save_or_warn();
 
     // Sort by assistant name, then start_time
     sessions.sort_by(|a, b| {
@@ -0,0 +1,275 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// USD per million tokens
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct Rate {
+    pub input: f64,
+    pub output: f64,
+    /// Defaults to a tenth of `input`, as Anthropic prices cache hits
+    #[serde(default)]
+    pub cache_read: Option<f64>,
+    /// Defaults to 1.25× `input`, the 5-minute cache write price
+    #[serde(default)]
+    pub cache_write: Option<f64>,
+}
+
+impl Rate {
+    const fn new(input: f64, output: f64) -> Self {
+        Self { input, output, cache_read: None, cache_write: None }
+    }
+
+    fn cost(&self, usage: &Usage) -> f64 {
+        let per_token = |tokens: u64, rate: f64| tokens as f64 * rate / 1_000_000.0;
+        per_token(usage.input_tokens, self.input)
+            + per_token(usage.output_tokens, self.output)
+            + per_token(usage.cache_read_tokens, self.cache_read.unwrap_or(self.input * 0.1))
+            + per_token(usage.cache_write_tokens, self.cache_write.unwrap_or(self.input * 1.25))
+    }
+}
+
+/// Built-in rates by model-name prefix; the longest matching prefix wins
+const BUILT_IN: &[(&str, Rate)] = &[
+    ("claude-opus-4-5", Rate::new(5.0, 25.0)),
+    ("claude-opus-4", Rate::new(15.0, 75.0)),
+    ("claude-sonnet-4", Rate::new(3.0, 15.0)),
+    ("claude-3-7-sonnet", Rate::new(3.0, 15.0)),
+    ("claude-haiku-4-5", Rate::new(1.0, 5.0)),
+    ("claude-3-5-haiku", Rate::new(0.8, 4.0)),
+];
+
+/// Rate for models matching nothing in the table
+const DEFAULT_RATE: Rate = Rate::new(3.0, 15.0);
+
+/// Token counts for a session or day, and their estimated cost. `input`
+/// counts fresh prompt tokens; cache writes and reads are kept apart since
+/// they are priced differently and reads dwarf everything else.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Usage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_write_tokens: u64,
+    pub cost_usd: f64,
+}
+
+impl Usage {
+    /// The counts in a log entry's `message.usage` block, not yet priced
+    pub fn from_block(block: &serde_json::Value) -> Option<Self> {
+        let count = |key: &str| block.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+        block.is_object().then(|| Self {
+            input_tokens: count("input_tokens"),
+            output_tokens: count("output_tokens"),
+            cache_read_tokens: count("cache_read_input_tokens"),
+            cache_write_tokens: count("cache_creation_input_tokens"),
+            cost_usd: 0.0,
+        })
+    }
+
+    pub fn add(&mut self, other: &Usage) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cache_read_tokens += other.cache_read_tokens;
+        self.cache_write_tokens += other.cache_write_tokens;
+        self.cost_usd += other.cost_usd;
+    }
+
+    /// Combined usage of several sessions; None if none of them had any
+    pub fn total<'a>(usages: impl IntoIterator<Item = Option<&'a Usage>>) -> Option<Usage> {
+        usages.into_iter().flatten().fold(None, |total, usage| {
+            let mut total = total.unwrap_or_default();
+            total.add(usage);
+            Some(total)
+        })
+    }
+
+    /// `~41k in / 12k out, ≈$0.38`, with cache reads when there were any
+    pub fn summary(&self) -> String {
+        let cache = if self.cache_read_tokens > 0 {
+            format!(" ({} cache reads)", approx_tokens(self.cache_read_tokens))
+        } else {
+            String::new()
+        };
+        format!(
+            "~{} in / {} out{}, \u{2248}${:.2}",
+            approx_tokens(self.input_tokens + self.cache_write_tokens),
+            approx_tokens(self.output_tokens),
+            cache,
+            self.cost_usd,
+        )
+    }
+}
+
+/// `850`, `41k`, `1.2M`
+fn approx_tokens(tokens: u64) -> String {
+    match tokens {
+        0..=999 => tokens.to_string(),
+        1_000..=999_999 => format!("{}k", (tokens + 500) / 1000),
+        _ => format!("{:.1}M", tokens as f64 / 1_000_000.0),
+    }
+}
+
+/// Prices by model name: `~/.config/continuum-activity/prices.toml` over the
+/// built-in table. Keys are model names or prefixes of them:
+///
+/// ```toml
+/// [claude-sonnet-4]
+/// input = 3.0
+/// output = 15.0
+/// cache_read = 0.3
+///
+/// [default]
+/// input = 3.0
+/// output = 15.0
+/// ```
+#[derive(Debug)]
+pub struct PriceTable {
+    rates: BTreeMap<String, Rate>,
+    default: Rate,
+    /// Models priced at the default rate, each warned about once
+    pub unknown: BTreeSet<String>,
+}
+
+impl Default for PriceTable {
+    fn default() -> Self {
+        Self {
+            rates: BUILT_IN.iter().map(|(model, rate)| (model.to_string(), *rate)).collect(),
+            default: DEFAULT_RATE,
+            unknown: BTreeSet::new(),
+        }
+    }
+}
+
+impl PriceTable {
+    pub fn config_path() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join(".config/continuum-activity/prices.toml"))
+    }
+
+    /// The built-in table with the config file's entries on top, if it exists
+    pub fn load() -> Result<Self> {
+        match Self::config_path().filter(|p| p.exists()) {
+            Some(path) => Self::from_file(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::from_toml(&content).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    fn from_toml(content: &str) -> Result<Self> {
+        let mut table = Self::default();
+        let mut overrides: BTreeMap<String, Rate> = toml::from_str(content)?;
+        if let Some(default) = overrides.remove("default") {
+            table.default = default;
+        }
+        table.rates.extend(overrides);
+        Ok(table)
+    }
+
+    /// The rate for `model`: an exact entry, else the longest entry the
+    /// name starts with, else the default (with a warning, once per model)
+    pub fn rate(&mut self, model: &str) -> Rate {
+        let found = self.rates.get(model).or_else(|| {
+            self.rates
+                .iter()
+                .filter(|(prefix, _)| model.starts_with(prefix.as_str()))
+                .max_by_key(|(prefix, _)| prefix.len())
+                .map(|(_, rate)| rate)
+        });
+        if let Some(rate) = found {
+            return *rate;
+        }
+        if self.unknown.insert(model.to_string()) {
+            eprintln!("Warning: no price for model '{}'; using the default rate", model);
+        }
+        self.default
+    }
+
+    /// `usage` with its cost filled in at `model`'s rate
+    pub fn price(&mut self, model: &str, mut usage: Usage) -> Usage {
+        usage.cost_usd = self.rate(model).cost(&usage);
+        usage
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(input: u64, output: u64, cache_read: u64, cache_write: u64) -> Usage {
+        Usage {
+            input_tokens: input,
+            output_tokens: output,
+            cache_read_tokens: cache_read,
+            cache_write_tokens: cache_write,
+            cost_usd: 0.0,
+        }
+    }
+
+    #[test]
+    fn cost_counts_each_kind_of_token() {
+        let mut prices = PriceTable::default();
+        // Sonnet: $3 in, $15 out, $0.30 cache reads, $3.75 cache writes
+        let priced = prices.price("claude-sonnet-4-5-20250929", usage(1_000_000, 100_000, 2_000_000, 200_000));
+        assert!((priced.cost_usd - (3.0 + 1.5 + 0.6 + 0.75)).abs() < 1e-9);
+        assert!(prices.unknown.is_empty());
+    }
+
+    #[test]
+    fn longest_prefix_wins() {
+        let mut prices = PriceTable::default();
+        assert_eq!(prices.rate("claude-opus-4-5-20251101").input, 5.0);
+        assert_eq!(prices.rate("claude-opus-4-1-20250805").input, 15.0);
+    }
+
+    #[test]
+    fn unknown_model_falls_back_to_default_with_warning() {
+        let mut prices = PriceTable::default();
+        assert_eq!(prices.rate("gpt-5"), DEFAULT_RATE);
+        assert_eq!(prices.rate("gpt-5"), DEFAULT_RATE);
+        assert_eq!(prices.unknown, BTreeSet::from(["gpt-5".to_string()]));
+    }
+
+    #[test]
+    fn config_file_overrides_and_extends_built_ins() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("prices.toml");
+        std::fs::write(
+            &path,
+            "[claude-sonnet-4]\ninput = 2.0\noutput = 10.0\ncache_read = 0.5\n\n\
+             [local-llama]\ninput = 0.0\noutput = 0.0\n\n\
+             [default]\ninput = 1.0\noutput = 2.0\n",
+        )
+        .unwrap();
+
+        let mut prices = PriceTable::from_file(&path).unwrap();
+        let sonnet = prices.rate("claude-sonnet-4-5");
+        assert_eq!((sonnet.input, sonnet.cache_read), (2.0, Some(0.5)));
+        assert_eq!(prices.rate("local-llama-3").output, 0.0);
+        assert_eq!(prices.rate("claude-haiku-4-5").input, 1.0);
+        assert_eq!(prices.rate("mystery").output, 2.0);
+    }
+
+    #[test]
+    fn malformed_config_is_an_error() {
+        assert!(PriceTable::from_toml("[claude-sonnet-4]\ninput = \"cheap\"\n").is_err());
+    }
+
+    #[test]
+    fn summary_rounds_tokens_and_cost() {
+        let mut day = usage(40_600, 12_000, 0, 0);
+        day.cost_usd = 0.3849;
+        assert_eq!(day.summary(), "~41k in / 12k out, \u{2248}$0.38");
+
+        day.add(&usage(400, 0, 1_240_000, 0));
+        assert_eq!(day.summary(), "~41k in / 12k out (1.2M cache reads), \u{2248}$0.38");
+
+        assert_eq!(Usage::total([None, None]), None);
+        assert_eq!(Usage::total([Some(&day), None]).unwrap().input_tokens, 41_000);
+    }
+}
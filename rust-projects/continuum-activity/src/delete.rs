@@ -0,0 +1,130 @@
+use anyhow::{bail, Context, Result};
+use std::io::Write;
+use std::path::Path;
+
+use crate::load::{find_session_by_id, format_time_range, SessionInfo};
+
+pub fn delete_session(session_id: &str, yes: bool) -> Result<()> {
+    let base_dir = dirs::home_dir()
+        .context("No home directory")?
+        .join("Assistants/continuum-logs");
+
+    if !base_dir.exists() {
+        bail!("Continuum logs directory not found: {}", base_dir.display());
+    }
+
+    let session = find_session_by_id(&base_dir, session_id)?;
+    eprint!("{}", describe(&session));
+
+    if !yes {
+        eprint!("\nDelete this session? [y/N] ");
+        std::io::stderr().flush()?;
+
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input)?;
+        if !matches!(input.trim().to_lowercase().as_str(), "y" | "yes") {
+            eprintln!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    remove_session_dir(&base_dir, &session.path)?;
+    eprintln!("Deleted {}", session.path.display());
+    Ok(())
+}
+
+fn describe(session: &SessionInfo) -> String {
+    let meta = &session.meta;
+    let mut out = format!("Session:   {}\n", meta.id);
+    out.push_str(&format!("Assistant: {}\n", meta.assistant));
+    out.push_str(&format!("Time:      {}\n", format_time_range(&meta.start_time, &meta.end_time)));
+    if let Some(count) = meta.message_count {
+        out.push_str(&format!("Messages:  {}\n", count));
+    }
+    if !meta.skills.is_empty() {
+        out.push_str(&format!("Skills:    {}\n", meta.skills.join(", ")));
+    }
+    out.push_str(&format!("Path:      {}\n", session.path.display()));
+    out
+}
+
+/// Remove a session directory, then its date directory if that is left empty.
+fn remove_session_dir(base_dir: &Path, session_dir: &Path) -> Result<()> {
+    // Sessions live at <base>/<assistant>/<date>/<session>; never go above that
+    if session_dir.parent().and_then(Path::parent).and_then(Path::parent) != Some(base_dir) {
+        bail!("Refusing to delete {}: not a session directory", session_dir.display());
+    }
+
+    std::fs::remove_dir_all(session_dir)
+        .with_context(|| format!("Failed to delete {}", session_dir.display()))?;
+
+    if let Some(date_dir) = session_dir.parent() {
+        let is_empty = std::fs::read_dir(date_dir).map(|mut d| d.next().is_none()).unwrap_or(false);
+        if is_empty {
+            let _ = std::fs::remove_dir(date_dir);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn add_session(base: &Path, assistant: &str, date: &str, id: &str) {
+        let dir = base.join(assistant).join(date).join(id);
+        fs::create_dir_all(&dir).unwrap();
+        let meta = format!(
+            r#"{{"id":"{id}","assistant":"{assistant}","start_time":"{date}T09:00:00Z","message_count":4}}"#
+        );
+        fs::write(dir.join("session.json"), meta).unwrap();
+        fs::write(dir.join("messages.jsonl"), "").unwrap();
+    }
+
+    fn archive() -> tempfile::TempDir {
+        let base = tempfile::tempdir().unwrap();
+        add_session(base.path(), "claude-code", "2026-02-10", "abc123");
+        add_session(base.path(), "claude-code", "2026-02-10", "abd456");
+        add_session(base.path(), "gemini-cli", "2026-02-11", "xyz789");
+        base
+    }
+
+    #[test]
+    fn ambiguous_prefix_is_refused_with_listing() {
+        let base = archive();
+        let err = find_session_by_id(base.path(), "ab").err().unwrap().to_string();
+        assert!(err.contains("matches 2 sessions"));
+        assert!(err.contains("abc123") && err.contains("abd456"));
+    }
+
+    #[test]
+    fn unique_prefix_and_exact_id_resolve() {
+        let base = archive();
+        assert_eq!(find_session_by_id(base.path(), "abc").unwrap().meta.id, "abc123");
+        assert_eq!(find_session_by_id(base.path(), "xyz789").unwrap().meta.id, "xyz789");
+        assert!(find_session_by_id(base.path(), "nope").is_err());
+    }
+
+    #[test]
+    fn delete_removes_session_and_empty_date_dir() {
+        let base = archive();
+
+        let session = find_session_by_id(base.path(), "xyz").unwrap();
+        remove_session_dir(base.path(), &session.path).unwrap();
+        assert!(!base.path().join("gemini-cli/2026-02-11").exists());
+
+        let session = find_session_by_id(base.path(), "abc").unwrap();
+        remove_session_dir(base.path(), &session.path).unwrap();
+        assert!(!session.path.exists());
+        assert!(base.path().join("claude-code/2026-02-10/abd456").exists());
+    }
+
+    #[test]
+    fn refuses_paths_outside_session_layout() {
+        let base = archive();
+        let date_dir = base.path().join("claude-code/2026-02-10");
+        assert!(remove_session_dir(base.path(), &date_dir).is_err());
+        assert!(date_dir.exists());
+    }
+}
@@ -0,0 +1,233 @@
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use serde_json::Value;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::load;
+
+/// One session in the export: where it lives in the archive, its
+/// `session.json` and every readable message, both exactly as stored so
+/// fields only some importers write come along too
+#[derive(Serialize)]
+struct ExportedSession {
+    /// `<assistant>/<date>/<session>`, relative to the archive
+    path: String,
+    session: Value,
+    messages: Vec<Value>,
+}
+
+/// What an export wrote
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct ExportCounts {
+    pub(crate) sessions: usize,
+    pub(crate) messages: usize,
+    /// Lines of `messages.jsonl` that weren't a message, and were left out
+    pub(crate) unreadable_lines: usize,
+}
+
+pub fn run(jsonl: bool, output: Option<&Path>) -> Result<()> {
+    let base_dir = dirs::home_dir()
+        .context("No home directory")?
+        .join("Assistants/continuum-logs");
+    if !base_dir.exists() {
+        bail!("Continuum logs directory not found: {}", base_dir.display());
+    }
+
+    let counts = match output {
+        Some(path) => {
+            let file = std::fs::File::create(path)
+                .with_context(|| format!("Failed to create {}", path.display()))?;
+            let mut out = std::io::BufWriter::new(file);
+            let counts = export_archive(&base_dir, &mut out, jsonl)?;
+            out.flush()?;
+            counts
+        }
+        None => export_archive(&base_dir, &mut std::io::stdout().lock(), jsonl)?,
+    };
+
+    eprintln!("Exported {} sessions ({} messages)", counts.sessions, counts.messages);
+    if counts.unreadable_lines > 0 {
+        eprintln!(
+            "Left out {} unreadable message lines (`continuum-activity check --verbose` lists them)",
+            counts.unreadable_lines
+        );
+    }
+    if let Some(path) = output {
+        eprintln!("Written to {}", path.display());
+    }
+    Ok(())
+}
+
+/// Write every session in the archive to `out`, ordered by assistant, then
+/// start time. As JSONL each session is one line; otherwise the sessions
+/// form a single JSON array, still one session per line, so the archive is
+/// streamed rather than held in memory.
+pub(crate) fn export_archive(base_dir: &Path, out: &mut impl Write, jsonl: bool) -> Result<ExportCounts> {
+    let mut sessions = load::collect_sessions(base_dir, None, None)?;
+    sessions.sort_by(|a, b| {
+        (&a.meta.assistant, &a.meta.start_time, &a.path).cmp(&(&b.meta.assistant, &b.meta.start_time, &b.path))
+    });
+
+    let mut counts = ExportCounts::default();
+    if !jsonl {
+        write!(out, "[")?;
+    }
+    for (i, session) in sessions.iter().enumerate() {
+        let (messages, unreadable) = read_messages(&session.path)?;
+        counts.sessions += 1;
+        counts.messages += messages.len();
+        counts.unreadable_lines += unreadable;
+
+        let exported = ExportedSession {
+            path: relative_path(base_dir, &session.path),
+            session: read_session(&session.path)?,
+            messages,
+        };
+        let line = serde_json::to_string(&exported)?;
+        if jsonl {
+            writeln!(out, "{}", line)?;
+        } else {
+            write!(out, "{}\n{}", if i == 0 { "" } else { "," }, line)?;
+        }
+    }
+    if !jsonl {
+        writeln!(out, "\n]")?;
+    }
+    Ok(counts)
+}
+
+/// A session's `session.json`, every field kept
+fn read_session(session_dir: &Path) -> Result<Value> {
+    let path = session_dir.join("session.json");
+    let content = std::fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// A session's messages in file order, every field kept, and the number of
+/// lines that couldn't be read as one. A missing `messages.jsonl` is no
+/// messages.
+fn read_messages(session_dir: &Path) -> Result<(Vec<Value>, usize)> {
+    let path = session_dir.join("messages.jsonl");
+    if !path.exists() {
+        return Ok((Vec::new(), 0));
+    }
+    let content = std::fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let mut messages = Vec::new();
+    let mut unreadable = 0;
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        match serde_json::from_str::<Value>(line) {
+            Ok(msg) if is_message(&msg) => messages.push(msg),
+            _ => unreadable += 1,
+        }
+    }
+    Ok((messages, unreadable))
+}
+
+/// A `messages.jsonl` line needs at least a role and content
+fn is_message(line: &Value) -> bool {
+    line.get("role").is_some_and(Value::is_string) && line.get("content").is_some_and(Value::is_string)
+}
+
+fn relative_path(base_dir: &Path, path: &Path) -> String {
+    path.strip_prefix(base_dir)
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| path.to_path_buf())
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn write_session(base: &Path, assistant: &str, id: &str, start: &str, messages: &[&str]) {
+        let dir = base.join(assistant).join(&start[..10]).join(id);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("session.json"),
+            format!(r#"{{"id":"{id}","assistant":"{assistant}","start_time":"{start}","skills":["stoic"]}}"#),
+        )
+        .unwrap();
+        fs::write(dir.join("messages.jsonl"), messages.join("\n")).unwrap();
+    }
+
+    fn archive() -> tempfile::TempDir {
+        let base = tempfile::tempdir().unwrap();
+        write_session(base.path(), "grok", "g1", "2026-03-02T08:00:00Z", &[
+            r#"{"id": 1, "role": "user", "content": "Later", "timestamp": "2026-03-02T08:00:00Z"}"#,
+        ]);
+        write_session(base.path(), "chatgpt", "c2", "2026-03-02T09:00:00Z", &[
+            r#"{"role": "user", "content": "No id or time"}"#,
+            r#"{"id": 2, "role": "assistant", "content": "Hi there"#,
+        ]);
+        write_session(base.path(), "chatgpt", "c1", "2026-03-01T09:00:00Z", &[
+            r#"{"id": 1, "role": "user", "content": "What did Seneca say about time?", "timestamp": "2026-03-01T09:00:00Z"}"#,
+            r#"{"id": 2, "role": "assistant", "content": "That it is ours.", "timestamp": "2026-03-01T09:00:20Z"}"#,
+        ]);
+        base
+    }
+
+    fn export(base: &Path, jsonl: bool) -> (String, ExportCounts) {
+        let mut out = Vec::new();
+        let counts = export_archive(base, &mut out, jsonl).unwrap();
+        (String::from_utf8(out).unwrap(), counts)
+    }
+
+    #[test]
+    fn jsonl_has_one_session_per_line_in_order() {
+        let base = archive();
+        let (text, counts) = export(base.path(), true);
+        assert_eq!(counts, ExportCounts { sessions: 3, messages: 4, unreadable_lines: 1 });
+
+        let lines: Vec<serde_json::Value> = text.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        let ids: Vec<&str> = lines.iter().map(|s| s["session"]["id"].as_str().unwrap()).collect();
+        assert_eq!(ids, ["c1", "c2", "g1"]);
+
+        let first = &lines[0];
+        assert_eq!(first["path"], "chatgpt/2026-03-01/c1");
+        assert_eq!(first["session"]["skills"][0], "stoic");
+        assert_eq!(first["messages"][1]["content"], "That it is ours.");
+        assert_eq!(first["messages"][1]["timestamp"], "2026-03-01T09:00:20Z");
+        // Fields a message lacks are null, not invented
+        assert!(lines[1]["messages"][0]["timestamp"].is_null());
+    }
+
+    #[test]
+    fn json_is_a_single_array_of_the_same_sessions() {
+        let base = archive();
+        let (jsonl, _) = export(base.path(), true);
+        let (json, _) = export(base.path(), false);
+
+        let array: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+        let lines: Vec<serde_json::Value> = jsonl.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(array, lines);
+    }
+
+    #[test]
+    fn empty_archive_is_an_empty_array() {
+        let base = tempfile::tempdir().unwrap();
+        let (json, counts) = export(base.path(), false);
+        assert_eq!(serde_json::from_str::<Vec<serde_json::Value>>(&json).unwrap(), Vec::<serde_json::Value>::new());
+        assert_eq!(counts, ExportCounts::default());
+        assert_eq!(export(base.path(), true).0, "");
+    }
+
+    #[test]
+    fn fields_the_loader_ignores_are_kept() {
+        let base = tempfile::tempdir().unwrap();
+        let dir = base.path().join("grok/2026-03-01/g1");
+        fs::create_dir_all(&dir).unwrap();
+        let session = r#"{"id":"g1","assistant":"grok","start_time":"2026-03-01T09:00:00Z","status":"completed","source_url":"https://grok.com/c/g1"}"#;
+        let message = r#"{"id":1,"role":"assistant","content":"Either way","timestamp":"2026-03-01T09:00:00Z","branch":2}"#;
+        fs::write(dir.join("session.json"), session).unwrap();
+        fs::write(dir.join("messages.jsonl"), message).unwrap();
+
+        let (text, _) = export(base.path(), true);
+        let exported: serde_json::Value = serde_json::from_str(text.trim_end()).unwrap();
+        assert_eq!(exported["session"], serde_json::from_str::<serde_json::Value>(session).unwrap());
+        assert_eq!(exported["messages"][0], serde_json::from_str::<serde_json::Value>(message).unwrap());
+    }
+}
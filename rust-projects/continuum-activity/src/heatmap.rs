@@ -0,0 +1,175 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+/// Colour per intensity level, from an empty day to the busiest
+/// (256-colour greys and greens, as on a GitHub contribution graph)
+const COLOURS: [u8; 5] = [238, 22, 28, 34, 40];
+/// The same levels as shading, for output that isn't a terminal
+const SHADES: [char; 5] = ['·', '░', '▒', '▓', '█'];
+const RESET: &str = "\x1b[0m";
+
+const WEEKDAYS: [&str; 7] = ["Mon", "", "Wed", "", "Fri", "", "Sun"];
+/// Width of the weekday label column
+const LABEL_WIDTH: usize = 4;
+
+/// Intensity 0-4 for `count` out of the range's busiest day: 0 only for no
+/// sessions, 4 for the busiest, the rest in even steps between
+fn level(count: usize, max: usize) -> usize {
+    if count == 0 || max == 0 {
+        return 0;
+    }
+    (count * 4).div_ceil(max).clamp(1, 4)
+}
+
+fn cell(level: usize, colour: bool) -> String {
+    if colour {
+        format!("\x1b[38;5;{}m■{}", COLOURS[level], RESET)
+    } else {
+        SHADES[level].to_string()
+    }
+}
+
+/// Session counts per day as a contribution grid: a row per weekday, a
+/// column per week starting Monday, month names over the week each month
+/// starts in. `days` must be consecutive, earliest first.
+pub fn render(days: &[(NaiveDate, usize)], colour: bool) -> String {
+    let Some(&(first, _)) = days.first() else {
+        return String::new();
+    };
+    let monday = first - Duration::days(first.weekday().num_days_from_monday() as i64);
+    let column = |date: NaiveDate| ((date - monday).num_days() / 7) as usize;
+    let weeks = days.last().map_or(0, |&(last, _)| column(last) + 1);
+    let max = days.iter().map(|&(_, n)| n).max().unwrap_or(0);
+
+    let mut grid = vec![vec![None; weeks]; 7];
+    for &(date, count) in days {
+        grid[date.weekday().num_days_from_monday() as usize][column(date)] = Some(count);
+    }
+
+    // Month labels over the first week starting in each month. Where one
+    // would run into the next (a month with a single week showing), the
+    // later one wins.
+    let mut labels: Vec<(usize, String)> = Vec::new();
+    let mut last_month = None;
+    for &(date, _) in days {
+        if date != first && date.weekday() != Weekday::Mon {
+            continue;
+        }
+        let month = (date.year(), date.month());
+        if last_month == Some(month) {
+            continue;
+        }
+        last_month = Some(month);
+        let at = LABEL_WIDTH + column(date) * 2;
+        if labels.last().is_some_and(|(prev, name)| prev + name.len() >= at) {
+            labels.pop();
+        }
+        labels.push((at, date.format("%b").to_string()));
+    }
+    let mut months = String::new();
+    for (at, name) in labels {
+        months.push_str(&" ".repeat(at - months.len()));
+        months.push_str(&name);
+    }
+
+    let mut out = format!("{}\n", months.trim_end());
+    for (weekday, row) in grid.iter().enumerate() {
+        let mut line = format!("{:<width$}", WEEKDAYS[weekday], width = LABEL_WIDTH);
+        for count in row {
+            match count {
+                Some(n) => line.push_str(&cell(level(*n, max), colour)),
+                None => line.push(' '),
+            }
+            line.push(' ');
+        }
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+
+    let legend: Vec<String> = (0..5).map(|l| cell(l, colour)).collect();
+    out.push_str(&format!("\n{}Less {} More\n", " ".repeat(LABEL_WIDTH), legend.join(" ")));
+
+    let total: usize = days.iter().map(|&(_, n)| n).sum();
+    let active = days.iter().filter(|&&(_, n)| n > 0).count();
+    out.push_str(&format!(
+        "{} session{} on {} of {} days",
+        total,
+        if total == 1 { "" } else { "s" },
+        active,
+        days.len()
+    ));
+    if let Some(&(busiest, n)) = days.iter().find(|&&(_, n)| n == max && n > 0) {
+        out.push_str(&format!(", busiest {} ({})", busiest.format("%a %Y-%m-%d"), n));
+    }
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn range(start: &str, counts: &[usize]) -> Vec<(NaiveDate, usize)> {
+        let start = date(start);
+        counts.iter().enumerate().map(|(i, &n)| (start + Duration::days(i as i64), n)).collect()
+    }
+
+    #[test]
+    fn levels_scale_to_busiest_day() {
+        assert_eq!(level(0, 8), 0);
+        assert_eq!(level(1, 8), 1);
+        assert_eq!(level(2, 8), 1);
+        assert_eq!(level(3, 8), 2);
+        assert_eq!(level(6, 8), 3);
+        assert_eq!(level(8, 8), 4);
+        assert_eq!(level(0, 0), 0);
+    }
+
+    #[test]
+    fn grid_has_weekday_rows_and_week_columns() {
+        // Mon 2026-01-19 to Tue 2026-02-10: four week columns
+        let mut counts = vec![0; 23];
+        counts[0] = 2; // Mon 19th
+        counts[2] = 1; // Wed 21st
+        counts[11] = 4; // Fri 30th
+        counts[14] = 3; // Mon 2nd
+        counts[22] = 8; // Tue 10th
+        let out = render(&range("2026-01-19", &counts), false);
+        let lines: Vec<&str> = out.lines().collect();
+
+        assert_eq!(lines[0], "    Jan Feb");
+        assert_eq!(lines[1], "Mon ░ · ▒ ·");
+        assert_eq!(lines[2], "    · · · █");
+        assert_eq!(lines[3], "Wed ░ · ·");
+        assert_eq!(lines[4], "    · · ·");
+        assert_eq!(lines[5], "Fri · ▒ ·");
+        assert_eq!(lines[7], "Sun · · ·");
+        assert_eq!(lines[9], "    Less · ░ ▒ ▓ █ More");
+        assert_eq!(lines[10], "18 sessions on 5 of 23 days, busiest Tue 2026-02-10 (8)");
+    }
+
+    #[test]
+    fn crowded_month_label_gives_way_to_the_next() {
+        // Wed 25th: February shows one week before March starts
+        let out = render(&range("2026-02-25", &[0; 14]), false);
+        assert_eq!(out.lines().next(), Some("      Mar"));
+    }
+
+    #[test]
+    fn colour_uses_ansi_intensity() {
+        let out = render(&range("2026-03-02", &[0, 5]), true);
+        assert!(out.contains("\x1b[38;5;238m■\x1b[0m"));
+        assert!(out.contains("\x1b[38;5;40m■\x1b[0m"));
+        assert!(!out.contains('█'));
+    }
+
+    #[test]
+    fn quiet_range_has_no_busiest_day() {
+        let out = render(&range("2026-03-02", &[0, 0]), false);
+        assert!(out.ends_with("0 sessions on 0 of 2 days\n"));
+        assert_eq!(render(&[], false), "");
+    }
+}
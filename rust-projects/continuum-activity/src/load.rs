@@ -1,17 +1,21 @@
 use anyhow::{bail, Context, Result};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
-#[derive(Debug, Deserialize)]
-struct SessionMeta {
-    id: String,
-    assistant: String,
-    start_time: Option<String>,
-    end_time: Option<String>,
-    message_count: Option<u32>,
+use crate::cache::SessionCache;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct SessionMeta {
+    pub(crate) id: String,
+    pub(crate) assistant: String,
+    pub(crate) start_time: Option<String>,
+    pub(crate) end_time: Option<String>,
+    pub(crate) message_count: Option<u32>,
     #[serde(default)]
-    skills: Vec<String>,
+    pub(crate) skills: Vec<String>,
+    pub(crate) title: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -20,9 +24,9 @@ struct Message {
     content: String,
 }
 
-struct SessionInfo {
-    path: PathBuf,
-    meta: SessionMeta,
+pub(crate) struct SessionInfo {
+    pub(crate) path: PathBuf,
+    pub(crate) meta: SessionMeta,
 }
 
 struct SessionMatch {
@@ -33,19 +37,19 @@ struct SessionMatch {
     relevance: Relevance,
 }
 
-struct Relevance {
+pub(crate) struct Relevance {
     /// Total occurrences of the query in the session
-    match_count: usize,
+    pub(crate) match_count: usize,
     /// Matches per 1000 tokens — how focused the session is on the topic
     density: f64,
     /// Whether the user (not just the assistant) mentions the query
     user_initiated: bool,
     /// Classification
-    tag: RelevanceTag,
+    pub(crate) tag: RelevanceTag,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-enum RelevanceTag {
+pub(crate) enum RelevanceTag {
     /// High density or user-initiated with multiple matches — core discussion
     Focused,
     /// Moderate engagement — topic is substantive but not the main thread
@@ -55,19 +59,18 @@ enum RelevanceTag {
 }
 
 // ANSI colour codes
-const BOLD: &str = "\x1b[1m";
-const DIM: &str = "\x1b[2m";
-const RESET: &str = "\x1b[0m";
+pub(crate) const BOLD: &str = "\x1b[1m";
+pub(crate) const DIM: &str = "\x1b[2m";
+pub(crate) const RESET: &str = "\x1b[0m";
 const GREEN: &str = "\x1b[32m";
 const YELLOW: &str = "\x1b[33m";
-const CYAN: &str = "\x1b[36m";
+pub(crate) const CYAN: &str = "\x1b[36m";
 const WHITE: &str = "\x1b[37m";
 const BRIGHT_GREEN: &str = "\x1b[92m";
 const BRIGHT_YELLOW: &str = "\x1b[93m";
 
 impl RelevanceTag {
-    #[allow(dead_code)]
-    fn label(&self) -> &'static str {
+    pub(crate) fn label(&self) -> &'static str {
         match self {
             RelevanceTag::Focused => "FOCUSED",
             RelevanceTag::Relevant => "relevant",
@@ -75,20 +78,59 @@ impl RelevanceTag {
         }
     }
 
-    fn coloured_label(&self) -> String {
+    pub(crate) fn coloured_label(&self) -> String {
         match self {
             RelevanceTag::Focused => format!("{BOLD}{BRIGHT_GREEN}FOCUSED{RESET}"),
             RelevanceTag::Relevant => format!("{BRIGHT_YELLOW}relevant{RESET}"),
             RelevanceTag::Mention => format!("{DIM}mention{RESET}"),
         }
     }
+
+    fn colour(&self) -> &'static str {
+        match self {
+            RelevanceTag::Focused => BRIGHT_GREEN,
+            RelevanceTag::Relevant => BRIGHT_YELLOW,
+            RelevanceTag::Mention => DIM,
+        }
+    }
+}
+
+/// Cells in the density bar shown beside each search result
+const BAR_WIDTH: usize = 8;
+
+/// Fixed-width bar showing `value` as a fraction of `max`, in eighth-cell steps
+fn score_bar(value: f64, max: f64) -> String {
+    const PARTIALS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+    let fraction = if max > 0.0 { (value / max).clamp(0.0, 1.0) } else { 0.0 };
+    let eighths = (fraction * (BAR_WIDTH * 8) as f64).round() as usize;
+    let full = eighths / 8;
+
+    let mut bar = "█".repeat(full);
+    if full < BAR_WIDTH {
+        let partial = eighths % 8;
+        if partial > 0 {
+            bar.push(PARTIALS[partial]);
+        }
+        let drawn = full + usize::from(partial > 0);
+        bar.push_str(&"░".repeat(BAR_WIDTH - drawn));
+    }
+    bar
 }
 
-fn estimate_tokens(text: &str) -> usize {
+/// Fenced blocks at least this long in an assistant message are treated as
+/// pasted tool output rather than conversation
+const TOOL_BLOCK_MIN_LINES: usize = 40;
+
+/// Jaro-Winkler similarity above which a misremembered filter value still
+/// counts as a match
+const FUZZY_THRESHOLD: f64 = 0.85;
+
+pub(crate) fn estimate_tokens(text: &str) -> usize {
     (text.len() + 3) / 4
 }
 
-fn compute_relevance(cleaned_text: &str, query_lower: &str) -> Relevance {
+pub(crate) fn compute_relevance(cleaned_text: &str, query_lower: &str) -> Relevance {
     let text_lower = cleaned_text.to_lowercase();
     let match_count = text_lower.matches(query_lower).count();
     let tokens = estimate_tokens(cleaned_text).max(1);
@@ -127,6 +169,7 @@ pub fn load_session(
     search: Option<&str>,
     skill_filter: Option<&str>,
     all: bool,
+    include_tool_output: bool,
 ) -> Result<()> {
     let base_dir = dirs::home_dir()
         .context("No home directory")?
@@ -136,13 +179,27 @@ pub fn load_session(
         bail!("Continuum logs directory not found: {}", base_dir.display());
     }
 
+    let (assistant_filter, skill_filter) = if assistant_filter.is_some() || skill_filter.is_some() {
+        let sessions = collect_sessions(&base_dir, None, None)?;
+        let assistants: BTreeSet<String> = sessions.iter().map(|s| s.meta.assistant.clone()).collect();
+        let skills: BTreeSet<String> = sessions.iter().flat_map(|s| s.meta.skills.iter().cloned()).collect();
+        (
+            assistant_filter.map(|a| resolve_filter("assistant", a, &assistants)).transpose()?,
+            skill_filter.map(|s| resolve_filter("skill", s, &skills)).transpose()?,
+        )
+    } else {
+        (None, None)
+    };
+    let assistant_filter = assistant_filter.as_deref();
+    let skill_filter = skill_filter.as_deref();
+
     if let Some(query) = search {
-        return search_and_load(&base_dir, query, assistant_filter, skill_filter, all);
+        return search_and_load(&base_dir, query, assistant_filter, skill_filter, all, include_tool_output);
     }
 
     // If only --skill is provided (no search, no session_id, no last), search all sessions with that skill
     if skill_filter.is_some() && !last && session_id.is_none() {
-        return search_and_load(&base_dir, "", assistant_filter, skill_filter, all);
+        return search_and_load(&base_dir, "", assistant_filter, skill_filter, all, include_tool_output);
     }
 
     let session = if last {
@@ -160,7 +217,7 @@ pub fn load_session(
         bail!("Specify --last, --search, --skill, or provide a session ID");
     };
 
-    let text = build_cleaned_text(&session)?;
+    let text = build_cleaned_text(&session, include_tool_output)?;
     let tokens = estimate_tokens(&text);
     eprintln!(
         "Session: {} | {} | approx {}k tokens",
@@ -173,12 +230,59 @@ pub fn load_session(
     Ok(())
 }
 
+/// Resolve a possibly misremembered `--skill`/`--assistant` value against the
+/// values present in the archive. An exact (case-insensitive) match wins,
+/// then a unique prefix, then a unique fuzzy match; several candidates at
+/// the same stage are listed so the user can pick one.
+pub(crate) fn resolve_filter(kind: &str, query: &str, known: &BTreeSet<String>) -> Result<String> {
+    let query_lower = query.to_lowercase();
+
+    if let Some(exact) = known.iter().find(|k| k.to_lowercase() == query_lower) {
+        return Ok(exact.clone());
+    }
+
+    let prefixed: Vec<&String> = known
+        .iter()
+        .filter(|k| k.to_lowercase().starts_with(&query_lower))
+        .collect();
+    let candidates = if prefixed.is_empty() {
+        known
+            .iter()
+            .filter(|k| strsim::jaro_winkler(&k.to_lowercase(), &query_lower) >= FUZZY_THRESHOLD)
+            .collect()
+    } else {
+        prefixed
+    };
+
+    match candidates.as_slice() {
+        [] => {
+            let listing: Vec<&str> = known.iter().map(String::as_str).collect();
+            bail!("No {} matching '{}'. Known: {}", kind, query, listing.join(", "))
+        }
+        [only] => {
+            eprintln!("Using {} '{}' for '{}'", kind, only, query);
+            Ok((*only).clone())
+        }
+        many => {
+            let listing: Vec<String> = many.iter().map(|c| format!("  {}", c)).collect();
+            bail!(
+                "'{}' matches {} {}s; be more specific:\n{}",
+                query,
+                many.len(),
+                kind,
+                listing.join("\n")
+            )
+        }
+    }
+}
+
 fn search_and_load(
     base_dir: &Path,
     query: &str,
     assistant_filter: Option<&str>,
     skill_filter: Option<&str>,
     all: bool,
+    include_tool_output: bool,
 ) -> Result<()> {
     let sessions = collect_sessions(base_dir, assistant_filter, skill_filter)?;
     let query_lower = query.to_lowercase();
@@ -194,31 +298,13 @@ fn search_and_load(
         let raw = std::fs::read_to_string(&messages_path).unwrap_or_default();
 
         // If query is non-empty, filter by text match (messages + session title)
-        if !query_lower.is_empty() {
-            let raw_lower = raw.to_lowercase();
-            let title_lower = session
-                .meta
-                .id
-                .to_lowercase();
-            let session_json_path = session.path.join("session.json");
-            let title_from_meta = std::fs::read_to_string(&session_json_path)
-                .ok()
-                .and_then(|s| {
-                    serde_json::from_str::<serde_json::Value>(&s)
-                        .ok()
-                        .and_then(|v| v.get("title")?.as_str().map(|s| s.to_lowercase()))
-                })
-                .unwrap_or_default();
-
-            if !raw_lower.contains(&query_lower)
-                && !title_lower.contains(&query_lower)
-                && !title_from_meta.contains(&query_lower)
-            {
-                continue;
-            }
+        if !query_lower.is_empty() && !matches_query(&session, &raw, &query_lower) {
+            continue;
         }
 
-        let cleaned_text = build_cleaned_text(&session)?;
+        // Relevance is scored on this text, so collapsed tool output no
+        // longer dilutes the density or inflates the match count
+        let cleaned_text = build_cleaned_text(&session, include_tool_output)?;
         let approx_tokens = estimate_tokens(&cleaned_text);
 
         let (snippet, relevance) = if query_lower.is_empty() {
@@ -308,6 +394,12 @@ fn search_and_load(
     };
     let mut display = header;
 
+    let max_density = matches
+        .iter()
+        .map(|m| m.relevance.density)
+        .fold(0.0, f64::max);
+    let index_width = matches.len().to_string().len();
+
     let mut current_tier: Option<RelevanceTag> = None;
     for (i, m) in matches.iter().enumerate() {
         // Insert tier separator when the relevance tier changes
@@ -334,7 +426,23 @@ fn search_and_load(
             .message_count
             .map(|c| format!("{} msgs", c))
             .unwrap_or_else(|| "? msgs".to_string());
-        let coloured_tag = m.relevance.tag.coloured_label();
+        // Pad by the plain label's width since ANSI codes defeat format padding
+        let coloured_tag = format!(
+            "{}{}",
+            m.relevance.tag.coloured_label(),
+            " ".repeat(8 - m.relevance.tag.label().len()),
+        );
+        let score = if query_lower.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "{}{}{RESET} {:>4}× {DIM}{:>5.1}/1k{RESET} ",
+                m.relevance.tag.colour(),
+                score_bar(m.relevance.density, max_density),
+                m.relevance.match_count,
+                m.relevance.density,
+            )
+        };
         let user_flag = if m.relevance.user_initiated {
             format!("{GREEN}+{RESET}")
         } else {
@@ -349,20 +457,23 @@ fn search_and_load(
             format!(" {}", tags.join(" "))
         };
         display.push_str(&format!(
-            "  {BOLD}{WHITE}[{}]{RESET} {} {}{BOLD}{}{RESET}{} {DIM}|{RESET} {} {DIM}|{RESET} {} {DIM}| approx {}k tokens{RESET}\n",
+            "  {BOLD}{WHITE}[{:>width$}]{RESET} {} {}{}{BOLD}{}{RESET}{} {DIM}|{RESET} {} {DIM}|{RESET} {} {DIM}| approx {}k tokens{RESET}\n",
             i + 1,
             coloured_tag,
+            score,
             user_flag,
             m.session.meta.assistant,
             skill_tags,
             time,
             msgs,
             (m.approx_tokens + 500) / 1000,
+            width = index_width,
         ));
         if !query_lower.is_empty() {
             display.push_str(&format!(
-                "      {DIM}({} matches, {:.1}/1k density) \"{}\"{RESET}\n",
-                m.relevance.match_count, m.relevance.density, m.snippet,
+                "  {}{DIM}\"{}\"{RESET}\n",
+                " ".repeat(index_width + 3),
+                m.snippet,
             ));
         }
     }
@@ -506,6 +617,14 @@ fn display_with_pager(text: &str) {
     }
 }
 
+/// Whether the query appears in a session's messages, id or title
+pub(crate) fn matches_query(session: &SessionInfo, raw: &str, query_lower: &str) -> bool {
+    let title = session.meta.title.as_deref().unwrap_or_default().to_lowercase();
+    raw.to_lowercase().contains(query_lower)
+        || session.meta.id.to_lowercase().contains(query_lower)
+        || title.contains(query_lower)
+}
+
 fn extract_snippet(raw: &str, query_lower: &str) -> String {
     let raw_lower = raw.to_lowercase();
     if let Some(pos) = raw_lower.find(query_lower) {
@@ -531,7 +650,7 @@ fn extract_snippet(raw: &str, query_lower: &str) -> String {
     }
 }
 
-fn build_cleaned_text(session: &SessionInfo) -> Result<String> {
+pub(crate) fn build_cleaned_text(session: &SessionInfo, include_tool_output: bool) -> Result<String> {
     let messages_path = session.path.join("messages.jsonl");
     if !messages_path.exists() {
         bail!("No messages file found for session {}", session.meta.id);
@@ -546,7 +665,11 @@ fn build_cleaned_text(session: &SessionInfo) -> Result<String> {
         }
 
         if let Ok(msg) = serde_json::from_str::<Message>(line) {
-            let cleaned = clean_content(&msg.content);
+            let cleaned = if include_tool_output {
+                clean_content(&msg.content)
+            } else {
+                collapse_tool_output(&msg.role, &clean_content(&msg.content))
+            };
             if cleaned.is_empty() {
                 continue;
             }
@@ -562,12 +685,67 @@ fn build_cleaned_text(session: &SessionInfo) -> Result<String> {
     Ok(output)
 }
 
-fn collect_sessions(
+fn tool_output_placeholder(lines: usize) -> String {
+    format!("[tool output: {} lines omitted]", lines)
+}
+
+/// Replace tool results with a one-line placeholder: the whole of a `tool`
+/// message, or the long fenced blocks of an assistant message that is mostly
+/// fenced output (file dumps, test runs pasted back into the conversation).
+fn collapse_tool_output(role: &str, content: &str) -> String {
+    if content.is_empty() {
+        return String::new();
+    }
+    if role == "tool" {
+        return tool_output_placeholder(content.lines().count());
+    }
+    if role != "assistant" {
+        return content.to_string();
+    }
+
+    // Split into alternating prose and fenced segments
+    let mut segments: Vec<(bool, Vec<&str>)> = vec![(false, Vec::new())];
+    for line in content.lines() {
+        let is_fence = line.trim_start().starts_with("```");
+        let in_fence = segments.last().is_some_and(|(fenced, _)| *fenced);
+        if is_fence && !in_fence {
+            segments.push((true, vec![line]));
+        } else if is_fence && in_fence {
+            segments.last_mut().unwrap().1.push(line);
+            segments.push((false, Vec::new()));
+        } else {
+            segments.last_mut().unwrap().1.push(line);
+        }
+    }
+
+    let total = content.lines().count();
+    let long_fenced: usize = segments
+        .iter()
+        .filter(|(fenced, lines)| *fenced && lines.len() >= TOOL_BLOCK_MIN_LINES)
+        .map(|(_, lines)| lines.len())
+        .sum();
+    if long_fenced * 2 <= total {
+        return content.to_string();
+    }
+
+    let mut out: Vec<String> = Vec::new();
+    for (fenced, lines) in &segments {
+        if *fenced && lines.len() >= TOOL_BLOCK_MIN_LINES {
+            out.push(tool_output_placeholder(lines.len()));
+        } else {
+            out.extend(lines.iter().map(|l| l.to_string()));
+        }
+    }
+    out.join("\n").trim().to_string()
+}
+
+pub(crate) fn collect_sessions(
     base_dir: &Path,
     assistant_filter: Option<&str>,
     skill_filter: Option<&str>,
 ) -> Result<Vec<SessionInfo>> {
     let mut all_sessions = Vec::new();
+    let mut cache = SessionCache::open(base_dir);
 
     for assistant_entry in std::fs::read_dir(base_dir)?.flatten() {
         let assistant_dir = assistant_entry.path();
@@ -595,25 +773,24 @@ fn collect_sessions(
                     continue;
                 }
 
-                if let Ok(content) = std::fs::read_to_string(&session_json) {
-                    if let Ok(meta) = serde_json::from_str::<SessionMeta>(&content) {
-                        // Apply skill filter
-                        if let Some(skill) = skill_filter {
-                            if !meta.skills.iter().any(|s| s == skill) {
-                                continue;
-                            }
+                if let Ok(meta) = cache.meta(&session_json) {
+                    // Apply skill filter
+                    if let Some(skill) = skill_filter {
+                        if !meta.skills.iter().any(|s| s == skill) {
+                            continue;
                         }
-
-                        all_sessions.push(SessionInfo {
-                            path: session_dir,
-                            meta,
-                        });
                     }
+
+                    all_sessions.push(SessionInfo {
+                        path: session_dir,
+                        meta,
+                    });
                 }
             }
         }
     }
 
+    cache.save_or_warn();
     Ok(all_sessions)
 }
 
@@ -634,21 +811,40 @@ fn find_last_session(base_dir: &Path, assistant_filter: Option<&str>) -> Result<
     Ok(sessions.into_iter().next().unwrap())
 }
 
-fn find_session_by_id(base_dir: &Path, id: &str) -> Result<SessionInfo> {
+/// Resolve a session ID or prefix (matched against `session.json` ids and
+/// directory names). An exact match wins; a prefix must be unambiguous.
+pub(crate) fn find_session_by_id(base_dir: &Path, id: &str) -> Result<SessionInfo> {
     let sessions = collect_sessions(base_dir, None, None)?;
 
-    for session in sessions {
-        if session.meta.id == id || session.meta.id.starts_with(id) {
-            return Ok(session);
-        }
-        if let Some(dir_name) = session.path.file_name().and_then(|n| n.to_str()) {
-            if dir_name == id || dir_name.starts_with(id) {
-                return Ok(session);
-            }
-        }
+    let dir_name = |s: &SessionInfo| s.path.file_name().and_then(|n| n.to_str()).map(str::to_string);
+    let (exact, mut prefixed): (Vec<SessionInfo>, Vec<SessionInfo>) = sessions
+        .into_iter()
+        .filter(|s| s.meta.id.starts_with(id) || dir_name(s).is_some_and(|d| d.starts_with(id)))
+        .partition(|s| s.meta.id == id || dir_name(s).as_deref() == Some(id));
+
+    if let Some(session) = exact.into_iter().next() {
+        return Ok(session);
     }
 
-    bail!("No session found matching ID '{}'", id);
+    match prefixed.len() {
+        0 => bail!("No session found matching ID '{}'", id),
+        1 => Ok(prefixed.remove(0)),
+        n => {
+            prefixed.sort_by(|a, b| a.meta.start_time.cmp(&b.meta.start_time));
+            let listing: Vec<String> = prefixed
+                .iter()
+                .map(|s| {
+                    format!(
+                        "  {}  {}  {}",
+                        s.meta.id,
+                        s.meta.assistant,
+                        format_time_range(&s.meta.start_time, &s.meta.end_time)
+                    )
+                })
+                .collect();
+            bail!("'{}' matches {} sessions; use a longer prefix:\n{}", id, n, listing.join("\n"))
+        }
+    }
 }
 
 /// Strip system scaffolding, tool XML, and command noise from message content.
@@ -676,7 +872,7 @@ fn clean_content(content: &str) -> String {
             if let Some(rest) = rest.strip_prefix('/') {
                 if let Some(name) = rest.split('>').next() {
                     let name = name.split_whitespace().next().unwrap_or(name);
-                    if skip_tags.iter().any(|t| *t == name) {
+                    if skip_tags.contains(&name) {
                         in_tag = false;
                         tag_name.clear();
                         continue;
@@ -686,7 +882,7 @@ fn clean_content(content: &str) -> String {
                 rest.split('>').next().or_else(|| rest.split_whitespace().next())
             {
                 let name = name.trim_end_matches('/');
-                if skip_tags.iter().any(|t| *t == name) {
+                if skip_tags.contains(&name) {
                     in_tag = true;
                     tag_name = name.to_string();
                     continue;
@@ -724,7 +920,7 @@ fn clean_content(content: &str) -> String {
     collapsed.trim().to_string()
 }
 
-fn format_time_range(start: &Option<String>, end: &Option<String>) -> String {
+pub(crate) fn format_time_range(start: &Option<String>, end: &Option<String>) -> String {
     let parse = |s: &str| -> Option<String> {
         chrono::DateTime::parse_from_rfc3339(s)
             .ok()
@@ -740,3 +936,99 @@ fn format_time_range(start: &Option<String>, end: &Option<String>) -> String {
         _ => "unknown time".to_string(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn score_bar_scales_to_the_best_match() {
+        assert_eq!(score_bar(4.0, 4.0), "████████");
+        assert_eq!(score_bar(2.0, 4.0), "████░░░░");
+        assert_eq!(score_bar(0.0, 4.0), "░░░░░░░░");
+        // Eighth-cell steps for the remainder
+        assert_eq!(score_bar(1.0, 64.0), "▏░░░░░░░");
+        assert_eq!(score_bar(27.0, 64.0), "███▍░░░░");
+    }
+
+    #[test]
+    fn score_bar_clamps_and_handles_zero_max() {
+        assert_eq!(score_bar(9.0, 4.0), "████████");
+        assert_eq!(score_bar(1.0, 0.0), "░░░░░░░░");
+        for (value, max) in [(1.0, 3.0), (5.0, 7.0), (0.1, 100.0)] {
+            assert_eq!(score_bar(value, max).chars().count(), BAR_WIDTH);
+        }
+    }
+
+    fn fixture_session() -> SessionInfo {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/tool-dump-session");
+        let meta = std::fs::read_to_string(path.join("session.json")).unwrap();
+        SessionInfo {
+            meta: serde_json::from_str(&meta).unwrap(),
+            path,
+        }
+    }
+
+    #[test]
+    fn collapses_tool_dump_and_long_fenced_output() {
+        let text = build_cleaned_text(&fixture_session(), false).unwrap();
+        assert!(text.contains("[tool]\n[tool output: 500 lines omitted]"));
+        assert!(text.contains("Here's the current file:\n\n[tool output: 61 lines omitted]"));
+        assert!(!text.contains("case_250"));
+        assert!(text.contains("collect the tokens first"));
+    }
+
+    #[test]
+    fn include_tool_output_keeps_everything() {
+        let text = build_cleaned_text(&fixture_session(), true).unwrap();
+        assert!(text.contains("case_500 ... ok"));
+        assert!(!text.contains("lines omitted"));
+    }
+
+    #[test]
+    fn collapsing_shrinks_tokens_and_sharpens_relevance() {
+        let session = fixture_session();
+        let full = build_cleaned_text(&session, true).unwrap();
+        let collapsed = build_cleaned_text(&session, false).unwrap();
+
+        assert!(estimate_tokens(&full) > 4000);
+        assert!(estimate_tokens(&collapsed) < 200);
+
+        let full_relevance = compute_relevance(&full, "borrow checker");
+        let collapsed_relevance = compute_relevance(&collapsed, "borrow checker");
+        assert_eq!(full_relevance.match_count, collapsed_relevance.match_count);
+        assert!(full_relevance.tag == RelevanceTag::Relevant);
+        assert!(collapsed_relevance.tag == RelevanceTag::Focused);
+    }
+
+    fn known(values: &[&str]) -> BTreeSet<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn filter_resolves_exact_prefix_and_fuzzy() {
+        let skills = known(&["senior-dev", "music-scr", "research"]);
+        assert_eq!(resolve_filter("skill", "Senior-Dev", &skills).unwrap(), "senior-dev");
+        assert_eq!(resolve_filter("skill", "senior", &skills).unwrap(), "senior-dev");
+        assert_eq!(resolve_filter("skill", "senoir-dev", &skills).unwrap(), "senior-dev");
+        assert_eq!(resolve_filter("skill", "musc-scr", &skills).unwrap(), "music-scr");
+    }
+
+    #[test]
+    fn ambiguous_filter_lists_candidates() {
+        let assistants = known(&["gemini-cli", "gemini-web", "grok"]);
+        let err = resolve_filter("assistant", "gemini", &assistants).unwrap_err().to_string();
+        assert!(err.contains("matches 2 assistants"));
+        assert!(err.contains("gemini-cli") && err.contains("gemini-web"));
+
+        let err = resolve_filter("assistant", "chatgpt", &assistants).unwrap_err().to_string();
+        assert!(err.contains("No assistant matching 'chatgpt'"));
+    }
+
+    #[test]
+    fn short_fenced_blocks_are_kept() {
+        let content = "Try this:\n```rust\nlet x = 1;\n```";
+        assert_eq!(collapse_tool_output("assistant", content), content);
+        assert_eq!(collapse_tool_output("user", "```\nlog\n```"), "```\nlog\n```");
+    }
+}
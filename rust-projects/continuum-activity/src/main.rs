@@ -1,14 +1,25 @@
 mod backfill;
+mod browse;
+mod cache;
 mod cc_logs;
+mod check;
 mod clean;
 mod continuum;
+mod cost;
+mod delete;
+mod export;
+mod heatmap;
 mod load;
 mod output;
+mod thread;
 mod types;
+mod vault;
 
 use anyhow::Result;
 use chrono::{Local, NaiveDate};
 use clap::{Parser, Subcommand};
+use std::io::IsTerminal;
+use std::path::PathBuf;
 
 use types::DayActivity;
 
@@ -29,6 +40,14 @@ struct ReportArgs {
     /// Target date (YYYY-MM-DD). Defaults to today.
     date: Option<NaiveDate>,
 
+    /// Report every day from the target date to this one (inclusive)
+    #[arg(long, value_name = "DATE")]
+    to: Option<NaiveDate>,
+
+    /// With --to, show sessions per day as a weekly grid instead of the report
+    #[arg(long, requires = "to", conflicts_with = "json")]
+    heatmap: bool,
+
     /// Output as JSON instead of markdown
     #[arg(long)]
     json: bool,
@@ -40,16 +59,30 @@ struct ReportArgs {
     /// Only show Claude Code sessions (skip Continuum archive)
     #[arg(long)]
     cc_only: bool,
+
+    /// Comma-separated directories whose edits count as vault changes
+    #[arg(long, value_delimiter = ',', default_value = vault::DEFAULT_VAULT_DIRS)]
+    vault_dirs: Vec<String>,
 }
 
 #[derive(Subcommand)]
 enum Command {
     /// Load a session's full conversation text (for LLM context injection)
     Load(LoadArgs),
+    /// Browse sessions interactively; Enter prints the selected one like `load`
+    Browse(BrowseArgs),
     /// Deduplicate messages across all sessions and fix metadata
     Clean(CleanArgs),
     /// Backfill skills into existing session.json files
     Backfill(BackfillArgs),
+    /// Delete a session's directory from the archive
+    Delete(DeleteArgs),
+    /// Check the archive for structural problems; exits non-zero on errors
+    Check(CheckArgs),
+    /// Follow a topic across sessions: every match in date order, with extracts
+    Thread(ThreadArgs),
+    /// Dump every session, metadata and messages, as one JSON (or JSONL) stream
+    ExportJson(ExportArgs),
 }
 
 #[derive(clap::Args)]
@@ -61,7 +94,7 @@ struct LoadArgs {
     #[arg(long)]
     last: bool,
 
-    /// Filter by assistant name (e.g. gemini-cli, claude-code)
+    /// Filter by assistant name (e.g. gemini-cli, claude-code); prefixes and near-misses are accepted
     #[arg(long)]
     assistant: Option<String>,
 
@@ -69,13 +102,53 @@ struct LoadArgs {
     #[arg(long)]
     search: Option<String>,
 
-    /// Filter by skill name (e.g. senior-dev, music-scr)
+    /// Filter by skill name (e.g. senior-dev, music-scr); prefixes and near-misses are accepted
     #[arg(long)]
     skill: Option<String>,
 
     /// Load all matching sessions (non-interactive)
     #[arg(long)]
     all: bool,
+
+    /// Keep tool results in full instead of collapsing them to a placeholder
+    #[arg(long)]
+    include_tool_output: bool,
+}
+
+#[derive(clap::Args)]
+struct ThreadArgs {
+    /// Text to follow (matched like `load --search`)
+    query: String,
+
+    /// Output the thread as JSON
+    #[arg(long)]
+    json: bool,
+
+    /// Also write the thread as a markdown note with [[date]] links
+    #[arg(long, value_name = "FILE")]
+    link_file: Option<PathBuf>,
+}
+
+#[derive(clap::Args)]
+struct ExportArgs {
+    /// One session per line (JSONL) instead of a single JSON array
+    #[arg(long)]
+    jsonl: bool,
+
+    /// Write to this file instead of stdout
+    #[arg(long, short, value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+#[derive(clap::Args)]
+struct BrowseArgs {
+    /// Only list sessions from this assistant; prefixes and near-misses are accepted
+    #[arg(long)]
+    assistant: Option<String>,
+
+    /// Keep tool results in full instead of collapsing them to a placeholder
+    #[arg(long)]
+    include_tool_output: bool,
 }
 
 #[derive(clap::Args)]
@@ -89,6 +162,27 @@ struct CleanArgs {
     no_backup: bool,
 }
 
+#[derive(clap::Args)]
+struct DeleteArgs {
+    /// Session ID (or unambiguous prefix) to delete
+    session_id: String,
+
+    /// Delete without asking for confirmation
+    #[arg(long, short)]
+    yes: bool,
+}
+
+#[derive(clap::Args)]
+struct CheckArgs {
+    /// List the offending path for every violation
+    #[arg(long)]
+    verbose: bool,
+
+    /// Output the full report as JSON
+    #[arg(long)]
+    json: bool,
+}
+
 #[derive(clap::Args)]
 struct BackfillArgs {
     /// Preview changes without modifying files
@@ -101,7 +195,16 @@ fn main() -> Result<()> {
 
     match cli.command {
         Some(Command::Backfill(args)) => backfill::run(args.dry_run),
+        Some(Command::Browse(args)) => browse::browse(args.assistant.as_deref(), args.include_tool_output),
+        Some(Command::Check(args)) => {
+            if !check::run(args.verbose, args.json)? {
+                std::process::exit(1);
+            }
+            Ok(())
+        }
         Some(Command::Clean(args)) => clean::clean_logs(args.dry_run, args.no_backup),
+        Some(Command::Delete(args)) => delete::delete_session(&args.session_id, args.yes),
+        Some(Command::ExportJson(args)) => export::run(args.jsonl, args.output.as_deref()),
         Some(Command::Load(args)) => load::load_session(
             args.session_id.as_deref(),
             args.last,
@@ -109,39 +212,95 @@ fn main() -> Result<()> {
             args.search.as_deref(),
             args.skill.as_deref(),
             args.all,
+            args.include_tool_output,
         ),
+        Some(Command::Thread(args)) => thread::run(&args.query, args.json, args.link_file.as_deref()),
         None => run_report(cli.report),
     }
 }
 
 fn run_report(args: ReportArgs) -> Result<()> {
     let target_date = args.date.unwrap_or_else(|| Local::now().date_naive());
-    let date_str = target_date.format("%Y-%m-%d").to_string();
+    if let Some(end) = args.to {
+        return run_range(&args, target_date, end);
+    }
+    let activity = extract_day(&args, target_date)?;
 
-    let cc_sessions = cc_logs::extract_cc_sessions(target_date, args.verbose)?;
+    if activity.cc_sessions.is_empty() && activity.continuum_sessions.is_empty() {
+        eprintln!("No activity found for {}", activity.date);
+        return Ok(());
+    }
 
-    let continuum_sessions = if args.cc_only {
-        Vec::new()
+    if args.json {
+        println!("{}", output::render_json(&activity));
     } else {
-        continuum::extract_continuum_sessions(target_date)?
-    };
+        print!("{}", output::render_markdown(&activity));
+    }
 
-    if cc_sessions.is_empty() && continuum_sessions.is_empty() {
-        eprintln!("No activity found for {}", date_str);
+    Ok(())
+}
+
+/// The report for each day from `start` to `end`, or with `--heatmap` the
+/// grid of their session counts
+fn run_range(args: &ReportArgs, start: NaiveDate, end: NaiveDate) -> Result<()> {
+    if end < start {
+        anyhow::bail!("--to {} is before {}", end, start);
+    }
+
+    let days: Vec<DayActivity> = start
+        .iter_days()
+        .take_while(|day| *day <= end)
+        .map(|day| extract_day(args, day))
+        .collect::<Result<_>>()?;
+
+    if args.heatmap {
+        let counts: Vec<(NaiveDate, usize)> = start
+            .iter_days()
+            .zip(&days)
+            .map(|(day, activity)| (day, activity.cc_sessions.len() + activity.continuum_sessions.len()))
+            .collect();
+        print!("{}", heatmap::render(&counts, std::io::stdout().is_terminal()));
         return Ok(());
     }
 
-    let activity = DayActivity {
-        date: date_str,
-        cc_sessions,
-        continuum_sessions,
-    };
+    let active: Vec<&DayActivity> = days
+        .iter()
+        .filter(|day| !day.cc_sessions.is_empty() || !day.continuum_sessions.is_empty())
+        .collect();
+    if active.is_empty() {
+        eprintln!("No activity found from {} to {}", start, end);
+        return Ok(());
+    }
 
     if args.json {
-        println!("{}", output::render_json(&activity));
+        println!("{}", serde_json::to_string_pretty(&active)?);
     } else {
-        print!("{}", output::render_markdown(&activity));
+        let reports: Vec<String> = active.iter().map(|day| output::render_markdown(day)).collect();
+        print!("{}", reports.join("\n"));
     }
 
     Ok(())
 }
+
+/// Claude Code and Continuum sessions for one day
+fn extract_day(args: &ReportArgs, date: NaiveDate) -> Result<DayActivity> {
+    let mut cc_sessions = cc_logs::extract_cc_sessions(date, args.verbose)?;
+
+    let vault_dirs: Vec<_> = args.vault_dirs.iter().map(|d| vault::expand_tilde(d)).collect();
+    for session in &mut cc_sessions {
+        session.vault_files = vault::vault_files(&session.files_modified, &vault_dirs);
+    }
+
+    let continuum_sessions = if args.cc_only {
+        Vec::new()
+    } else {
+        continuum::extract_continuum_sessions(date)?
+    };
+
+    Ok(DayActivity {
+        date: date.format("%Y-%m-%d").to_string(),
+        cc_usage: cost::Usage::total(cc_sessions.iter().map(|s| s.usage.as_ref())),
+        cc_sessions,
+        continuum_sessions,
+    })
+}
@@ -1,4 +1,6 @@
+use crate::cost::Usage;
 use crate::types::{CcSession, DayActivity};
+use crate::vault;
 
 /// Replace home directory prefix with ~/
 fn tilde_path(path: &str) -> String {
@@ -56,12 +58,16 @@ pub fn render_markdown(activity: &DayActivity) -> String {
             } else {
                 &session.slug
             };
+            let vault_marker = if session.vault_files.is_empty() { "" } else { " \u{270e} vault" };
             out.push_str(&format!(
-                "\n### Session: {} ({})\n",
+                "\n### Session: {} ({}){}\n",
                 name,
-                format_time_range(session)
+                format_time_range(session),
+                vault_marker
             ));
 
+            out.push_str(&format!("Usage: {}\n", usage_summary(session.usage.as_ref())));
+
             if !session.skills.is_empty() {
                 out.push_str(&format!("Skills: {}\n", session.skills.join(", ")));
             }
@@ -73,6 +79,13 @@ pub fn render_markdown(activity: &DayActivity) -> String {
                 }
             }
 
+            if !session.vault_files.is_empty() {
+                out.push_str("Vault Notes:\n");
+                for item in vault::wikilink_items(&session.vault_files, vault::MAX_LISTED_NOTES) {
+                    out.push_str(&format!("- {}\n", item));
+                }
+            }
+
             if !session.user_messages.is_empty() {
                 out.push_str("User Requests (chronological):\n");
                 for (ts, msg) in &session.user_messages {
@@ -89,6 +102,8 @@ pub fn render_markdown(activity: &DayActivity) -> String {
                 out.push_str(&format!("Tool Usage: {}\n", tools.join(", ")));
             }
         }
+
+        out.push_str(&format!("\nDay total: {}\n", usage_summary(activity.cc_usage.as_ref())));
     }
 
     if !activity.continuum_sessions.is_empty() {
@@ -115,6 +130,11 @@ pub fn render_markdown(activity: &DayActivity) -> String {
     out
 }
 
+/// Token usage and cost, or `n/a` for logs without usage data
+fn usage_summary(usage: Option<&Usage>) -> String {
+    usage.map(Usage::summary).unwrap_or_else(|| "n/a".to_string())
+}
+
 /// Render the activity report as JSON.
 pub fn render_json(activity: &DayActivity) -> String {
     serde_json::to_string_pretty(activity).unwrap_or_else(|_| "{}".to_string())
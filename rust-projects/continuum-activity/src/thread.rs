@@ -0,0 +1,342 @@
+use anyhow::{bail, Context, Result};
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::ops::Range;
+use std::path::Path;
+
+use crate::load::{self, RelevanceTag, SessionInfo, BOLD, CYAN, DIM, RESET};
+
+/// Lines of conversation shown under each session
+const EXTRACT_LINES: usize = 3;
+/// Longest an extract line gets before it's trimmed around the match
+const EXTRACT_LINE_CHARS: usize = 160;
+
+/// One session in a thread
+#[derive(Serialize)]
+pub(crate) struct ThreadEntry {
+    pub(crate) date: String,
+    pub(crate) assistant: String,
+    pub(crate) session_id: String,
+    pub(crate) title: Option<String>,
+    pub(crate) start_time: Option<String>,
+    /// FOCUSED, relevant or mention, as in `load --search`
+    pub(crate) relevance: &'static str,
+    #[serde(skip)]
+    pub(crate) tag: RelevanceTag,
+    pub(crate) match_count: usize,
+    pub(crate) extract: Vec<String>,
+}
+
+/// Every session mentioning a topic, oldest first
+#[derive(Serialize)]
+pub(crate) struct Thread {
+    pub(crate) query: String,
+    pub(crate) sessions: usize,
+    pub(crate) first_date: Option<String>,
+    pub(crate) last_date: Option<String>,
+    /// Days from the first session to the last, counting both
+    pub(crate) span_days: Option<i64>,
+    pub(crate) entries: Vec<ThreadEntry>,
+}
+
+pub fn run(query: &str, json: bool, link_file: Option<&Path>) -> Result<()> {
+    let base_dir = dirs::home_dir()
+        .context("No home directory")?
+        .join("Assistants/continuum-logs");
+    if !base_dir.exists() {
+        bail!("Continuum logs directory not found: {}", base_dir.display());
+    }
+
+    let thread = build_thread(&base_dir, query)?;
+    if thread.entries.is_empty() {
+        bail!("No sessions found matching '{}'", query);
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&thread)?);
+    } else {
+        print!("{}", render_terminal(&thread));
+    }
+
+    if let Some(path) = link_file {
+        std::fs::write(path, render_note(&thread))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        eprintln!("Thread note written to {}", path.display());
+    }
+
+    Ok(())
+}
+
+/// Find the sessions matching `query` the way `load --search` does, with
+/// its relevance tags, and put them in date order
+pub(crate) fn build_thread(base_dir: &Path, query: &str) -> Result<Thread> {
+    let query_lower = query.to_lowercase();
+    let mut entries = Vec::new();
+
+    for session in load::collect_sessions(base_dir, None, None)? {
+        let Ok(raw) = std::fs::read_to_string(session.path.join("messages.jsonl")) else {
+            continue;
+        };
+        if !load::matches_query(&session, &raw, &query_lower) {
+            continue;
+        }
+
+        let cleaned = load::build_cleaned_text(&session, false)?;
+        let relevance = load::compute_relevance(&cleaned, &query_lower);
+        entries.push(ThreadEntry {
+            date: session_date(&session),
+            extract: extract(&cleaned, &query_lower),
+            relevance: relevance.tag.label(),
+            tag: relevance.tag,
+            match_count: relevance.match_count,
+            assistant: session.meta.assistant,
+            session_id: session.meta.id,
+            title: session.meta.title,
+            start_time: session.meta.start_time,
+        });
+    }
+
+    entries.sort_by(|a, b| (&a.date, &a.start_time).cmp(&(&b.date, &b.start_time)));
+
+    let first_date = entries.first().map(|e| e.date.clone());
+    let last_date = entries.last().map(|e| e.date.clone());
+    let parse = |d: &Option<String>| d.as_deref().and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok());
+    let span_days = parse(&first_date)
+        .zip(parse(&last_date))
+        .map(|(first, last)| (last - first).num_days() + 1);
+
+    Ok(Thread {
+        query: query.to_string(),
+        sessions: entries.len(),
+        first_date,
+        last_date,
+        span_days,
+        entries,
+    })
+}
+
+/// The session's start date, or else the date directory it's filed under
+fn session_date(session: &SessionInfo) -> String {
+    session
+        .meta
+        .start_time
+        .as_deref()
+        .and_then(|t| t.get(..10))
+        .or_else(|| session.path.parent()?.file_name()?.to_str())
+        .unwrap_or("undated")
+        .to_string()
+}
+
+/// Up to `EXTRACT_LINES` lines of `cleaned` text around its best matches
+fn extract(cleaned: &str, query_lower: &str) -> Vec<String> {
+    let lines: Vec<&str> = cleaned
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !is_role_marker(l))
+        .collect();
+    lines[best_window(&lines, query_lower, EXTRACT_LINES)]
+        .iter()
+        .map(|line| trim_around(line, query_lower, EXTRACT_LINE_CHARS))
+        .collect()
+}
+
+fn is_role_marker(line: &str) -> bool {
+    line.starts_with('[') && line.ends_with(']') && !line.contains(' ')
+}
+
+/// The run of `size` lines holding the most matches. Among equals, the one
+/// with the most matches on its middle line wins (so a lone match sits
+/// centred between its neighbours), then the earliest.
+fn best_window(lines: &[&str], query_lower: &str, size: usize) -> Range<usize> {
+    let size = size.min(lines.len());
+    if size == 0 {
+        return 0..0;
+    }
+    let counts: Vec<usize> = lines.iter().map(|l| l.to_lowercase().matches(query_lower).count()).collect();
+
+    let mut best = 0..size;
+    let mut best_key = (0, 0);
+    for start in 0..=lines.len() - size {
+        let window = start..start + size;
+        let key = (counts[window.clone()].iter().sum::<usize>(), counts[start + size / 2]);
+        if key > best_key {
+            best_key = key;
+            best = window;
+        }
+    }
+    best
+}
+
+/// `line` cut to `max` characters centred on the first match, with `…`
+/// marking what was cut
+fn trim_around(line: &str, query_lower: &str, max: usize) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    if chars.len() <= max {
+        return line.to_string();
+    }
+
+    let lower = line.to_lowercase();
+    let at = lower.find(query_lower).map_or(0, |byte| lower[..byte].chars().count());
+    let query_len = query_lower.chars().count();
+    let start = at
+        .saturating_sub(max.saturating_sub(query_len) / 2)
+        .min(chars.len() - max);
+    let end = start + max;
+
+    let mut out = String::new();
+    if start > 0 {
+        out.push('…');
+    }
+    out.extend(&chars[start..end]);
+    if end < chars.len() {
+        out.push('…');
+    }
+    out
+}
+
+fn summary(thread: &Thread) -> String {
+    let assistants: BTreeSet<&str> = thread.entries.iter().map(|e| e.assistant.as_str()).collect();
+    let span = match (&thread.first_date, &thread.last_date, thread.span_days) {
+        (Some(first), Some(last), Some(days)) if first != last => {
+            format!(" from {} to {} ({} days)", first, last, days)
+        }
+        (Some(first), _, _) => format!(" on {}", first),
+        _ => String::new(),
+    };
+    format!(
+        "{} session{}{} across {}",
+        thread.sessions,
+        if thread.sessions == 1 { "" } else { "s" },
+        span,
+        assistants.into_iter().collect::<Vec<_>>().join(", ")
+    )
+}
+
+fn render_terminal(thread: &Thread) -> String {
+    let mut out = format!("\n{BOLD}Thread: '{CYAN}{}{RESET}{BOLD}'{RESET}\n", thread.query);
+    for entry in &thread.entries {
+        out.push_str(&format!(
+            "\n{BOLD}{}{RESET}  {}  {}  {}\n",
+            entry.date,
+            entry.assistant,
+            entry.tag.coloured_label(),
+            entry.title.as_deref().unwrap_or("(untitled)")
+        ));
+        for line in &entry.extract {
+            out.push_str(&format!("  {DIM}│{RESET} {}\n", line));
+        }
+        out.push_str(&format!("  {DIM}continuum-activity load {}{RESET}\n", entry.session_id));
+    }
+    out.push_str(&format!("\n{}\n", summary(thread)));
+    out
+}
+
+/// The thread as a markdown note: each session under a `[[date]]` heading
+/// with its extract and the command that loads it
+pub(crate) fn render_note(thread: &Thread) -> String {
+    let mut out = format!("# Thread: {}\n\n{}\n", thread.query, summary(thread));
+    for entry in &thread.entries {
+        out.push_str(&format!(
+            "\n## [[{}]] {} — {}\n\n*{} · {} match{}*\n\n",
+            entry.date,
+            entry.assistant,
+            entry.title.as_deref().unwrap_or("(untitled)"),
+            entry.relevance,
+            entry.match_count,
+            if entry.match_count == 1 { "" } else { "es" }
+        ));
+        for line in &entry.extract {
+            out.push_str(&format!("> {}\n", line));
+        }
+        if !entry.extract.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(&format!("`continuum-activity load {}`\n", entry.session_id));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn window_takes_the_densest_run() {
+        let lines = ["intro", "practice once", "other", "practice twice", "practice again", "outro"];
+        assert_eq!(best_window(&lines, "practice", 3), 2..5);
+    }
+
+    #[test]
+    fn lone_match_is_centred() {
+        let lines = ["a", "b", "the practice plan", "c", "d"];
+        assert_eq!(best_window(&lines, "practice", 3), 1..4);
+        // At the very start there's nothing before it
+        assert_eq!(best_window(&["practice", "b", "c", "d"], "practice", 3), 0..3);
+    }
+
+    #[test]
+    fn window_fits_short_texts() {
+        assert_eq!(best_window(&["practice"], "practice", 3), 0..1);
+        assert_eq!(best_window(&[], "practice", 3), 0..0);
+        // No match anywhere: the opening lines
+        assert_eq!(best_window(&["a", "b", "c", "d"], "practice", 3), 0..3);
+    }
+
+    #[test]
+    fn long_lines_are_trimmed_around_the_match() {
+        let line = format!("{} Practice scheduling {}", "x".repeat(100), "y".repeat(100));
+        let trimmed = trim_around(&line, "practice scheduling", 41);
+        assert_eq!(trimmed, format!("…{} Practice scheduling {}…", "x".repeat(10), "y".repeat(10)));
+        assert_eq!(trim_around("short", "short", 41), "short");
+
+        let start = format!("Practice {}", "z".repeat(100));
+        assert_eq!(trim_around(&start, "practice", 20), format!("Practice {}…", "z".repeat(11)));
+    }
+
+    #[test]
+    fn extract_skips_role_markers_and_blank_lines() {
+        let cleaned = "[User]\nHow should I plan practice?\n\n[Assistant]\nSpace it out.\nPractice daily.\n\n";
+        assert_eq!(
+            extract(cleaned, "practice"),
+            vec!["How should I plan practice?", "Space it out.", "Practice daily."]
+        );
+    }
+
+    fn write_session(base: &Path, assistant: &str, date: &str, id: &str, messages: &[(&str, &str)]) {
+        let dir = base.join(assistant).join(date).join(id);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("session.json"),
+            format!(r#"{{"id":"{id}","assistant":"{assistant}","start_time":"{date}T09:00:00Z","title":"About {id}"}}"#),
+        )
+        .unwrap();
+        let lines: Vec<String> = messages
+            .iter()
+            .map(|(role, content)| serde_json::json!({ "role": role, "content": content }).to_string())
+            .collect();
+        fs::write(dir.join("messages.jsonl"), lines.join("\n")).unwrap();
+    }
+
+    #[test]
+    fn thread_is_chronological_across_assistants() {
+        let base = tempfile::tempdir().unwrap();
+        write_session(base.path(), "grok", "2026-03-10", "later", &[("user", "More on practice scheduling"), ("assistant", "Sure.")]);
+        write_session(base.path(), "chatgpt", "2026-02-01", "first", &[("user", "Practice scheduling ideas?"), ("assistant", "Interleave.")]);
+        write_session(base.path(), "chatgpt", "2026-02-05", "unrelated", &[("user", "Tax forms"), ("assistant", "OK.")]);
+
+        let thread = build_thread(base.path(), "Practice Scheduling").unwrap();
+        let ids: Vec<&str> = thread.entries.iter().map(|e| e.session_id.as_str()).collect();
+        assert_eq!(ids, vec!["first", "later"]);
+        assert_eq!(thread.span_days, Some(38));
+        assert_eq!(thread.entries[0].extract, vec!["Practice scheduling ideas?", "Interleave."]);
+        assert_eq!(summary(&thread), "2 sessions from 2026-02-01 to 2026-03-10 (38 days) across chatgpt, grok");
+
+        let note = render_note(&thread);
+        assert!(note.starts_with("# Thread: Practice Scheduling\n"));
+        assert!(note.contains("\n## [[2026-02-01]] chatgpt — About first\n"));
+        assert!(note.contains("> Practice scheduling ideas?\n"));
+        assert!(note.contains("`continuum-activity load later`\n"));
+    }
+}
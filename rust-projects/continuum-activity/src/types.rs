@@ -3,6 +3,8 @@ use std::collections::BTreeMap;
 use chrono::{DateTime, Utc};
 use serde::Serialize;
 
+use crate::cost::Usage;
+
 /// A CC session with rich extracted data.
 #[derive(Debug, Serialize)]
 pub struct CcSession {
@@ -13,10 +15,14 @@ pub struct CcSession {
     pub skills: Vec<String>,
     /// file_path -> edit count
     pub files_modified: BTreeMap<String, u32>,
+    /// Edited files under the vault directories (full paths)
+    pub vault_files: Vec<String>,
     /// tool_name -> use count
     pub tool_usage: BTreeMap<String, u32>,
     /// (timestamp, truncated user message)
     pub user_messages: Vec<(DateTime<Utc>, String)>,
+    /// Tokens and estimated cost; None when the log has no usage blocks
+    pub usage: Option<Usage>,
 }
 
 /// A session from the Continuum archive (ChatGPT, Grok, Gemini, etc.).
@@ -35,5 +41,7 @@ pub struct ContinuumSession {
 pub struct DayActivity {
     pub date: String,
     pub cc_sessions: Vec<CcSession>,
+    /// Usage across the CC sessions that reported any
+    pub cc_usage: Option<Usage>,
     pub continuum_sessions: Vec<ContinuumSession>,
 }
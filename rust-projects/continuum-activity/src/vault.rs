@@ -0,0 +1,123 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Directories treated as "the vault" when --vault-dirs isn't given.
+pub const DEFAULT_VAULT_DIRS: &str = "~/Forge,~/Admin";
+
+/// Maximum note names listed under a session before "+N more".
+pub const MAX_LISTED_NOTES: usize = 5;
+
+/// Expand a leading `~` or `~/` to the home directory.
+pub fn expand_tilde(path: &str) -> PathBuf {
+    if path == "~" {
+        return dirs::home_dir().unwrap_or_else(|| PathBuf::from(path));
+    }
+    if let Some(rest) = path.strip_prefix("~/") {
+        if let Some(home) = dirs::home_dir() {
+            return home.join(rest);
+        }
+    }
+    PathBuf::from(path)
+}
+
+/// Edited files that live under any of `vault_dirs`, in path order.
+///
+/// Matching is by whole path components, so `~/Forge` does not claim
+/// `~/Forge-old/x.md`.
+pub fn vault_files(files_modified: &BTreeMap<String, u32>, vault_dirs: &[PathBuf]) -> Vec<String> {
+    files_modified
+        .keys()
+        .filter(|path| vault_dirs.iter().any(|dir| Path::new(path).starts_with(dir)))
+        .cloned()
+        .collect()
+}
+
+/// Wikilink list items for edited vault files: `[[note]]` per distinct note,
+/// capped at `cap` entries plus a trailing `+N more`.
+///
+/// Markdown notes link by stem (`[[Sleep]]`); other files keep their
+/// extension, as Obsidian links attachments (`[[diagram.png]]`).
+pub fn wikilink_items(paths: &[String], cap: usize) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for path in paths {
+        let name = note_name(Path::new(path));
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    let mut items: Vec<String> = names.iter().take(cap).map(|n| format!("[[{n}]]")).collect();
+    if names.len() > cap {
+        items.push(format!("+{} more", names.len() - cap));
+    }
+    items
+}
+
+fn note_name(path: &Path) -> String {
+    let is_markdown = path.extension().is_some_and(|e| e == "md");
+    let name = if is_markdown { path.file_stem() } else { path.file_name() };
+    name.map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string_lossy().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edits(paths: &[&str]) -> BTreeMap<String, u32> {
+        paths.iter().map(|p| (p.to_string(), 1)).collect()
+    }
+
+    #[test]
+    fn classifies_files_under_vault_dirs() {
+        let files = edits(&[
+            "/home/w/Forge/Sleep.md",
+            "/home/w/Admin/Invoices/2026-02.md",
+            "/home/w/dotfiles/rust-projects/x/src/main.rs",
+            "/home/w/Forge-old/Stale.md",
+        ]);
+        let dirs = vec![PathBuf::from("/home/w/Forge"), PathBuf::from("/home/w/Admin")];
+
+        assert_eq!(
+            vault_files(&files, &dirs),
+            vec!["/home/w/Admin/Invoices/2026-02.md", "/home/w/Forge/Sleep.md"]
+        );
+    }
+
+    #[test]
+    fn no_vault_dirs_matches_nothing() {
+        let files = edits(&["/home/w/Forge/Sleep.md"]);
+        assert!(vault_files(&files, &[]).is_empty());
+    }
+
+    #[test]
+    fn tilde_expands_to_home() {
+        let home = dirs::home_dir().unwrap();
+        assert_eq!(expand_tilde("~/Forge"), home.join("Forge"));
+        assert_eq!(expand_tilde("~"), home);
+        assert_eq!(expand_tilde("/srv/notes"), PathBuf::from("/srv/notes"));
+    }
+
+    #[test]
+    fn wikilinks_use_note_stems_and_dedupe() {
+        let paths = vec![
+            "/v/Forge/Sleep.md".to_string(),
+            "/v/Forge/NapierianLogs/DayPages/2026-02-10.md".to_string(),
+            "/v/Admin/Sleep.md".to_string(),
+            "/v/Forge/assets/diagram.png".to_string(),
+        ];
+        assert_eq!(
+            wikilink_items(&paths, 5),
+            vec!["[[Sleep]]", "[[2026-02-10]]", "[[diagram.png]]"]
+        );
+    }
+
+    #[test]
+    fn wikilinks_capped_with_more_marker() {
+        let paths: Vec<String> = (1..=8).map(|i| format!("/v/Forge/Note {i}.md")).collect();
+        let items = wikilink_items(&paths, MAX_LISTED_NOTES);
+        assert_eq!(items.len(), 6);
+        assert_eq!(items[4], "[[Note 5]]");
+        assert_eq!(items[5], "+3 more");
+    }
+}
@@ -0,0 +1,469 @@
+use anyhow::{Context, Result};
+use chrono::DateTime;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Per-session list of message hashes, written next to `messages.jsonl`
+pub const HASHES_FILE: &str = "hashes.json";
+
+/// Share of a new conversation's messages an existing session must already
+/// hold before the two are treated as the same conversation and merged
+pub const MERGE_THRESHOLD: f64 = 0.8;
+
+/// What `save_session` did with a conversation
+#[derive(Debug, PartialEq)]
+pub enum Saved {
+    /// Written as its own session directory
+    Created(PathBuf),
+    /// Every message is already in this existing session; nothing written
+    Skipped(PathBuf),
+    /// Mostly a repeat of this existing session; its new messages were
+    /// appended there
+    Merged { dir: PathBuf, added: usize },
+}
+
+impl Saved {
+    /// The session directory written to or matched
+    pub fn path(&self) -> &Path {
+        match self {
+            Saved::Created(path) | Saved::Skipped(path) => path,
+            Saved::Merged { dir, .. } => dir,
+        }
+    }
+}
+
+/// Stable hash of one message: FNV-1a 64 of the lowercased role and the
+/// trimmed content, as 16 hex digits. Converters differ in surrounding
+/// whitespace and role case, so both are normalised first.
+pub fn message_hash(role: &str, content: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    let role = role.trim().to_lowercase();
+    let bytes = role.bytes().chain([0u8]).chain(content.trim().bytes());
+    for byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+fn value_hash(message: &Value) -> String {
+    let field = |name| message.get(name).and_then(Value::as_str).unwrap_or("");
+    message_hash(field("role"), field("content"))
+}
+
+/// Message hashes of a session, in message order. Read from `hashes.json`,
+/// or computed from `messages.jsonl` for sessions written before it existed.
+pub fn read_hashes(session_dir: &Path) -> Result<Vec<String>> {
+    let hashes_path = session_dir.join(HASHES_FILE);
+    if let Ok(content) = fs::read_to_string(&hashes_path) {
+        if let Ok(hashes) = serde_json::from_str(&content) {
+            return Ok(hashes);
+        }
+    }
+    Ok(read_messages(session_dir)?.iter().map(value_hash).collect())
+}
+
+fn write_hashes(session_dir: &Path, hashes: &[String]) -> Result<()> {
+    let path = session_dir.join(HASHES_FILE);
+    fs::write(&path, serde_json::to_string_pretty(hashes)?)
+        .with_context(|| format!("Failed to write {:?}", path))
+}
+
+/// Parsed lines of a session's `messages.jsonl`; unparseable lines are left out
+fn read_messages(session_dir: &Path) -> Result<Vec<Value>> {
+    let path = session_dir.join("messages.jsonl");
+    let content = fs::read_to_string(&path).with_context(|| format!("Failed to read {:?}", path))?;
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Share of the distinct `new` hashes already in `existing` (0 for no hashes)
+pub fn overlap(existing: &HashSet<String>, new: &[String]) -> f64 {
+    let distinct: HashSet<&String> = new.iter().collect();
+    if distinct.is_empty() {
+        return 0.0;
+    }
+    let shared = distinct.iter().filter(|h| existing.contains(h.as_str())).count();
+    shared as f64 / distinct.len() as f64
+}
+
+/// How a new conversation relates to the sessions already on its date
+#[derive(Debug, PartialEq)]
+pub enum Decision {
+    Create,
+    Skip(PathBuf),
+    Merge(PathBuf),
+}
+
+/// Pick what to do with a conversation whose message hashes are `new`,
+/// given each existing session's hashes. A session holding every message
+/// wins (the most complete one if several do); otherwise the session with
+/// the highest overlap above `MERGE_THRESHOLD`.
+pub fn decide(candidates: &[(PathBuf, HashSet<String>)], new: &[String]) -> Decision {
+    if new.is_empty() {
+        return Decision::Create;
+    }
+
+    let superset = candidates
+        .iter()
+        .filter(|(_, hashes)| overlap(hashes, new) >= 1.0)
+        .max_by_key(|(_, hashes)| hashes.len());
+    if let Some((dir, _)) = superset {
+        return Decision::Skip(dir.clone());
+    }
+
+    candidates
+        .iter()
+        .map(|(dir, hashes)| (dir, overlap(hashes, new)))
+        .filter(|(_, share)| *share > MERGE_THRESHOLD)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map_or(Decision::Create, |(dir, _)| Decision::Merge(dir.clone()))
+}
+
+/// Other sessions in `day_dir` with their hashes, sorted by path. The
+/// session being written (`id`) is left out: re-importing it overwrites it.
+fn candidates(day_dir: &Path, id: &str) -> Result<Vec<(PathBuf, HashSet<String>)>> {
+    let Ok(entries) = fs::read_dir(day_dir) else {
+        return Ok(Vec::new());
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.file_name().is_some_and(|n| n != id))
+        .filter(|p| p.join("messages.jsonl").is_file())
+        .collect();
+    dirs.sort();
+
+    let mut candidates = Vec::new();
+    for dir in dirs {
+        let hashes = read_hashes(&dir)?;
+        candidates.push((dir, hashes.into_iter().collect()));
+    }
+    Ok(candidates)
+}
+
+/// Write a conversation as `day_dir/id/` (messages.jsonl, session.json and
+/// hashes.json), unless another session on the same day already holds it.
+///
+/// Messages must serialise with `role` and `content` fields, the session
+/// with `message_count` and `end_time`, as every continuum importer's do.
+pub fn save_session<M: Serialize, S: Serialize>(
+    day_dir: &Path,
+    id: &str,
+    messages: &[M],
+    session: &S,
+) -> Result<Saved> {
+    let messages: Vec<Value> = messages.iter().map(serde_json::to_value).collect::<Result<_, _>>()?;
+    let session = serde_json::to_value(session)?;
+    let hashes: Vec<String> = messages.iter().map(value_hash).collect();
+
+    match decide(&candidates(day_dir, id)?, &hashes) {
+        Decision::Skip(dir) => Ok(Saved::Skipped(dir)),
+        Decision::Merge(dir) => {
+            let added = merge_into(&dir, &messages, &session)?;
+            Ok(Saved::Merged { dir, added })
+        }
+        Decision::Create => {
+            let session_dir = day_dir.join(id);
+            fs::create_dir_all(&session_dir)
+                .with_context(|| format!("Failed to create {:?}", session_dir))?;
+            write_session(&session_dir, &messages, &session, &hashes)?;
+            Ok(Saved::Created(session_dir))
+        }
+    }
+}
+
+fn write_session(session_dir: &Path, messages: &[Value], session: &Value, hashes: &[String]) -> Result<()> {
+    let mut jsonl_content = String::new();
+    for msg in messages {
+        jsonl_content.push_str(&serde_json::to_string(msg)?);
+        jsonl_content.push('\n');
+    }
+    fs::write(session_dir.join("messages.jsonl"), jsonl_content)?;
+    fs::write(session_dir.join("session.json"), serde_json::to_string_pretty(session)?)?;
+    write_hashes(session_dir, hashes)
+}
+
+/// Append the messages of `messages` that come after what `session_dir`
+/// already holds, numbered on from the existing ones, and bring its
+/// `message_count` and `end_time` up to date. Returns the number of
+/// messages added.
+pub fn merge_into(session_dir: &Path, messages: &[Value], session: &Value) -> Result<usize> {
+    let mut existing = read_messages(session_dir)?;
+    let mut hashes: Vec<String> = existing.iter().map(value_hash).collect();
+    let new_hashes: Vec<String> = messages.iter().map(value_hash).collect();
+    let start = continue_from(&hashes, &new_hashes);
+    let mut next_id = existing
+        .iter()
+        .filter_map(|m| m.get("id").and_then(Value::as_u64))
+        .max()
+        .unwrap_or(0);
+
+    let mut added = 0;
+    for (message, hash) in messages.iter().zip(new_hashes).skip(start) {
+        next_id += 1;
+        let mut message = message.clone();
+        if let Some(fields) = message.as_object_mut() {
+            fields.insert("id".to_string(), next_id.into());
+        }
+        existing.push(message);
+        hashes.push(hash);
+        added += 1;
+    }
+
+    let session_path = session_dir.join("session.json");
+    let mut merged_session: Value = fs::read_to_string(&session_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_else(|| session.clone());
+    if let Some(fields) = merged_session.as_object_mut() {
+        fields.insert("message_count".to_string(), existing.len().into());
+        let end_time = later(
+            fields.get("end_time").and_then(Value::as_str),
+            session.get("end_time").and_then(Value::as_str),
+        );
+        if let Some(end_time) = end_time {
+            fields.insert("end_time".to_string(), end_time.into());
+        }
+    }
+
+    write_session(session_dir, &existing, &merged_session, &hashes)?;
+    Ok(added)
+}
+
+/// Where in `new` the messages after the stored ones begin: just past the
+/// last place the longest possible tail of `existing` occurs in `new`.
+/// Messages before that point are the overlap; any after it are kept, even
+/// ones that repeat earlier messages. If no tail occurs (the stored last
+/// message is missing from `new`), after the last message `existing` holds.
+fn continue_from(existing: &[String], new: &[String]) -> usize {
+    for len in (1..=existing.len().min(new.len())).rev() {
+        let tail = &existing[existing.len() - len..];
+        if let Some(pos) = new.windows(len).rposition(|window| window == tail) {
+            return pos + len;
+        }
+    }
+    let held: HashSet<&String> = existing.iter().collect();
+    new.iter().rposition(|h| held.contains(h)).map_or(0, |pos| pos + 1)
+}
+
+/// The later of two RFC 3339 timestamps; an unparseable one loses
+fn later<'a>(a: Option<&'a str>, b: Option<&'a str>) -> Option<&'a str> {
+    let parse = |t: &str| DateTime::parse_from_rfc3339(t).ok();
+    match (a, b) {
+        (Some(a), Some(b)) => match (parse(a), parse(b)) {
+            (Some(ta), Some(tb)) if tb > ta => Some(b),
+            (None, Some(_)) => Some(b),
+            _ => Some(a),
+        },
+        (a, b) => a.or(b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn fixture_day() -> tempfile::TempDir {
+        let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/2026-03-01");
+        let dir = tempfile::tempdir().unwrap();
+        for session in fs::read_dir(&src).unwrap().flatten() {
+            let dest = dir.path().join(session.file_name());
+            fs::create_dir_all(&dest).unwrap();
+            for file in fs::read_dir(session.path()).unwrap().flatten() {
+                fs::copy(file.path(), dest.join(file.file_name())).unwrap();
+            }
+        }
+        dir
+    }
+
+    /// The ten messages of the `browser-export` fixture session
+    fn stoic_messages() -> Vec<Value> {
+        read_messages(&fixture_day().path().join("browser-export")).unwrap()
+    }
+
+    fn session(id: &str, end_time: &str) -> Value {
+        json!({
+            "id": id,
+            "assistant": "chatgpt",
+            "start_time": "2026-03-01T09:00:00+00:00",
+            "end_time": end_time,
+            "message_count": 0,
+        })
+    }
+
+    fn hashes(messages: &[Value]) -> Vec<String> {
+        messages.iter().map(value_hash).collect()
+    }
+
+    fn set(hashes: &[String]) -> HashSet<String> {
+        hashes.iter().cloned().collect()
+    }
+
+    #[test]
+    fn hash_normalises_role_case_and_whitespace() {
+        assert_eq!(message_hash("user", "Hello"), message_hash(" User ", "\n  Hello \n"));
+        assert_ne!(message_hash("user", "Hello"), message_hash("assistant", "Hello"));
+        assert_ne!(message_hash("user", "Hello"), message_hash("user", "hello"));
+        // The separator keeps role and content from running together
+        assert_ne!(message_hash("user", "x"), message_hash("userx", ""));
+        // Stable across runs and builds
+        assert_eq!(message_hash("user", "Hello"), "d7745f004fc181c6");
+    }
+
+    #[test]
+    fn overlap_counts_distinct_new_messages() {
+        let existing = set(&["a".into(), "b".into(), "c".into()]);
+        let new: Vec<String> = ["a", "a", "b", "d"].iter().map(|s| s.to_string()).collect();
+        assert!((overlap(&existing, &new) - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(overlap(&existing, &[]), 0.0);
+    }
+
+    #[test]
+    fn decides_skip_merge_or_create() {
+        let ten: Vec<String> = (0..10).map(|i| i.to_string()).collect();
+        let day = |n: usize| (PathBuf::from(format!("s{}", n)), set(&ten[..n]));
+
+        // All present: skip, preferring the most complete session
+        assert_eq!(decide(&[day(8), day(10)], &ten[..5]), Decision::Skip("s10".into()));
+        // 9 of 10 present (> 80%): merge into the best match
+        assert_eq!(decide(&[day(5), day(9)], &ten), Decision::Merge("s9".into()));
+        // Exactly 80% is not enough
+        assert_eq!(decide(&[day(8)], &ten), Decision::Create);
+        assert_eq!(decide(&[], &ten), Decision::Create);
+        assert_eq!(decide(&[day(3)], &[]), Decision::Create);
+    }
+
+    #[test]
+    fn hashes_fall_back_to_messages_file() {
+        let day = fixture_day();
+        // The fixture predates hashes.json
+        let dir = day.path().join("browser-export");
+        assert!(!dir.join(HASHES_FILE).exists());
+        let computed = read_hashes(&dir).unwrap();
+        assert_eq!(computed.len(), 10);
+
+        write_hashes(&dir, &["stale".to_string()]).unwrap();
+        assert_eq!(read_hashes(&dir).unwrap(), vec!["stale".to_string()]);
+    }
+
+    #[test]
+    fn new_conversation_is_created_with_hashes() {
+        let day = fixture_day();
+        let messages = vec![
+            json!({"id": 1, "role": "user", "content": "Something else entirely", "timestamp": "t"}),
+            json!({"id": 2, "role": "assistant", "content": "Indeed", "timestamp": "t"}),
+        ];
+        let saved = save_session(day.path(), "fresh", &messages, &session("fresh", "2026-03-01T12:00:00+00:00")).unwrap();
+        let dir = day.path().join("fresh");
+        assert_eq!(saved, Saved::Created(dir.clone()));
+        assert_eq!(read_hashes(&dir).unwrap(), hashes(&messages));
+        assert_eq!(read_messages(&dir).unwrap(), messages);
+        assert!(dir.join(HASHES_FILE).exists());
+    }
+
+    #[test]
+    fn repeat_under_another_id_is_skipped() {
+        let day = fixture_day();
+        // The official export renders the same chat with different spacing
+        let mut messages = stoic_messages();
+        for message in &mut messages[..4] {
+            let content = message["content"].as_str().unwrap().to_string();
+            message["content"] = format!("  {}\n", content).into();
+        }
+        let saved = save_session(day.path(), "official-id", &messages[..6], &session("official-id", "x")).unwrap();
+        assert_eq!(saved, Saved::Skipped(day.path().join("browser-export")));
+        assert!(!day.path().join("official-id").exists());
+    }
+
+    #[test]
+    fn continued_conversation_is_merged() {
+        let day = fixture_day();
+        let dir = day.path().join("browser-export");
+        let mut messages = stoic_messages();
+        messages.push(json!({"id": 11, "role": "user", "content": "And Seneca?", "timestamp": "t"}));
+        messages.push(json!({"id": 12, "role": "assistant", "content": "Letters from a Stoic.", "timestamp": "t"}));
+        messages.push(json!({"id": 13, "role": "user", "content": "And Seneca?", "timestamp": "t"}));
+
+        let saved = save_session(day.path(), "official-id", &messages, &session("official-id", "2026-03-01T11:30:00+00:00")).unwrap();
+        assert_eq!(saved, Saved::Merged { dir: dir.clone(), added: 3 });
+        assert!(!day.path().join("official-id").exists());
+
+        // The repeated question is a message in its own right, and kept
+        let merged = read_messages(&dir).unwrap();
+        assert_eq!(merged.len(), 13);
+        assert_eq!(merged[10]["id"], 11);
+        assert_eq!(merged[11]["content"], "Letters from a Stoic.");
+        assert_eq!(merged[12]["content"], "And Seneca?");
+        assert_eq!(merged[12]["id"], 13);
+        assert_eq!(read_hashes(&dir).unwrap(), hashes(&merged));
+
+        let meta: Value = serde_json::from_str(&fs::read_to_string(dir.join("session.json")).unwrap()).unwrap();
+        assert_eq!(meta["id"], "browser-export");
+        assert_eq!(meta["message_count"], 13);
+        assert_eq!(meta["end_time"], "2026-03-01T11:30:00+00:00");
+        assert_eq!(meta["title"], "Stoic reading list");
+
+        // Importing it again is now a no-op
+        let again = save_session(day.path(), "official-id", &messages, &session("official-id", "x")).unwrap();
+        assert_eq!(again, Saved::Skipped(dir));
+    }
+
+    #[test]
+    fn continuation_starts_after_the_stored_tail() {
+        let h = |names: &str| names.split(' ').map(str::to_string).collect::<Vec<_>>();
+        // The whole stored session, then more
+        assert_eq!(continue_from(&h("q1 a1 ok q2"), &h("q1 a1 ok q2 ok q3")), 4);
+        // Only the end of it overlaps
+        assert_eq!(continue_from(&h("q1 a1 ok q2"), &h("ok q2 ok q3")), 2);
+        // The stored last message is missing: after the last one held
+        assert_eq!(continue_from(&h("q1 a1 q2 a2"), &h("q1 a1 q2 q3")), 3);
+        assert_eq!(continue_from(&h("q1"), &h("x y")), 0);
+    }
+
+    #[test]
+    fn merge_keeps_the_later_end_time() {
+        let day = fixture_day();
+        let dir = day.path().join("browser-export");
+        let mut messages = stoic_messages();
+        messages.push(json!({"id": 11, "role": "user", "content": "One more", "timestamp": "t"}));
+        save_session(day.path(), "other", &messages, &session("other", "2026-03-01T08:00:00+00:00")).unwrap();
+
+        let meta: Value = serde_json::from_str(&fs::read_to_string(dir.join("session.json")).unwrap()).unwrap();
+        assert_eq!(meta["end_time"], "2026-03-01T10:45:00+00:00");
+    }
+
+    #[test]
+    fn low_overlap_and_same_id_are_written_separately() {
+        let day = fixture_day();
+        // Half the messages shared: a different conversation
+        let mut messages = stoic_messages()[..5].to_vec();
+        for i in 0..5 {
+            messages.push(json!({"role": "user", "content": format!("new {}", i)}));
+        }
+        let saved = save_session(day.path(), "half", &messages, &session("half", "x")).unwrap();
+        assert_eq!(saved, Saved::Created(day.path().join("half")));
+
+        // Re-importing a session under its own id overwrites it
+        let day = fixture_day();
+        let messages = stoic_messages();
+        let saved = save_session(day.path(), "browser-export", &messages[..3], &session("browser-export", "x")).unwrap();
+        assert_eq!(saved, Saved::Created(day.path().join("browser-export")));
+        assert_eq!(read_messages(&day.path().join("browser-export")).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn later_timestamp() {
+        assert_eq!(later(Some("2026-03-01T10:00:00Z"), Some("2026-03-01T11:00:00+00:00")), Some("2026-03-01T11:00:00+00:00"));
+        assert_eq!(later(Some("2026-03-01T12:00:00Z"), Some("2026-03-01T11:00:00Z")), Some("2026-03-01T12:00:00Z"));
+        assert_eq!(later(Some("junk"), Some("2026-03-01T11:00:00Z")), Some("2026-03-01T11:00:00Z"));
+        assert_eq!(later(None, Some("b")), Some("b"));
+        assert_eq!(later(None, None), None);
+    }
+}
@@ -0,0 +1,15 @@
+//! Shared pieces of the continuum importers (chatgpt-, claude- and
+//! grok-to-continuum).
+//!
+//! `dedup` keeps the same conversation from landing in the logs twice when
+//! it arrives through more than one route, e.g. a browser export picked up
+//! by ai-export-watcher and later the official bulk export.
+//!
+//! `note` renders a conversation as a markdown note for the vault, named
+//! so it can be wikilinked.
+
+pub mod dedup;
+pub mod note;
+
+pub use dedup::{message_hash, read_hashes, save_session, Saved, HASHES_FILE};
+pub use note::{wikilink, Note};
@@ -0,0 +1,147 @@
+//! Readable markdown notes of single conversations, for the importers'
+//! `--note`: frontmatter, the title as H1, then a `### You` / `### Assistant`
+//! section per message.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A conversation as a note. `date` is `YYYY-MM-DD`; `messages` are
+/// `(role, content)` pairs in order.
+pub struct Note<'a> {
+    pub assistant: &'a str,
+    pub date: &'a str,
+    pub title: &'a str,
+    pub source_url: Option<&'a str>,
+    /// Written as the note's tags
+    pub skills: &'a [String],
+    pub messages: Vec<(&'a str, &'a str)>,
+}
+
+impl Note<'_> {
+    pub fn render(&self) -> String {
+        let mut out = String::from("---\n");
+        out.push_str(&format!("assistant: {}\n", self.assistant));
+        out.push_str(&format!("date: {}\n", self.date));
+        if let Some(url) = self.source_url {
+            out.push_str(&format!("source_url: {}\n", url));
+        }
+        if !self.skills.is_empty() {
+            out.push_str(&format!("tags: [{}]\n", self.skills.join(", ")));
+        }
+        out.push_str("---\n\n");
+
+        out.push_str(&format!("# {}\n", self.title));
+        for (role, content) in &self.messages {
+            out.push_str(&format!("\n### {}\n\n{}\n", heading(role), content.trim()));
+        }
+        out
+    }
+
+    /// `2025-11-24 Stoic reading list`: the date, then the title made safe
+    /// for a filename and a wikilink
+    pub fn file_stem(&self) -> String {
+        format!("{} {}", self.date, sanitize_title(self.title))
+    }
+
+    /// Write the note into `dir` as `<file_stem>.md`, replacing an earlier
+    /// note of the same conversation
+    pub fn write(&self, dir: &Path) -> Result<PathBuf> {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        let path = dir.join(format!("{}.md", self.file_stem()));
+        fs::write(&path, self.render()).with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+}
+
+/// `[[2025-11-24 Stoic reading list]]` for a note written by `Note::write`
+pub fn wikilink(path: &Path) -> String {
+    let stem = path.file_stem().map(|s| s.to_string_lossy()).unwrap_or_default();
+    format!("[[{}]]", stem)
+}
+
+fn heading(role: &str) -> String {
+    match role {
+        "user" => "You".to_string(),
+        "assistant" => "Assistant".to_string(),
+        other => {
+            let mut chars = other.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => "Unknown".to_string(),
+            }
+        }
+    }
+}
+
+/// A title usable as a filename and wikilink target: `:` and `|` become
+/// ` -`, slashes become `-`, and the other characters Obsidian rejects in
+/// filenames or links are dropped.
+pub fn sanitize_title(title: &str) -> String {
+    let sanitized = title
+        .replace([':', '|'], " -")
+        .replace(['/', '\\'], "-")
+        .replace(['?', '*', '<', '>', '"', '[', ']', '#', '^'], "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let sanitized = sanitized.trim_end_matches('.').trim();
+    if sanitized.is_empty() {
+        "Untitled".to_string()
+    } else {
+        sanitized.chars().take(100).collect::<String>().trim_end().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SNAPSHOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/snapshots/note.md");
+
+    fn note(skills: &[String]) -> Note<'_> {
+        Note {
+            assistant: "chatgpt",
+            date: "2025-11-24",
+            title: "Stoic reading list: where to start?",
+            source_url: Some("https://chatgpt.com/c/abc123"),
+            skills,
+            messages: vec![
+                ("user", "Where should I start with the Stoics?\n"),
+                ("assistant", "Begin with Epictetus' *Enchiridion*.\n\nThen Seneca's letters."),
+                ("tool", "search results"),
+            ],
+        }
+    }
+
+    #[test]
+    fn renders_snapshot() {
+        let skills = vec!["philosophy".to_string(), "reading".to_string()];
+        assert_eq!(note(&skills).render(), fs::read_to_string(SNAPSHOT).unwrap());
+    }
+
+    #[test]
+    fn omits_missing_fields() {
+        let mut note = note(&[]);
+        note.source_url = None;
+        let rendered = note.render();
+        assert!(rendered.starts_with("---\nassistant: chatgpt\ndate: 2025-11-24\n---\n\n# "));
+    }
+
+    #[test]
+    fn file_stem_is_dated_and_wikilink_safe() {
+        assert_eq!(note(&[]).file_stem(), "2025-11-24 Stoic reading list - where to start");
+        assert_eq!(sanitize_title("Deep Work: Rules | Notes"), "Deep Work - Rules - Notes");
+        assert_eq!(sanitize_title("[[Draft]] #1 / v2?"), "Draft 1 - v2");
+        assert_eq!(sanitize_title("???"), "Untitled");
+    }
+
+    #[test]
+    fn writes_note_and_links_to_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = note(&[]).write(&dir.path().join("notes")).unwrap();
+        assert_eq!(path, dir.path().join("notes/2025-11-24 Stoic reading list - where to start.md"));
+        assert!(fs::read_to_string(&path).unwrap().contains("### You\n\nWhere should I start"));
+        assert_eq!(wikilink(&path), "[[2025-11-24 Stoic reading list - where to start]]");
+    }
+}
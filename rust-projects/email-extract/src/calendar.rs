@@ -0,0 +1,228 @@
+use serde::Serialize;
+
+/// The interesting fields of a VEVENT from a `text/calendar` part.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CalendarEvent {
+    pub summary: Option<String>,
+    /// ISO 8601: `2025-03-04T10:00:00` (with `Z` for UTC), or a bare date
+    /// for all-day events
+    pub start: Option<String>,
+    pub end: Option<String>,
+    /// TZID of the start time, when it isn't UTC or floating
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    pub location: Option<String>,
+    /// `Name <address>` from the ORGANIZER's CN and mailto
+    pub organizer: Option<String>,
+}
+
+/// Parse the main event out of an iCalendar document. Invites carry one
+/// VEVENT per meeting; recurring ones may add exception VEVENTs with a
+/// RECURRENCE-ID, so the first VEVENT without one wins.
+pub fn parse_ics(text: &str) -> Option<CalendarEvent> {
+    let mut events: Vec<(CalendarEvent, bool)> = Vec::new();
+    let mut current: Option<(CalendarEvent, bool)> = None;
+    // Nested components (VALARM) have their own SUMMARY/LOCATION-like fields
+    let mut nested = 0;
+
+    for line in unfold(text) {
+        let Some((name, params, value)) = split_property(&line) else {
+            continue;
+        };
+
+        match (name.as_str(), value) {
+            ("BEGIN", v) if v.eq_ignore_ascii_case("VEVENT") => {
+                current = Some((CalendarEvent::default(), false));
+                nested = 0;
+                continue;
+            }
+            ("END", v) if v.eq_ignore_ascii_case("VEVENT") => {
+                events.extend(current.take());
+                continue;
+            }
+            ("BEGIN", _) if current.is_some() => nested += 1,
+            ("END", _) if current.is_some() => nested -= 1,
+            _ => {}
+        }
+
+        let Some((event, is_exception)) = current.as_mut() else {
+            continue;
+        };
+        if nested > 0 {
+            continue;
+        }
+
+        match name.as_str() {
+            "SUMMARY" => event.summary = Some(unescape(value)),
+            "LOCATION" => event.location = Some(unescape(value)).filter(|l| !l.is_empty()),
+            "DTSTART" => {
+                event.start = Some(format_datetime(value));
+                event.timezone = param(&params, "TZID").map(|tz| tz.trim_matches('"').to_string());
+            }
+            "DTEND" => event.end = Some(format_datetime(value)),
+            "ORGANIZER" => event.organizer = Some(format_organizer(&params, value)),
+            "RECURRENCE-ID" => *is_exception = true,
+            _ => {}
+        }
+    }
+
+    let master = events.iter().position(|(_, is_exception)| !is_exception).unwrap_or(0);
+    (master < events.len()).then(|| events.swap_remove(master).0)
+}
+
+/// Property parameters as (uppercased name, raw value) pairs
+type Params = Vec<(String, String)>;
+
+/// Join folded lines (RFC 5545 §3.1: CRLF followed by a space or tab)
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        let line = line.trim_end_matches('\r');
+        match line.strip_prefix([' ', '\t']) {
+            Some(rest) if !lines.is_empty() => lines.last_mut().unwrap().push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+/// Split `NAME;PARAM=x;PARAM2=y:value` into its uppercased name, parameters
+/// and value. Colons inside quoted parameter values don't end the name.
+fn split_property(line: &str) -> Option<(String, Params, &str)> {
+    let mut in_quotes = false;
+    let colon = line.char_indices().find_map(|(i, c)| match c {
+        '"' => {
+            in_quotes = !in_quotes;
+            None
+        }
+        ':' if !in_quotes => Some(i),
+        _ => None,
+    })?;
+
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+    let mut parts = head.split(';');
+    let name = parts.next()?.trim().to_uppercase();
+    let params = parts
+        .filter_map(|p| p.split_once('='))
+        .map(|(k, v)| (k.trim().to_uppercase(), v.to_string()))
+        .collect();
+    Some((name, params, value))
+}
+
+fn param<'a>(params: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    params.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+}
+
+/// Undo iCalendar TEXT escaping
+fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out.trim().to_string()
+}
+
+/// `20250304T100000Z` → `2025-03-04T10:00:00Z`, `20250304` → `2025-03-04`.
+/// Anything unexpected is passed through unchanged.
+fn format_datetime(value: &str) -> String {
+    let value = value.trim();
+    let (date, time) = match value.split_once('T') {
+        Some((d, t)) => (d, Some(t)),
+        None => (value, None),
+    };
+    if date.len() != 8 || !date.bytes().all(|b| b.is_ascii_digit()) {
+        return value.to_string();
+    }
+    let date = format!("{}-{}-{}", &date[..4], &date[4..6], &date[6..]);
+
+    match time {
+        None => date,
+        Some(t) => {
+            let (digits, utc) = match t.strip_suffix('Z') {
+                Some(d) => (d, "Z"),
+                None => (t, ""),
+            };
+            if digits.len() != 6 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return value.to_string();
+            }
+            format!("{}T{}:{}:{}{}", date, &digits[..2], &digits[2..4], &digits[4..], utc)
+        }
+    }
+}
+
+fn format_organizer(params: &[(String, String)], value: &str) -> String {
+    let address = value
+        .strip_prefix("mailto:")
+        .or_else(|| value.strip_prefix("MAILTO:"))
+        .unwrap_or(value)
+        .trim();
+    match param(params, "CN").map(|cn| cn.trim_matches('"').trim()) {
+        Some(name) if !name.is_empty() && !address.is_empty() => format!("{} <{}>", name, address),
+        Some(name) if !name.is_empty() => name.to_string(),
+        _ => address.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INVITE: &str = "BEGIN:VCALENDAR\r\n\
+        METHOD:REQUEST\r\n\
+        BEGIN:VEVENT\r\n\
+        UID:abc@example.com\r\n\
+        SUMMARY:Quarterly review\\, budget and\r\n \x20hiring\r\n\
+        DTSTART;TZID=Europe/London:20250304T100000\r\n\
+        DTEND;TZID=Europe/London:20250304T113000\r\n\
+        LOCATION:Room 4\\; 2nd floor\r\n\
+        ORGANIZER;CN=\"Smith, Jo\":mailto:jo@example.com\r\n\
+        BEGIN:VALARM\r\n\
+        SUMMARY:Reminder\r\n\
+        END:VALARM\r\n\
+        END:VEVENT\r\n\
+        END:VCALENDAR\r\n";
+
+    #[test]
+    fn test_parse_invite() {
+        let event = parse_ics(INVITE).unwrap();
+        assert_eq!(event.summary.as_deref(), Some("Quarterly review, budget and hiring"));
+        assert_eq!(event.start.as_deref(), Some("2025-03-04T10:00:00"));
+        assert_eq!(event.end.as_deref(), Some("2025-03-04T11:30:00"));
+        assert_eq!(event.timezone.as_deref(), Some("Europe/London"));
+        assert_eq!(event.location.as_deref(), Some("Room 4; 2nd floor"));
+        assert_eq!(event.organizer.as_deref(), Some("Smith, Jo <jo@example.com>"));
+    }
+
+    #[test]
+    fn test_utc_and_all_day_dates() {
+        assert_eq!(format_datetime("20250304T100000Z"), "2025-03-04T10:00:00Z");
+        assert_eq!(format_datetime("20250304"), "2025-03-04");
+        assert_eq!(format_datetime("garbage"), "garbage");
+    }
+
+    #[test]
+    fn test_master_event_preferred_over_exception() {
+        let ics = "BEGIN:VCALENDAR\n\
+            BEGIN:VEVENT\nRECURRENCE-ID:20250311T100000Z\nSUMMARY:Moved standup\nEND:VEVENT\n\
+            BEGIN:VEVENT\nSUMMARY:Standup\nDTSTART:20250304T100000Z\nORGANIZER:mailto:a@example.com\nEND:VEVENT\n\
+            END:VCALENDAR\n";
+        let event = parse_ics(ics).unwrap();
+        assert_eq!(event.summary.as_deref(), Some("Standup"));
+        assert_eq!(event.timezone, None);
+        assert_eq!(event.organizer.as_deref(), Some("a@example.com"));
+    }
+
+    #[test]
+    fn test_no_vevent() {
+        assert!(parse_ics("BEGIN:VCALENDAR\nBEGIN:VTODO\nSUMMARY:x\nEND:VTODO\nEND:VCALENDAR\n").is_none());
+    }
+}
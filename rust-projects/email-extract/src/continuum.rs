@@ -0,0 +1,322 @@
+//! `--format continuum`: email threads written as sessions in the continuum
+//! archive, beside the AI conversations, so continuum-activity can search
+//! them.
+
+use crate::extract::EmailData;
+use anyhow::{Context, Result};
+use chrono::{DateTime, FixedOffset};
+use continuum_core::Saved;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// `session.assistant`, and the archive directory sessions are written to
+pub const ASSISTANT: &str = "email";
+
+#[derive(Debug, Serialize)]
+struct ContinuumMessage {
+    id: u32,
+    role: String,
+    content: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timestamp: Option<String>,
+    from: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ContinuumSession {
+    id: String,
+    assistant: String,
+    start_time: Option<String>,
+    end_time: Option<String>,
+    status: Option<String>,
+    message_count: Option<u32>,
+    created_at: Option<String>,
+    title: Option<String>,
+    source_url: Option<String>,
+}
+
+/// `~/Assistants/continuum-logs/email`
+pub fn default_output_dir() -> Result<PathBuf> {
+    let home = std::env::var("HOME").context("HOME not set")?;
+    Ok(PathBuf::from(home).join("Assistants").join("continuum-logs").join(ASSISTANT))
+}
+
+/// "user" for mail sent from one of `my_addresses`, otherwise "correspondent"
+pub fn role(from: &str, my_addresses: &[String]) -> &'static str {
+    let sender = address(from);
+    if my_addresses.iter().any(|mine| address(mine) == sender) {
+        "user"
+    } else {
+        "correspondent"
+    }
+}
+
+/// The bare, lowercased address of a `Name <addr>` or plain `addr` value
+fn address(value: &str) -> String {
+    let addr = match value.rsplit_once('<') {
+        Some((_, rest)) => rest.split('>').next().unwrap_or(rest),
+        None => value,
+    };
+    addr.trim().to_lowercase()
+}
+
+/// The ids in a Message-ID, In-Reply-To or References header, without
+/// their angle brackets. A bare id with no brackets is taken whole.
+fn message_ids(header: &str) -> Vec<&str> {
+    let bracketed: Vec<&str> = header
+        .split('<')
+        .skip(1)
+        .filter_map(|part| part.split_once('>').map(|(id, _)| id.trim()))
+        .filter(|id| !id.is_empty())
+        .collect();
+    if bracketed.is_empty() && !header.trim().is_empty() {
+        return vec![header.trim()];
+    }
+    bracketed
+}
+
+/// Group emails into sessions. With `thread`, a reply joins the email its
+/// In-Reply-To (or else the latest known References entry) points at;
+/// otherwise each email stands alone. Each group is in date order, and
+/// groups keep the order their first email came in.
+pub fn group(emails: &[EmailData], thread: bool) -> Vec<Vec<&EmailData>> {
+    if !thread {
+        return emails.iter().map(|email| vec![email]).collect();
+    }
+
+    let index: HashMap<&str, usize> = emails
+        .iter()
+        .enumerate()
+        .filter_map(|(i, email)| Some((*message_ids(&email.message_id).first()?, i)))
+        .collect();
+    let parent = |email: &EmailData| {
+        let references = email
+            .all_headers
+            .iter()
+            .filter(|(key, _)| key.eq_ignore_ascii_case("References"))
+            .flat_map(|(_, value)| message_ids(value).into_iter().rev());
+        message_ids(&email.in_reply_to)
+            .into_iter()
+            .chain(references)
+            .find_map(|id| index.get(id).copied())
+    };
+
+    let mut order: Vec<usize> = Vec::new();
+    let mut groups: HashMap<usize, Vec<&EmailData>> = HashMap::new();
+    for (i, email) in emails.iter().enumerate() {
+        // Walk up to the thread root; the step limit guards against cycles
+        let mut root = i;
+        for _ in 0..emails.len() {
+            match parent(&emails[root]) {
+                Some(up) if up != root => root = up,
+                _ => break,
+            }
+        }
+        groups
+            .entry(root)
+            .or_insert_with(|| {
+                order.push(root);
+                Vec::new()
+            })
+            .push(email);
+    }
+
+    order
+        .into_iter()
+        .map(|root| {
+            let mut thread = groups.remove(&root).unwrap_or_default();
+            thread.sort_by_key(|email| parsed_date(email));
+            thread
+        })
+        .collect()
+}
+
+fn parsed_date(email: &EmailData) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(email.date_parsed.as_deref()?).ok()
+}
+
+/// Directory-safe session id from a Message-ID, e.g.
+/// `<CAB+x1@mail.gmail.com>` → `cab-x1-mail-gmail-com`. The same email
+/// always gets the same id, so exporting it again overwrites its session.
+fn session_id(email: &EmailData) -> String {
+    let slug = message_ids(&email.message_id)
+        .first()
+        .map(|id| {
+            id.to_lowercase()
+                .split(|c: char| !c.is_ascii_alphanumeric())
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<_>>()
+                .join("-")
+        })
+        .unwrap_or_default();
+    if slug.is_empty() {
+        // No usable Message-ID: fall back to the file it came from
+        return continuum_core::message_hash(ASSISTANT, &email.source_path);
+    }
+    slug
+}
+
+/// Write each group from `group` as `output_dir/<date>/<id>/`, through
+/// `continuum_core::save_session` like the AI importers
+pub fn export(
+    emails: &[EmailData],
+    thread: bool,
+    my_addresses: &[String],
+    output_dir: &Path,
+) -> Result<Vec<Saved>> {
+    let mut saved = Vec::new();
+
+    for group in group(emails, thread) {
+        // The earliest email names the session
+        let Some(first) = group.first() else {
+            continue;
+        };
+
+        let messages: Vec<ContinuumMessage> = group
+            .iter()
+            .enumerate()
+            .map(|(i, email)| ContinuumMessage {
+                id: (i + 1) as u32,
+                role: role(&email.from, my_addresses).to_string(),
+                content: email.body.trim().to_string(),
+                timestamp: email.date_parsed.clone(),
+                from: email.from.clone(),
+            })
+            .collect();
+
+        let start_time = first.date_parsed.clone();
+        let end_time = group.iter().rev().find_map(|email| email.date_parsed.clone());
+        let id = session_id(first);
+        let session = ContinuumSession {
+            id: id.clone(),
+            assistant: ASSISTANT.to_string(),
+            start_time: start_time.clone(),
+            end_time,
+            status: Some("imported".to_string()),
+            message_count: Some(messages.len() as u32),
+            created_at: start_time.clone(),
+            title: Some(first.subject.clone()),
+            source_url: Some(first.source_path.clone()),
+        };
+
+        let date = start_time
+            .as_deref()
+            .and_then(|t| t.get(..10))
+            .unwrap_or("undated");
+        saved.push(continuum_core::save_session(&output_dir.join(date), &id, &messages, &session)?);
+    }
+
+    Ok(saved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn email(id: &str, in_reply_to: &str, from: &str, date: &str) -> EmailData {
+        EmailData {
+            from: from.into(),
+            date_parsed: Some(date.into()),
+            message_id: format!("<{}@example.com>", id),
+            in_reply_to: in_reply_to.into(),
+            source_path: format!("/mail/{}.eml", id),
+            ..EmailData::test(&format!("Subject {}", id), &format!("Body {}\n", id))
+        }
+    }
+
+    fn ids(groups: &[Vec<&EmailData>]) -> Vec<Vec<String>> {
+        groups
+            .iter()
+            .map(|g| g.iter().map(|e| e.message_id.clone()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn roles_follow_my_addresses() {
+        let mine = vec!["Will@Example.com".to_string(), "w.n@work.org".to_string()];
+        assert_eq!(role("William Napier <will@example.com>", &mine), "user");
+        assert_eq!(role("w.n@work.org", &mine), "user");
+        assert_eq!(role("Jo <jo@example.com>", &mine), "correspondent");
+        assert_eq!(role("will@example.com", &[]), "correspondent");
+    }
+
+    #[test]
+    fn replies_join_their_thread_in_date_order() {
+        let mut late_reply = email("c", "", "jo@example.com", "2025-03-04T12:00:00+00:00");
+        late_reply
+            .all_headers
+            .push(("References".into(), "<a@example.com> <b@example.com>".into()));
+        let emails = vec![
+            email("b", "<a@example.com>", "me@example.com", "2025-03-04T11:00:00+01:00"),
+            email("a", "", "jo@example.com", "2025-03-04T09:00:00+00:00"),
+            email("other", "<unknown@example.com>", "x@example.com", "2025-03-05T09:00:00+00:00"),
+            late_reply,
+        ];
+
+        let threads = group(&emails, true);
+        assert_eq!(
+            ids(&threads),
+            vec![
+                vec!["<a@example.com>", "<b@example.com>", "<c@example.com>"],
+                vec!["<other@example.com>"],
+            ]
+        );
+        assert_eq!(group(&emails, false).len(), 4);
+    }
+
+    #[test]
+    fn reply_cycles_terminate() {
+        let emails = vec![
+            email("a", "<b@example.com>", "x@example.com", "2025-03-04T09:00:00+00:00"),
+            email("b", "<a@example.com>", "x@example.com", "2025-03-04T10:00:00+00:00"),
+        ];
+        let threads = group(&emails, true);
+        assert_eq!(threads.iter().map(Vec::len).sum::<usize>(), 2);
+    }
+
+    #[test]
+    fn session_ids_come_from_message_ids() {
+        let mut e = email("x", "", "x@example.com", "2025-03-04T09:00:00+00:00");
+        e.message_id = "<CAB+x1@mail.gmail.com>".into();
+        assert_eq!(session_id(&e), "cab-x1-mail-gmail-com");
+        e.message_id = String::new();
+        assert_eq!(session_id(&e), continuum_core::message_hash(ASSISTANT, "/mail/x.eml"));
+    }
+
+    #[test]
+    fn exports_fixture_thread_as_one_session() {
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/thread");
+        let emails: Vec<EmailData> = ["1.eml", "2.eml", "3.eml"]
+            .iter()
+            .map(|name| crate::extract::parse_email(&fixtures.join(name), false, false, false).unwrap())
+            .collect();
+        let out = tempfile::tempdir().unwrap();
+
+        let saved = export(&emails, true, &["will@example.com".to_string()], out.path()).unwrap();
+        let session_dir = out.path().join("2025-03-04/rota-1-example-com");
+        assert_eq!(saved, vec![Saved::Created(session_dir.clone())]);
+
+        let messages: Vec<serde_json::Value> = std::fs::read_to_string(session_dir.join("messages.jsonl"))
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let roles: Vec<&str> = messages.iter().map(|m| m["role"].as_str().unwrap()).collect();
+        assert_eq!(roles, vec!["correspondent", "user", "correspondent"]);
+        assert_eq!(messages[1]["content"], "Thursday works for me.");
+
+        let session: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(session_dir.join("session.json")).unwrap()).unwrap();
+        assert_eq!(session["title"], "Clinic rota");
+        assert_eq!(session["message_count"], 3);
+        assert_eq!(session["assistant"], "email");
+        assert_eq!(session["start_time"], "2025-03-04T09:15:00+00:00");
+        assert_eq!(session["end_time"], "2025-03-04T14:02:00+00:00");
+        assert!(session["source_url"].as_str().unwrap().ends_with("tests/fixtures/thread/1.eml"));
+
+        // Without threading, one session per email
+        let out = tempfile::tempdir().unwrap();
+        assert_eq!(export(&emails, false, &[], out.path()).unwrap().len(), 3);
+    }
+}
@@ -1,9 +1,12 @@
+use crate::calendar::{self, CalendarEvent};
+use crate::pgp::{self, Protection};
 use anyhow::{Context, Result};
+use base64::Engine as _;
 use chrono::{DateTime, FixedOffset};
-use mailparse::{parse_mail, MailHeaderMap, ParsedMail};
+use mailparse::{parse_headers, parse_mail, Body, MailHeaderMap, ParsedMail};
 use regex::Regex;
 use serde::Serialize;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Structured email data extracted from a MIME message.
 #[derive(Debug, Clone, Serialize)]
@@ -19,11 +22,41 @@ pub struct EmailData {
     pub body: String,
     pub body_type: BodyType,
     pub attachments: Vec<AttachmentInfo>,
+    /// Meeting details from a `text/calendar` part, if the email is an invite
+    pub event: Option<CalendarEvent>,
+    /// Whether the message was PGP signed or encrypted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pgp: Option<Protection>,
     #[serde(skip)]
     pub all_headers: Vec<(String, String)>,
     pub source_path: String,
 }
 
+#[cfg(test)]
+impl EmailData {
+    /// A plain-text email with just a subject and body; tests fill in
+    /// whatever else they need with struct update syntax
+    pub fn test(subject: &str, body: &str) -> Self {
+        EmailData {
+            from: String::new(),
+            to: String::new(),
+            cc: String::new(),
+            date: String::new(),
+            date_parsed: None,
+            subject: subject.into(),
+            message_id: String::new(),
+            in_reply_to: String::new(),
+            body: body.into(),
+            body_type: BodyType::PlainText,
+            attachments: Vec::new(),
+            event: None,
+            pgp: None,
+            all_headers: Vec::new(),
+            source_path: String::new(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub enum BodyType {
     PlainText,
@@ -36,16 +69,98 @@ pub struct AttachmentInfo {
     pub filename: String,
     pub content_type: String,
     pub size: usize,
+    /// Location on disk for attachments found in a sidecar folder rather
+    /// than embedded as MIME parts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_path: Option<String>,
 }
 
-/// Parse an email file from disk into structured EmailData.
-pub fn parse_email(path: &Path, prefer_html: bool, strip_html: bool) -> Result<EmailData> {
+/// An image part of an email, decoded: inline (referenced from the body by
+/// `cid:`) or attached
+pub struct ImagePart {
+    /// The `Content-ID`, without its angle brackets
+    pub content_id: Option<String>,
+    pub filename: Option<String>,
+    pub content_type: String,
+    pub data: Vec<u8>,
+}
+
+/// Every `image/*` part of an email file, in message order
+pub fn extract_images(path: &Path) -> Result<Vec<ImagePart>> {
+    let raw = std::fs::read(path)
+        .with_context(|| format!("Failed to read email file: {}", path.display()))?;
+    let parsed = parse_mail(&raw)
+        .with_context(|| format!("Failed to parse MIME message: {}", path.display()))?;
+
+    let mut images = Vec::new();
+    collect_images(&parsed, &mut images);
+    Ok(images)
+}
+
+fn collect_images(parsed: &ParsedMail, images: &mut Vec<ImagePart>) {
+    if !parsed.subparts.is_empty() {
+        for subpart in &parsed.subparts {
+            collect_images(subpart, images);
+        }
+        return;
+    }
+
+    let content_type = parsed.ctype.mimetype.to_lowercase();
+    if !content_type.starts_with("image/") {
+        return;
+    }
+    let Ok(data) = parsed.get_body_raw() else {
+        return;
+    };
+    let disposition = parsed.headers.get_first_value("Content-Disposition").unwrap_or_default();
+    images.push(ImagePart {
+        content_id: parsed
+            .headers
+            .get_first_value("Content-ID")
+            .map(|id| id.trim().trim_start_matches('<').trim_end_matches('>').to_string())
+            .filter(|id| !id.is_empty()),
+        filename: parsed
+            .ctype
+            .params
+            .get("name")
+            .cloned()
+            .or_else(|| extract_filename_from_disposition(&disposition)),
+        content_type,
+        data,
+    });
+}
+
+/// Parse an email file from disk into structured EmailData. With
+/// `decrypt`, PGP-encrypted content is run through gpg.
+pub fn parse_email(path: &Path, prefer_html: bool, strip_html: bool, decrypt: bool) -> Result<EmailData> {
     let raw = std::fs::read(path)
         .with_context(|| format!("Failed to read email file: {}", path.display()))?;
 
     let parsed = parse_mail(&raw)
         .with_context(|| format!("Failed to parse MIME message: {}", path.display()))?;
 
+    let mut protection = find_protection(&parsed);
+    let decrypted = match find_encrypted(&parsed) {
+        Some(part) if decrypt => match decrypt_part(part) {
+            Ok(plain) => Some(plain),
+            Err(e) => {
+                eprintln!("Warning: {}: {:#}", path.display(), e);
+                None
+            }
+        },
+        _ => None,
+    };
+    let inner = decrypted
+        .as_deref()
+        .map(parse_mail)
+        .transpose()
+        .with_context(|| format!("Failed to parse decrypted message: {}", path.display()))?;
+    if inner.is_some() {
+        protection = Some(Protection::Decrypted);
+    }
+    // Body, attachments and invite come from inside the encryption, if any
+    let content = inner.as_ref().unwrap_or(&parsed);
+
     let headers = &parsed.headers;
 
     let from = headers
@@ -86,10 +201,16 @@ pub fn parse_email(path: &Path, prefer_html: bool, strip_html: bool) -> Result<E
         .collect();
 
     // Extract body (text/plain preferred, HTML fallback)
-    let (body, body_type) = extract_body(&parsed, prefer_html, strip_html);
+    let (mut body, body_type) = extract_body(content, prefer_html, strip_html);
+    if let Some(inline) = unwrap_inline_pgp(&body, decrypt, &mut protection) {
+        body = inline;
+    }
 
-    // Collect attachment info
-    let attachments = extract_attachment_info(&parsed);
+    // Collect attachment info, including any sidecar attachment folder
+    let mut attachments = extract_attachment_info(content);
+    attachments.extend(external_attachments(path));
+
+    let event = extract_event(content);
 
     Ok(EmailData {
         from,
@@ -103,11 +224,37 @@ pub fn parse_email(path: &Path, prefer_html: bool, strip_html: bool) -> Result<E
         body,
         body_type,
         attachments,
+        event,
+        pgp: protection,
         all_headers,
         source_path: path.display().to_string(),
     })
 }
 
+/// Sender, parsed date and file size of an email, reading only its headers.
+pub struct HeaderInfo {
+    pub from: String,
+    pub date_parsed: Option<String>,
+    pub size: u64,
+}
+
+/// Parse just the header block of an email file, skipping MIME body decoding.
+pub fn parse_header_info(path: &Path) -> Result<HeaderInfo> {
+    let raw = std::fs::read(path)
+        .with_context(|| format!("Failed to read email file: {}", path.display()))?;
+
+    let (headers, _) = parse_headers(&raw)
+        .with_context(|| format!("Failed to parse headers: {}", path.display()))?;
+
+    let date_raw = headers.get_first_value("Date").unwrap_or_default();
+
+    Ok(HeaderInfo {
+        from: headers.get_first_value("From").unwrap_or_default(),
+        date_parsed: parse_email_date(&date_raw),
+        size: raw.len() as u64,
+    })
+}
+
 /// Extract the body from a parsed email message.
 /// Prefers text/plain unless prefer_html is set.
 /// Falls back to HTML with tag stripping if no text/plain is available.
@@ -138,6 +285,79 @@ fn extract_body(parsed: &ParsedMail, prefer_html: bool, strip_html: bool) -> (St
     (String::new(), BodyType::Empty)
 }
 
+/// Whether a message is PGP/MIME signed or encrypted, encryption winning
+/// when a part of each is present.
+fn find_protection(parsed: &ParsedMail) -> Option<Protection> {
+    if find_encrypted(parsed).is_some() {
+        return Some(Protection::Encrypted);
+    }
+    fn is_signed(part: &ParsedMail) -> bool {
+        part.ctype.mimetype.eq_ignore_ascii_case("multipart/signed") || part.subparts.iter().any(is_signed)
+    }
+    is_signed(parsed).then_some(Protection::Signed)
+}
+
+/// The first `multipart/encrypted` part, searching depth first
+fn find_encrypted<'a>(parsed: &'a ParsedMail<'a>) -> Option<&'a ParsedMail<'a>> {
+    if parsed.ctype.mimetype.eq_ignore_ascii_case("multipart/encrypted") {
+        return Some(parsed);
+    }
+    parsed.subparts.iter().find_map(find_encrypted)
+}
+
+/// Decrypt a `multipart/encrypted` part, whose second subpart holds the
+/// ciphertext, into the raw MIME entity it protects
+fn decrypt_part(part: &ParsedMail) -> Result<Vec<u8>> {
+    let ciphertext = part
+        .subparts
+        .get(1)
+        .context("multipart/encrypted part has no encrypted content")?
+        .get_body_raw()?;
+    pgp::decrypt(&ciphertext)
+}
+
+/// Handle inline PGP in a text body: a clearsigned block loses its armor
+/// and signature, and an encrypted block is decrypted with `decrypt` or
+/// replaced by a note. Records what it found in `protection`; None when
+/// the body has no inline PGP.
+fn unwrap_inline_pgp(body: &str, decrypt: bool, protection: &mut Option<Protection>) -> Option<String> {
+    let mut found = None;
+    let mut text = body.to_string();
+
+    if let Some(unsigned) = pgp::strip_clearsign(&text) {
+        text = unsigned;
+        found = Some(Protection::Signed);
+    }
+
+    let mut decrypted_all = true;
+    if let Some(replaced) = pgp::replace_inline_messages(&text, |block| {
+        let plain = if decrypt {
+            pgp::decrypt(block.as_bytes())
+                .map_err(|e| eprintln!("Warning: {:#}", e))
+                .ok()
+        } else {
+            None
+        };
+        match plain {
+            Some(plain) => String::from_utf8_lossy(&plain).into_owned(),
+            None => {
+                decrypted_all = false;
+                pgp::ENCRYPTED_NOTE.to_string()
+            }
+        }
+    }) {
+        text = clean_text(&replaced);
+        found = Some(if decrypted_all { Protection::Decrypted } else { Protection::Encrypted });
+    }
+
+    let found = found?;
+    // Encryption found in the MIME structure outranks anything inline
+    if matches!(protection, None | Some(Protection::Signed)) {
+        *protection = Some(found);
+    }
+    Some(text)
+}
+
 /// Recursively collect text/plain and text/html parts from a MIME message.
 fn collect_body_parts(
     parsed: &ParsedMail,
@@ -146,9 +366,17 @@ fn collect_body_parts(
 ) {
     let content_type = parsed.ctype.mimetype.to_lowercase();
 
+    if content_type == "multipart/encrypted" {
+        // Only ciphertext in here; say so rather than show it
+        if text_body.is_none() {
+            *text_body = Some(pgp::ENCRYPTED_NOTE.to_string());
+        }
+        return;
+    }
+
     if parsed.subparts.is_empty() {
         // Leaf node
-        if let Ok(body) = parsed.get_body() {
+        if let Some(body) = decode_text_part(parsed) {
             match content_type.as_str() {
                 "text/plain" => {
                     if text_body.is_none() {
@@ -171,6 +399,71 @@ fn collect_body_parts(
     }
 }
 
+/// A text part's body as a string: its `Content-Transfer-Encoding`
+/// (quoted-printable or base64) undone, then its charset. The header is
+/// read leniently, so `Quoted-Printable ` with odd case or trailing space
+/// still decodes rather than leaving `=20` and soft breaks in the text.
+fn decode_text_part(part: &ParsedMail) -> Option<String> {
+    let raw = match part.get_body_encoded() {
+        Body::Base64(body) | Body::QuotedPrintable(body) => body.get_raw(),
+        Body::SevenBit(body) | Body::EightBit(body) => body.get_raw(),
+        Body::Binary(body) => body.get_raw(),
+    };
+    let encoding = part
+        .headers
+        .get_first_value("Content-Transfer-Encoding")
+        .map(|value| transfer_encoding(&value));
+    let bytes = match encoding.as_deref() {
+        Some("quoted-printable") => quoted_printable::decode(raw, quoted_printable::ParseMode::Robust).ok()?,
+        Some("base64") => {
+            let compact: Vec<u8> = raw.iter().copied().filter(|b| !b.is_ascii_whitespace()).collect();
+            base64::engine::general_purpose::STANDARD.decode(compact).ok()?
+        }
+        _ => raw.to_vec(),
+    };
+    Some(decode_charset(&bytes, &part.ctype.charset))
+}
+
+/// `quoted-printable` from a header value like ` Quoted-Printable (QP)`
+fn transfer_encoding(value: &str) -> String {
+    value
+        .trim()
+        .split(|c: char| c.is_whitespace() || c == ';' || c == '(')
+        .next()
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+/// Decode text in `label`'s charset, falling back to lossy UTF-8 for
+/// unknown labels
+fn decode_charset(bytes: &[u8], label: &str) -> String {
+    match charset::Charset::for_label(label.trim().as_bytes()) {
+        Some(charset) => charset.decode_without_bom_handling(bytes).0.into_owned(),
+        None => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+/// Parse the first calendar part (`text/calendar`, `application/ics`, or
+/// an attached `.ics` file) that contains a VEVENT.
+fn extract_event(parsed: &ParsedMail) -> Option<CalendarEvent> {
+    if !parsed.subparts.is_empty() {
+        return parsed.subparts.iter().find_map(extract_event);
+    }
+
+    let content_type = parsed.ctype.mimetype.to_lowercase();
+    let is_ics_file = parsed
+        .ctype
+        .params
+        .get("name")
+        .is_some_and(|name| name.to_lowercase().ends_with(".ics"));
+    if content_type != "text/calendar" && content_type != "application/ics" && !is_ics_file {
+        return None;
+    }
+
+    let body = decode_text_part(parsed)?;
+    calendar::parse_ics(&body)
+}
+
 /// Extract attachment metadata (filename, content-type, size) from a MIME message.
 fn extract_attachment_info(parsed: &ParsedMail) -> Vec<AttachmentInfo> {
     let mut attachments = Vec::new();
@@ -180,6 +473,11 @@ fn extract_attachment_info(parsed: &ParsedMail) -> Vec<AttachmentInfo> {
 
 fn collect_attachments(parsed: &ParsedMail, attachments: &mut Vec<AttachmentInfo>) {
     let content_type = parsed.ctype.mimetype.to_lowercase();
+
+    // PGP/MIME signatures and ciphertext are protocol parts, not attachments
+    if content_type == "multipart/encrypted" || content_type == "application/pgp-signature" {
+        return;
+    }
     let disposition = parsed
         .headers
         .get_first_value("Content-Disposition")
@@ -211,6 +509,7 @@ fn collect_attachments(parsed: &ParsedMail, attachments: &mut Vec<AttachmentInfo
                 filename,
                 content_type: content_type.clone(),
                 size,
+                external_path: None,
             });
         }
     } else {
@@ -220,6 +519,89 @@ fn collect_attachments(parsed: &ParsedMail, attachments: &mut Vec<AttachmentInfo
     }
 }
 
+/// List attachments stored next to the email rather than inside it.
+///
+/// Outlook and some archiving tools export `message.eml` alongside a
+/// `message_attachments/` or `message.files/` folder. Every file in that
+/// folder (recursively) is reported as an attachment.
+fn external_attachments(path: &Path) -> Vec<AttachmentInfo> {
+    let mut attachments = Vec::new();
+    for dir in sidecar_dirs(path) {
+        collect_external_files(&dir, &dir, &mut attachments);
+    }
+    attachments
+}
+
+/// Candidate sidecar folders for an email file that actually exist on disk.
+fn sidecar_dirs(path: &Path) -> Vec<PathBuf> {
+    let (Some(parent), Some(stem)) = (path.parent(), path.file_stem()) else {
+        return Vec::new();
+    };
+    let stem = stem.to_string_lossy();
+    [format!("{}_attachments", stem), format!("{}.files", stem)]
+        .iter()
+        .map(|name| parent.join(name))
+        .filter(|dir| dir.is_dir())
+        .collect()
+}
+
+fn collect_external_files(root: &Path, dir: &Path, attachments: &mut Vec<AttachmentInfo>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut entries: Vec<PathBuf> = entries.filter_map(|e| e.ok()).map(|e| e.path()).collect();
+    entries.sort();
+
+    for entry in entries {
+        if entry.is_dir() {
+            collect_external_files(root, &entry, attachments);
+            continue;
+        }
+        let name = entry.file_name().unwrap_or_default().to_string_lossy();
+        if name.starts_with('.') {
+            continue;
+        }
+        let filename = entry
+            .strip_prefix(root)
+            .unwrap_or(&entry)
+            .to_string_lossy()
+            .to_string();
+        attachments.push(AttachmentInfo {
+            filename,
+            content_type: guess_content_type(&entry).to_string(),
+            size: entry.metadata().map(|m| m.len() as usize).unwrap_or(0),
+            external_path: Some(entry.display().to_string()),
+        });
+    }
+}
+
+/// Best-effort MIME type from a file extension.
+fn guess_content_type(path: &Path) -> &'static str {
+    let ext = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "pdf" => "application/pdf",
+        "doc" => "application/msword",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "xls" => "application/vnd.ms-excel",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "ppt" => "application/vnd.ms-powerpoint",
+        "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        "zip" => "application/zip",
+        "txt" => "text/plain",
+        "csv" => "text/csv",
+        "htm" | "html" => "text/html",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "ics" => "text/calendar",
+        "eml" => "message/rfc822",
+        _ => "application/octet-stream",
+    }
+}
+
 /// Extract filename from Content-Disposition header value.
 fn extract_filename_from_disposition(disposition: &str) -> Option<String> {
     let re = Regex::new(r#"filename="?([^";\s]+)"?"#).ok()?;
@@ -240,6 +622,10 @@ fn html_to_text(html: &str, aggressive_strip: bool) -> String {
     let hr_tag = Regex::new(r"(?i)<hr[^>]*>").unwrap();
     text = hr_tag.replace_all(&text, "\n---\n").to_string();
 
+    // Keep inline images as the `[cid:...]` marker plain-text parts use
+    let cid_img = Regex::new(r#"(?i)<img\b[^>]*?\bsrc\s*=\s*["']?cid:([^"'\s>]+)[^>]*>"#).unwrap();
+    text = cid_img.replace_all(&text, "[cid:$1]").to_string();
+
     // Remove style and script blocks entirely
     let style_script = Regex::new(r"(?is)<(?:style|script)[^>]*>.*?</(?:style|script)>").unwrap();
     text = style_script.replace_all(&text, "").to_string();
@@ -356,6 +742,14 @@ mod tests {
         assert!(result.contains("Line two"));
     }
 
+    #[test]
+    fn test_html_to_text_keeps_cid_images() {
+        let html = r#"<p>Your order</p><br><img width="40" src="cid:logo@shop" alt="Logo"><img src="https://x/y.png">"#;
+        let result = html_to_text(html, false);
+        assert!(result.contains("Your order\n[cid:logo@shop]"));
+        assert!(!result.contains("https://x/y.png"));
+    }
+
     #[test]
     fn test_html_to_text_entities() {
         let html = "Tom &amp; Jerry &lt;friends&gt;";
@@ -404,4 +798,170 @@ mod tests {
         let result = extract_filename_from_disposition(d);
         assert_eq!(result, Some("report.pdf".to_string()));
     }
+
+    const SIMPLE_EMAIL: &str = "From: a@example.com\r\nTo: b@example.com\r\n\
+                                Subject: Minutes\r\n\r\nSee attached.\r\n";
+
+    #[test]
+    fn test_sidecar_attachments_folder_listed() {
+        let dir = tempfile::tempdir().unwrap();
+        let eml = dir.path().join("minutes.eml");
+        std::fs::write(&eml, SIMPLE_EMAIL).unwrap();
+        let sidecar = dir.path().join("minutes_attachments");
+        std::fs::create_dir_all(sidecar.join("scans")).unwrap();
+        std::fs::write(sidecar.join("agenda.pdf"), b"%PDF-1.4").unwrap();
+        std::fs::write(sidecar.join("scans/page1.png"), b"png").unwrap();
+
+        let email = parse_email(&eml, false, false, false).unwrap();
+        let names: Vec<&str> = email.attachments.iter().map(|a| a.filename.as_str()).collect();
+        assert_eq!(names, vec!["agenda.pdf", "scans/page1.png"]);
+        assert_eq!(email.attachments[0].content_type, "application/pdf");
+        assert_eq!(email.attachments[0].size, 8);
+        assert!(email.attachments[0].external_path.is_some());
+    }
+
+    #[test]
+    fn test_dot_files_sidecar_folder_listed() {
+        let dir = tempfile::tempdir().unwrap();
+        let eml = dir.path().join("minutes.eml");
+        std::fs::write(&eml, SIMPLE_EMAIL).unwrap();
+        std::fs::create_dir(dir.path().join("minutes.files")).unwrap();
+        std::fs::write(dir.path().join("minutes.files/budget.xlsx"), b"xlsx").unwrap();
+
+        let email = parse_email(&eml, false, false, false).unwrap();
+        assert_eq!(email.attachments.len(), 1);
+        assert_eq!(email.attachments[0].filename, "budget.xlsx");
+    }
+
+    #[test]
+    fn test_calendar_invite_extracted() {
+        let dir = tempfile::tempdir().unwrap();
+        let eml = dir.path().join("invite.eml");
+        let raw = "From: jo@example.com\r\nTo: b@example.com\r\nSubject: Invitation: Review\r\n\
+                   MIME-Version: 1.0\r\n\
+                   Content-Type: multipart/alternative; boundary=\"b1\"\r\n\r\n\
+                   --b1\r\nContent-Type: text/plain\r\n\r\nYou have been invited.\r\n\
+                   --b1\r\nContent-Type: text/calendar; method=REQUEST; charset=UTF-8\r\n\r\n\
+                   BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nSUMMARY:Review\r\n\
+                   DTSTART:20250304T100000Z\r\nDTEND:20250304T110000Z\r\n\
+                   LOCATION:Room 4\r\nORGANIZER;CN=Jo:mailto:jo@example.com\r\n\
+                   END:VEVENT\r\nEND:VCALENDAR\r\n\
+                   --b1--\r\n";
+        std::fs::write(&eml, raw).unwrap();
+
+        let email = parse_email(&eml, false, false, false).unwrap();
+        assert_eq!(email.body, "You have been invited.\n");
+        let event = email.event.unwrap();
+        assert_eq!(event.summary.as_deref(), Some("Review"));
+        assert_eq!(event.start.as_deref(), Some("2025-03-04T10:00:00Z"));
+        assert_eq!(event.location.as_deref(), Some("Room 4"));
+        assert_eq!(event.organizer.as_deref(), Some("Jo <jo@example.com>"));
+    }
+
+    #[test]
+    fn test_pgp_signed_drops_signature_part() {
+        let dir = tempfile::tempdir().unwrap();
+        let eml = dir.path().join("signed.eml");
+        let raw = "From: jo@example.com\r\nTo: b@example.com\r\nSubject: Rota\r\n\
+                   MIME-Version: 1.0\r\n\
+                   Content-Type: multipart/signed; micalg=pgp-sha256;\r\n \
+                   protocol=\"application/pgp-signature\"; boundary=\"s1\"\r\n\r\n\
+                   --s1\r\nContent-Type: text/plain; charset=UTF-8\r\n\r\nRota attached.\r\n\
+                   --s1\r\nContent-Type: application/pgp-signature; name=\"signature.asc\"\r\n\
+                   Content-Disposition: attachment; filename=\"signature.asc\"\r\n\r\n\
+                   -----BEGIN PGP SIGNATURE-----\r\n\r\niQEzBAEBCAAd\r\n-----END PGP SIGNATURE-----\r\n\
+                   --s1--\r\n";
+        std::fs::write(&eml, raw).unwrap();
+
+        let email = parse_email(&eml, false, false, false).unwrap();
+        assert_eq!(email.body, "Rota attached.\n");
+        assert!(email.attachments.is_empty());
+        assert_eq!(email.pgp, Some(Protection::Signed));
+    }
+
+    #[test]
+    fn test_pgp_encrypted_replaced_by_note() {
+        let dir = tempfile::tempdir().unwrap();
+        let eml = dir.path().join("encrypted.eml");
+        let raw = "From: jo@example.com\r\nTo: b@example.com\r\nSubject: Private\r\n\
+                   MIME-Version: 1.0\r\n\
+                   Content-Type: multipart/encrypted;\r\n \
+                   protocol=\"application/pgp-encrypted\"; boundary=\"e1\"\r\n\r\n\
+                   --e1\r\nContent-Type: application/pgp-encrypted\r\n\r\nVersion: 1\r\n\
+                   --e1\r\nContent-Type: application/octet-stream; name=\"encrypted.asc\"\r\n\r\n\
+                   -----BEGIN PGP MESSAGE-----\r\n\r\nhQEMA1b2c3d4\r\n-----END PGP MESSAGE-----\r\n\
+                   --e1--\r\n";
+        std::fs::write(&eml, raw).unwrap();
+
+        let email = parse_email(&eml, false, false, false).unwrap();
+        assert_eq!(email.body, format!("{}\n", pgp::ENCRYPTED_NOTE));
+        assert!(email.attachments.is_empty());
+        assert_eq!(email.pgp, Some(Protection::Encrypted));
+    }
+
+    #[test]
+    fn test_inline_clearsigned_body_unwrapped() {
+        let mut protection = None;
+        let body = "-----BEGIN PGP SIGNED MESSAGE-----\nHash: SHA256\n\nHello\n\
+                    -----BEGIN PGP SIGNATURE-----\n\nabc\n-----END PGP SIGNATURE-----\n";
+        assert_eq!(unwrap_inline_pgp(body, false, &mut protection).as_deref(), Some("Hello\n"));
+        assert_eq!(protection, Some(Protection::Signed));
+
+        let mut protection = None;
+        assert_eq!(unwrap_inline_pgp("Hello\n", false, &mut protection), None);
+        assert_eq!(protection, None);
+    }
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name)
+    }
+
+    #[test]
+    fn test_transfer_encoding_read_leniently() {
+        assert_eq!(transfer_encoding("Quoted-Printable "), "quoted-printable");
+        assert_eq!(transfer_encoding(" base64 (from Outlook)"), "base64");
+        assert_eq!(transfer_encoding("7bit"), "7bit");
+    }
+
+    #[test]
+    fn test_quoted_printable_body_decoded() {
+        let email = parse_email(&fixture("quoted-printable.eml"), false, false, false).unwrap();
+        assert_eq!(
+            email.body,
+            "The notes from today \u{2014} with the reading list we agreed on, which runs to three books.\n\n\
+             Cost: \u{a3}40\n"
+        );
+        assert!(!email.body.contains("=20") && !email.body.contains("=E2"));
+    }
+
+    #[test]
+    fn test_quoted_printable_html_decoded_before_stripping() {
+        let email = parse_email(&fixture("quoted-printable.eml"), true, false, false).unwrap();
+        assert!(email.body.contains("today \u{2014} with the reading list we agreed on, which runs to"));
+        assert!(!email.body.contains('='));
+    }
+
+    #[test]
+    fn test_base64_body_decoded() {
+        let dir = tempfile::tempdir().unwrap();
+        let eml = dir.path().join("base64.eml");
+        let raw = "From: a@example.com\r\nTo: b@example.com\r\nSubject: Rota\r\n\
+                   MIME-Version: 1.0\r\nContent-Type: text/plain; charset=ISO-8859-1\r\n\
+                   Content-Transfer-Encoding: base64\r\n\r\nQ2Fm6SBhdCA5\r\nLjMw\r\n";
+        std::fs::write(&eml, raw).unwrap();
+
+        let email = parse_email(&eml, false, false, false).unwrap();
+        assert_eq!(email.body, "Caf\u{e9} at 9.30\n");
+    }
+
+    #[test]
+    fn test_no_sidecar_folder() {
+        let dir = tempfile::tempdir().unwrap();
+        let eml = dir.path().join("minutes.eml");
+        std::fs::write(&eml, SIMPLE_EMAIL).unwrap();
+
+        let email = parse_email(&eml, false, false, false).unwrap();
+        assert!(email.attachments.is_empty());
+        assert!(email.event.is_none());
+    }
 }
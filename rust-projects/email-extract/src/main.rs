@@ -1,8 +1,18 @@
+mod calendar;
+mod continuum;
 mod extract;
+mod mbox;
+mod media;
 mod output;
+mod pgp;
+mod quotes;
+mod redact;
+mod search;
+mod stats;
 
 use anyhow::{Context, Result};
 use clap::{Parser, ValueEnum};
+use regex::Regex;
 use std::path::PathBuf;
 
 #[derive(Parser)]
@@ -22,7 +32,9 @@ struct Cli {
     #[arg(long)]
     full_headers: bool,
 
-    /// Output to a directory instead of stdout (one file per email)
+    /// Output to a directory instead of stdout (one file per email). For
+    /// continuum, the archive directory (default
+    /// ~/Assistants/continuum-logs/email)
     #[arg(short, long)]
     output_dir: Option<PathBuf>,
 
@@ -42,9 +54,65 @@ struct Cli {
     #[arg(long)]
     maildir: bool,
 
-    /// Limit number of emails to process (0 = unlimited)
+    /// Limit number of emails to process (0 = unlimited); with --search,
+    /// the number of matches
     #[arg(short = 'n', long, default_value = "0")]
     limit: usize,
+
+    /// Only output emails whose subject, sender or body matches this regex
+    /// (case-sensitive; prefix with (?i) to ignore case)
+    #[arg(long, value_name = "REGEX", value_parser = Regex::new, conflicts_with_all = ["count", "to_maildir"])]
+    search: Option<Regex>,
+
+    /// Only report statistics (count, size, date range, top senders);
+    /// reads headers only and writes no output files
+    #[arg(long)]
+    count: bool,
+
+    /// Redact patterns from ~/.config/email-extract/redact.toml in headers
+    /// and body, replacing matches with each pattern's label
+    #[arg(long)]
+    redact: bool,
+
+    /// Like --redact, but replace matches with a stable short hash so
+    /// distinct values stay distinguishable
+    #[arg(long, conflicts_with = "redact")]
+    redact_hash: bool,
+
+    /// Drop quoted reply history (`>` lines, "On ... wrote:", Outlook
+    /// "Original Message" blocks) from bodies, leaving a one-line marker
+    #[arg(long)]
+    trim_quotes: bool,
+
+    /// Decrypt PGP-encrypted messages with gpg (otherwise their body is a
+    /// "[encrypted message – not decrypted]" note)
+    #[arg(long)]
+    decrypt: bool,
+
+    /// My own email address(es), comma-separated; for --format continuum,
+    /// mail from these is the "user" and everyone else a "correspondent"
+    #[arg(long, value_name = "ADDRESS", value_delimiter = ',')]
+    my_address: Vec<String>,
+
+    /// For --format continuum, group replies into one session per thread
+    /// (by In-Reply-To/References) instead of one per email
+    #[arg(long)]
+    thread: bool,
+
+    /// Write image parts (inline and attached) to OUTPUT_DIR/linked_media/
+    /// and embed them where the body references them by cid:
+    #[arg(long, requires = "output_dir")]
+    extract_images: bool,
+
+    /// With --extract-images, embed images Obsidian-style (![[...]])
+    /// rather than as markdown image links
+    #[arg(long, requires = "extract_images")]
+    obsidian: bool,
+
+    /// Convert mbox file(s) into a Maildir at DIR (messages go in DIR/cur/,
+    /// content unchanged) instead of extracting
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["count", "output_dir"])]
+    to_maildir: Option<PathBuf>,
 }
 
 #[derive(ValueEnum, Clone, Debug)]
@@ -55,6 +123,9 @@ enum OutputFormat {
     Markdown,
     /// JSON output
     Json,
+    /// Continuum archive sessions (messages.jsonl + session.json), so
+    /// continuum-activity can search them
+    Continuum,
 }
 
 fn main() -> Result<()> {
@@ -64,6 +135,10 @@ fn main() -> Result<()> {
         anyhow::bail!("Provide at least one file or directory path");
     }
 
+    if let Some(ref maildir) = cli.to_maildir {
+        return convert_to_maildir(&cli.paths, maildir);
+    }
+
     // Collect all email file paths
     let email_paths = collect_email_paths(&cli.paths, cli.maildir)?;
 
@@ -78,6 +153,43 @@ fn main() -> Result<()> {
         cli.limit.min(email_paths.len())
     };
 
+    let redactor = match (cli.redact, cli.redact_hash) {
+        (false, false) => None,
+        (_, hash) => {
+            let mode = if hash { redact::Mode::Hash } else { redact::Mode::Label };
+            Some(redact::Redactor::load(&redact::default_config_path()?, mode)?)
+        }
+    };
+    let mut redact_counts = redact::Counts::default();
+
+    if cli.count {
+        let mut summary = stats::CountSummary::default();
+        for path in email_paths.iter().take(limit) {
+            match extract::parse_header_info(path) {
+                Ok(info) => {
+                    let from = match redactor {
+                        Some(ref r) => r.redact(&info.from, &mut redact_counts),
+                        None => info.from,
+                    };
+                    summary.add(&from, info.date_parsed.as_deref(), info.size)
+                }
+                Err(e) => {
+                    eprintln!("Error processing {}: {}", path.display(), e);
+                    summary.errors += 1;
+                }
+            }
+        }
+        print!("{}", summary.render());
+        if let Some(ref r) = redactor {
+            eprintln!("{}", r.report(&redact_counts));
+        }
+        return Ok(());
+    }
+
+    if cli.extract_images && !matches!(cli.format, OutputFormat::Markdown | OutputFormat::Text) {
+        anyhow::bail!("--extract-images works with --format markdown or text");
+    }
+
     // Create output directory if specified
     if let Some(ref out_dir) = cli.output_dir {
         std::fs::create_dir_all(out_dir)
@@ -86,10 +198,31 @@ fn main() -> Result<()> {
 
     let mut results: Vec<extract::EmailData> = Vec::new();
     let mut errors = 0;
+    let mut read = 0;
 
-    for path in email_paths.iter().take(limit) {
-        match extract::parse_email(path, cli.prefer_html, cli.strip_html) {
-            Ok(email) => results.push(email),
+    // With --search the limit counts matches, so read on until enough match
+    let to_read = if cli.search.is_some() { email_paths.len() } else { limit };
+    for path in email_paths.iter().take(to_read) {
+        if results.len() == limit {
+            break;
+        }
+        read += 1;
+        match extract::parse_email(path, cli.prefer_html, cli.strip_html, cli.decrypt) {
+            Ok(mut email) => {
+                if cli.trim_quotes {
+                    email.body = quotes::trim_quotes(&email.body);
+                }
+                // Searched before redaction, so redacted values can still be found
+                if let Some(ref pattern) = cli.search {
+                    if !search::matches(&email, pattern) {
+                        continue;
+                    }
+                }
+                if let Some(ref r) = redactor {
+                    r.redact_email(&mut email, &mut redact_counts);
+                }
+                results.push(email)
+            }
             Err(e) => {
                 eprintln!("Error processing {}: {}", path.display(), e);
                 errors += 1;
@@ -99,6 +232,24 @@ fn main() -> Result<()> {
 
     // Output results
     match cli.format {
+        OutputFormat::Continuum => {
+            if cli.my_address.is_empty() {
+                eprintln!("Warning: no --my-address given; every message will be a \"correspondent\"");
+            }
+            let out_dir = match cli.output_dir {
+                Some(ref dir) => dir.clone(),
+                None => continuum::default_output_dir()?,
+            };
+            for saved in continuum::export(&results, cli.thread, &cli.my_address, &out_dir)? {
+                match saved {
+                    continuum_core::Saved::Created(dir) => println!("Created: {}", dir.display()),
+                    continuum_core::Saved::Skipped(dir) => println!("Already imported: {}", dir.display()),
+                    continuum_core::Saved::Merged { dir, added } => {
+                        println!("Merged {} new messages into {}", added, dir.display())
+                    }
+                }
+            }
+        }
         OutputFormat::Json => {
             if let Some(ref out_dir) = cli.output_dir {
                 for email in &results {
@@ -118,13 +269,19 @@ fn main() -> Result<()> {
         }
         OutputFormat::Markdown => {
             if let Some(ref out_dir) = cli.output_dir {
+                let style = if cli.obsidian { media::EmbedStyle::Obsidian } else { media::EmbedStyle::Markdown };
+                let mut media = cli.extract_images.then(|| media::MediaWriter::new(out_dir));
                 for email in &results {
-                    let filename = output::safe_filename(&email.subject, &email.date) + ".md";
-                    let out_path = out_dir.join(&filename);
-                    let md = output::to_markdown(email, cli.metadata_only, cli.full_headers);
+                    let stem = output::safe_filename(&email.subject, &email.date);
+                    let out_path = out_dir.join(format!("{}.md", stem));
+                    let email = with_images(email, &stem, media.as_mut(), style)?;
+                    let md = output::to_markdown(&email, cli.metadata_only, cli.full_headers);
                     std::fs::write(&out_path, md)
                         .with_context(|| format!("Failed to write {}", out_path.display()))?;
                 }
+                if let Some(media) = media {
+                    eprintln!("{}", media.summary());
+                }
             } else {
                 for (i, email) in results.iter().enumerate() {
                     if i > 0 {
@@ -137,13 +294,18 @@ fn main() -> Result<()> {
         }
         OutputFormat::Text => {
             if let Some(ref out_dir) = cli.output_dir {
+                let mut media = cli.extract_images.then(|| media::MediaWriter::new(out_dir));
                 for email in &results {
-                    let filename = output::safe_filename(&email.subject, &email.date) + ".txt";
-                    let out_path = out_dir.join(&filename);
-                    let txt = output::to_text(email, cli.metadata_only, cli.full_headers);
+                    let stem = output::safe_filename(&email.subject, &email.date);
+                    let out_path = out_dir.join(format!("{}.txt", stem));
+                    let email = with_images(email, &stem, media.as_mut(), media::EmbedStyle::Text)?;
+                    let txt = output::to_text(&email, cli.metadata_only, cli.full_headers);
                     std::fs::write(&out_path, txt)
                         .with_context(|| format!("Failed to write {}", out_path.display()))?;
                 }
+                if let Some(media) = media {
+                    eprintln!("{}", media.summary());
+                }
             } else {
                 for (i, email) in results.iter().enumerate() {
                     if i > 0 {
@@ -156,8 +318,20 @@ fn main() -> Result<()> {
         }
     }
 
-    // Summary to stderr when processing multiple files
-    if results.len() + errors > 1 {
+    if let Some(ref r) = redactor {
+        eprintln!("{}", r.report(&redact_counts));
+    }
+
+    // Summary to stderr when searching or processing multiple files
+    if let Some(ref pattern) = cli.search {
+        eprintln!(
+            "\n{} of {} email(s) matched /{}/, {} error(s)",
+            results.len(),
+            read,
+            pattern,
+            errors
+        );
+    } else if results.len() + errors > 1 {
         eprintln!(
             "\nProcessed {} email(s), {} error(s)",
             results.len(),
@@ -168,6 +342,45 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// The email with its images written out and embedded in its body, when
+/// extracting images; otherwise unchanged
+fn with_images<'a>(
+    email: &'a extract::EmailData,
+    stem: &str,
+    media: Option<&mut media::MediaWriter>,
+    style: media::EmbedStyle,
+) -> Result<std::borrow::Cow<'a, extract::EmailData>> {
+    let Some(media) = media else {
+        return Ok(std::borrow::Cow::Borrowed(email));
+    };
+    let images = extract::extract_images(std::path::Path::new(&email.source_path))?;
+    if images.is_empty() {
+        return Ok(std::borrow::Cow::Borrowed(email));
+    }
+    let saved = media.save(stem, &images)?;
+    let mut email = email.clone();
+    email.body = media::embed_images(&email.body, &saved, style);
+    Ok(std::borrow::Cow::Owned(email))
+}
+
+/// Split each mbox file and write every message into one Maildir.
+fn convert_to_maildir(paths: &[PathBuf], maildir: &std::path::Path) -> Result<()> {
+    let mut messages = Vec::new();
+    for path in paths {
+        if !mbox::is_mbox(path) {
+            anyhow::bail!("{} is not an mbox file", path.display());
+        }
+        let data = std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let split = mbox::split(&data);
+        eprintln!("{}: {} message(s)", path.display(), split.len());
+        messages.extend(split);
+    }
+
+    let written = mbox::write_maildir(&messages, maildir)?;
+    eprintln!("Wrote {} message(s) to {}", written, maildir.join("cur").display());
+    Ok(())
+}
+
 /// Collect all email file paths from the given paths.
 /// If a path is a directory, scan for email files within it.
 /// If --maildir is set, look specifically in cur/, new/, tmp/ subdirectories.
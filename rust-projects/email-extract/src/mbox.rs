@@ -0,0 +1,257 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, NaiveDateTime};
+use std::path::Path;
+
+/// One message cut out of an mbox file: the raw bytes from the first header
+/// to the end of the body, with the `From ` separator line removed.
+#[derive(Debug)]
+pub struct MboxMessage {
+    /// The `From sender date` separator line, without its newline
+    pub from_line: String,
+    pub raw: Vec<u8>,
+}
+
+/// True if the file starts like an mbox (`From ` on the first line)
+pub fn is_mbox(path: &Path) -> bool {
+    use std::io::Read;
+    let mut start = [0u8; 5];
+    std::fs::File::open(path)
+        .and_then(|mut f| f.read_exact(&mut start))
+        .is_ok_and(|_| &start == b"From ")
+}
+
+/// Split mbox contents into messages. A message starts at a `From ` line
+/// at the top of the file or after a blank line. Bodies are unescaped the
+/// mboxrd way (`>From ` loses one `>`), and the blank line mbox puts between
+/// messages is dropped; everything else, headers included, is kept byte for
+/// byte.
+pub fn split(data: &[u8]) -> Vec<MboxMessage> {
+    let mut messages = Vec::new();
+    let mut current: Option<MboxMessage> = None;
+    let mut previous_blank = true;
+    let mut in_headers = false;
+
+    for line in data.split_inclusive(|&b| b == b'\n') {
+        let content = trim_newline(line);
+        if previous_blank && content.starts_with(b"From ") {
+            if let Some(message) = current.take() {
+                messages.push(finish(message));
+            }
+            current = Some(MboxMessage {
+                from_line: String::from_utf8_lossy(content).into_owned(),
+                raw: Vec::new(),
+            });
+            in_headers = true;
+            previous_blank = false;
+            continue;
+        }
+
+        previous_blank = content.is_empty();
+        let Some(message) = current.as_mut() else {
+            continue; // Junk before the first separator
+        };
+        if in_headers {
+            in_headers = !content.is_empty();
+            message.raw.extend_from_slice(line);
+        } else if is_escaped_from(content) {
+            message.raw.extend_from_slice(&line[1..]);
+        } else {
+            message.raw.extend_from_slice(line);
+        }
+    }
+    if let Some(message) = current {
+        messages.push(finish(message));
+    }
+    messages
+}
+
+fn trim_newline(line: &[u8]) -> &[u8] {
+    let line = line.strip_suffix(b"\n").unwrap_or(line);
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+/// `>From `, `>>From `, ...
+fn is_escaped_from(line: &[u8]) -> bool {
+    let quotes = line.iter().take_while(|&&b| b == b'>').count();
+    quotes > 0 && line[quotes..].starts_with(b"From ")
+}
+
+/// Drop the separator blank line that ends every message but the last
+fn finish(mut message: MboxMessage) -> MboxMessage {
+    for ending in [&b"\r\n\r\n"[..], b"\n\n"] {
+        if message.raw.ends_with(ending) {
+            let keep = message.raw.len() - ending.len() / 2;
+            message.raw.truncate(keep);
+            break;
+        }
+    }
+    message
+}
+
+/// Delivery time in seconds: the `From ` line's date, else the Date header
+fn delivery_time(message: &MboxMessage) -> Option<i64> {
+    // "From jane@example.com Tue Mar  3 10:00:00 2026"
+    let parts: Vec<&str> = message.from_line.split_whitespace().collect();
+    if parts.len() >= 7 {
+        let date = parts[parts.len() - 5..].join(" ");
+        if let Ok(naive) = NaiveDateTime::parse_from_str(&date, "%a %b %e %H:%M:%S %Y") {
+            return Some(naive.and_utc().timestamp());
+        }
+    }
+    let date = header_value(&message.raw, "date")?;
+    DateTime::parse_from_rfc2822(date.trim()).ok().map(|dt| dt.timestamp())
+}
+
+/// First value of a header (unfolded lines aren't needed for the short
+/// headers looked up here)
+fn header_value(raw: &[u8], name: &str) -> Option<String> {
+    for line in raw.split(|&b| b == b'\n') {
+        let line = trim_newline(line);
+        if line.is_empty() {
+            break;
+        }
+        let line = String::from_utf8_lossy(line);
+        if let Some((key, value)) = line.split_once(':') {
+            if key.eq_ignore_ascii_case(name) {
+                return Some(value.trim().to_string());
+            }
+        }
+    }
+    None
+}
+
+/// Maildir info flags from the mbox `Status` / `X-Status` headers, in the
+/// ASCII order Maildir requires: F(lagged), R(eplied), S(een), T(rashed)
+fn maildir_flags(raw: &[u8]) -> String {
+    let status = header_value(raw, "status").unwrap_or_default();
+    let x_status = header_value(raw, "x-status").unwrap_or_default();
+    let mut flags = String::new();
+    if x_status.contains('F') {
+        flags.push('F');
+    }
+    if x_status.contains('A') {
+        flags.push('R');
+    }
+    if status.contains('R') {
+        flags.push('S');
+    }
+    if x_status.contains('D') {
+        flags.push('T');
+    }
+    flags
+}
+
+fn hostname() -> String {
+    let name = std::fs::read_to_string("/etc/hostname")
+        .ok()
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .map(|h| h.trim().to_string())
+        .filter(|h| !h.is_empty())
+        .unwrap_or_else(|| "localhost".to_string());
+    // Maildir escapes the characters it uses as separators
+    name.replace('/', "\\057").replace(':', "\\072")
+}
+
+/// Unique Maildir name: `time.PpidQseq.host,S=size:2,flags`
+fn maildir_name(time: i64, seq: usize, host: &str, size: usize, flags: &str) -> String {
+    format!("{}.P{}Q{}.{},S={}:2,{}", time, std::process::id(), seq, host, size, flags)
+}
+
+/// Write messages into a Maildir (creating `cur/`, `new/` and `tmp/`), each
+/// via `tmp/` then renamed into `cur/` as Maildir delivery does. Returns
+/// the number of messages written.
+pub fn write_maildir(messages: &[MboxMessage], dir: &Path) -> Result<usize> {
+    for sub in ["cur", "new", "tmp"] {
+        std::fs::create_dir_all(dir.join(sub))
+            .with_context(|| format!("Failed to create {}", dir.join(sub).display()))?;
+    }
+
+    let host = hostname();
+    let now = chrono::Utc::now().timestamp();
+    let mut written = 0;
+    for (seq, message) in messages.iter().enumerate() {
+        let time = delivery_time(message).unwrap_or(now);
+        let name = maildir_name(time, seq, &host, message.raw.len(), &maildir_flags(&message.raw));
+        let (tmp, cur) = (dir.join("tmp").join(&name), dir.join("cur").join(&name));
+        if cur.exists() {
+            bail!("{} already exists", cur.display());
+        }
+        std::fs::write(&tmp, &message.raw).with_context(|| format!("Failed to write {}", tmp.display()))?;
+        std::fs::rename(&tmp, &cur).with_context(|| format!("Failed to move {} into cur/", name))?;
+        written += 1;
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MBOX: &[u8] = b"From jane@example.com Tue Mar  3 10:00:00 2026\r
+Return-Path: <jane@example.com>\r
+From: Jane Smith <jane@example.com>\r
+Subject: =?UTF-8?Q?Caf=C3=A9?= plans\r
+Status: RO\r
+X-Status: A\r
+\r
+Meet at the caf\xc3\xa9?\r
+>From the top, as agreed.\r
+\r
+From bob@example.com Wed Mar  4 09:15:30 2026
+From: Bob <bob@example.com>
+Subject: Unread
+X-Status: F
+
+Quoted in body:
+>>From here
+From lines mid-paragraph stay
+";
+
+    #[test]
+    fn splits_and_unescapes_without_touching_headers() {
+        let messages = split(MBOX);
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].from_line, "From jane@example.com Tue Mar  3 10:00:00 2026");
+        assert_eq!(
+            messages[0].raw,
+            b"Return-Path: <jane@example.com>\r\nFrom: Jane Smith <jane@example.com>\r\nSubject: =?UTF-8?Q?Caf=C3=A9?= plans\r\nStatus: RO\r\nX-Status: A\r\n\r\nMeet at the caf\xc3\xa9?\r\nFrom the top, as agreed.\r\n"
+        );
+        assert_eq!(
+            messages[1].raw,
+            b"From: Bob <bob@example.com>\nSubject: Unread\nX-Status: F\n\nQuoted in body:\n>From here\nFrom lines mid-paragraph stay\n"
+        );
+    }
+
+    #[test]
+    fn flags_and_delivery_time() {
+        let messages = split(MBOX);
+        assert_eq!(maildir_flags(&messages[0].raw), "RS");
+        assert_eq!(maildir_flags(&messages[1].raw), "F");
+        assert_eq!(delivery_time(&messages[0]), Some(1772532000));
+
+        let no_date_line = MboxMessage {
+            from_line: "From MAILER-DAEMON".to_string(),
+            raw: b"Date: Tue, 03 Mar 2026 10:00:00 +0000\n\nHi\n".to_vec(),
+        };
+        assert_eq!(delivery_time(&no_date_line), Some(1772532000));
+    }
+
+    #[test]
+    fn writes_maildir_cur() {
+        let dir = tempfile::tempdir().unwrap();
+        let messages = split(MBOX);
+        assert_eq!(write_maildir(&messages, dir.path()).unwrap(), 2);
+
+        let mut names: Vec<String> = std::fs::read_dir(dir.path().join("cur"))
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert!(names[0].starts_with("1772532000.P"));
+        assert!(names[0].ends_with(&format!(",S={}:2,RS", messages[0].raw.len())));
+        assert!(names[1].ends_with(":2,F"));
+        assert_eq!(std::fs::read(dir.path().join("cur").join(&names[0])).unwrap(), messages[0].raw);
+        assert_eq!(std::fs::read_dir(dir.path().join("tmp")).unwrap().count(), 0);
+        assert!(dir.path().join("new").is_dir());
+    }
+}
@@ -0,0 +1,256 @@
+use crate::extract::ImagePart;
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+/// Folder under the output directory that extracted images go in
+pub const MEDIA_DIR: &str = "linked_media";
+
+/// How an extracted image is referenced from the output
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EmbedStyle {
+    /// `![[linked_media/x.png]]`
+    Obsidian,
+    /// `![x.png](linked_media/x.png)`
+    Markdown,
+    /// `[image: linked_media/x.png]`
+    Text,
+}
+
+impl EmbedStyle {
+    fn embed(self, link: &str) -> String {
+        match self {
+            EmbedStyle::Obsidian => format!("![[{}]]", link),
+            EmbedStyle::Markdown => {
+                let name = link.rsplit('/').next().unwrap_or(link);
+                format!("![{}]({})", name, link.replace(' ', "%20"))
+            }
+            EmbedStyle::Text => format!("[image: {}]", link),
+        }
+    }
+}
+
+/// An email's images once written: `linked_media/...` paths relative to the
+/// output directory, by Content-ID for inline ones
+#[derive(Debug, Default)]
+pub struct SavedImages {
+    pub by_cid: HashMap<String, String>,
+    /// Every image's path in message order, inline ones included
+    pub links: Vec<String>,
+}
+
+/// Writes images into `<output-dir>/linked_media/`, once per distinct
+/// content across a run
+pub struct MediaWriter {
+    dir: PathBuf,
+    by_hash: HashMap<(u64, usize), String>,
+    pub written: usize,
+    pub reused: usize,
+}
+
+impl MediaWriter {
+    pub fn new(output_dir: &Path) -> Self {
+        Self {
+            dir: output_dir.join(MEDIA_DIR),
+            by_hash: HashMap::new(),
+            written: 0,
+            reused: 0,
+        }
+    }
+
+    /// Write an email's images as `<stem>-1.png`, `<stem>-2.jpg`, ...; an
+    /// image identical to one already written this run links to that file
+    pub fn save(&mut self, stem: &str, images: &[ImagePart]) -> Result<SavedImages> {
+        let mut saved = SavedImages::default();
+        for (i, image) in images.iter().enumerate() {
+            let key = (content_hash(&image.data), image.data.len());
+            let name = match self.by_hash.get(&key) {
+                Some(name) => {
+                    self.reused += 1;
+                    name.clone()
+                }
+                None => {
+                    let name = format!("{}-{}.{}", stem, i + 1, extension(image));
+                    std::fs::create_dir_all(&self.dir)
+                        .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+                    let path = self.dir.join(&name);
+                    std::fs::write(&path, &image.data)
+                        .with_context(|| format!("Failed to write {}", path.display()))?;
+                    self.written += 1;
+                    self.by_hash.insert(key, name.clone());
+                    name
+                }
+            };
+            let link = format!("{}/{}", MEDIA_DIR, name);
+            if let Some(ref cid) = image.content_id {
+                saved.by_cid.insert(cid.clone(), link.clone());
+            }
+            saved.links.push(link);
+        }
+        Ok(saved)
+    }
+
+    /// `Wrote 3 image(s) to out/linked_media (2 duplicates reused)`
+    pub fn summary(&self) -> String {
+        let reused = if self.reused > 0 {
+            format!(" ({} duplicates reused)", self.reused)
+        } else {
+            String::new()
+        };
+        format!("Wrote {} image(s) to {}{}", self.written, self.dir.display(), reused)
+    }
+}
+
+fn content_hash(data: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    data.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// The attachment's own extension if it has a sensible one, else one for
+/// its content type
+fn extension(image: &ImagePart) -> String {
+    let from_name = image
+        .filename
+        .as_deref()
+        .and_then(|name| Path::new(name).extension())
+        .and_then(|ext| ext.to_str())
+        .filter(|ext| !ext.is_empty() && ext.chars().all(|c| c.is_ascii_alphanumeric()));
+    if let Some(ext) = from_name {
+        return ext.to_lowercase();
+    }
+    match image.content_type.trim_start_matches("image/") {
+        "jpeg" | "pjpeg" => "jpg".to_string(),
+        "svg+xml" => "svg".to_string(),
+        other if !other.is_empty() && other.chars().all(|c| c.is_ascii_alphanumeric()) => other.to_string(),
+        _ => "img".to_string(),
+    }
+}
+
+/// Replace `cid:` references in a body (`[cid:x]`, as plain-text parts and
+/// converted HTML have them, or bare `cid:x`) with embeds of the saved
+/// images. Then embed any image the body never referenced, under an
+/// "Images" heading, so attached images appear too.
+pub fn embed_images(body: &str, saved: &SavedImages, style: EmbedStyle) -> String {
+    let cid_ref = Regex::new(r"\[cid:([^\]\s]+)\]|cid:([^\s\]>\x22')]+)").unwrap();
+    let mut referenced: Vec<&str> = Vec::new();
+    let mut out = cid_ref
+        .replace_all(body, |caps: &regex::Captures| {
+            let cid = caps.get(1).or_else(|| caps.get(2)).unwrap().as_str();
+            match saved.by_cid.get(cid) {
+                Some(link) => {
+                    referenced.push(link);
+                    style.embed(link)
+                }
+                None => caps[0].to_string(),
+            }
+        })
+        .into_owned();
+
+    let mut unreferenced: Vec<&String> = saved.links.iter().filter(|l| !referenced.contains(&l.as_str())).collect();
+    unreferenced.dedup();
+    if !unreferenced.is_empty() {
+        if !out.ends_with('\n') {
+            out.push('\n');
+        }
+        out.push_str(match style {
+            EmbedStyle::Text => "\nImages:\n",
+            _ => "\n## Images\n\n",
+        });
+        for link in unreferenced {
+            out.push_str(&format!("{}\n", style.embed(link)));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image(cid: Option<&str>, filename: Option<&str>, content_type: &str, data: &[u8]) -> ImagePart {
+        ImagePart {
+            content_id: cid.map(str::to_string),
+            filename: filename.map(str::to_string),
+            content_type: content_type.to_string(),
+            data: data.to_vec(),
+        }
+    }
+
+    #[test]
+    fn writes_images_named_after_the_email() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut writer = MediaWriter::new(dir.path());
+        let saved = writer
+            .save("2025-03-06-receipt", &[
+                image(Some("logo@shop"), None, "image/jpeg", b"jpeg"),
+                image(None, Some("Receipt.PNG"), "image/png", b"png"),
+            ])
+            .unwrap();
+
+        assert_eq!(saved.links, ["linked_media/2025-03-06-receipt-1.jpg", "linked_media/2025-03-06-receipt-2.png"]);
+        assert_eq!(saved.by_cid["logo@shop"], "linked_media/2025-03-06-receipt-1.jpg");
+        assert_eq!(std::fs::read(dir.path().join("linked_media/2025-03-06-receipt-2.png")).unwrap(), b"png");
+    }
+
+    #[test]
+    fn identical_images_are_written_once_per_run() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut writer = MediaWriter::new(dir.path());
+        writer.save("first", &[image(Some("a"), None, "image/gif", b"logo")]).unwrap();
+        let second = writer.save("second", &[image(Some("b"), None, "image/gif", b"logo")]).unwrap();
+
+        assert_eq!(second.by_cid["b"], "linked_media/first-1.gif");
+        assert_eq!((writer.written, writer.reused), (1, 1));
+        assert!(!dir.path().join("linked_media/second-1.gif").exists());
+    }
+
+    #[test]
+    fn cid_references_become_embeds() {
+        let saved = SavedImages {
+            by_cid: HashMap::from([("logo@shop".to_string(), "linked_media/r-1.png".to_string())]),
+            links: vec!["linked_media/r-1.png".to_string(), "linked_media/r-2.jpg".to_string()],
+        };
+        let body = "Thanks for your order\n[cid:logo@shop]\nSee cid:unknown@x\n";
+
+        assert_eq!(
+            embed_images(body, &saved, EmbedStyle::Obsidian),
+            "Thanks for your order\n![[linked_media/r-1.png]]\nSee cid:unknown@x\n\n## Images\n\n![[linked_media/r-2.jpg]]\n"
+        );
+        assert!(embed_images(body, &saved, EmbedStyle::Markdown).contains("![r-1.png](linked_media/r-1.png)\n"));
+        assert!(embed_images(body, &saved, EmbedStyle::Text).ends_with("\nImages:\n[image: linked_media/r-2.jpg]\n"));
+    }
+
+    #[test]
+    fn fixture_images_written_and_embedded() {
+        let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/inline-images.eml");
+        let email = crate::extract::parse_email(&fixture, false, false, false).unwrap();
+        let images = crate::extract::extract_images(&fixture).unwrap();
+        assert_eq!(images.len(), 2);
+        assert_eq!(images[0].content_id.as_deref(), Some("logo@bookshop.example"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut writer = MediaWriter::new(dir.path());
+        let saved = writer.save("2025-03-06-your-order", &images).unwrap();
+        assert!(dir.path().join("linked_media/2025-03-06-your-order-1.png").is_file());
+        assert!(dir.path().join("linked_media/2025-03-06-your-order-2.jpg").is_file());
+
+        let body = embed_images(&email.body, &saved, EmbedStyle::Obsidian);
+        assert!(body.contains("Thanks for your order.\n![[linked_media/2025-03-06-your-order-1.png]]\n"));
+        assert!(body.ends_with("## Images\n\n![[linked_media/2025-03-06-your-order-2.jpg]]\n"));
+        assert!(!body.contains("cid:"));
+
+        // The PDF is left to the attachment list
+        assert!(email.attachments.iter().any(|a| a.filename == "invoice.pdf"));
+    }
+
+    #[test]
+    fn extension_falls_back_to_content_type() {
+        assert_eq!(extension(&image(None, Some("scan"), "image/svg+xml", b"")), "svg");
+        assert_eq!(extension(&image(None, None, "image/webp", b"")), "webp");
+        assert_eq!(extension(&image(None, None, "image/", b"")), "img");
+    }
+}
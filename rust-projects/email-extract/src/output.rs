@@ -1,3 +1,4 @@
+use crate::calendar::CalendarEvent;
 use crate::extract::{BodyType, EmailData};
 use anyhow::Result;
 use regex::Regex;
@@ -14,6 +15,17 @@ pub fn to_text(email: &EmailData, metadata_only: bool, full_headers: bool) -> St
     }
     out.push_str(&format!("Date:    {}\n", display_date(email)));
     out.push_str(&format!("Subject: {}\n", email.subject));
+    if let Some(pgp) = email.pgp {
+        out.push_str(&format!("PGP:     {}\n", pgp.as_str()));
+    }
+
+    if let Some(ref event) = email.event {
+        out.push('\n');
+        out.push_str("Event:\n");
+        for (key, value) in event_fields(event) {
+            out.push_str(&format!("  {:<10}{}\n", format!("{}:", key), value));
+        }
+    }
 
     if full_headers {
         out.push('\n');
@@ -31,6 +43,9 @@ pub fn to_text(email: &EmailData, metadata_only: bool, full_headers: bool) -> St
                 "  - {} ({}, {} bytes)\n",
                 att.filename, att.content_type, att.size
             ));
+            if let Some(ref path) = att.external_path {
+                out.push_str(&format!("    external: {}\n", path));
+            }
         }
     }
 
@@ -86,6 +101,9 @@ pub fn to_markdown(email: &EmailData, metadata_only: bool, full_headers: bool) -
         BodyType::Empty => "empty",
     };
     out.push_str(&format!("body_type: \"{}\"\n", body_type_str));
+    if let Some(pgp) = email.pgp {
+        out.push_str(&format!("pgp: \"{}\"\n", pgp.as_str()));
+    }
 
     if !email.attachments.is_empty() {
         out.push_str("attachments:\n");
@@ -96,6 +114,16 @@ pub fn to_markdown(email: &EmailData, metadata_only: bool, full_headers: bool) -
                 att.content_type,
                 att.size
             ));
+            if let Some(ref path) = att.external_path {
+                out.push_str(&format!("    path: \"{}\"\n", yaml_escape(path)));
+            }
+        }
+    }
+
+    if let Some(ref event) = email.event {
+        out.push_str("event:\n");
+        for (key, value) in event_fields(event) {
+            out.push_str(&format!("  {}: \"{}\"\n", key, yaml_escape(&value)));
         }
     }
 
@@ -175,10 +203,18 @@ fn email_to_json_value(email: &EmailData, metadata_only: bool) -> Value {
     };
     obj["body_type"] = json!(body_type_str);
 
+    if let Some(pgp) = email.pgp {
+        obj["pgp"] = json!(pgp);
+    }
+
     if !email.attachments.is_empty() {
         obj["attachments"] = json!(email.attachments);
     }
 
+    if let Some(ref event) = email.event {
+        obj["event"] = json!(event);
+    }
+
     if !metadata_only {
         obj["body"] = json!(email.body);
     }
@@ -186,6 +222,22 @@ fn email_to_json_value(email: &EmailData, metadata_only: bool) -> Value {
     obj
 }
 
+/// The populated fields of a calendar event, in display order. Line breaks
+/// in descriptions are flattened so each field stays on one line.
+fn event_fields(event: &CalendarEvent) -> Vec<(&'static str, String)> {
+    [
+        ("summary", &event.summary),
+        ("start", &event.start),
+        ("end", &event.end),
+        ("timezone", &event.timezone),
+        ("location", &event.location),
+        ("organizer", &event.organizer),
+    ]
+    .into_iter()
+    .filter_map(|(key, value)| Some((key, value.as_ref()?.replace('\n', " "))))
+    .collect()
+}
+
 /// Generate a safe filename from subject and date.
 pub fn safe_filename(subject: &str, date: &str) -> String {
     // Try to extract a date prefix
@@ -251,6 +303,7 @@ fn display_date(email: &EmailData) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::pgp::Protection;
 
     #[test]
     fn test_safe_filename_with_date() {
@@ -279,6 +332,55 @@ mod tests {
         );
     }
 
+    fn invite() -> EmailData {
+        EmailData {
+            from: "jo@example.com".into(),
+            to: "b@example.com".into(),
+            date_parsed: Some("2025-02-20T09:00:00+00:00".into()),
+            event: Some(CalendarEvent {
+                summary: Some("Review".into()),
+                start: Some("2025-03-04T10:00:00".into()),
+                end: Some("2025-03-04T11:00:00".into()),
+                timezone: Some("Europe/London".into()),
+                location: Some("Room \"4\"\nEast wing".into()),
+                organizer: None,
+            }),
+            source_path: "invite.eml".into(),
+            ..EmailData::test("Invitation: Review", "You have been invited.\n")
+        }
+    }
+
+    #[test]
+    fn test_event_in_markdown_frontmatter() {
+        let md = to_markdown(&invite(), false, false);
+        assert!(md.contains(
+            "event:\n  summary: \"Review\"\n  start: \"2025-03-04T10:00:00\"\n  end: \"2025-03-04T11:00:00\"\n  \
+             timezone: \"Europe/London\"\n  location: \"Room \\\"4\\\" East wing\"\nsource:"
+        ));
+    }
+
+    #[test]
+    fn test_event_in_json_and_text() {
+        let value: Value = serde_json::from_str(&to_json(&invite(), true).unwrap()).unwrap();
+        assert_eq!(value["event"]["start"], "2025-03-04T10:00:00");
+        assert_eq!(value["event"]["organizer"], Value::Null);
+
+        let text = to_text(&invite(), true, false);
+        assert!(text.contains("Event:\n  summary:  Review\n  start:    2025-03-04T10:00:00\n"));
+    }
+
+    #[test]
+    fn test_pgp_status_shown() {
+        let mut email = invite();
+        assert!(!to_markdown(&email, true, false).contains("pgp:"));
+
+        email.pgp = Some(Protection::Signed);
+        assert!(to_markdown(&email, true, false).contains("body_type: \"text/plain\"\npgp: \"signed\"\n"));
+        assert!(to_text(&email, true, false).contains("Subject: Invitation: Review\nPGP:     signed\n"));
+        let value: Value = serde_json::from_str(&to_json(&email, true).unwrap()).unwrap();
+        assert_eq!(value["pgp"], "signed");
+    }
+
     #[test]
     fn test_yaml_escape() {
         assert_eq!(yaml_escape(r#"say "hello""#), r#"say \"hello\""#);
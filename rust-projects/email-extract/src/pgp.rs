@@ -0,0 +1,151 @@
+//! PGP-protected messages: PGP/MIME (RFC 3156) `multipart/signed` and
+//! `multipart/encrypted` parts, and the older inline armor in text bodies.
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Stands in for the body of an encrypted message that wasn't decrypted
+pub const ENCRYPTED_NOTE: &str = "[encrypted message – not decrypted]";
+
+const BEGIN_SIGNED: &str = "-----BEGIN PGP SIGNED MESSAGE-----";
+const BEGIN_SIGNATURE: &str = "-----BEGIN PGP SIGNATURE-----";
+const END_SIGNATURE: &str = "-----END PGP SIGNATURE-----";
+const BEGIN_MESSAGE: &str = "-----BEGIN PGP MESSAGE-----";
+const END_MESSAGE: &str = "-----END PGP MESSAGE-----";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Protection {
+    /// Signed: the content is shown and the signature dropped (not verified)
+    Signed,
+    /// Encrypted and left that way
+    Encrypted,
+    /// Encrypted, then decrypted with `--decrypt`
+    Decrypted,
+}
+
+impl Protection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Protection::Signed => "signed",
+            Protection::Encrypted => "encrypted",
+            Protection::Decrypted => "decrypted",
+        }
+    }
+}
+
+/// `text` with an inline clearsigned block reduced to the signed text:
+/// armor headers and signature dropped, dash-escaping undone. None when
+/// there's no complete clearsigned block.
+pub fn strip_clearsign(text: &str) -> Option<String> {
+    let lines: Vec<&str> = text.lines().collect();
+    let is = |line: &str, marker: &str| line.trim_end() == marker;
+
+    let begin = lines.iter().position(|l| is(l, BEGIN_SIGNED))?;
+    let signature = begin + lines[begin..].iter().position(|l| is(l, BEGIN_SIGNATURE))?;
+    let end = signature + lines[signature..].iter().position(|l| is(l, END_SIGNATURE))?;
+
+    // Armor headers ("Hash: SHA256") run up to the first blank line
+    let headers = &lines[begin + 1..signature];
+    let content = begin + 1 + headers.iter().position(|l| l.trim().is_empty()).map_or(0, |i| i + 1);
+
+    let mut out: Vec<&str> = lines[..begin].to_vec();
+    out.extend(lines[content..signature].iter().map(|l| l.strip_prefix("- ").unwrap_or(l)));
+    out.extend(&lines[end + 1..]);
+    Some(out.join("\n") + "\n")
+}
+
+/// `text` with each inline `PGP MESSAGE` block, armor lines included,
+/// swapped for what `replace` makes of it. None when there isn't one.
+pub fn replace_inline_messages(text: &str, mut replace: impl FnMut(&str) -> String) -> Option<String> {
+    let mut out = String::new();
+    let mut rest = text;
+    let mut found = false;
+
+    while let Some(start) = rest.find(BEGIN_MESSAGE) {
+        let Some(len) = rest[start..].find(END_MESSAGE) else {
+            break;
+        };
+        let end = start + len + END_MESSAGE.len();
+        out.push_str(&rest[..start]);
+        out.push_str(replace(&rest[start..end]).trim_end());
+        rest = &rest[end..];
+        found = true;
+    }
+
+    found.then(|| out + rest)
+}
+
+/// Decrypt armored or binary OpenPGP data with `gpg --decrypt`, using the
+/// user's own keyring and agent
+pub fn decrypt(ciphertext: &[u8]) -> Result<Vec<u8>> {
+    let mut child = Command::new("gpg")
+        .args(["--decrypt", "--batch", "--quiet", "--no-tty"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run gpg (is it installed?)")?;
+
+    // Feed stdin from another thread so a large message can't fill both
+    // pipes and deadlock
+    let mut stdin = child.stdin.take().context("gpg stdin unavailable")?;
+    let input = ciphertext.to_vec();
+    let writer = std::thread::spawn(move || stdin.write_all(&input));
+    let output = child.wait_with_output().context("Failed to run gpg")?;
+    let written = writer.join().map_err(|_| anyhow::anyhow!("gpg input thread panicked"))?;
+
+    if !output.status.success() {
+        bail!("gpg could not decrypt: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    written.context("Failed to send message to gpg")?;
+    Ok(output.stdout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLEARSIGNED: &str = "Forwarding as agreed.\n\n\
+        -----BEGIN PGP SIGNED MESSAGE-----\n\
+        Hash: SHA256\n\
+        \n\
+        Meeting moved to Thursday.\n\
+        - -- \n\
+        Jo\n\
+        -----BEGIN PGP SIGNATURE-----\n\
+        \n\
+        iQEzBAEBCAAdFiEE\n\
+        =abcd\n\
+        -----END PGP SIGNATURE-----\n";
+
+    #[test]
+    fn clearsign_keeps_only_the_signed_text() {
+        assert_eq!(
+            strip_clearsign(CLEARSIGNED).unwrap(),
+            "Forwarding as agreed.\n\nMeeting moved to Thursday.\n-- \nJo\n"
+        );
+    }
+
+    #[test]
+    fn clearsign_needs_a_complete_block() {
+        assert_eq!(strip_clearsign("Plain text\n"), None);
+        assert_eq!(strip_clearsign("-----BEGIN PGP SIGNED MESSAGE-----\nHash: SHA256\n\nCut off"), None);
+    }
+
+    #[test]
+    fn inline_messages_are_replaced() {
+        let text = "Hi,\n\n-----BEGIN PGP MESSAGE-----\n\nhQEMA1b2\n=xyz\n-----END PGP MESSAGE-----\n\nThanks\n";
+        let mut seen = Vec::new();
+        let replaced = replace_inline_messages(text, |block| {
+            seen.push(block.to_string());
+            ENCRYPTED_NOTE.to_string()
+        });
+
+        assert_eq!(replaced.unwrap(), format!("Hi,\n\n{}\n\nThanks\n", ENCRYPTED_NOTE));
+        assert_eq!(seen, vec!["-----BEGIN PGP MESSAGE-----\n\nhQEMA1b2\n=xyz\n-----END PGP MESSAGE-----"]);
+        assert_eq!(replace_inline_messages("Nothing here\n", |_| unreachable!()), None);
+    }
+}
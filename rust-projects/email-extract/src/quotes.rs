@@ -0,0 +1,195 @@
+use regex::Regex;
+
+/// Remove quoted reply history from an email body, keeping the new content
+/// and ending with a `[quoted history trimmed: N lines]` marker.
+///
+/// Trimmed:
+/// - lines starting with `>`, wherever they are (inline replies keep the
+///   unquoted answers between them)
+/// - an `On ... wrote:` attribution (possibly wrapped over three lines); if
+///   the history after it isn't `>`-quoted, everything from it to the end
+/// - everything from an Outlook `-----Original Message-----` separator, or a
+///   mid-body `From:` line followed by `Sent:`/`Date:`/`To:`/`Subject:`/`Cc:`
+///   lines (and the `____` rule Outlook draws above it)
+///
+/// The body is returned unchanged when nothing matches, or when trimming
+/// would leave no content (a bare forward, or an all-quote message).
+pub fn trim_quotes(body: &str) -> String {
+    let separator_re = Regex::new(r"(?i)^-{2,}\s*original message\s*-{2,}$").unwrap();
+    let header_re = Regex::new(r"(?i)^\s*(sent|date|to|subject|cc):\s").unwrap();
+    let attribution_re = Regex::new(r"^On\s.+\swrote:$").unwrap();
+
+    let lines: Vec<&str> = body.lines().collect();
+    let mut removed = vec![false; lines.len()];
+
+    // Unquoted history: cut from the first separator or header block to the end
+    let cut = (0..lines.len()).find(|&i| {
+        has_content_before(&lines, i)
+            && (separator_re.is_match(lines[i].trim()) || is_header_block(&lines, i, &header_re))
+    });
+    let end = cut.map_or(lines.len(), |start| {
+        let mut start = start;
+        // Outlook's rule line above the header block goes too
+        if let Some(prev) = previous_content(&lines, start) {
+            if is_rule(lines[prev]) {
+                start = prev;
+            }
+        }
+        removed[start..].iter_mut().for_each(|r| *r = true);
+        start
+    });
+
+    let mut i = 0;
+    while i < end {
+        if let Some(len) = attribution_len(&lines[i..end], &attribution_re) {
+            let after = i + len;
+            let quoted_next = lines[after..end]
+                .iter()
+                .find(|l| !l.trim().is_empty())
+                .is_some_and(|l| is_quoted(l));
+            let stop = if quoted_next { after } else { end };
+            removed[i..stop].iter_mut().for_each(|r| *r = true);
+            i = stop;
+            continue;
+        }
+        if is_quoted(lines[i]) {
+            removed[i] = true;
+        }
+        i += 1;
+    }
+
+    let trimmed_count = removed.iter().filter(|&&r| r).count();
+    if trimmed_count == 0 {
+        return body.to_string();
+    }
+
+    // Keep the rest, collapsing the blank runs left where quotes were
+    let mut kept: Vec<&str> = Vec::new();
+    for (line, _) in lines.iter().zip(&removed).filter(|(_, &r)| !r) {
+        let blank = line.trim().is_empty();
+        if blank && kept.last().is_none_or(|l| l.trim().is_empty()) {
+            continue;
+        }
+        kept.push(line);
+    }
+    while kept.last().is_some_and(|l| l.trim().is_empty()) {
+        kept.pop();
+    }
+    if kept.is_empty() {
+        return body.to_string();
+    }
+
+    format!("{}\n\n[quoted history trimmed: {} lines]\n", kept.join("\n"), trimmed_count)
+}
+
+fn is_quoted(line: &str) -> bool {
+    line.trim_start().starts_with('>')
+}
+
+fn is_rule(line: &str) -> bool {
+    let line = line.trim();
+    line.len() >= 10 && line.chars().all(|c| c == '_')
+}
+
+/// A `From:` line with at least two more mail headers in the next five lines
+fn is_header_block(lines: &[&str], i: usize, header_re: &Regex) -> bool {
+    if !lines[i].trim_start().to_lowercase().starts_with("from:") {
+        return false;
+    }
+    lines[i + 1..]
+        .iter()
+        .take(5)
+        .filter(|l| header_re.is_match(l))
+        .count()
+        >= 2
+}
+
+/// Number of lines (1 to 3) making up an `On ... wrote:` attribution at the
+/// start of `lines`
+fn attribution_len(lines: &[&str], attribution_re: &Regex) -> Option<usize> {
+    if !lines.first()?.trim_start().starts_with("On ") {
+        return None;
+    }
+    let mut joined = String::new();
+    for (n, line) in lines.iter().take(3).enumerate() {
+        if !joined.is_empty() {
+            joined.push(' ');
+        }
+        joined.push_str(line.trim());
+        if attribution_re.is_match(&joined) {
+            return Some(n + 1);
+        }
+    }
+    None
+}
+
+fn has_content_before(lines: &[&str], i: usize) -> bool {
+    previous_content(lines, i).is_some()
+}
+
+fn previous_content(lines: &[&str], i: usize) -> Option<usize> {
+    (0..i).rev().find(|&j| !lines[j].trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reply_styles() {
+        let cases: &[(&str, &str, &str)] = &[
+            (
+                "gmail top-post",
+                "Sounds good, see you then.\n\nOn Tue, 3 Mar 2026 at 10:00, Jane Smith <jane@example.com> wrote:\n> Shall we meet at 4?\n>\n>> On Mon, Bob wrote:\n>> Are you free this week?\n",
+                "Sounds good, see you then.\n\n[quoted history trimmed: 5 lines]\n",
+            ),
+            (
+                "attribution wrapped over two lines",
+                "Yes.\n\nOn Tue, 3 Mar 2026 at 10:00, Jane Smith <\njane@example.com> wrote:\n\n> Confirm?\n",
+                "Yes.\n\n[quoted history trimmed: 3 lines]\n",
+            ),
+            (
+                "apple mail unquoted history",
+                "Thanks!\n\nOn 3 Mar 2026, at 10:00, Jane Smith wrote:\n\nHere is the agenda.\nItem one.\n",
+                "Thanks!\n\n[quoted history trimmed: 4 lines]\n",
+            ),
+            (
+                "outlook original message",
+                "Please see below.\n\n-----Original Message-----\nFrom: Jane Smith\nSent: 03 March 2026 10:00\nTo: William\nSubject: Referral\n\nCould you take this client?\n",
+                "Please see below.\n\n[quoted history trimmed: 7 lines]\n",
+            ),
+            (
+                "outlook header block under a rule",
+                "Approved.\n\n________________________________\nFrom: Jane Smith <jane@example.com>\nSent: Tuesday, March 3, 2026 10:00 AM\nTo: William Napier\nSubject: Invoice\n\nInvoice attached.\n",
+                "Approved.\n\n[quoted history trimmed: 7 lines]\n",
+            ),
+            (
+                "inline reply mixture",
+                "Answers inline.\n\nOn Tue, Jane wrote:\n> Can you do Thursday?\nYes, after 2pm.\n\n> And the room?\nRoom 4 is booked.\n\n> Thanks\n",
+                "Answers inline.\n\nYes, after 2pm.\n\nRoom 4 is booked.\n\n[quoted history trimmed: 4 lines]\n",
+            ),
+            (
+                "bottom-post keeps the reply",
+                "> Is the report done?\n\nIt is, sending now.\n\nWilliam\n",
+                "It is, sending now.\n\nWilliam\n\n[quoted history trimmed: 1 lines]\n",
+            ),
+        ];
+        for (name, input, expected) in cases {
+            assert_eq!(trim_quotes(input), *expected, "case: {}", name);
+        }
+    }
+
+    #[test]
+    fn left_alone() {
+        let cases: &[(&str, &str)] = &[
+            ("no quotes", "Just a note.\nOn Monday I'll call.\n"),
+            ("header block at the top is the message itself", "From: Jane\nTo: William\nSubject: Hi\n\nHello\n"),
+            ("all quoted", "> only\n> quotes\n"),
+            ("attribution only", "On Tue, Jane wrote:\n> Hi\n"),
+            ("not an attribution", "On reflection, I think she wrote: yes.\n"),
+        ];
+        for (name, input) in cases {
+            assert_eq!(trim_quotes(input), *input, "case: {}", name);
+        }
+    }
+}
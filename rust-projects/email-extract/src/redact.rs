@@ -0,0 +1,296 @@
+use crate::extract::EmailData;
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// One entry in redact.toml: a regex or a literal string, and the label
+/// that replaces each match.
+#[derive(Debug, Deserialize)]
+struct PatternConfig {
+    /// Name used in error messages and the stderr report (default: the label)
+    name: Option<String>,
+    regex: Option<String>,
+    literal: Option<String>,
+    label: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RedactConfig {
+    #[serde(default, rename = "pattern")]
+    patterns: Vec<PatternConfig>,
+}
+
+/// How a match is replaced.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    /// The pattern's label, e.g. `[ACCOUNT]`
+    Label,
+    /// A short hash of the matched text, so distinct values stay distinct
+    Hash,
+}
+
+struct Rule {
+    name: String,
+    label: String,
+    regex: Regex,
+}
+
+/// A compiled set of redaction patterns.
+pub struct Redactor {
+    rules: Vec<Rule>,
+    mode: Mode,
+}
+
+/// ~/.config/email-extract/redact.toml
+pub fn default_config_path() -> Result<PathBuf> {
+    let home = std::env::var_os("HOME").context("couldn't resolve $HOME")?;
+    Ok(PathBuf::from(home).join(".config/email-extract/redact.toml"))
+}
+
+impl Redactor {
+    /// Load and compile patterns from a redact.toml file.
+    pub fn load(path: &Path, mode: Mode) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read redaction config: {}", path.display()))?;
+        Self::from_toml(&text, mode)
+            .with_context(|| format!("Invalid redaction config: {}", path.display()))
+    }
+
+    fn from_toml(text: &str, mode: Mode) -> Result<Self> {
+        let config: RedactConfig = toml::from_str(text)?;
+        if config.patterns.is_empty() {
+            bail!("No [[pattern]] entries");
+        }
+
+        let mut rules = Vec::new();
+        for (i, pattern) in config.patterns.into_iter().enumerate() {
+            let name = pattern.name.clone().unwrap_or_else(|| pattern.label.clone());
+            let source = match (&pattern.regex, &pattern.literal) {
+                (Some(re), None) => re.clone(),
+                // Literals (usually names) match regardless of case
+                (None, Some(lit)) => format!("(?i){}", regex::escape(lit)),
+                _ => bail!("Pattern {} ('{}') needs exactly one of `regex` or `literal`", i + 1, name),
+            };
+            let regex = Regex::new(&source)
+                .with_context(|| format!("Pattern {} ('{}') has an invalid regex", i + 1, name))?;
+            rules.push(Rule {
+                name,
+                label: pattern.label,
+                regex,
+            });
+        }
+        Ok(Redactor { rules, mode })
+    }
+
+    /// Redact every text field that reaches the output: headers, subject,
+    /// body, attachment names and calendar event details.
+    pub fn redact_email(&self, email: &mut EmailData, counts: &mut Counts) {
+        for field in [
+            &mut email.from,
+            &mut email.to,
+            &mut email.cc,
+            &mut email.subject,
+            &mut email.message_id,
+            &mut email.in_reply_to,
+            &mut email.body,
+        ] {
+            *field = self.redact(field, counts);
+        }
+        for (_, value) in &mut email.all_headers {
+            *value = self.redact(value, counts);
+        }
+        for attachment in &mut email.attachments {
+            attachment.filename = self.redact(&attachment.filename, counts);
+        }
+        if let Some(ref mut event) = email.event {
+            for field in [
+                &mut event.summary,
+                &mut event.location,
+                &mut event.organizer,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                *field = self.redact(field, counts);
+            }
+        }
+    }
+
+    /// Replace every match in `text`, adding to `counts`.
+    pub fn redact(&self, text: &str, counts: &mut Counts) -> String {
+        let regexes: Vec<&Regex> = self.rules.iter().map(|r| &r.regex).collect();
+        let (matches, redacted) = redact_text(text, &regexes, |rule, matched| match self.mode {
+            Mode::Label => self.rules[rule].label.clone(),
+            Mode::Hash => format!("[{}]", short_hash(matched)),
+        });
+        counts.0.resize(self.rules.len(), 0);
+        for rule in matches {
+            counts.0[rule] += 1;
+        }
+        redacted
+    }
+
+    /// Per-pattern totals for the stderr report, skipping patterns that
+    /// never matched.
+    pub fn report(&self, counts: &Counts) -> String {
+        let parts: Vec<String> = self
+            .rules
+            .iter()
+            .zip(counts.0.iter())
+            .filter(|(_, &n)| n > 0)
+            .map(|(rule, n)| format!("{}: {}", rule.name, n))
+            .collect();
+        if parts.is_empty() {
+            "Redacted: nothing matched".to_string()
+        } else {
+            format!("Redacted: {}", parts.join(", "))
+        }
+    }
+}
+
+/// Running match totals, indexed like the redactor's patterns.
+#[derive(Debug, Default)]
+pub struct Counts(Vec<usize>);
+
+/// Replace matches of `patterns` in `text` using `replace(pattern_index,
+/// matched_text)`. Where matches overlap, the earliest wins, then the
+/// longest, then the first-listed pattern, and any match overlapping an
+/// earlier replacement is dropped. Returns the pattern index of every
+/// replacement made, plus the new text.
+fn redact_text(
+    text: &str,
+    patterns: &[&Regex],
+    mut replace: impl FnMut(usize, &str) -> String,
+) -> (Vec<usize>, String) {
+    let mut candidates: Vec<(usize, usize, usize)> = patterns
+        .iter()
+        .enumerate()
+        .flat_map(|(i, re)| re.find_iter(text).map(move |m| (m.start(), m.end(), i)))
+        .filter(|(start, end, _)| end > start)
+        .collect();
+    candidates.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)).then(a.2.cmp(&b.2)));
+
+    let mut out = String::with_capacity(text.len());
+    let mut applied = Vec::new();
+    let mut pos = 0;
+    for (start, end, rule) in candidates {
+        if start < pos {
+            continue;
+        }
+        out.push_str(&text[pos..start]);
+        out.push_str(&replace(rule, &text[start..end]));
+        applied.push(rule);
+        pos = end;
+    }
+    out.push_str(&text[pos..]);
+    (applied, out)
+}
+
+/// Eight hex digits of FNV-1a over the lowercased match: stable across runs
+/// and machines, and the same for "Jane Doe" and "JANE DOE".
+fn short_hash(value: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in value.to_lowercase().bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:08x}", (hash >> 32) as u32 ^ hash as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+[[pattern]]
+name = "account"
+regex = '\b\d{8}\b'
+label = "[ACCOUNT]"
+
+[[pattern]]
+name = "ni-number"
+regex = '\b[A-CEGHJ-PR-TW-Z]{2}\s?\d{2}\s?\d{2}\s?\d{2}\s?[A-D]\b'
+label = "[NI]"
+
+[[pattern]]
+literal = "Jane Doe"
+label = "[CLIENT]"
+
+[[pattern]]
+name = "sort-code-and-account"
+regex = '\d{2}-\d{2}-\d{2} \d{8}'
+label = "[BANK]"
+"#;
+
+    fn redactor(mode: Mode) -> Redactor {
+        Redactor::from_toml(CONFIG, mode).unwrap()
+    }
+
+    #[test]
+    fn test_redaction_table() {
+        let cases: &[(&str, &str, &str)] = &[
+            ("no sensitive data", "no sensitive data", "Redacted: nothing matched"),
+            ("Account 12345678 closed", "Account [ACCOUNT] closed", "Redacted: account: 1"),
+            ("NI: AB 12 34 56 C.", "NI: [NI].", "Redacted: ni-number: 1"),
+            ("Dear JANE DOE,", "Dear [CLIENT],", "Redacted: [CLIENT]: 1"),
+            // Overlap: the longer bank match starting earlier beats the
+            // account number inside it
+            ("Pay 20-00-00 12345678 now", "Pay [BANK] now", "Redacted: sort-code-and-account: 1"),
+            // Multi-line body, several patterns
+            (
+                "Hi Jane Doe,\n\nYour account 87654321\nand NI AB123456D\nare on file.\n",
+                "Hi [CLIENT],\n\nYour account [ACCOUNT]\nand NI [NI]\nare on file.\n",
+                "Redacted: account: 1, ni-number: 1, [CLIENT]: 1",
+            ),
+        ];
+
+        for (input, expected, report) in cases {
+            let r = redactor(Mode::Label);
+            let mut counts = Counts::default();
+            assert_eq!(&r.redact(input, &mut counts), expected, "input: {input:?}");
+            assert_eq!(&r.report(&counts), report, "input: {input:?}");
+        }
+    }
+
+    #[test]
+    fn test_counts_accumulate() {
+        let r = redactor(Mode::Label);
+        let mut counts = Counts::default();
+        r.redact("11111111 and 22222222", &mut counts);
+        r.redact("Jane Doe, 33333333", &mut counts);
+        assert_eq!(r.report(&counts), "Redacted: account: 3, [CLIENT]: 1");
+    }
+
+    #[test]
+    fn test_hash_mode_is_stable_and_distinguishes_values() {
+        let r = redactor(Mode::Hash);
+        let mut counts = Counts::default();
+        let out = r.redact("12345678, 87654321, 12345678, jane doe, Jane Doe", &mut counts);
+        let parts: Vec<&str> = out.split(", ").collect();
+        assert_eq!(parts.len(), 5);
+        assert_ne!(parts[0], parts[1]);
+        assert_eq!(parts[0], parts[2]);
+        assert_eq!(parts[3], parts[4]);
+        assert!(parts[0].starts_with('[') && parts[0].len() == 10);
+        assert!(!out.contains("12345678"));
+        assert_eq!(short_hash("12345678"), short_hash("12345678"));
+    }
+
+    #[test]
+    fn test_invalid_regex_names_pattern() {
+        let config = "[[pattern]]\nname = \"broken\"\nregex = '(unclosed'\nlabel = \"[X]\"\n";
+        let err = format!("{:#}", Redactor::from_toml(config, Mode::Label).err().unwrap());
+        assert!(err.contains("Pattern 1 ('broken') has an invalid regex"), "{err}");
+    }
+
+    #[test]
+    fn test_pattern_needs_one_source() {
+        let config = "[[pattern]]\nlabel = \"[X]\"\n";
+        let err = Redactor::from_toml(config, Mode::Label).err().unwrap().to_string();
+        assert!(err.contains("needs exactly one of"));
+
+        let both = "[[pattern]]\nregex = 'a'\nliteral = \"a\"\nlabel = \"[X]\"\n";
+        assert!(Redactor::from_toml(both, Mode::Label).is_err());
+    }
+}
@@ -0,0 +1,46 @@
+use regex::Regex;
+
+use crate::extract::EmailData;
+
+/// Whether `--search` matches an email's subject, sender or body
+pub fn matches(email: &EmailData, pattern: &Regex) -> bool {
+    [&email.subject, &email.from, &email.body]
+        .iter()
+        .any(|field| pattern.is_match(field))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn email(from: &str, subject: &str, body: &str) -> EmailData {
+        EmailData {
+            from: from.into(),
+            to: "will@example.com".into(),
+            ..EmailData::test(subject, body)
+        }
+    }
+
+    #[test]
+    fn matches_subject_sender_or_body() {
+        let pattern = Regex::new(r"(?i)invoice").unwrap();
+        assert!(matches(&email("jo@example.com", "Invoice 42", "Hi"), &pattern));
+        assert!(matches(&email("invoices@supplier.com", "March", "Hi"), &pattern));
+        assert!(matches(&email("jo@example.com", "March", "The invoice is attached"), &pattern));
+        assert!(!matches(&email("jo@example.com", "March", "Hi"), &pattern));
+    }
+
+    #[test]
+    fn ignores_other_headers() {
+        // The recipient alone doesn't make a match
+        let pattern = Regex::new("will@example").unwrap();
+        assert!(!matches(&email("jo@example.com", "Hello", "Hi"), &pattern));
+    }
+
+    #[test]
+    fn pattern_is_a_regex() {
+        let pattern = Regex::new(r"\bDNA\b|session \d+").unwrap();
+        assert!(matches(&email("a@b.c", "Re: session 12", ""), &pattern));
+        assert!(!matches(&email("a@b.c", "DNAs", "sessions"), &pattern));
+    }
+}
@@ -0,0 +1,159 @@
+//! Collection statistics for `--count`: message count, size, date range and
+//! top senders, built from headers only.
+
+use chrono::{DateTime, FixedOffset};
+use std::collections::HashMap;
+
+/// Number of senders listed in the summary.
+pub const TOP_SENDERS: usize = 10;
+
+#[derive(Default)]
+pub struct CountSummary {
+    pub messages: usize,
+    pub errors: usize,
+    pub total_bytes: u64,
+    pub undated: usize,
+    earliest: Option<DateTime<FixedOffset>>,
+    latest: Option<DateTime<FixedOffset>>,
+    senders: HashMap<String, usize>,
+}
+
+impl CountSummary {
+    /// Record one message. `date_parsed` is the ISO form produced by
+    /// `extract::parse_header_info`.
+    pub fn add(&mut self, from: &str, date_parsed: Option<&str>, size: u64) {
+        self.messages += 1;
+        self.total_bytes += size;
+
+        match date_parsed.and_then(|d| DateTime::parse_from_rfc3339(d).ok()) {
+            Some(date) => {
+                if self.earliest.is_none_or(|e| date < e) {
+                    self.earliest = Some(date);
+                }
+                if self.latest.is_none_or(|l| date > l) {
+                    self.latest = Some(date);
+                }
+            }
+            None => self.undated += 1,
+        }
+
+        *self.senders.entry(sender_address(from)).or_insert(0) += 1;
+    }
+
+    /// The `n` most frequent senders, ties broken alphabetically
+    pub fn top_senders(&self, n: usize) -> Vec<(&str, usize)> {
+        let mut senders: Vec<(&str, usize)> = self
+            .senders
+            .iter()
+            .map(|(sender, count)| (sender.as_str(), *count))
+            .collect();
+        senders.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        senders.truncate(n);
+        senders
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        out.push_str(&format!("Messages:   {}\n", self.messages));
+        out.push_str(&format!("Total size: {}\n", format_size(self.total_bytes)));
+
+        match (self.earliest, self.latest) {
+            (Some(earliest), Some(latest)) => {
+                out.push_str(&format!("Earliest:   {}\n", earliest.format("%Y-%m-%d %H:%M %:z")));
+                out.push_str(&format!("Latest:     {}\n", latest.format("%Y-%m-%d %H:%M %:z")));
+            }
+            _ => out.push_str("Date range: (no parseable dates)\n"),
+        }
+        if self.undated > 0 {
+            out.push_str(&format!("Undated:    {}\n", self.undated));
+        }
+        if self.errors > 0 {
+            out.push_str(&format!("Unreadable: {}\n", self.errors));
+        }
+
+        let top = self.top_senders(TOP_SENDERS);
+        if !top.is_empty() {
+            out.push_str(&format!("\nTop senders ({} distinct):\n", self.senders.len()));
+            let width = top.iter().map(|(_, count)| count.to_string().len()).max().unwrap_or(1);
+            for (sender, count) in top {
+                out.push_str(&format!("  {:>width$}  {}\n", count, sender, width = width));
+            }
+        }
+        out
+    }
+}
+
+/// Lowercased address from a From header (`Name <a@b.c>` → `a@b.c`), so one
+/// sender with varying display names counts once.
+fn sender_address(from: &str) -> String {
+    let from = from.trim();
+    if from.is_empty() {
+        return "(unknown)".to_string();
+    }
+    let address = match (from.rfind('<'), from.rfind('>')) {
+        (Some(start), Some(end)) if start < end => &from[start + 1..end],
+        _ => from,
+    };
+    address.trim().to_lowercase()
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sender_address_normalised() {
+        assert_eq!(sender_address("Alice Smith <Alice@Example.com>"), "alice@example.com");
+        assert_eq!(sender_address("bob@example.com"), "bob@example.com");
+        assert_eq!(sender_address("\"Smith, A\" <a@x.org>"), "a@x.org");
+        assert_eq!(sender_address(""), "(unknown)");
+    }
+
+    #[test]
+    fn test_top_senders_by_volume() {
+        let mut summary = CountSummary::default();
+        for from in ["A <a@x.org>", "b@x.org", "Alice <A@x.org>", "c@x.org", "b@x.org", "a@x.org"] {
+            summary.add(from, None, 100);
+        }
+        assert_eq!(summary.top_senders(2), vec![("a@x.org", 3), ("b@x.org", 2)]);
+        assert_eq!(summary.top_senders(10).len(), 3);
+    }
+
+    #[test]
+    fn test_date_range_and_totals() {
+        let mut summary = CountSummary::default();
+        summary.add("a@x.org", Some("2024-03-01T09:00:00+00:00"), 2048);
+        summary.add("a@x.org", Some("2023-12-31T23:30:00-05:00"), 1024);
+        summary.add("a@x.org", Some("2024-01-15T12:00:00+01:00"), 1024);
+        summary.add("a@x.org", None, 0);
+
+        let report = summary.render();
+        assert!(report.contains("Messages:   4\n"));
+        assert!(report.contains("Total size: 4.0 KB\n"));
+        assert!(report.contains("Earliest:   2023-12-31 23:30 -05:00\n"));
+        assert!(report.contains("Latest:     2024-03-01 09:00 +00:00\n"));
+        assert!(report.contains("Undated:    1\n"));
+        assert!(report.contains("  4  a@x.org\n"));
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MB");
+    }
+}
@@ -0,0 +1,94 @@
+use eframe::egui::{self, Pos2};
+
+use crate::EdgeData;
+
+/// The selected node's edges split by direction, as indices into the
+/// current graph's edge list. Rebuilt only when the selection or the graph
+/// changes.
+pub struct IncidentEdges {
+    pub node: usize,
+    pub incoming: Vec<usize>, // Other notes linking to the selected one
+    pub outgoing: Vec<usize>, // Links out of the selected note
+}
+
+impl IncidentEdges {
+    pub fn new(edges: &[EdgeData], node: usize) -> Self {
+        let mut incoming = Vec::new();
+        let mut outgoing = Vec::new();
+        for (i, edge) in edges.iter().enumerate() {
+            if edge.from == node {
+                outgoing.push(i);
+            } else if edge.to == node {
+                incoming.push(i);
+            }
+        }
+        Self { node, incoming, outgoing }
+    }
+
+    pub fn contains(&self, edge: usize) -> bool {
+        self.incoming.binary_search(&edge).is_ok() || self.outgoing.binary_search(&edge).is_ok()
+    }
+}
+
+/// Triangle for an arrowhead `size` long on the segment `from` → `to`, its
+/// tip touching the circle of radius `target_radius` around `to`. `None`
+/// when the nodes are too close together for the arrow to fit.
+pub fn arrowhead(from: Pos2, to: Pos2, target_radius: f32, size: f32) -> Option<[Pos2; 3]> {
+    let delta = to - from;
+    let length = delta.length();
+    if length <= target_radius + size {
+        return None;
+    }
+    let dir = delta / length;
+    let tip = to - dir * target_radius;
+    let base = tip - dir * size;
+    let half_width = egui::vec2(-dir.y, dir.x) * (size * 0.5);
+    Some([tip, base + half_width, base - half_width])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eframe::egui::pos2;
+
+    fn close(a: Pos2, b: Pos2) -> bool {
+        (a - b).length() < 1e-4
+    }
+
+    #[test]
+    fn arrowhead_tip_on_target_circle() {
+        let [tip, left, right] = arrowhead(pos2(0.0, 0.0), pos2(100.0, 0.0), 10.0, 8.0).unwrap();
+        assert!(close(tip, pos2(90.0, 0.0)));
+        assert!(close(left, pos2(82.0, 4.0)));
+        assert!(close(right, pos2(82.0, -4.0)));
+    }
+
+    #[test]
+    fn arrowhead_follows_direction() {
+        // Pointing up-left at 45°: tip sits back along the line from the target
+        let [tip, left, right] = arrowhead(pos2(10.0, 10.0), pos2(0.0, 0.0), 2.0_f32.sqrt(), 4.0).unwrap();
+        assert!(close(tip, pos2(1.0, 1.0)));
+        let base = pos2((left.x + right.x) / 2.0, (left.y + right.y) / 2.0);
+        assert!(((base - tip).length() - 4.0).abs() < 1e-4);
+        assert!(((left - right).length() - 4.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn no_arrowhead_when_nodes_overlap() {
+        assert!(arrowhead(pos2(0.0, 0.0), pos2(12.0, 0.0), 10.0, 8.0).is_none());
+        assert!(arrowhead(pos2(5.0, 5.0), pos2(5.0, 5.0), 0.0, 0.0).is_none());
+    }
+
+    #[test]
+    fn incident_edges_by_direction() {
+        let edges: Vec<EdgeData> = [(0, 1), (2, 0), (1, 2), (0, 2), (1, 0)]
+            .iter()
+            .map(|&(from, to)| EdgeData { from, to })
+            .collect();
+        let incident = IncidentEdges::new(&edges, 0);
+        assert_eq!(incident.outgoing, vec![0, 3]);
+        assert_eq!(incident.incoming, vec![1, 4]);
+        assert!(incident.contains(4));
+        assert!(!incident.contains(2));
+    }
+}
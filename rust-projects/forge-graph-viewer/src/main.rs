@@ -1,30 +1,52 @@
 use eframe::egui;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 use regex::Regex;
 use anyhow::{Context, Result};
 
+mod direction;
+mod physics;
+mod preview;
+mod recent;
+mod sizing;
+mod tags;
+mod transition;
+
+use direction::IncidentEdges;
+use physics::Simulation;
+use preview::PreviewCache;
+use recent::RecentVaults;
+use sizing::{Degree, SizeMetric};
+use tags::TagMatch;
+use transition::{Snapshot, Transition};
+
 #[derive(Debug, Clone)]
 struct Note {
     name: String,
+    path: PathBuf,
     links: Vec<String>,
+    tags: Vec<String>,
 }
 
 #[derive(Clone)]
 struct GraphData {
     nodes: Vec<NodeData>,
     edges: Vec<EdgeData>,
-    node_map: HashMap<String, usize>,
+    node_map: HashMap<String, usize>, // Name lookup, only populated for the full graph
 }
 
 #[derive(Clone)]
 struct NodeData {
     name: String,
+    path: PathBuf,
     x: f32,
     y: f32,
     is_orphan: bool,
+    component: usize, // Connected component id in the full graph (largest = 0)
+    tags: Vec<String>, // Sorted, lowercase, without '#'
+    degree: Degree, // Links in and out in the full graph
 }
 
 #[derive(Clone)]
@@ -42,9 +64,26 @@ struct ForgeGraphViewer {
     drag_start: egui::Pos2,
     selected_node: Option<usize>,
     filter_orphans: bool,
-    velocities: Vec<(f32, f32)>,
-    simulation_running: bool,
+    simulation: Simulation,
+    reseed_layout: bool, // "Re-run layout" starts again from a circle rather than the current positions
     ego_mode: EgoMode,
+    component_sizes: Vec<usize>, // Indexed by component id, largest first
+    color_by_component: bool,
+    size_metric: SizeMetric,
+    selection: HashSet<usize>, // Shift-clicked nodes, as full-graph indices
+    isolate_with_neighbors: bool,
+    previews: PreviewCache,
+    hidden_target: Option<usize>, // Link clicked in the panel for a node outside the current filter
+    tag_counts: Vec<(String, usize)>, // Every tag in the vault with its note count, by name
+    tag_filter: BTreeSet<String>,
+    tag_match: TagMatch,
+    hide_unmatched: bool, // Hide nodes failing the tag filter instead of dimming them
+    incident_edges: Option<IncidentEdges>, // Selected node's edges, cached per selection
+    before_change: Option<Snapshot>, // View before the graph was re-filtered, until the next refit animates from it
+    transition: Option<Transition>,
+    vault_path: Option<PathBuf>, // None until a vault is opened or dropped
+    recent: RecentVaults,
+    open_error: Option<String>, // Last vault that failed to open, shown in the overlay
 }
 
 #[derive(Clone, Copy, PartialEq)]
@@ -52,18 +91,47 @@ enum EgoMode {
     Full,      // Show entire graph
     OneHop,    // Show selected node + immediate neighbors
     TwoHop,    // Show selected node + neighbors + neighbors of neighbors
+    Selection, // Show the shift-click selection (+ optionally its neighbors)
 }
 
 impl ForgeGraphViewer {
-    fn new(vault_path: &Path, filter_orphans: bool) -> Result<Self> {
-        println!("📖 Parsing vault at {:?}...", vault_path);
-        let graph = parse_vault(vault_path, filter_orphans)?;
-        println!("✅ Loaded {} nodes, {} edges", graph.nodes.len(), graph.edges.len());
+    fn new(vault_path: &Path, filter_orphans: bool, recent: RecentVaults) -> Result<Self> {
+        let graph = load_graph(vault_path, filter_orphans)?;
+        Ok(Self::with_vault(vault_path, graph, filter_orphans, recent))
+    }
+
+    /// A viewer showing `graph`, recording its vault as the most recent
+    fn with_vault(vault_path: &Path, graph: GraphData, filter_orphans: bool, mut recent: RecentVaults) -> Self {
+        recent.remember(vault_path);
+        let mut viewer = Self::with_graph(graph, filter_orphans, recent);
+        viewer.vault_path = Some(vault_path.to_path_buf());
+        viewer
+    }
+
+    /// A viewer with nothing loaded, waiting for a vault to be dropped or
+    /// picked from the recent list
+    fn empty(filter_orphans: bool, recent: RecentVaults) -> Self {
+        let graph = GraphData {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            node_map: HashMap::new(),
+        };
+        Self::with_graph(graph, filter_orphans, recent)
+    }
 
-        let velocities = vec![(0.0, 0.0); graph.nodes.len()];
+    fn with_graph(mut graph: GraphData, filter_orphans: bool, recent: RecentVaults) -> Self {
+        let component_sizes = assign_components(&mut graph);
+        println!("🧩 {} connected components", component_sizes.len());
+
+        sizing::assign_degrees(&mut graph);
+
+        let tag_counts = count_tags(&graph);
+        println!("🏷️ {} tags", tag_counts.len());
+
+        let simulation = Simulation::new(graph.nodes.len()); // Stopped: the full graph is too dense to run unasked
         let full_graph = graph.clone();
 
-        Ok(Self {
+        Self {
             graph: full_graph.clone(),
             full_graph,
             camera_pos: egui::Vec2::ZERO,
@@ -72,89 +140,121 @@ impl ForgeGraphViewer {
             drag_start: egui::Pos2::ZERO,
             selected_node: None,
             filter_orphans,
-            velocities,
-            simulation_running: false, // Disable physics for now - too dense!
+            simulation,
+            reseed_layout: false,
             ego_mode: EgoMode::Full,
-        })
+            component_sizes,
+            color_by_component: true,
+            size_metric: SizeMetric::Uniform,
+            selection: HashSet::new(),
+            isolate_with_neighbors: true,
+            previews: PreviewCache::default(),
+            hidden_target: None,
+            tag_counts,
+            tag_filter: BTreeSet::new(),
+            tag_match: TagMatch::Any,
+            hide_unmatched: false,
+            incident_edges: None,
+            before_change: None,
+            transition: None,
+            vault_path: None,
+            recent,
+            open_error: None,
+        }
     }
 
-    fn apply_forces(&mut self) {
-        if !self.simulation_running {
+    /// Replace the graph with another vault's, keeping the display options.
+    /// On failure the current vault stays open and the error is shown.
+    fn open_vault(&mut self, path: &Path) {
+        let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        if !path.is_dir() {
+            self.open_error = Some(format!("Not a folder: {}", path.display()));
             return;
         }
-
-        let k = 30.0; // Ideal spring length (much shorter for dense graphs)
-        let damping = 0.9; // Higher damping for faster settling
-        let repulsion_strength = 5000.0; // Much weaker repulsion
-        let attraction_strength = 0.05; // Stronger attraction to pull clusters together
-
-        let mut forces = vec![(0.0, 0.0); self.graph.nodes.len()];
-
-        // Repulsive forces between all nodes (Barnes-Hut would be better but this works for now)
-        for i in 0..self.graph.nodes.len() {
-            for j in (i + 1)..self.graph.nodes.len() {
-                let node1 = &self.graph.nodes[i];
-                let node2 = &self.graph.nodes[j];
-
-                let dx = node2.x - node1.x;
-                let dy = node2.y - node1.y;
-                let distance = (dx * dx + dy * dy).sqrt().max(1.0);
-
-                // Coulomb's law (repulsion)
-                let force = repulsion_strength / (distance * distance);
-                let fx = (dx / distance) * force;
-                let fy = (dy / distance) * force;
-
-                forces[i].0 -= fx;
-                forces[i].1 -= fy;
-                forces[j].0 += fx;
-                forces[j].1 += fy;
+        match load_graph(&path, self.filter_orphans) {
+            Ok(graph) => {
+                let recent = std::mem::take(&mut self.recent);
+                let mut viewer = Self::with_vault(&path, graph, self.filter_orphans, recent);
+                viewer.keep_display_options(self);
+                *self = viewer;
             }
+            Err(e) => self.open_error = Some(format!("Could not open {}: {}", path.display(), e)),
         }
+    }
 
-        // Attractive forces along edges (Hooke's law)
-        for edge in &self.graph.edges {
-            let from = &self.graph.nodes[edge.from];
-            let to = &self.graph.nodes[edge.to];
-
-            let dx = to.x - from.x;
-            let dy = to.y - from.y;
-            let distance = (dx * dx + dy * dy).sqrt().max(1.0);
+    /// Carry colouring, sizing, tag filter and physics settings over from
+    /// the viewer being replaced. Filter tags the new vault lacks are dropped.
+    fn keep_display_options(&mut self, old: &Self) {
+        self.color_by_component = old.color_by_component;
+        self.size_metric = old.size_metric;
+        self.isolate_with_neighbors = old.isolate_with_neighbors;
+        self.tag_filter = old
+            .tag_filter
+            .iter()
+            .filter(|tag| self.tag_counts.iter().any(|(t, _)| t == *tag))
+            .cloned()
+            .collect();
+        self.tag_match = old.tag_match;
+        self.hide_unmatched = old.hide_unmatched;
+        self.simulation.paused = old.simulation.paused;
+    }
 
-            // Spring force
-            let force = (distance - k) * attraction_strength;
-            let fx = (dx / distance) * force;
-            let fy = (dy / distance) * force;
+    /// Node positions as plain pairs, for the physics
+    fn positions(&self) -> Vec<(f32, f32)> {
+        self.graph.nodes.iter().map(|n| (n.x, n.y)).collect()
+    }
 
-            forces[edge.from].0 += fx;
-            forces[edge.from].1 += fy;
-            forces[edge.to].0 -= fx;
-            forces[edge.to].1 -= fy;
+    fn set_positions(&mut self, positions: &[(f32, f32)]) {
+        for (node, &(x, y)) in self.graph.nodes.iter_mut().zip(positions) {
+            node.x = x;
+            node.y = y;
         }
+    }
 
-        // Update positions based on forces
-        let mut total_kinetic_energy = 0.0;
-        for i in 0..self.graph.nodes.len() {
-            let (fx, fy) = forces[i];
-
-            self.velocities[i].0 = (self.velocities[i].0 + fx) * damping;
-            self.velocities[i].1 = (self.velocities[i].1 + fy) * damping;
+    fn edge_pairs(&self) -> Vec<(usize, usize)> {
+        self.graph.edges.iter().map(|e| (e.from, e.to)).collect()
+    }
 
-            self.graph.nodes[i].x += self.velocities[i].0;
-            self.graph.nodes[i].y += self.velocities[i].1;
+    /// Advance the layout by `dt` seconds of wall time
+    fn apply_forces(&mut self, dt: f32) {
+        if !self.simulation.active() || self.transition.is_some() {
+            return;
+        }
 
-            total_kinetic_energy += self.velocities[i].0 * self.velocities[i].0
-                                  + self.velocities[i].1 * self.velocities[i].1;
+        let mut positions = self.positions();
+        let edges = self.edge_pairs();
+        if self.simulation.advance(dt, &mut positions, &edges) {
+            self.set_positions(&positions);
         }
 
-        // Stop simulation when energy is low (graph has stabilized)
-        if total_kinetic_energy < 1.0 {
-            self.simulation_running = false;
+        if !self.simulation.running {
             println!("⚡ Simulation stabilized!");
         }
     }
 
+    /// Start the layout again, from the current positions or a fresh circle
+    fn rerun_layout(&mut self) {
+        if self.reseed_layout {
+            let seeded = physics::seed_circle(self.graph.nodes.len());
+            self.set_positions(&seeded);
+        }
+        self.simulation.start();
+    }
+
+    /// A short, synchronous relaxation of a freshly extracted subgraph, so
+    /// the refit transition animates straight to the tidied layout
+    fn settle_subgraph(&mut self) {
+        let mut positions = self.positions();
+        let edges = self.edge_pairs();
+        self.simulation.settle(&mut positions, &edges, physics::LOCAL_TEMPERATURE, physics::LOCAL_STEPS);
+        self.set_positions(&positions);
+    }
+
     fn recenter_view(&mut self, viewport_size: egui::Vec2) {
+        if self.graph.nodes.is_empty() {
+            return;
+        }
+
         // Calculate bounding box of all nodes
         let mut min_x = f32::INFINITY;
         let mut max_x = f32::NEG_INFINITY;
@@ -177,10 +277,61 @@ impl ForgeGraphViewer {
         let height = max_y - min_y;
         let zoom_x = viewport_size.x / width * 0.8; // 80% to add padding
         let zoom_y = viewport_size.y / height * 0.8;
-        self.zoom = zoom_x.min(zoom_y).max(0.1).min(10.0);
+        let zoom = zoom_x.min(zoom_y).max(0.1).min(10.0);
 
         // Center camera on graph center
-        self.camera_pos = egui::vec2(-center_x, -center_y);
+        self.animate_to(egui::vec2(-center_x, -center_y), zoom);
+    }
+
+    /// Remember the view before the graph is replaced, so the next
+    /// `animate_to` can fly nodes from where they were shown
+    fn save_view(&mut self) {
+        if self.before_change.is_none() {
+            self.before_change = Some(Snapshot {
+                positions: self.graph.nodes.iter().map(|n| (n.name.clone(), egui::pos2(n.x, n.y))).collect(),
+                camera: self.camera_pos,
+                zoom: self.zoom,
+            });
+        }
+        self.transition = None;
+    }
+
+    /// Ease nodes and camera from the saved view (or the current one) to
+    /// their new places over `transition::DURATION`
+    fn animate_to(&mut self, camera: egui::Vec2, zoom: f32) {
+        // Mid-flight nodes are still heading for the previous targets
+        let targets: Vec<egui::Pos2> = match self.transition.take() {
+            Some(transition) => transition.target,
+            None => self.graph.nodes.iter().map(|n| egui::pos2(n.x, n.y)).collect(),
+        };
+        self.save_view();
+        let before = self.before_change.take().expect("view just saved");
+
+        let nodes = self.graph.nodes.iter().zip(targets).map(|(n, pos)| (n.name.as_str(), pos));
+        self.transition = Some(Transition::new(&before, nodes, camera, zoom));
+        self.advance_transition(0.0);
+    }
+
+    /// Move the transition on by `dt` seconds and show where it's got to
+    fn advance_transition(&mut self, dt: f32) {
+        let Some(transition) = &mut self.transition else {
+            return;
+        };
+        transition.step(dt);
+        for (node, pos) in self.graph.nodes.iter_mut().zip(transition.positions()) {
+            node.x = pos.x;
+            node.y = pos.y;
+        }
+        self.camera_pos = transition.camera();
+        self.zoom = transition.zoom();
+        if transition.settled() {
+            self.transition = None;
+        }
+    }
+
+    /// Jump to the end of any transition, e.g. when the user takes over the camera
+    fn finish_transition(&mut self) {
+        self.advance_transition(transition::DURATION);
     }
 
     fn screen_to_world(&self, screen_pos: egui::Pos2, center: egui::Pos2) -> egui::Pos2 {
@@ -199,7 +350,38 @@ impl ForgeGraphViewer {
         center + offset
     }
 
+    /// Show the full-graph node `center_node` and everything within `hops` of it
     fn extract_ego_network(&mut self, center_node: usize, hops: usize) {
+        let new_node_map = self.extract_subgraph(&[center_node], hops);
+
+        // Update selected node index to match new graph
+        self.selected_node = new_node_map.get(&center_node).copied();
+
+        println!("🎯 Ego network: {} nodes, {} edges", self.graph.nodes.len(), self.graph.edges.len());
+    }
+
+    /// Re-filter the ego network around a node of the graph on screen
+    fn refilter_around(&mut self, idx: usize, hops: usize) {
+        if let Some(full_idx) = self.full_index(idx) {
+            self.extract_ego_network(full_idx, hops);
+        }
+    }
+
+    /// Show only the shift-click selection, plus its 1-hop neighbors if enabled
+    fn isolate_selection(&mut self) {
+        let seeds: Vec<usize> = self.selection.iter().copied().collect();
+        let hops = if self.isolate_with_neighbors { 1 } else { 0 };
+        self.extract_subgraph(&seeds, hops);
+        self.selected_node = None;
+
+        println!("🧷 Selection: {} nodes, {} edges", self.graph.nodes.len(), self.graph.edges.len());
+    }
+
+    /// Replace the current graph with the full-graph nodes within `hops` of
+    /// any of `seeds`. Returns the full-graph → new index mapping.
+    fn extract_subgraph(&mut self, seeds: &[usize], hops: usize) -> HashMap<usize, usize> {
+        self.save_view();
+
         // Build adjacency list from full graph
         let mut adj_list: HashMap<usize, Vec<usize>> = HashMap::new();
         for edge in &self.full_graph.edges {
@@ -208,9 +390,8 @@ impl ForgeGraphViewer {
         }
 
         // BFS to find nodes within N hops
-        let mut nodes_to_include = HashSet::new();
-        let mut current_frontier = vec![center_node];
-        nodes_to_include.insert(center_node);
+        let mut nodes_to_include: HashSet<usize> = seeds.iter().copied().collect();
+        let mut current_frontier = seeds.to_vec();
 
         for _ in 0..hops {
             let mut next_frontier = Vec::new();
@@ -249,9 +430,6 @@ impl ForgeGraphViewer {
             }
         }
 
-        // Update selected node index to match new graph
-        self.selected_node = new_node_map.get(&center_node).copied();
-
         // Create filtered graph
         self.graph = GraphData {
             nodes: new_nodes,
@@ -259,16 +437,129 @@ impl ForgeGraphViewer {
             node_map: HashMap::new(), // Not needed for rendering
         };
 
-        // Reset velocities
-        self.velocities = vec![(0.0, 0.0); self.graph.nodes.len()];
+        self.simulation.reset(self.graph.nodes.len());
+        self.settle_subgraph();
+        self.incident_edges = None;
 
-        println!("🎯 Ego network: {} nodes, {} edges", self.graph.nodes.len(), self.graph.edges.len());
+        new_node_map
+    }
+
+    /// Index in `full_graph` of a node in the (possibly filtered) current graph
+    fn full_index(&self, idx: usize) -> Option<usize> {
+        self.full_graph.node_map.get(&self.graph.nodes[idx].name).copied()
+    }
+
+    /// Backlinks (notes linking here) and forward links of a full-graph node,
+    /// each sorted by name
+    fn neighbors(&self, full_idx: usize) -> (Vec<usize>, Vec<usize>) {
+        let mut backlinks = Vec::new();
+        let mut forward = Vec::new();
+        for edge in &self.full_graph.edges {
+            if edge.to == full_idx {
+                backlinks.push(edge.from);
+            }
+            if edge.from == full_idx {
+                forward.push(edge.to);
+            }
+        }
+        for list in [&mut backlinks, &mut forward] {
+            list.sort_by(|a, b| self.full_graph.nodes[*a].name.cmp(&self.full_graph.nodes[*b].name));
+            list.dedup();
+        }
+        (backlinks, forward)
+    }
+
+    /// Select a full-graph node and center the camera on it, re-filtering
+    /// the ego network around it when one is active. A node hidden by an
+    /// isolated selection is left as `hidden_target` for the panel to offer
+    /// a filter reset.
+    fn focus_node(&mut self, full_idx: usize, viewport_size: egui::Vec2) {
+        self.hidden_target = None;
+        match self.ego_mode {
+            EgoMode::Full | EgoMode::Selection => {
+                let name = &self.full_graph.nodes[full_idx].name;
+                let Some(idx) = self.graph.nodes.iter().position(|n| &n.name == name) else {
+                    self.hidden_target = Some(full_idx);
+                    return;
+                };
+                let node = &self.graph.nodes[idx];
+                let camera = egui::vec2(-node.x, -node.y);
+                self.selected_node = Some(idx);
+                self.animate_to(camera, self.zoom);
+            }
+            EgoMode::OneHop | EgoMode::TwoHop => {
+                let hops = if self.ego_mode == EgoMode::OneHop { 1 } else { 2 };
+                self.extract_ego_network(full_idx, hops);
+                self.recenter_view(viewport_size);
+            }
+        }
+    }
+
+    /// Tag dropdown (multi-select), AND/OR toggle and dim/hide choice
+    fn tag_filter_ui(&mut self, ui: &mut egui::Ui, tag_matched: &[bool]) {
+        if self.tag_counts.is_empty() {
+            ui.label("🏷️ No tags in vault");
+            return;
+        }
+
+        let summary = if self.tag_filter.is_empty() {
+            "All notes".to_string()
+        } else {
+            self.tag_filter.iter().map(|t| format!("#{}", t)).collect::<Vec<_>>().join(" ")
+        };
+        ui.horizontal(|ui| {
+            ui.label("🏷️ Tags:");
+            egui::ComboBox::from_id_salt("tag_filter")
+                .selected_text(summary)
+                .width(160.0)
+                .show_ui(ui, |ui| {
+                    egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                        for (tag, count) in &self.tag_counts {
+                            let mut on = self.tag_filter.contains(tag);
+                            if ui.checkbox(&mut on, format!("#{} ({})", tag, count)).changed() {
+                                if on {
+                                    self.tag_filter.insert(tag.clone());
+                                } else {
+                                    self.tag_filter.remove(tag);
+                                }
+                            }
+                        }
+                    });
+                });
+        });
+
+        if !self.tag_filter.is_empty() {
+            ui.horizontal(|ui| {
+                if ui.selectable_label(self.tag_match == TagMatch::Any, "Any (OR)").clicked() {
+                    self.tag_match = TagMatch::Any;
+                }
+                if ui.selectable_label(self.tag_match == TagMatch::All, "All (AND)").clicked() {
+                    self.tag_match = TagMatch::All;
+                }
+                if ui.button("✖ Clear").clicked() {
+                    self.tag_filter.clear();
+                }
+            });
+            ui.checkbox(&mut self.hide_unmatched, "Hide non-matching (dim otherwise)");
+            let shown = tag_matched.iter().filter(|&&m| m).count();
+            ui.label(format!("🏷️ {} of {} nodes match", shown, tag_matched.len()));
+        }
+    }
+
+    /// Rebuild the selected node's in/out edge lists if the selection (or
+    /// the graph, which clears the cache) changed since the last frame
+    fn refresh_incident_edges(&mut self) {
+        if self.incident_edges.as_ref().map(|e| e.node) != self.selected_node {
+            self.incident_edges = self.selected_node.map(|idx| IncidentEdges::new(&self.graph.edges, idx));
+        }
     }
 
     fn reset_to_full_graph(&mut self) {
+        self.save_view();
         self.graph = self.full_graph.clone();
-        self.velocities = vec![(0.0, 0.0); self.graph.nodes.len()];
+        self.simulation.reset(self.graph.nodes.len());
         self.ego_mode = EgoMode::Full;
+        self.incident_edges = None;
         println!("🌐 Restored full graph: {} nodes, {} edges", self.graph.nodes.len(), self.graph.edges.len());
     }
 }
@@ -276,7 +567,84 @@ impl ForgeGraphViewer {
 impl eframe::App for ForgeGraphViewer {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         // Apply physics simulation
-        self.apply_forces();
+        let dt = ctx.input(|i| i.stable_dt).min(0.1);
+        self.apply_forces(dt);
+        self.advance_transition(dt);
+        self.refresh_incident_edges();
+
+        // A folder dropped onto the window replaces the open vault
+        let dropped = ctx.input(|i| i.raw.dropped_files.iter().find_map(|f| f.path.clone()));
+        if let Some(path) = dropped {
+            self.open_vault(&path);
+        }
+
+        let mut focus_target = None;
+        let mut open_target = None;
+        let mut reveal_hidden = false;
+
+        if let Some(full_idx) = self.selected_node.and_then(|idx| self.full_index(idx)) {
+            egui::SidePanel::right("note_preview")
+                .default_width(340.0)
+                .resizable(true)
+                .show(ctx, |ui| {
+                    ui.heading(&self.full_graph.nodes[full_idx].name);
+
+                    if let Some(hidden) = self.hidden_target {
+                        ui.colored_label(
+                            egui::Color32::from_rgb(255, 165, 0),
+                            format!("“{}” is hidden by the current filter", self.full_graph.nodes[hidden].name),
+                        );
+                        ui.horizontal(|ui| {
+                            if ui.button("🌐 Reset filter").clicked() {
+                                reveal_hidden = true;
+                            }
+                            if ui.button("Cancel").clicked() {
+                                self.hidden_target = None;
+                            }
+                        });
+                    }
+                    ui.separator();
+
+                    let (backlinks, forward) = self.neighbors(full_idx);
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        let path = self.full_graph.nodes[full_idx].path.clone();
+                        match self.previews.get(full_idx, &path, ctx) {
+                            None => {
+                                ui.spinner();
+                            }
+                            Some(Err(e)) => {
+                                ui.colored_label(egui::Color32::from_rgb(255, 107, 107), e);
+                            }
+                            Some(Ok(preview)) => {
+                                if !preview.frontmatter.is_empty() {
+                                    egui::Grid::new("frontmatter").num_columns(2).show(ui, |ui| {
+                                        for (key, value) in &preview.frontmatter {
+                                            ui.weak(key);
+                                            ui.label(value);
+                                            ui.end_row();
+                                        }
+                                    });
+                                    ui.separator();
+                                }
+                                ui.label(egui::RichText::new(&preview.body).monospace());
+                                if preview.truncated {
+                                    ui.weak("…");
+                                }
+                            }
+                        }
+
+                        for (heading, list) in [("➡️ Links", &forward), ("⬅️ Backlinks", &backlinks)] {
+                            ui.separator();
+                            ui.label(format!("{}: {}", heading, list.len()));
+                            for &neighbor in list {
+                                if ui.link(&self.full_graph.nodes[neighbor].name).clicked() {
+                                    focus_target = Some(neighbor);
+                                }
+                            }
+                        }
+                    });
+                });
+        }
 
         egui::CentralPanel::default().show(ctx, |ui| {
             let (response, painter) = ui.allocate_painter(
@@ -290,12 +658,14 @@ impl eframe::App for ForgeGraphViewer {
             // Handle mouse wheel zoom
             let scroll_delta = ui.input(|i| i.smooth_scroll_delta.y);
             if scroll_delta.abs() > 0.1 {
+                self.finish_transition();
                 let zoom_factor = 1.0 + scroll_delta * 0.001;
                 self.zoom = (self.zoom * zoom_factor).clamp(0.1, 10.0);
             }
 
             // Handle dragging
             if response.dragged() {
+                self.finish_transition();
                 if !self.dragging {
                     self.dragging = true;
                     self.drag_start = response.interact_pointer_pos().unwrap_or(center);
@@ -309,9 +679,33 @@ impl eframe::App for ForgeGraphViewer {
                 self.dragging = false;
             }
 
+            // Nodes passing the tag filter; the rest are dimmed, or hidden
+            // along with their edges
+            let tag_matched: Vec<bool> = self.graph.nodes.iter()
+                .map(|node| tags::matches(&node.tags, &self.tag_filter, self.tag_match))
+                .collect();
+            let hidden = |idx: usize| self.hide_unmatched && !tag_matched[idx];
+
+            let incident = self.incident_edges.as_ref();
+            let base_radius = 5.0 * self.zoom.sqrt().max(3.0); // Ensure minimum size
+
+            // Scaled against the whole vault, so a hub stays the same size
+            // in an ego network
+            let max_degree = self.full_graph.nodes.iter()
+                .map(|n| self.size_metric.degree(n.degree))
+                .max()
+                .unwrap_or(0);
+            let scales: Vec<f32> = self.graph.nodes.iter()
+                .map(|n| sizing::scale(self.size_metric.degree(n.degree), max_degree))
+                .collect();
+            let radii: Vec<f32> = scales.iter().map(|s| base_radius * s).collect();
+
             // Draw edges first (so they appear behind nodes)
             let edge_color = egui::Color32::from_rgba_unmultiplied(132, 132, 132, 50);
-            for edge in &self.graph.edges {
+            for (i, edge) in self.graph.edges.iter().enumerate() {
+                if hidden(edge.from) || hidden(edge.to) || incident.is_some_and(|inc| inc.contains(i)) {
+                    continue;
+                }
                 let from = &self.graph.nodes[edge.from];
                 let to = &self.graph.nodes[edge.to];
 
@@ -327,29 +721,59 @@ impl eframe::App for ForgeGraphViewer {
                 }
             }
 
+            // The selected node's own edges on top, coloured by direction
+            // with an arrowhead at the target
+            if let Some(incident) = incident {
+                for (edges, color) in [(&incident.incoming, INCOMING_COLOR), (&incident.outgoing, OUTGOING_COLOR)] {
+                    for &i in edges {
+                        let edge = &self.graph.edges[i];
+                        if hidden(edge.from) || hidden(edge.to) {
+                            continue;
+                        }
+                        let from = &self.graph.nodes[edge.from];
+                        let to = &self.graph.nodes[edge.to];
+                        let from_pos = self.world_to_screen(egui::pos2(from.x, from.y), center);
+                        let to_pos = self.world_to_screen(egui::pos2(to.x, to.y), center);
+                        if !rect.intersects(egui::Rect::from_two_pos(from_pos, to_pos)) {
+                            continue;
+                        }
+                        painter.line_segment([from_pos, to_pos], egui::Stroke::new(1.5, color));
+                        if let Some(points) = direction::arrowhead(from_pos, to_pos, radii[edge.to], ARROW_SIZE) {
+                            painter.add(egui::Shape::convex_polygon(points.to_vec(), color, egui::Stroke::NONE));
+                        }
+                    }
+                }
+            }
+
             // Draw nodes
-            let mut visible_count = 0;
             for (idx, node) in self.graph.nodes.iter().enumerate() {
+                if hidden(idx) {
+                    continue;
+                }
                 let pos = self.world_to_screen(egui::pos2(node.x, node.y), center);
 
-                // Draw ALL nodes (remove visibility culling for debugging)
-                let node_radius = 5.0 * self.zoom.sqrt().max(3.0); // Ensure minimum size
+                let in_selection = !self.selection.is_empty()
+                    && self.full_index(idx).is_some_and(|full| self.selection.contains(&full));
 
                 let color = if Some(idx) == self.selected_node {
                     egui::Color32::YELLOW
+                } else if in_selection {
+                    egui::Color32::from_rgb(255, 165, 0)
                 } else if node.is_orphan {
                     egui::Color32::from_rgb(255, 107, 107)
+                } else if self.color_by_component {
+                    component_color(node.component)
                 } else {
                     egui::Color32::from_rgb(78, 205, 196)
                 };
+                let color = if tag_matched[idx] { color } else { color.gamma_multiply(0.15) };
 
-                painter.circle_filled(pos, node_radius, color);
-                visible_count += 1;
+                painter.circle_filled(pos, radii[idx], color);
 
                 // Draw label for selected or hovered node
-                if Some(idx) == self.selected_node {
+                if Some(idx) == self.selected_node || in_selection {
                     painter.text(
-                        pos + egui::vec2(node_radius + 5.0, 0.0),
+                        pos + egui::vec2(radii[idx] + 5.0, 0.0),
                         egui::Align2::LEFT_CENTER,
                         &node.name,
                         egui::FontId::proportional(12.0),
@@ -359,40 +783,35 @@ impl eframe::App for ForgeGraphViewer {
             }
 
             // Handle node selection
+            let shift_held = ui.input(|i| i.modifiers.shift);
             if response.clicked() {
                 if let Some(click_pos) = response.interact_pointer_pos() {
                     let world_pos = self.screen_to_world(click_pos, center);
-                    let click_radius = 10.0 / self.zoom;
+                    let click_radius = |idx: usize| 10.0 * scales[idx] / self.zoom;
 
                     let clicked_node = self.graph.nodes.iter().enumerate()
-                        .find(|(_, node)| {
+                        .filter(|(idx, _)| !hidden(*idx))
+                        .find(|(idx, node)| {
                             let dx = node.x - world_pos.x;
                             let dy = node.y - world_pos.y;
-                            (dx * dx + dy * dy).sqrt() < click_radius
+                            (dx * dx + dy * dy).sqrt() < click_radius(*idx)
                         })
                         .map(|(idx, _)| idx);
 
-                    if let Some(idx) = clicked_node {
+                    if shift_held {
+                        // Shift-click toggles a node in the multi-selection
+                        if let Some(full_idx) = clicked_node.and_then(|idx| self.full_index(idx))
+                            && !self.selection.remove(&full_idx) {
+                            self.selection.insert(full_idx);
+                        }
+                    } else if let Some(idx) = clicked_node {
                         self.selected_node = Some(idx);
+                        self.hidden_target = None;
 
                         // Apply ego network filter based on current mode
-                        if self.ego_mode != EgoMode::Full {
-                            let hops = match self.ego_mode {
-                                EgoMode::OneHop => 1,
-                                EgoMode::TwoHop => 2,
-                                EgoMode::Full => 0,
-                            };
-
-                            // Find original node index if we're in filtered view
-                            let original_idx = if self.ego_mode == EgoMode::Full {
-                                idx
-                            } else {
-                                // Need to map back to original graph
-                                // For now, just use the clicked node in current graph
-                                idx
-                            };
-
-                            self.extract_ego_network(original_idx, hops);
+                        if matches!(self.ego_mode, EgoMode::OneHop | EgoMode::TwoHop) {
+                            let hops = if self.ego_mode == EgoMode::OneHop { 1 } else { 2 };
+                            self.refilter_around(idx, hops);
                             self.recenter_view(rect.size());
                         }
                     } else {
@@ -409,15 +828,66 @@ impl eframe::App for ForgeGraphViewer {
             egui::Window::new("🔗 Forge Graph Viewer")
                 .default_pos(egui::pos2(10.0, 10.0))
                 .show(ctx, |ui| {
+                    match &self.vault_path {
+                        Some(path) => {
+                            let name = path.file_name().map_or_else(|| path.display().to_string(), |n| n.to_string_lossy().into_owned());
+                            ui.label(format!("📁 {}", name)).on_hover_text(path.display().to_string());
+                        }
+                        None => {
+                            ui.label("📁 No vault open");
+                        }
+                    }
+                    ui.horizontal(|ui| {
+                        ui.menu_button("🕘 Recent vaults", |ui| {
+                            if self.recent.iter().next().is_none() {
+                                ui.weak("None yet");
+                            }
+                            for vault in self.recent.iter() {
+                                let current = self.vault_path.as_deref() == Some(vault);
+                                let button = ui.add_enabled(vault.is_dir(), egui::SelectableLabel::new(current, vault.display().to_string()));
+                                if button.clicked() {
+                                    open_target = Some(vault.to_path_buf());
+                                    ui.close_menu();
+                                }
+                            }
+                        });
+                        ui.weak("or drop a folder");
+                    });
+                    if let Some(error) = &self.open_error {
+                        ui.colored_label(egui::Color32::from_rgb(255, 107, 107), error);
+                    }
+                    ui.separator();
+
                     ui.label(format!("📄 Nodes: {}", self.graph.nodes.len()));
                     ui.label(format!("🔗 Edges: {}", self.graph.edges.len()));
                     ui.label(format!("🔍 Zoom: {:.1}x", self.zoom));
 
-                    if self.simulation_running {
-                        ui.label("⚡ Organizing graph...");
+                    if self.simulation.running {
+                        if self.simulation.paused {
+                            ui.label("⏸ Layout paused");
+                        } else {
+                            ui.label("⚡ Organizing graph...");
+                        }
+                        ui.label(format!(
+                            "🌡 Temperature: {:.0}% · energy {:.0}",
+                            self.simulation.temperature * 100.0,
+                            self.simulation.energy
+                        ));
                     } else {
                         ui.label("✅ Layout stabilized");
                     }
+                    ui.horizontal(|ui| {
+                        if ui.button("🔄 Re-run layout").clicked() {
+                            self.rerun_layout();
+                        }
+                        if self.simulation.running {
+                            let label = if self.simulation.paused { "▶ Resume" } else { "⏸ Pause" };
+                            if ui.button(label).clicked() {
+                                self.simulation.paused = !self.simulation.paused;
+                            }
+                        }
+                    });
+                    ui.checkbox(&mut self.reseed_layout, "Re-seed positions");
 
                     ui.separator();
                     ui.label("🔬 Filter Mode:");
@@ -436,6 +906,51 @@ impl eframe::App for ForgeGraphViewer {
                         }
                     });
 
+                    ui.separator();
+                    ui.label(format!("🧷 Selection: {} node{}", self.selection.len(), if self.selection.len() == 1 { "" } else { "s" }));
+                    if !self.selection.is_empty() {
+                        ui.checkbox(&mut self.isolate_with_neighbors, "Include 1-hop neighbors");
+                        ui.horizontal(|ui| {
+                            if ui.button("🧷 Isolate selection").clicked() {
+                                new_mode = EgoMode::Selection;
+                                mode_changed = true;
+                            }
+                            if ui.button("✖ Clear").clicked() {
+                                self.selection.clear();
+                            }
+                        });
+                    }
+
+                    ui.separator();
+                    self.tag_filter_ui(ui, &tag_matched);
+
+                    ui.separator();
+                    ui.checkbox(&mut self.color_by_component, "🧩 Color by component");
+                    if self.color_by_component {
+                        ui.label(format!("🧩 Components: {}", self.component_sizes.len()));
+                        for (id, size) in self.component_sizes.iter().enumerate().take(LEGEND_COMPONENTS) {
+                            ui.horizontal(|ui| {
+                                ui.colored_label(component_color(id), "⏺");
+                                ui.label(format!("{} nodes", size));
+                            });
+                        }
+                        let rest = self.component_sizes.len().saturating_sub(LEGEND_COMPONENTS);
+                        if rest > 0 {
+                            ui.label(format!("… and {} smaller", rest));
+                        }
+                    }
+
+                    ui.horizontal(|ui| {
+                        ui.label("⏺ Size by:");
+                        egui::ComboBox::from_id_salt("size_metric")
+                            .selected_text(self.size_metric.label())
+                            .show_ui(ui, |ui| {
+                                for metric in SizeMetric::ALL {
+                                    ui.selectable_value(&mut self.size_metric, metric, metric.label());
+                                }
+                            });
+                    });
+
                     ui.separator();
                     if ui.button("🎯 Fit to View").clicked() {
                         reset_view = true;
@@ -445,16 +960,31 @@ impl eframe::App for ForgeGraphViewer {
                     ui.label("🖱️ Drag to pan");
                     ui.label("🎡 Scroll to zoom");
                     ui.label("🎯 Click node to filter");
+                    ui.label("⇧ Shift-click to multi-select");
 
                     if let Some(idx) = self.selected_node {
                         ui.separator();
                         ui.label(format!("Selected: {}", self.graph.nodes[idx].name));
+                        if let Some(incident) = &self.incident_edges {
+                            ui.horizontal(|ui| {
+                                ui.colored_label(INCOMING_COLOR, format!("⬅ In: {}", incident.incoming.len()));
+                                ui.colored_label(OUTGOING_COLOR, format!("➡ Out: {}", incident.outgoing.len()));
+                            });
+                        }
                     }
 
-                    if self.ego_mode != EgoMode::Full {
-                        ui.separator();
-                        ui.colored_label(egui::Color32::LIGHT_BLUE, "🔬 Ego Network Active");
-                        ui.label("Click another node to re-filter");
+                    match self.ego_mode {
+                        EgoMode::OneHop | EgoMode::TwoHop => {
+                            ui.separator();
+                            ui.colored_label(egui::Color32::LIGHT_BLUE, "🔬 Ego Network Active");
+                            ui.label("Click another node to re-filter");
+                        }
+                        EgoMode::Selection => {
+                            ui.separator();
+                            ui.colored_label(egui::Color32::LIGHT_BLUE, "🧷 Selection Isolated");
+                            ui.label("Full Graph to return");
+                        }
+                        EgoMode::Full => {}
                     }
                 });
 
@@ -462,21 +992,34 @@ impl eframe::App for ForgeGraphViewer {
                 self.recenter_view(rect.size());
             }
 
+            if let Some(path) = open_target {
+                self.open_vault(&path);
+                return;
+            }
+
+            if let Some(full_idx) = focus_target {
+                self.focus_node(full_idx, rect.size());
+            }
+
+            if reveal_hidden && let Some(full_idx) = self.hidden_target {
+                self.reset_to_full_graph();
+                self.focus_node(full_idx, rect.size());
+            }
+
             // Handle mode changes
             if mode_changed {
                 self.ego_mode = new_mode;
                 if new_mode == EgoMode::Full {
                     self.reset_to_full_graph();
                     self.recenter_view(rect.size());
+                } else if new_mode == EgoMode::Selection {
+                    self.isolate_selection();
+                    self.recenter_view(rect.size());
                 } else {
                     // If we have a selected node, apply ego filter
                     if let Some(idx) = self.selected_node {
-                        let hops = match new_mode {
-                            EgoMode::OneHop => 1,
-                            EgoMode::TwoHop => 2,
-                            EgoMode::Full => 0,
-                        };
-                        self.extract_ego_network(idx, hops);
+                        let hops = if new_mode == EgoMode::OneHop { 1 } else { 2 };
+                        self.refilter_around(idx, hops);
                         self.recenter_view(rect.size());
                     }
                 }
@@ -487,8 +1030,16 @@ impl eframe::App for ForgeGraphViewer {
     }
 }
 
+fn load_graph(vault_path: &Path, filter_orphans: bool) -> Result<GraphData> {
+    println!("📖 Parsing vault at {:?}...", vault_path);
+    let graph = parse_vault(vault_path, filter_orphans)?;
+    println!("✅ Loaded {} nodes, {} edges", graph.nodes.len(), graph.edges.len());
+    Ok(graph)
+}
+
 fn parse_vault(vault_path: &Path, filter_orphans: bool) -> Result<GraphData> {
     let link_regex = Regex::new(r"!?\[\[([^\]]+)\]\]")?;
+    let tag_regex = tags::inline_tag_regex();
     let mut notes = HashMap::new();
 
     // Parse all markdown files
@@ -542,7 +1093,9 @@ fn parse_vault(vault_path: &Path, filter_orphans: bool) -> Result<GraphData> {
 
         notes.insert(name.clone(), Note {
             name,
+            path: path.to_path_buf(),
             links: links_set.into_iter().collect(),
+            tags: tags::extract_tags(&content, &tag_regex),
         });
     }
 
@@ -575,19 +1128,20 @@ fn parse_vault(vault_path: &Path, filter_orphans: bool) -> Result<GraphData> {
     let mut node_map = HashMap::new();
     let mut nodes = Vec::new();
 
-    // Much smaller initial radius for dense graphs
-    let radius = (nodes_to_include.len() as f32).sqrt() * 5.0;
+    let seeded = physics::seed_circle(nodes_to_include.len());
     for (i, name) in nodes_to_include.iter().enumerate() {
-        let angle = (i as f32 / nodes_to_include.len() as f32) * 2.0 * std::f32::consts::PI;
-        let x = radius * angle.cos();
-        let y = radius * angle.sin();
+        let (x, y) = seeded[i];
 
         node_map.insert(name.clone(), i);
         nodes.push(NodeData {
             name: name.clone(),
+            path: notes[name].path.clone(),
             x,
             y,
             is_orphan: orphans.contains(name),
+            component: 0,
+            tags: notes[name].tags.clone(),
+            degree: Degree::default(),
         });
     }
 
@@ -621,15 +1175,97 @@ fn parse_vault(vault_path: &Path, filter_orphans: bool) -> Result<GraphData> {
     })
 }
 
-fn main() -> eframe::Result {
-    let vault_path = std::env::args()
-        .nth(1)
-        .unwrap_or_else(|| {
-            eprintln!("Usage: forge-graph-viewer <vault-path> [--filter-orphans]");
-            std::process::exit(1);
-        });
+/// Note count per tag, sorted by tag name
+fn count_tags(graph: &GraphData) -> Vec<(String, usize)> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for node in &graph.nodes {
+        for tag in &node.tags {
+            *counts.entry(tag).or_default() += 1;
+        }
+    }
+    let mut counts: Vec<(String, usize)> = counts.into_iter().map(|(t, n)| (t.to_string(), n)).collect();
+    counts.sort();
+    counts
+}
 
+/// Number of components listed individually in the legend
+const LEGEND_COMPONENTS: usize = 8;
+
+/// Edges into / out of the selected node
+const INCOMING_COLOR: egui::Color32 = egui::Color32::from_rgb(120, 220, 120);
+const OUTGOING_COLOR: egui::Color32 = egui::Color32::from_rgb(100, 170, 255);
+
+/// Arrowhead length in screen pixels
+const ARROW_SIZE: f32 = 9.0;
+
+/// Label each node with its (undirected) connected component, numbered by
+/// descending size so the main cluster is always component 0.
+/// Returns the size of each component.
+fn assign_components(graph: &mut GraphData) -> Vec<usize> {
+    let n = graph.nodes.len();
+    let mut adj_list: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for edge in &graph.edges {
+        adj_list[edge.from].push(edge.to);
+        adj_list[edge.to].push(edge.from);
+    }
+
+    // Flood fill from each unvisited node
+    let mut raw_ids = vec![usize::MAX; n];
+    let mut sizes = Vec::new();
+    for start in 0..n {
+        if raw_ids[start] != usize::MAX {
+            continue;
+        }
+        let id = sizes.len();
+        let mut stack = vec![start];
+        raw_ids[start] = id;
+        let mut size = 0;
+        while let Some(node) = stack.pop() {
+            size += 1;
+            for &neighbor in &adj_list[node] {
+                if raw_ids[neighbor] == usize::MAX {
+                    raw_ids[neighbor] = id;
+                    stack.push(neighbor);
+                }
+            }
+        }
+        sizes.push(size);
+    }
+
+    // Renumber largest-first
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by(|&a, &b| sizes[b].cmp(&sizes[a]));
+    let mut rank = vec![0; sizes.len()];
+    for (new_id, &old_id) in order.iter().enumerate() {
+        rank[old_id] = new_id;
+    }
+
+    for (node, raw_id) in graph.nodes.iter_mut().zip(raw_ids) {
+        node.component = rank[raw_id];
+    }
+
+    order.iter().map(|&old_id| sizes[old_id]).collect()
+}
+
+/// Distinct hue per component, spread by the golden angle so neighbours differ
+fn component_color(component: usize) -> egui::Color32 {
+    // Start at teal (the old uniform colour) so the main cluster looks familiar
+    let hue = (0.48 + component as f32 * 0.618_034).fract();
+    egui::ecolor::Hsva::new(hue, 0.65, 0.9, 1.0).into()
+}
+
+fn main() -> eframe::Result {
     let filter_orphans = std::env::args().any(|arg| arg == "--filter-orphans");
+    let recent = RecentVaults::load(recent::config_path());
+
+    // With no path given, reopen the last vault (or start empty and wait
+    // for one to be dropped, when launched from a dock)
+    let vault_path = std::env::args()
+        .skip(1)
+        .find(|arg| !arg.starts_with("--"))
+        .map(PathBuf::from)
+        .or_else(|| recent.last().map(Path::to_path_buf))
+        .map(|path| fs::canonicalize(&path).unwrap_or(path));
 
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
@@ -641,8 +1277,11 @@ fn main() -> eframe::Result {
     eframe::run_native(
         "Forge Graph Viewer",
         options,
-        Box::new(|_cc| {
-            match ForgeGraphViewer::new(Path::new(&vault_path), filter_orphans) {
+        Box::new(move |_cc| {
+            let Some(vault_path) = vault_path else {
+                return Ok(Box::new(ForgeGraphViewer::empty(filter_orphans, recent)) as Box<dyn eframe::App>);
+            };
+            match ForgeGraphViewer::new(&vault_path, filter_orphans, recent) {
                 Ok(app) => Ok(Box::new(app) as Box<dyn eframe::App>),
                 Err(e) => {
                     eprintln!("Error loading vault: {}", e);
@@ -652,3 +1291,104 @@ fn main() -> eframe::Result {
         }),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A graph of named notes and links between them
+    fn graph(names: &[&str], links: &[(usize, usize)]) -> GraphData {
+        GraphData {
+            nodes: names
+                .iter()
+                .map(|name| NodeData {
+                    name: name.to_string(),
+                    path: PathBuf::from(format!("{}.md", name)),
+                    x: 0.0,
+                    y: 0.0,
+                    is_orphan: false,
+                    component: 0,
+                    tags: Vec::new(),
+                    degree: Degree::default(),
+                })
+                .collect(),
+            edges: links.iter().map(|&(from, to)| EdgeData { from, to }).collect(),
+            node_map: names.iter().enumerate().map(|(i, name)| (name.to_string(), i)).collect(),
+        }
+    }
+
+    fn viewer(names: &[&str], links: &[(usize, usize)]) -> ForgeGraphViewer {
+        ForgeGraphViewer::with_graph(graph(names, links), false, RecentVaults::default())
+    }
+
+    fn components(graph: &GraphData) -> Vec<usize> {
+        graph.nodes.iter().map(|n| n.component).collect()
+    }
+
+    #[test]
+    fn components_numbered_largest_first() {
+        // {0} alone, {1, 2} and {3, 4, 5}: the triple becomes component 0
+        let mut g = graph(&["A", "B", "C", "D", "E", "F"], &[(1, 2), (3, 4), (5, 4)]);
+        let sizes = assign_components(&mut g);
+        assert_eq!(sizes, [3, 2, 1]);
+        assert_eq!(components(&g), [2, 1, 1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn link_direction_does_not_split_components() {
+        // A -> B <- C is one component even though C can't reach A
+        let mut g = graph(&["A", "B", "C"], &[(0, 1), (2, 1)]);
+        assert_eq!(assign_components(&mut g), [3]);
+        assert_eq!(components(&g), [0, 0, 0]);
+    }
+
+    #[test]
+    fn empty_graph_has_no_components() {
+        let mut g = graph(&[], &[]);
+        assert!(assign_components(&mut g).is_empty());
+    }
+
+    fn shown(viewer: &ForgeGraphViewer) -> BTreeSet<&str> {
+        viewer.graph.nodes.iter().map(|n| n.name.as_str()).collect()
+    }
+
+    #[test]
+    fn drilling_from_one_ego_view_into_another() {
+        // A chain: Alpha - Beta - Gamma - Delta - Epsilon
+        let mut viewer = viewer(&["Alpha", "Beta", "Gamma", "Delta", "Epsilon"], &[(0, 1), (1, 2), (2, 3), (3, 4)]);
+        viewer.extract_ego_network(1, 1);
+        assert_eq!(shown(&viewer), BTreeSet::from(["Alpha", "Beta", "Gamma"]));
+
+        // Clicking Gamma in the filtered graph centres on Gamma, whatever
+        // its index there
+        let gamma = viewer.graph.nodes.iter().position(|n| n.name == "Gamma").unwrap();
+        viewer.refilter_around(gamma, 1);
+        assert_eq!(shown(&viewer), BTreeSet::from(["Beta", "Gamma", "Delta"]));
+        let selected = viewer.selected_node.unwrap();
+        assert_eq!(viewer.graph.nodes[selected].name, "Gamma");
+    }
+
+    #[test]
+    fn opening_a_vault_keeps_display_options() {
+        let vault = tempfile::tempdir().unwrap();
+        fs::write(vault.path().join("Alpha.md"), "#stoicism\n[[Beta]]\n").unwrap();
+        fs::write(vault.path().join("Beta.md"), "Back to [[Alpha]]\n").unwrap();
+
+        let mut viewer = viewer(&["Old"], &[]);
+        viewer.color_by_component = false;
+        viewer.size_metric = SizeMetric::InDegree;
+        viewer.tag_filter = BTreeSet::from(["stoicism".to_string(), "gone".to_string()]);
+        viewer.tag_match = TagMatch::All;
+        viewer.hide_unmatched = true;
+        viewer.simulation.paused = true;
+
+        viewer.open_vault(vault.path());
+        assert_eq!(shown(&viewer), BTreeSet::from(["Alpha", "Beta"]));
+        assert!(!viewer.color_by_component);
+        assert!(viewer.size_metric == SizeMetric::InDegree);
+        assert_eq!(viewer.tag_filter, BTreeSet::from(["stoicism".to_string()]));
+        assert!(viewer.tag_match == TagMatch::All);
+        assert!(viewer.hide_unmatched);
+        assert!(viewer.simulation.paused);
+    }
+}
@@ -0,0 +1,301 @@
+//! Force-directed layout, integrated at a fixed rate whatever the display's
+//! refresh rate, and cooled so a run always comes to rest.
+
+/// Simulated seconds per integration step: the old one-step-per-frame
+/// pace on a 60 Hz display
+pub const STEP: f32 = 1.0 / 60.0;
+/// Most steps taken in one frame. A slow frame drops the backlog rather
+/// than stalling the UI to catch up.
+const MAX_STEPS_PER_FRAME: usize = 8;
+
+const SPRING_LENGTH: f32 = 30.0; // Much shorter for dense graphs
+const DAMPING: f32 = 0.9; // High, for faster settling
+const REPULSION: f32 = 5000.0;
+const ATTRACTION: f32 = 0.05; // Strong, to pull clusters together
+
+/// Furthest a node moves in one step at full temperature
+const MAX_DISPLACEMENT: f32 = 50.0;
+/// Temperature kept per step (about 0.74 per second)
+pub const COOLING: f32 = 0.995;
+/// Below this the layout is considered cold and the run stops
+const MIN_TEMPERATURE: f32 = 0.01;
+/// Kinetic energy below which the layout has stabilised
+const SETTLED_ENERGY: f32 = 1.0;
+
+/// Starting temperature of the short pass after extracting a subgraph:
+/// enough to untangle it without flinging it apart
+pub const LOCAL_TEMPERATURE: f32 = 0.3;
+/// Steps in that pass
+pub const LOCAL_STEPS: usize = 120;
+
+/// One integration step over plain slices: repulsion between every pair,
+/// springs along `edges`, damping, and a displacement cap that shrinks
+/// with `temperature`. Returns the kinetic energy afterwards.
+pub fn step(
+    positions: &mut [(f32, f32)],
+    velocities: &mut [(f32, f32)],
+    edges: &[(usize, usize)],
+    temperature: f32,
+) -> f32 {
+    let mut forces = vec![(0.0f32, 0.0f32); positions.len()];
+
+    // Repulsive forces between all nodes (Barnes-Hut would be better but this works for now)
+    for i in 0..positions.len() {
+        for j in (i + 1)..positions.len() {
+            let dx = positions[j].0 - positions[i].0;
+            let dy = positions[j].1 - positions[i].1;
+            let distance = (dx * dx + dy * dy).sqrt().max(1.0);
+
+            // Coulomb's law (repulsion)
+            let force = REPULSION / (distance * distance);
+            let (fx, fy) = (dx / distance * force, dy / distance * force);
+            forces[i].0 -= fx;
+            forces[i].1 -= fy;
+            forces[j].0 += fx;
+            forces[j].1 += fy;
+        }
+    }
+
+    // Attractive forces along edges (Hooke's law)
+    for &(from, to) in edges {
+        let dx = positions[to].0 - positions[from].0;
+        let dy = positions[to].1 - positions[from].1;
+        let distance = (dx * dx + dy * dy).sqrt().max(1.0);
+
+        let force = (distance - SPRING_LENGTH) * ATTRACTION;
+        let (fx, fy) = (dx / distance * force, dy / distance * force);
+        forces[from].0 += fx;
+        forces[from].1 += fy;
+        forces[to].0 -= fx;
+        forces[to].1 -= fy;
+    }
+
+    let max_move = MAX_DISPLACEMENT * temperature;
+    let mut energy = 0.0;
+    for ((position, velocity), (fx, fy)) in positions.iter_mut().zip(velocities.iter_mut()).zip(forces) {
+        velocity.0 = (velocity.0 + fx) * DAMPING;
+        velocity.1 = (velocity.1 + fy) * DAMPING;
+
+        let speed = (velocity.0 * velocity.0 + velocity.1 * velocity.1).sqrt();
+        if speed > max_move {
+            velocity.0 *= max_move / speed;
+            velocity.1 *= max_move / speed;
+        }
+
+        position.0 += velocity.0;
+        position.1 += velocity.1;
+        energy += velocity.0 * velocity.0 + velocity.1 * velocity.1;
+    }
+    energy
+}
+
+/// Nodes spread evenly on a circle sized for their number
+pub fn seed_circle(count: usize) -> Vec<(f32, f32)> {
+    // Much smaller initial radius for dense graphs
+    let radius = (count as f32).sqrt() * 5.0;
+    (0..count)
+        .map(|i| {
+            let angle = (i as f32 / count as f32) * 2.0 * std::f32::consts::PI;
+            (radius * angle.cos(), radius * angle.sin())
+        })
+        .collect()
+}
+
+/// State of the layout run: velocities, temperature, and the wall-clock
+/// time not yet turned into steps
+pub struct Simulation {
+    pub running: bool,
+    pub paused: bool,
+    pub temperature: f32,
+    pub energy: f32,
+    velocities: Vec<(f32, f32)>,
+    accumulator: f32,
+}
+
+impl Simulation {
+    /// A stopped simulation for `nodes` nodes
+    pub fn new(nodes: usize) -> Self {
+        Self {
+            running: false,
+            paused: false,
+            temperature: 0.0,
+            energy: 0.0,
+            velocities: vec![(0.0, 0.0); nodes],
+            accumulator: 0.0,
+        }
+    }
+
+    /// Stop and forget all motion, for a graph of `nodes` nodes
+    pub fn reset(&mut self, nodes: usize) {
+        *self = Self::new(nodes);
+    }
+
+    /// (Re)start a full-temperature run from the current positions
+    pub fn start(&mut self) {
+        let nodes = self.velocities.len();
+        self.reset(nodes);
+        self.running = true;
+        self.temperature = 1.0;
+    }
+
+    /// Whether `advance` would move anything
+    pub fn active(&self) -> bool {
+        self.running && !self.paused
+    }
+
+    /// Move the layout on by `dt` seconds of wall time, in whole `STEP`s.
+    /// Returns whether any step ran; the run stops once settled or cold.
+    pub fn advance(&mut self, dt: f32, positions: &mut [(f32, f32)], edges: &[(usize, usize)]) -> bool {
+        if !self.active() {
+            return false;
+        }
+
+        self.accumulator += dt;
+        let mut steps = 0;
+        while self.accumulator >= STEP && steps < MAX_STEPS_PER_FRAME {
+            self.one_step(positions, edges);
+            self.accumulator -= STEP;
+            steps += 1;
+            if !self.running {
+                break;
+            }
+        }
+        if steps == MAX_STEPS_PER_FRAME {
+            self.accumulator = self.accumulator.min(STEP);
+        }
+        steps > 0
+    }
+
+    /// Run up to `steps` steps at once from `temperature`, as the quick
+    /// tidy-up after extracting a subgraph. Leaves the simulation stopped.
+    pub fn settle(&mut self, positions: &mut [(f32, f32)], edges: &[(usize, usize)], temperature: f32, steps: usize) {
+        self.start();
+        self.temperature = temperature;
+        for _ in 0..steps {
+            self.one_step(positions, edges);
+            if !self.running {
+                break;
+            }
+        }
+        self.running = false;
+        self.velocities.iter_mut().for_each(|v| *v = (0.0, 0.0));
+    }
+
+    fn one_step(&mut self, positions: &mut [(f32, f32)], edges: &[(usize, usize)]) {
+        self.energy = step(positions, &mut self.velocities, edges, self.temperature);
+        self.temperature *= COOLING;
+        if self.energy < SETTLED_ENERGY || self.temperature < MIN_TEMPERATURE {
+            self.running = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+        ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+    }
+
+    /// A spread-out triangle, and a loose node off to one side
+    const EDGES: &[(usize, usize)] = &[(0, 1), (1, 2), (2, 0)];
+
+    fn layout() -> Vec<(f32, f32)> {
+        vec![(0.0, 0.0), (300.0, 0.0), (0.0, 300.0), (-200.0, -200.0)]
+    }
+
+    #[test]
+    fn springs_pull_and_repulsion_pushes() {
+        // Linked nodes 300 apart move together
+        let mut positions = layout();
+        let mut velocities = vec![(0.0, 0.0); positions.len()];
+        let before = positions.clone();
+        let energy = step(&mut positions, &mut velocities, EDGES, 1.0);
+        assert!(energy > 0.0);
+        assert!(distance(positions[0], positions[1]) < distance(before[0], before[1]));
+
+        // An unlinked close pair separates
+        let mut positions = vec![(0.0, 0.0), (5.0, 5.0)];
+        let mut velocities = vec![(0.0, 0.0); 2];
+        step(&mut positions, &mut velocities, &[], 1.0);
+        assert!(distance(positions[0], positions[1]) > distance((0.0, 0.0), (5.0, 5.0)));
+    }
+
+    #[test]
+    fn temperature_caps_displacement() {
+        let mut positions = layout();
+        let mut velocities = vec![(0.0, 0.0); positions.len()];
+        let before = positions.clone();
+
+        step(&mut positions, &mut velocities, EDGES, 0.1);
+        for (a, b) in before.iter().zip(&positions) {
+            assert!(distance(*a, *b) <= MAX_DISPLACEMENT * 0.1 + 1e-3);
+        }
+    }
+
+    #[test]
+    fn same_layout_at_any_frame_rate() {
+        let run = |frames: usize, dt: f32| {
+            let mut positions = layout();
+            let mut simulation = Simulation::new(positions.len());
+            simulation.start();
+            for _ in 0..frames {
+                simulation.advance(dt, &mut positions, EDGES);
+            }
+            positions
+        };
+
+        // Half a simulated second at 30, 60 and 120 Hz
+        let at_60 = run(30, STEP);
+        assert_eq!(run(15, STEP * 2.0), at_60);
+        assert_eq!(run(60, STEP / 2.0), at_60);
+    }
+
+    #[test]
+    fn slow_frame_drops_backlog() {
+        let mut positions = layout();
+        let mut simulation = Simulation::new(positions.len());
+        simulation.start();
+
+        simulation.advance(5.0, &mut positions, EDGES);
+        assert!(simulation.accumulator <= STEP);
+        assert!((simulation.temperature - COOLING.powi(MAX_STEPS_PER_FRAME as i32)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn pause_holds_and_runs_cool_to_a_stop() {
+        let mut positions = layout();
+        let mut simulation = Simulation::new(positions.len());
+        simulation.start();
+
+        simulation.paused = true;
+        assert!(!simulation.advance(1.0, &mut positions, EDGES));
+        assert_eq!(positions, layout());
+
+        simulation.paused = false;
+        for _ in 0..10_000 {
+            simulation.advance(STEP, &mut positions, EDGES);
+        }
+        assert!(!simulation.running);
+        assert!(simulation.temperature < 1.0);
+
+        // Stopped for good until restarted
+        assert!(!simulation.advance(STEP, &mut positions, EDGES));
+        simulation.start();
+        assert!(simulation.active());
+        assert_eq!(simulation.temperature, 1.0);
+    }
+
+    #[test]
+    fn settle_runs_a_bounded_pass_and_stops() {
+        let mut positions = layout();
+        let mut simulation = Simulation::new(positions.len());
+        let before = positions.clone();
+
+        simulation.settle(&mut positions, EDGES, LOCAL_TEMPERATURE, LOCAL_STEPS);
+        assert_ne!(positions, before);
+        assert!(!simulation.running);
+        assert!(simulation.temperature >= LOCAL_TEMPERATURE * COOLING.powi(LOCAL_STEPS as i32) - 1e-6);
+    }
+}
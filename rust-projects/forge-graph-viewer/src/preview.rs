@@ -0,0 +1,170 @@
+use eframe::egui;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+
+/// Body lines shown in the preview panel
+const PREVIEW_LINES: usize = 40;
+
+/// Never read more than this from a note, however large it is
+const PREVIEW_BYTES: u64 = 64 * 1024;
+
+pub struct NotePreview {
+    pub frontmatter: Vec<(String, String)>,
+    pub body: String,
+    pub truncated: bool,
+}
+
+/// Previews keyed by full-graph node index. Files are read on a background
+/// thread the first time a node is selected and kept afterwards.
+#[derive(Default)]
+pub struct PreviewCache {
+    loaded: HashMap<usize, Result<NotePreview, String>>,
+    pending: HashMap<usize, Receiver<Result<NotePreview, String>>>,
+}
+
+impl PreviewCache {
+    /// Preview for `idx`, or `None` while it is still being read
+    pub fn get(&mut self, idx: usize, path: &Path, ctx: &egui::Context) -> Option<&Result<NotePreview, String>> {
+        if !self.loaded.contains_key(&idx) {
+            match self.pending.get(&idx) {
+                Some(rx) => match rx.try_recv() {
+                    Ok(preview) => {
+                        self.pending.remove(&idx);
+                        self.loaded.insert(idx, preview);
+                    }
+                    Err(mpsc::TryRecvError::Empty) => return None,
+                    Err(mpsc::TryRecvError::Disconnected) => {
+                        self.pending.remove(&idx);
+                        self.loaded.insert(idx, Err("Preview thread exited".to_string()));
+                    }
+                },
+                None => {
+                    let (tx, rx) = mpsc::channel();
+                    let path: PathBuf = path.to_path_buf();
+                    let ctx = ctx.clone();
+                    std::thread::spawn(move || {
+                        let _ = tx.send(load(&path));
+                        ctx.request_repaint();
+                    });
+                    self.pending.insert(idx, rx);
+                    return None;
+                }
+            }
+        }
+        self.loaded.get(&idx)
+    }
+}
+
+/// Read the start of a note (at most `PREVIEW_BYTES`) and build its preview
+fn load(path: &Path) -> Result<NotePreview, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut buf = Vec::new();
+    file.take(PREVIEW_BYTES + 1)
+        .read_to_end(&mut buf)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+    let hit_limit = buf.len() as u64 > PREVIEW_BYTES;
+    buf.truncate(PREVIEW_BYTES as usize);
+
+    let mut preview = parse(&String::from_utf8_lossy(&buf));
+    preview.truncated |= hit_limit;
+    Ok(preview)
+}
+
+/// Split off YAML frontmatter as flat key/value pairs (list items joined
+/// with commas) and keep the first `PREVIEW_LINES` lines of the body
+fn parse(text: &str) -> NotePreview {
+    let mut frontmatter: Vec<(String, String)> = Vec::new();
+    let mut body_start = 0;
+
+    let mut lines = text.lines();
+    if lines.next().map(str::trim_end) == Some("---") {
+        let mut consumed = 1;
+        let mut closed = false;
+        for line in lines {
+            consumed += 1;
+            if line.trim_end() == "---" {
+                closed = true;
+                break;
+            }
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            let is_continuation = line.starts_with([' ', '\t']) || trimmed.starts_with("- ");
+            if is_continuation && let Some((_, value)) = frontmatter.last_mut() {
+                let item = trimmed.trim_start_matches("- ").trim();
+                if !value.is_empty() {
+                    value.push_str(", ");
+                }
+                value.push_str(item);
+            } else if let Some((key, value)) = trimmed.split_once(':') {
+                frontmatter.push((key.trim().to_string(), value.trim().to_string()));
+            }
+        }
+        if closed {
+            body_start = consumed;
+        } else {
+            // No closing marker: treat the whole thing as body
+            frontmatter.clear();
+        }
+    }
+
+    let body_lines: Vec<&str> = text.lines().skip(body_start).collect();
+    let truncated = body_lines.len() > PREVIEW_LINES;
+    let body = body_lines
+        .iter()
+        .take(PREVIEW_LINES)
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string();
+
+    NotePreview {
+        frontmatter,
+        body,
+        truncated,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collapses_frontmatter() {
+        let text = "---\ndate created: 2024-01-01 10:00\ntags:\n  - philosophy\n  - ethics\naliases: [Virtue]\n---\n\n# Heading\nBody text\n";
+        let preview = parse(text);
+        assert_eq!(
+            preview.frontmatter,
+            vec![
+                ("date created".to_string(), "2024-01-01 10:00".to_string()),
+                ("tags".to_string(), "philosophy, ethics".to_string()),
+                ("aliases".to_string(), "[Virtue]".to_string()),
+            ]
+        );
+        assert_eq!(preview.body, "# Heading\nBody text");
+        assert!(!preview.truncated);
+    }
+
+    #[test]
+    fn limits_body_lines() {
+        let text: String = (1..=100).map(|i| format!("line {}\n", i)).collect();
+        let preview = parse(&text);
+        assert!(preview.frontmatter.is_empty());
+        assert!(preview.truncated);
+        assert_eq!(preview.body.lines().count(), PREVIEW_LINES);
+        assert_eq!(preview.body.lines().last(), Some("line 40"));
+    }
+
+    #[test]
+    fn unclosed_frontmatter_is_body() {
+        let preview = parse("---\ntitle: draft\nstill writing");
+        assert!(preview.frontmatter.is_empty());
+        assert_eq!(preview.body, "---\ntitle: draft\nstill writing");
+    }
+}
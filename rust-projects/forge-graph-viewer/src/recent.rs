@@ -0,0 +1,124 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Vaults kept in the "Recent vaults" menu
+const MAX_RECENT: usize = 8;
+
+/// `~/.config/forge-graph-viewer/recent.json` (or the platform's equivalent)
+pub fn config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("forge-graph-viewer").join("recent.json"))
+}
+
+/// Vaults opened in the viewer, most recent first. The first one that still
+/// exists is reopened when no path is given on the command line.
+#[derive(Default, Serialize, Deserialize)]
+pub struct RecentVaults {
+    vaults: Vec<PathBuf>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl RecentVaults {
+    /// The list saved at `path`; empty if there's none yet or it can't be read
+    pub fn load(path: Option<PathBuf>) -> Self {
+        let vaults = path
+            .as_deref()
+            .and_then(|p| fs::read_to_string(p).ok())
+            .and_then(|json| serde_json::from_str::<RecentVaults>(&json).ok())
+            .map(|saved| saved.vaults)
+            .unwrap_or_default();
+        Self { vaults, path }
+    }
+
+    /// Move `vault` to the front, dropping the oldest beyond `MAX_RECENT`
+    pub fn push(&mut self, vault: &Path) {
+        self.vaults.retain(|v| v != vault);
+        self.vaults.insert(0, vault.to_path_buf());
+        self.vaults.truncate(MAX_RECENT);
+    }
+
+    /// The most recent vault that's still a directory
+    pub fn last(&self) -> Option<&Path> {
+        self.vaults.iter().map(PathBuf::as_path).find(|v| v.is_dir())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Path> {
+        self.vaults.iter().map(PathBuf::as_path)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Record `vault` as just opened and save, warning rather than failing
+    /// since the viewer works without the list
+    pub fn remember(&mut self, vault: &Path) {
+        self.push(vault);
+        if let Err(e) = self.save() {
+            eprintln!("⚠️ Could not save recent vaults: {:#}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_moves_to_front_and_caps() {
+        let mut recent = RecentVaults::default();
+        for i in 0..MAX_RECENT + 2 {
+            recent.push(Path::new(&format!("/vault{}", i)));
+        }
+        recent.push(Path::new("/vault5"));
+
+        let vaults: Vec<&Path> = recent.iter().collect();
+        assert_eq!(vaults.len(), MAX_RECENT);
+        assert_eq!(vaults[0], Path::new("/vault5"));
+        assert_eq!(vaults[1], Path::new("/vault9"));
+        assert_eq!(vaults.iter().filter(|v| **v == Path::new("/vault5")).count(), 1);
+    }
+
+    #[test]
+    fn round_trips_through_config_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = dir.path().join("notes");
+        fs::create_dir(&vault).unwrap();
+        let config = dir.path().join("config").join("recent.json");
+
+        let mut recent = RecentVaults::load(Some(config.clone()));
+        assert!(recent.last().is_none());
+        recent.push(&dir.path().join("gone"));
+        recent.remember(&vault);
+
+        let reloaded = RecentVaults::load(Some(config));
+        assert_eq!(reloaded.iter().count(), 2);
+        assert_eq!(reloaded.last(), Some(vault.as_path()));
+    }
+
+    #[test]
+    fn last_skips_vaults_that_no_longer_exist() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut recent = RecentVaults::default();
+        recent.push(dir.path());
+        recent.push(&dir.path().join("deleted"));
+        assert_eq!(recent.last(), Some(dir.path()));
+    }
+
+    #[test]
+    fn unreadable_config_starts_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("recent.json");
+        fs::write(&config, "not json").unwrap();
+        assert_eq!(RecentVaults::load(Some(config)).iter().count(), 0);
+    }
+}
@@ -0,0 +1,120 @@
+use crate::GraphData;
+
+/// What a node's radius is scaled by
+#[derive(Clone, Copy, PartialEq)]
+pub enum SizeMetric {
+    Uniform,
+    InDegree,    // Notes linking to it
+    OutDegree,   // Links out of it
+    TotalDegree, // Both
+}
+
+impl SizeMetric {
+    pub const ALL: [SizeMetric; 4] = [
+        SizeMetric::Uniform,
+        SizeMetric::InDegree,
+        SizeMetric::OutDegree,
+        SizeMetric::TotalDegree,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SizeMetric::Uniform => "Uniform",
+            SizeMetric::InDegree => "In-degree",
+            SizeMetric::OutDegree => "Out-degree",
+            SizeMetric::TotalDegree => "Total degree",
+        }
+    }
+
+    /// The node's value of this metric; 0 for every node when uniform
+    pub fn degree(self, degrees: Degree) -> usize {
+        match self {
+            SizeMetric::Uniform => 0,
+            SizeMetric::InDegree => degrees.incoming,
+            SizeMetric::OutDegree => degrees.outgoing,
+            SizeMetric::TotalDegree => degrees.incoming + degrees.outgoing,
+        }
+    }
+}
+
+/// A node's link counts in the full graph
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct Degree {
+    pub incoming: usize,
+    pub outgoing: usize,
+}
+
+/// Radius multiplier of the best-connected node
+pub const MAX_SCALE: f32 = 3.5;
+
+/// Fill in each node's in- and out-degree from the graph's edges
+pub fn assign_degrees(graph: &mut GraphData) {
+    for node in &mut graph.nodes {
+        node.degree = Degree::default();
+    }
+    for edge in &graph.edges {
+        graph.nodes[edge.from].degree.outgoing += 1;
+        graph.nodes[edge.to].degree.incoming += 1;
+    }
+}
+
+/// Radius multiplier for a node with `degree` when the largest in the vault
+/// is `max`: 1.0 for an unlinked node up to `MAX_SCALE` for the top hub,
+/// log-scaled so a few hubs don't shrink everything else to dots
+pub fn scale(degree: usize, max: usize) -> f32 {
+    if max == 0 {
+        return 1.0;
+    }
+    let t = (degree as f32).ln_1p() / (max as f32).ln_1p();
+    1.0 + t.clamp(0.0, 1.0) * (MAX_SCALE - 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EdgeData, NodeData};
+    use std::collections::HashMap;
+
+    fn graph(n: usize, edges: &[(usize, usize)]) -> GraphData {
+        GraphData {
+            nodes: (0..n)
+                .map(|i| NodeData {
+                    name: i.to_string(),
+                    path: Default::default(),
+                    x: 0.0,
+                    y: 0.0,
+                    is_orphan: false,
+                    component: 0,
+                    tags: Vec::new(),
+                    degree: Degree::default(),
+                })
+                .collect(),
+            edges: edges.iter().map(|&(from, to)| EdgeData { from, to }).collect(),
+            node_map: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn degrees_count_each_direction() {
+        let mut g = graph(3, &[(0, 1), (2, 1), (1, 0)]);
+        assign_degrees(&mut g);
+        let degrees: Vec<Degree> = g.nodes.iter().map(|n| n.degree).collect();
+        assert_eq!(degrees, [
+            Degree { incoming: 1, outgoing: 1 },
+            Degree { incoming: 2, outgoing: 1 },
+            Degree { incoming: 0, outgoing: 1 },
+        ]);
+        assert_eq!(SizeMetric::TotalDegree.degree(degrees[1]), 3);
+        assert_eq!(SizeMetric::Uniform.degree(degrees[1]), 0);
+    }
+
+    #[test]
+    fn scale_is_logarithmic_and_clamped() {
+        assert_eq!(scale(0, 100), 1.0);
+        assert_eq!(scale(100, 100), MAX_SCALE);
+        assert_eq!(scale(500, 100), MAX_SCALE);
+        assert_eq!(scale(3, 0), 1.0);
+        // A tenth of the top hub's links is well over a tenth of its extra size
+        assert!(scale(10, 100) > 1.0 + 0.5 * (MAX_SCALE - 1.0));
+    }
+}
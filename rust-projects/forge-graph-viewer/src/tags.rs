@@ -0,0 +1,116 @@
+use regex::Regex;
+use std::collections::BTreeSet;
+
+/// How a multi-tag filter combines the selected tags
+#[derive(Clone, Copy, PartialEq)]
+pub enum TagMatch {
+    Any, // OR: node has at least one selected tag
+    All, // AND: node has every selected tag
+}
+
+/// Tags from a note's frontmatter `tags:` field and inline `#tags` in the
+/// body, lowercased without the `#`, sorted and deduplicated
+pub fn extract_tags(content: &str, inline_re: &Regex) -> Vec<String> {
+    let mut tags = BTreeSet::new();
+    let mut body = content;
+
+    let mut lines = content.lines();
+    if lines.next().map(str::trim_end) == Some("---") {
+        let mut offset = content.find('\n').map_or(content.len(), |i| i + 1);
+        let mut in_tags = false;
+        for line in lines {
+            offset += line.len() + 1;
+            if line.trim_end() == "---" {
+                body = content.get(offset..).unwrap_or("");
+                break;
+            }
+            let trimmed = line.trim();
+            if in_tags && trimmed.starts_with("- ") {
+                add_tag(&mut tags, trimmed.trim_start_matches("- "));
+                continue;
+            }
+            in_tags = false;
+            if let Some((key, value)) = trimmed.split_once(':')
+                && matches!(key.trim(), "tags" | "tag")
+            {
+                let value = value.trim().trim_start_matches('[').trim_end_matches(']');
+                if value.is_empty() {
+                    in_tags = true; // Block list follows
+                }
+                for tag in value.split([',', ' ']) {
+                    add_tag(&mut tags, tag);
+                }
+            }
+        }
+    }
+
+    for cap in inline_re.captures_iter(body) {
+        add_tag(&mut tags, &cap[1]);
+    }
+    tags.into_iter().collect()
+}
+
+/// Regex for inline `#tag`: starts with a letter, so headings (`# Title`),
+/// `#123` issue numbers and `[[Note#Heading]]` anchors are not tags
+pub fn inline_tag_regex() -> Regex {
+    Regex::new(r"(?:^|\s)#([A-Za-z][\w/-]*)").unwrap()
+}
+
+fn add_tag(tags: &mut BTreeSet<String>, raw: &str) {
+    let tag = raw.trim().trim_matches(['"', '\'']).trim_start_matches('#');
+    if !tag.is_empty() {
+        tags.insert(tag.to_lowercase());
+    }
+}
+
+/// Whether a node's tags satisfy the filter; an empty selection matches everything
+pub fn matches(node_tags: &[String], selected: &BTreeSet<String>, mode: TagMatch) -> bool {
+    if selected.is_empty() {
+        return true;
+    }
+    let has = |tag: &String| node_tags.binary_search(tag).is_ok();
+    match mode {
+        TagMatch::Any => selected.iter().any(has),
+        TagMatch::All => selected.iter().all(has),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(content: &str) -> Vec<String> {
+        extract_tags(content, &inline_tag_regex())
+    }
+
+    #[test]
+    fn frontmatter_list_styles() {
+        assert_eq!(tags("---\ntags: [project, Stoicism]\n---\nBody"), vec!["project", "stoicism"]);
+        assert_eq!(tags("---\ntags: project, reading\n---\n"), vec!["project", "reading"]);
+        assert_eq!(
+            tags("---\ntitle: X\ntags:\n  - project\n  - \"#clinic\"\naliases: [Y]\n---\n"),
+            vec!["clinic", "project"]
+        );
+    }
+
+    #[test]
+    fn inline_tags_skip_headings_and_anchors() {
+        let content = "# Heading\n\nSee [[Seneca#Letters]] about #ethics and #project/forge.\nIssue #42\n";
+        assert_eq!(tags(content), vec!["ethics", "project/forge"]);
+    }
+
+    #[test]
+    fn frontmatter_and_inline_merge() {
+        let content = "---\ntags: [project]\n---\nA #Project note about #virtue\n";
+        assert_eq!(tags(content), vec!["project", "virtue"]);
+    }
+
+    #[test]
+    fn and_or_matching() {
+        let node = vec!["ethics".to_string(), "project".to_string()];
+        let selected: BTreeSet<String> = ["project", "reading"].iter().map(|s| s.to_string()).collect();
+        assert!(matches(&node, &selected, TagMatch::Any));
+        assert!(!matches(&node, &selected, TagMatch::All));
+        assert!(matches(&node, &BTreeSet::new(), TagMatch::All));
+    }
+}
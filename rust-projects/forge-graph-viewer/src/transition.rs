@@ -0,0 +1,155 @@
+use eframe::egui::{Pos2, Vec2};
+use std::collections::HashMap;
+
+/// Seconds a re-filter or refit takes to play out
+pub const DURATION: f32 = 0.3;
+
+/// Where the nodes and camera were just before the graph changed
+pub struct Snapshot {
+    pub positions: HashMap<String, Pos2>,
+    pub camera: Vec2,
+    pub zoom: f32,
+}
+
+/// Eases node positions and the camera from a `Snapshot` to their new
+/// values instead of snapping. `target` is the true layout; the graph's
+/// node positions follow `positions()` until the transition settles.
+pub struct Transition {
+    elapsed: f32,
+    start: Vec<Pos2>,
+    pub target: Vec<Pos2>,
+    camera: (Vec2, Vec2),
+    zoom: (f32, f32),
+}
+
+impl Transition {
+    /// `nodes` are the new graph's `(name, position)` pairs, heading for
+    /// `camera` and `zoom`
+    pub fn new<'a>(
+        before: &Snapshot,
+        nodes: impl IntoIterator<Item = (&'a str, Pos2)>,
+        camera: Vec2,
+        zoom: f32,
+    ) -> Self {
+        let (names, target): (Vec<&str>, Vec<Pos2>) = nodes.into_iter().unzip();
+        let start = start_positions(&before.positions, &names, &target);
+        Self {
+            elapsed: 0.0,
+            start,
+            target,
+            camera: (before.camera, camera),
+            zoom: (before.zoom, zoom),
+        }
+    }
+
+    pub fn step(&mut self, dt: f32) {
+        self.elapsed = (self.elapsed + dt).min(DURATION);
+    }
+
+    pub fn settled(&self) -> bool {
+        self.elapsed >= DURATION
+    }
+
+    fn progress(&self) -> f32 {
+        ease(self.elapsed / DURATION)
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos2> + '_ {
+        let t = self.progress();
+        self.start.iter().zip(&self.target).map(move |(a, b)| a.lerp(*b, t))
+    }
+
+    pub fn camera(&self) -> Vec2 {
+        let (from, to) = self.camera;
+        from + (to - from) * self.progress()
+    }
+
+    /// Interpolated geometrically, so zooming in and out feel alike
+    pub fn zoom(&self) -> f32 {
+        let (from, to) = self.zoom;
+        from * (to / from).powf(self.progress())
+    }
+}
+
+/// Nodes already on screen start where they were. Newly shown ones fly out
+/// from the middle of those, or appear in place if nothing carried over.
+fn start_positions(before: &HashMap<String, Pos2>, names: &[&str], target: &[Pos2]) -> Vec<Pos2> {
+    let kept: Vec<Pos2> = names.iter().filter_map(|name| before.get(*name).copied()).collect();
+    let origin = (!kept.is_empty()).then(|| {
+        let sum = kept.iter().fold(Vec2::ZERO, |sum, p| sum + p.to_vec2());
+        (sum / kept.len() as f32).to_pos2()
+    });
+
+    names
+        .iter()
+        .zip(target)
+        .map(|(name, &to)| before.get(*name).copied().or(origin).unwrap_or(to))
+        .collect()
+}
+
+/// Cubic ease-in-out over `t` in 0..=1
+fn ease(t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+    if t < 0.5 {
+        4.0 * t * t * t
+    } else {
+        1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eframe::egui::{pos2, vec2};
+
+    fn snapshot(positions: &[(&str, Pos2)]) -> Snapshot {
+        Snapshot {
+            positions: positions.iter().map(|(n, p)| (n.to_string(), *p)).collect(),
+            camera: vec2(0.0, 0.0),
+            zoom: 1.0,
+        }
+    }
+
+    #[test]
+    fn easing_endpoints() {
+        assert_eq!(ease(0.0), 0.0);
+        assert_eq!(ease(0.5), 0.5);
+        assert_eq!(ease(1.0), 1.0);
+        assert_eq!(ease(2.0), 1.0);
+    }
+
+    #[test]
+    fn new_nodes_fly_out_from_kept_ones() {
+        let before = snapshot(&[("a", pos2(0.0, 0.0)), ("b", pos2(10.0, 0.0)), ("gone", pos2(99.0, 99.0))]);
+        let nodes = [("a", pos2(0.0, 0.0)), ("b", pos2(10.0, 0.0)), ("new", pos2(50.0, 50.0))];
+        let transition = Transition::new(&before, nodes, vec2(0.0, 0.0), 1.0);
+
+        let start: Vec<Pos2> = transition.positions().collect();
+        assert_eq!(start, vec![pos2(0.0, 0.0), pos2(10.0, 0.0), pos2(5.0, 0.0)]);
+    }
+
+    #[test]
+    fn nothing_kept_means_no_movement() {
+        let before = snapshot(&[("old", pos2(1.0, 1.0))]);
+        let transition = Transition::new(&before, [("new", pos2(7.0, 3.0))], vec2(0.0, 0.0), 1.0);
+        assert_eq!(transition.positions().collect::<Vec<_>>(), vec![pos2(7.0, 3.0)]);
+    }
+
+    #[test]
+    fn settles_on_targets_and_camera() {
+        let before = snapshot(&[("a", pos2(0.0, 0.0))]);
+        let mut transition = Transition::new(&before, [("a", pos2(0.0, 0.0)), ("b", pos2(20.0, 0.0))], vec2(-8.0, 4.0), 4.0);
+
+        transition.step(DURATION / 2.0);
+        assert!(!transition.settled());
+        assert_eq!(transition.positions().nth(1), Some(pos2(10.0, 0.0)));
+        assert_eq!(transition.camera(), vec2(-4.0, 2.0));
+        assert!((transition.zoom() - 2.0).abs() < 1e-5);
+
+        transition.step(1.0);
+        assert!(transition.settled());
+        assert_eq!(transition.positions().collect::<Vec<_>>(), transition.target);
+        assert_eq!(transition.camera(), vec2(-8.0, 4.0));
+        assert!((transition.zoom() - 4.0).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,165 @@
+use petgraph::algo::tarjan_scc;
+use petgraph::graph::{Graph, NodeIndex};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// A circular chain of links, starting from its alphabetically first note;
+/// the link back to the start is implied
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cycle {
+    pub notes: Vec<String>,
+}
+
+impl Cycle {
+    /// `A → B → C → A`
+    pub fn chain(&self) -> String {
+        let mut chain = self.notes.join(" → ");
+        if let Some(first) = self.notes.first() {
+            chain.push_str(" → ");
+            chain.push_str(first);
+        }
+        chain
+    }
+}
+
+/// Cycles in the link graph, shortest first, plus the sizes of the tangles
+/// (strongly connected components) they come from, largest first.
+///
+/// Each note gets the shortest cycle of at least `min_length` notes that
+/// starts from it and visits only notes after it in name order, so every
+/// cycle is reported once and each tangle contributes at most one cycle
+/// per note. Self-links are never cycles.
+pub fn find_cycles(graph: &Graph<String, ()>, min_length: usize) -> (Vec<Cycle>, Vec<usize>) {
+    let min_length = min_length.max(2);
+    let mut cycles = Vec::new();
+    let mut tangles = Vec::new();
+
+    for mut component in tarjan_scc(graph) {
+        if component.len() < 2 {
+            continue;
+        }
+        tangles.push(component.len());
+        component.sort_by(|a, b| graph[*a].cmp(&graph[*b]));
+        let rank: HashMap<NodeIndex, usize> = component.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+
+        for (i, &start) in component.iter().enumerate() {
+            if let Some(path) = shortest_cycle(graph, start, min_length, |n| rank.get(&n).is_some_and(|&r| r > i)) {
+                cycles.push(Cycle { notes: path.into_iter().map(|n| graph[n].clone()).collect() });
+            }
+        }
+    }
+
+    cycles.sort_by(|a, b| a.notes.len().cmp(&b.notes.len()).then_with(|| a.notes.cmp(&b.notes)));
+    tangles.sort_unstable_by(|a, b| b.cmp(a));
+    (cycles, tangles)
+}
+
+/// Breadth-first search out of `start` through notes `allowed` admits, for
+/// the nearest note that links back to `start` and closes a loop of at
+/// least `min_length` notes. Returns the loop's notes from `start`.
+///
+/// The search is over (note, notes so far) states, the count capped at
+/// `min_length`, so a note first reached by a path too short to count can
+/// still be reached again by a longer one. A path never revisits a note.
+fn shortest_cycle(
+    graph: &Graph<String, ()>,
+    start: NodeIndex,
+    min_length: usize,
+    allowed: impl Fn(NodeIndex) -> bool,
+) -> Option<Vec<NodeIndex>> {
+    type State = (NodeIndex, usize);
+    let first: State = (start, 1);
+    let mut parent: HashMap<State, State> = HashMap::new();
+    let mut seen: HashSet<State> = HashSet::from([first]);
+    let mut queue = VecDeque::from([first]);
+
+    // Notes on the path to `state`, last first
+    let path_to = |parent: &HashMap<State, State>, mut state: State| {
+        let mut path = vec![state.0];
+        while let Some(&prev) = parent.get(&state) {
+            path.push(prev.0);
+            state = prev;
+        }
+        path
+    };
+
+    while let Some(state @ (node, notes)) = queue.pop_front() {
+        if notes >= min_length && node != start && graph.contains_edge(node, start) {
+            let mut path = path_to(&parent, state);
+            path.reverse();
+            return Some(path);
+        }
+        let on_path = path_to(&parent, state);
+        for next in graph.neighbors(node) {
+            let next_state = (next, (notes + 1).min(min_length));
+            if allowed(next) && !on_path.contains(&next) && seen.insert(next_state) {
+                parent.insert(next_state, state);
+                queue.push_back(next_state);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(links: &[(&str, &str)]) -> Graph<String, ()> {
+        let mut graph = Graph::new();
+        let mut nodes = HashMap::new();
+        for (a, b) in links {
+            for name in [a, b] {
+                nodes.entry(*name).or_insert_with(|| graph.add_node(name.to_string()));
+            }
+            graph.add_edge(nodes[a], nodes[b], ());
+        }
+        graph
+    }
+
+    fn chains(cycles: &[Cycle]) -> Vec<String> {
+        cycles.iter().map(Cycle::chain).collect()
+    }
+
+    #[test]
+    fn finds_each_cycle_once_shortest_first() {
+        let graph = graph(&[
+            ("Stoicism", "Anger"), ("Anger", "Seneca"), ("Seneca", "Stoicism"),
+            ("B", "C"), ("C", "D"), ("D", "E"), ("E", "B"),
+            ("Anger", "Sleep"),
+        ]);
+        let (cycles, tangles) = find_cycles(&graph, 2);
+        assert_eq!(chains(&cycles), [
+            "Anger → Seneca → Stoicism → Anger",
+            "B → C → D → E → B",
+        ]);
+        assert_eq!(tangles, [4, 3]);
+    }
+
+    #[test]
+    fn mutual_links_need_min_length_two() {
+        let graph = graph(&[("A", "B"), ("B", "A"), ("B", "C"), ("C", "A")]);
+        let (cycles, _) = find_cycles(&graph, 2);
+        assert_eq!(chains(&cycles), ["A → B → A"]);
+
+        // Skipping mutual links finds the longer loop through the same notes
+        let (cycles, tangles) = find_cycles(&graph, 3);
+        assert_eq!(chains(&cycles), ["A → B → C → A"]);
+        assert_eq!(tangles, [3]);
+    }
+
+    #[test]
+    fn note_reached_early_by_a_mutual_link_still_closes_a_longer_loop() {
+        // B is two notes from A via the mutual link, but three via C
+        let graph = graph(&[("A", "B"), ("B", "A"), ("A", "C"), ("C", "B")]);
+        let (cycles, _) = find_cycles(&graph, 3);
+        assert_eq!(chains(&cycles), ["A → C → B → A"]);
+    }
+
+    #[test]
+    fn self_links_and_chains_are_not_cycles() {
+        let graph = graph(&[("A", "A"), ("A", "B"), ("B", "C")]);
+        let (cycles, tangles) = find_cycles(&graph, 1);
+        assert!(cycles.is_empty());
+        assert!(tangles.is_empty());
+    }
+}
@@ -0,0 +1,68 @@
+use std::collections::{BTreeSet, HashSet};
+
+/// Daily note names (`2024-03-01`), used by `--collapse-daily` when no
+/// `--exclude-pattern` is given
+pub const DEFAULT_DAILY_PATTERN: &str = r"^\d{4}-\d{2}-\d{2}$";
+
+/// Weak edges between notes cited by the same daily note, for when the
+/// daily notes themselves are collapsed out of the graph.
+///
+/// `citations` holds, per daily note, the kept notes it links to. Each pair
+/// comes back once, ordered `(a, b)` with `a < b`, however many daily
+/// notes cite it. A note is never paired with itself, and pairs already
+/// linked directly (in either direction, per `direct`) are left out.
+pub fn co_citations<'a>(
+    citations: impl IntoIterator<Item = &'a [String]>,
+    direct: &HashSet<(String, String)>,
+) -> Vec<(String, String)> {
+    let mut pairs = BTreeSet::new();
+    for cited in citations {
+        let cited: BTreeSet<&str> = cited.iter().map(String::as_str).collect();
+        let cited: Vec<&str> = cited.into_iter().collect();
+        for (i, a) in cited.iter().enumerate() {
+            for b in &cited[i + 1..] {
+                if direct.contains(&(a.to_string(), b.to_string())) || direct.contains(&(b.to_string(), a.to_string())) {
+                    continue;
+                }
+                pairs.insert((a.to_string(), b.to_string()));
+            }
+        }
+    }
+    pairs.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|n| n.to_string()).collect()
+    }
+
+    fn pair(a: &str, b: &str) -> (String, String) {
+        (a.to_string(), b.to_string())
+    }
+
+    #[test]
+    fn pairs_each_co_cited_couple_once() {
+        let days = [names(&["Stoicism", "Anger"]), names(&["Anger", "Stoicism", "Sleep"])];
+        let inferred = co_citations(days.iter().map(Vec::as_slice), &HashSet::new());
+        assert_eq!(inferred, vec![pair("Anger", "Sleep"), pair("Anger", "Stoicism"), pair("Sleep", "Stoicism")]);
+    }
+
+    #[test]
+    fn never_pairs_a_note_with_itself() {
+        let days = [names(&["Anger", "Anger"]), names(&["Anger"])];
+        assert!(co_citations(days.iter().map(Vec::as_slice), &HashSet::new()).is_empty());
+
+        let days = [names(&["Anger", "Sleep", "Anger"])];
+        assert_eq!(co_citations(days.iter().map(Vec::as_slice), &HashSet::new()), vec![pair("Anger", "Sleep")]);
+    }
+
+    #[test]
+    fn skips_pairs_already_linked_either_way() {
+        let days = [names(&["A", "B", "C"])];
+        let direct: HashSet<_> = [pair("B", "A"), pair("B", "C")].into_iter().collect();
+        assert_eq!(co_citations(days.iter().map(Vec::as_slice), &direct), vec![pair("A", "C")]);
+    }
+}
@@ -0,0 +1,140 @@
+use chrono::{Datelike, NaiveDate};
+use std::collections::BTreeMap;
+
+/// Notes created in one calendar month, and the links they carry
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonthGrowth {
+    pub year: i32,
+    pub month: u32,
+    pub notes: usize,
+    pub links: usize,
+}
+
+/// Widest histogram bar, in characters
+const BAR_WIDTH: usize = 30;
+
+/// Bucket (creation date, outgoing links) pairs by month, oldest first. Months
+/// between the first and last with no new notes are included as zeros so
+/// quiet periods show up in the histogram.
+pub fn monthly(notes: impl IntoIterator<Item = (NaiveDate, usize)>) -> Vec<MonthGrowth> {
+    let mut buckets: BTreeMap<(i32, u32), (usize, usize)> = BTreeMap::new();
+    for (date, links) in notes {
+        let bucket = buckets.entry((date.year(), date.month())).or_default();
+        bucket.0 += 1;
+        bucket.1 += links;
+    }
+
+    let (Some(&first), Some(&last)) = (buckets.keys().next(), buckets.keys().next_back()) else {
+        return Vec::new();
+    };
+
+    let mut months = Vec::new();
+    let (mut year, mut month) = first;
+    while (year, month) <= last {
+        let (notes, links) = buckets.get(&(year, month)).copied().unwrap_or_default();
+        months.push(MonthGrowth { year, month, notes, links });
+        (year, month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+    }
+    months
+}
+
+/// Bar of `value` scaled against `max`; any non-zero value gets at least one block
+fn bar(value: usize, max: usize) -> String {
+    if value == 0 || max == 0 {
+        return String::new();
+    }
+    let len = ((value as f64 / max as f64) * BAR_WIDTH as f64).round().max(1.0) as usize;
+    "█".repeat(len)
+}
+
+/// Per-month histogram of notes created and links added, with running totals
+pub fn render_growth(months: &[MonthGrowth], undated: usize) -> String {
+    let mut out = String::new();
+    out.push_str("\n🌱 VAULT GROWTH (by `date created`)\n");
+    out.push_str("═══════════════════════════════════════════\n");
+
+    let max_notes = months.iter().map(|m| m.notes).max().unwrap_or(0);
+    let max_links = months.iter().map(|m| m.links).max().unwrap_or(0);
+    let (mut total_notes, mut total_links) = (0, 0);
+
+    out.push_str(&format!(
+        "{:<7}  {:>5} {:<width$}  {:>5} {:<width$}  {:>7}\n",
+        "Month", "Notes", "", "Links", "", "Total",
+        width = BAR_WIDTH
+    ));
+    for m in months {
+        total_notes += m.notes;
+        total_links += m.links;
+        out.push_str(&format!(
+            "{:04}-{:02}  {:>5} {:<width$}  {:>5} {:<width$}  {:>7}\n",
+            m.year,
+            m.month,
+            m.notes,
+            bar(m.notes, max_notes),
+            m.links,
+            bar(m.links, max_links),
+            total_notes,
+            width = BAR_WIDTH
+        ));
+    }
+
+    out.push_str("═══════════════════════════════════════════\n");
+    out.push_str(&format!(
+        "{} dated notes with {} links over {} months\n",
+        total_notes,
+        total_links,
+        months.len()
+    ));
+    if undated > 0 {
+        out.push_str(&format!("{} notes have no `date created` and are not counted\n", undated));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn buckets_by_month_and_fills_gaps() {
+        let months = monthly([
+            (date("2025-11-03"), 2),
+            (date("2026-02-10"), 5),
+            (date("2025-11-28"), 1),
+        ]);
+        let summary: Vec<(i32, u32, usize, usize)> =
+            months.iter().map(|m| (m.year, m.month, m.notes, m.links)).collect();
+        assert_eq!(summary, vec![
+            (2025, 11, 2, 3),
+            (2025, 12, 0, 0),
+            (2026, 1, 0, 0),
+            (2026, 2, 1, 5),
+        ]);
+    }
+
+    #[test]
+    fn no_dated_notes_means_no_months() {
+        assert!(monthly([]).is_empty());
+    }
+
+    #[test]
+    fn bars_scale_to_busiest_month() {
+        assert_eq!(bar(10, 10).chars().count(), BAR_WIDTH);
+        assert_eq!(bar(5, 10).chars().count(), BAR_WIDTH / 2);
+        assert_eq!(bar(1, 1000), "█");
+        assert_eq!(bar(0, 10), "");
+    }
+
+    #[test]
+    fn render_shows_running_total_and_undated() {
+        let out = render_growth(&monthly([(date("2026-01-05"), 4), (date("2026-02-01"), 0)]), 3);
+        assert!(out.lines().any(|l| l.starts_with("2026-01") && l.trim_end().ends_with(" 1")));
+        assert!(out.lines().any(|l| l.starts_with("2026-02") && l.trim_end().ends_with(" 2")));
+        assert!(out.contains("2 dated notes with 4 links over 2 months"));
+        assert!(out.contains("3 notes have no `date created`"));
+    }
+}
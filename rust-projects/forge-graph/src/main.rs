@@ -1,3 +1,10 @@
+mod cycles;
+mod daily;
+mod growth;
+mod metrics;
+mod suggest;
+mod watch;
+
 use clap::{Parser, Subcommand};
 use std::collections::{HashMap, HashSet};
 use std::fs;
@@ -5,7 +12,9 @@ use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 use regex::Regex;
 use petgraph::graph::{Graph, NodeIndex};
+use petgraph::unionfind::UnionFind;
 use anyhow::{Context, Result};
+use chrono::{Local, NaiveDate, NaiveDateTime};
 
 #[derive(Parser)]
 #[command(name = "forge-graph")]
@@ -13,6 +22,10 @@ use anyhow::{Context, Result};
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    /// Leave out notes whose name matches this regex, along with links to
+    /// them (e.g. daily notes: '^\d{4}-\d{2}-\d{2}$')
+    #[arg(long, global = true, value_name = "REGEX")]
+    exclude_pattern: Option<String>,
 }
 
 #[derive(Subcommand)]
@@ -21,6 +34,10 @@ enum Commands {
     Analyze {
         /// Path to vault directory
         vault_path: PathBuf,
+        /// Drop daily notes (or --exclude-pattern matches), linking the notes
+        /// each one cites instead; reports numbers with and without them
+        #[arg(long)]
+        collapse_daily: bool,
     },
     /// Find orphaned notes (no incoming links)
     Orphans {
@@ -40,6 +57,23 @@ enum Commands {
         /// Filter to apply: "all" (default) or "connected" (only nodes with links)
         #[arg(short, long, default_value = "all")]
         filter: String,
+        /// Node colouring: "orphan" (default) or "age" (fresh → stale gradient)
+        #[arg(long, default_value = "orphan")]
+        color_by: String,
+        /// Link scheme for double-clicking a node: "file" (default) or "obsidian"
+        #[arg(long, default_value = "file")]
+        uri_scheme: String,
+        /// Obsidian vault name (required for --uri-scheme obsidian)
+        #[arg(long)]
+        vault_name: Option<String>,
+        /// Drop daily notes (or --exclude-pattern matches), drawing dashed
+        /// links between the notes each one cites instead
+        #[arg(long)]
+        collapse_daily: bool,
+        /// Keep running, regenerating the output whenever a markdown note
+        /// in the vault changes
+        #[arg(long)]
+        watch: bool,
     },
     /// Show random orphans for daily connection work
     Daily {
@@ -57,6 +91,53 @@ enum Commands {
         #[arg(short, long, default_value_t = 20)]
         count: usize,
     },
+    /// Find neglected notes (not modified recently but still linked to)
+    Stale {
+        /// Path to vault directory
+        vault_path: PathBuf,
+        /// Minimum age in days since last modification
+        #[arg(short, long, default_value_t = 365)]
+        days: i64,
+        /// Number of notes to display
+        #[arg(short, long, default_value_t = 20)]
+        count: usize,
+    },
+    /// Find wikilinks whose target doesn't exist, grouped by source note
+    DeadLinks {
+        /// Path to vault directory
+        vault_path: PathBuf,
+        /// Number of source notes to display
+        #[arg(short, long, default_value_t = 20)]
+        count: usize,
+    },
+    /// Record today's vault metrics in ~/.local/share/forge-graph/metrics.csv
+    Snapshot {
+        /// Path to vault directory
+        vault_path: PathBuf,
+    },
+    /// Show recorded metrics with 7- and 30-day changes
+    Trend {
+        /// Number of snapshots to list
+        #[arg(short, long, default_value_t = 10)]
+        count: usize,
+    },
+    /// Histogram of notes created and links added per month, from `date created`
+    Growth {
+        /// Path to vault directory
+        vault_path: PathBuf,
+    },
+    /// Find circular chains of links (A → B → C → A), shortest first
+    Cycles {
+        /// Path to vault directory
+        vault_path: PathBuf,
+        /// Number of cycles to display
+        #[arg(short, long, default_value_t = 20)]
+        count: usize,
+        /// Fewest notes in a cycle; 2 includes mutual links (A ↔ B), which
+        /// `analyze` already counts as reciprocated
+        #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u16).range(2..))]
+        min_length: u16,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -64,25 +145,56 @@ struct Note {
     path: PathBuf,
     name: String,
     links: Vec<String>,
+    modified: Option<NaiveDateTime>, // Frontmatter "date modified", else file mtime
+    created: Option<NaiveDateTime>,  // Frontmatter "date created" only
+    tags: Vec<String>,               // Frontmatter tags, lowercased, no '#'
+}
+
+/// Headline vault-health numbers shown by `analyze`
+struct VaultStats {
+    total_notes: usize,
+    orphans: usize,
+    links: usize,
+    reciprocity: Option<f64>, // % of A→B links with a B→A counterpart
 }
 
 struct VaultGraph {
+    root: PathBuf,
     notes: HashMap<String, Note>,
+    attachments: HashSet<String>, // File names of non-markdown files, for dead-link checks
     graph: Graph<String, ()>,
     node_indices: HashMap<String, NodeIndex>,
+    inferred: Vec<(String, String)>, // Co-cited pairs from collapsed daily notes
 }
 
 impl VaultGraph {
-    fn new() -> Self {
+    fn new(root: PathBuf) -> Self {
         VaultGraph {
+            root,
             notes: HashMap::new(),
+            attachments: HashSet::new(),
             graph: Graph::new(),
             node_indices: HashMap::new(),
+            inferred: Vec::new(),
         }
     }
 
+    /// `parse_vault`, then drop notes matching `exclude` (see `exclude`)
+    fn load(vault_path: &Path, exclude: Option<&Regex>, collapse: bool) -> Result<Self> {
+        let mut vault = VaultGraph::parse_vault(vault_path)?;
+        if let Some(pattern) = exclude {
+            let excluded = vault.exclude(pattern, collapse);
+            if collapse {
+                println!("🗓️  Collapsed {} notes matching {} into {} inferred links", excluded, pattern, vault.inferred.len());
+            } else {
+                println!("🚫 Excluded {} notes matching {}", excluded, pattern);
+            }
+        }
+        Ok(vault)
+    }
+
     fn parse_vault<P: AsRef<Path>>(vault_path: P) -> Result<Self> {
-        let mut vault = VaultGraph::new();
+        let mut vault = VaultGraph::new(vault_path.as_ref().to_path_buf());
         let link_regex = Regex::new(r"!?\[\[([^\]]+)\]\]")?;
 
         println!("📖 Parsing vault...");
@@ -95,13 +207,18 @@ impl VaultGraph {
         {
             let path = entry.path();
 
-            // Skip non-markdown files and certain directories
-            if !path.is_file() || path.extension().and_then(|s| s.to_str()) != Some("md") {
+            // Skip certain directories and non-markdown files
+            if !path.is_file()
+                || path.to_string_lossy().contains(".git")
+                || path.to_string_lossy().contains(".obsidian") {
                 continue;
             }
 
-            if path.to_string_lossy().contains(".git")
-                || path.to_string_lossy().contains(".obsidian") {
+            if path.extension().and_then(|s| s.to_str()) != Some("md") {
+                // Remember attachments so links to them aren't reported dead
+                if let Some(file_name) = path.file_name().and_then(|s| s.to_str()) {
+                    vault.attachments.insert(file_name.to_string());
+                }
                 continue;
             }
 
@@ -142,10 +259,18 @@ impl VaultGraph {
             }
             let links: Vec<String> = links_set.into_iter().collect();
 
+            let modified = frontmatter_date(&content, "date modified")
+                .or_else(|| file_mtime(path));
+            let created = frontmatter_date(&content, "date created");
+            let tags = suggest::frontmatter_tags(&content);
+
             vault.notes.insert(name.clone(), Note {
                 path: path.to_path_buf(),
                 name: name.clone(),
                 links,
+                modified,
+                created,
+                tags,
             });
         }
 
@@ -153,37 +278,117 @@ impl VaultGraph {
 
         // Second pass: build graph
         println!("🔗 Building graph...");
+        vault.build_graph();
+        println!("✅ Graph built with {} edges", vault.graph.edge_count());
 
+        Ok(vault)
+    }
+
+    fn build_graph(&mut self) {
         // Create nodes for all notes
-        for name in vault.notes.keys() {
-            let idx = vault.graph.add_node(name.clone());
-            vault.node_indices.insert(name.clone(), idx);
+        for name in self.notes.keys() {
+            let idx = self.graph.add_node(name.clone());
+            self.node_indices.insert(name.clone(), idx);
         }
 
         // Add edges for links
-        for note in vault.notes.values() {
-            let source_idx = vault.node_indices.get(&note.name);
+        for note in self.notes.values() {
+            let source_idx = self.node_indices.get(&note.name);
 
             for link in &note.links {
                 // Try to find target note (with or without .md extension)
-                let target_name = if vault.notes.contains_key(link) {
+                let target_name = if self.notes.contains_key(link) {
                     link.clone()
-                } else if vault.notes.contains_key(&format!("{}.md", link)) {
+                } else if self.notes.contains_key(&format!("{}.md", link)) {
                     format!("{}.md", link)
                 } else {
                     // Link target doesn't exist as a note
                     continue;
                 };
 
-                if let (Some(&src), Some(&tgt)) = (source_idx, vault.node_indices.get(&target_name)) {
-                    vault.graph.add_edge(src, tgt, ());
+                if let (Some(&src), Some(&tgt)) = (source_idx, self.node_indices.get(&target_name)) {
+                    self.graph.add_edge(src, tgt, ());
                 }
             }
         }
+    }
 
-        println!("✅ Graph built with {} edges", vault.graph.edge_count());
+    /// Drop notes whose name matches `pattern`, and links pointing at them,
+    /// then rebuild the graph. With `collapse`, each pair of remaining
+    /// notes that a dropped note links to gets a weak inferred edge.
+    /// Returns how many notes were dropped.
+    fn exclude(&mut self, pattern: &Regex, collapse: bool) -> usize {
+        let excluded: HashSet<String> = self.notes.keys()
+            .filter(|name| pattern.is_match(name))
+            .cloned()
+            .collect();
+        // Same lookup as build_graph, plus path-style links by final component
+        let points_at_excluded = |link: &str| {
+            let file_name = link.rsplit('/').next().unwrap_or(link);
+            let stem = file_name.strip_suffix(".md").unwrap_or(file_name);
+            excluded.contains(link) || excluded.contains(&format!("{}.md", link)) || excluded.contains(stem)
+        };
 
-        Ok(vault)
+        if collapse {
+            let direct: HashSet<(String, String)> = self.graph.raw_edges()
+                .iter()
+                .map(|e| (self.graph[e.source()].clone(), self.graph[e.target()].clone()))
+                .collect();
+            let citations: Vec<Vec<String>> = excluded.iter()
+                .map(|name| {
+                    self.notes[name].links.iter()
+                        .filter(|link| !points_at_excluded(link))
+                        .filter_map(|link| {
+                            [link.clone(), format!("{}.md", link)]
+                                .into_iter()
+                                .find(|target| self.notes.contains_key(target))
+                        })
+                        .collect()
+                })
+                .collect();
+            self.inferred = daily::co_citations(citations.iter().map(Vec::as_slice), &direct);
+        }
+
+        self.notes.retain(|name, _| !excluded.contains(name));
+        for note in self.notes.values_mut() {
+            note.links.retain(|link| !points_at_excluded(link));
+        }
+
+        self.graph = Graph::new();
+        self.node_indices.clear();
+        self.build_graph();
+        excluded.len()
+    }
+
+    /// Whether a link target exists as a note or attachment. Path-style
+    /// links (`folder/Note`) resolve by their final component.
+    fn link_resolves(&self, link: &str) -> bool {
+        let file_name = link.rsplit('/').next().unwrap_or(link);
+        let stem = file_name.strip_suffix(".md").unwrap_or(file_name);
+        self.notes.contains_key(link)
+            || self.notes.contains_key(&format!("{}.md", link))
+            || self.notes.contains_key(stem)
+            || self.attachments.contains(file_name)
+    }
+
+    /// Notes with links to nonexistent targets, most dead links first
+    fn find_dead_links(&self) -> Vec<(&Note, Vec<&str>)> {
+        let mut dead: Vec<_> = self.notes.values()
+            .filter_map(|note| {
+                let mut targets: Vec<&str> = note.links.iter()
+                    .map(String::as_str)
+                    .filter(|link| !self.link_resolves(link))
+                    .collect();
+                if targets.is_empty() {
+                    return None;
+                }
+                targets.sort_unstable();
+                Some((note, targets))
+            })
+            .collect();
+
+        dead.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then(a.0.name.cmp(&b.0.name)));
+        dead
     }
 
     fn find_orphans(&self) -> Vec<String> {
@@ -205,6 +410,32 @@ impl VaultGraph {
             .collect()
     }
 
+    /// Number of incoming edges per note
+    fn in_degrees(&self) -> HashMap<String, usize> {
+        let mut degrees: HashMap<String, usize> = HashMap::new();
+        for edge in self.graph.raw_edges() {
+            *degrees.entry(self.graph[edge.target()].clone()).or_insert(0) += 1;
+        }
+        degrees
+    }
+
+    /// Notes older than `days` that something still links to, most-linked first
+    fn find_stale(&self, days: i64) -> Vec<(&Note, usize, i64)> {
+        let now = Local::now().naive_local();
+        let in_degrees = self.in_degrees();
+
+        let mut stale: Vec<_> = self.notes.values()
+            .filter_map(|note| {
+                let age = (now - note.modified?).num_days();
+                let in_degree = *in_degrees.get(&note.name)?;
+                (age > days).then_some((note, in_degree, age))
+            })
+            .collect();
+
+        stale.sort_by(|a, b| b.1.cmp(&a.1).then(b.2.cmp(&a.2)).then(a.0.name.cmp(&b.0.name)));
+        stale
+    }
+
     fn compute_layout(&self) -> HashMap<String, (f64, f64)> {
         use std::collections::HashMap;
         use std::f64::consts::PI;
@@ -315,33 +546,140 @@ impl VaultGraph {
         positions
     }
 
+    /// Monthly growth from notes' `date created`, counting each note's
+    /// resolved outgoing links as added when the note was created. Also
+    /// returns how many notes have no creation date.
+    fn growth(&self) -> (Vec<growth::MonthGrowth>, usize) {
+        let mut out_degrees: HashMap<&str, usize> = HashMap::new();
+        for edge in self.graph.raw_edges() {
+            *out_degrees.entry(self.graph[edge.source()].as_str()).or_insert(0) += 1;
+        }
+
+        let dated: Vec<(NaiveDate, usize)> = self.notes.values()
+            .filter_map(|note| {
+                let created = note.created?.date();
+                Some((created, out_degrees.get(note.name.as_str()).copied().unwrap_or(0)))
+            })
+            .collect();
+        let undated = self.notes.len() - dated.len();
+        (growth::monthly(dated), undated)
+    }
+
+    fn stats(&self) -> VaultStats {
+        VaultStats {
+            total_notes: self.notes.len(),
+            orphans: self.find_orphans().len(),
+            links: self.graph.edge_count(),
+            reciprocity: self.reciprocity(),
+        }
+    }
+
+    /// Size of the largest weakly connected component (link direction ignored)
+    fn largest_component(&self) -> usize {
+        let mut components = UnionFind::new(self.graph.node_count());
+        for edge in self.graph.raw_edges() {
+            components.union(edge.source().index(), edge.target().index());
+        }
+        let mut sizes: HashMap<usize, usize> = HashMap::new();
+        for label in components.into_labeling() {
+            *sizes.entry(label).or_insert(0) += 1;
+        }
+        sizes.into_values().max().unwrap_or(0)
+    }
+
+    /// Metrics row for `snapshot`; average degree counts links in and out
+    fn snapshot(&self, date: NaiveDate) -> metrics::Snapshot {
+        let stats = self.stats();
+        let avg_degree = if stats.total_notes == 0 {
+            0.0
+        } else {
+            2.0 * stats.links as f64 / stats.total_notes as f64
+        };
+        metrics::Snapshot {
+            date,
+            total_notes: stats.total_notes,
+            orphans: stats.orphans,
+            edges: stats.links,
+            largest_component: self.largest_component(),
+            avg_degree,
+        }
+    }
+
+    /// Percentage of distinct A→B links (A ≠ B) where B→A also exists,
+    /// or None when the vault has no such links
+    fn reciprocity(&self) -> Option<f64> {
+        let pairs: HashSet<(NodeIndex, NodeIndex)> = self.graph.raw_edges()
+            .iter()
+            .map(|e| (e.source(), e.target()))
+            .filter(|(a, b)| a != b)
+            .collect();
+        if pairs.is_empty() {
+            return None;
+        }
+        let reciprocated = pairs.iter().filter(|(a, b)| pairs.contains(&(*b, *a))).count();
+        Some(reciprocated as f64 / pairs.len() as f64 * 100.0)
+    }
+
     fn analyze(&self) {
-        let orphans = self.find_orphans();
-        let total = self.notes.len();
-        let connected = total - orphans.len();
-        let orphan_pct = (orphans.len() as f64 / total as f64) * 100.0;
+        let stats = self.stats();
+        let connected = stats.total_notes - stats.orphans;
+        let orphan_pct = (stats.orphans as f64 / stats.total_notes as f64) * 100.0;
 
         println!("\n📊 VAULT ANALYSIS");
         println!("═══════════════════════════════════════════");
-        println!("Total notes:        {}", total);
+        println!("Total notes:        {}", stats.total_notes);
         println!("Connected notes:    {} ({:.1}%)", connected, 100.0 - orphan_pct);
-        println!("Orphaned notes:     {} ({:.1}%)", orphans.len(), orphan_pct);
-        println!("Total links:        {}", self.graph.edge_count());
+        println!("Orphaned notes:     {} ({:.1}%)", stats.orphans, orphan_pct);
+        println!("Total links:        {}", stats.links);
+        match stats.reciprocity {
+            Some(pct) => println!("Reciprocated links: {:.1}%", pct),
+            None => println!("Reciprocated links: n/a"),
+        }
         println!("═══════════════════════════════════════════\n");
     }
 
-    fn generate_html_viz<P: AsRef<Path>>(&self, output_path: P, filter: &str) -> Result<()> {
+    /// `analyze` after `exclude`: the full vault's numbers (`raw`) beside
+    /// what's left, described by `label`
+    fn analyze_against(&self, raw: &VaultStats, label: &str, collapsed: bool) {
+        let inferred = collapsed.then_some(self.inferred.len());
+        print!("{}", render_comparison(raw, &self.stats(), inferred, label));
+    }
+
+    fn generate_html_viz<P: AsRef<Path>>(&self, output_path: P, filter: &str, color_by: &str, opener: &NoteOpener) -> Result<()> {
+        let html = self.render_html_viz(filter, color_by, opener)?;
+        fs::write(output_path.as_ref(), html)
+            .context("Failed to write HTML file")?;
+
+        Ok(())
+    }
+
+    /// Path of a note relative to the vault root, always '/'-separated
+    fn relative_path(&self, note: &Note) -> String {
+        let rel = note.path.strip_prefix(&self.root).unwrap_or(&note.path);
+        rel.components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    fn render_html_viz(&self, filter: &str, color_by: &str, opener: &NoteOpener) -> Result<String> {
         use serde_json::json;
 
+        let by_age = color_by == "age";
+        let now = Local::now().naive_local();
+
         println!("🧮 Computing layout positions in Rust (this will be fast!)...");
 
         let orphans_set: HashSet<String> = self.find_orphans().into_iter().collect();
+        let co_cited: HashSet<&str> = self.inferred.iter()
+            .flat_map(|(a, b)| [a.as_str(), b.as_str()])
+            .collect();
 
         // Determine which nodes to include based on filter
         let nodes_to_include: HashSet<String> = if filter == "connected" {
             println!("🔍 Filtering to show only connected notes...");
             self.notes.keys()
-                .filter(|name| !orphans_set.contains(*name))
+                .filter(|name| !orphans_set.contains(*name) || co_cited.contains(name.as_str()))
                 .cloned()
                 .collect()
         } else {
@@ -362,13 +700,31 @@ impl VaultGraph {
             let is_orphan = orphans_set.contains(name);
             let (x, y) = positions.get(name).unwrap_or(&(0.0, 0.0));
 
+            let (color, title) = if by_age {
+                let age = self.notes[name].modified.map(|m| (now - m).num_days());
+                let detail = match age {
+                    Some(days) => format!("Modified {} days ago", days),
+                    None => "Modification date unknown".to_string(),
+                };
+                (age_color(age), format!("{}\n{}", name, detail))
+            } else {
+                let color = if is_orphan { "#ff6b6b" } else { "#4ecdc4" };
+                (color.to_string(), format!("{}\n{}", name, if is_orphan { "Orphan (no incoming links)" } else { "Connected" }))
+            };
+
+            let note = &self.notes[name];
+            let path = self.relative_path(note);
+            let url = opener.url(&path, &note.path);
+
             nodes.push(json!({
                 "id": name,
                 "label": name,
                 "x": x,
                 "y": y,
-                "color": if is_orphan { "#ff6b6b" } else { "#4ecdc4" },
-                "title": format!("{}\n{}", name, if is_orphan { "Orphan (no incoming links)" } else { "Connected" })
+                "color": color,
+                "title": title,
+                "path": path,
+                "url": url
             }));
         }
 
@@ -387,6 +743,18 @@ impl VaultGraph {
                 }));
             }
         }
+        for (a, b) in &self.inferred {
+            if nodes_to_include.contains(a) && nodes_to_include.contains(b) {
+                edges.push(json!({
+                    "from": a,
+                    "to": b,
+                    "inferred": true,
+                    "dashes": true,
+                    "color": { "color": "#b0b0b0", "opacity": 0.4 },
+                    "title": "Cited together in a daily note"
+                }));
+            }
+        }
 
         println!("✅ {} edges included", edges.len());
 
@@ -395,6 +763,11 @@ impl VaultGraph {
             "edges": edges
         });
 
+        let mut legend = String::from(if by_age { AGE_LEGEND } else { ORPHAN_LEGEND });
+        if !self.inferred.is_empty() {
+            legend.push_str(INFERRED_LEGEND);
+        }
+
         // Generate HTML with embedded vis.js
         let html = format!(r#"<!DOCTYPE html>
 <html>
@@ -427,17 +800,18 @@ impl VaultGraph {
         <div class="stat">📄 Showing: {}</div>
         <div class="stat">🔗 Links: {}</div>
         <div class="stat">📊 Total: {}</div>
-        <div class="legend">
-            <div class="legend-item"><span class="color-box" style="background: #4ecdc4;"></span> Connected</div>
-            <div class="legend-item"><span class="color-box" style="background: #ff6b6b;"></span> Orphan</div>
-        </div>
+        <div class="legend">{}</div>
     </div>
     <div id="mynetwork"></div>
     <script type="text/javascript">
         const graphData = {};
 
         const container = document.getElementById('mynetwork');
-        const data = graphData;
+        // DataSets, so the handlers below can look nodes up and restyle them
+        const data = {{
+            nodes: new vis.DataSet(graphData.nodes),
+            edges: new vis.DataSet(graphData.edges)
+        }};
         const options = {{
             nodes: {{
                 shape: 'dot',
@@ -489,6 +863,16 @@ impl VaultGraph {
             }}
         }});
 
+        // Open the note on double-click
+        network.on('doubleClick', function(params) {{
+            if (params.nodes.length > 0) {{
+                const node = data.nodes.get(params.nodes[0]);
+                if (node && node.url) {{
+                    window.location.href = node.url;
+                }}
+            }}
+        }});
+
         // Reset on background click
         network.on('deselectNode', function() {{
             const allNodes = data.nodes.get({{ returnType: 'Array' }});
@@ -505,27 +889,189 @@ impl VaultGraph {
             nodes.len(),
             edges.len(),
             self.notes.len(),
-            serde_json::to_string(&graph_data)?
+            legend,
+            // Note names can contain "</script>"; keep them from closing the tag
+            serde_json::to_string(&graph_data)?.replace("</", "<\\/")
         );
 
-        fs::write(output_path.as_ref(), html)
-            .context("Failed to write HTML file")?;
+        Ok(html)
+    }
+}
 
-        Ok(())
+/// Side-by-side `analyze` table for the whole vault and a filtered one
+fn render_comparison(raw: &VaultStats, filtered: &VaultStats, inferred: Option<usize>, label: &str) -> String {
+    let share = |n: usize, total: usize| {
+        let pct = if total == 0 { 0.0 } else { n as f64 / total as f64 * 100.0 };
+        format!("{} ({:.1}%)", n, pct)
+    };
+    let reciprocity = |stats: &VaultStats| stats.reciprocity.map_or("n/a".to_string(), |pct| format!("{:.1}%", pct));
+    let mut rows = vec![
+        ("Total notes:", raw.total_notes.to_string(), filtered.total_notes.to_string()),
+        (
+            "Connected notes:",
+            share(raw.total_notes - raw.orphans, raw.total_notes),
+            share(filtered.total_notes - filtered.orphans, filtered.total_notes),
+        ),
+        ("Orphaned notes:", share(raw.orphans, raw.total_notes), share(filtered.orphans, filtered.total_notes)),
+        ("Total links:", raw.links.to_string(), filtered.links.to_string()),
+        ("Reciprocated links:", reciprocity(raw), reciprocity(filtered)),
+    ];
+    if let Some(inferred) = inferred {
+        rows.push(("Inferred links:", "-".to_string(), inferred.to_string()));
+    }
+
+    let mut out = format!("\n📊 VAULT ANALYSIS ({})\n", label);
+    out.push_str("═══════════════════════════════════════════\n");
+    out.push_str(&format!("{:<20}{:<18}{}\n", "", "All notes", "Filtered"));
+    for (name, all, kept) in rows {
+        out.push_str(&format!("{:<20}{:<18}{}\n", name, all, kept));
+    }
+    out.push_str("═══════════════════════════════════════════\n\n");
+    out
+}
+
+/// The notes `--exclude-pattern` drops; `--collapse-daily` alone means
+/// daily notes
+fn exclusion(exclude: Option<&Regex>, collapse_daily: bool) -> Option<Regex> {
+    match exclude {
+        Some(pattern) => Some(pattern.clone()),
+        None if collapse_daily => Some(Regex::new(daily::DEFAULT_DAILY_PATTERN).expect("valid daily pattern")),
+        None => None,
+    }
+}
+
+/// How a double-clicked node in the HTML viz opens its note
+enum NoteOpener {
+    File,
+    Obsidian { vault: String },
+}
+
+impl NoteOpener {
+    fn new(scheme: &str, vault_name: Option<String>) -> Self {
+        match (scheme, vault_name) {
+            ("obsidian", Some(vault)) => NoteOpener::Obsidian { vault },
+            ("obsidian", None) => {
+                eprintln!("⚠️  --uri-scheme obsidian needs --vault-name; using file:// links");
+                NoteOpener::File
+            }
+            _ => NoteOpener::File,
+        }
     }
+
+    /// URI for a note, given its vault-relative and on-disk paths
+    fn url(&self, relative: &str, path: &Path) -> String {
+        match self {
+            NoteOpener::Obsidian { vault } => {
+                let file = relative.strip_suffix(".md").unwrap_or(relative);
+                format!("obsidian://open?vault={}&file={}", uri_encode(vault, false), uri_encode(file, false))
+            }
+            NoteOpener::File => {
+                let absolute = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+                format!("file://{}", uri_encode(&absolute.to_string_lossy(), true))
+            }
+        }
+    }
+}
+
+/// Percent-encode everything outside RFC 3986 unreserved characters,
+/// optionally leaving '/' intact for path URLs
+fn uri_encode(s: &str, keep_slash: bool) -> String {
+    let mut out = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => out.push(byte as char),
+            b'/' if keep_slash => out.push('/'),
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+const ORPHAN_LEGEND: &str = r#"
+            <div class="legend-item"><span class="color-box" style="background: #4ecdc4;"></span> Connected</div>
+            <div class="legend-item"><span class="color-box" style="background: #ff6b6b;"></span> Orphan</div>
+        "#;
+
+const AGE_LEGEND: &str = r#"
+            <div class="legend-item"><span class="color-box" style="background: linear-gradient(to right, #4ecdc4, #b08d57);"></span> Fresh → 2+ years</div>
+            <div class="legend-item"><span class="color-box" style="background: #cccccc;"></span> Date unknown</div>
+        "#;
+
+const INFERRED_LEGEND: &str = r#"
+            <div class="legend-item"><span style="display: inline-block; width: 15px; margin-right: 5px; border-top: 2px dashed #b0b0b0; vertical-align: middle;"></span> Cited together in a daily note</div>
+        "#;
+
+/// Age at which the viz gradient bottoms out
+const AGE_HORIZON_DAYS: f64 = 730.0;
+
+/// Colour on a fresh (teal) to stale (faded brown) gradient
+fn age_color(age_days: Option<i64>) -> String {
+    let Some(days) = age_days else {
+        return "#cccccc".to_string();
+    };
+    let t = (days.max(0) as f64 / AGE_HORIZON_DAYS).min(1.0);
+    let lerp = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+    format!("#{:02x}{:02x}{:02x}", lerp(0x4e, 0xb0), lerp(0xcd, 0x8d), lerp(0xc4, 0x57))
+}
+
+/// Read a date field from YAML frontmatter, e.g. `date modified: 2024-03-01 14:30`
+fn frontmatter_date(content: &str, field: &str) -> Option<NaiveDateTime> {
+    let rest = content.strip_prefix("---")?;
+    let end = rest.find("\n---")?;
+    rest[..end].lines()
+        .find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            if key.trim() == field { parse_note_date(value) } else { None }
+        })
+}
+
+/// Parse the vault's "%Y-%m-%d %H:%M" convention, tolerating seconds,
+/// ISO `T` separators, quotes, and bare dates
+fn parse_note_date(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+    ["%Y-%m-%d %H:%M", "%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%dT%H:%M:%S"]
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(value, fmt).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(value.get(..10)?, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+        })
+}
+
+fn file_mtime(path: &Path) -> Option<NaiveDateTime> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    Some(chrono::DateTime::<Local>::from(modified).naive_local())
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    let exclude = cli.exclude_pattern.as_deref()
+        .map(Regex::new)
+        .transpose()
+        .context("Invalid --exclude-pattern")?;
 
     match cli.command {
-        Commands::Analyze { vault_path } => {
-            let vault = VaultGraph::parse_vault(&vault_path)?;
-            vault.analyze();
+        Commands::Analyze { vault_path, collapse_daily } => {
+            let mut vault = VaultGraph::parse_vault(&vault_path)?;
+            match exclusion(exclude.as_ref(), collapse_daily) {
+                Some(pattern) => {
+                    let raw = vault.stats();
+                    let excluded = vault.exclude(&pattern, collapse_daily);
+                    let label = format!(
+                        "{} {} notes matching {}",
+                        if collapse_daily { "collapsing" } else { "excluding" },
+                        excluded,
+                        pattern
+                    );
+                    vault.analyze_against(&raw, &label, collapse_daily);
+                }
+                None => vault.analyze(),
+            }
         }
 
         Commands::Orphans { vault_path, count } => {
-            let vault = VaultGraph::parse_vault(&vault_path)?;
+            let vault = VaultGraph::load(&vault_path, exclude.as_ref(), false)?;
             let orphans = vault.find_orphans();
 
             println!("\n🔍 ORPHANED NOTES (showing {} of {})",
@@ -539,52 +1085,65 @@ fn main() -> Result<()> {
         }
 
         Commands::Daily { vault_path, count } => {
-            let vault = VaultGraph::parse_vault(&vault_path)?;
+            let vault = VaultGraph::load(&vault_path, exclude.as_ref(), false)?;
             let mut orphans = vault.find_orphans();
 
             // Shuffle for randomness
             use std::collections::hash_map::RandomState;
-            use std::hash::{BuildHasher, Hash, Hasher};
+            use std::hash::{BuildHasher, Hasher};
             let seed = RandomState::new().build_hasher().finish();
             orphans.sort_by_cached_key(|name| {
-                let mut hasher = RandomState::new().build_hasher();
-                name.hash(&mut hasher);
-                hasher.finish().wrapping_add(seed)
+                RandomState::new().hash_one(name).wrapping_add(seed)
             });
 
             println!("\n📝 TODAY'S CONNECTION OPPORTUNITIES");
             println!("═══════════════════════════════════════════");
             println!("Work on connecting these {} orphaned notes:\n", count);
 
+            let suggester = suggest::Suggester::new(
+                vault.notes.values().map(|note| (note.name.as_str(), note.tags.as_slice())),
+            );
             for (i, name) in orphans.iter().take(count).enumerate() {
                 if let Some(note) = vault.notes.get(name) {
                     println!("{}. {}", i + 1, name);
                     println!("   Path: {}", note.path.display());
+                    for suggestion in suggester.suggest(name, suggest::SUGGESTIONS_PER_ORPHAN) {
+                        println!("   → [[{}]] ({})", suggestion.name, suggestion.reason());
+                    }
                     println!();
                 }
             }
         }
 
-        Commands::Viz { vault_path, output, filter } => {
-            let vault = VaultGraph::parse_vault(&vault_path)?;
-            println!("\n🎨 Generating HTML visualization...");
-
-            vault.generate_html_viz(&output, &filter)?;
-
-            println!("✅ Interactive graph saved to: {}", output.display());
+        Commands::Viz { vault_path, output, filter, color_by, uri_scheme, vault_name, collapse_daily, watch } => {
+            let pattern = exclusion(exclude.as_ref(), collapse_daily);
+            let opener = NoteOpener::new(&uri_scheme, vault_name);
+            let generate = || -> Result<()> {
+                let vault = VaultGraph::load(&vault_path, pattern.as_ref(), collapse_daily)?;
+                println!("\n🎨 Generating HTML visualization...");
+                vault.generate_html_viz(&output, &filter, &color_by, &opener)?;
+                println!("✅ Interactive graph saved to: {}", output.display());
+                Ok(())
+            };
+
+            generate()?;
             println!("\n💡 Open in browser:");
             println!("   open {}", output.display());
+
+            if watch {
+                watch::watch(&vault_path, generate)?;
+            }
         }
 
         Commands::Hubs { vault_path, count } => {
-            let vault = VaultGraph::parse_vault(&vault_path)?;
+            let vault = VaultGraph::load(&vault_path, exclude.as_ref(), false)?;
 
             // Find notes with most outgoing links
             let mut hubs: Vec<_> = vault.notes.values()
                 .map(|note| (note.name.clone(), note.links.len(), note.path.clone()))
                 .collect();
 
-            hubs.sort_by(|a, b| b.1.cmp(&a.1)); // Sort by link count descending
+            hubs.sort_by_key(|hub| std::cmp::Reverse(hub.1)); // Sort by link count descending
 
             println!("\n🌟 HUB NOTES (notes with most outgoing links)");
             println!("═══════════════════════════════════════════");
@@ -611,7 +1170,323 @@ fn main() -> Result<()> {
                 println!();
             }
         }
+
+        Commands::Stale { vault_path, days, count } => {
+            let vault = VaultGraph::load(&vault_path, exclude.as_ref(), false)?;
+            let stale = vault.find_stale(days);
+
+            println!("\n🕸️  STALE NOTES (untouched for {}+ days, still linked)", days);
+            println!("═══════════════════════════════════════════");
+            println!("Showing {} of {}:\n", count.min(stale.len()), stale.len());
+
+            for (i, (note, in_degree, age)) in stale.iter().take(count).enumerate() {
+                println!("{}. {} ← {} links, {} days old", i + 1, note.name, in_degree, age);
+                println!("   Path: {}", note.path.display());
+                println!();
+            }
+        }
+
+        Commands::DeadLinks { vault_path, count } => {
+            let vault = VaultGraph::load(&vault_path, exclude.as_ref(), false)?;
+            let dead = vault.find_dead_links();
+            let total: usize = dead.iter().map(|(_, targets)| targets.len()).sum();
+
+            println!("\n💀 DEAD LINKS ({} in {} notes)", total, dead.len());
+            println!("═══════════════════════════════════════════");
+            println!("Showing {} of {} notes:\n", count.min(dead.len()), dead.len());
+
+            for (i, (note, targets)) in dead.iter().take(count).enumerate() {
+                println!("{}. {} → {} dead", i + 1, note.name, targets.len());
+                println!("   Path: {}", note.path.display());
+                for target in targets {
+                    println!("   ✗ [[{}]]", target);
+                }
+                println!();
+            }
+        }
+
+        Commands::Snapshot { vault_path } => {
+            let vault = VaultGraph::load(&vault_path, exclude.as_ref(), false)?;
+            let snapshot = vault.snapshot(Local::now().date_naive());
+            let path = metrics::default_path()?;
+            let replaced = metrics::record(&path, snapshot.clone())?;
+
+            println!(
+                "\n📸 {} snapshot for {}: {} notes, {} orphans, {} links, largest component {}, avg degree {:.2}",
+                if replaced { "Updated" } else { "Recorded" },
+                snapshot.date,
+                snapshot.total_notes,
+                snapshot.orphans,
+                snapshot.edges,
+                snapshot.largest_component,
+                snapshot.avg_degree,
+            );
+            println!("   {}", path.display());
+        }
+
+        Commands::Trend { count } => {
+            let path = metrics::default_path()?;
+            let history = metrics::read_history(&path)?;
+            if history.is_empty() {
+                println!("No snapshots yet in {}; run `forge-graph snapshot <vault>` first", path.display());
+            } else {
+                print!("{}", metrics::render_trend(&history, count));
+            }
+        }
+
+        Commands::Growth { vault_path } => {
+            let vault = VaultGraph::load(&vault_path, exclude.as_ref(), false)?;
+            let (months, undated) = vault.growth();
+            if months.is_empty() {
+                println!("\nNo notes with a `date created` field in {}", vault_path.display());
+            } else {
+                print!("{}", growth::render_growth(&months, undated));
+            }
+        }
+
+        Commands::Cycles { vault_path, count, min_length } => {
+            let vault = VaultGraph::load(&vault_path, exclude.as_ref(), false)?;
+            let (cycles, tangles) = cycles::find_cycles(&vault.graph, min_length as usize);
+
+            println!("\n🔄 CYCLES ({}+ notes, shortest first)", min_length);
+            println!("═══════════════════════════════════════════");
+            println!("Showing {} of {}, from {} tangled clusters:\n", count.min(cycles.len()), cycles.len(), tangles.len());
+
+            for (i, cycle) in cycles.iter().take(count).enumerate() {
+                println!("{}. {} ({} notes)", i + 1, cycle.chain(), cycle.notes.len());
+            }
+            if let Some(largest) = tangles.first() {
+                println!("\nLargest tangle: {} notes that can all reach each other", largest);
+            }
+            println!();
+        }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(y: i32, m: u32, d: u32, h: u32, min: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap().and_hms_opt(h, min, 0).unwrap()
+    }
+
+    #[test]
+    fn parses_vault_date_convention() {
+        assert_eq!(parse_note_date("2024-03-01 14:30"), Some(at(2024, 3, 1, 14, 30)));
+    }
+
+    #[test]
+    fn parses_date_fallbacks() {
+        assert_eq!(parse_note_date("2024-03-01 14:30:15").map(|d| d.date()), Some(at(2024, 3, 1, 0, 0).date()));
+        assert_eq!(parse_note_date("2024-03-01T14:30"), Some(at(2024, 3, 1, 14, 30)));
+        assert_eq!(parse_note_date("\"2024-03-01 14:30\""), Some(at(2024, 3, 1, 14, 30)));
+        assert_eq!(parse_note_date("2024-03-01"), Some(at(2024, 3, 1, 0, 0)));
+        assert_eq!(parse_note_date("not a date"), None);
+        assert_eq!(parse_note_date(""), None);
+    }
+
+    #[test]
+    fn reads_field_from_frontmatter() {
+        let content = "---\ndate created: 2020-01-01 09:00\ndate modified: 2024-03-01 14:30\n---\nBody";
+        assert_eq!(frontmatter_date(content, "date modified"), Some(at(2024, 3, 1, 14, 30)));
+        assert_eq!(frontmatter_date(content, "date created"), Some(at(2020, 1, 1, 9, 0)));
+        assert_eq!(frontmatter_date(content, "updated"), None);
+    }
+
+    #[test]
+    fn ignores_fields_outside_frontmatter() {
+        assert_eq!(frontmatter_date("date modified: 2024-03-01 14:30\n", "date modified"), None);
+        assert_eq!(frontmatter_date("---\ntitle: x\n---\ndate modified: 2024-03-01 14:30", "date modified"), None);
+    }
+
+    #[test]
+    fn age_gradient_endpoints() {
+        assert_eq!(age_color(Some(0)), "#4ecdc4");
+        assert_eq!(age_color(Some(5000)), "#b08d57");
+        assert_eq!(age_color(None), "#cccccc");
+    }
+
+    fn vault_from_links(links: &[(&str, &[&str])]) -> VaultGraph {
+        let mut vault = VaultGraph::new(PathBuf::from("/vault"));
+        for (name, targets) in links {
+            vault.notes.insert(name.to_string(), Note {
+                path: PathBuf::from(format!("/vault/{}.md", name)),
+                name: name.to_string(),
+                links: targets.iter().map(|t| t.to_string()).collect(),
+                modified: None,
+                created: None,
+                tags: Vec::new(),
+            });
+        }
+        vault.build_graph();
+        vault
+    }
+
+    #[test]
+    fn dead_links_grouped_by_source() {
+        let mut vault = vault_from_links(&[
+            ("A", &["B", "Missing", "Typo Note"]),
+            ("B", &["folder/A", "diagram.png", "Gone.md"]),
+            ("C", &["A"]),
+        ]);
+        vault.attachments.insert("diagram.png".to_string());
+
+        let dead = vault.find_dead_links();
+        let summary: Vec<(&str, Vec<&str>)> = dead.iter()
+            .map(|(note, targets)| (note.name.as_str(), targets.clone()))
+            .collect();
+        assert_eq!(summary, vec![
+            ("A", vec!["Missing", "Typo Note"]),
+            ("B", vec!["Gone.md"]),
+        ]);
+    }
+
+    #[test]
+    fn reciprocity_counts_mutual_links() {
+        // A↔B mutual, A→C and C→D one-way, D→D self-link ignored
+        let vault = vault_from_links(&[
+            ("A", &["B", "C"]),
+            ("B", &["A"]),
+            ("C", &["D"]),
+            ("D", &["D"]),
+        ]);
+        assert_eq!(vault.reciprocity(), Some(50.0));
+        assert_eq!(vault.stats().links, 5);
+    }
+
+    #[test]
+    fn largest_component_ignores_direction() {
+        let vault = vault_from_links(&[
+            ("a", &["b"]), ("b", &[]), ("c", &["b"]), ("d", &["e"]), ("e", &[]), ("f", &[]),
+        ]);
+        assert_eq!(vault.largest_component(), 3);
+        let snapshot = vault.snapshot(NaiveDate::from_ymd_opt(2026, 3, 1).unwrap());
+        assert_eq!(snapshot.edges, 3);
+        assert_eq!(snapshot.avg_degree, 1.0);
+    }
+
+    #[test]
+    fn growth_attributes_links_to_creation_month() {
+        let mut vault = vault_from_links(&[
+            ("A", &["B", "C", "Missing"]),
+            ("B", &["A"]),
+            ("C", &[]),
+            ("D", &["A"]),
+        ]);
+        for (name, created) in [("A", at(2026, 1, 5, 9, 0)), ("B", at(2026, 1, 20, 9, 0)), ("C", at(2026, 3, 2, 9, 0))] {
+            vault.notes.get_mut(name).unwrap().created = Some(created);
+        }
+
+        let (months, undated) = vault.growth();
+        let summary: Vec<(u32, usize, usize)> = months.iter().map(|m| (m.month, m.notes, m.links)).collect();
+        // A's dead link isn't counted; D has no creation date
+        assert_eq!(summary, vec![(1, 2, 3), (2, 0, 0), (3, 1, 0)]);
+        assert_eq!(undated, 1);
+    }
+
+    #[test]
+    fn reciprocity_undefined_without_links() {
+        let vault = vault_from_links(&[("A", &[]), ("B", &["missing"])]);
+        assert_eq!(vault.reciprocity(), None);
+    }
+
+    fn daily_pattern() -> Regex {
+        Regex::new(daily::DEFAULT_DAILY_PATTERN).unwrap()
+    }
+
+    #[test]
+    fn exclusion_drops_notes_and_links_to_them() {
+        let mut vault = vault_from_links(&[
+            ("2026-03-01", &["Anger", "Sleep"]),
+            ("Anger", &["Sleep", "journal/2026-03-01", "2026-03-01"]),
+            ("Sleep", &[]),
+        ]);
+        assert_eq!(vault.exclude(&daily_pattern(), false), 1);
+
+        let mut names: Vec<&String> = vault.notes.keys().collect();
+        names.sort();
+        assert_eq!(names, ["Anger", "Sleep"]);
+        assert_eq!(vault.notes["Anger"].links, ["Sleep"]);
+        assert!(vault.find_dead_links().is_empty());
+        assert_eq!(vault.stats().links, 1);
+        // Only the daily note linked to Anger
+        assert_eq!(vault.find_orphans(), ["Anger"]);
+        assert!(vault.inferred.is_empty());
+    }
+
+    #[test]
+    fn collapse_links_co_cited_notes_once() {
+        let mut vault = vault_from_links(&[
+            ("2026-03-01", &["Anger", "Sleep", "Stoicism", "2026-02-28", "Missing"]),
+            ("2026-03-02", &["Sleep", "Anger", "Anger.md"]),
+            ("2026-02-28", &["Stoicism"]),
+            ("Anger", &["Stoicism"]),
+            ("Sleep", &[]),
+            ("Stoicism", &[]),
+        ]);
+        assert_eq!(vault.exclude(&daily_pattern(), true), 3);
+
+        // Anger–Stoicism is already a direct link; no daily note pairs with itself
+        assert_eq!(vault.inferred, [
+            ("Anger".to_string(), "Sleep".to_string()),
+            ("Sleep".to_string(), "Stoicism".to_string()),
+        ]);
+        assert_eq!(vault.stats().links, 1);
+    }
+
+    #[test]
+    fn comparison_shows_both_columns() {
+        let raw = VaultStats { total_notes: 4, orphans: 1, links: 6, reciprocity: Some(50.0) };
+        let filtered = VaultStats { total_notes: 2, orphans: 2, links: 0, reciprocity: None };
+        let table = render_comparison(&raw, &filtered, Some(3), "collapsing 2 notes");
+
+        assert!(table.contains("📊 VAULT ANALYSIS (collapsing 2 notes)"));
+        assert!(table.contains("Orphaned notes:     1 (25.0%)         2 (100.0%)\n"));
+        assert!(table.contains("Reciprocated links: 50.0%             n/a\n"));
+        assert!(table.contains("Inferred links:     -                 3\n"));
+        assert!(!render_comparison(&raw, &filtered, None, "x").contains("Inferred"));
+    }
+
+    #[test]
+    fn viz_draws_inferred_links_dashed() {
+        let mut vault = vault_from_links(&[("2026-03-01", &["A", "B"]), ("A", &[]), ("B", &[])]);
+        vault.exclude(&daily_pattern(), true);
+
+        // Both are orphans now, but co-citation keeps them in the connected view
+        let html = vault.render_html_viz("connected", "orphan", &NoteOpener::File).unwrap();
+        assert!(html.contains(r#""dashes":true"#));
+        assert!(html.contains(r#""inferred":true"#));
+        assert!(html.contains("Cited together in a daily note</div>"));
+        assert!(html.contains("📄 Showing: 2"));
+    }
+
+    #[test]
+    fn uri_encoding() {
+        assert_eq!(uri_encode("Zettel/Café notes", false), "Zettel%2FCaf%C3%A9%20notes");
+        assert_eq!(uri_encode("/home/w/Café notes.md", true), "/home/w/Caf%C3%A9%20notes.md");
+    }
+
+    #[test]
+    fn viz_embeds_encoded_note_paths() {
+        let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/viz-vault");
+        let vault = VaultGraph::parse_vault(&fixture).unwrap();
+
+        let obsidian = NoteOpener::new("obsidian", Some("My Vault".to_string()));
+        let html = vault.render_html_viz("all", "orphan", &obsidian).unwrap();
+        assert!(html.contains(r#""path":"Zettel/Café Society — Notes.md""#));
+        assert!(html.contains(
+            "obsidian://open?vault=My%20Vault&file=Zettel%2FCaf%C3%A9%20Society%20%E2%80%94%20Notes"
+        ));
+        assert!(html.contains("network.on('doubleClick'"));
+        // The handlers' data.nodes.get() needs a DataSet, not the plain array
+        assert!(html.contains("nodes: new vis.DataSet(graphData.nodes)"));
+        assert!(html.contains("const node = data.nodes.get(params.nodes[0]);"));
+
+        let html = vault.render_html_viz("all", "orphan", &NoteOpener::File).unwrap();
+        assert!(html.contains("file:///"));
+        assert!(html.contains("/tests/fixtures/viz-vault/Zettel/Caf%C3%A9%20Society%20%E2%80%94%20Notes.md"));
+    }
+}
@@ -0,0 +1,237 @@
+use anyhow::{Context, Result};
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// One row of metrics.csv: the vault's shape on a given day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub date: NaiveDate,
+    pub total_notes: usize,
+    pub orphans: usize,
+    pub edges: usize,
+    pub largest_component: usize,
+    pub avg_degree: f64,
+}
+
+impl Snapshot {
+    fn orphan_pct(&self) -> f64 {
+        if self.total_notes == 0 {
+            0.0
+        } else {
+            self.orphans as f64 / self.total_notes as f64 * 100.0
+        }
+    }
+}
+
+/// Metrics shown by `trend`: label, value accessor, decimal places
+type Metric = (&'static str, fn(&Snapshot) -> f64, usize);
+
+const METRICS: [Metric; 6] = [
+    ("Notes", |s| s.total_notes as f64, 0),
+    ("Orphans", |s| s.orphans as f64, 0),
+    ("Orphan %", Snapshot::orphan_pct, 1),
+    ("Links", |s| s.edges as f64, 0),
+    ("Largest component", |s| s.largest_component as f64, 0),
+    ("Avg degree", |s| s.avg_degree, 2),
+];
+
+/// Points shown in each sparkline
+const SPARKLINE_POINTS: usize = 30;
+
+/// ~/.local/share/forge-graph/metrics.csv
+pub fn default_path() -> Result<PathBuf> {
+    let home = dirs::home_dir().context("No home directory")?;
+    Ok(home.join(".local/share/forge-graph/metrics.csv"))
+}
+
+/// All snapshots in `path`, oldest first. A missing file is an empty history.
+pub fn read_history(path: &Path) -> Result<Vec<Snapshot>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let mut reader = csv::Reader::from_path(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut history: Vec<Snapshot> = reader
+        .deserialize()
+        .collect::<Result<_, _>>()
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    history.sort_by_key(|s| s.date);
+    Ok(history)
+}
+
+/// Add `snapshot` to the history in `path`, replacing any row for the same
+/// day. Returns true if an existing row was replaced.
+pub fn record(path: &Path, snapshot: Snapshot) -> Result<bool> {
+    let mut history = read_history(path)?;
+    let before = history.len();
+    history.retain(|s| s.date != snapshot.date);
+    let replaced = history.len() != before;
+    history.push(snapshot);
+    history.sort_by_key(|s| s.date);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let mut writer = csv::Writer::from_path(path)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    for row in &history {
+        writer.serialize(row)?;
+    }
+    writer.flush()?;
+    Ok(replaced)
+}
+
+/// The most recent snapshot at least `days` older than the latest one
+fn baseline(history: &[Snapshot], days: i64) -> Option<&Snapshot> {
+    let latest = history.last()?;
+    let cutoff = latest.date - Duration::days(days);
+    history.iter().rev().find(|s| s.date <= cutoff)
+}
+
+/// Change in each metric since `days` ago, or None without enough history
+fn deltas(history: &[Snapshot], days: i64) -> Option<Vec<f64>> {
+    let latest = history.last()?;
+    let base = baseline(history, days)?;
+    Some(METRICS.iter().map(|(_, value, _)| value(latest) - value(base)).collect())
+}
+
+/// One block character per value, scaled between the series' min and max
+fn sparkline(values: &[f64]) -> String {
+    const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    values
+        .iter()
+        .map(|v| {
+            if max > min {
+                BLOCKS[((v - min) / (max - min) * 7.0).round() as usize]
+            } else {
+                BLOCKS[3]
+            }
+        })
+        .collect()
+}
+
+fn format_delta(delta: Option<f64>, decimals: usize) -> String {
+    match delta {
+        Some(d) if d.abs() < 0.5 * 10f64.powi(-(decimals as i32)) => "±0".to_string(),
+        Some(d) => format!("{:+.*}", decimals, d),
+        None => "—".to_string(),
+    }
+}
+
+/// Table of the last `count` snapshots, then per-metric deltas and sparklines
+pub fn render_trend(history: &[Snapshot], count: usize) -> String {
+    let mut out = String::new();
+    out.push_str("\n📈 VAULT TREND\n");
+    out.push_str("═══════════════════════════════════════════\n");
+
+    out.push_str(&format!(
+        "{:<10}  {:>6}  {:>7}  {:>6}  {:>7}  {:>9}\n",
+        "Date", "Notes", "Orphans", "Links", "Largest", "Avg deg"
+    ));
+    for s in history.iter().skip(history.len().saturating_sub(count)) {
+        out.push_str(&format!(
+            "{:<10}  {:>6}  {:>7}  {:>6}  {:>7}  {:>9.2}\n",
+            s.date, s.total_notes, s.orphans, s.edges, s.largest_component, s.avg_degree
+        ));
+    }
+
+    let week = deltas(history, 7);
+    let month = deltas(history, 30);
+    let recent = &history[history.len().saturating_sub(SPARKLINE_POINTS)..];
+
+    out.push('\n');
+    out.push_str(&format!(
+        "{:<18} {:>9}  {:>8}  {:>8}  History\n",
+        "Metric", "Now", "Δ 7d", "Δ 30d"
+    ));
+    let latest = history.last();
+    for (i, (label, value, decimals)) in METRICS.iter().enumerate() {
+        let now = latest.map(|s| format!("{:.*}", decimals, value(s))).unwrap_or_default();
+        let series: Vec<f64> = recent.iter().map(value).collect();
+        out.push_str(&format!(
+            "{:<18} {:>9}  {:>8}  {:>8}  {}\n",
+            label,
+            now,
+            format_delta(week.as_ref().map(|d| d[i]), *decimals),
+            format_delta(month.as_ref().map(|d| d[i]), *decimals),
+            sparkline(&series),
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snap(date: &str, total_notes: usize, orphans: usize, edges: usize) -> Snapshot {
+        Snapshot {
+            date: NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+            total_notes,
+            orphans,
+            edges,
+            largest_component: total_notes - orphans,
+            avg_degree: 2.0 * edges as f64 / total_notes as f64,
+        }
+    }
+
+    #[test]
+    fn deltas_use_latest_snapshot_at_least_n_days_old() {
+        let history = vec![
+            snap("2026-01-01", 100, 40, 200),
+            snap("2026-01-20", 110, 35, 240),
+            snap("2026-01-24", 115, 33, 250),
+            snap("2026-01-31", 120, 30, 260),
+        ];
+
+        // 7 days before 01-31 is 01-24; 30 days before is 01-01
+        let week = deltas(&history, 7).unwrap();
+        assert_eq!(week[0], 5.0);
+        assert_eq!(week[1], -3.0);
+        assert_eq!(week[3], 10.0);
+
+        let month = deltas(&history, 30).unwrap();
+        assert_eq!(month[0], 20.0);
+        assert_eq!(month[1], -10.0);
+        assert!((month[2] - (25.0 - 40.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn deltas_need_enough_history() {
+        let history = vec![snap("2026-01-28", 100, 40, 200), snap("2026-01-31", 101, 40, 201)];
+        assert!(deltas(&history, 7).is_none());
+        assert!(deltas(&[], 7).is_none());
+    }
+
+    #[test]
+    fn same_day_snapshot_overwrites() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested/metrics.csv");
+
+        assert!(!record(&path, snap("2026-02-01", 100, 40, 200)).unwrap());
+        assert!(!record(&path, snap("2026-02-02", 101, 39, 205)).unwrap());
+        assert!(record(&path, snap("2026-02-02", 102, 38, 210)).unwrap());
+
+        let history = read_history(&path).unwrap();
+        assert_eq!(history, vec![snap("2026-02-01", 100, 40, 200), snap("2026-02-02", 102, 38, 210)]);
+    }
+
+    #[test]
+    fn sparkline_scales_between_min_and_max() {
+        assert_eq!(sparkline(&[0.0, 7.0, 3.5]), "▁█▅");
+        assert_eq!(sparkline(&[2.0, 2.0]), "▄▄");
+    }
+
+    #[test]
+    fn trend_marks_missing_deltas() {
+        let out = render_trend(&[snap("2026-02-01", 100, 40, 200)], 10);
+        assert!(out.contains("2026-02-01"));
+        assert!(out.lines().any(|l| l.starts_with("Orphans") && l.contains('—')));
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::{BTreeSet, HashMap};
+
+/// Suggestions printed under each orphan by `daily`
+pub const SUGGESTIONS_PER_ORPHAN: usize = 3;
+
+/// A shared tag says more about two notes than a shared title word
+const TAG_WEIGHT: usize = 2;
+const WORD_WEIGHT: usize = 1;
+
+/// Title words too common to say what a note is about
+const STOPWORDS: &[&str] = &[
+    "the", "and", "for", "with", "from", "into", "about", "what", "why", "how", "who", "when",
+    "this", "that", "are", "was", "not", "its", "our", "your", "notes", "note",
+];
+
+/// Tags from a note's frontmatter `tags:` (or `tag:`) field, lowercased and
+/// without `#`. Accepts `tags: [a, b]`, `tags: a, b`, `tags: a b` and a
+/// YAML list on the following lines.
+pub fn frontmatter_tags(content: &str) -> Vec<String> {
+    let Some(rest) = content.strip_prefix("---") else {
+        return Vec::new();
+    };
+    let Some(end) = rest.find("\n---") else {
+        return Vec::new();
+    };
+
+    let mut tags = Vec::new();
+    let mut lines = rest[..end].lines().peekable();
+    while let Some(line) = lines.next() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        if !matches!(key.trim(), "tags" | "tag") {
+            continue;
+        }
+        let value = value.trim().trim_start_matches('[').trim_end_matches(']');
+        if value.is_empty() {
+            while let Some(item) = lines.peek().and_then(|l| l.trim().strip_prefix('-')) {
+                tags.push(item.to_string());
+                lines.next();
+            }
+        } else {
+            tags.extend(value.split([',', ' ']).map(str::to_string));
+        }
+        break;
+    }
+
+    tags.iter()
+        .map(|t| t.trim().trim_matches(|c| c == '"' || c == '\'').trim_start_matches('#').to_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+/// Lowercased words of a title that could tie it to another note: at least
+/// three letters, not a stopword and not a bare number (dates, years)
+pub fn title_words(title: &str) -> BTreeSet<String> {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|w| w.chars().count() >= 3)
+        .filter(|w| !w.chars().all(|c| c.is_ascii_digit()))
+        .filter(|w| !STOPWORDS.contains(&w.as_str()))
+        .collect()
+}
+
+/// A note worth linking to an orphan, and what the two have in common
+#[derive(Debug, PartialEq)]
+pub struct Suggestion<'a> {
+    pub name: &'a str,
+    pub shared_tags: Vec<&'a str>,
+    pub shared_words: Vec<&'a str>,
+}
+
+impl Suggestion<'_> {
+    fn score(&self) -> usize {
+        self.shared_tags.len() * TAG_WEIGHT + self.shared_words.len() * WORD_WEIGHT
+    }
+
+    /// "tags: stoicism · title: seneca, letters"
+    pub fn reason(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.shared_tags.is_empty() {
+            parts.push(format!("tags: {}", self.shared_tags.join(", ")));
+        }
+        if !self.shared_words.is_empty() {
+            parts.push(format!("title: {}", self.shared_words.join(", ")));
+        }
+        parts.join(" · ")
+    }
+}
+
+struct Terms {
+    tags: BTreeSet<String>,
+    words: BTreeSet<String>,
+}
+
+/// Tag and title-word index over the vault, for finding notes related to
+/// an orphan without comparing it against every note
+pub struct Suggester<'a> {
+    terms: HashMap<&'a str, Terms>,
+    by_tag: HashMap<String, Vec<&'a str>>,
+    by_word: HashMap<String, Vec<&'a str>>,
+}
+
+impl<'a> Suggester<'a> {
+    /// Index `(note name, tags)` pairs
+    pub fn new(notes: impl IntoIterator<Item = (&'a str, &'a [String])>) -> Self {
+        let mut suggester = Suggester { terms: HashMap::new(), by_tag: HashMap::new(), by_word: HashMap::new() };
+        for (name, tags) in notes {
+            let terms = Terms { tags: tags.iter().cloned().collect(), words: title_words(name) };
+            for tag in &terms.tags {
+                suggester.by_tag.entry(tag.clone()).or_default().push(name);
+            }
+            for word in &terms.words {
+                suggester.by_word.entry(word.clone()).or_default().push(name);
+            }
+            suggester.terms.insert(name, terms);
+        }
+        suggester
+    }
+
+    /// Up to `limit` notes sharing tags or title words with `orphan`, best
+    /// first: two points per shared tag, one per shared word, ties by name
+    pub fn suggest(&self, orphan: &str, limit: usize) -> Vec<Suggestion<'_>> {
+        let Some(terms) = self.terms.get(orphan) else {
+            return Vec::new();
+        };
+
+        let mut found: HashMap<&str, Suggestion> = HashMap::new();
+        for (tag, names) in terms.tags.iter().filter_map(|t| self.by_tag.get_key_value(t)) {
+            for &name in names.iter().filter(|&&n| n != orphan) {
+                found.entry(name).or_insert_with(|| empty(name)).shared_tags.push(tag);
+            }
+        }
+        for (word, names) in terms.words.iter().filter_map(|w| self.by_word.get_key_value(w)) {
+            for &name in names.iter().filter(|&&n| n != orphan) {
+                found.entry(name).or_insert_with(|| empty(name)).shared_words.push(word);
+            }
+        }
+
+        let mut suggestions: Vec<_> = found.into_values().collect();
+        suggestions.sort_by(|a, b| b.score().cmp(&a.score()).then(a.name.cmp(b.name)));
+        suggestions.truncate(limit);
+        suggestions
+    }
+}
+
+fn empty(name: &str) -> Suggestion<'_> {
+    Suggestion { name, shared_tags: Vec::new(), shared_words: Vec::new() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(list: &[&str]) -> Vec<String> {
+        list.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn reads_tag_styles() {
+        assert_eq!(frontmatter_tags("---\ntags: [Stoicism, \"#ethics\"]\n---\n"), tags(&["stoicism", "ethics"]));
+        assert_eq!(frontmatter_tags("---\ntags: stoicism, ethics\n---\n"), tags(&["stoicism", "ethics"]));
+        assert_eq!(frontmatter_tags("---\ntag: #stoicism #ethics\n---\n"), tags(&["stoicism", "ethics"]));
+        assert_eq!(
+            frontmatter_tags("---\ntitle: x\ntags:\n  - stoicism\n  - ethics\ndate created: 2024-01-01\n---\n"),
+            tags(&["stoicism", "ethics"])
+        );
+        assert!(frontmatter_tags("tags: stoicism\n").is_empty());
+        assert!(frontmatter_tags("---\ntags:\n---\n").is_empty());
+    }
+
+    #[test]
+    fn title_words_skip_noise() {
+        let words: Vec<String> = title_words("2024-03-01 Notes on the Letters of Seneca").into_iter().collect();
+        assert_eq!(words, tags(&["letters", "seneca"]));
+        assert!(title_words("On it").is_empty());
+    }
+
+    #[test]
+    fn ranks_by_shared_tags_then_words() {
+        let notes = [
+            ("Seneca on anger", tags(&["stoicism"])),
+            ("Letters from Seneca", tags(&["stoicism", "letters"])),
+            ("Anger management", tags(&[])),
+            ("Seneca biography", tags(&[])),
+            ("Unrelated", tags(&["cooking"])),
+        ];
+        let suggester = Suggester::new(notes.iter().map(|(n, t)| (*n, t.as_slice())));
+
+        let found = suggester.suggest("Seneca on anger", 3);
+        let names: Vec<&str> = found.iter().map(|s| s.name).collect();
+        // 2 (tag) + 1 (seneca) beats the two single-word matches
+        assert_eq!(names, vec!["Letters from Seneca", "Anger management", "Seneca biography"]);
+        assert_eq!(found[0].reason(), "tags: stoicism · title: seneca");
+        assert_eq!(found[1].reason(), "title: anger");
+
+        assert_eq!(suggester.suggest("Seneca on anger", 1).len(), 1);
+        assert!(suggester.suggest("Unrelated", 3).is_empty());
+        assert!(suggester.suggest("Missing", 3).is_empty());
+    }
+}
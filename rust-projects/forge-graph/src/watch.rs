@@ -0,0 +1,110 @@
+use anyhow::{Context, Result};
+use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::Path;
+use std::sync::mpsc::{channel, Receiver};
+use std::time::Duration;
+
+/// Quiet period after the last markdown change before regenerating, so a
+/// burst (an editor's save-via-rename, a sync pulling many files) runs once
+pub const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Whether `event` creates, changes or removes a markdown note. Access
+/// events, non-markdown files and `.git`/`.obsidian` internals are ignored,
+/// as in `parse_vault`.
+pub fn touches_markdown(event: &Event) -> bool {
+    if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)) {
+        return false;
+    }
+    event.paths.iter().any(|path| {
+        let text = path.to_string_lossy();
+        path.extension().and_then(|s| s.to_str()) == Some("md")
+            && !text.contains(".git")
+            && !text.contains(".obsidian")
+    })
+}
+
+/// Block until a markdown change arrives, then until `quiet` passes with no
+/// further event. Returns false once the watcher has gone away.
+pub fn next_change(rx: &Receiver<Event>, quiet: Duration) -> bool {
+    loop {
+        match rx.recv() {
+            Ok(event) if touches_markdown(&event) => break,
+            Ok(_) => continue,
+            Err(_) => return false,
+        }
+    }
+    // Anything still arriving means the burst isn't over
+    while rx.recv_timeout(quiet).is_ok() {}
+    true
+}
+
+/// Watch `vault_path` recursively, calling `regenerate` after each settled
+/// burst of markdown changes. A failed regeneration is reported and the
+/// watch carries on, so a half-written note can't end it.
+pub fn watch(vault_path: &Path, mut regenerate: impl FnMut() -> Result<()>) -> Result<()> {
+    let (tx, rx) = channel();
+    let mut watcher = RecommendedWatcher::new(
+        move |res: Result<Event, notify::Error>| {
+            if let Ok(event) = res {
+                let _ = tx.send(event);
+            }
+        },
+        Config::default(),
+    )?;
+    watcher
+        .watch(vault_path, RecursiveMode::Recursive)
+        .with_context(|| format!("Failed to watch {}", vault_path.display()))?;
+
+    println!("\n👀 Watching {} for markdown changes (Ctrl-C to stop)", vault_path.display());
+    while next_change(&rx, DEBOUNCE) {
+        println!("\n✏️  Vault changed, regenerating...");
+        if let Err(e) = regenerate() {
+            eprintln!("⚠️  Regeneration failed: {:#}", e);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{AccessKind, CreateKind, ModifyKind, RemoveKind};
+    use std::path::PathBuf;
+
+    fn event(kind: EventKind, path: &str) -> Event {
+        Event::new(kind).add_path(PathBuf::from(path))
+    }
+
+    #[test]
+    fn only_markdown_changes_count() {
+        assert!(touches_markdown(&event(EventKind::Modify(ModifyKind::Any), "/v/Note.md")));
+        assert!(touches_markdown(&event(EventKind::Create(CreateKind::File), "/v/sub/New.md")));
+        assert!(touches_markdown(&event(EventKind::Remove(RemoveKind::File), "/v/Gone.md")));
+
+        assert!(!touches_markdown(&event(EventKind::Modify(ModifyKind::Any), "/v/image.png")));
+        assert!(!touches_markdown(&event(EventKind::Modify(ModifyKind::Any), "/v/graph.html")));
+        assert!(!touches_markdown(&event(EventKind::Access(AccessKind::Any), "/v/Note.md")));
+        assert!(!touches_markdown(&event(EventKind::Modify(ModifyKind::Any), "/v/.obsidian/x.md")));
+    }
+
+    #[test]
+    fn bursts_collapse_into_one_change() {
+        let (tx, rx) = channel();
+        tx.send(event(EventKind::Modify(ModifyKind::Any), "/v/a.png")).unwrap();
+        for name in ["/v/a.md", "/v/b.md", "/v/c.md"] {
+            tx.send(event(EventKind::Modify(ModifyKind::Any), name)).unwrap();
+        }
+
+        assert!(next_change(&rx, Duration::from_millis(20)));
+        // The whole burst was consumed
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn stops_when_the_watcher_goes() {
+        let (tx, rx) = channel();
+        tx.send(event(EventKind::Modify(ModifyKind::Any), "/v/a.png")).unwrap();
+        drop(tx);
+        assert!(!next_change(&rx, Duration::from_millis(20)));
+    }
+}
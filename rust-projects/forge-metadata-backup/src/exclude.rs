@@ -0,0 +1,174 @@
+use anyhow::{Context, Result};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Always excluded unless --no-default-excludes is given
+pub const DEFAULT_EXCLUDES: &[&str] = &[".git", ".obsidian/cache", "node_modules", "target"];
+
+/// Compiled exclude patterns, matched against paths relative to the backup root.
+///
+/// A pattern without a `/` matches a file or directory name at any depth
+/// (`node_modules`, `*.tmp`); a pattern with a `/` is anchored at the root
+/// (`.obsidian/cache`, `linked_media/**/*.mp4`). Excluding a directory
+/// excludes everything beneath it.
+#[derive(Clone)]
+pub struct Excludes {
+    set: GlobSet,
+}
+
+impl Excludes {
+    pub fn new(patterns: &[String], use_defaults: bool) -> Result<Self> {
+        let defaults = DEFAULT_EXCLUDES.iter().filter(|_| use_defaults).map(|p| p.to_string());
+        let mut builder = GlobSetBuilder::new();
+        for pattern in defaults.chain(patterns.iter().cloned()) {
+            let anchored = match pattern.strip_prefix('/') {
+                Some(rest) => rest.to_string(),
+                None if pattern.contains('/') => pattern.clone(),
+                None => format!("**/{}", pattern),
+            };
+            let glob = GlobBuilder::new(&anchored)
+                .literal_separator(true)
+                .build()
+                .with_context(|| format!("Invalid exclude pattern: {}", pattern))?;
+            builder.add(glob);
+        }
+        Ok(Self { set: builder.build()? })
+    }
+
+    /// Whether a root-relative path, or any directory containing it, is excluded
+    pub fn is_excluded(&self, relative: &Path) -> bool {
+        relative.ancestors()
+            .filter(|p| !p.as_os_str().is_empty())
+            .any(|p| self.set.is_match(p))
+    }
+}
+
+/// All files under `dir` that survive the excludes, sorted. With
+/// `respect_gitignore`, `.gitignore`/`.ignore` files are honoured too.
+pub fn collect_files(dir: &Path, excludes: &Excludes, respect_gitignore: bool) -> Vec<PathBuf> {
+    let keep = |root: &Path, excludes: &Excludes, path: &Path| {
+        path.strip_prefix(root)
+            .map(|rel| !excludes.is_excluded(rel))
+            .unwrap_or(true)
+    };
+
+    let mut files: Vec<PathBuf> = if respect_gitignore {
+        // The ignore walker wants an owned, 'static filter
+        let root = dir.to_path_buf();
+        let excludes = excludes.clone();
+        ignore::WalkBuilder::new(dir)
+            .hidden(false)
+            .git_global(false)
+            .require_git(false)
+            .follow_links(false)
+            .filter_entry(move |e| keep(&root, &excludes, e.path()))
+            .build()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_some_and(|t| t.is_file()))
+            .map(|e| e.into_path())
+            .collect()
+    } else {
+        WalkDir::new(dir)
+            .follow_links(false)
+            .into_iter()
+            .filter_entry(|e| keep(dir, excludes, e.path()))
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .map(|e| e.into_path())
+            .collect()
+    };
+    files.sort();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// Fixture tree with a mix of excluded and included files
+    fn fixture_tree() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for file in [
+            "notes/a.md",
+            "notes/drafts/b.md",
+            ".obsidian/app.json",
+            ".obsidian/cache/index.bin",
+            ".git/objects/ab/cdef",
+            "projects/site/node_modules/pkg/index.js",
+            "projects/site/index.html",
+            "projects/tool/target/debug/tool",
+            "linked_media/clip.mp4",
+            "linked_media/photo.jpg",
+            "scratch.tmp",
+        ] {
+            let path = dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "x").unwrap();
+        }
+        fs::write(dir.path().join(".gitignore"), "drafts/\n*.tmp\n").unwrap();
+        dir
+    }
+
+    fn relative(dir: &Path, files: Vec<PathBuf>) -> Vec<String> {
+        files.iter()
+            .map(|f| f.strip_prefix(dir).unwrap().to_string_lossy().replace('\\', "/"))
+            .collect()
+    }
+
+    #[test]
+    fn default_patterns_match_directories_at_any_depth() {
+        let excludes = Excludes::new(&[], true).unwrap();
+        assert!(excludes.is_excluded(Path::new(".git/objects/ab/cdef")));
+        assert!(excludes.is_excluded(Path::new("projects/site/node_modules/pkg/index.js")));
+        assert!(excludes.is_excluded(Path::new(".obsidian/cache/index.bin")));
+        assert!(!excludes.is_excluded(Path::new(".obsidian/app.json")));
+        assert!(!excludes.is_excluded(Path::new("notes/targets.md")));
+    }
+
+    #[test]
+    fn user_patterns_anchor_when_they_contain_a_slash() {
+        let patterns = vec!["linked_media/*.mp4".to_string(), "*.tmp".to_string()];
+        let excludes = Excludes::new(&patterns, false).unwrap();
+        assert!(excludes.is_excluded(Path::new("linked_media/clip.mp4")));
+        assert!(!excludes.is_excluded(Path::new("archive/linked_media/clip.mp4")));
+        assert!(excludes.is_excluded(Path::new("deep/scratch.tmp")));
+        assert!(!excludes.is_excluded(Path::new(".git/HEAD")));
+    }
+
+    #[test]
+    fn invalid_pattern_is_an_error() {
+        assert!(Excludes::new(&["notes/[".to_string()], true).is_err());
+    }
+
+    #[test]
+    fn walkdir_walker_applies_excludes_only() {
+        let tree = fixture_tree();
+        let excludes = Excludes::new(&["linked_media/*.mp4".to_string()], true).unwrap();
+        let files = relative(tree.path(), collect_files(tree.path(), &excludes, false));
+        assert_eq!(files, vec![
+            ".gitignore",
+            ".obsidian/app.json",
+            "linked_media/photo.jpg",
+            "notes/a.md",
+            "notes/drafts/b.md",
+            "projects/site/index.html",
+            "scratch.tmp",
+        ]);
+    }
+
+    #[test]
+    fn gitignore_walker_also_honours_gitignore() {
+        let tree = fixture_tree();
+        let excludes = Excludes::new(&["linked_media/*.mp4".to_string()], true).unwrap();
+        let files = relative(tree.path(), collect_files(tree.path(), &excludes, true));
+        assert_eq!(files, vec![
+            ".gitignore",
+            ".obsidian/app.json",
+            "linked_media/photo.jpg",
+            "notes/a.md",
+            "projects/site/index.html",
+        ]);
+    }
+}
@@ -1,11 +1,18 @@
+mod exclude;
+mod relocate;
+
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use csv::Writer;
 use indicatif::{ProgressBar, ProgressStyle};
+use std::collections::HashSet;
 use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::time::UNIX_EPOCH;
-use walkdir::WalkDir;
+
+use exclude::Excludes;
+use relocate::{FilenameIndex, Remap, Resolution};
 
 #[derive(Parser, Debug)]
 #[command(name = "forge-metadata-backup")]
@@ -17,33 +24,108 @@ struct Args {
 
 #[derive(Subcommand, Debug)]
 enum Commands {
-    /// Export file metadata to CSV
+    /// Export file metadata to CSV or JSON
     Export {
         /// Directory to backup (e.g., ~/Forge)
         #[arg(value_name = "DIRECTORY")]
         directory: PathBuf,
 
-        /// Custom output file (default: DIR/.metadata-backup.csv)
+        /// Custom output file (default: DIR/.metadata-backup.csv or .json)
         #[arg(short, long)]
         output: Option<PathBuf>,
+
+        /// Backup file format
+        #[arg(long, value_enum, default_value_t = Format::Csv)]
+        format: Format,
+
+        #[command(flatten)]
+        excludes: ExcludeArgs,
+
+        /// Also skip files ignored by .gitignore / .ignore files
+        #[arg(long)]
+        respect_gitignore: bool,
     },
-    /// Restore file metadata from CSV
+    /// Restore file metadata from a CSV or JSON backup
     Restore {
         /// Directory to restore  (e.g., ~/Forge)
         #[arg(value_name = "DIRECTORY")]
         directory: PathBuf,
 
-        /// Custom input file (default: DIR/.metadata-backup.csv)
+        /// Custom input file (default: DIR/.metadata-backup.csv or .json)
         #[arg(short, long)]
         input: Option<PathBuf>,
 
+        /// Backup file format (default: detected from the file extension)
+        #[arg(long, value_enum)]
+        format: Option<Format>,
+
         /// Show what would be restored without making changes
         #[arg(long)]
         dry_run: bool,
+
+        #[command(flatten)]
+        excludes: ExcludeArgs,
+
+        /// Skip backup rows matching the excludes (by default they are
+        /// restored with a warning, so old backups still restore fully)
+        #[arg(long)]
+        apply_excludes: bool,
+
+        /// Read backup paths starting with OLD_PREFIX as starting with
+        /// NEW_PREFIX (repeatable; the first match applies)
+        #[arg(long, value_name = "OLD_PREFIX=NEW_PREFIX")]
+        remap: Vec<Remap>,
+
+        /// When a file isn't at its backed-up path, use the only file with
+        /// the same name elsewhere under DIRECTORY (skipping names shared by
+        /// several files)
+        #[arg(long)]
+        match_by_filename: bool,
     },
 }
 
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[derive(clap::Args, Debug)]
+struct ExcludeArgs {
+    /// Glob pattern to exclude, relative to DIRECTORY (repeatable). Patterns
+    /// without a '/' match at any depth, e.g. 'node_modules' or '*.tmp'
+    #[arg(long = "exclude", value_name = "PATTERN")]
+    patterns: Vec<String>,
+
+    /// Don't exclude .git, .obsidian/cache, node_modules and target by default
+    #[arg(long)]
+    no_default_excludes: bool,
+}
+
+impl ExcludeArgs {
+    fn compile(&self) -> Result<Excludes> {
+        Excludes::new(&self.patterns, !self.no_default_excludes)
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum Format {
+    Csv,
+    Json,
+}
+
+impl Format {
+    /// JSON for `.json` files, CSV for anything else
+    fn detect(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Format::Json,
+            _ => Format::Csv,
+        }
+    }
+
+    fn default_file_name(self) -> &'static str {
+        match self {
+            Format::Csv => ".metadata-backup.csv",
+            Format::Json => ".metadata-backup.json",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
 struct FileMetadata {
     path: String,
     created: u64,
@@ -54,36 +136,65 @@ fn main() -> Result<()> {
     let args = Args::parse();
 
     match args.command {
-        Commands::Export { directory, output } => export_metadata(&directory, output.as_deref())?,
+        Commands::Export {
+            directory,
+            output,
+            format,
+            excludes,
+            respect_gitignore,
+        } => export_metadata(
+            &directory,
+            output.as_deref(),
+            format,
+            &excludes.compile()?,
+            respect_gitignore,
+        )?,
         Commands::Restore {
             directory,
             input,
+            format,
             dry_run,
-        } => restore_metadata(&directory, input.as_deref(), dry_run)?,
+            excludes,
+            apply_excludes,
+            remap,
+            match_by_filename,
+        } => restore_metadata(
+            &directory,
+            RestoreOptions {
+                input,
+                format,
+                dry_run,
+                excludes: excludes.compile()?,
+                apply_excludes,
+                remaps: remap,
+                match_by_filename,
+            },
+        )?,
     }
 
     Ok(())
 }
 
-fn export_metadata(dir: &Path, output_file: Option<&Path>) -> Result<()> {
+fn export_metadata(
+    dir: &Path,
+    output_file: Option<&Path>,
+    format: Format,
+    excludes: &Excludes,
+    respect_gitignore: bool,
+) -> Result<()> {
     let dir = fs::canonicalize(dir)
         .with_context(|| format!("Failed to resolve directory: {}", dir.display()))?;
 
     let backup_file = output_file
         .map(PathBuf::from)
-        .unwrap_or_else(|| dir.join(".metadata-backup.csv"));
+        .unwrap_or_else(|| dir.join(format.default_file_name()));
 
     println!("Exporting metadata from: {}", dir.display());
     println!("Output file: {}\n", backup_file.display());
 
-    // Collect all files
+    // Collect all files that aren't excluded
     println!("Scanning files...");
-    let entries: Vec<_> = WalkDir::new(&dir)
-        .follow_links(false)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
-        .collect();
+    let entries = exclude::collect_files(&dir, excludes, respect_gitignore);
 
     println!("Found {} files\n", entries.len());
 
@@ -98,8 +209,7 @@ fn export_metadata(dir: &Path, output_file: Option<&Path>) -> Result<()> {
 
     // Extract metadata
     let mut metadata_records = Vec::new();
-    for entry in entries {
-        let path = entry.path();
+    for path in &entries {
         if let Ok(meta) = fs::metadata(path) {
             let created = meta
                 .created()
@@ -132,15 +242,8 @@ fn export_metadata(dir: &Path, output_file: Option<&Path>) -> Result<()> {
 
     pb.finish_with_message("Scan complete");
 
-    // Write to CSV
-    println!("\nWriting to CSV...");
-    let mut wtr = Writer::from_path(&backup_file)
-        .with_context(|| format!("Failed to create CSV file: {}", backup_file.display()))?;
-
-    for record in &metadata_records {
-        wtr.serialize(record)?;
-    }
-    wtr.flush()?;
+    println!("\nWriting {:?}...", format);
+    write_records(&backup_file, format, &metadata_records)?;
 
     let file_size = fs::metadata(&backup_file)?.len();
     println!("\n✅ Exported {} files", metadata_records.len());
@@ -150,18 +253,43 @@ fn export_metadata(dir: &Path, output_file: Option<&Path>) -> Result<()> {
         file_size
     );
     println!("\n💡 Tip: Commit this file to git for ultimate protection:");
-    println!("   cd {} && git add .metadata-backup.csv && git commit -m 'Update metadata backup'", dir.display());
+    println!(
+        "   cd {} && git add {} && git commit -m 'Update metadata backup'",
+        dir.display(),
+        backup_file.strip_prefix(&dir).unwrap_or(&backup_file).display()
+    );
 
     Ok(())
 }
 
-fn restore_metadata(dir: &Path, input_file: Option<&Path>, dry_run: bool) -> Result<()> {
+/// The `restore` command's options, as given on the command line
+struct RestoreOptions {
+    input: Option<PathBuf>,
+    format: Option<Format>,
+    dry_run: bool,
+    excludes: Excludes,
+    apply_excludes: bool,
+    remaps: Vec<Remap>,
+    match_by_filename: bool,
+}
+
+fn restore_metadata(dir: &Path, options: RestoreOptions) -> Result<()> {
+    let RestoreOptions {
+        input,
+        format,
+        dry_run,
+        excludes,
+        apply_excludes,
+        remaps,
+        match_by_filename,
+    } = options;
+
     let dir = fs::canonicalize(dir)
         .with_context(|| format!("Failed to resolve directory: {}", dir.display()))?;
 
-    let backup_file = input_file
-        .map(PathBuf::from)
-        .unwrap_or_else(|| dir.join(".metadata-backup.csv"));
+    let backup_file = input
+        .unwrap_or_else(|| dir.join(format.unwrap_or(Format::Csv).default_file_name()));
+    let format = format.unwrap_or_else(|| Format::detect(&backup_file));
 
     if !backup_file.exists() {
         anyhow::bail!(
@@ -178,17 +306,36 @@ fn restore_metadata(dir: &Path, input_file: Option<&Path>, dry_run: bool) -> Res
         println!();
     }
 
-    // Read CSV
-    let mut rdr = csv::Reader::from_path(&backup_file)
-        .with_context(|| format!("Failed to read CSV file: {}", backup_file.display()))?;
-
-    let records: Vec<FileMetadata> = rdr
-        .deserialize()
-        .collect::<Result<_, _>>()
-        .context("Failed to parse CSV")?;
+    let mut records = read_records(&backup_file, format)?;
+    for record in &mut records {
+        record.path = relocate::remap_path(&remaps, &record.path);
+    }
 
     println!("Found {} files in backup\n", records.len());
 
+    let index = match_by_filename.then(|| {
+        println!("Indexing files by name...");
+        let claimed: HashSet<PathBuf> = records.iter().map(|r| dir.join(&r.path)).collect();
+        FilenameIndex::build(&dir, &excludes, &claimed)
+    });
+
+    // Older backups may predate the current excludes
+    let excluded_rows = records
+        .iter()
+        .filter(|r| excludes.is_excluded(Path::new(&r.path)))
+        .count();
+    if excluded_rows > 0 {
+        if apply_excludes {
+            println!("⚠️  Skipping {} backup rows that match the excludes\n", excluded_rows);
+        } else {
+            println!(
+                "⚠️  {} backup rows match the excludes and would not be exported today; \
+                 restoring them anyway (pass --apply-excludes to skip)\n",
+                excluded_rows
+            );
+        }
+    }
+
     // Create progress bar
     let pb = ProgressBar::new(records.len() as u64);
     pb.set_style(
@@ -202,13 +349,38 @@ fn restore_metadata(dir: &Path, input_file: Option<&Path>, dry_run: bool) -> Res
     let mut restored = 0;
     let mut missing = 0;
     let mut errors = 0;
+    let mut relocated = 0;
+    let mut ambiguous = Vec::new();
 
-    for record in &records {
-        let full_path = dir.join(&record.path);
+    let rows: Vec<&FileMetadata> = records
+        .iter()
+        .filter(|r| !(apply_excludes && excludes.is_excluded(Path::new(&r.path))))
+        .collect();
+    let skipped = records.len() - rows.len();
+    pb.inc(skipped as u64);
+    let paths: Vec<&str> = rows.iter().map(|r| r.path.as_str()).collect();
+    let resolutions = relocate::resolve_all(&dir, &paths, index.as_ref());
+
+    for (record, resolution) in rows.iter().zip(resolutions) {
+        let full_path = match resolution {
+            Resolution::Exact(path) => path,
+            Resolution::ByFilename(path) => {
+                relocated += 1;
+                path
+            }
+            Resolution::Ambiguous(candidates) => {
+                ambiguous.push((&record.path, candidates.len()));
+                pb.inc(1);
+                continue;
+            }
+            Resolution::Missing => {
+                missing += 1;
+                pb.inc(1);
+                continue;
+            }
+        };
 
-        if !full_path.exists() {
-            missing += 1;
-        } else if dry_run {
+        if dry_run {
             restored += 1;
         } else {
             // Set modification time
@@ -233,7 +405,23 @@ fn restore_metadata(dir: &Path, input_file: Option<&Path>, dry_run: bool) -> Res
     } else {
         println!("\nFiles restored: {}", restored);
     }
+    if index.is_some() {
+        println!("Files found by filename after moving: {}", relocated);
+    }
     println!("Files missing - not in directory: {}", missing);
+    if !ambiguous.is_empty() {
+        println!("Files skipped - filename ambiguous: {}", ambiguous.len());
+        for (path, candidates) in &ambiguous {
+            if *candidates > 1 {
+                println!("  {} ({} files with this name)", path, candidates);
+            } else {
+                println!("  {} (its file by name is another row's too)", path);
+            }
+        }
+    }
+    if skipped > 0 {
+        println!("Files skipped - excluded: {}", skipped);
+    }
     if errors > 0 {
         println!("Errors: {}", errors);
     }
@@ -244,3 +432,83 @@ fn restore_metadata(dir: &Path, input_file: Option<&Path>, dry_run: bool) -> Res
 
     Ok(())
 }
+
+fn write_records(path: &Path, format: Format, records: &[FileMetadata]) -> Result<()> {
+    match format {
+        Format::Csv => {
+            let mut wtr = Writer::from_path(path)
+                .with_context(|| format!("Failed to create CSV file: {}", path.display()))?;
+            for record in records {
+                wtr.serialize(record)?;
+            }
+            wtr.flush()?;
+        }
+        Format::Json => {
+            let file = fs::File::create(path)
+                .with_context(|| format!("Failed to create JSON file: {}", path.display()))?;
+            let mut writer = std::io::BufWriter::new(file);
+            serde_json::to_writer_pretty(&mut writer, records)?;
+            writeln!(writer)?;
+            writer.flush()?;
+        }
+    }
+    Ok(())
+}
+
+fn read_records(path: &Path, format: Format) -> Result<Vec<FileMetadata>> {
+    match format {
+        Format::Csv => {
+            let mut rdr = csv::Reader::from_path(path)
+                .with_context(|| format!("Failed to read CSV file: {}", path.display()))?;
+            rdr.deserialize()
+                .collect::<Result<_, _>>()
+                .context("Failed to parse CSV")
+        }
+        Format::Json => {
+            let file = fs::File::open(path)
+                .with_context(|| format!("Failed to read JSON file: {}", path.display()))?;
+            serde_json::from_reader(std::io::BufReader::new(file)).context("Failed to parse JSON")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Vec<FileMetadata> {
+        vec![
+            FileMetadata { path: "notes/2024-01-15.md".into(), created: 1705276800, modified: 1705363200 },
+            FileMetadata { path: "projects/readme, draft.md".into(), created: 1700000000, modified: 1705400000 },
+        ]
+    }
+
+    #[test]
+    fn detects_format_from_extension() {
+        assert_eq!(Format::detect(Path::new("backup.json")), Format::Json);
+        assert_eq!(Format::detect(Path::new("backup.JSON")), Format::Json);
+        assert_eq!(Format::detect(Path::new(".metadata-backup.csv")), Format::Csv);
+        assert_eq!(Format::detect(Path::new("backup")), Format::Csv);
+    }
+
+    #[test]
+    fn json_round_trip_is_a_plain_array() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("backup.json");
+        write_records(&path, Format::Json, &sample()).unwrap();
+
+        let value: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(value[0]["path"], "notes/2024-01-15.md");
+        assert_eq!(value[1]["modified"], 1705400000);
+
+        assert_eq!(read_records(&path, Format::detect(&path)).unwrap(), sample());
+    }
+
+    #[test]
+    fn csv_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("backup.csv");
+        write_records(&path, Format::Csv, &sample()).unwrap();
+        assert_eq!(read_records(&path, Format::Csv).unwrap(), sample());
+    }
+}
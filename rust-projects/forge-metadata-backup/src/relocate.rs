@@ -0,0 +1,251 @@
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use crate::exclude::{self, Excludes};
+
+/// `--remap OLD=NEW`: backup paths starting with `OLD` are read as starting
+/// with `NEW` instead, e.g. `Notes=notes` after a folder rename
+#[derive(Clone, Debug, PartialEq)]
+pub struct Remap {
+    from: String,
+    to: String,
+}
+
+impl std::str::FromStr for Remap {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('=') {
+            Some((from, to)) if !from.is_empty() => Ok(Self {
+                from: from.trim_end_matches('/').to_string(),
+                to: to.trim_end_matches('/').to_string(),
+            }),
+            _ => Err(format!("expected OLD_PREFIX=NEW_PREFIX, got '{}'", s)),
+        }
+    }
+}
+
+impl Remap {
+    /// `path` with the prefix swapped, if it starts with it at a component
+    /// boundary (`notes` remaps `notes/a.md` but not `notes-old/a.md`)
+    fn apply(&self, path: &str) -> Option<String> {
+        let rest = path.strip_prefix(&self.from)?;
+        if !rest.is_empty() && !rest.starts_with('/') {
+            return None;
+        }
+        let joined = format!("{}{}", self.to, rest);
+        // Remapping a prefix to nothing leaves a relative path
+        Some(joined.strip_prefix('/').filter(|_| self.to.is_empty()).unwrap_or(&joined).to_string())
+    }
+}
+
+/// A backup path after the first matching remap, in the order given
+pub fn remap_path(remaps: &[Remap], path: &str) -> String {
+    remaps.iter().find_map(|r| r.apply(path)).unwrap_or_else(|| path.to_string())
+}
+
+/// Where a backup row's file was found
+#[derive(Debug, PartialEq)]
+pub enum Resolution {
+    /// At its (remapped) path
+    Exact(PathBuf),
+    /// Moved: the only file elsewhere with the same name
+    ByFilename(PathBuf),
+    /// Moved, but several files share its name, or another row found the
+    /// same file by name, so none is used
+    Ambiguous(Vec<PathBuf>),
+    Missing,
+}
+
+/// Files under the root by file name, for finding ones moved between
+/// folders since the backup. Files a backup row already points at exactly
+/// are left out, so a moved file can't take over another row's file.
+pub struct FilenameIndex {
+    by_name: HashMap<OsString, Vec<PathBuf>>,
+}
+
+impl FilenameIndex {
+    pub fn build(dir: &Path, excludes: &Excludes, claimed: &HashSet<PathBuf>) -> Self {
+        let mut by_name: HashMap<OsString, Vec<PathBuf>> = HashMap::new();
+        for path in exclude::collect_files(dir, excludes, false) {
+            if claimed.contains(&path) {
+                continue;
+            }
+            if let Some(name) = path.file_name() {
+                by_name.entry(name.to_os_string()).or_default().push(path);
+            }
+        }
+        Self { by_name }
+    }
+
+    fn lookup(&self, name: &std::ffi::OsStr) -> Resolution {
+        match self.by_name.get(name).map(Vec::as_slice) {
+            Some([only]) => Resolution::ByFilename(only.clone()),
+            Some(several) if !several.is_empty() => Resolution::Ambiguous(several.to_vec()),
+            _ => Resolution::Missing,
+        }
+    }
+}
+
+/// Find a backup row's file under `dir`: at its remapped path, or failing
+/// that by file name when an index is given
+pub fn resolve(dir: &Path, path: &str, index: Option<&FilenameIndex>) -> Resolution {
+    let full_path = dir.join(path);
+    if full_path.exists() {
+        return Resolution::Exact(full_path);
+    }
+    match (index, full_path.file_name()) {
+        (Some(index), Some(name)) => index.lookup(name),
+        _ => Resolution::Missing,
+    }
+}
+
+/// `resolve` every row. A file found by name for more than one row can't be
+/// told to be any one row's, so all of those rows are ambiguous.
+pub fn resolve_all(dir: &Path, paths: &[&str], index: Option<&FilenameIndex>) -> Vec<Resolution> {
+    let resolutions: Vec<Resolution> = paths.iter().map(|path| resolve(dir, path, index)).collect();
+    let mut found: HashMap<&Path, usize> = HashMap::new();
+    for resolution in &resolutions {
+        if let Resolution::ByFilename(path) = resolution {
+            *found.entry(path).or_default() += 1;
+        }
+    }
+    let shared: HashSet<PathBuf> = found
+        .into_iter()
+        .filter(|(_, rows)| *rows > 1)
+        .map(|(path, _)| path.to_path_buf())
+        .collect();
+
+    resolutions
+        .into_iter()
+        .map(|resolution| match resolution {
+            Resolution::ByFilename(path) if shared.contains(&path) => Resolution::Ambiguous(vec![path]),
+            other => other,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// The tree since a backup of `notes/moved.md`, `notes/kept.md` and
+    /// `a/dup.md`: `moved.md` has gone to `archive/2024/`, and two files
+    /// now share the name `dup.md`
+    fn fixture_tree() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for file in [
+            "notes/kept.md",
+            "archive/2024/moved.md",
+            "projects/one/dup.md",
+            "projects/two/dup.md",
+            "archive/kept.md",
+            ".git/moved.md",
+        ] {
+            let path = dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "x").unwrap();
+        }
+        dir
+    }
+
+    fn index(dir: &Path, claimed: &[&str]) -> FilenameIndex {
+        let claimed = claimed.iter().map(|p| dir.join(p)).collect();
+        FilenameIndex::build(dir, &Excludes::new(&[], true).unwrap(), &claimed)
+    }
+
+    fn remap(s: &str) -> Remap {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn remap_swaps_prefix_at_component_boundary() {
+        let remaps = [remap("/Users/will/Forge=/home/will/Forge"), remap("notes/=journal")];
+        assert_eq!(remap_path(&remaps, "/Users/will/Forge/notes/a.md"), "/home/will/Forge/notes/a.md");
+        assert_eq!(remap_path(&remaps, "notes/a.md"), "journal/a.md");
+        assert_eq!(remap_path(&remaps, "notes-old/a.md"), "notes-old/a.md");
+        assert_eq!(remap_path(&remaps, "other/a.md"), "other/a.md");
+    }
+
+    #[test]
+    fn remap_to_nothing_leaves_a_relative_path() {
+        assert_eq!(remap_path(&[remap("/Users/will/Forge=")], "/Users/will/Forge/notes/a.md"), "notes/a.md");
+    }
+
+    #[test]
+    fn remap_needs_an_old_prefix() {
+        assert!("notes".parse::<Remap>().is_err());
+        assert!("=notes".parse::<Remap>().is_err());
+    }
+
+    #[test]
+    fn exact_path_wins() {
+        let tree = fixture_tree();
+        let index = index(tree.path(), &["notes/kept.md"]);
+        assert_eq!(
+            resolve(tree.path(), "notes/kept.md", Some(&index)),
+            Resolution::Exact(tree.path().join("notes/kept.md"))
+        );
+    }
+
+    #[test]
+    fn moved_file_found_by_unique_name() {
+        let tree = fixture_tree();
+        let index = index(tree.path(), &[]);
+        // .git/moved.md is excluded, so archive/2024/moved.md is the only one
+        assert_eq!(
+            resolve(tree.path(), "notes/moved.md", Some(&index)),
+            Resolution::ByFilename(tree.path().join("archive/2024/moved.md"))
+        );
+        assert_eq!(resolve(tree.path(), "notes/moved.md", None), Resolution::Missing);
+        assert_eq!(resolve(tree.path(), "notes/deleted.md", Some(&index)), Resolution::Missing);
+    }
+
+    #[test]
+    fn duplicated_name_is_ambiguous() {
+        let tree = fixture_tree();
+        let index = index(tree.path(), &[]);
+        let Resolution::Ambiguous(mut candidates) = resolve(tree.path(), "a/dup.md", Some(&index)) else {
+            panic!("expected an ambiguous match");
+        };
+        candidates.sort();
+        assert_eq!(
+            candidates,
+            vec![tree.path().join("projects/one/dup.md"), tree.path().join("projects/two/dup.md")]
+        );
+    }
+
+    #[test]
+    fn files_claimed_by_exact_rows_are_not_candidates() {
+        let tree = fixture_tree();
+        // notes/kept.md is restored exactly, leaving archive/kept.md as the
+        // only candidate for a row whose file has gone
+        let index = index(tree.path(), &["notes/kept.md"]);
+        assert_eq!(
+            resolve(tree.path(), "old/kept.md", Some(&index)),
+            Resolution::ByFilename(tree.path().join("archive/kept.md"))
+        );
+    }
+
+    #[test]
+    fn file_found_for_two_rows_is_ambiguous() {
+        let tree = fixture_tree();
+        let index = index(tree.path(), &[]);
+        let moved = tree.path().join("archive/2024/moved.md");
+        assert_eq!(
+            resolve_all(tree.path(), &["notes/moved.md", "drafts/moved.md", "notes/kept.md"], Some(&index)),
+            vec![
+                Resolution::Ambiguous(vec![moved.clone()]),
+                Resolution::Ambiguous(vec![moved]),
+                Resolution::Exact(tree.path().join("notes/kept.md")),
+            ]
+        );
+        // One row on its own still gets it
+        assert_eq!(
+            resolve_all(tree.path(), &["notes/moved.md"], Some(&index)),
+            vec![Resolution::ByFilename(tree.path().join("archive/2024/moved.md"))]
+        );
+    }
+}
@@ -64,10 +64,8 @@ struct ContinuumSession {
 
 #[derive(Debug, Deserialize)]
 struct ContinuumMessage {
-    id: u32,
     role: String,
     content: String,
-    timestamp: String,
 }
 
 // Grok export structures (for preview)
@@ -1,11 +1,12 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use clap::Parser;
+use continuum_core::Saved;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 // ============================================================================
 // Skill matching
@@ -93,6 +94,11 @@ struct Cli {
     /// Non-interactive mode - import all conversations
     #[arg(long)]
     all: bool,
+
+    /// Also import regenerated/abandoned branches (newer exports only),
+    /// appended after the main thread and tagged with a branch id
+    #[arg(long)]
+    include_branches: bool,
 }
 
 // Grok export structures
@@ -114,6 +120,9 @@ struct Conversation {
     create_time: String,
     #[serde(default)]
     media_types: Vec<String>,
+    /// Newer exports only: first response of the threaded conversation
+    #[serde(default)]
+    root_response_id: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -123,9 +132,20 @@ struct ResponseWrapper {
 
 #[derive(Debug, Deserialize)]
 struct Response {
+    #[serde(rename = "_id", default)]
+    id: Option<String>,
     message: String,
     sender: String,
     create_time: MongoDate,
+    /// Newer exports only: the response this one replies to
+    #[serde(default)]
+    parent_response_id: Option<String>,
+}
+
+impl Response {
+    fn millis(&self) -> Option<i64> {
+        self.create_time.date.number_long.parse().ok()
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -147,6 +167,9 @@ struct ContinuumMessage {
     role: String,
     content: String,
     timestamp: String,
+    /// Set on messages off the canonical thread (--include-branches)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    branch: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -211,10 +234,19 @@ fn main() -> Result<()> {
 
     for idx in selected {
         let conv_wrapper = &export.conversations[idx];
-        match import_conversation(conv_wrapper, &output_dir) {
-            Ok(_) => {
+        match import_conversation(conv_wrapper, &output_dir, cli.include_branches) {
+            Ok(saved) => {
                 success_count += 1;
-                println!("  ✓ Imported: {}", conv_wrapper.conversation.title);
+                let title = &conv_wrapper.conversation.title;
+                match saved {
+                    Some(Saved::Skipped(dir)) => {
+                        println!("  = Already imported: {} ({})", title, dir.display())
+                    }
+                    Some(Saved::Merged { dir, added }) => {
+                        println!("  + Merged {} new messages: {} ({})", added, title, dir.display())
+                    }
+                    _ => println!("  ✓ Imported: {}", title),
+                }
             }
             Err(e) => {
                 error_count += 1;
@@ -249,13 +281,16 @@ fn select_conversations(conversations: &[ConversationWrapper]) -> Result<Vec<usi
             println!("Media: {}", conv.media_types.join(", "));
         }
 
-        println!("Messages: {}", conv_wrapper.responses.len());
+        let thread = order_responses(conv_wrapper, false);
+        println!("Messages: {}", thread.len());
+        if thread.len() < conv_wrapper.responses.len() {
+            println!("Branches: {} messages off the main thread", conv_wrapper.responses.len() - thread.len());
+        }
         println!();
 
         // Show first 3 messages as preview
-        let preview_count = conv_wrapper.responses.len().min(3);
-        for (i, resp_wrapper) in conv_wrapper.responses.iter().take(preview_count).enumerate() {
-            let resp = &resp_wrapper.response;
+        let preview_count = thread.len().min(3);
+        for (i, (resp, _)) in thread.iter().take(preview_count).enumerate() {
             let role = match resp.sender.as_str() {
                 "human" => "USER",
                 "assistant" => "ASSISTANT",
@@ -275,8 +310,8 @@ fn select_conversations(conversations: &[ConversationWrapper]) -> Result<Vec<usi
             }
         }
 
-        if conv_wrapper.responses.len() > preview_count {
-            println!("  ... ({} more messages)", conv_wrapper.responses.len() - preview_count);
+        if thread.len() > preview_count {
+            println!("  ... ({} more messages)", thread.len() - preview_count);
         }
 
         println!();
@@ -313,7 +348,10 @@ fn select_conversations(conversations: &[ConversationWrapper]) -> Result<Vec<usi
     Ok(selected)
 }
 
-fn import_conversation(conv_wrapper: &ConversationWrapper, output_dir: &PathBuf) -> Result<()> {
+/// Write one conversation, or `None` if it has no messages. Conversations
+/// already held by another session on the same day are skipped or merged
+/// into it.
+fn import_conversation(conv_wrapper: &ConversationWrapper, output_dir: &Path, include_branches: bool) -> Result<Option<Saved>> {
     let conv = &conv_wrapper.conversation;
 
     // Parse the created_at timestamp
@@ -321,27 +359,13 @@ fn import_conversation(conv_wrapper: &ConversationWrapper, output_dir: &PathBuf)
         .context("Invalid timestamp")?;
     let date_str = datetime.format("%Y-%m-%d").to_string();
 
-    // Create session directory
-    let session_dir = output_dir.join(&date_str).join(&conv.id);
-    fs::create_dir_all(&session_dir)
-        .with_context(|| format!("Failed to create {:?}", session_dir))?;
-
     // Convert messages
-    let messages = convert_messages(&conv_wrapper.responses)?;
+    let messages = convert_messages(&order_responses(conv_wrapper, include_branches))?;
 
     if messages.is_empty() {
-        return Ok(()); // Skip empty conversations
+        return Ok(None); // Skip empty conversations
     }
 
-    // Write messages.jsonl
-    let messages_path = session_dir.join("messages.jsonl");
-    let mut jsonl_content = String::new();
-    for msg in &messages {
-        jsonl_content.push_str(&serde_json::to_string(msg)?);
-        jsonl_content.push('\n');
-    }
-    fs::write(&messages_path, jsonl_content)?;
-
     // Find last message timestamp for end_time
     let end_time = messages.last()
         .map(|msg| msg.timestamp.clone());
@@ -362,19 +386,92 @@ fn import_conversation(conv_wrapper: &ConversationWrapper, output_dir: &PathBuf)
         skills,
     };
 
-    let session_path = session_dir.join("session.json");
-    let session_json = serde_json::to_string_pretty(&session)?;
-    fs::write(&session_path, session_json)?;
+    // Writes messages.jsonl, session.json and hashes.json
+    let saved = continuum_core::save_session(&output_dir.join(&date_str), &conv.id, &messages, &session)?;
+    Ok(Some(saved))
+}
 
-    Ok(())
+/// Whether a conversation uses the newer threaded export shape
+/// (`root_response_id` / `parent_response_id`) rather than a flat list
+fn is_threaded(conv_wrapper: &ConversationWrapper) -> bool {
+    conv_wrapper.conversation.root_response_id.is_some()
+        || conv_wrapper.responses.iter().any(|r| r.response.parent_response_id.is_some())
 }
 
-fn convert_messages(responses: &[ResponseWrapper]) -> Result<Vec<ContinuumMessage>> {
+/// Responses in reading order, each with its branch id (None = main thread).
+///
+/// Flat exports keep their file order. Threaded exports follow parent links
+/// back from the most recent leaf; everything else is a regenerated or
+/// abandoned branch, dropped unless `include_branches` is set, in which case
+/// it is appended grouped by branch (the id of the branch's first response).
+fn order_responses(conv_wrapper: &ConversationWrapper, include_branches: bool) -> Vec<(&Response, Option<String>)> {
+    let responses: Vec<&Response> = conv_wrapper.responses.iter().map(|r| &r.response).collect();
+    if !is_threaded(conv_wrapper) {
+        return responses.into_iter().map(|r| (r, None)).collect();
+    }
+
+    let by_id: HashMap<&str, &Response> = responses.iter()
+        .filter_map(|r| r.id.as_deref().map(|id| (id, *r)))
+        .collect();
+    let parents: HashSet<&str> = responses.iter()
+        .filter_map(|r| r.parent_response_id.as_deref())
+        .collect();
+
+    // Latest leaf: the newest response nothing replies to
+    let leaf = responses.iter()
+        .filter(|r| r.id.as_deref().is_some_and(|id| !parents.contains(id)))
+        .max_by_key(|r| r.millis().unwrap_or(i64::MIN));
+
+    let mut canonical: Vec<&Response> = Vec::new();
+    let mut on_path: HashSet<&str> = HashSet::new();
+    let mut current = leaf.copied();
+    while let Some(resp) = current {
+        let id = resp.id.as_deref().unwrap_or_default();
+        if !on_path.insert(id) {
+            break; // cycle in a malformed export
+        }
+        canonical.push(resp);
+        current = resp.parent_response_id.as_deref().and_then(|p| by_id.get(p).copied());
+    }
+    canonical.reverse();
+
+    let mut ordered: Vec<(&Response, Option<String>)> = canonical.into_iter().map(|r| (r, None)).collect();
+    if !include_branches {
+        return ordered;
+    }
+
+    // Branch id: the off-thread ancestor closest to where the branch forks
+    let branch_root = |resp: &Response| -> String {
+        let mut root = resp;
+        let mut seen = HashSet::new();
+        while let Some(parent) = root.parent_response_id.as_deref().and_then(|p| by_id.get(p)) {
+            let parent_id = parent.id.as_deref().unwrap_or_default();
+            if on_path.contains(parent_id) || !seen.insert(parent_id) {
+                break;
+            }
+            root = parent;
+        }
+        root.id.clone().unwrap_or_default()
+    };
+
+    let mut branches: Vec<(i64, String, i64, &Response)> = responses.iter()
+        .filter(|r| r.id.as_deref().is_none_or(|id| !on_path.contains(id)))
+        .map(|r| {
+            let branch = branch_root(r);
+            let branch_time = by_id.get(branch.as_str()).and_then(|b| b.millis()).unwrap_or(i64::MAX);
+            (branch_time, branch, r.millis().unwrap_or(i64::MAX), *r)
+        })
+        .collect();
+    branches.sort_by(|a, b| (a.0, &a.1, a.2).cmp(&(b.0, &b.1, b.2)));
+    ordered.extend(branches.into_iter().map(|(_, branch, _, r)| (r, Some(branch))));
+    ordered
+}
+
+fn convert_messages(responses: &[(&Response, Option<String>)]) -> Result<Vec<ContinuumMessage>> {
     let mut messages = Vec::new();
     let mut msg_id = 1u32;
 
-    for resp_wrapper in responses {
-        let resp = &resp_wrapper.response;
+    for (resp, branch) in responses {
 
         // Skip empty messages
         if resp.message.trim().is_empty() {
@@ -400,9 +497,89 @@ fn convert_messages(responses: &[ResponseWrapper]) -> Result<Vec<ContinuumMessag
             role: role.to_string(),
             content: resp.message.clone(),
             timestamp,
+            branch: branch.clone(),
         });
         msg_id += 1;
     }
 
     Ok(messages)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load_fixture(name: &str) -> ConversationWrapper {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name);
+        let export: GrokExport = serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        export.conversations.into_iter().next().unwrap()
+    }
+
+    fn thread(conv: &ConversationWrapper, include_branches: bool) -> Vec<(String, Option<String>)> {
+        convert_messages(&order_responses(conv, include_branches))
+            .unwrap()
+            .into_iter()
+            .map(|m| (m.content, m.branch))
+            .collect()
+    }
+
+    fn contents(messages: &[(String, Option<String>)]) -> Vec<&str> {
+        messages.iter().map(|(content, _)| content.as_str()).collect()
+    }
+
+    #[test]
+    fn old_format_keeps_flat_order() {
+        let conv = load_fixture("old-format.json");
+        assert!(!is_threaded(&conv));
+
+        let messages = thread(&conv, true);
+        assert_eq!(contents(&messages), vec![
+            "What is a monad?",
+            "A monoid in the category of endofunctors.",
+            "Less jargon please.",
+            "A way to chain computations that carry context.",
+        ]);
+        assert!(messages.iter().all(|(_, branch)| branch.is_none()));
+    }
+
+    #[test]
+    fn new_format_linear_follows_parent_links() {
+        let conv = load_fixture("new-format-linear.json");
+        assert!(is_threaded(&conv));
+
+        let messages = thread(&conv, true);
+        assert_eq!(contents(&messages), vec![
+            "What is a monad?",
+            "A monoid in the category of endofunctors.",
+            "Less jargon please.",
+            "A way to chain computations that carry context.",
+        ]);
+        assert!(messages.iter().all(|(_, branch)| branch.is_none()));
+    }
+
+    #[test]
+    fn new_format_branched_keeps_latest_thread_only() {
+        let conv = load_fixture("new-format-branched.json");
+
+        let messages = thread(&conv, false);
+        assert_eq!(contents(&messages), vec![
+            "Suggest a name for a cat.",
+            "Marmalade.",
+            "Why that one?",
+            "It suits a ginger cat.",
+        ]);
+    }
+
+    #[test]
+    fn include_branches_appends_tagged_branches() {
+        let conv = load_fixture("new-format-branched.json");
+
+        let messages = thread(&conv, true);
+        assert_eq!(messages.len(), 6);
+        assert_eq!(&messages[4..], &[
+            ("Whiskers.".to_string(), Some("a1".to_string())),
+            ("Something less common?".to_string(), Some("a1".to_string())),
+        ]);
+        assert!(messages[..4].iter().all(|(_, branch)| branch.is_none()));
+    }
+}
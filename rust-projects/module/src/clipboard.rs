@@ -0,0 +1,47 @@
+use anyhow::{bail, Context, Result};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Put `text` on the system clipboard: `pbcopy` on macOS, `wl-copy` on
+/// Linux (falling back to `xclip` outside Wayland)
+pub fn copy(text: &str) -> Result<()> {
+    #[cfg(target_os = "macos")]
+    {
+        pipe_to("pbcopy", &[], text)
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            pipe_to("wl-copy", &[], text)
+        } else {
+            pipe_to("xclip", &["-selection", "clipboard"], text)
+        }
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        let _ = text;
+        bail!("Clipboard output isn't supported on this platform")
+    }
+}
+
+#[cfg_attr(not(any(target_os = "macos", target_os = "linux")), allow(dead_code))]
+fn pipe_to(program: &str, args: &[&str], text: &str) -> Result<()> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run {} (is it installed?)", program))?;
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(text.as_bytes())
+        .with_context(|| format!("Failed to write to {}", program))?;
+    let status = child.wait().with_context(|| format!("Failed to wait for {}", program))?;
+    if !status.success() {
+        bail!("{} exited with {}", program, status);
+    }
+    Ok(())
+}
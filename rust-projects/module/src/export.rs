@@ -1,11 +1,14 @@
 use anyhow::{Context, Result};
 use std::fs;
-use std::io::Write;
+use std::io::{self, BufRead, Write};
 use std::path::{Path, PathBuf};
 use zip::write::SimpleFileOptions;
 use zip::ZipWriter;
 
-use crate::scrolls::{advisor_scrolls, read_scroll};
+use crate::clipboard;
+use crate::redact::redact;
+use crate::scrolls::{advisor_scrolls, persona_preamble, read_scroll};
+use crate::split::split_bundle;
 
 /// Extract just the filename from a scroll name (handles ~/... paths)
 fn scroll_display_name(name: &str) -> &str {
@@ -15,14 +18,46 @@ fn scroll_display_name(name: &str) -> &str {
         .unwrap_or(name)
 }
 
+/// Read a scroll and strip any sections tagged for redaction
+fn load_scroll(name: &str, redact_tags: &[String]) -> Result<String> {
+    let content = read_scroll(name)?;
+    let (content, warnings) = redact(&content, redact_tags);
+    for warning in warnings {
+        println!("⚠ {}: {}", scroll_display_name(name), warning);
+    }
+    Ok(content)
+}
+
 /// Run the export command
-pub fn run(advisor: &str, output: Option<&str>, zip: bool) -> Result<()> {
+pub fn run(
+    advisor: &str,
+    output: Option<&str>,
+    zip: bool,
+    redact_tags: &[String],
+    to_clipboard: bool,
+    split: Option<usize>,
+) -> Result<()> {
     let scrolls = advisor_scrolls(advisor);
 
     println!("Exporting scrolls for {} advisor:", advisor);
     for scroll in &scrolls {
         println!("  • {}", scroll);
     }
+    if !redact_tags.is_empty() {
+        println!("Redacting: {}", redact_tags.join(", "));
+    }
+
+    let preamble = match persona_preamble(advisor)? {
+        Some((source, content)) => {
+            println!("Preamble: {}", source);
+            let (content, warnings) = redact(&content, redact_tags);
+            for warning in warnings {
+                println!("⚠ {}: {}", source, warning);
+            }
+            Some(content)
+        }
+        None => None,
+    };
     println!();
 
     let output_dir = match output {
@@ -32,15 +67,47 @@ pub fn run(advisor: &str, output: Option<&str>, zip: bool) -> Result<()> {
             .join("Downloads"),
     };
 
-    if zip {
-        export_zip(advisor, &scrolls, &output_dir)
+    if to_clipboard || split.is_some() {
+        let scrolls = scrolls
+            .iter()
+            .map(|s| Ok((scroll_display_name(s).to_string(), load_scroll(s, redact_tags)?)))
+            .collect::<Result<Vec<_>>>()?;
+        let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+        let chunks = assemble(advisor, &date, preamble.as_deref(), &scrolls);
+        let parts = match split {
+            Some(max_bytes) => split_bundle(&chunks, max_bytes),
+            None => vec![chunks.concat()],
+        };
+        if let Some(max_bytes) = split {
+            for (i, part) in parts.iter().enumerate().filter(|(_, p)| p.len() > max_bytes) {
+                println!(
+                    "⚠ Part {} is {} bytes, over the {}-byte limit (a scroll is never cut)",
+                    i + 1,
+                    part.len(),
+                    max_bytes
+                );
+            }
+        }
+        if to_clipboard {
+            copy_parts(advisor, &parts)
+        } else {
+            write_parts(advisor, &date, &parts, &output_dir)
+        }
+    } else if zip {
+        export_zip(advisor, &scrolls, &output_dir, redact_tags, preamble.as_deref())
     } else {
-        export_directory(advisor, &scrolls, &output_dir)
+        export_directory(advisor, &scrolls, &output_dir, redact_tags, preamble.as_deref())
     }
 }
 
-/// Export scrolls to a directory
-fn export_directory(advisor: &str, scrolls: &[&str], output_dir: &PathBuf) -> Result<()> {
+/// Export scrolls to a directory; the preamble, if any, opens the README
+fn export_directory(
+    advisor: &str,
+    scrolls: &[&str],
+    output_dir: &PathBuf,
+    redact_tags: &[String],
+    preamble: Option<&str>,
+) -> Result<()> {
     let timestamp = chrono::Local::now().format("%Y-%m-%d");
     let bundle_name = format!("{}-scrolls-{}", advisor, timestamp);
     let bundle_dir = output_dir.join(&bundle_name);
@@ -49,7 +116,7 @@ fn export_directory(advisor: &str, scrolls: &[&str], output_dir: &PathBuf) -> Re
         .with_context(|| format!("Failed to create directory: {}", bundle_dir.display()))?;
 
     for scroll in scrolls {
-        let content = read_scroll(scroll)?;
+        let content = load_scroll(scroll, redact_tags)?;
         let filename = scroll_display_name(scroll);
         let dest = bundle_dir.join(filename);
         fs::write(&dest, &content)
@@ -84,6 +151,10 @@ fn export_directory(advisor: &str, scrolls: &[&str], output_dir: &PathBuf) -> Re
         scrolls.iter().map(|s| format!("- {}", scroll_display_name(s))).collect::<Vec<_>>().join("\n"),
         usage_steps
     );
+    let readme = match preamble {
+        Some(preamble) => format!("{}\n\n---\n\n{}", preamble, readme),
+        None => readme,
+    };
     fs::write(bundle_dir.join("README.md"), readme)?;
 
     println!("✓ Exported to: {}", bundle_dir.display());
@@ -93,8 +164,88 @@ fn export_directory(advisor: &str, scrolls: &[&str], output_dir: &PathBuf) -> Re
     Ok(())
 }
 
-/// Export scrolls to a zip file
-fn export_zip(advisor: &str, scrolls: &[&str], output_dir: &PathBuf) -> Result<()> {
+/// The bundle as a single markdown text, in chunks that concatenate to it:
+/// one per scroll, each opening with the `# FILENAME.md` header `restore`
+/// splits on. The preamble and a bundle header line, which `restore` skips,
+/// lead the first chunk so no part of a split bundle is header alone.
+fn assemble(advisor: &str, date: &str, preamble: Option<&str>, scrolls: &[(String, String)]) -> Vec<String> {
+    let mut header = String::new();
+    if let Some(preamble) = preamble {
+        header.push_str(&format!("{}\n\n---\n\n", preamble.trim_end()));
+    }
+    header.push_str(&format!("{} scrolls bundle, exported {}\n\n", advisor.to_uppercase(), date));
+
+    let mut chunks: Vec<String> = scrolls
+        .iter()
+        .map(|(name, content)| format!("# {}\n\n{}\n\n", name, content.trim_end()))
+        .collect();
+    match chunks.first_mut() {
+        Some(first) => first.insert_str(0, &header),
+        None => chunks.push(header),
+    }
+    chunks
+}
+
+/// Write the assembled bundle as one markdown file, or one per part
+fn write_parts(advisor: &str, date: &str, parts: &[String], output_dir: &Path) -> Result<()> {
+    fs::create_dir_all(output_dir)
+        .with_context(|| format!("Failed to create directory: {}", output_dir.display()))?;
+
+    for (i, part) in parts.iter().enumerate() {
+        let filename = if parts.len() == 1 {
+            format!("{}-scrolls-{}.md", advisor, date)
+        } else {
+            format!("{}-scrolls-{}-part-{}-of-{}.md", advisor, date, i + 1, parts.len())
+        };
+        let dest = output_dir.join(filename);
+        fs::write(&dest, part).with_context(|| format!("Failed to write: {}", dest.display()))?;
+        println!("✓ Exported to: {} ({} bytes)", dest.display(), part.len());
+    }
+
+    println!();
+    if parts.len() == 1 {
+        println!("Paste this file to start your {} session.", advisor);
+    } else {
+        println!("Paste these {} parts in order to start your {} session.", parts.len(), advisor);
+    }
+    Ok(())
+}
+
+/// Copy the bundle to the clipboard, part by part with a keypress between
+fn copy_parts(advisor: &str, parts: &[String]) -> Result<()> {
+    let stdin = io::stdin();
+    for (i, part) in parts.iter().enumerate() {
+        if i > 0 {
+            print!("Press Enter to copy part {}/{} (q to stop): ", i + 1, parts.len());
+            io::stdout().flush()?;
+            let mut input = String::new();
+            if stdin.lock().read_line(&mut input)? == 0 || input.trim().eq_ignore_ascii_case("q") {
+                println!();
+                println!("Stopped after part {}/{}.", i, parts.len());
+                return Ok(());
+            }
+        }
+        clipboard::copy(part)?;
+        if parts.len() == 1 {
+            println!("✓ Copied bundle to clipboard ({} bytes)", part.len());
+        } else {
+            println!("✓ Copied part {}/{} to clipboard ({} bytes)", i + 1, parts.len(), part.len());
+        }
+    }
+
+    println!();
+    println!("Paste into your {} session.", advisor);
+    Ok(())
+}
+
+/// Export scrolls to a zip file; the preamble, if any, is the first entry
+fn export_zip(
+    advisor: &str,
+    scrolls: &[&str],
+    output_dir: &PathBuf,
+    redact_tags: &[String],
+    preamble: Option<&str>,
+) -> Result<()> {
     let timestamp = chrono::Local::now().format("%Y-%m-%d");
     let zip_name = format!("{}-scrolls-{}.zip", advisor, timestamp);
     let zip_path = output_dir.join(&zip_name);
@@ -106,8 +257,14 @@ fn export_zip(advisor: &str, scrolls: &[&str], output_dir: &PathBuf) -> Result<(
     let options = SimpleFileOptions::default()
         .compression_method(zip::CompressionMethod::Deflated);
 
+    if let Some(preamble) = preamble {
+        zip.start_file("PREAMBLE.md", options)?;
+        zip.write_all(preamble.as_bytes())?;
+        zip.write_all(b"\n")?;
+    }
+
     for scroll in scrolls {
-        let content = read_scroll(scroll)?;
+        let content = load_scroll(scroll, redact_tags)?;
         let filename = scroll_display_name(scroll);
         zip.start_file(filename, options)?;
         zip.write_all(content.as_bytes())?;
@@ -121,3 +278,45 @@ fn export_zip(advisor: &str, scrolls: &[&str], output_dir: &PathBuf) -> Result<(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::restore::read_bundle;
+
+    fn scrolls() -> Vec<(String, String)> {
+        vec![
+            ("WILLIAM-INDEX.md".to_string(), "# Index\n\nSee below.\n".to_string()),
+            ("SENECA-PROTOCOL.md".to_string(), "## Preamble\n\nYou are Seneca.\n".to_string()),
+        ]
+    }
+
+    #[test]
+    fn assembled_bundle_restores_each_scroll() {
+        let chunks = assemble("seneca", "2026-02-10", Some("You are Seneca.\n"), &scrolls());
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks[0].starts_with("You are Seneca.\n\n---\n\nSENECA scrolls bundle, exported 2026-02-10\n\n# WILLIAM-INDEX.md\n"));
+        assert!(chunks[1].starts_with("# SENECA-PROTOCOL.md\n"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("seneca-scrolls-2026-02-10.md");
+        fs::write(&path, chunks.concat()).unwrap();
+        assert_eq!(read_bundle(&path).unwrap(), scrolls());
+    }
+
+    #[test]
+    fn split_parts_restore_like_the_whole() {
+        let chunks = assemble("seneca", "2026-02-10", None, &scrolls());
+        let parts = split_bundle(&chunks, chunks[0].len() + 50);
+        assert_eq!(parts.len(), 2);
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut restored = Vec::new();
+        for (i, part) in parts.iter().enumerate() {
+            let path = dir.path().join(format!("part-{}.md", i + 1));
+            fs::write(&path, part).unwrap();
+            restored.extend(read_bundle(&path).unwrap());
+        }
+        assert_eq!(restored, scrolls());
+    }
+}
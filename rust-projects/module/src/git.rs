@@ -0,0 +1,292 @@
+//! Optional git history for the scrolls directory.
+//!
+//! When `~/Assistants/shared` is a git repository (see `module init-git`),
+//! each import commits the scrolls it touched, and `module history` reads
+//! the log back. Without git, or outside a repository, both quietly do
+//! nothing so the tool behaves as before.
+
+use anyhow::{bail, Context, Result};
+use std::path::Path;
+use std::process::Command;
+
+/// Field and record separators for `git log` output
+const FIELD_SEP: char = '\u{1f}';
+const RECORD_SEP: char = '\u{1e}';
+
+#[derive(Debug, PartialEq)]
+pub struct HistoryEntry {
+    pub hash: String,
+    pub date: String,
+    pub advisor: String,
+    pub summary: String,
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<std::process::Output> {
+    let mut command = Command::new("git");
+    command.arg("-C").arg(dir);
+    // Tests commit without depending on the machine's global git config
+    #[cfg(test)]
+    command.args(["-c", "user.name=module test", "-c", "user.email=module@test"]);
+    command.args(args).output().context("Failed to run git")
+}
+
+fn git_ok(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = git(dir, args)?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Whether `dir` is the top of its own git work tree. A directory that
+/// merely sits inside some other repository (a dotfiles checkout, say)
+/// doesn't count. False if git isn't installed.
+pub fn is_repo(dir: &Path) -> bool {
+    let Ok(toplevel) = git_ok(dir, &["rev-parse", "--show-toplevel"]) else {
+        return false;
+    };
+    match (Path::new(toplevel.trim()).canonicalize(), dir.canonicalize()) {
+        (Ok(toplevel), Ok(dir)) => toplevel == dir,
+        _ => false,
+    }
+}
+
+/// Make `dir` a repository and commit the current scrolls as a baseline
+pub fn init(dir: &Path) -> Result<()> {
+    if is_repo(dir) {
+        bail!("{} is already a git repository", dir.display());
+    }
+    git_ok(dir, &["init", "--quiet"])?;
+    git_ok(dir, &["add", "--all"])?;
+    git_ok(dir, &["commit", "--quiet", "--allow-empty", "-m", "Initial scroll snapshot"])?;
+    Ok(())
+}
+
+/// Commit `paths` (relative to `dir`) if any of them changed. Returns the
+/// short hash of the new commit, or None when there was nothing to commit.
+pub fn commit_paths(dir: &Path, paths: &[String], message: &str) -> Result<Option<String>> {
+    let mut add = vec!["add", "--"];
+    add.extend(paths.iter().map(String::as_str));
+    git_ok(dir, &add)?;
+
+    let mut diff = vec!["diff", "--cached", "--quiet", "--"];
+    diff.extend(paths.iter().map(String::as_str));
+    if git(dir, &diff)?.status.success() {
+        return Ok(None);
+    }
+
+    let mut commit = vec!["commit", "--quiet", "-m", message, "--"];
+    commit.extend(paths.iter().map(String::as_str));
+    git_ok(dir, &commit)?;
+    Ok(Some(git_ok(dir, &["rev-parse", "--short", "HEAD"])?.trim().to_string()))
+}
+
+/// Condensed log, newest first, optionally limited to one module's file
+pub fn history(dir: &Path, module: Option<&str>, limit: usize) -> Result<Vec<HistoryEntry>> {
+    let format = format!("--pretty=format:%h{0}%ad{0}%s{0}%b{1}", FIELD_SEP, RECORD_SEP);
+    let count = format!("-n{}", limit);
+    let mut args = vec!["log", "--date=short", format.as_str(), count.as_str()];
+    if let Some(module) = module {
+        args.extend(["--", module]);
+    }
+    Ok(parse_log(&git_ok(dir, &args)?))
+}
+
+/// Parse `git log` output in the `history` pretty format
+fn parse_log(output: &str) -> Vec<HistoryEntry> {
+    output
+        .split(RECORD_SEP)
+        .filter(|record| !record.trim().is_empty())
+        .filter_map(|record| {
+            let mut fields = record.trim_start_matches('\n').splitn(4, FIELD_SEP);
+            let hash = fields.next()?.to_string();
+            let date = fields.next()?.to_string();
+            let summary = fields.next()?.to_string();
+            let body = fields.next().unwrap_or_default();
+            let advisor = body
+                .lines()
+                .find_map(|line| line.strip_prefix("Advisor: "))
+                .unwrap_or("-")
+                .trim()
+                .to_string();
+            Some(HistoryEntry { hash, date, advisor, summary })
+        })
+        .collect()
+}
+
+/// Commit message for an import: a one-line summary, then advisor,
+/// timestamp and modules, then the changelog entry verbatim.
+pub fn commit_message(modules: &[String], changelog_entry: Option<&str>, timestamp: &str) -> String {
+    let entry = changelog_entry.unwrap_or_default();
+    let advisor = changelog_advisor(entry).unwrap_or_else(|| "unknown".to_string());
+    let summary = changelog_summary(entry)
+        .unwrap_or_else(|| format!("Update {}", modules.join(", ")));
+
+    let mut message = format!(
+        "{}\n\nAdvisor: {}\nImported: {}\nModules: {}\n",
+        summary,
+        advisor,
+        timestamp,
+        modules.join(", ")
+    );
+    if !entry.trim().is_empty() {
+        message.push('\n');
+        message.push_str(entry.trim());
+        message.push('\n');
+    }
+    message
+}
+
+/// `**Advisor**: Seneca` → `Seneca`, ignoring the auto-generated placeholder
+fn changelog_advisor(entry: &str) -> Option<String> {
+    entry
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("**Advisor**:"))
+        .map(str::trim)
+        .filter(|advisor| !advisor.is_empty() && !advisor.starts_with('('))
+        .map(String::from)
+}
+
+/// The entry's heading title (`### 2026-02-10 — Title` → `Title`), or else
+/// its first bullet
+fn changelog_summary(entry: &str) -> Option<String> {
+    let heading = entry
+        .lines()
+        .find_map(|line| line.trim().strip_prefix('#').map(|h| h.trim_start_matches('#').trim()))
+        .map(|h| h.split_once(" — ").map_or(h, |(_, title)| title).trim())
+        .filter(|title| !title.is_empty());
+    let bullet = || {
+        entry
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("- "))
+            .map(str::trim)
+    };
+    heading.or_else(bullet).map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const ENTRY: &str = "### 2026-02-10 — Sleep routine revised\n\n**Advisor**: Seneca\n\
+                         **Modules changed**: WILLIAM-LIFESTYLE.md\n\n- Moved bedtime earlier\n\n---";
+
+    #[test]
+    fn message_from_changelog_entry() {
+        let modules = vec!["WILLIAM-LIFESTYLE.md".to_string(), "WILLIAM-CHANGELOG.md".to_string()];
+        let message = commit_message(&modules, Some(ENTRY), "2026-02-10 21:04");
+        assert_eq!(
+            message,
+            format!(
+                "Sleep routine revised\n\nAdvisor: Seneca\nImported: 2026-02-10 21:04\n\
+                 Modules: WILLIAM-LIFESTYLE.md, WILLIAM-CHANGELOG.md\n\n{}\n",
+                ENTRY
+            )
+        );
+    }
+
+    #[test]
+    fn message_without_changelog_entry() {
+        let modules = vec!["WILLIAM-SOCIAL.md".to_string()];
+        let message = commit_message(&modules, None, "2026-02-10 21:04");
+        assert_eq!(
+            message,
+            "Update WILLIAM-SOCIAL.md\n\nAdvisor: unknown\nImported: 2026-02-10 21:04\nModules: WILLIAM-SOCIAL.md\n"
+        );
+    }
+
+    #[test]
+    fn auto_generated_placeholder_advisor_is_unknown() {
+        let entry = "### 2026-02-10 — Module import from conversation\n\n\
+                     **Advisor**: (imported via module tool)\n";
+        assert_eq!(changelog_advisor(entry), None);
+        assert_eq!(changelog_summary(entry).as_deref(), Some("Module import from conversation"));
+        assert_eq!(changelog_summary("- Only a bullet\n").as_deref(), Some("Only a bullet"));
+    }
+
+    #[test]
+    fn parses_log_records() {
+        let output = format!(
+            "a1b2c3d{0}2026-02-10{0}Sleep routine revised{0}Advisor: Seneca\nImported: x\n{1}\n\
+             e4f5a6b{0}2026-02-01{0}Initial scroll snapshot{0}{1}",
+            FIELD_SEP, RECORD_SEP
+        );
+        assert_eq!(
+            parse_log(&output),
+            vec![
+                HistoryEntry {
+                    hash: "a1b2c3d".into(),
+                    date: "2026-02-10".into(),
+                    advisor: "Seneca".into(),
+                    summary: "Sleep routine revised".into(),
+                },
+                HistoryEntry {
+                    hash: "e4f5a6b".into(),
+                    date: "2026-02-01".into(),
+                    advisor: "-".into(),
+                    summary: "Initial scroll snapshot".into(),
+                },
+            ]
+        );
+    }
+
+    /// Throwaway directory holding two scrolls
+    fn scratch_repo() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("WILLIAM-LIFESTYLE.md"), "# Lifestyle\n").unwrap();
+        fs::write(dir.path().join("WILLIAM-CHANGELOG.md"), "# Changelog\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn plain_directory_is_not_a_repo() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(!is_repo(dir.path()));
+    }
+
+    #[test]
+    fn directory_inside_another_repo_is_not_a_repo() {
+        let repo = tempfile::tempdir().unwrap();
+        git_ok(repo.path(), &["init", "--quiet"]).unwrap();
+        let scrolls = repo.path().join("Assistants/shared");
+        fs::create_dir_all(&scrolls).unwrap();
+
+        assert!(is_repo(repo.path()));
+        assert!(!is_repo(&scrolls));
+        init(&scrolls).unwrap();
+        assert!(is_repo(&scrolls));
+    }
+
+    #[test]
+    fn import_commits_show_up_in_history() {
+        let repo = scratch_repo();
+        let dir = repo.path();
+        init(dir).unwrap();
+        assert!(is_repo(dir));
+        assert!(init(dir).is_err());
+
+        fs::write(dir.join("WILLIAM-LIFESTYLE.md"), "# Lifestyle\n\nEarlier bedtime\n").unwrap();
+        fs::write(dir.join("WILLIAM-CHANGELOG.md"), format!("# Changelog\n\n{}\n", ENTRY)).unwrap();
+        let modules = vec!["WILLIAM-LIFESTYLE.md".to_string(), "WILLIAM-CHANGELOG.md".to_string()];
+        let message = commit_message(&modules, Some(ENTRY), "2026-02-10 21:04");
+        assert!(commit_paths(dir, &modules, &message).unwrap().is_some());
+
+        // Nothing changed since: no empty commit
+        assert_eq!(commit_paths(dir, &modules, &message).unwrap(), None);
+
+        let log = history(dir, None, 10).unwrap();
+        assert_eq!(log.len(), 2);
+        assert_eq!(log[0].advisor, "Seneca");
+        assert_eq!(log[0].summary, "Sleep routine revised");
+        assert_eq!(log[1].summary, "Initial scroll snapshot");
+
+        let lifestyle = history(dir, Some("WILLIAM-LIFESTYLE.md"), 10).unwrap();
+        assert_eq!(lifestyle.len(), 2);
+        assert!(history(dir, Some("WILLIAM-SOCIAL.md"), 10).unwrap().is_empty());
+    }
+}
@@ -1,12 +1,15 @@
 use anyhow::{Context, Result};
 use regex::Regex;
 use std::fs;
+use std::path::Path;
 
 use crate::changelog;
+use crate::git;
+use crate::review::{self, Outcome, TerminalReviewer};
 use crate::scrolls::{read_scroll, scrolls_dir, write_scroll};
 
 /// Run the import command
-pub fn run(file: &str, dry_run: bool) -> Result<()> {
+pub fn run(file: &str, dry_run: bool, ignore_missing: bool, review: bool) -> Result<()> {
     let content = fs::read_to_string(file)
         .with_context(|| format!("Failed to read file: {}", file))?;
 
@@ -18,7 +21,7 @@ pub fn run(file: &str, dry_run: bool) -> Result<()> {
     };
 
     // Extract module updates
-    let updates = extract_module_updates(&text)?;
+    let mut updates = extract_module_updates(&text)?;
 
     if updates.is_empty() {
         println!("No module updates found in file.");
@@ -36,9 +39,48 @@ pub fn run(file: &str, dry_run: bool) -> Result<()> {
     }
     println!();
 
+    // Pre-flight: every referenced module must already exist, so a typo
+    // can't leave the import partially applied
+    let missing = missing_modules(&scrolls_dir(), &updates);
+    if !missing.is_empty() {
+        if !ignore_missing {
+            println!("Unknown module(s) in {}:", scrolls_dir().display());
+            for name in &missing {
+                println!("  ✗ {}", name);
+            }
+            anyhow::bail!(
+                "{} module(s) not found; nothing applied (use --ignore-missing to skip them)",
+                missing.len()
+            );
+        }
+        for name in &missing {
+            println!("⚠ Skipping {} (module not found)", name);
+        }
+        println!();
+        updates.retain(|(name, _)| !missing.contains(name));
+        if updates.is_empty() {
+            println!("No remaining module updates to apply.");
+            return Ok(());
+        }
+    }
+
     // Extract changelog entry
     let changelog_entry = extract_changelog_entry(&text)?;
 
+    // Decide on every update before touching anything
+    let mut outcome: Option<Outcome> = None;
+    if review {
+        let current = |name: &str| read_scroll(name).unwrap_or_default();
+        let reviewed = review::review(updates, current, &mut TerminalReviewer)?;
+        println!();
+        if reviewed.applied.is_empty() {
+            println!("No updates accepted; nothing applied.");
+            return Ok(());
+        }
+        updates = reviewed.applied.clone();
+        outcome = Some(reviewed);
+    }
+
     if dry_run {
         println!("DRY RUN - would apply:");
         for (name, content) in &updates {
@@ -63,15 +105,23 @@ pub fn run(file: &str, dry_run: bool) -> Result<()> {
         }
 
         // Apply changelog
-        if let Some(entry) = changelog_entry {
-            changelog::append_entry(&entry)?;
-            println!("✓ Appended to WILLIAM-CHANGELOG.md");
-        } else {
-            // Auto-generate changelog entry
-            let auto_entry = changelog::generate_entry(&updates)?;
-            changelog::append_entry(&auto_entry)?;
-            println!("✓ Auto-generated changelog entry");
-        }
+        let entry = match changelog_entry {
+            Some(entry) => {
+                let entry = annotate(entry, outcome.as_ref());
+                changelog::append_entry(&entry)?;
+                println!("✓ Appended to WILLIAM-CHANGELOG.md");
+                entry
+            }
+            None => {
+                // Auto-generate changelog entry
+                let auto_entry = annotate(changelog::generate_entry(&updates)?, outcome.as_ref());
+                changelog::append_entry(&auto_entry)?;
+                println!("✓ Auto-generated changelog entry");
+                auto_entry
+            }
+        };
+
+        commit_import(&updates, &entry);
 
         println!();
         println!("Import complete. Scrolls updated at: {}", scrolls_dir().display());
@@ -80,6 +130,38 @@ pub fn run(file: &str, dry_run: bool) -> Result<()> {
     Ok(())
 }
 
+/// Record a review's applied/skipped split in the changelog entry
+fn annotate(entry: String, outcome: Option<&Outcome>) -> String {
+    match outcome {
+        Some(outcome) => outcome.annotate(&entry),
+        None => entry,
+    }
+}
+
+/// Commit the touched scrolls when the scrolls directory is a git repo.
+/// Git problems are reported but never fail the import itself.
+fn commit_import(updates: &[(String, String)], entry: &str) {
+    let dir = scrolls_dir();
+    if !git::is_repo(&dir) {
+        return;
+    }
+
+    let mut paths: Vec<String> = Vec::new();
+    for name in updates.iter().map(|(name, _)| name.as_str()).chain(["WILLIAM-CHANGELOG.md"]) {
+        if !paths.iter().any(|p| p == name) {
+            paths.push(name.to_string());
+        }
+    }
+    let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M").to_string();
+    let message = git::commit_message(&paths, Some(entry), &timestamp);
+
+    match git::commit_paths(&dir, &paths, &message) {
+        Ok(Some(hash)) => println!("✓ Committed to git ({})", hash),
+        Ok(None) => println!("✓ No changes to commit"),
+        Err(e) => println!("⚠ Git commit skipped: {}", e),
+    }
+}
+
 /// Extract text content from JSON (continuum log format)
 fn extract_text_from_json(json: &str) -> Result<String> {
     // Try to parse as a single object with "content" field
@@ -128,6 +210,18 @@ fn extract_module_updates(text: &str) -> Result<Vec<(String, String)>> {
     Ok(updates)
 }
 
+/// Collect update targets that have no existing scroll file in `dir`
+/// (deduplicated, in order)
+fn missing_modules(dir: &Path, updates: &[(String, String)]) -> Vec<String> {
+    let mut missing: Vec<String> = Vec::new();
+    for (name, _) in updates {
+        if !dir.join(name).exists() && !missing.contains(name) {
+            missing.push(name.clone());
+        }
+    }
+    missing
+}
+
 /// Extract changelog entry from conversation text
 fn extract_changelog_entry(text: &str) -> Result<Option<String>> {
     let re = Regex::new(
@@ -151,3 +245,23 @@ fn simple_diff(old: &str, new: &str) -> usize {
 
     added + removed
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_modules_listed_once_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("WILLIAM-LIFESTYLE.md"), "# Lifestyle\n").unwrap();
+        let update = |name: &str| (name.to_string(), String::new());
+        let updates = vec![
+            update("WILLIAM-SOCAIL.md"),
+            update("WILLIAM-LIFESTYLE.md"),
+            update("WILLIAM-HEALTH.md"),
+            update("WILLIAM-SOCAIL.md"),
+        ];
+        assert_eq!(missing_modules(dir.path(), &updates), vec!["WILLIAM-SOCAIL.md", "WILLIAM-HEALTH.md"]);
+        assert!(missing_modules(dir.path(), &updates[1..2]).is_empty());
+    }
+}
@@ -2,9 +2,15 @@ use anyhow::Result;
 use clap::{Parser, Subcommand};
 
 mod changelog;
+mod clipboard;
 mod export;
+mod git;
 mod import;
+mod redact;
+mod restore;
+mod review;
 mod scrolls;
+mod split;
 
 #[derive(Parser)]
 #[command(name = "module")]
@@ -29,6 +35,18 @@ enum Commands {
         /// Create zip bundle instead of directory
         #[arg(short, long)]
         zip: bool,
+
+        /// Strip sections marked `<!-- redact: TAG -->` for these tags (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        redact: Vec<String>,
+
+        /// Copy the bundle, assembled into one markdown text, to the clipboard
+        #[arg(short, long, conflicts_with = "zip")]
+        clipboard: bool,
+
+        /// Assemble the bundle and split it between scrolls into parts of at most BYTES
+        #[arg(long, value_name = "BYTES", conflicts_with = "zip")]
+        split: Option<usize>,
     },
 
     /// Import and apply module updates from conversation JSON
@@ -39,6 +57,24 @@ enum Commands {
         /// Dry run - show what would be updated without applying
         #[arg(short, long)]
         dry_run: bool,
+
+        /// Skip updates for modules that don't exist instead of aborting
+        #[arg(long)]
+        ignore_missing: bool,
+
+        /// Show a diff of each update and choose to apply, skip or edit it
+        #[arg(short, long, conflicts_with = "dry_run")]
+        review: bool,
+    },
+
+    /// Restore scrolls from an export bundle (directory, zip, or assembled file)
+    Restore {
+        /// Path to the export bundle
+        bundle: String,
+
+        /// Dry run - list what would be restored without writing
+        #[arg(short, long)]
+        dry_run: bool,
     },
 
     /// Verify scroll consistency and completeness
@@ -50,17 +86,33 @@ enum Commands {
         #[arg(short, long)]
         full: bool,
     },
+
+    /// Make the scrolls directory a git repository so imports are committed
+    InitGit,
+
+    /// Show the import history of all scrolls, or of one module
+    History {
+        /// Module file name (e.g. WILLIAM-LIFESTYLE.md)
+        module: Option<String>,
+
+        /// Number of entries to show
+        #[arg(short = 'n', long, default_value = "20")]
+        limit: usize,
+    },
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Export { advisor, output, zip } => {
-            export::run(&advisor, output.as_deref(), zip)
+        Commands::Export { advisor, output, zip, redact, clipboard, split } => {
+            export::run(&advisor, output.as_deref(), zip, &redact, clipboard, split)
+        }
+        Commands::Import { file, dry_run, ignore_missing, review } => {
+            import::run(&file, dry_run, ignore_missing, review)
         }
-        Commands::Import { file, dry_run } => {
-            import::run(&file, dry_run)
+        Commands::Restore { bundle, dry_run } => {
+            restore::run(&bundle, dry_run)
         }
         Commands::Verify => {
             scrolls::verify()
@@ -68,5 +120,11 @@ fn main() -> Result<()> {
         Commands::List { full } => {
             scrolls::list(full)
         }
+        Commands::InitGit => {
+            scrolls::init_git()
+        }
+        Commands::History { module, limit } => {
+            scrolls::history(module.as_deref(), limit)
+        }
     }
 }
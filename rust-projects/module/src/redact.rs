@@ -0,0 +1,157 @@
+use regex::Regex;
+
+/// Strip sections marked `<!-- redact: TAG -->` ... `<!-- /redact -->` whose
+/// tag is in `tags`, leaving a one-line `[redacted: TAG]` marker in their place.
+///
+/// Sections may nest; a redacted section swallows everything inside it. An
+/// unterminated section runs to the end of the file. Returns the redacted
+/// text and any warnings about malformed markers.
+pub fn redact(content: &str, tags: &[String]) -> (String, Vec<String>) {
+    if tags.is_empty() {
+        return (content.to_string(), Vec::new());
+    }
+
+    let marker = Regex::new(r"<!--\s*(/?)redact(?::\s*([\w-]+))?\s*-->").unwrap();
+
+    let mut output = String::with_capacity(content.len());
+    let mut warnings = Vec::new();
+    // One entry per open section: (tag, whether it started a redaction)
+    let mut stack: Vec<(String, bool)> = Vec::new();
+    let mut redacting = false;
+    let mut last = 0;
+
+    for cap in marker.captures_iter(content) {
+        let whole = cap.get(0).unwrap();
+        if !redacting {
+            output.push_str(&content[last..whole.start()]);
+        }
+        last = whole.end();
+
+        let closing = !cap[1].is_empty();
+        if closing {
+            match stack.pop() {
+                Some((_, started)) => {
+                    if started {
+                        redacting = false;
+                    } else if !redacting {
+                        output.push_str(whole.as_str());
+                    }
+                }
+                None => {
+                    warnings.push(format!("unmatched {} ignored", whole.as_str()));
+                    if !redacting {
+                        output.push_str(whole.as_str());
+                    }
+                }
+            }
+            continue;
+        }
+
+        let tag = match cap.get(2) {
+            Some(t) => t.as_str().to_string(),
+            None => {
+                warnings.push(format!("{} has no tag, treated as untagged", whole.as_str()));
+                String::new()
+            }
+        };
+
+        let starts = !redacting && tags.iter().any(|t| t.eq_ignore_ascii_case(&tag));
+        if starts {
+            output.push_str(&format!("[redacted: {}]", tag));
+            redacting = true;
+        } else if !redacting {
+            output.push_str(whole.as_str());
+        }
+        stack.push((tag, starts));
+    }
+
+    if !redacting {
+        output.push_str(&content[last..]);
+    }
+
+    if let Some((tag, _)) = stack.iter().find(|(_, started)| *started) {
+        warnings.push(format!("unterminated redact: {} section redacted to end of file", tag));
+        output.push('\n');
+    } else if let Some((tag, _)) = stack.last() {
+        warnings.push(format!("unterminated redact: {} section (not redacted)", tag));
+    }
+
+    (output, warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn no_tags_is_byte_identical() {
+        let input = "# Title\r\n<!-- redact: financial -->\nsecret\n<!-- /redact -->\ntail";
+        let (out, warnings) = redact(input, &[]);
+        assert_eq!(out, input);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn redacts_matching_section() {
+        let input = "before\n<!-- redact: financial -->\n£1000\n<!-- /redact -->\nafter\n";
+        let (out, warnings) = redact(input, &tags(&["financial"]));
+        assert_eq!(out, "before\n[redacted: financial]\nafter\n");
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn leaves_other_tags_untouched() {
+        let input = "<!-- redact: family -->\nkids\n<!-- /redact -->\n";
+        let (out, _) = redact(input, &tags(&["financial"]));
+        assert_eq!(out, input);
+    }
+
+    #[test]
+    fn multiple_sections_of_same_tag() {
+        let input = "a\n<!-- redact: financial -->\nx\n<!-- /redact -->\nb\n\
+                     <!-- redact: financial -->\ny\n<!-- /redact -->\nc\n";
+        let (out, _) = redact(input, &tags(&["financial"]));
+        assert_eq!(out, "a\n[redacted: financial]\nb\n[redacted: financial]\nc\n");
+    }
+
+    #[test]
+    fn nested_inner_redacted_outer_kept() {
+        let input = "<!-- redact: family -->\nkids\n<!-- redact: financial -->\n\
+                     school fees\n<!-- /redact -->\nholidays\n<!-- /redact -->\n";
+        let (out, warnings) = redact(input, &tags(&["financial"]));
+        assert_eq!(
+            out,
+            "<!-- redact: family -->\nkids\n[redacted: financial]\nholidays\n<!-- /redact -->\n"
+        );
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn nested_outer_redacted_swallows_inner() {
+        let input = "<!-- redact: family -->\nkids\n<!-- redact: financial -->\n\
+                     school fees\n<!-- /redact -->\nholidays\n<!-- /redact -->\nend\n";
+        let (out, warnings) = redact(input, &tags(&["family", "financial"]));
+        assert_eq!(out, "[redacted: family]\nend\n");
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn unterminated_redacts_to_end_with_warning() {
+        let input = "keep\n<!-- redact: financial -->\nsecret\nmore secret\n";
+        let (out, warnings) = redact(input, &tags(&["financial"]));
+        assert_eq!(out, "keep\n[redacted: financial]\n");
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn unmatched_close_is_kept_with_warning() {
+        let input = "text\n<!-- /redact -->\n";
+        let (out, warnings) = redact(input, &tags(&["financial"]));
+        assert_eq!(out, input);
+        assert_eq!(warnings.len(), 1);
+    }
+}
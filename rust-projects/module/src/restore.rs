@@ -0,0 +1,222 @@
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+use crate::git;
+use crate::scrolls::{scrolls_dir, write_scroll, CONTENT_SCROLLS, PROTOCOL_FILES};
+
+/// Bundle files written by `export` itself rather than copied from a scroll
+const BUNDLE_EXTRAS: &[&str] = &["README.md", "PREAMBLE.md"];
+
+/// Run the restore command
+pub fn run(bundle: &str, dry_run: bool) -> Result<()> {
+    let path = Path::new(bundle);
+    let sections = read_bundle(path)?;
+    if sections.is_empty() {
+        println!("No scrolls found in bundle: {}", path.display());
+        return Ok(());
+    }
+
+    let dir = scrolls_dir();
+    let mut restore: Vec<(String, String)> = Vec::new();
+    for (name, content) in sections {
+        if !is_known_scroll(&dir, &name) {
+            println!("⚠ Skipping {} (not a scroll in {})", name, dir.display());
+        } else if content.contains("[redacted: ") {
+            println!("⚠ Skipping {} (exported with redaction; restoring would lose content)", name);
+        } else {
+            restore.push((name, content));
+        }
+    }
+
+    if restore.is_empty() {
+        println!("No scrolls to restore.");
+        return Ok(());
+    }
+
+    if dry_run {
+        println!("DRY RUN - would restore from {}:", path.display());
+        for (name, content) in &restore {
+            let status = match fs::read_to_string(dir.join(name)) {
+                Ok(current) if current == *content => "unchanged",
+                Ok(_) => "overwrite",
+                Err(_) => "create",
+            };
+            println!("  • {} ({} lines, {})", name, content.lines().count(), status);
+        }
+        return Ok(());
+    }
+
+    for (name, content) in &restore {
+        write_scroll(name, content)?;
+        println!("✓ Restored {}", name);
+    }
+    commit_restore(&restore, path);
+
+    println!();
+    println!("Restore complete. Scrolls written to: {}", dir.display());
+    Ok(())
+}
+
+/// Commit the restored scrolls when the scrolls directory is a git repo
+fn commit_restore(restored: &[(String, String)], bundle: &Path) {
+    let dir = scrolls_dir();
+    if !git::is_repo(&dir) {
+        return;
+    }
+
+    let paths: Vec<String> = restored.iter().map(|(name, _)| name.clone()).collect();
+    let bundle_name = bundle.file_name().unwrap_or(bundle.as_os_str()).to_string_lossy();
+    let message = format!("Restore from {}\n\nModules: {}\n", bundle_name, paths.join(", "));
+
+    match git::commit_paths(&dir, &paths, &message) {
+        Ok(Some(hash)) => println!("✓ Committed to git ({})", hash),
+        Ok(None) => println!("✓ No changes to commit"),
+        Err(e) => println!("⚠ Git commit skipped: {}", e),
+    }
+}
+
+/// A scroll the tool manages, or one that already exists in the scrolls dir
+fn is_known_scroll(dir: &Path, name: &str) -> bool {
+    CONTENT_SCROLLS.contains(&name) || PROTOCOL_FILES.contains(&name) || dir.join(name).is_file()
+}
+
+/// Scroll sections of an export bundle as (file name, content), in bundle
+/// order. Accepts an export directory, an export zip, or a single assembled
+/// markdown file whose scrolls each start with a `# FILENAME.md` header.
+pub fn read_bundle(path: &Path) -> Result<Vec<(String, String)>> {
+    let sections = if path.is_dir() {
+        read_directory(path)?
+    } else if path.extension().is_some_and(|e| e.eq_ignore_ascii_case("zip")) {
+        read_zip(path)?
+    } else if path.is_file() {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read bundle: {}", path.display()))?;
+        split_sections(&text)
+    } else {
+        bail!("Bundle not found: {}", path.display());
+    };
+
+    Ok(sections
+        .into_iter()
+        .filter(|(name, _)| !BUNDLE_EXTRAS.contains(&name.as_str()))
+        .collect())
+}
+
+fn read_directory(dir: &Path) -> Result<Vec<(String, String)>> {
+    let mut sections = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to read bundle: {}", dir.display()))? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|e| e == "md") {
+            let name = path.file_name().unwrap().to_string_lossy().to_string();
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read: {}", path.display()))?;
+            sections.push((name, content));
+        }
+    }
+    sections.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(sections)
+}
+
+fn read_zip(path: &Path) -> Result<Vec<(String, String)>> {
+    let file = fs::File::open(path)
+        .with_context(|| format!("Failed to open zip: {}", path.display()))?;
+    let mut archive = zip::ZipArchive::new(file)
+        .with_context(|| format!("Not a zip bundle: {}", path.display()))?;
+
+    let mut sections = Vec::new();
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        if !entry.is_file() || !entry.name().ends_with(".md") || entry.name().contains('/') {
+            continue;
+        }
+        let name = entry.name().to_string();
+        let mut content = String::new();
+        entry
+            .read_to_string(&mut content)
+            .with_context(|| format!("Failed to read {} from zip", name))?;
+        sections.push((name, content));
+    }
+    Ok(sections)
+}
+
+/// Split an assembled bundle on its `# FILENAME.md` headers. Text before the
+/// first header is the bundle's own header and is dropped; each section's
+/// content runs up to the next header, without the header line itself.
+fn split_sections(text: &str) -> Vec<(String, String)> {
+    let header = Regex::new(r"(?m)^#\s+([\w.-]+\.md)[ \t]*\r?$").unwrap();
+
+    let headers: Vec<(String, usize, usize)> = header
+        .captures_iter(text)
+        .map(|cap| {
+            let whole = cap.get(0).unwrap();
+            (cap[1].to_string(), whole.start(), whole.end())
+        })
+        .collect();
+
+    headers
+        .iter()
+        .enumerate()
+        .map(|(i, (name, _, body_start))| {
+            let body_end = headers.get(i + 1).map_or(text.len(), |next| next.1);
+            let body = text[*body_start..body_end].trim_matches('\n');
+            (name.clone(), format!("{}\n", body.trim_end()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_skips_bundle_header() {
+        let text = "SENECA bundle, exported 2026-02-10\n\n\
+                    # WILLIAM-INDEX.md\n\n# Index\n\nSee below.\n\n\
+                    # WILLIAM-LIFESTYLE.md\n\n## Sleep\n\nEarly.\n";
+        let sections = split_sections(text);
+        assert_eq!(
+            sections,
+            vec![
+                ("WILLIAM-INDEX.md".to_string(), "# Index\n\nSee below.\n".to_string()),
+                ("WILLIAM-LIFESTYLE.md".to_string(), "## Sleep\n\nEarly.\n".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn split_without_headers_is_empty() {
+        assert!(split_sections("# Just a heading\n\nText\n").is_empty());
+    }
+
+    #[test]
+    fn directory_bundle_drops_readme() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("README.md"), "# SENECA Scrolls Bundle\n").unwrap();
+        fs::write(dir.path().join("WILLIAM-SOCIAL.md"), "social\n").unwrap();
+        fs::write(dir.path().join("notes.txt"), "ignored\n").unwrap();
+
+        let sections = read_bundle(dir.path()).unwrap();
+        assert_eq!(sections, vec![("WILLIAM-SOCIAL.md".to_string(), "social\n".to_string())]);
+    }
+
+    #[test]
+    fn zip_bundle_drops_preamble() {
+        use std::io::Write;
+        use zip::write::SimpleFileOptions;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("seneca-scrolls-2026-02-10.zip");
+        let mut zip = zip::ZipWriter::new(fs::File::create(&path).unwrap());
+        for (name, content) in [("PREAMBLE.md", "You are Seneca.\n"), ("WILLIAM-INDEX.md", "index\n")] {
+            zip.start_file(name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let sections = read_bundle(&path).unwrap();
+        assert_eq!(sections, vec![("WILLIAM-INDEX.md".to_string(), "index\n".to_string())]);
+    }
+}
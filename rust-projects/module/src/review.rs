@@ -0,0 +1,301 @@
+//! `module import --review`: step through each module update, show how it
+//! changes the current scroll and decide whether to apply, skip or edit it.
+//!
+//! The decision loop talks to a [`Reviewer`] so the accumulation of
+//! decisions can be tested without a terminal; [`TerminalReviewer`] is the
+//! interactive one.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use similar::{ChangeTag, TextDiff};
+use std::io::{self, BufRead, Write};
+
+/// Lines of unchanged context around each hunk
+const CONTEXT_LINES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+    Apply,
+    Skip,
+    Edit,
+}
+
+/// The interactive half of a review
+pub trait Reviewer {
+    /// Show how `proposed` would change `current`
+    fn show(&mut self, name: &str, current: &str, proposed: &str);
+    fn choose(&mut self, name: &str) -> Result<Action>;
+    /// Let the user rework the proposed content, returning the new version
+    fn edit(&mut self, name: &str, proposed: &str) -> Result<String>;
+}
+
+/// Decisions collected over a review, in update order
+#[derive(Debug, Default, PartialEq)]
+pub struct Outcome {
+    pub applied: Vec<(String, String)>,
+    pub edited: Vec<String>,
+    pub skipped: Vec<String>,
+}
+
+impl Outcome {
+    /// Changelog lines recording what the review kept and dropped
+    pub fn changelog_note(&self) -> String {
+        let list = |names: Vec<String>| {
+            if names.is_empty() {
+                "None".to_string()
+            } else {
+                names.join(", ")
+            }
+        };
+        let applied = self
+            .applied
+            .iter()
+            .map(|(name, _)| {
+                if self.edited.contains(name) {
+                    format!("{} (edited)", name)
+                } else {
+                    name.clone()
+                }
+            })
+            .collect();
+
+        format!(
+            "**Review**:\n- Applied: {}\n- Skipped: {}\n",
+            list(applied),
+            list(self.skipped.clone())
+        )
+    }
+
+    /// `entry` with the review note added, ahead of a closing `---` rule
+    pub fn annotate(&self, entry: &str) -> String {
+        let note = self.changelog_note();
+        let body = entry.trim_end();
+        match body.strip_suffix("---") {
+            Some(head) => format!("{}\n\n{}\n---\n", head.trim_end(), note),
+            None => format!("{}\n\n{}", body, note),
+        }
+    }
+}
+
+/// Review every update in turn. `current` gives a module's present content;
+/// edits are re-shown before asking again.
+pub fn review(
+    updates: Vec<(String, String)>,
+    current: impl Fn(&str) -> String,
+    reviewer: &mut impl Reviewer,
+) -> Result<Outcome> {
+    let mut outcome = Outcome::default();
+
+    for (name, mut proposed) in updates {
+        let existing = current(&name);
+        loop {
+            reviewer.show(&name, &existing, &proposed);
+            match reviewer.choose(&name)? {
+                Action::Apply => {
+                    outcome.applied.push((name, proposed));
+                    break;
+                }
+                Action::Skip => {
+                    outcome.skipped.push(name);
+                    break;
+                }
+                Action::Edit => {
+                    proposed = reviewer.edit(&name, &proposed)?.trim().to_string();
+                    if !outcome.edited.contains(&name) {
+                        outcome.edited.push(name.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(outcome)
+}
+
+/// Coloured unified diff of `current` → `proposed`
+pub fn render_diff(name: &str, current: &str, proposed: &str) -> String {
+    // Imported content arrives trimmed, so a missing final newline alone
+    // isn't worth a hunk
+    let current = format!("{}\n", current.trim_end_matches(['\n', '\r']));
+    let proposed = format!("{}\n", proposed.trim_end_matches(['\n', '\r']));
+    let diff = TextDiff::from_lines(&current, &proposed);
+    let mut out = String::new();
+
+    out.push_str(&format!("{}\n", format!("--- {} (current)", name).red().bold()));
+    out.push_str(&format!("{}\n", format!("+++ {} (proposed)", name).green().bold()));
+
+    let mut unified = diff.unified_diff();
+    unified.context_radius(CONTEXT_LINES);
+    let mut any = false;
+    for hunk in unified.iter_hunks() {
+        any = true;
+        out.push_str(&format!("{}\n", hunk.header().to_string().cyan()));
+        for change in hunk.iter_changes() {
+            let line = change.value().trim_end_matches(['\n', '\r']);
+            let rendered = match change.tag() {
+                ChangeTag::Delete => format!("-{}", line).red().to_string(),
+                ChangeTag::Insert => format!("+{}", line).green().to_string(),
+                ChangeTag::Equal => format!(" {}", line),
+            };
+            out.push_str(&rendered);
+            out.push('\n');
+        }
+    }
+    if !any {
+        out.push_str(&format!("{}\n", "(no changes)".dimmed()));
+    }
+
+    out
+}
+
+/// Prompts on stdin/stdout and edits in `$EDITOR`
+pub struct TerminalReviewer;
+
+impl Reviewer for TerminalReviewer {
+    fn show(&mut self, name: &str, current: &str, proposed: &str) {
+        println!();
+        print!("{}", render_diff(name, current, proposed));
+    }
+
+    fn choose(&mut self, name: &str) -> Result<Action> {
+        let stdin = io::stdin();
+        loop {
+            print!("{}: [a]pply / [s]kip / [e]dit? ", name);
+            io::stdout().flush()?;
+
+            let mut input = String::new();
+            if stdin.lock().read_line(&mut input)? == 0 {
+                // End of input: leave anything undecided untouched
+                println!();
+                return Ok(Action::Skip);
+            }
+            match input.trim().to_lowercase().as_str() {
+                "a" | "apply" => return Ok(Action::Apply),
+                "s" | "skip" => return Ok(Action::Skip),
+                "e" | "edit" => return Ok(Action::Edit),
+                other => println!("Unknown choice '{}'", other),
+            }
+        }
+    }
+
+    fn edit(&mut self, name: &str, proposed: &str) -> Result<String> {
+        let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
+
+        let tmp = std::env::temp_dir().join(format!("module-review-{}", name));
+        std::fs::write(&tmp, proposed).context("Failed to write temp file for editing")?;
+
+        let status = std::process::Command::new(&editor)
+            .arg(&tmp)
+            .status()
+            .with_context(|| format!("Failed to open editor '{}'", editor))?;
+        if !status.success() {
+            anyhow::bail!("Editor exited with non-zero status");
+        }
+
+        let edited = std::fs::read_to_string(&tmp).context("Failed to read edited file")?;
+        let _ = std::fs::remove_file(&tmp);
+        Ok(edited)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    /// Replays scripted choices and edits, recording what it was shown
+    #[derive(Default)]
+    struct Scripted {
+        actions: VecDeque<Action>,
+        edits: VecDeque<String>,
+        shown: Vec<(String, String)>,
+    }
+
+    impl Reviewer for Scripted {
+        fn show(&mut self, name: &str, _current: &str, proposed: &str) {
+            self.shown.push((name.to_string(), proposed.to_string()));
+        }
+
+        fn choose(&mut self, _name: &str) -> Result<Action> {
+            Ok(self.actions.pop_front().expect("ran out of scripted actions"))
+        }
+
+        fn edit(&mut self, _name: &str, _proposed: &str) -> Result<String> {
+            Ok(self.edits.pop_front().expect("ran out of scripted edits"))
+        }
+    }
+
+    fn updates(names: &[&str]) -> Vec<(String, String)> {
+        names.iter().map(|n| (n.to_string(), format!("new {}", n))).collect()
+    }
+
+    #[test]
+    fn accumulates_applied_and_skipped_in_order() {
+        let mut reviewer = Scripted {
+            actions: [Action::Skip, Action::Apply, Action::Skip].into(),
+            ..Default::default()
+        };
+        let outcome = review(updates(&["A.md", "B.md", "C.md"]), |_| String::new(), &mut reviewer).unwrap();
+
+        assert_eq!(outcome.applied, vec![("B.md".to_string(), "new B.md".to_string())]);
+        assert_eq!(outcome.skipped, vec!["A.md".to_string(), "C.md".to_string()]);
+        assert!(outcome.edited.is_empty());
+    }
+
+    #[test]
+    fn edit_rediffs_before_deciding() {
+        let mut reviewer = Scripted {
+            actions: [Action::Edit, Action::Edit, Action::Apply].into(),
+            edits: ["first try\n".to_string(), "  second try\n\n".to_string()].into(),
+            ..Default::default()
+        };
+        let outcome = review(updates(&["A.md"]), |_| "old".to_string(), &mut reviewer).unwrap();
+
+        let shown: Vec<&str> = reviewer.shown.iter().map(|(_, p)| p.as_str()).collect();
+        assert_eq!(shown, vec!["new A.md", "first try", "second try"]);
+        assert_eq!(outcome.applied, vec![("A.md".to_string(), "second try".to_string())]);
+        assert_eq!(outcome.edited, vec!["A.md".to_string()]);
+    }
+
+    #[test]
+    fn changelog_note_lists_both_sides() {
+        let outcome = Outcome {
+            applied: vec![("A.md".into(), "a".into()), ("B.md".into(), "b".into())],
+            edited: vec!["B.md".into()],
+            skipped: vec![],
+        };
+        assert_eq!(
+            outcome.changelog_note(),
+            "**Review**:\n- Applied: A.md, B.md (edited)\n- Skipped: None\n"
+        );
+    }
+
+    #[test]
+    fn annotate_keeps_closing_rule_last() {
+        let outcome = Outcome {
+            applied: vec![("A.md".into(), "a".into())],
+            edited: vec![],
+            skipped: vec!["C.md".into()],
+        };
+        assert_eq!(
+            outcome.annotate("### 2026-03-01 — Title\n\n**Summary**: x\n\n---\n"),
+            "### 2026-03-01 — Title\n\n**Summary**: x\n\n\
+             **Review**:\n- Applied: A.md\n- Skipped: C.md\n\n---\n"
+        );
+        assert_eq!(
+            outcome.annotate("### Title\nBody"),
+            "### Title\nBody\n\n**Review**:\n- Applied: A.md\n- Skipped: C.md\n"
+        );
+    }
+
+    #[test]
+    fn diff_marks_changed_lines() {
+        colored::control::set_override(false);
+        let diff = render_diff("A.md", "one\ntwo\nthree\n", "one\n2\nthree\n");
+        assert_eq!(
+            diff,
+            "--- A.md (current)\n+++ A.md (proposed)\n@@ -1,3 +1,3 @@\n one\n-two\n+2\n three\n"
+        );
+        assert!(render_diff("A.md", "same\n", "same").ends_with("(no changes)\n"));
+    }
+}
@@ -3,6 +3,8 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
+use crate::git;
+
 /// The base directory for all scrolls
 pub fn scrolls_dir() -> PathBuf {
     dirs::home_dir()
@@ -91,6 +93,47 @@ pub fn read_scroll(name: &str) -> Result<String> {
         .with_context(|| format!("Failed to read scroll: {}", path.display()))
 }
 
+/// Persona-specific preamble for an export bundle, with where it came from.
+///
+/// Looks for `preamble/<persona>.md` in the scrolls directory first, then for
+/// a `## Preamble` section in the persona's protocol file.
+pub fn persona_preamble(advisor: &str) -> Result<Option<(String, String)>> {
+    let persona = advisor.to_lowercase();
+
+    let preamble_name = format!("preamble/{}.md", persona);
+    if resolve_scroll_path(&preamble_name).exists() {
+        let content = read_scroll(&preamble_name)?;
+        return Ok(Some((preamble_name, content.trim().to_string())));
+    }
+
+    let protocol_name = format!("{}-PROTOCOL.md", persona.to_uppercase());
+    if resolve_scroll_path(&protocol_name).exists() {
+        let content = read_scroll(&protocol_name)?;
+        if let Some(section) = preamble_section(&content) {
+            return Ok(Some((format!("{} (## Preamble)", protocol_name), section)));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Body of the `## Preamble` section, up to the next heading of the same or
+/// higher level
+fn preamble_section(content: &str) -> Option<String> {
+    let mut lines = content.lines();
+    lines.find(|line| line.trim().eq_ignore_ascii_case("## preamble"))?;
+
+    let body: Vec<&str> = lines
+        .take_while(|line| !(line.starts_with("# ") || line.starts_with("## ")))
+        .collect();
+    let body = body.join("\n").trim().to_string();
+    if body.is_empty() {
+        None
+    } else {
+        Some(body)
+    }
+}
+
 /// Write a scroll's content
 pub fn write_scroll(name: &str, content: &str) -> Result<()> {
     let path = scrolls_dir().join(name);
@@ -210,3 +253,54 @@ pub fn list(full: bool) -> Result<()> {
 
     Ok(())
 }
+
+/// Turn the scrolls directory into a git repository
+pub fn init_git() -> Result<()> {
+    let dir = scrolls_dir();
+    git::init(&dir)?;
+    println!("✓ Initialised git repository at {}", dir.display());
+    println!("  Imports will now be committed; see `module history`.");
+    Ok(())
+}
+
+/// Show the condensed import history
+pub fn history(module: Option<&str>, limit: usize) -> Result<()> {
+    let dir = scrolls_dir();
+    if !git::is_repo(&dir) {
+        println!("{} is not a git repository (run `module init-git`).", dir.display());
+        return Ok(());
+    }
+
+    let module = module.map(|m| if m.ends_with(".md") { m.to_string() } else { format!("{}.md", m) });
+    let entries = git::history(&dir, module.as_deref(), limit)?;
+    if entries.is_empty() {
+        println!("No history{}.", module.map(|m| format!(" for {}", m)).unwrap_or_default());
+        return Ok(());
+    }
+
+    let width = entries.iter().map(|e| e.advisor.chars().count()).max().unwrap_or(0);
+    for entry in entries {
+        println!("{}  {}  {:<width$}  {}", entry.date, entry.hash, entry.advisor, entry.summary, width = width);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preamble_section_stops_at_next_heading() {
+        let protocol = "# SENECA PROTOCOL\n\n## Preamble\n\nYou are Seneca.\n\n### Tone\nStoic.\n\n## Rules\n- one\n";
+        assert_eq!(
+            preamble_section(protocol).as_deref(),
+            Some("You are Seneca.\n\n### Tone\nStoic.")
+        );
+    }
+
+    #[test]
+    fn missing_or_empty_preamble_section() {
+        assert_eq!(preamble_section("# GEOFF PROTOCOL\n\n## Rules\n- one\n"), None);
+        assert_eq!(preamble_section("## Preamble\n\n## Rules\n"), None);
+    }
+}
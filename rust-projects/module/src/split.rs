@@ -0,0 +1,147 @@
+//! Splitting an assembled bundle into parts small enough to paste into a
+//! chat UI with a size limit, cutting only between scrolls.
+
+/// Line opening each part, so the advisor waits for the rest
+pub fn part_header(index: usize, total: usize) -> String {
+    format!("Part {}/{} — paste all parts before responding\n\n", index, total)
+}
+
+/// Divide a bundle, given as its chunks in order (one per scroll, the first
+/// carrying the bundle header), into as few parts as fit in `max_bytes`
+/// each, header line included. Within that number of parts the cut points
+/// minimise the largest part. A bundle that fits is returned whole, without
+/// a part header. A chunk that can't fit on its own gets a part to itself
+/// and is left oversized rather than cut.
+pub fn split_bundle(chunks: &[String], max_bytes: usize) -> Vec<String> {
+    let sizes: Vec<usize> = chunks.iter().map(String::len).collect();
+    let total: usize = sizes.iter().sum();
+    if total <= max_bytes || chunks.len() < 2 {
+        return vec![chunks.concat()];
+    }
+
+    for parts in 2..=chunks.len() {
+        let budget = max_bytes.saturating_sub(part_header(parts, parts).len());
+        if let Some(groups) = balanced_groups(&sizes, parts, budget) {
+            return render(chunks, &groups);
+        }
+    }
+
+    // Some scroll is too big alone: pack the rest as tightly as they go
+    let budget = max_bytes.saturating_sub(part_header(chunks.len(), chunks.len()).len());
+    render(chunks, &greedy_groups(&sizes, budget))
+}
+
+/// Group sizes into at most `parts` runs with the smallest possible
+/// largest run, if that run fits in `budget`. Returns each run's length.
+fn balanced_groups(sizes: &[usize], parts: usize, budget: usize) -> Option<Vec<usize>> {
+    let mut low = sizes.iter().copied().max().unwrap_or(0);
+    let mut high = sizes.iter().sum::<usize>();
+    if low > budget {
+        return None;
+    }
+    // Smallest capacity at which greedy packing needs no more than `parts`
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if greedy_groups(sizes, mid).len() <= parts {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    (low <= budget).then(|| greedy_groups(sizes, low))
+}
+
+/// Fill each run up to `capacity` before starting the next; a size over
+/// capacity gets a run of its own
+fn greedy_groups(sizes: &[usize], capacity: usize) -> Vec<usize> {
+    let mut groups = Vec::new();
+    let (mut count, mut bytes) = (0, 0);
+    for &size in sizes {
+        if count > 0 && bytes + size > capacity {
+            groups.push(count);
+            (count, bytes) = (0, 0);
+        }
+        count += 1;
+        bytes += size;
+    }
+    if count > 0 {
+        groups.push(count);
+    }
+    groups
+}
+
+fn render(chunks: &[String], groups: &[usize]) -> Vec<String> {
+    let total = groups.len();
+    let mut rest = chunks;
+    groups
+        .iter()
+        .enumerate()
+        .map(|(i, &count)| {
+            let (group, tail) = rest.split_at(count);
+            rest = tail;
+            format!("{}{}", part_header(i + 1, total), group.concat())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A chunk of `len` bytes whose scroll can be recognised in the output
+    fn chunk(name: char, len: usize) -> String {
+        format!("# {}.md\n\n{}\n", name, "x".repeat(len - 9))
+    }
+
+    fn bundle(sizes: &[(char, usize)]) -> Vec<String> {
+        sizes.iter().map(|&(name, len)| chunk(name, len)).collect()
+    }
+
+    /// Parts with their header stripped, so the scrolls can be checked
+    fn bodies(parts: &[String]) -> Vec<String> {
+        parts
+            .iter()
+            .map(|p| p.split_once("\n\n").map(|(_, body)| body.to_string()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn small_bundle_is_one_part_without_header() {
+        let chunks = bundle(&[('A', 100), ('B', 100)]);
+        assert_eq!(split_bundle(&chunks, 1000), vec![chunks.concat()]);
+    }
+
+    #[test]
+    fn parts_fit_and_never_cut_a_scroll() {
+        let chunks = bundle(&[('A', 400), ('B', 300), ('C', 500), ('D', 200), ('E', 450), ('F', 100)]);
+        let parts = split_bundle(&chunks, 1000);
+
+        assert_eq!(parts.len(), 3);
+        for (i, part) in parts.iter().enumerate() {
+            assert!(part.len() <= 1000, "part {} is {} bytes", i + 1, part.len());
+            assert!(part.starts_with(&format!("Part {}/3 — paste all parts before responding\n\n", i + 1)));
+        }
+        assert_eq!(bodies(&parts).concat(), chunks.concat());
+    }
+
+    #[test]
+    fn cuts_balance_part_sizes() {
+        // Greedy packing would give 900 + 100; balanced is 500 + 500
+        let chunks = bundle(&[('A', 300), ('B', 200), ('C', 200), ('D', 200), ('E', 100)]);
+        let parts = bodies(&split_bundle(&chunks, 950));
+        assert_eq!(parts, vec![chunks[..2].concat(), chunks[2..].concat()]);
+    }
+
+    #[test]
+    fn oversized_scroll_gets_its_own_part() {
+        let chunks = bundle(&[('A', 200), ('B', 1500), ('C', 200), ('D', 300)]);
+        let parts = bodies(&split_bundle(&chunks, 1000));
+        assert_eq!(parts, vec![chunks[0].clone(), chunks[1].clone(), chunks[2..].concat()]);
+    }
+
+    #[test]
+    fn single_scroll_is_never_split() {
+        let chunks = bundle(&[('A', 5000)]);
+        assert_eq!(split_bundle(&chunks, 1000), chunks);
+    }
+}
@@ -0,0 +1,301 @@
+//! Highlights filed by Readwise category: `highlights/<category>/`, each
+//! category optionally rendered through its own template from
+//! `~/.config/readwise/templates/<category>.md`.
+
+use slug::slugify;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{format_highlights, format_tags, parse_frontmatter, Book, IdIndex};
+
+/// Directory (and template) name for a Readwise category: `books`,
+/// `articles`, `tweets`, `podcasts`, ...
+pub fn category_dir(category: &str) -> String {
+    let slug = slugify(category);
+    if slug.is_empty() {
+        "uncategorized".to_string()
+    } else {
+        slug
+    }
+}
+
+/// The per-category subdirectories of `highlights/`, each with its index of
+/// synced files. Existing ones are indexed up front so a book can be found
+/// whichever category it was filed under; new ones are created as books of
+/// each category come in.
+pub struct CategoryDirs {
+    root: PathBuf,
+    indexes: HashMap<String, IdIndex>,
+}
+
+impl CategoryDirs {
+    pub fn new(root: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut indexes = HashMap::new();
+        for entry in fs::read_dir(root)? {
+            let path = entry?.path();
+            if !path.is_dir() {
+                continue;
+            }
+            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                indexes.insert(name.to_string(), IdIndex::build(&path)?);
+            }
+        }
+        Ok(Self { root: root.to_path_buf(), indexes })
+    }
+
+    /// Directory and file index for book `id` in `category`. Files synced
+    /// for the book under another category, before Readwise recategorised
+    /// it, are moved into this category's directory first.
+    pub fn get(&mut self, category: &str, id: &str) -> Result<(PathBuf, &mut IdIndex), Box<dyn std::error::Error>> {
+        let name = category_dir(category);
+        let dir = self.root.join(&name);
+        if !self.indexes.contains_key(&name) {
+            fs::create_dir_all(&dir)?;
+            self.indexes.insert(name.clone(), IdIndex::build(&dir)?);
+        }
+
+        let mut moved = Vec::new();
+        for (other, index) in self.indexes.iter_mut().filter(|(other, _)| **other != name) {
+            for file in index.take(id) {
+                let from = self.root.join(other).join(&file);
+                if dir.join(&file).exists() {
+                    // The name is taken here; the book is about to be rewritten anyway
+                    fs::remove_file(&from)?;
+                    println!("    Removed: {}/{} (now in {})", other, file, name);
+                } else {
+                    fs::rename(&from, dir.join(&file))?;
+                    println!("    Moved: {}/{} -> {}/{}", other, file, name, file);
+                    moved.push(file);
+                }
+            }
+        }
+
+        let index = self.indexes.get_mut(&name).unwrap();
+        for file in &moved {
+            index.add(id, file);
+        }
+        Ok((dir, index))
+    }
+}
+
+/// Per-category markdown templates, by category directory name
+#[derive(Debug, Default)]
+pub struct Templates {
+    by_category: HashMap<String, String>,
+}
+
+impl Templates {
+    /// `~/.config/readwise/templates`
+    pub fn default_dir() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join(".config").join("readwise").join("templates"))
+    }
+
+    /// Every `<category>.md` in `dir`; none if it doesn't exist
+    pub fn load(dir: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut by_category = HashMap::new();
+        let Ok(entries) = fs::read_dir(dir) else {
+            return Ok(Self::default());
+        };
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("md") {
+                continue;
+            }
+            if let Some(category) = path.file_stem().and_then(|s| s.to_str()) {
+                by_category.insert(category.to_string(), fs::read_to_string(&path)?);
+            }
+        }
+        Ok(Self { by_category })
+    }
+
+    pub fn get(&self, category: &str) -> Option<&str> {
+        self.by_category.get(&category_dir(category)).map(String::as_str)
+    }
+
+    pub fn categories(&self) -> impl Iterator<Item = &str> {
+        self.by_category.keys().map(String::as_str)
+    }
+}
+
+/// Fill in `{{title}}`, `{{author}}`, `{{highlights}}` (the quoted
+/// highlights, without a heading) and `{{tags}}` (a `[a, b]` list). The
+/// book's `readwise_id` is added to the frontmatter if the template leaves
+/// it out, since updating files in place depends on it.
+pub fn render_template(template: &str, book: &Book, vault_compat: bool) -> String {
+    let tags: Vec<&str> = book.book_tags.iter().map(|t| t.name.as_str()).collect();
+    let rendered = template
+        .replace("{{title}}", &book.title)
+        .replace("{{author}}", book.author.as_deref().unwrap_or(""))
+        .replace("{{tags}}", &format_tags(tags, vault_compat))
+        .replace("{{highlights}}", format_highlights(&book.highlights).trim_end());
+
+    if parse_frontmatter(&rendered).contains_key("readwise_id") {
+        return rendered;
+    }
+    match rendered.strip_prefix("---\n") {
+        Some(rest) => format!("---\nreadwise_id: {}\n{}", book.id, rest),
+        None => format!("---\nreadwise_id: {}\n---\n\n{}", book.id, rendered),
+    }
+}
+
+/// A file moved out of the flat `highlights/` directory
+#[derive(Debug, PartialEq)]
+pub enum Move {
+    Moved { file: String, category: String },
+    /// The category directory already has a file by that name
+    Conflict { file: String, category: String },
+}
+
+/// Move files synced before category directories existed, those directly in
+/// `root` with a `readwise_id`, into the directory for the `category` in
+/// their frontmatter. Other files are left alone.
+pub fn migrate_flat(root: &Path) -> Result<Vec<Move>, Box<dyn std::error::Error>> {
+    let mut moves = Vec::new();
+    let mut files: Vec<PathBuf> = fs::read_dir(root)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<_, _>>()?;
+    files.sort();
+
+    for path in files {
+        if !path.is_file() || path.extension().and_then(|e| e.to_str()) != Some("md") {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let fields = parse_frontmatter(&content);
+        if !fields.contains_key("readwise_id") {
+            continue;
+        }
+        let category = category_dir(fields.get("category").map(String::as_str).unwrap_or(""));
+        let file = path.file_name().unwrap().to_string_lossy().to_string();
+        let target = root.join(&category).join(&file);
+        if target.exists() {
+            moves.push(Move::Conflict { file, category });
+            continue;
+        }
+        fs::create_dir_all(root.join(&category))?;
+        fs::rename(&path, &target)?;
+        moves.push(Move::Moved { file, category });
+    }
+    Ok(moves)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HighlightsExportResponse;
+
+    fn meditations() -> Book {
+        let response: HighlightsExportResponse =
+            serde_json::from_str(include_str!("../tests/fixtures/highlights-export.json")).unwrap();
+        response.results.into_iter().nth(2).unwrap()
+    }
+
+    #[test]
+    fn category_names_are_directory_safe() {
+        assert_eq!(category_dir("books"), "books");
+        assert_eq!(category_dir("Podcasts"), "podcasts");
+        assert_eq!(category_dir(""), "uncategorized");
+    }
+
+    #[test]
+    fn template_placeholders_are_filled() {
+        let book = meditations();
+        let template = "---\ntitle: \"{{title}}\"\nreadwise_id: {{readwise_id}}\ntags: {{tags}}\n---\n\n\
+                        # {{title}} ({{author}})\n\n{{highlights}}\n";
+        let rendered = render_template(template, &book, false);
+
+        assert!(rendered.starts_with(&format!("---\ntitle: \"{}\"\n", book.title)));
+        assert!(rendered.contains(&format!("# {} ({})\n", book.title, book.author.as_deref().unwrap())));
+        assert!(rendered.contains(&format!("\n> {}", book.highlights[0].text)));
+        assert!(!rendered.contains("## Highlights"));
+        assert!(!rendered.contains("{{title}}"));
+        // An unknown placeholder is left as written
+        assert!(rendered.contains("readwise_id: {{readwise_id}}"));
+    }
+
+    #[test]
+    fn template_without_readwise_id_gets_one() {
+        let book = meditations();
+        let with_frontmatter = render_template("---\ntitle: {{title}}\n---\n{{highlights}}", &book, false);
+        assert!(with_frontmatter.starts_with(&format!("---\nreadwise_id: {}\ntitle: ", book.id)));
+
+        let without = render_template("# {{title}}\n", &book, false);
+        assert_eq!(without, format!("---\nreadwise_id: {}\n---\n\n# {}\n", book.id, book.title));
+    }
+
+    #[test]
+    fn templates_load_by_category() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("podcasts.md"), "# {{title}}").unwrap();
+        fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let templates = Templates::load(dir.path()).unwrap();
+        assert_eq!(templates.get("Podcasts"), Some("# {{title}}"));
+        assert_eq!(templates.get("books"), None);
+        assert_eq!(templates.categories().count(), 1);
+
+        assert_eq!(Templates::load(&dir.path().join("missing")).unwrap().categories().count(), 0);
+    }
+
+    #[test]
+    fn flat_files_move_into_category_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("books-meditations.md"), "---\ncategory: books\nreadwise_id: 1\n---\n").unwrap();
+        fs::write(root.join("tweets-a-thread.md"), "---\ncategory: tweets\nreadwise_id: 2\n---\n").unwrap();
+        fs::write(root.join("my-notes.md"), "---\ntitle: mine\n---\n").unwrap();
+        fs::create_dir_all(root.join("books")).unwrap();
+        fs::write(root.join("books/books-taken.md"), "newer").unwrap();
+        fs::write(root.join("books-taken.md"), "---\ncategory: books\nreadwise_id: 3\n---\n").unwrap();
+
+        let moves = migrate_flat(root).unwrap();
+        assert_eq!(moves, vec![
+            Move::Moved { file: "books-meditations.md".into(), category: "books".into() },
+            Move::Conflict { file: "books-taken.md".into(), category: "books".into() },
+            Move::Moved { file: "tweets-a-thread.md".into(), category: "tweets".into() },
+        ]);
+        assert!(root.join("books/books-meditations.md").exists());
+        assert!(root.join("tweets/tweets-a-thread.md").exists());
+        assert!(root.join("my-notes.md").exists());
+        assert!(root.join("books-taken.md").exists());
+        assert_eq!(fs::read_to_string(root.join("books/books-taken.md")).unwrap(), "newer");
+
+        // Nothing left to move on the next run
+        assert!(migrate_flat(root).unwrap().iter().all(|m| matches!(m, Move::Conflict { .. })));
+    }
+
+    #[test]
+    fn category_dirs_index_their_own_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut dirs = CategoryDirs::new(dir.path()).unwrap();
+        let (books, _) = dirs.get("books", "1").unwrap();
+        assert_eq!(books, dir.path().join("books"));
+        assert!(books.is_dir());
+    }
+
+    #[test]
+    fn recategorised_book_moves_to_its_new_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("articles")).unwrap();
+        fs::write(root.join("articles/articles-meditations.md"), "---\nreadwise_id: 7\n---\n").unwrap();
+        fs::write(root.join("articles/articles-other.md"), "---\nreadwise_id: 8\n---\n").unwrap();
+
+        let mut dirs = CategoryDirs::new(root).unwrap();
+        let (books, files) = dirs.get("books", "7").unwrap();
+        files.claim(&books, "7", "books-meditations.md").unwrap();
+
+        assert!(root.join("books/books-meditations.md").exists());
+        assert!(!root.join("articles/articles-meditations.md").exists());
+        assert!(root.join("articles/articles-other.md").exists());
+
+        // Already where it belongs: nothing else moves
+        let (_, files) = dirs.get("books", "7").unwrap();
+        assert_eq!(files.take("7"), vec!["books-meditations.md".to_string()]);
+        let (_, files) = dirs.get("articles", "8").unwrap();
+        assert_eq!(files.take("8"), vec!["articles-other.md".to_string()]);
+    }
+}
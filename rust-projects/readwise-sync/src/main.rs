@@ -2,17 +2,34 @@
 //!
 //! Syncs to ~/Captures/readwise/ with incremental updates.
 //! Run nightly via launchd/systemd.
-
-use chrono::{DateTime, Utc};
+//!
+//! Flags:
+//!   --merge-reader-highlights  Put highlights made in Reader into the Reader
+//!                              document's markdown instead of a separate file
+//!   --vault-compat             Obsidian-friendly output: `date created` /
+//!                              `date modified`, title-named files with
+//!                              wikilink-safe H1s, original title as an alias
+//!   --quiet                    No desktop notification after a successful
+//!                              run (failures always notify)
+//!   --log-run                  Append a line per run to sync-log.md
+
+mod category;
+mod report;
+
+use chrono::{DateTime, FixedOffset, Local, Utc};
+use continuum_core::note::sanitize_title;
 use reqwest::blocking::Client;
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
 use serde::{Deserialize, Serialize};
 use slug::slugify;
 use std::collections::HashMap;
 use std::env;
+use std::fmt::Write as _;
 use std::fs;
-use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use category::{CategoryDirs, Templates};
+use report::{RunRecord, SystemNotifier};
 
 const READWISE_EXPORT_URL: &str = "https://readwise.io/api/v2/export/";
 const READER_LIST_URL: &str = "https://readwise.io/api/v3/list/";
@@ -23,7 +40,6 @@ const READER_LIST_URL: &str = "https://readwise.io/api/v3/list/";
 
 #[derive(Debug, Deserialize)]
 struct HighlightsExportResponse {
-    count: u32,
     #[serde(rename = "nextPageCursor", deserialize_with = "deserialize_optional_id")]
     next_page_cursor: Option<String>,
     results: Vec<Book>,
@@ -46,20 +62,15 @@ struct Book {
     book_tags: Vec<Tag>,
     #[serde(rename = "unique_url")]
     source_url: Option<String>,
-    cover_image_url: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 struct Highlight {
-    #[serde(deserialize_with = "deserialize_id")]
-    id: String,
     text: String,
     note: Option<String>,
-    location: Option<u32>,
-    location_type: Option<String>,
     highlighted_at: Option<String>,
-    url: Option<String>,
-    color: Option<String>,
+    created_at: Option<String>,
+    updated_at: Option<String>,
     #[serde(default)]
     tags: Vec<Tag>,
     #[serde(default)]
@@ -77,8 +88,6 @@ struct Tag {
 
 #[derive(Debug, Deserialize)]
 struct ReaderListResponse {
-    #[serde(default)]
-    count: u32,
     #[serde(rename = "nextPageCursor", deserialize_with = "deserialize_optional_id", default)]
     next_page_cursor: Option<String>,
     #[serde(default)]
@@ -104,12 +113,8 @@ struct Document {
     #[serde(default)]
     reading_progress: f32,
     source_url: Option<String>,
-    #[serde(default)]
-    source: Option<String>,
-    site_name: Option<String>,
     summary: Option<String>,
     notes: Option<String>,
-    published_date: Option<String>,
     /// Full HTML content of the document (when withHtmlContent=true)
     html_content: Option<String>,
 }
@@ -139,11 +144,229 @@ impl SyncState {
     }
 }
 
+// ============================================================================
+// Reader correlation index (url/title -> reader markdown file)
+// ============================================================================
+
+/// Cached index of synced Reader documents, built from their frontmatter.
+/// Entries are keyed by filename and re-parsed only when the file's mtime changes.
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct ReaderIndex {
+    entries: HashMap<String, ReaderIndexEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct ReaderIndexEntry {
+    urls: Vec<String>,
+    title: String,
+    mtime: u64,
+}
+
+impl ReaderIndex {
+    fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Bring the index up to date with the markdown files in `reader_dir`
+    fn refresh(&mut self, reader_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let mut seen = std::collections::HashSet::new();
+
+        for entry in fs::read_dir(reader_dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("md") {
+                continue;
+            }
+            let Some(filename) = path.file_name().and_then(|f| f.to_str()) else {
+                continue;
+            };
+            let mtime = fs::metadata(&path)?
+                .modified()?
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            seen.insert(filename.to_string());
+
+            if self.entries.get(filename).is_some_and(|e| e.mtime == mtime) {
+                continue;
+            }
+
+            let content = fs::read_to_string(&path)?;
+            let fields = parse_frontmatter(&content);
+            let urls = ["url", "source_url"]
+                .iter()
+                .filter_map(|key| fields.get(*key))
+                .map(|u| normalize_url(u))
+                .filter(|u| !u.is_empty())
+                .collect();
+            let title = fields.get("title").cloned().unwrap_or_default();
+
+            self.entries.insert(filename.to_string(), ReaderIndexEntry { urls, title, mtime });
+        }
+
+        self.entries.retain(|name, _| seen.contains(name));
+        Ok(())
+    }
+
+    /// Find the Reader file for a v2 book: by URL first, then by exact title
+    fn find(&self, book: &Book) -> Option<&str> {
+        if let Some(url) = book.source_url.as_deref().map(normalize_url) {
+            let by_url = self
+                .entries
+                .iter()
+                .find(|(_, e)| e.urls.contains(&url))
+                .map(|(name, _)| name.as_str());
+            if by_url.is_some() {
+                return by_url;
+            }
+        }
+
+        let title = book.title.trim().to_lowercase();
+        self.entries
+            .iter()
+            .find(|(_, e)| !e.title.is_empty() && e.title.trim().to_lowercase() == title)
+            .map(|(name, _)| name.as_str())
+    }
+}
+
+/// Top-level `key: value` pairs from a markdown file's YAML frontmatter
+fn parse_frontmatter(content: &str) -> HashMap<String, String> {
+    let mut fields = HashMap::new();
+    let Some(rest) = content.strip_prefix("---\n") else {
+        return fields;
+    };
+    for line in rest.lines().take_while(|l| *l != "---") {
+        if let Some((key, value)) = line.split_once(':') {
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .map(|v| v.replace("\\\"", "\"").replace("\\\\", "\\"))
+                .unwrap_or_else(|| value.to_string());
+            fields.insert(key.trim().to_string(), value);
+        }
+    }
+    fields
+}
+
+fn normalize_url(url: &str) -> String {
+    url.trim().trim_end_matches('/').to_string()
+}
+
+// ============================================================================
+// Synced file index (readwise_id -> markdown files)
+// ============================================================================
+
+/// Markdown files already synced into a directory, by the `readwise_id` in
+/// their frontmatter. Filenames follow the title, so a title changed on
+/// Readwise is found here by id and renamed rather than written again.
+#[derive(Debug, Default)]
+struct IdIndex {
+    files: HashMap<String, Vec<String>>,
+}
+
+impl IdIndex {
+    fn build(dir: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut index = IdIndex::default();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("md") {
+                continue;
+            }
+            let Some(filename) = path.file_name().and_then(|f| f.to_str()) else {
+                continue;
+            };
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            if let Some(id) = parse_frontmatter(&content).remove("readwise_id") {
+                index.files.entry(id).or_default().push(filename.to_string());
+            }
+        }
+        Ok(index)
+    }
+
+    /// Make `filename` the file for `id` before it is written: an existing
+    /// file for the id is renamed to it, and any further copies (left by
+    /// earlier title changes) are deleted. Returns the name the kept file
+    /// had, if there was one.
+    fn claim(&mut self, dir: &Path, id: &str, filename: &str) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        let existing = self.files.remove(id).unwrap_or_default();
+        let keep = if existing.iter().any(|f| f == filename) {
+            Some(filename.to_string())
+        } else {
+            existing
+                .iter()
+                .max_by_key(|f| fs::metadata(dir.join(f)).and_then(|m| m.modified()).ok())
+                .cloned()
+        };
+
+        for stale in existing.iter().filter(|f| Some(*f) != keep.as_ref()) {
+            fs::remove_file(dir.join(stale))?;
+            println!("    Removed duplicate of {}: {}", id, stale);
+        }
+        if let Some(old) = keep.as_deref().filter(|old| *old != filename) {
+            fs::rename(dir.join(old), dir.join(filename))?;
+            println!("    Renamed: {} -> {}", old, filename);
+        }
+
+        // Whatever id the target name belonged to, it is about to hold this one
+        for files in self.files.values_mut() {
+            files.retain(|f| f != filename);
+        }
+        self.files.insert(id.to_string(), vec![filename.to_string()]);
+        Ok(keep)
+    }
+
+    /// Forget the files for `id`, returning their names
+    fn take(&mut self, id: &str) -> Vec<String> {
+        self.files.remove(id).unwrap_or_default()
+    }
+
+    /// Record `filename` as another file for `id`
+    fn add(&mut self, id: &str, filename: &str) {
+        self.files.entry(id.to_string()).or_default().push(filename.to_string());
+    }
+}
+
 // ============================================================================
 // Main sync logic
 // ============================================================================
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let quiet = env::args().any(|arg| arg == "--quiet");
+    let log_run = env::args().any(|arg| arg == "--log-run");
+
+    let mut record = RunRecord::new(Local::now());
+    let result = run(&mut record);
+    if let Err(e) = &result {
+        record.error = Some(e.to_string());
+    }
+
+    report::notify(&record, quiet, &SystemNotifier);
+    if log_run {
+        let log_path = get_base_dir().join("sync-log.md");
+        if let Err(e) = report::append_log(&log_path, &record) {
+            eprintln!("Warning: couldn't write {}: {}", log_path.display(), e);
+        }
+    }
+
+    result
+}
+
+/// The sync itself, filling in `record`'s counts as each step finishes
+fn run(record: &mut RunRecord) -> Result<(), Box<dyn std::error::Error>> {
+    let merge_reader_highlights = env::args().any(|arg| arg == "--merge-reader-highlights");
+    let vault_compat = env::args().any(|arg| arg == "--vault-compat");
+
     // Get API token
     let token = get_api_token()?;
 
@@ -152,6 +375,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let highlights_dir = base_dir.join("highlights");
     let reader_dir = base_dir.join("reader");
     let state_path = base_dir.join("sync-state.json");
+    let index_path = base_dir.join("reader-index.json");
 
     // Ensure directories exist
     fs::create_dir_all(&highlights_dir)?;
@@ -164,17 +388,57 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Create HTTP client
     let client = create_client(&token)?;
 
-    // Sync highlights
-    println!("Syncing Readwise highlights...");
-    let highlights_count = sync_highlights(&client, &highlights_dir, &state.last_highlights_sync)?;
-    println!("  Synced {} books with highlights", highlights_count);
-    state.last_highlights_sync = Some(now.clone());
+    // Highlights synced before category folders move into them
+    for moved in category::migrate_flat(&highlights_dir)? {
+        match moved {
+            category::Move::Moved { file, category } => println!("Moved: {} -> {}/{}", file, category, file),
+            category::Move::Conflict { file, category } => {
+                println!("Not moved: {} ({}/{} already exists)", file, category, file)
+            }
+        }
+    }
+
+    let templates = match Templates::default_dir() {
+        Some(dir) => Templates::load(&dir)?,
+        None => Templates::default(),
+    };
+    let mut templated: Vec<&str> = templates.categories().collect();
+    if !templated.is_empty() {
+        templated.sort();
+        println!("Using templates for: {}", templated.join(", "));
+    }
+    let mut reader_files = IdIndex::build(&reader_dir)?;
+    let mut highlight_dirs = CategoryDirs::new(&highlights_dir)?;
 
-    // Sync Reader documents
+    // Sync Reader documents first so highlights can be merged into them
     println!("Syncing Reader documents...");
-    let reader_count = sync_reader(&client, &reader_dir, &state.last_reader_sync)?;
+    let reader_count = sync_reader(&client, &reader_dir, &mut reader_files, &state.last_reader_sync, vault_compat)?;
     println!("  Synced {} documents", reader_count);
-    state.last_reader_sync = Some(now);
+    record.documents = Some(reader_count);
+    state.last_reader_sync = Some(now.clone());
+
+    let reader_index = if merge_reader_highlights {
+        let mut index = ReaderIndex::load(&index_path);
+        index.refresh(&reader_dir)?;
+        index.save(&index_path)?;
+        Some(index)
+    } else {
+        None
+    };
+
+    // Sync highlights
+    println!("Syncing Readwise highlights...");
+    let highlights_count = sync_highlights(
+        &client,
+        &mut highlight_dirs,
+        &templates,
+        &state.last_highlights_sync,
+        reader_index.as_ref().map(|index| (index, reader_dir.as_path())),
+        vault_compat,
+    )?;
+    println!("  Synced {} books with highlights", highlights_count);
+    record.books = Some(highlights_count);
+    state.last_highlights_sync = Some(now);
 
     // Save state
     state.save(&state_path)?;
@@ -234,10 +498,14 @@ fn create_client(token: &str) -> Result<Client, Box<dyn std::error::Error>> {
 
 fn sync_highlights(
     client: &Client,
-    output_dir: &PathBuf,
+    output: &mut CategoryDirs,
+    templates: &Templates,
     last_sync: &Option<String>,
+    reader: Option<(&ReaderIndex, &Path)>,
+    vault_compat: bool,
 ) -> Result<u32, Box<dyn std::error::Error>> {
     let mut total_books = 0;
+    let mut merged_count = 0;
     let mut cursor: Option<String> = None;
 
     loop {
@@ -254,7 +522,17 @@ fn sync_highlights(
         let response: HighlightsExportResponse = client.get(url).send()?.json()?;
 
         for book in response.results {
-            write_book_markdown(&book, output_dir)?;
+            let reader_file = reader.and_then(|(index, dir)| Some(dir.join(index.find(&book)?)));
+            match reader_file {
+                Some(path) => {
+                    merge_highlights_into(&path, &book)?;
+                    merged_count += 1;
+                }
+                None => {
+                    let (dir, files) = output.get(&book.category, &book.id)?;
+                    write_book_markdown(&book, &dir, files, templates.get(&book.category), vault_compat)?
+                }
+            }
             total_books += 1;
         }
 
@@ -264,98 +542,197 @@ fn sync_highlights(
         }
     }
 
+    if reader.is_some() {
+        println!("    ({} merged into Reader documents)", merged_count);
+    }
     Ok(total_books)
 }
 
-fn write_book_markdown(book: &Book, output_dir: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
-    let filename = format!(
-        "{}-{}.md",
-        slugify(&book.category),
-        slugify(&book.title.chars().take(50).collect::<String>())
-    );
-    let path = output_dir.join(&filename);
+/// Write a book's highlights file, through `template` if its category has one
+fn write_book_markdown(
+    book: &Book,
+    output_dir: &Path,
+    files: &mut IdIndex,
+    template: Option<&str>,
+    vault_compat: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let filename = if vault_compat {
+        vault_filename(output_dir, &book.title, &book.id)
+    } else {
+        format!(
+            "{}-{}.md",
+            slugify(&book.category),
+            slugify(book.title.chars().take(50).collect::<String>())
+        )
+    };
+    files.claim(output_dir, &book.id, &filename)?;
+    let content = match template {
+        Some(template) => category::render_template(template, book, vault_compat),
+        None => render_book_markdown(book, vault_compat),
+    };
+    fs::write(output_dir.join(&filename), content)?;
+    Ok(())
+}
 
-    let mut file = fs::File::create(&path)?;
+fn render_book_markdown(book: &Book, vault_compat: bool) -> String {
+    let mut out = String::new();
 
     // Frontmatter
-    writeln!(file, "---")?;
-    writeln!(file, "title: \"{}\"", escape_yaml(&book.title))?;
+    let _ = writeln!(out, "---");
+    let _ = writeln!(out, "title: \"{}\"", escape_yaml(&book.title));
+    if vault_compat {
+        let _ = writeln!(out, "aliases: [\"{}\"]", escape_yaml(&book.title));
+    }
     if let Some(ref author) = book.author {
-        writeln!(file, "author: \"{}\"", escape_yaml(author))?;
+        let _ = writeln!(out, "author: \"{}\"", escape_yaml(author));
     }
-    writeln!(file, "category: {}", book.category)?;
+    let _ = writeln!(out, "category: {}", book.category);
     if let Some(ref source) = book.source {
-        writeln!(file, "source: {}", source)?;
+        let _ = writeln!(out, "source: {}", source);
     }
     if let Some(ref url) = book.source_url {
-        writeln!(file, "source_url: \"{}\"", url)?;
+        let _ = writeln!(out, "source_url: \"{}\"", url);
     }
-    writeln!(file, "highlight_count: {}", book.num_highlights)?;
-    writeln!(file, "readwise_id: {}", book.id)?;
+    let _ = writeln!(out, "highlight_count: {}", book.num_highlights);
+    let _ = writeln!(out, "readwise_id: {}", book.id);
     if !book.book_tags.is_empty() {
         let tags: Vec<&str> = book.book_tags.iter().map(|t| t.name.as_str()).collect();
-        writeln!(file, "tags: [{}]", tags.join(", "))?;
+        let _ = writeln!(out, "tags: {}", format_tags(tags, vault_compat));
     }
-    writeln!(file, "---")?;
-    writeln!(file)?;
+    if vault_compat {
+        let (created, modified) = book_date_range(book);
+        if let Some(created) = created {
+            let _ = writeln!(out, "date created: {}", vault_date(&created));
+        }
+        if let Some(modified) = modified {
+            let _ = writeln!(out, "date modified: {}", vault_date(&modified));
+        }
+    }
+    let _ = writeln!(out, "---");
+    let _ = writeln!(out);
 
     // Title
-    writeln!(file, "# {}", book.title)?;
+    if vault_compat {
+        let _ = writeln!(out, "# {}", sanitize_title(&book.title));
+    } else {
+        let _ = writeln!(out, "# {}", book.title);
+    }
     if let Some(ref author) = book.author {
-        writeln!(file, "*by {}*", author)?;
+        let _ = writeln!(out, "*by {}*", author);
     }
-    writeln!(file)?;
+    let _ = writeln!(out);
 
     if let Some(ref url) = book.source_url {
-        writeln!(file, "Source: <{}>", url)?;
-        writeln!(file)?;
+        let _ = writeln!(out, "Source: <{}>", url);
+        let _ = writeln!(out);
     }
 
     // Highlights
-    writeln!(file, "## Highlights")?;
-    writeln!(file)?;
+    out.push_str(&format_highlights_section(&book.highlights));
+
+    out
+}
+
+/// Earliest and latest activity across a book's live highlights. Books carry
+/// no timestamps of their own in the export API.
+fn book_date_range(book: &Book) -> (Option<DateTime<FixedOffset>>, Option<DateTime<FixedOffset>>) {
+    let parse = |s: &Option<String>| s.as_deref().and_then(|s| DateTime::parse_from_rfc3339(s).ok());
+    let live = book.highlights.iter().filter(|h| !h.is_deleted);
+
+    let created = live.clone()
+        .filter_map(|h| parse(&h.highlighted_at).or_else(|| parse(&h.created_at)))
+        .min();
+    let modified = live
+        .filter_map(|h| parse(&h.updated_at).or_else(|| parse(&h.highlighted_at)))
+        .max();
+    (created, modified)
+}
+
+/// The `## Highlights` section: heading plus one quoted block per live highlight
+fn format_highlights_section(highlights: &[Highlight]) -> String {
+    format!("## Highlights\n\n{}", format_highlights(highlights))
+}
+
+/// One quoted block per live highlight, each followed by a `---` rule
+fn format_highlights(highlights: &[Highlight]) -> String {
+    let mut out = String::new();
 
-    for highlight in &book.highlights {
+    for highlight in highlights {
         if highlight.is_deleted {
             continue;
         }
 
-        writeln!(file, "> {}", highlight.text.replace('\n', "\n> "))?;
+        out.push_str(&format!("> {}\n", highlight.text.replace('\n', "\n> ")));
 
         if let Some(ref note) = highlight.note {
             if !note.is_empty() {
-                writeln!(file)?;
-                writeln!(file, "**Note:** {}", note)?;
+                out.push_str(&format!("\n**Note:** {}\n", note));
             }
         }
 
         if !highlight.tags.is_empty() {
             let tags: Vec<String> = highlight.tags.iter().map(|t| format!("#{}", t.name)).collect();
-            writeln!(file, "\n{}", tags.join(" "))?;
+            out.push_str(&format!("\n{}\n", tags.join(" ")));
         }
 
         if let Some(ref date) = highlight.highlighted_at {
             if let Some(short_date) = date.get(..10) {
-                writeln!(file, "\n— {}", short_date)?;
+                out.push_str(&format!("\n— {}\n", short_date));
             }
         }
 
-        writeln!(file)?;
-        writeln!(file, "---")?;
-        writeln!(file)?;
+        out.push_str("\n---\n\n");
     }
 
+    out
+}
+
+/// Write a book's highlights into an existing Reader document, replacing any
+/// previously merged `## Highlights` section
+fn merge_highlights_into(path: &Path, book: &Book) -> Result<(), Box<dyn std::error::Error>> {
+    let content = fs::read_to_string(path)?;
+    let section = format_highlights_section(&book.highlights);
+    fs::write(path, replace_section(&content, "## Highlights", &section))?;
     Ok(())
 }
 
+/// Byte range of a `## ` section (heading through to the next `## ` heading or EOF)
+fn find_section(content: &str, heading: &str) -> Option<(usize, usize)> {
+    let start = if content.starts_with(heading) {
+        0
+    } else {
+        content.find(&format!("\n{}", heading))? + 1
+    };
+    let body_start = start + heading.len();
+    let end = content[body_start..]
+        .find("\n## ")
+        .map_or(content.len(), |offset| body_start + offset + 1);
+    Some((start, end))
+}
+
+/// Replace a `## ` section in place, or append it if absent
+fn replace_section(content: &str, heading: &str, section: &str) -> String {
+    match find_section(content, heading) {
+        Some((start, end)) => format!("{}{}{}", &content[..start], section, &content[end..]),
+        None => {
+            let mut out = content.trim_end().to_string();
+            out.push_str("\n\n");
+            out.push_str(section);
+            out
+        }
+    }
+}
+
 // ============================================================================
 // Reader sync (Readwise API v3)
 // ============================================================================
 
 fn sync_reader(
     client: &Client,
-    output_dir: &PathBuf,
+    output_dir: &Path,
+    files: &mut IdIndex,
     last_sync: &Option<String>,
+    vault_compat: bool,
 ) -> Result<u32, Box<dyn std::error::Error>> {
     let mut total_docs = 0;
     let mut html_count = 0;
@@ -383,7 +760,7 @@ fn sync_reader(
 
         for doc in response.results {
             let has_html = doc.html_content.is_some();
-            write_document_markdown(&doc, output_dir, &html_dir)?;
+            write_document_markdown(&doc, output_dir, &html_dir, files, vault_compat)?;
             total_docs += 1;
             if has_html {
                 html_count += 1;
@@ -402,95 +779,161 @@ fn sync_reader(
 
 fn write_document_markdown(
     doc: &Document,
-    output_dir: &PathBuf,
-    html_dir: &PathBuf,
+    output_dir: &Path,
+    html_dir: &Path,
+    files: &mut IdIndex,
+    vault_compat: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let title = doc.title.as_deref().unwrap_or("Untitled");
     let date_prefix = doc.created_at.get(..10).unwrap_or("unknown");
     let base_filename = format!(
         "{}-{}",
         date_prefix,
-        slugify(&title.chars().take(50).collect::<String>())
+        slugify(title.chars().take(50).collect::<String>())
     );
-    let md_filename = format!("{}.md", base_filename);
+    let md_filename = if vault_compat {
+        vault_filename(output_dir, title, &doc.id)
+    } else {
+        format!("{}.md", base_filename)
+    };
     let html_filename = format!("{}.html", base_filename);
     let path = output_dir.join(&md_filename);
 
+    let previous = files.claim(output_dir, &doc.id, &md_filename)?;
+    let existing = previous.and_then(|_| fs::read_to_string(&path).ok());
+
+    // A renamed document takes its HTML snapshot along
+    let old_snapshot = existing.as_deref().and_then(|content| {
+        let snapshot = parse_frontmatter(content).remove("html_snapshot")?;
+        Some(snapshot.strip_prefix("html/")?.to_string())
+    });
+    if let Some(old) = old_snapshot.filter(|old| *old != html_filename) {
+        if html_dir.join(&old).exists() {
+            fs::rename(html_dir.join(&old), html_dir.join(&html_filename))?;
+        }
+    }
+
+    // Keep highlights merged in by --merge-reader-highlights across rewrites
+    let merged_highlights = existing.and_then(|existing| {
+        let (start, end) = find_section(&existing, "## Highlights")?;
+        Some(existing[start..end].to_string())
+    });
+
     // Save HTML snapshot if available
     let html_saved = if let Some(ref html_content) = doc.html_content {
         let html_path = html_dir.join(&html_filename);
         fs::write(&html_path, html_content)?;
         true
     } else {
-        false
+        // Keep pointing at a snapshot from an earlier sync (possibly just renamed)
+        html_dir.join(&html_filename).exists()
     };
 
-    let mut file = fs::File::create(&path)?;
+    let markdown = render_document_markdown(
+        doc,
+        html_saved.then_some(html_filename.as_str()),
+        merged_highlights.as_deref(),
+        vault_compat,
+    );
+    fs::write(&path, markdown)?;
+    Ok(())
+}
+
+fn render_document_markdown(
+    doc: &Document,
+    html_filename: Option<&str>,
+    merged_highlights: Option<&str>,
+    vault_compat: bool,
+) -> String {
+    let title = doc.title.as_deref().unwrap_or("Untitled");
+    let mut out = String::new();
 
     // Frontmatter
-    writeln!(file, "---")?;
-    writeln!(file, "title: \"{}\"", escape_yaml(title))?;
+    let _ = writeln!(out, "---");
+    let _ = writeln!(out, "title: \"{}\"", escape_yaml(title));
+    if vault_compat {
+        let _ = writeln!(out, "aliases: [\"{}\"]", escape_yaml(title));
+    }
     if let Some(ref author) = doc.author {
-        writeln!(file, "author: \"{}\"", escape_yaml(author))?;
+        let _ = writeln!(out, "author: \"{}\"", escape_yaml(author));
     }
-    writeln!(file, "category: {}", doc.category)?;
-    writeln!(file, "location: {}", doc.location)?;
-    writeln!(file, "url: \"{}\"", doc.url)?;
+    let _ = writeln!(out, "category: {}", doc.category);
+    let _ = writeln!(out, "location: {}", doc.location);
+    let _ = writeln!(out, "url: \"{}\"", doc.url);
     if let Some(ref source_url) = doc.source_url {
-        writeln!(file, "source_url: \"{}\"", source_url)?;
+        let _ = writeln!(out, "source_url: \"{}\"", source_url);
     }
-    if html_saved {
-        writeln!(file, "html_snapshot: \"html/{}\"", html_filename)?;
+    if let Some(html_filename) = html_filename {
+        let _ = writeln!(out, "html_snapshot: \"html/{}\"", html_filename);
     }
     if let Some(word_count) = doc.word_count {
-        writeln!(file, "word_count: {}", word_count)?;
+        let _ = writeln!(out, "word_count: {}", word_count);
     }
-    writeln!(file, "reading_progress: {:.0}%", doc.reading_progress * 100.0)?;
-    writeln!(file, "created_at: {}", doc.created_at)?;
-    writeln!(file, "updated_at: {}", doc.updated_at)?;
-    writeln!(file, "readwise_id: \"{}\"", doc.id)?;
+    let _ = writeln!(out, "reading_progress: {:.0}%", doc.reading_progress * 100.0);
+    let _ = writeln!(out, "created_at: {}", doc.created_at);
+    let _ = writeln!(out, "updated_at: {}", doc.updated_at);
+    if vault_compat {
+        for (key, value) in [("date created", &doc.created_at), ("date modified", &doc.updated_at)] {
+            if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+                let _ = writeln!(out, "{}: {}", key, vault_date(&date));
+            }
+        }
+    }
+    let _ = writeln!(out, "readwise_id: \"{}\"", doc.id);
     if !doc.tags.is_empty() {
-        let tags: Vec<&String> = doc.tags.keys().collect();
-        writeln!(file, "tags: [{}]", tags.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(", "))?;
+        let mut tags: Vec<&str> = doc.tags.keys().map(|s| s.as_str()).collect();
+        if vault_compat {
+            // HashMap order would churn every regenerated file
+            tags.sort_unstable();
+        }
+        let _ = writeln!(out, "tags: {}", format_tags(tags, vault_compat));
     }
-    writeln!(file, "---")?;
-    writeln!(file)?;
+    let _ = writeln!(out, "---");
+    let _ = writeln!(out);
 
     // Title and metadata
-    writeln!(file, "# {}", title)?;
+    if vault_compat {
+        let _ = writeln!(out, "# {}", sanitize_title(title));
+    } else {
+        let _ = writeln!(out, "# {}", title);
+    }
     if let Some(ref author) = doc.author {
-        writeln!(file, "*by {}*", author)?;
+        let _ = writeln!(out, "*by {}*", author);
     }
-    writeln!(file)?;
+    let _ = writeln!(out);
 
-    writeln!(file, "**URL:** <{}>", doc.url)?;
-    writeln!(file, "**Status:** {} ({:.0}% read)", doc.location, doc.reading_progress * 100.0)?;
-    if html_saved {
-        writeln!(file, "**Local snapshot:** [[captures/readwise/reader/html/{}]]", html_filename)?;
+    let _ = writeln!(out, "**URL:** <{}>", doc.url);
+    let _ = writeln!(out, "**Status:** {} ({:.0}% read)", doc.location, doc.reading_progress * 100.0);
+    if let Some(html_filename) = html_filename {
+        let _ = writeln!(out, "**Local snapshot:** [[captures/readwise/reader/html/{}]]", html_filename);
     }
-    writeln!(file)?;
+    let _ = writeln!(out);
 
     // Summary
     if let Some(ref summary) = doc.summary {
         if !summary.is_empty() {
-            writeln!(file, "## Summary")?;
-            writeln!(file)?;
-            writeln!(file, "{}", summary)?;
-            writeln!(file)?;
+            let _ = writeln!(out, "## Summary");
+            let _ = writeln!(out);
+            let _ = writeln!(out, "{}", summary);
+            let _ = writeln!(out);
         }
     }
 
     // Notes
     if let Some(ref notes) = doc.notes {
         if !notes.is_empty() {
-            writeln!(file, "## Notes")?;
-            writeln!(file)?;
-            writeln!(file, "{}", notes)?;
-            writeln!(file)?;
+            let _ = writeln!(out, "## Notes");
+            let _ = writeln!(out);
+            let _ = writeln!(out, "{}", notes);
+            let _ = writeln!(out);
         }
     }
 
-    Ok(())
+    if let Some(highlights) = merged_highlights {
+        out.push_str(highlights);
+    }
+
+    out
 }
 
 // ============================================================================
@@ -503,6 +946,48 @@ fn escape_yaml(s: &str) -> String {
         .replace('\n', " ")
 }
 
+/// Flow-style YAML tag list. With `quote`, tags that YAML would misread
+/// (commas, colons, brackets, leading `#` etc.) are double-quoted.
+fn format_tags(tags: Vec<&str>, quote: bool) -> String {
+    const SPECIAL: &[char] = &[',', '[', ']', '{', '}', ':', '#', '&', '*', '!', '|', '>', '\'', '"', '%', '@', '`'];
+    let items: Vec<String> = tags
+        .into_iter()
+        .map(|tag| {
+            let needs_quotes = tag.is_empty()
+                || tag.contains(SPECIAL)
+                || tag.starts_with(['-', '?', ' '])
+                || tag.ends_with(' ');
+            if quote && needs_quotes {
+                format!("\"{}\"", escape_yaml(tag))
+            } else {
+                tag.to_string()
+            }
+        })
+        .collect();
+    format!("[{}]", items.join(", "))
+}
+
+/// The `date created` / `date modified` format used across the vault
+fn vault_date(date: &DateTime<FixedOffset>) -> String {
+    date.format("%Y-%m-%d %H:%M").to_string()
+}
+
+/// `<title>.md` for --vault-compat output, or `<title> (<id>).md` when a
+/// different Readwise item already owns that name
+fn vault_filename(dir: &Path, title: &str, id: &str) -> String {
+    let base = sanitize_title(title);
+    let filename = format!("{}.md", base);
+    let taken_by_other = fs::read_to_string(dir.join(&filename))
+        .ok()
+        .and_then(|content| parse_frontmatter(&content).get("readwise_id").cloned())
+        .is_some_and(|existing| existing != id);
+    if taken_by_other {
+        format!("{} ({}).md", base, id)
+    } else {
+        filename
+    }
+}
+
 /// Deserialize an ID that could be either a string or an integer
 fn deserialize_id<'de, D>(deserializer: D) -> Result<String, D::Error>
 where
@@ -623,3 +1108,213 @@ where
     Option::<HashMap<String, serde_json::Value>>::deserialize(deserializer)
         .map(|opt| opt.unwrap_or_default())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPORT_FIXTURE: &str = include_str!("../tests/fixtures/highlights-export.json");
+    const READER_FIXTURE: &str = include_str!("../tests/fixtures/reader-document.md");
+
+    fn fixture_books() -> Vec<Book> {
+        let response: HighlightsExportResponse = serde_json::from_str(EXPORT_FIXTURE).unwrap();
+        response.results
+    }
+
+    /// A reader/ directory holding the fixture document, plus its fresh index
+    fn reader_dir_with_fixture() -> (tempfile::TempDir, ReaderIndex) {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("2025-03-01-the-slow-web.md"), READER_FIXTURE).unwrap();
+        let mut index = ReaderIndex::default();
+        index.refresh(dir.path()).unwrap();
+        (dir, index)
+    }
+
+    #[test]
+    fn index_reads_urls_and_title_from_frontmatter() {
+        let (_dir, index) = reader_dir_with_fixture();
+        let entry = &index.entries["2025-03-01-the-slow-web.md"];
+        assert_eq!(entry.title, "The Slow Web");
+        assert!(entry.urls.contains(&"https://example.com/the-slow-web".to_string()));
+        assert!(entry.urls.contains(&"https://read.readwise.io/read/01abc".to_string()));
+    }
+
+    #[test]
+    fn matched_book_finds_reader_document_by_url() {
+        let (_dir, index) = reader_dir_with_fixture();
+        let books = fixture_books();
+        assert_eq!(index.find(&books[0]), Some("2025-03-01-the-slow-web.md"));
+    }
+
+    #[test]
+    fn unmatched_book_has_no_reader_document() {
+        let (_dir, index) = reader_dir_with_fixture();
+        let books = fixture_books();
+        assert_eq!(index.find(&books[1]), None);
+    }
+
+    #[test]
+    fn index_drops_deleted_files() {
+        let (dir, mut index) = reader_dir_with_fixture();
+        fs::remove_file(dir.path().join("2025-03-01-the-slow-web.md")).unwrap();
+        index.refresh(dir.path()).unwrap();
+        assert!(index.entries.is_empty());
+    }
+
+    #[test]
+    fn merge_appends_highlights_section_once() {
+        let (dir, index) = reader_dir_with_fixture();
+        let books = fixture_books();
+        let path = dir.path().join(index.find(&books[0]).unwrap());
+
+        merge_highlights_into(&path, &books[0]).unwrap();
+        merge_highlights_into(&path, &books[0]).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content.matches("## Highlights").count(), 1);
+        assert!(content.contains("> Timely, not real-time."));
+        assert!(content.contains("**Note:** Key idea"));
+        assert!(!content.contains("A deleted highlight"));
+        assert!(content.find("## Summary").unwrap() < content.find("## Highlights").unwrap());
+    }
+
+    const SNAPSHOT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/snapshots");
+
+    fn snapshot(name: &str) -> String {
+        fs::read_to_string(Path::new(SNAPSHOT_DIR).join(name)).unwrap()
+    }
+
+    fn fixture_document() -> Document {
+        let response: ReaderListResponse =
+            serde_json::from_str(include_str!("../tests/fixtures/reader-list.json")).unwrap();
+        response.results.into_iter().next().unwrap()
+    }
+
+    #[test]
+    fn book_markdown_default_unchanged() {
+        let books = fixture_books();
+        assert_eq!(render_book_markdown(&books[2], false), snapshot("book.md"));
+    }
+
+    #[test]
+    fn book_markdown_vault_compat() {
+        let books = fixture_books();
+        assert_eq!(render_book_markdown(&books[2], true), snapshot("book-vault-compat.md"));
+    }
+
+    #[test]
+    fn document_markdown_default_unchanged() {
+        let mut doc = fixture_document();
+        // Default output keeps HashMap tag order, so pin it to a single tag
+        doc.tags.retain(|name, _| name == "productivity");
+        assert_eq!(render_document_markdown(&doc, None, None, false), snapshot("document.md"));
+    }
+
+    #[test]
+    fn document_markdown_vault_compat() {
+        let doc = fixture_document();
+        let rendered = render_document_markdown(&doc, Some("2025-04-03-deep-work.html"), None, true);
+        assert_eq!(rendered, snapshot("document-vault-compat.md"));
+    }
+
+    #[test]
+    fn vault_filename_avoids_other_items() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(vault_filename(dir.path(), "Meditations", "40002"), "Meditations.md");
+
+        fs::write(dir.path().join("Meditations.md"), "---\nreadwise_id: 40002\n---\n").unwrap();
+        assert_eq!(vault_filename(dir.path(), "Meditations", "40002"), "Meditations.md");
+        assert_eq!(vault_filename(dir.path(), "Meditations", "50000"), "Meditations (50000).md");
+    }
+
+    fn ids_in(dir: &Path) -> Vec<(String, String)> {
+        let mut files: Vec<(String, String)> = fs::read_dir(dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .filter(|p| p.extension().is_some_and(|e| e == "md"))
+            .map(|p| {
+                let id = parse_frontmatter(&fs::read_to_string(&p).unwrap())["readwise_id"].clone();
+                (p.file_name().unwrap().to_string_lossy().to_string(), id)
+            })
+            .collect();
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn retitled_book_is_renamed_not_duplicated() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut book = fixture_books().remove(2);
+        let id = book.id.clone();
+
+        let mut files = IdIndex::build(dir.path()).unwrap();
+        write_book_markdown(&book, dir.path(), &mut files, None, false).unwrap();
+        let before = ids_in(dir.path());
+        assert_eq!(before.len(), 1);
+
+        // Next run: fresh index from disk, new title on Readwise
+        book.title = "Meditations (Hays translation)".to_string();
+        let mut files = IdIndex::build(dir.path()).unwrap();
+        write_book_markdown(&book, dir.path(), &mut files, None, false).unwrap();
+
+        let after = ids_in(dir.path());
+        assert_eq!(after, vec![(format!("{}-meditations-hays-translation.md", book.category), id)]);
+        assert_ne!(before[0].0, after[0].0);
+    }
+
+    #[test]
+    fn stale_copies_of_an_id_are_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, id) in [("Old title.md", "7"), ("Older title.md", "7"), ("Other.md", "8")] {
+            fs::write(dir.path().join(name), format!("---\nreadwise_id: {}\n---\n", id)).unwrap();
+        }
+
+        let mut files = IdIndex::build(dir.path()).unwrap();
+        let previous = files.claim(dir.path(), "7", "New title.md").unwrap();
+        assert!(matches!(previous.as_deref(), Some("Old title.md" | "Older title.md")));
+
+        let names: Vec<String> = ids_in(dir.path()).into_iter().map(|(name, _)| name).collect();
+        assert_eq!(names, vec!["New title.md", "Other.md"]);
+    }
+
+    #[test]
+    fn document_rename_moves_snapshot_and_merged_highlights() {
+        let dir = tempfile::tempdir().unwrap();
+        let html_dir = dir.path().join("html");
+        fs::create_dir_all(&html_dir).unwrap();
+        let mut doc = fixture_document();
+        doc.html_content = Some("<p>Deep work</p>".to_string());
+
+        let mut files = IdIndex::build(dir.path()).unwrap();
+        write_document_markdown(&doc, dir.path(), &html_dir, &mut files, false).unwrap();
+        let (first, _) = ids_in(dir.path()).remove(0);
+        let path = dir.path().join(&first);
+        let content = fs::read_to_string(&path).unwrap();
+        fs::write(&path, format!("{}## Highlights\n\n> kept\n", content)).unwrap();
+
+        doc.title = Some("Deep Work, Revisited".to_string());
+        doc.html_content = None;
+        let mut files = IdIndex::build(dir.path()).unwrap();
+        write_document_markdown(&doc, dir.path(), &html_dir, &mut files, false).unwrap();
+
+        let after = ids_in(dir.path());
+        assert_eq!(after.len(), 1);
+        assert!(after[0].0.ends_with("-deep-work-revisited.md"));
+        let content = fs::read_to_string(dir.path().join(&after[0].0)).unwrap();
+        assert!(content.contains("> kept"));
+        assert!(content.contains(&format!("html_snapshot: \"html/{}\"", after[0].0.replace(".md", ".html"))));
+
+        let snapshots: Vec<String> = fs::read_dir(&html_dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(snapshots, vec![after[0].0.replace(".md", ".html")]);
+    }
+
+    #[test]
+    fn replace_section_keeps_following_sections() {
+        let content = "# T\n\n## Highlights\n\nold\n\n## Notes\n\nmine\n";
+        let result = replace_section(content, "## Highlights", "## Highlights\n\nnew\n\n");
+        assert_eq!(result, "# T\n\n## Highlights\n\nnew\n\n## Notes\n\nmine\n");
+    }
+}
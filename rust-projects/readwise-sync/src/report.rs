@@ -0,0 +1,194 @@
+//! End-of-run reporting for unattended (launchd/systemd) syncs: a desktop
+//! notification and an optional line in `sync-log.md`.
+
+use chrono::{DateTime, Local};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+const NOTIFICATION_TITLE: &str = "Readwise Sync";
+
+/// What one run did. Counts stay `None` for steps that didn't finish.
+#[derive(Debug, Clone)]
+pub struct RunRecord {
+    pub time: DateTime<Local>,
+    pub books: Option<u32>,
+    pub documents: Option<u32>,
+    pub error: Option<String>,
+}
+
+impl RunRecord {
+    pub fn new(time: DateTime<Local>) -> Self {
+        RunRecord { time, books: None, documents: None, error: None }
+    }
+
+    pub fn failed(&self) -> bool {
+        self.error.is_some()
+    }
+
+    /// `- 2026-03-01 03:00 · 12 books · 4 documents · ok`, one line however
+    /// long the error
+    pub fn log_line(&self) -> String {
+        let count = |n: Option<u32>| n.map_or("—".to_string(), |n| n.to_string());
+        let outcome = match &self.error {
+            Some(error) => format!("error: {}", one_line(error)),
+            None => "ok".to_string(),
+        };
+        format!(
+            "- {} · {} books · {} documents · {}",
+            self.time.format("%Y-%m-%d %H:%M"),
+            count(self.books),
+            count(self.documents),
+            outcome
+        )
+    }
+
+    /// Notification body
+    pub fn summary(&self) -> String {
+        match &self.error {
+            Some(error) => format!("Sync failed: {}", one_line(error)),
+            None => format!(
+                "Synced {} books, {} documents",
+                self.books.unwrap_or(0),
+                self.documents.unwrap_or(0)
+            ),
+        }
+    }
+}
+
+fn one_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Failures always notify; successes only when not `--quiet`
+pub fn should_notify(record: &RunRecord, quiet: bool) -> bool {
+    record.failed() || !quiet
+}
+
+/// Something that can show a desktop notification
+pub trait Notifier {
+    fn notify(&self, title: &str, message: &str);
+}
+
+/// osascript on macOS, notify-send on Linux. Best effort: a missing
+/// notifier never fails the sync.
+pub struct SystemNotifier;
+
+impl Notifier for SystemNotifier {
+    #[allow(unused_variables)]
+    fn notify(&self, title: &str, message: &str) {
+        #[cfg(target_os = "macos")]
+        {
+            let escape = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+            let _ = std::process::Command::new("osascript")
+                .arg("-e")
+                .arg(format!(
+                    "display notification \"{}\" with title \"{}\"",
+                    escape(message),
+                    escape(title)
+                ))
+                .status();
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            let _ = std::process::Command::new("notify-send").arg(title).arg(message).status();
+        }
+    }
+}
+
+/// Notify about the run if `should_notify` says so
+pub fn notify(record: &RunRecord, quiet: bool, notifier: &dyn Notifier) {
+    if should_notify(record, quiet) {
+        notifier.notify(NOTIFICATION_TITLE, &record.summary());
+    }
+}
+
+/// Append the run's line to `path`, starting the file with a heading
+pub fn append_log(path: &Path, record: &RunRecord) -> std::io::Result<()> {
+    let is_new = !path.exists();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if is_new {
+        writeln!(file, "# Readwise sync log\n")?;
+    }
+    writeln!(file, "{}", record.log_line())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::cell::RefCell;
+
+    #[derive(Default)]
+    struct Recorder(RefCell<Vec<(String, String)>>);
+
+    impl Notifier for Recorder {
+        fn notify(&self, title: &str, message: &str) {
+            self.0.borrow_mut().push((title.to_string(), message.to_string()));
+        }
+    }
+
+    fn record(books: Option<u32>, documents: Option<u32>, error: Option<&str>) -> RunRecord {
+        RunRecord {
+            time: Local.with_ymd_and_hms(2026, 3, 1, 3, 0, 0).unwrap(),
+            books,
+            documents,
+            error: error.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn log_lines() {
+        assert_eq!(
+            record(Some(12), Some(4), None).log_line(),
+            "- 2026-03-01 03:00 · 12 books · 4 documents · ok"
+        );
+        assert_eq!(
+            record(None, Some(4), Some("HTTP 429\nToo Many Requests")).log_line(),
+            "- 2026-03-01 03:00 · — books · 4 documents · error: HTTP 429 Too Many Requests"
+        );
+    }
+
+    #[test]
+    fn summaries() {
+        assert_eq!(record(Some(1), Some(0), None).summary(), "Synced 1 books, 0 documents");
+        assert_eq!(record(None, None, Some("No token")).summary(), "Sync failed: No token");
+    }
+
+    #[test]
+    fn failures_notify_even_when_quiet() {
+        let ok = record(Some(1), Some(2), None);
+        let failed = record(None, None, Some("boom"));
+        assert!(should_notify(&ok, false));
+        assert!(!should_notify(&ok, true));
+        assert!(should_notify(&failed, false));
+        assert!(should_notify(&failed, true));
+
+        let recorder = Recorder::default();
+        notify(&ok, true, &recorder);
+        assert!(recorder.0.borrow().is_empty());
+        notify(&failed, true, &recorder);
+        assert_eq!(
+            *recorder.0.borrow(),
+            vec![("Readwise Sync".to_string(), "Sync failed: boom".to_string())]
+        );
+    }
+
+    #[test]
+    fn log_file_gets_heading_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sync-log.md");
+        append_log(&path, &record(Some(1), Some(2), None)).unwrap();
+        append_log(&path, &record(None, None, Some("boom"))).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "# Readwise sync log\n\n\
+             - 2026-03-01 03:00 · 1 books · 2 documents · ok\n\
+             - 2026-03-01 03:00 · — books · — documents · error: boom\n"
+        );
+    }
+}
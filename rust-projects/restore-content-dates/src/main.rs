@@ -1,22 +1,25 @@
 use anyhow::{Context, Result};
-use chrono::{DateTime, NaiveDateTime, Utc};
+use chrono::{DateTime, Utc};
 use clap::Parser;
 use indicatif::{ProgressBar, ProgressStyle};
-use quick_xml::events::Event;
-use quick_xml::Reader;
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use strsim::jaro_winkler;
 use walkdir::WalkDir;
 
+mod source;
+mod tags;
+
+use source::{SourceFormat, SourceNote};
+
 #[derive(Parser, Debug)]
 #[command(name = "restore-content-dates")]
-#[command(about = "Restore file creation dates from Evernote export using multi-strategy matching")]
+#[command(about = "Restore file creation dates from an Evernote, Bear or Apple Notes export using multi-strategy matching")]
 struct Args {
-    /// Path to Evernote .enex export file
-    #[arg(value_name = "ENEX_FILE")]
-    enex_file: PathBuf,
+    /// Evernote .enex export file, or a Bear / Apple Notes export folder
+    #[arg(value_name = "SOURCE")]
+    source: PathBuf,
 
     /// Directory containing files to update (e.g., ~/Forge)
     #[arg(value_name = "TARGET_DIR")]
@@ -37,12 +40,14 @@ struct Args {
     /// Only update files with 2025 dates (skip already-correct files)
     #[arg(long)]
     only_2025: bool,
-}
 
-#[derive(Debug, Clone)]
-struct EvernoteNote {
-    title: String,
-    created: String,
+    /// Merge each note's Evernote tags into the matched file's frontmatter `tags:`
+    #[arg(long)]
+    import_tags: bool,
+
+    /// Export format of SOURCE (default: detected from the path)
+    #[arg(long, value_enum)]
+    source_format: Option<SourceFormat>,
 }
 
 #[derive(Debug)]
@@ -55,8 +60,6 @@ struct MarkdownFile {
 #[derive(Debug)]
 struct MatchResult {
     status: MatchStatus,
-    note_title: String,
-    file_path: Option<PathBuf>,
     match_strategy: Option<String>,
 }
 
@@ -65,7 +68,6 @@ enum MatchStatus {
     Updated,
     WouldUpdate,
     NoMatch,
-    Skipped2024,
     Error(String),
 }
 
@@ -74,7 +76,11 @@ fn main() -> Result<()> {
 
     println!("Multi-Strategy Date Restoration Tool");
     println!("====================================\n");
-    println!("Reading Evernote export: {}", args.enex_file.display());
+    let format = match args.source_format {
+        Some(format) => format,
+        None => SourceFormat::detect(&args.source)?,
+    };
+    println!("Reading {} export: {}", format.label(), args.source.display());
     println!("Target directory: {}", args.target_dir.display());
     if args.only_2025 {
         println!("Mode: Only updating files with 2025 dates\n");
@@ -82,10 +88,10 @@ fn main() -> Result<()> {
         println!();
     }
 
-    // Parse Evernote export
-    println!("Parsing Evernote notes...");
-    let notes = parse_evernote_export(&args.enex_file)?;
-    println!("Found {} notes in Evernote export\n", notes.len());
+    // Load the source notes
+    println!("Parsing {} notes...", format.label());
+    let notes = format.load(&args.source)?;
+    println!("Found {} notes in {} export\n", notes.len(), format.label());
 
     // Scan target directory for markdown files
     println!("Scanning target directory for markdown files...");
@@ -105,14 +111,7 @@ fn main() -> Result<()> {
 
     // Match notes to files using multiple strategies
     println!("Matching notes to files...");
-    let results = match_notes_multi_strategy(
-        &notes,
-        &exact_map,
-        &fuzzy_list,
-        args.dry_run,
-        args.verbose,
-        args.similarity_threshold,
-    )?;
+    let results = match_notes_multi_strategy(&notes, &exact_map, &fuzzy_list, &args)?;
 
     // Print summary
     print_summary(&results, notes.len(), markdown_files.len(), args.dry_run);
@@ -120,57 +119,6 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn parse_evernote_export(path: &Path) -> Result<Vec<EvernoteNote>> {
-    let content = fs::read_to_string(path)
-        .with_context(|| format!("Failed to read file: {}", path.display()))?;
-
-    let mut reader = Reader::from_str(&content);
-    reader.trim_text(true);
-
-    let mut notes = Vec::new();
-    let mut current_title = None;
-    let mut current_created = None;
-    let mut inside_title = false;
-    let mut inside_created = false;
-
-    let mut buf = Vec::new();
-
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(ref e)) => {
-                match e.name().as_ref() {
-                    b"title" => inside_title = true,
-                    b"created" => inside_created = true,
-                    _ => {}
-                }
-            }
-            Ok(Event::Text(e)) => {
-                let text = e.unescape().unwrap().to_string();
-                if inside_title {
-                    current_title = Some(text);
-                    inside_title = false;
-                } else if inside_created {
-                    current_created = Some(text);
-                    inside_created = false;
-                }
-            }
-            Ok(Event::End(ref e)) => {
-                if e.name().as_ref() == b"note" {
-                    if let (Some(title), Some(created)) = (current_title.take(), current_created.take()) {
-                        notes.push(EvernoteNote { title, created });
-                    }
-                }
-            }
-            Ok(Event::Eof) => break,
-            Err(e) => return Err(anyhow::anyhow!("Error parsing XML: {:?}", e)),
-            _ => {}
-        }
-        buf.clear();
-    }
-
-    Ok(notes)
-}
-
 fn scan_markdown_files(dir: &Path, check_2025: bool) -> Result<Vec<MarkdownFile>> {
     let mut files = Vec::new();
 
@@ -238,14 +186,12 @@ fn build_file_indexes(
 }
 
 fn match_notes_multi_strategy(
-    notes: &[EvernoteNote],
+    notes: &[SourceNote],
     exact_map: &HashMap<String, Vec<&MarkdownFile>>,
     fuzzy_list: &[&MarkdownFile],
-    dry_run: bool,
-    verbose: bool,
-    similarity_threshold: f64,
+    args: &Args,
 ) -> Result<Vec<MatchResult>> {
-    let progress = if !verbose {
+    let progress = if !args.verbose {
         let pb = ProgressBar::new(notes.len() as u64);
         pb.set_style(
             ProgressStyle::default_bar()
@@ -261,16 +207,7 @@ fn match_notes_multi_strategy(
     let mut results = Vec::new();
 
     for (idx, note) in notes.iter().enumerate() {
-        let result = match_note_multi_strategy(
-            note,
-            exact_map,
-            fuzzy_list,
-            dry_run,
-            verbose,
-            idx + 1,
-            notes.len(),
-            similarity_threshold,
-        )?;
+        let result = match_note_multi_strategy(note, exact_map, fuzzy_list, args, idx + 1, notes.len())?;
         results.push(result);
 
         if let Some(ref pb) = progress {
@@ -286,14 +223,12 @@ fn match_notes_multi_strategy(
 }
 
 fn match_note_multi_strategy(
-    note: &EvernoteNote,
+    note: &SourceNote,
     exact_map: &HashMap<String, Vec<&MarkdownFile>>,
     fuzzy_list: &[&MarkdownFile],
-    dry_run: bool,
-    verbose: bool,
+    args: &Args,
     idx: usize,
     total: usize,
-    similarity_threshold: f64,
 ) -> Result<MatchResult> {
     // Strategy 1: Exact filename match
     let sanitized_title = sanitize_filename(&note.title);
@@ -303,8 +238,7 @@ fn match_note_multi_strategy(
                 note,
                 files[0].path.clone(),
                 "exact",
-                dry_run,
-                verbose,
+                args,
                 idx,
                 total,
             );
@@ -319,8 +253,7 @@ fn match_note_multi_strategy(
                     note,
                     files[0].path.clone(),
                     "sanitization",
-                    dry_run,
-                    verbose,
+                    args,
                     idx,
                     total,
                 );
@@ -334,7 +267,7 @@ fn match_note_multi_strategy(
     for file in fuzzy_list {
         let similarity = jaro_winkler(&sanitized_title.to_lowercase(), &file.stem.to_lowercase());
 
-        if similarity >= similarity_threshold {
+        if similarity >= args.similarity_threshold {
             if let Some((_, best_score)) = best_match {
                 if similarity > best_score {
                     best_match = Some((file, similarity));
@@ -346,28 +279,25 @@ fn match_note_multi_strategy(
     }
 
     if let Some((file, score)) = best_match {
-        if verbose {
+        if args.verbose {
             println!("Fuzzy match: {} -> {} (score: {:.2})", note.title, file.stem, score);
         }
         return process_match(
             note,
             file.path.clone(),
             &format!("fuzzy({:.2})", score),
-            dry_run,
-            verbose,
+            args,
             idx,
             total,
         );
     }
 
     // No match found
-    if verbose {
+    if args.verbose {
         println!("⊘ [{}/{}] No match: {}", idx, total, note.title);
     }
     Ok(MatchResult {
         status: MatchStatus::NoMatch,
-        note_title: note.title.clone(),
-        file_path: None,
         match_strategy: None,
     })
 }
@@ -414,57 +344,63 @@ fn generate_sanitization_variations(title: &str) -> Vec<String> {
 }
 
 fn process_match(
-    note: &EvernoteNote,
+    note: &SourceNote,
     file_path: PathBuf,
     strategy: &str,
-    dry_run: bool,
-    verbose: bool,
+    args: &Args,
     idx: usize,
     total: usize,
 ) -> Result<MatchResult> {
-    // Parse the Evernote timestamp
-    let timestamp = match parse_evernote_timestamp(&note.created) {
+    let verbose = args.verbose;
+    let timestamp = match note.timestamp {
         Ok(ts) => ts,
-        Err(e) => {
+        Err(ref e) => {
             if verbose {
                 println!("⚠ [{}/{}] Failed to parse date: {} - {}", idx, total, note.title, e);
             }
             return Ok(MatchResult {
                 status: MatchStatus::Error(format!("Failed to parse date: {}", e)),
-                note_title: note.title.clone(),
-                file_path: Some(file_path),
                 match_strategy: Some(strategy.to_string()),
             });
         }
     };
 
-    if dry_run {
+    if args.dry_run {
         if verbose {
             println!("✓ [{}/{}] Would update ({}):", idx, total, strategy);
-            println!("   Evernote: {}", note.title);
+            println!("   Note: {}", note.title);
             println!("   File: {}", file_path.display());
             println!("   Date: {}", note.created);
+            if args.import_tags && !note.tags.is_empty() {
+                println!("   Tags: {}", note.tags.join(", "));
+            }
         }
         Ok(MatchResult {
             status: MatchStatus::WouldUpdate,
-            note_title: note.title.clone(),
-            file_path: Some(file_path),
             match_strategy: Some(strategy.to_string()),
         })
     } else {
         // Update YAML frontmatter
-        match update_yaml_frontmatter(&file_path, timestamp) {
-            Ok(_) => {
+        let updated = update_yaml_frontmatter(&file_path, timestamp).and_then(|_| {
+            if args.import_tags && !note.tags.is_empty() {
+                tags::merge_tags_file(&file_path, &note.tags)
+            } else {
+                Ok(Vec::new())
+            }
+        });
+        match updated {
+            Ok(added_tags) => {
                 if verbose {
                     println!("✓ [{}/{}] Updated ({}):", idx, total, strategy);
-                    println!("   Evernote: {}", note.title);
+                    println!("   Note: {}", note.title);
                     println!("   File: {}", file_path.display());
                     println!("   Date: {}", note.created);
+                    if !added_tags.is_empty() {
+                        println!("   Tags added: {}", added_tags.join(", "));
+                    }
                 }
                 Ok(MatchResult {
                     status: MatchStatus::Updated,
-                    note_title: note.title.clone(),
-                    file_path: Some(file_path),
                     match_strategy: Some(strategy.to_string()),
                 })
             }
@@ -472,8 +408,6 @@ fn process_match(
                 eprintln!("⚠ [{}/{}] Failed to update YAML: {} - {}", idx, total, note.title, e);
                 Ok(MatchResult {
                     status: MatchStatus::Error(format!("Failed to update YAML: {}", e)),
-                    note_title: note.title.clone(),
-                    file_path: Some(file_path),
                     match_strategy: Some(strategy.to_string()),
                 })
             }
@@ -481,27 +415,6 @@ fn process_match(
     }
 }
 
-fn parse_evernote_timestamp(timestamp: &str) -> Result<i64> {
-    if timestamp.len() < 15 {
-        return Err(anyhow::anyhow!("Timestamp too short: {}", timestamp));
-    }
-
-    let year: i32 = timestamp[0..4].parse()?;
-    let month: u32 = timestamp[4..6].parse()?;
-    let day: u32 = timestamp[6..8].parse()?;
-    let hour: u32 = timestamp[9..11].parse()?;
-    let minute: u32 = timestamp[11..13].parse()?;
-    let second: u32 = timestamp[13..15].parse()?;
-
-    let naive = NaiveDateTime::parse_from_str(
-        &format!("{:04}-{:02}-{:02} {:02}:{:02}:{:02}", year, month, day, hour, minute, second),
-        "%Y-%m-%d %H:%M:%S"
-    )?;
-
-    let datetime: DateTime<Utc> = DateTime::from_naive_utc_and_offset(naive, Utc);
-    Ok(datetime.timestamp())
-}
-
 fn update_yaml_frontmatter(path: &Path, timestamp: i64) -> Result<()> {
     let datetime: DateTime<Utc> = DateTime::from_timestamp(timestamp, 0)
         .ok_or_else(|| anyhow::anyhow!("Invalid timestamp"))?;
@@ -568,7 +481,7 @@ fn print_summary(results: &[MatchResult], total_notes: usize, total_files: usize
     let fuzzy = results.iter().filter(|r| r.match_strategy.as_ref().map_or(false, |s| s.starts_with("fuzzy"))).count();
 
     println!("\n=== SUMMARY ===");
-    println!("Evernote notes: {}", total_notes);
+    println!("Source notes: {}", total_notes);
     println!("Target files: {}", total_files);
     println!();
     if dry_run {
@@ -0,0 +1,392 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use clap::ValueEnum;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// Where the original notes (and their creation dates) come from
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum SourceFormat {
+    /// Evernote `.enex` export file
+    Enex,
+    /// Bear markdown export folder ("Created:" footer or frontmatter date)
+    Bear,
+    /// Apple Notes HTML export folder (creation date in a `<meta>` tag)
+    AppleNotes,
+}
+
+impl SourceFormat {
+    pub fn label(self) -> &'static str {
+        match self {
+            SourceFormat::Enex => "Evernote",
+            SourceFormat::Bear => "Bear",
+            SourceFormat::AppleNotes => "Apple Notes",
+        }
+    }
+
+    /// Guess the format from the path: a `.enex` file is Evernote; a folder
+    /// is Bear if it holds more markdown files than HTML, Apple Notes otherwise.
+    pub fn detect(path: &Path) -> Result<Self> {
+        if path.is_file() {
+            return match path.extension().and_then(|e| e.to_str()) {
+                Some("enex") => Ok(SourceFormat::Enex),
+                _ => Err(anyhow::anyhow!(
+                    "Can't tell the export format of {}; pass --source-format",
+                    path.display()
+                )),
+            };
+        }
+
+        let (mut markdown, mut html) = (0, 0);
+        for entry in WalkDir::new(path).into_iter().filter_map(|e| e.ok()) {
+            match entry.path().extension().and_then(|e| e.to_str()) {
+                Some("md") | Some("markdown") => markdown += 1,
+                Some("html") | Some("htm") => html += 1,
+                _ => {}
+            }
+        }
+        match (markdown, html) {
+            (0, 0) => Err(anyhow::anyhow!(
+                "No markdown or HTML notes in {}; pass --source-format",
+                path.display()
+            )),
+            (m, h) if m >= h => Ok(SourceFormat::Bear),
+            _ => Ok(SourceFormat::AppleNotes),
+        }
+    }
+
+    pub fn load(self, path: &Path) -> Result<Vec<SourceNote>> {
+        match self {
+            SourceFormat::Enex => load_enex(path),
+            SourceFormat::Bear => load_bear(path),
+            SourceFormat::AppleNotes => load_apple_notes(path),
+        }
+    }
+}
+
+/// A note from the original app, to be matched against files in the target
+#[derive(Debug, Clone)]
+pub struct SourceNote {
+    pub title: String,
+    /// Creation date as written in the export, for messages
+    pub created: String,
+    /// Parsed creation date (UTC seconds), or why it couldn't be parsed
+    pub timestamp: Result<i64, String>,
+    /// Only Evernote exports carry tags separately from the note text
+    pub tags: Vec<String>,
+}
+
+impl SourceNote {
+    fn new(title: String, created: String, timestamp: Result<i64, String>) -> Self {
+        SourceNote { title, created, timestamp, tags: Vec::new() }
+    }
+}
+
+pub fn load_enex(path: &Path) -> Result<Vec<SourceNote>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read file: {}", path.display()))?;
+
+    let mut reader = Reader::from_str(&content);
+    reader.trim_text(true);
+
+    let mut notes = Vec::new();
+    let mut current_title = None;
+    let mut current_created = None;
+    let mut current_tags = Vec::new();
+    let mut inside_title = false;
+    let mut inside_created = false;
+    let mut inside_tag = false;
+
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) => {
+                match e.name().as_ref() {
+                    b"title" => inside_title = true,
+                    b"created" => inside_created = true,
+                    b"tag" => inside_tag = true,
+                    _ => {}
+                }
+            }
+            Ok(Event::Text(e)) => {
+                let text = e.unescape().unwrap().to_string();
+                if inside_title {
+                    current_title = Some(text);
+                    inside_title = false;
+                } else if inside_created {
+                    current_created = Some(text);
+                    inside_created = false;
+                } else if inside_tag {
+                    current_tags.push(text);
+                    inside_tag = false;
+                }
+            }
+            Ok(Event::End(ref e)) => {
+                if e.name().as_ref() == b"note" {
+                    let tags = std::mem::take(&mut current_tags);
+                    if let (Some(title), Some(created)) = (current_title.take(), current_created.take()) {
+                        let timestamp = parse_evernote_timestamp(&created).map_err(|e| e.to_string());
+                        notes.push(SourceNote { title, created, timestamp, tags });
+                    }
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(anyhow::anyhow!("Error parsing XML: {:?}", e)),
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(notes)
+}
+
+fn parse_evernote_timestamp(timestamp: &str) -> Result<i64> {
+    if timestamp.len() < 15 {
+        return Err(anyhow::anyhow!("Timestamp too short: {}", timestamp));
+    }
+
+    let year: i32 = timestamp[0..4].parse()?;
+    let month: u32 = timestamp[4..6].parse()?;
+    let day: u32 = timestamp[6..8].parse()?;
+    let hour: u32 = timestamp[9..11].parse()?;
+    let minute: u32 = timestamp[11..13].parse()?;
+    let second: u32 = timestamp[13..15].parse()?;
+
+    let naive = NaiveDateTime::parse_from_str(
+        &format!("{:04}-{:02}-{:02} {:02}:{:02}:{:02}", year, month, day, hour, minute, second),
+        "%Y-%m-%d %H:%M:%S"
+    )?;
+
+    let datetime: DateTime<Utc> = DateTime::from_naive_utc_and_offset(naive, Utc);
+    Ok(datetime.timestamp())
+}
+
+/// Bear markdown export: one `.md` per note. The title is the first `# `
+/// heading (Bear's first line), falling back to the file name. The date comes
+/// from a `Created:` footer line, or a `created` / `date created` frontmatter key.
+pub fn load_bear(dir: &Path) -> Result<Vec<SourceNote>> {
+    let mut notes = Vec::new();
+    for path in files_with_extension(dir, &["md", "markdown"]) {
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read file: {}", path.display()))?;
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("").to_string();
+
+        let (frontmatter, body) = split_frontmatter(&content);
+        let title = body
+            .lines()
+            .find_map(|line| line.strip_prefix("# "))
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .unwrap_or(stem);
+
+        let footer = body.lines().rev().find_map(|line| {
+            line.trim().strip_prefix("Created:").map(|d| d.trim().to_string())
+        });
+        let created = footer.or_else(|| {
+            frontmatter.lines().find_map(|line| {
+                let (key, value) = line.split_once(':')?;
+                matches!(key.trim(), "created" | "date created")
+                    .then(|| value.trim().trim_matches(['"', '\'']).to_string())
+            })
+        });
+        notes.push(dated(title, created));
+    }
+    Ok(notes)
+}
+
+/// Apple Notes HTML export: one `.html` per note, dated by a
+/// `<meta name="created" content="...">` tag (`creation-date`, `date-created`
+/// and `dcterms.created` are accepted too). The title is `<title>`, then the
+/// first `<h1>`, then the file name.
+pub fn load_apple_notes(dir: &Path) -> Result<Vec<SourceNote>> {
+    let meta_re = Regex::new(r"(?is)<meta\s[^>]*>").unwrap();
+    let attr_re = Regex::new(r#"(?i)\b(name|content)\s*=\s*"([^"]*)""#).unwrap();
+    let title_re = Regex::new(r"(?is)<title[^>]*>(.*?)</title>|<h1[^>]*>(.*?)</h1>").unwrap();
+    let tag_re = Regex::new(r"<[^>]+>").unwrap();
+
+    let mut notes = Vec::new();
+    for path in files_with_extension(dir, &["html", "htm"]) {
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read file: {}", path.display()))?;
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("").to_string();
+
+        let created = meta_re.find_iter(&content).find_map(|meta| {
+            let (mut name, mut value) = (None, None);
+            for cap in attr_re.captures_iter(meta.as_str()) {
+                match cap[1].to_lowercase().as_str() {
+                    "name" => name = Some(cap[2].to_lowercase()),
+                    _ => value = Some(unescape_html(&cap[2])),
+                }
+            }
+            matches!(
+                name.as_deref(),
+                Some("created" | "creation-date" | "date-created" | "dcterms.created")
+            )
+            .then_some(value)
+            .flatten()
+        });
+
+        let title = title_re
+            .captures_iter(&content)
+            .filter_map(|cap| cap.get(1).or(cap.get(2)))
+            .map(|m| unescape_html(tag_re.replace_all(m.as_str(), "").trim()))
+            .find(|t| !t.is_empty())
+            .unwrap_or(stem);
+
+        notes.push(dated(title, created));
+    }
+    Ok(notes)
+}
+
+fn dated(title: String, created: Option<String>) -> SourceNote {
+    match created {
+        Some(created) => {
+            let timestamp = parse_date(&created)
+                .map(|dt| dt.timestamp())
+                .ok_or_else(|| format!("Unrecognised date: {}", created));
+            SourceNote::new(title, created, timestamp)
+        }
+        None => SourceNote::new(title, String::new(), Err("No creation date".to_string())),
+    }
+}
+
+/// Dates as Bear and Apple Notes exporters write them. Times without an
+/// offset are taken as UTC, like Evernote's.
+fn parse_date(s: &str) -> Option<DateTime<Utc>> {
+    let s = s.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    for format in ["%Y-%m-%d %H:%M:%S %z", "%Y-%m-%d %H:%M %z"] {
+        if let Ok(dt) = DateTime::parse_from_str(s, format) {
+            return Some(dt.with_timezone(&Utc));
+        }
+    }
+    let naive_formats = [
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%d %B %Y at %H:%M",   // 14 March 2019 at 09:21
+        "%B %d, %Y at %I:%M %p", // March 14, 2019 at 9:21 AM
+        "%A, %B %d, %Y at %I:%M:%S %p", // Apple Notes' own long form
+    ];
+    for format in naive_formats {
+        if let Ok(naive) = NaiveDateTime::parse_from_str(s, format) {
+            return Some(naive.and_utc());
+        }
+    }
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|naive| naive.and_utc())
+}
+
+fn split_frontmatter(content: &str) -> (&str, &str) {
+    if let Some(rest) = content.strip_prefix("---\n") {
+        if let Some(end) = rest.find("\n---\n") {
+            return (&rest[..end], &rest[end + 5..]);
+        }
+    }
+    ("", content)
+}
+
+fn unescape_html(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Files under `dir` with one of `extensions`, in path order
+fn files_with_extension(dir: &Path, extensions: &[&str]) -> Vec<std::path::PathBuf> {
+    let mut paths: Vec<_> = WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| e.into_path())
+        .filter(|p| {
+            p.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| extensions.contains(&e))
+        })
+        .collect();
+    paths.sort();
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name)
+    }
+
+    fn date(note: &SourceNote) -> String {
+        let ts = note.timestamp.clone().unwrap();
+        DateTime::from_timestamp(ts, 0).unwrap().format("%Y-%m-%d %H:%M").to_string()
+    }
+
+    #[test]
+    fn enex_titles_dates_and_tags() {
+        let notes = load_enex(&fixture("export.enex")).unwrap();
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes[0].title, "Stoic Reading List");
+        assert_eq!(date(&notes[0]), "2014-03-02 10:15");
+        assert_eq!(notes[0].tags, vec!["reading", "stoicism"]);
+        assert_eq!(notes[1].title, "Broken date");
+        assert!(notes[1].timestamp.is_err());
+    }
+
+    #[test]
+    fn bear_footer_frontmatter_and_fallback_title() {
+        let notes = load_bear(&fixture("bear")).unwrap();
+        let summary: Vec<(&str, Result<String, String>)> = notes
+            .iter()
+            .map(|n| (n.title.as_str(), n.timestamp.clone().map(|_| date(n))))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Garden plans", Ok("2018-05-20 07:45".to_string())),
+                ("Marcus on anger", Ok("2019-03-14 09:21".to_string())),
+                ("untitled-scrap", Err("No creation date".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn apple_notes_meta_tags() {
+        let notes = load_apple_notes(&fixture("apple-notes")).unwrap();
+        let titles: Vec<&str> = notes.iter().map(|n| n.title.as_str()).collect();
+        assert_eq!(titles, vec!["Q&A with Dr. Hays", "Shopping list"]);
+        // Offset converted to UTC
+        assert_eq!(date(&notes[0]), "2017-11-02 17:05");
+        assert_eq!(date(&notes[1]), "2016-01-09 08:00");
+    }
+
+    #[test]
+    fn detects_format_from_path() {
+        assert_eq!(SourceFormat::detect(&fixture("export.enex")).unwrap(), SourceFormat::Enex);
+        assert_eq!(SourceFormat::detect(&fixture("bear")).unwrap(), SourceFormat::Bear);
+        assert_eq!(SourceFormat::detect(&fixture("apple-notes")).unwrap(), SourceFormat::AppleNotes);
+    }
+
+    #[test]
+    fn exporter_date_styles() {
+        let expected = "2019-03-14 09:21";
+        for s in ["2019-03-14 09:21", "2019-03-14T09:21:00Z", "14 March 2019 at 09:21", "March 14, 2019 at 9:21 AM"] {
+            let parsed = parse_date(s).unwrap_or_else(|| panic!("unparsed: {}", s));
+            assert_eq!(parsed.format("%Y-%m-%d %H:%M").to_string(), expected);
+        }
+        assert!(parse_date("sometime in spring").is_none());
+    }
+}
@@ -0,0 +1,204 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+/// Turn an Evernote tag into something Obsidian accepts as a tag:
+/// no leading `#`, no internal whitespace.
+pub fn normalize_tag(tag: &str) -> Option<String> {
+    let tag = tag.trim().trim_start_matches('#');
+    let tag = tag.split_whitespace().collect::<Vec<_>>().join("-");
+    if tag.is_empty() {
+        None
+    } else {
+        Some(tag)
+    }
+}
+
+/// Merge `tags` into the file's YAML frontmatter. Returns the tags that were
+/// actually added (empty if the file already had them all).
+pub fn merge_tags_file(path: &Path, tags: &[String]) -> Result<Vec<String>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read file: {}", path.display()))?;
+
+    let (new_content, added) = merge_tags(&content, tags)?;
+    if !added.is_empty() {
+        fs::write(path, new_content)?;
+    }
+    Ok(added)
+}
+
+/// Merge `tags` into the `tags:` key of the frontmatter in `content`,
+/// skipping any already present (case-insensitive). Flow lists
+/// (`tags: [a, b]`) and block lists (`- a` lines) keep their style; a bare
+/// scalar is converted to a flow list; a missing key is added as a flow list.
+pub fn merge_tags(content: &str, tags: &[String]) -> Result<(String, Vec<String>)> {
+    if !content.starts_with("---\n") {
+        let added = dedupe(&[], tags);
+        if added.is_empty() {
+            return Ok((content.to_string(), added));
+        }
+        let new_content = format!("---\ntags: [{}]\n---\n{}", added.join(", "), content);
+        return Ok((new_content, added));
+    }
+
+    // `rest` starts at the closing `---`; an empty block closes straight away
+    let body = &content[4..];
+    let (frontmatter, rest) = if body.starts_with("---\n") || body == "---" {
+        ("", body)
+    } else {
+        let end_pos = body
+            .find("\n---\n")
+            .ok_or_else(|| anyhow::anyhow!("Malformed YAML frontmatter"))?;
+        (&body[..end_pos], &body[end_pos + 1..])
+    };
+
+    let lines: Vec<&str> = frontmatter.lines().collect();
+    let tags_idx = lines.iter().position(|l| l.starts_with("tags:"));
+
+    let mut new_lines: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+    let added;
+
+    match tags_idx {
+        None => {
+            added = dedupe(&[], tags);
+            if !added.is_empty() {
+                new_lines.push(format!("tags: [{}]", added.join(", ")));
+            }
+        }
+        Some(idx) => {
+            let value = lines[idx]["tags:".len()..].trim();
+            if value.is_empty() {
+                // Block list: collect the indented `- item` lines that follow
+                let mut existing = Vec::new();
+                let mut last = idx;
+                let mut indent = "  ".to_string();
+                for (i, line) in lines.iter().enumerate().skip(idx + 1) {
+                    let trimmed = line.trim_start();
+                    if !line.starts_with([' ', '\t', '-']) || !trimmed.starts_with('-') {
+                        break;
+                    }
+                    if existing.is_empty() {
+                        indent = line[..line.len() - trimmed.len()].to_string();
+                    }
+                    existing.push(unquote(trimmed.trim_start_matches('-').trim()));
+                    last = i;
+                }
+                added = dedupe(&existing, tags);
+                for (offset, tag) in added.iter().enumerate() {
+                    new_lines.insert(last + 1 + offset, format!("{}- {}", indent, tag));
+                }
+            } else {
+                let existing: Vec<String> = match value.strip_prefix('[') {
+                    Some(inner) => inner
+                        .trim_end_matches(']')
+                        .split(',')
+                        .map(|t| unquote(t.trim()))
+                        .filter(|t| !t.is_empty())
+                        .collect(),
+                    None => value
+                        .split([',', ' '])
+                        .map(|t| unquote(t.trim()))
+                        .filter(|t| !t.is_empty())
+                        .collect(),
+                };
+                added = dedupe(&existing, tags);
+                if !added.is_empty() {
+                    let mut all = existing;
+                    all.extend(added.iter().cloned());
+                    new_lines[idx] = format!("tags: [{}]", all.join(", "));
+                }
+            }
+        }
+    }
+
+    if added.is_empty() {
+        return Ok((content.to_string(), added));
+    }
+
+    let new_content = format!("---\n{}\n{}", new_lines.join("\n"), rest);
+    Ok((new_content, added))
+}
+
+fn unquote(s: &str) -> String {
+    s.trim_matches(|c| c == '"' || c == '\'').to_string()
+}
+
+fn dedupe(existing: &[String], tags: &[String]) -> Vec<String> {
+    let mut seen: Vec<String> = existing.iter().map(|t| t.to_lowercase()).collect();
+    let mut added = Vec::new();
+    for tag in tags.iter().filter_map(|t| normalize_tag(t)) {
+        let key = tag.to_lowercase();
+        if !seen.contains(&key) {
+            seen.push(key);
+            added.push(tag);
+        }
+    }
+    added
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(list: &[&str]) -> Vec<String> {
+        list.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn adds_flow_list_when_key_missing() {
+        let content = "---\ndate created: 2019-01-01 10:00\n---\nBody\n";
+        let (out, added) = merge_tags(content, &tags(&["reading", "#to do"])).unwrap();
+        assert_eq!(added, tags(&["reading", "to-do"]));
+        assert_eq!(
+            out,
+            "---\ndate created: 2019-01-01 10:00\ntags: [reading, to-do]\n---\nBody\n"
+        );
+    }
+
+    #[test]
+    fn extends_flow_list_without_duplicates() {
+        let content = "---\ntags: [Reading, \"philosophy\"]\n---\nBody\n";
+        let (out, added) = merge_tags(content, &tags(&["reading", "ethics", "Ethics"])).unwrap();
+        assert_eq!(added, tags(&["ethics"]));
+        assert_eq!(out, "---\ntags: [Reading, philosophy, ethics]\n---\nBody\n");
+    }
+
+    #[test]
+    fn extends_block_list_in_place() {
+        let content = "---\ntags:\n  - reading\n  - ethics\ndate created: 2019-01-01 10:00\n---\nBody\n";
+        let (out, added) = merge_tags(content, &tags(&["Ethics", "stoicism"])).unwrap();
+        assert_eq!(added, tags(&["stoicism"]));
+        assert_eq!(
+            out,
+            "---\ntags:\n  - reading\n  - ethics\n  - stoicism\ndate created: 2019-01-01 10:00\n---\nBody\n"
+        );
+    }
+
+    #[test]
+    fn converts_scalar_to_flow_list() {
+        let content = "---\ntags: reading\n---\nBody\n";
+        let (out, _) = merge_tags(content, &tags(&["ethics"])).unwrap();
+        assert_eq!(out, "---\ntags: [reading, ethics]\n---\nBody\n");
+    }
+
+    #[test]
+    fn unchanged_when_all_tags_present() {
+        let content = "---\ntags: [reading]\n---\nBody\n";
+        let (out, added) = merge_tags(content, &tags(&["Reading"])).unwrap();
+        assert!(added.is_empty());
+        assert_eq!(out, content);
+    }
+
+    #[test]
+    fn fills_empty_frontmatter() {
+        let (out, added) = merge_tags("---\n---\nBody\n", &tags(&["reading"])).unwrap();
+        assert_eq!(added, tags(&["reading"]));
+        assert_eq!(out, "---\ntags: [reading]\n---\nBody\n");
+    }
+
+    #[test]
+    fn creates_frontmatter_when_missing() {
+        let (out, _) = merge_tags("Body\n", &tags(&["reading"])).unwrap();
+        assert_eq!(out, "---\ntags: [reading]\n---\nBody\n");
+    }
+}
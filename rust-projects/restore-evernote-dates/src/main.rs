@@ -4,11 +4,13 @@ use clap::Parser;
 use indicatif::{ProgressBar, ProgressStyle};
 use quick_xml::events::Event;
 use quick_xml::Reader;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
+mod report;
+
 #[derive(Parser, Debug)]
 #[command(name = "restore-evernote-dates")]
 #[command(about = "Restore file creation dates from Evernote export")]
@@ -28,6 +30,15 @@ struct Args {
     /// Show detailed progress
     #[arg(long)]
     verbose: bool,
+
+    /// Write no-match.txt, errors.csv and matched.csv into this directory
+    #[arg(long, value_name = "DIR")]
+    report_dir: Option<PathBuf>,
+
+    /// Only process notes whose titles are listed, one per line (e.g. a
+    /// previous run's no-match.txt)
+    #[arg(long, value_name = "FILE")]
+    titles_from: Option<PathBuf>,
 }
 
 #[derive(Debug)]
@@ -40,6 +51,60 @@ struct EvernoteNote {
 struct MatchResult {
     status: MatchStatus,
     title: String,
+    /// The matched file, when there was one
+    file: Option<PathBuf>,
+    /// How the file was matched, when there was one
+    strategy: Option<MatchStrategy>,
+    /// Frontmatter dates before the update
+    old_created: Option<String>,
+    old_modified: Option<String>,
+    /// The Evernote date written (or that would be)
+    new_date: Option<String>,
+}
+
+impl MatchResult {
+    fn new(title: &str, status: MatchStatus) -> Self {
+        Self {
+            status,
+            title: title.to_string(),
+            file: None,
+            strategy: None,
+            old_created: None,
+            old_modified: None,
+            new_date: None,
+        }
+    }
+}
+
+/// How a note was matched to a file. Both are exact title matches; they
+/// differ in whether the title was ambiguous.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MatchStrategy {
+    /// The only file named after the note
+    Exact,
+    /// One of several files named after the note, in different folders,
+    /// each of which gets the date
+    SharedTitle,
+}
+
+impl MatchStrategy {
+    const ALL: [MatchStrategy; 2] = [MatchStrategy::Exact, MatchStrategy::SharedTitle];
+
+    /// Name in `matched.csv`
+    fn label(self) -> &'static str {
+        match self {
+            MatchStrategy::Exact => "exact",
+            MatchStrategy::SharedTitle => "shared-title",
+        }
+    }
+
+    /// Line in the summary breakdown
+    fn description(self) -> &'static str {
+        match self {
+            MatchStrategy::Exact => "Exact matches",
+            MatchStrategy::SharedTitle => "Same-named files in several folders",
+        }
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -58,9 +123,15 @@ fn main() -> Result<()> {
 
     // Parse Evernote export
     println!("Parsing Evernote notes...");
-    let notes = parse_evernote_export(&args.enex_file)?;
+    let mut notes = parse_evernote_export(&args.enex_file)?;
     println!("Found {} notes in Evernote export\n", notes.len());
 
+    if let Some(path) = &args.titles_from {
+        let titles = report::load_titles(path)?;
+        notes = filter_notes(notes, &titles);
+        println!("Restricted to {} notes listed in {}\n", notes.len(), path.display());
+    }
+
     // Scan target directory for markdown files
     println!("Scanning target directory for markdown files...");
     let target_files = find_markdown_files(&args.target_dir)?;
@@ -83,9 +154,25 @@ fn main() -> Result<()> {
     // Print summary
     print_summary(&results, notes.len(), target_files.len(), args.dry_run);
 
+    if let Some(dir) = &args.report_dir {
+        report::write_reports(dir, &results)?;
+        println!(
+            "\n📄 Reports written to {}: {}, {}, {}",
+            dir.display(),
+            report::NO_MATCH_FILE,
+            report::ERRORS_FILE,
+            report::MATCHED_FILE
+        );
+    }
+
     Ok(())
 }
 
+/// Only the notes whose titles are in `titles`, in export order
+fn filter_notes(notes: Vec<EvernoteNote>, titles: &HashSet<String>) -> Vec<EvernoteNote> {
+    notes.into_iter().filter(|note| titles.contains(&note.title)).collect()
+}
+
 fn parse_evernote_export(path: &Path) -> Result<Vec<EvernoteNote>> {
     let content = fs::read_to_string(path)
         .with_context(|| format!("Failed to read file: {}", path.display()))?;
@@ -120,11 +207,9 @@ fn parse_evernote_export(path: &Path) -> Result<Vec<EvernoteNote>> {
                     inside_created = false;
                 }
             }
-            Ok(Event::End(ref e)) => {
-                if e.name().as_ref() == b"note" {
-                    if let (Some(title), Some(created)) = (current_title.take(), current_created.take()) {
-                        notes.push(EvernoteNote { title, created });
-                    }
+            Ok(Event::End(ref e)) if e.name().as_ref() == b"note" => {
+                if let (Some(title), Some(created)) = (current_title.take(), current_created.take()) {
+                    notes.push(EvernoteNote { title, created });
                 }
             }
             Ok(Event::Eof) => break,
@@ -159,7 +244,7 @@ fn build_file_map(files: &[PathBuf]) -> HashMap<String, Vec<PathBuf>> {
             if let Some(name) = stem.to_str() {
                 // Store all files with the same name (handles duplicates)
                 map.entry(name.to_string())
-                    .or_insert_with(Vec::new)
+                    .or_default()
                     .push(file.clone());
             }
         }
@@ -189,8 +274,7 @@ fn match_and_process_notes(
     let mut results = Vec::new();
 
     for (idx, note) in notes.iter().enumerate() {
-        let result = process_note(note, file_map, dry_run, verbose, idx + 1, notes.len())?;
-        results.push(result);
+        results.extend(process_note(note, file_map, dry_run, verbose, idx + 1, notes.len())?);
 
         if let Some(ref pb) = progress {
             pb.inc(1);
@@ -204,25 +288,24 @@ fn match_and_process_notes(
     Ok(results)
 }
 
+/// Apply the note's date to every file named after it (there may be
+/// several in different folders), with one result per file
 fn process_note(
     note: &EvernoteNote,
-    file_map: &HashMap<String, PathBuf>,
+    file_map: &HashMap<String, Vec<PathBuf>>,
     dry_run: bool,
     verbose: bool,
     idx: usize,
     total: usize,
-) -> Result<MatchResult> {
+) -> Result<Vec<MatchResult>> {
     // Try to find matching file
-    let file_path = match file_map.get(&note.title) {
-        Some(path) => path,
+    let file_paths = match file_map.get(&note.title) {
+        Some(paths) => paths,
         None => {
             if verbose {
                 println!("⊘ [{}/{}] No match: {}", idx, total, note.title);
             }
-            return Ok(MatchResult {
-                status: MatchStatus::NoMatch,
-                title: note.title.clone(),
-            });
+            return Ok(vec![MatchResult::new(&note.title, MatchStatus::NoMatch)]);
         }
     };
 
@@ -233,23 +316,54 @@ fn process_note(
             if verbose {
                 println!("⚠ [{}/{}] Failed to parse date: {} - {}", idx, total, note.title, e);
             }
-            return Ok(MatchResult {
-                status: MatchStatus::Error(format!("Failed to parse date: {}", e)),
-                title: note.title.clone(),
-            });
+            return Ok(vec![MatchResult::new(
+                &note.title,
+                MatchStatus::Error(format!("Failed to parse date: {}", e)),
+            )]);
         }
     };
 
+    let strategy = if file_paths.len() == 1 {
+        MatchStrategy::Exact
+    } else {
+        MatchStrategy::SharedTitle
+    };
+    Ok(file_paths
+        .iter()
+        .map(|file_path| MatchResult {
+            strategy: Some(strategy),
+            ..process_file(note, file_path, timestamp, dry_run, verbose, idx, total)
+        })
+        .collect())
+}
+
+fn process_file(
+    note: &EvernoteNote,
+    file_path: &Path,
+    timestamp: i64,
+    dry_run: bool,
+    verbose: bool,
+    idx: usize,
+    total: usize,
+) -> MatchResult {
+    let (old_created, old_modified) = existing_dates(file_path);
+    let result = |status| MatchResult {
+        status,
+        title: note.title.clone(),
+        file: Some(file_path.to_path_buf()),
+        strategy: None,
+        old_created: old_created.clone(),
+        old_modified: old_modified.clone(),
+        new_date: yaml_date(timestamp).ok(),
+    };
+
     if dry_run {
         if verbose {
             println!("🔍 [{}/{}] Would update: {}", idx, total, note.title);
             println!("   File: {}", file_path.display());
             println!("   Date: {}", note.created);
         }
-        Ok(MatchResult {
-            status: MatchStatus::WouldUpdate,
-            title: note.title.clone(),
-        })
+        result(MatchStatus::WouldUpdate)
     } else {
         // Update YAML frontmatter first
         match update_yaml_frontmatter(file_path, timestamp) {
@@ -260,34 +374,42 @@ fn process_note(
                         if verbose {
                             println!("✓ [{}/{}] Updated: {}", idx, total, note.title);
                             println!("   Date: {}", note.created);
-                        } else if idx % 100 == 0 {
+                        } else if idx.is_multiple_of(100) {
                             eprintln!("Progress: {}/{} files processed...", idx, total);
                         }
-                        Ok(MatchResult {
-                            status: MatchStatus::Updated,
-                            title: note.title.clone(),
-                        })
+                        result(MatchStatus::Updated)
                     }
                     Err(e) => {
                         eprintln!("⚠ [{}/{}] Failed to update mtime: {} - {}", idx, total, note.title, e);
-                        Ok(MatchResult {
-                            status: MatchStatus::Error(format!("Failed to update mtime: {}", e)),
-                            title: note.title.clone(),
-                        })
+                        result(MatchStatus::Error(format!("Failed to update mtime: {}", e)))
                     }
                 }
             }
             Err(e) => {
                 eprintln!("⚠ [{}/{}] Failed to update YAML: {} - {}", idx, total, note.title, e);
-                Ok(MatchResult {
-                    status: MatchStatus::Error(format!("Failed to update YAML: {}", e)),
-                    title: note.title.clone(),
-                })
+                result(MatchStatus::Error(format!("Failed to update YAML: {}", e)))
             }
         }
     }
 }
 
+/// `date created` and `date modified` from a file's frontmatter, if present
+fn existing_dates(path: &Path) -> (Option<String>, Option<String>) {
+    let Ok(content) = fs::read_to_string(path) else {
+        return (None, None);
+    };
+    let Some(frontmatter) = content
+        .strip_prefix("---\n")
+        .and_then(|rest| rest.find("\n---\n").map(|end| &rest[..end]))
+    else {
+        return (None, None);
+    };
+    (
+        extract_date_field(frontmatter, "date created"),
+        extract_date_field(frontmatter, "date modified"),
+    )
+}
+
 fn parse_evernote_timestamp(timestamp: &str) -> Result<i64> {
     // Format: 20151001T080944Z -> Unix timestamp
     // Extract: YYYYMMDD HHMMSS
@@ -319,11 +441,15 @@ fn set_file_mtime(path: &Path, timestamp: i64) -> Result<()> {
     Ok(())
 }
 
-fn update_yaml_frontmatter(path: &Path, timestamp: i64) -> Result<()> {
-    // Convert timestamp to YAML date format: "YYYY-MM-DD HH:MM"
+/// Timestamp in the frontmatter's date format: "YYYY-MM-DD HH:MM"
+fn yaml_date(timestamp: i64) -> Result<String> {
     let datetime: DateTime<Utc> = DateTime::from_timestamp(timestamp, 0)
         .ok_or_else(|| anyhow::anyhow!("Invalid timestamp"))?;
-    let date_str = datetime.format("%Y-%m-%d %H:%M").to_string();
+    Ok(datetime.format("%Y-%m-%d %H:%M").to_string())
+}
+
+fn update_yaml_frontmatter(path: &Path, timestamp: i64) -> Result<()> {
+    let date_str = yaml_date(timestamp)?;
 
     // Read file content
     let content = fs::read_to_string(path)
@@ -354,7 +480,7 @@ fn update_yaml_frontmatter(path: &Path, timestamp: i64) -> Result<()> {
     let mut new_frontmatter = frontmatter.to_string();
 
     // Update date created (only if it doesn't exist or is newer than Evernote date)
-    if let Some(existing_date) = extract_date_field(&new_frontmatter, "date created") {
+    if extract_date_field(&new_frontmatter, "date created").is_some() {
         // Only update if existing date is clearly wrong (e.g., 2025 when Evernote says 2015)
         // We'll update any existing date with the Evernote date since that's authoritative
         new_frontmatter = replace_date_field(&new_frontmatter, "date created", &date_str);
@@ -405,6 +531,22 @@ fn replace_date_field(frontmatter: &str, field: &str, new_date: &str) -> String
         .join("\n")
 }
 
+/// Updated (or would-be updated) files per match strategy, every strategy
+/// listed even when none matched that way
+fn strategy_counts(results: &[MatchResult]) -> Vec<(MatchStrategy, usize)> {
+    MatchStrategy::ALL
+        .iter()
+        .map(|&strategy| {
+            let count = results
+                .iter()
+                .filter(|r| matches!(r.status, MatchStatus::Updated | MatchStatus::WouldUpdate))
+                .filter(|r| r.strategy == Some(strategy))
+                .count();
+            (strategy, count)
+        })
+        .collect()
+}
+
 fn print_summary(results: &[MatchResult], total_notes: usize, total_files: usize, dry_run: bool) {
     println!("\n=== SUMMARY ===");
     println!("Evernote notes: {}", total_notes);
@@ -427,11 +569,19 @@ fn print_summary(results: &[MatchResult], total_notes: usize, total_files: usize
     } else {
         println!("\nFiles updated: {}", matched);
     }
+    for (strategy, count) in strategy_counts(results) {
+        println!("  - {}: {}", strategy.description(), count);
+    }
     println!("Files with no match: {}", no_match);
     println!("Errors: {}", errors);
 
-    if total_notes > 0 {
-        let match_rate = (matched * 100) / total_notes;
+    // A title matching several files counts once
+    let matched_titles: HashSet<&str> = results
+        .iter()
+        .filter(|r| matches!(r.status, MatchStatus::Updated | MatchStatus::WouldUpdate))
+        .map(|r| r.title.as_str())
+        .collect();
+    if let Some(match_rate) = (matched_titles.len() * 100).checked_div(total_notes) {
         println!("\nMatch rate: {}%", match_rate);
     }
 
@@ -448,3 +598,40 @@ fn print_summary(results: &[MatchResult], total_notes: usize, total_files: usize
         println!("\n💡 Run without --dry-run to apply changes");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(title: &str) -> EvernoteNote {
+        EvernoteNote { title: title.to_string(), created: "20151001T080944Z".to_string() }
+    }
+
+    #[test]
+    fn strategies_distinguish_shared_titles() {
+        let paths = |names: &[&str]| names.iter().map(PathBuf::from).collect::<Vec<_>>();
+        let file_map = HashMap::from([
+            ("Seneca".to_string(), paths(&["/forge/Seneca.md"])),
+            ("Inbox".to_string(), paths(&["/forge/a/Inbox.md", "/forge/b/Inbox.md"])),
+        ]);
+
+        let mut results = Vec::new();
+        for title in ["Seneca", "Inbox", "Missing"] {
+            results.extend(process_note(&note(title), &file_map, true, false, 1, 3).unwrap());
+        }
+        results.push(MatchResult {
+            strategy: Some(MatchStrategy::Exact),
+            ..MatchResult::new("Unwritable", MatchStatus::Error("Failed to update YAML".to_string()))
+        });
+
+        let strategies: Vec<Option<MatchStrategy>> = results.iter().map(|r| r.strategy).collect();
+        assert_eq!(&strategies[..4], [
+            Some(MatchStrategy::Exact),
+            Some(MatchStrategy::SharedTitle),
+            Some(MatchStrategy::SharedTitle),
+            None,
+        ]);
+        // Only files that were (or would be) updated count
+        assert_eq!(strategy_counts(&results), vec![(MatchStrategy::Exact, 1), (MatchStrategy::SharedTitle, 2)]);
+    }
+}
@@ -0,0 +1,147 @@
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use crate::{MatchResult, MatchStatus, MatchStrategy};
+
+pub const NO_MATCH_FILE: &str = "no-match.txt";
+pub const ERRORS_FILE: &str = "errors.csv";
+pub const MATCHED_FILE: &str = "matched.csv";
+
+/// Titles to restrict a run to, one per line as in `no-match.txt`. Blank
+/// lines are skipped; other whitespace is kept, since it is part of the title.
+pub fn load_titles(path: &Path) -> Result<HashSet<String>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read titles file: {}", path.display()))?;
+    Ok(content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Write `no-match.txt`, `errors.csv` and `matched.csv` into `dir`,
+/// creating it if needed. Each file is written even when empty, so a stale
+/// one from an earlier run never survives.
+pub fn write_reports(dir: &Path, results: &[MatchResult]) -> Result<()> {
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create report dir: {}", dir.display()))?;
+
+    let no_match: String = results
+        .iter()
+        .filter(|r| r.status == MatchStatus::NoMatch)
+        .map(|r| format!("{}\n", r.title))
+        .collect();
+    let path = dir.join(NO_MATCH_FILE);
+    fs::write(&path, no_match).with_context(|| format!("Failed to write {}", path.display()))?;
+
+    let mut errors = csv_writer(&dir.join(ERRORS_FILE))?;
+    errors.write_record(["title", "error"])?;
+    for result in results {
+        if let MatchStatus::Error(message) = &result.status {
+            errors.write_record([result.title.as_str(), message.as_str()])?;
+        }
+    }
+    errors.flush()?;
+
+    let mut matched = csv_writer(&dir.join(MATCHED_FILE))?;
+    matched.write_record(["title", "file", "strategy", "old_date_created", "old_date_modified", "new_date"])?;
+    for result in results {
+        if !matches!(result.status, MatchStatus::Updated | MatchStatus::WouldUpdate) {
+            continue;
+        }
+        let file = result.file.as_deref().map(|p| p.display().to_string()).unwrap_or_default();
+        matched.write_record([
+            result.title.as_str(),
+            file.as_str(),
+            result.strategy.map_or("", MatchStrategy::label),
+            result.old_created.as_deref().unwrap_or(""),
+            result.old_modified.as_deref().unwrap_or(""),
+            result.new_date.as_deref().unwrap_or(""),
+        ])?;
+    }
+    matched.flush()?;
+
+    Ok(())
+}
+
+fn csv_writer(path: &Path) -> Result<csv::Writer<fs::File>> {
+    csv::Writer::from_path(path).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn result(title: &str, status: MatchStatus) -> MatchResult {
+        MatchResult {
+            status,
+            title: title.to_string(),
+            file: None,
+            strategy: None,
+            old_created: None,
+            old_modified: None,
+            new_date: None,
+        }
+    }
+
+    #[test]
+    fn reports_split_results_by_outcome() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut updated = result("Stoicism, and anger", MatchStatus::Updated);
+        updated.file = Some(PathBuf::from("/forge/Stoicism, and anger.md"));
+        updated.strategy = Some(MatchStrategy::Exact);
+        updated.old_created = Some("2025-01-02 10:00".to_string());
+        updated.new_date = Some("2015-10-01 08:09".to_string());
+        let mut shared = result("Inbox", MatchStatus::WouldUpdate);
+        shared.file = Some(PathBuf::from("/forge/Work/Inbox.md"));
+        shared.strategy = Some(MatchStrategy::SharedTitle);
+        let results = vec![
+            result("Lost note", MatchStatus::NoMatch),
+            updated,
+            shared,
+            result("Bad \"date\"", MatchStatus::Error("Failed to parse date: too short".to_string())),
+            result("Also lost", MatchStatus::NoMatch),
+        ];
+
+        write_reports(&dir.path().join("reports"), &results).unwrap();
+        let read = |name: &str| fs::read_to_string(dir.path().join("reports").join(name)).unwrap();
+
+        assert_eq!(read(NO_MATCH_FILE), "Lost note\nAlso lost\n");
+        assert_eq!(
+            read(ERRORS_FILE),
+            "title,error\n\"Bad \"\"date\"\"\",Failed to parse date: too short\n"
+        );
+        assert_eq!(
+            read(MATCHED_FILE),
+            "title,file,strategy,old_date_created,old_date_modified,new_date\n\
+             \"Stoicism, and anger\",\"/forge/Stoicism, and anger.md\",exact,2025-01-02 10:00,,2015-10-01 08:09\n\
+             Inbox,/forge/Work/Inbox.md,shared-title,,,\n"
+        );
+    }
+
+    #[test]
+    fn empty_reports_replace_old_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(NO_MATCH_FILE), "Stale\n").unwrap();
+
+        write_reports(dir.path(), &[]).unwrap();
+        assert_eq!(fs::read_to_string(dir.path().join(NO_MATCH_FILE)).unwrap(), "");
+        assert_eq!(fs::read_to_string(dir.path().join(ERRORS_FILE)).unwrap(), "title,error\n");
+    }
+
+    #[test]
+    fn titles_file_round_trips_no_match_list() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(NO_MATCH_FILE);
+        fs::write(&path, "Lost note\n\n  Indented title\nTrailing space \n").unwrap();
+
+        let titles = load_titles(&path).unwrap();
+        assert_eq!(titles.len(), 3);
+        assert!(titles.contains("Lost note"));
+        assert!(titles.contains("  Indented title"));
+        assert!(titles.contains("Trailing space "));
+        assert!(load_titles(&dir.path().join("missing.txt")).is_err());
+    }
+}
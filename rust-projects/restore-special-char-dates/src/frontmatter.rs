@@ -0,0 +1,182 @@
+//! YAML frontmatter date handling.
+//!
+//! Besides `date created` / `date modified`, a successful restore records a
+//! provenance marker such as `date restored: evernote 2026-02-10`. Files
+//! carrying any `date restored` marker are skipped by default, so a second
+//! run (or a later, fuzzier restore pass) can't overwrite a good match.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, Utc};
+use std::fs;
+use std::path::Path;
+
+pub const PROVENANCE_FIELD: &str = "date restored";
+
+/// Marker value for a restore from `source` performed today
+pub fn provenance_marker(source: &str) -> String {
+    format!("{} {}", source, Local::now().format("%Y-%m-%d"))
+}
+
+/// The `date restored` marker of a file, if it has one
+pub fn read_provenance(path: &Path) -> Result<Option<String>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read file: {}", path.display()))?;
+    Ok(split(&content).and_then(|(frontmatter, _)| extract_field(frontmatter, PROVENANCE_FIELD)))
+}
+
+/// Whether a file should be left alone because an earlier restore marked it
+pub fn should_skip(path: &Path, force: bool) -> Result<bool> {
+    if force {
+        return Ok(false);
+    }
+    Ok(read_provenance(path)?.is_some())
+}
+
+/// Set `date created` and `date modified` to `timestamp`, and record
+/// `provenance` (if given) as the `date restored` marker.
+pub fn update_dates(path: &Path, timestamp: i64, provenance: Option<&str>) -> Result<()> {
+    // Convert timestamp to YAML date format: "YYYY-MM-DD HH:MM"
+    let datetime: DateTime<Utc> = DateTime::from_timestamp(timestamp, 0)
+        .ok_or_else(|| anyhow::anyhow!("Invalid timestamp"))?;
+    let date_str = datetime.format("%Y-%m-%d %H:%M").to_string();
+
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read file: {}", path.display()))?;
+
+    let new_content = if !content.starts_with("---\n") {
+        // No frontmatter - add it at the beginning
+        let mut frontmatter = format!("date created: {}\ndate modified: {}\n", date_str, date_str);
+        if let Some(marker) = provenance {
+            frontmatter.push_str(&format!("{}: {}\n", PROVENANCE_FIELD, marker));
+        }
+        format!("---\n{}---\n{}", frontmatter, content)
+    } else {
+        let (frontmatter, rest) =
+            split(&content).ok_or_else(|| anyhow::anyhow!("Malformed YAML frontmatter"))?;
+
+        let mut new_frontmatter = frontmatter.to_string();
+
+        // Update date created
+        if extract_field(&new_frontmatter, "date created").is_some() {
+            new_frontmatter = replace_field(&new_frontmatter, "date created", &date_str);
+        } else {
+            new_frontmatter = format!("date created: {}\n{}", date_str, new_frontmatter);
+        }
+
+        // Update date modified
+        if extract_field(&new_frontmatter, "date modified").is_some() {
+            new_frontmatter = replace_field(&new_frontmatter, "date modified", &date_str);
+        } else if new_frontmatter.starts_with("date created:") {
+            let first_newline = new_frontmatter.find('\n').unwrap_or(new_frontmatter.len());
+            new_frontmatter.insert_str(first_newline + 1, &format!("date modified: {}\n", date_str));
+        } else {
+            new_frontmatter = format!("date modified: {}\n{}", date_str, new_frontmatter);
+        }
+
+        if let Some(marker) = provenance {
+            if extract_field(&new_frontmatter, PROVENANCE_FIELD).is_some() {
+                new_frontmatter = replace_field(&new_frontmatter, PROVENANCE_FIELD, marker);
+            } else {
+                if !new_frontmatter.ends_with('\n') {
+                    new_frontmatter.push('\n');
+                }
+                new_frontmatter.push_str(&format!("{}: {}\n", PROVENANCE_FIELD, marker));
+            }
+        }
+
+        if !new_frontmatter.ends_with('\n') {
+            new_frontmatter.push('\n');
+        }
+        format!("---\n{}---\n{}", new_frontmatter, rest)
+    };
+
+    fs::write(path, new_content)?;
+    Ok(())
+}
+
+/// Split content into (frontmatter lines, body) when it opens with `---`
+fn split(content: &str) -> Option<(&str, &str)> {
+    let inner = content.strip_prefix("---\n")?;
+    if let Some(body) = inner.strip_prefix("---\n") {
+        return Some(("", body));
+    }
+    let end = inner.find("\n---\n")?;
+    Some((&inner[..end + 1], &inner[end + 5..]))
+}
+
+fn extract_field(frontmatter: &str, field: &str) -> Option<String> {
+    let prefix = format!("{}: ", field);
+    frontmatter
+        .lines()
+        .find_map(|line| line.strip_prefix(&prefix))
+        .map(|value| value.trim().to_string())
+}
+
+fn replace_field(frontmatter: &str, field: &str, value: &str) -> String {
+    let prefix = format!("{}: ", field);
+    frontmatter
+        .lines()
+        .map(|line| {
+            if line.starts_with(&prefix) {
+                format!("{}{}\n", prefix, value)
+            } else {
+                format!("{}\n", line)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2015-10-01 08:09:44 UTC
+    const TIMESTAMP: i64 = 1443686984;
+
+    fn write_temp(content: &str) -> (tempfile::TempDir, std::path::PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("note.md");
+        fs::write(&path, content).unwrap();
+        (dir, path)
+    }
+
+    #[test]
+    fn updates_existing_frontmatter_and_records_marker() {
+        let (_dir, path) = write_temp("---\ntags: [x]\ndate created: 2024-01-01 00:00\n---\nBody\n");
+        update_dates(&path, TIMESTAMP, Some("evernote 2026-02-10")).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            content,
+            "---\ndate modified: 2015-10-01 08:09\ntags: [x]\ndate created: 2015-10-01 08:09\n\
+             date restored: evernote 2026-02-10\n---\nBody\n"
+        );
+        assert_eq!(read_provenance(&path).unwrap().as_deref(), Some("evernote 2026-02-10"));
+    }
+
+    #[test]
+    fn adds_frontmatter_when_missing() {
+        let (_dir, path) = write_temp("Body\n");
+        update_dates(&path, TIMESTAMP, Some("evernote 2026-02-10")).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("---\ndate created: 2015-10-01 08:09\n"));
+        assert!(content.ends_with("date restored: evernote 2026-02-10\n---\nBody\n"));
+    }
+
+    #[test]
+    fn provenance_off_writes_no_marker() {
+        let (_dir, path) = write_temp("---\ntitle: x\n---\nBody\n");
+        update_dates(&path, TIMESTAMP, None).unwrap();
+
+        assert_eq!(read_provenance(&path).unwrap(), None);
+        assert!(!should_skip(&path, false).unwrap());
+    }
+
+    #[test]
+    fn marked_files_skipped_unless_forced() {
+        let (_dir, path) = write_temp("---\ndate restored: content 2026-01-05\n---\nBody\n");
+        assert!(should_skip(&path, false).unwrap());
+        assert!(!should_skip(&path, true).unwrap());
+    }
+}
@@ -1,9 +1,11 @@
+mod frontmatter;
+
 use anyhow::{Context, Result};
 use chrono::{DateTime, NaiveDateTime, Utc};
 use clap::Parser;
 use quick_xml::events::Event;
 use quick_xml::Reader;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
@@ -27,6 +29,14 @@ struct Args {
     /// Show detailed progress
     #[arg(long)]
     verbose: bool,
+
+    /// Also update files that already carry a `date restored` marker
+    #[arg(long)]
+    force: bool,
+
+    /// Don't write a `date restored` marker into updated files
+    #[arg(long)]
+    provenance_off: bool,
 }
 
 #[derive(Debug)]
@@ -40,19 +50,26 @@ struct MatchResult {
     status: MatchStatus,
     evernote_title: String,
     file_title: String,
+    file_path: Option<PathBuf>,
 }
 
 #[derive(Debug, PartialEq)]
 enum MatchStatus {
     Updated,
     WouldUpdate,
+    AlreadyRestored,
     NoMatch,
     Error(String),
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
+    run(&args)?;
+    Ok(())
+}
 
+/// Run both matching passes and print the summary; returns every result
+fn run(args: &Args) -> Result<Vec<MatchResult>> {
     println!("Restore dates for files with special character substitutions");
     println!("Reading Evernote export: {}", args.enex_file.display());
     println!("Target directory: {}\n", args.target_dir.display());
@@ -87,17 +104,39 @@ fn main() -> Result<()> {
 
     // Match notes to files using fuzzy matching
     println!("Matching Evernote notes to files with special character substitutions...");
-    let results = match_and_process_notes(
-        &special_char_notes,
-        &file_map,
-        args.dry_run,
-        args.verbose,
-    )?;
+    let results = match_and_process_notes(&special_char_notes, &file_map, args)?;
+
+    // Reverse pass: start from `_` filenames the forward pass didn't reach
+    println!("\nReverse pass: reconstructing titles from filenames containing _...");
+    let matched_files: HashSet<&Path> = results
+        .iter()
+        .filter(|r| r.status != MatchStatus::NoMatch)
+        .filter_map(|r| r.file_path.as_deref())
+        .collect();
+    let matched_titles: HashSet<&str> = results
+        .iter()
+        .filter(|r| r.status != MatchStatus::NoMatch)
+        .map(|r| r.evernote_title.as_str())
+        .collect();
+    let reverse_matches = reverse_match_files(&notes, &file_map, &matched_files, &matched_titles, args.verbose);
+    println!("Found {} additional matches\n", reverse_matches.len());
+
+    let mut reverse_results = Vec::new();
+    for (idx, (note, file_title, file_path)) in reverse_matches.iter().enumerate() {
+        reverse_results.push(apply_note_date(
+            note,
+            file_path,
+            file_title.clone(),
+            args,
+            idx + 1,
+            reverse_matches.len(),
+        ));
+    }
 
     // Print summary
-    print_summary(&results, special_char_notes.len(), args.dry_run);
+    print_summary(&results, &reverse_results, special_char_notes.len(), args.dry_run);
 
-    Ok(())
+    Ok(results.into_iter().chain(reverse_results).collect())
 }
 
 fn parse_evernote_export(path: &Path) -> Result<Vec<EvernoteNote>> {
@@ -134,11 +173,9 @@ fn parse_evernote_export(path: &Path) -> Result<Vec<EvernoteNote>> {
                     inside_created = false;
                 }
             }
-            Ok(Event::End(ref e)) => {
-                if e.name().as_ref() == b"note" {
-                    if let (Some(title), Some(created)) = (current_title.take(), current_created.take()) {
-                        notes.push(EvernoteNote { title, created });
-                    }
+            Ok(Event::End(ref e)) if e.name().as_ref() == b"note" => {
+                if let (Some(title), Some(created)) = (current_title.take(), current_created.take()) {
+                    notes.push(EvernoteNote { title, created });
                 }
             }
             Ok(Event::Eof) => break,
@@ -168,11 +205,100 @@ fn find_markdown_files(dir: &Path) -> Result<Vec<PathBuf>> {
 
 fn normalize_title(title: &str) -> String {
     // Replace special characters that macOS/Linux don't allow in filenames with underscore
-    title
-        .replace('?', "_")
-        .replace('!', "_")
-        .replace(':', "_")
-        .replace('/', "_")
+    title.replace(SPECIAL_CHARS, "_")
+}
+
+/// Characters Evernote titles may contain that were exported as `_`
+const SPECIAL_CHARS: [char; 4] = ['?', '!', ':', '/'];
+
+/// Filenames with more underscores than this only get uniform substitutions
+/// (every `_` → the same character) to keep the candidate count bounded
+const MAX_EXPANDED_UNDERSCORES: usize = 4;
+
+/// Plausible original titles for a filename stem containing `_`.
+///
+/// Each `_` may have been any of `? ! : /` or a genuine underscore. Every
+/// combination is tried for up to `MAX_EXPANDED_UNDERSCORES` underscores;
+/// beyond that only uniform substitutions are generated.
+fn candidate_titles(stem: &str) -> Vec<String> {
+    let positions = stem.matches('_').count();
+    if positions == 0 {
+        return Vec::new();
+    }
+
+    if positions > MAX_EXPANDED_UNDERSCORES {
+        return SPECIAL_CHARS
+            .iter()
+            .map(|c| stem.replace('_', &c.to_string()))
+            .collect();
+    }
+
+    let choices: Vec<char> = std::iter::once('_').chain(SPECIAL_CHARS).collect();
+    let mut candidates = vec![String::new()];
+    for (i, part) in stem.split('_').enumerate() {
+        if i > 0 {
+            candidates = candidates
+                .into_iter()
+                .flat_map(|prefix| choices.iter().map(move |c| format!("{}{}", prefix, c)))
+                .collect();
+        }
+        for candidate in &mut candidates {
+            candidate.push_str(part);
+        }
+    }
+    // Drop the unchanged stem; the forward pass already covers it
+    candidates.retain(|c| c != stem);
+    candidates
+}
+
+/// For each markdown file whose stem contains `_` and that the forward pass
+/// didn't update, look for exactly one unmatched Evernote title among the
+/// reconstructed candidates. Returns (note, file stem, file path) triples.
+fn reverse_match_files<'a>(
+    notes: &'a [EvernoteNote],
+    file_map: &HashMap<String, PathBuf>,
+    matched_files: &HashSet<&Path>,
+    matched_titles: &HashSet<&str>,
+    verbose: bool,
+) -> Vec<(&'a EvernoteNote, String, PathBuf)> {
+    let by_title: HashMap<&str, &EvernoteNote> = notes
+        .iter()
+        .filter(|note| !matched_titles.contains(note.title.as_str()))
+        .map(|note| (note.title.as_str(), note))
+        .collect();
+
+    let mut stems: Vec<(&String, &PathBuf)> = file_map
+        .iter()
+        .filter(|(stem, path)| stem.contains('_') && !matched_files.contains(path.as_path()))
+        .collect();
+    stems.sort();
+
+    let mut matches = Vec::new();
+    let mut claimed: HashSet<&str> = HashSet::new();
+    for (stem, path) in stems {
+        let hits: Vec<&EvernoteNote> = candidate_titles(stem)
+            .iter()
+            .filter_map(|title| by_title.get(title.as_str()).copied())
+            .filter(|note| !claimed.contains(note.title.as_str()))
+            .collect();
+
+        match hits.as_slice() {
+            [] => {}
+            [note] => {
+                claimed.insert(note.title.as_str());
+                matches.push((*note, stem.clone(), path.clone()));
+            }
+            _ => {
+                if verbose {
+                    println!("⊘ Ambiguous reverse match for {}:", stem);
+                    for note in &hits {
+                        println!("   Evernote: {}", note.title);
+                    }
+                }
+            }
+        }
+    }
+    matches
 }
 
 fn build_file_map(files: &[PathBuf]) -> HashMap<String, PathBuf> {
@@ -191,13 +317,12 @@ fn build_file_map(files: &[PathBuf]) -> HashMap<String, PathBuf> {
 fn match_and_process_notes(
     notes: &[&EvernoteNote],
     file_map: &HashMap<String, PathBuf>,
-    dry_run: bool,
-    verbose: bool,
+    args: &Args,
 ) -> Result<Vec<MatchResult>> {
     let mut results = Vec::new();
 
     for (idx, note) in notes.iter().enumerate() {
-        let result = process_note(note, file_map, dry_run, verbose, idx + 1, notes.len())?;
+        let result = process_note(note, file_map, args, idx + 1, notes.len())?;
         results.push(result);
     }
 
@@ -207,11 +332,11 @@ fn match_and_process_notes(
 fn process_note(
     note: &EvernoteNote,
     file_map: &HashMap<String, PathBuf>,
-    dry_run: bool,
-    verbose: bool,
+    args: &Args,
     idx: usize,
     total: usize,
 ) -> Result<MatchResult> {
+    let verbose = args.verbose;
     // Normalize the Evernote title by replacing special chars with _
     let normalized_title = normalize_title(&note.title);
 
@@ -243,12 +368,47 @@ fn process_note(
                         status: MatchStatus::NoMatch,
                         evernote_title: note.title.clone(),
                         file_title: normalized_title,
+                        file_path: None,
                     });
                 }
             }
         }
     };
 
+    Ok(apply_note_date(note, file_path, normalized_title, args, idx, total))
+}
+
+/// Write the Evernote creation date into a matched file's frontmatter and mtime
+fn apply_note_date(
+    note: &EvernoteNote,
+    file_path: &Path,
+    normalized_title: String,
+    args: &Args,
+    idx: usize,
+    total: usize,
+) -> MatchResult {
+    let verbose = args.verbose;
+    let result = |status: MatchStatus| MatchResult {
+        status,
+        evernote_title: note.title.clone(),
+        file_title: normalized_title.clone(),
+        file_path: Some(file_path.to_path_buf()),
+    };
+
+    // Leave files an earlier restore already dated
+    match frontmatter::should_skip(file_path, args.force) {
+        Ok(true) => {
+            if verbose {
+                println!("⏭ [{}/{}] Already restored:", idx, total);
+                println!("   Evernote: {}", note.title);
+                println!("   File: {}", file_path.display());
+            }
+            return result(MatchStatus::AlreadyRestored);
+        }
+        Ok(false) => {}
+        Err(e) => return result(MatchStatus::Error(format!("Failed to read file: {}", e))),
+    }
+
     // Parse the Evernote timestamp (format: 20151001T080944Z)
     let timestamp = match parse_evernote_timestamp(&note.created) {
         Ok(ts) => ts,
@@ -256,29 +416,22 @@ fn process_note(
             if verbose {
                 println!("⚠ [{}/{}] Failed to parse date: {} - {}", idx, total, note.title, e);
             }
-            return Ok(MatchResult {
-                status: MatchStatus::Error(format!("Failed to parse date: {}", e)),
-                evernote_title: note.title.clone(),
-                file_title: normalized_title,
-            });
+            return result(MatchStatus::Error(format!("Failed to parse date: {}", e)));
         }
     };
 
-    if dry_run {
+    if args.dry_run {
         if verbose {
             println!("🔍 [{}/{}] Would update:", idx, total);
             println!("   Evernote: {}", note.title);
             println!("   File: {}", file_path.display());
             println!("   Date: {}", note.created);
         }
-        Ok(MatchResult {
-            status: MatchStatus::WouldUpdate,
-            evernote_title: note.title.clone(),
-            file_title: normalized_title,
-        })
+        result(MatchStatus::WouldUpdate)
     } else {
         // Update YAML frontmatter
-        match update_yaml_frontmatter(file_path, timestamp) {
+        let provenance = (!args.provenance_off).then(|| frontmatter::provenance_marker("evernote"));
+        match frontmatter::update_dates(file_path, timestamp, provenance.as_deref()) {
             Ok(_) => {
                 // Then update file timestamp
                 match set_file_mtime(file_path, timestamp) {
@@ -289,29 +442,17 @@ fn process_note(
                             println!("   File: {}", file_path.display());
                             println!("   Date: {}", note.created);
                         }
-                        Ok(MatchResult {
-                            status: MatchStatus::Updated,
-                            evernote_title: note.title.clone(),
-                            file_title: normalized_title,
-                        })
+                        result(MatchStatus::Updated)
                     }
                     Err(e) => {
                         eprintln!("⚠ [{}/{}] Failed to update mtime: {} - {}", idx, total, note.title, e);
-                        Ok(MatchResult {
-                            status: MatchStatus::Error(format!("Failed to update mtime: {}", e)),
-                            evernote_title: note.title.clone(),
-                            file_title: normalized_title,
-                        })
+                        result(MatchStatus::Error(format!("Failed to update mtime: {}", e)))
                     }
                 }
             }
             Err(e) => {
                 eprintln!("⚠ [{}/{}] Failed to update YAML: {} - {}", idx, total, note.title, e);
-                Ok(MatchResult {
-                    status: MatchStatus::Error(format!("Failed to update YAML: {}", e)),
-                    evernote_title: note.title.clone(),
-                    file_title: normalized_title,
-                })
+                result(MatchStatus::Error(format!("Failed to update YAML: {}", e)))
             }
         }
     }
@@ -347,89 +488,7 @@ fn set_file_mtime(path: &Path, timestamp: i64) -> Result<()> {
     Ok(())
 }
 
-fn update_yaml_frontmatter(path: &Path, timestamp: i64) -> Result<()> {
-    // Convert timestamp to YAML date format: "YYYY-MM-DD HH:MM"
-    let datetime: DateTime<Utc> = DateTime::from_timestamp(timestamp, 0)
-        .ok_or_else(|| anyhow::anyhow!("Invalid timestamp"))?;
-    let date_str = datetime.format("%Y-%m-%d %H:%M").to_string();
-
-    // Read file content
-    let content = fs::read_to_string(path)
-        .with_context(|| format!("Failed to read file: {}", path.display()))?;
-
-    // Check if file has YAML frontmatter
-    if !content.starts_with("---\n") {
-        // No frontmatter - add it at the beginning
-        let new_content = format!(
-            "---\ndate created: {}\ndate modified: {}\n---\n{}",
-            date_str, date_str, content
-        );
-        fs::write(path, new_content)?;
-        return Ok(());
-    }
-
-    // Find end of frontmatter
-    let end_marker = content[4..].find("\n---\n");
-    if end_marker.is_none() {
-        return Err(anyhow::anyhow!("Malformed YAML frontmatter"));
-    }
-
-    let end_pos = end_marker.unwrap() + 4;
-    let frontmatter = &content[4..end_pos];
-    let rest = &content[end_pos + 5..]; // Skip "\n---\n"
-
-    // Update or add date fields
-    let mut new_frontmatter = frontmatter.to_string();
-
-    // Update date created
-    if let Some(_) = extract_date_field(&new_frontmatter, "date created") {
-        new_frontmatter = replace_date_field(&new_frontmatter, "date created", &date_str);
-    } else {
-        new_frontmatter = format!("date created: {}\n{}", date_str, new_frontmatter);
-    }
-
-    // Update date modified
-    if new_frontmatter.contains("date modified:") {
-        new_frontmatter = replace_date_field(&new_frontmatter, "date modified", &date_str);
-    } else {
-        if new_frontmatter.starts_with("date created:") {
-            let first_newline = new_frontmatter.find('\n').unwrap_or(new_frontmatter.len());
-            new_frontmatter.insert_str(first_newline + 1, &format!("date modified: {}\n", date_str));
-        } else {
-            new_frontmatter = format!("date modified: {}\n{}", date_str, new_frontmatter);
-        }
-    }
-
-    // Write back
-    let new_content = format!("---\n{}---\n{}", new_frontmatter, rest);
-    fs::write(path, new_content)?;
-    Ok(())
-}
-
-fn extract_date_field(frontmatter: &str, field: &str) -> Option<String> {
-    for line in frontmatter.lines() {
-        if line.starts_with(&format!("{}: ", field)) {
-            return Some(line[field.len() + 2..].trim().to_string());
-        }
-    }
-    None
-}
-
-fn replace_date_field(frontmatter: &str, field: &str, new_date: &str) -> String {
-    frontmatter
-        .lines()
-        .map(|line| {
-            if line.starts_with(&format!("{}: ", field)) {
-                format!("{}: {}", field, new_date)
-            } else {
-                line.to_string()
-            }
-        })
-        .collect::<Vec<_>>()
-        .join("\n")
-}
-
-fn print_summary(results: &[MatchResult], total_notes: usize, dry_run: bool) {
+fn print_summary(results: &[MatchResult], reverse_results: &[MatchResult], total_notes: usize, dry_run: bool) {
     println!("\n=== SUMMARY ===");
     println!("Notes with special characters: {}", total_notes);
 
@@ -441,26 +500,44 @@ fn print_summary(results: &[MatchResult], total_notes: usize, dry_run: bool) {
         matches!(r.status, MatchStatus::NoMatch)
     }).count();
 
-    let errors = results.iter().filter(|r| {
+    let errors = results.iter().chain(reverse_results).filter(|r| {
         matches!(r.status, MatchStatus::Error(_))
     }).count();
 
+    let already_restored = results.iter().chain(reverse_results).filter(|r| {
+        matches!(r.status, MatchStatus::AlreadyRestored)
+    }).count();
+
+    let reverse_matched = reverse_results.iter().filter(|r| {
+        matches!(r.status, MatchStatus::Updated | MatchStatus::WouldUpdate)
+    }).count();
+
     if dry_run {
         println!("\nFiles that would be updated: {}", matched);
     } else {
         println!("\nFiles updated: {}", matched);
     }
     println!("Files with no match: {}", no_match);
+    println!("Already restored (skipped): {}", already_restored);
+    println!("Reverse-pass matches: {}", reverse_matched);
     println!("Errors: {}", errors);
 
-    if total_notes > 0 {
-        let match_rate = (matched * 100) / total_notes;
+    if let Some(match_rate) = (matched * 100).checked_div(total_notes) {
         println!("\nMatch rate: {}%", match_rate);
     }
 
+    if reverse_matched > 0 {
+        println!("\nReverse-pass matches:");
+        for result in reverse_results {
+            if matches!(result.status, MatchStatus::Updated | MatchStatus::WouldUpdate) {
+                println!("  - {} → {}", result.file_title, result.evernote_title);
+            }
+        }
+    }
+
     if errors > 0 {
         println!("\nErrors encountered:");
-        for result in results {
+        for result in results.iter().chain(reverse_results) {
             if let MatchStatus::Error(msg) = &result.status {
                 println!("  - {}: {}", result.evernote_title, msg);
             }
@@ -471,3 +548,86 @@ fn print_summary(results: &[MatchResult], total_notes: usize, dry_run: bool) {
         println!("\n💡 Run without --dry-run to apply changes");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn candidates_cover_every_substitution() {
+        let candidates = candidate_titles("Why_ Really_");
+        assert_eq!(candidates.len(), 24); // 5 choices at 2 positions, minus the unchanged stem
+        assert!(candidates.contains(&"Why? Really!".to_string()));
+        assert!(candidates.contains(&"Why: Really_".to_string()));
+        assert!(!candidates.contains(&"Why_ Really_".to_string()));
+    }
+
+    #[test]
+    fn many_underscores_use_uniform_substitution() {
+        let candidates = candidate_titles("a_b_c_d_e_f");
+        assert_eq!(candidates, vec!["a?b?c?d?e?f", "a!b!c!d!e!f", "a:b:c:d:e:f", "a/b/c/d/e/f"]);
+    }
+
+    #[test]
+    fn reverse_pass_finds_unmatched_title() {
+        let notes = vec![
+            EvernoteNote { title: "Q&A: Sleep?".to_string(), created: "20151001T080944Z".to_string() },
+            EvernoteNote { title: "Already done!".to_string(), created: "20151001T080944Z".to_string() },
+        ];
+        let mut file_map = HashMap::new();
+        file_map.insert("Q&A_ Sleep_".to_string(), PathBuf::from("/v/Q&A_ Sleep_.md"));
+        file_map.insert("Already done_".to_string(), PathBuf::from("/v/Already done_.md"));
+        file_map.insert("plain_name".to_string(), PathBuf::from("/v/plain_name.md"));
+
+        let matched_files = HashSet::new();
+        let matched_titles: HashSet<&str> = ["Already done!"].into_iter().collect();
+        let matches = reverse_match_files(&notes, &file_map, &matched_files, &matched_titles, false);
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].0.title, "Q&A: Sleep?");
+        assert_eq!(matches[0].2, PathBuf::from("/v/Q&A_ Sleep_.md"));
+    }
+
+    #[test]
+    fn second_run_skips_already_restored_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let enex = dir.path().join("notes.enex");
+        fs::write(
+            &enex,
+            "<en-export>\
+             <note><title>Why: now?</title><created>20151001T080944Z</created></note>\
+             <note><title>Done!</title><created>20160101T120000Z</created></note>\
+             </en-export>",
+        )
+        .unwrap();
+        let target = dir.path().join("vault");
+        fs::create_dir(&target).unwrap();
+        fs::write(target.join("Why_ now_.md"), "---\ntags: [x]\n---\nBody\n").unwrap();
+        fs::write(target.join("Done_.md"), "No frontmatter\n").unwrap();
+
+        let args = Args::parse_from([
+            "restore-special-char-dates",
+            enex.to_str().unwrap(),
+            target.to_str().unwrap(),
+        ]);
+
+        let first = run(&args).unwrap();
+        assert_eq!(first.len(), 2);
+        assert!(first.iter().all(|r| r.status == MatchStatus::Updated));
+        let snapshot = |name: &str| fs::read_to_string(target.join(name)).unwrap();
+        let after_first = (snapshot("Why_ now_.md"), snapshot("Done_.md"));
+
+        let second = run(&args).unwrap();
+        assert_eq!(second.len(), 2);
+        assert!(second.iter().all(|r| r.status == MatchStatus::AlreadyRestored));
+        assert_eq!((snapshot("Why_ now_.md"), snapshot("Done_.md")), after_first);
+
+        let forced = Args::parse_from([
+            "restore-special-char-dates",
+            "--force",
+            enex.to_str().unwrap(),
+            target.to_str().unwrap(),
+        ]);
+        assert!(run(&forced).unwrap().iter().all(|r| r.status == MatchStatus::Updated));
+    }
+}
@@ -2,15 +2,18 @@
 //!
 //! Scans markdown files and removes ?[[ prefixes where the target file exists.
 //! Handles multiple ? prefixes (??[[, ???[[, etc.) from accumulated marking.
+//! `--stats-json` and `--check-mode` make the outcome readable by scripts.
 
 use anyhow::{Context, Result};
 use clap::Parser;
 use colored::*;
 use rayon::prelude::*;
 use regex::{Captures, Regex};
+use serde::Serialize;
 use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Mutex;
 use walkdir::WalkDir;
@@ -28,13 +31,104 @@ struct Args {
     dry_run: bool,
 
     /// Verbose output - show each file being processed
-    #[arg(short, long)]
+    #[arg(short, long, conflicts_with = "quiet")]
     verbose: bool,
+
+    /// Print only the final counts (and any errors)
+    #[arg(short, long)]
+    quiet: bool,
+
+    /// Write a JSON summary of the run to this file, or to stdout with `-`
+    /// (which also silences the rest of the output)
+    #[arg(long, value_name = "PATH")]
+    stats_json: Option<PathBuf>,
+
+    /// Exit 0 when there was nothing to do, 1 when markers were (or in a
+    /// dry run would be) changed, 2 when any file couldn't be processed
+    #[arg(long)]
+    check_mode: bool,
 }
 
-fn main() -> Result<()> {
-    let args = Args::parse();
+/// Outcome of a run, as written by `--stats-json`
+#[derive(Debug, Default, PartialEq, Serialize)]
+struct Stats {
+    files_scanned: usize,
+    /// Files changed, or that a dry run would change
+    files_modified: usize,
+    /// Markers removed because their target now exists
+    markers_cleaned: usize,
+    /// `??[[` and longer markers reduced to `?[[`
+    markers_normalized: usize,
+    /// Markers whose target still doesn't exist, normalized or not
+    unresolved_remaining: usize,
+    errors: usize,
+}
+
+impl Stats {
+    /// Exit code for `--check-mode`
+    fn exit_code(&self) -> u8 {
+        if self.errors > 0 {
+            2
+        } else if self.files_modified > 0 {
+            1
+        } else {
+            0
+        }
+    }
+}
+
+/// Markers found in one file
+#[derive(Debug, Default, PartialEq)]
+struct FileOutcome {
+    modified: bool,
+    cleaned: usize,
+    normalized: usize,
+    unresolved: usize,
+}
+
+fn main() -> ExitCode {
+    let mut args = Args::parse();
+    let json_to_stdout = args.stats_json.as_deref() == Some(Path::new("-"));
+    if json_to_stdout {
+        args.quiet = true;
+        args.verbose = false;
+    }
+
+    let stats = match run(&args, !json_to_stdout) {
+        Ok(stats) => stats,
+        Err(e) => {
+            eprintln!("{} {:#}", "Error:".red().bold(), e);
+            return ExitCode::from(if args.check_mode { 2 } else { 1 });
+        }
+    };
+
+    if let Some(path) = &args.stats_json {
+        if let Err(e) = write_stats(&stats, path) {
+            eprintln!("{} {:#}", "Error:".red().bold(), e);
+            return ExitCode::from(if args.check_mode { 2 } else { 1 });
+        }
+    }
 
+    if args.check_mode {
+        ExitCode::from(stats.exit_code())
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn write_stats(stats: &Stats, path: &Path) -> Result<()> {
+    let json = serde_json::to_string_pretty(stats)?;
+    if path == Path::new("-") {
+        println!("{}", json);
+        Ok(())
+    } else {
+        fs::write(path, json + "\n").with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Scan and clean, returning the counts. `summary` prints the final counts,
+/// even when `args.quiet` suppresses everything else.
+fn run(args: &Args, summary: bool) -> Result<Stats> {
     let dirs = if args.dirs.is_empty() {
         let home = dirs::home_dir().context("Could not determine home directory")?;
         vec![
@@ -51,22 +145,12 @@ fn main() -> Result<()> {
 
     if dirs.is_empty() {
         eprintln!("{}", "No valid directories to scan".red());
-        return Ok(());
+        return Ok(Stats { errors: 1, ..Stats::default() });
     }
 
-    println!(
-        "{} {}",
-        "Scanning directories:".blue().bold(),
-        dirs.iter()
-            .map(|d| d.display().to_string())
-            .collect::<Vec<_>>()
-            .join(", ")
-    );
-
-    if args.dry_run {
-        println!("{}", "(Dry run - no files will be modified)".yellow());
+    if !args.quiet {
+        print_preamble(&dirs, args.dry_run);
     }
-    println!();
 
     // First pass: collect all markdown filenames (without .md extension)
     // This is our "exists" lookup table
@@ -79,7 +163,7 @@ fn main() -> Result<()> {
                 .filter(|e| {
                     e.path()
                         .extension()
-                        .map_or(false, |ext| ext == "md")
+                        .is_some_and(|ext| ext == "md")
                 })
                 .filter_map(|e| {
                     e.path()
@@ -90,11 +174,13 @@ fn main() -> Result<()> {
         })
         .collect();
 
-    println!(
-        "{} {} markdown files indexed",
-        "Found".green(),
-        existing_files.len()
-    );
+    if !args.quiet {
+        println!(
+            "{} {} markdown files indexed",
+            "Found".green(),
+            existing_files.len()
+        );
+    }
 
     // Collect all markdown files to process
     let files: Vec<PathBuf> = dirs
@@ -106,14 +192,16 @@ fn main() -> Result<()> {
                 .filter(|e| {
                     e.path()
                         .extension()
-                        .map_or(false, |ext| ext == "md")
+                        .is_some_and(|ext| ext == "md")
                 })
                 .map(|e| e.path().to_path_buf())
         })
         .collect();
 
-    println!("{} {} files to scan", "Processing".green(), files.len());
-    println!();
+    if !args.quiet {
+        println!("{} {} files to scan", "Processing".green(), files.len());
+        println!();
+    }
 
     // Pattern to match ?[[link]] with one or more ? prefixes
     // Captures: group 1 = the ?'s, group 2 = the link name (without |alias or #header)
@@ -121,16 +209,20 @@ fn main() -> Result<()> {
 
     let files_modified = AtomicUsize::new(0);
     let markers_cleaned = AtomicUsize::new(0);
+    let markers_normalized = AtomicUsize::new(0);
+    let unresolved = AtomicUsize::new(0);
     let errors: Mutex<Vec<String>> = Mutex::new(Vec::new());
 
     // Process files in parallel
     files.par_iter().for_each(|path| {
         match process_file(path, &pattern, &existing_files, args.dry_run, args.verbose) {
-            Ok((modified, cleaned)) => {
-                if modified {
+            Ok(outcome) => {
+                if outcome.modified {
                     files_modified.fetch_add(1, Ordering::Relaxed);
                 }
-                markers_cleaned.fetch_add(cleaned, Ordering::Relaxed);
+                markers_cleaned.fetch_add(outcome.cleaned, Ordering::Relaxed);
+                markers_normalized.fetch_add(outcome.normalized, Ordering::Relaxed);
+                unresolved.fetch_add(outcome.unresolved, Ordering::Relaxed);
             }
             Err(e) => {
                 errors
@@ -142,26 +234,67 @@ fn main() -> Result<()> {
     });
 
     // Report results
-    let modified = files_modified.load(Ordering::Relaxed);
-    let cleaned = markers_cleaned.load(Ordering::Relaxed);
-    let errs = errors.lock().unwrap();
+    let errs = errors.into_inner().unwrap();
+    let stats = Stats {
+        files_scanned: files.len(),
+        files_modified: files_modified.into_inner(),
+        markers_cleaned: markers_cleaned.into_inner(),
+        markers_normalized: markers_normalized.into_inner(),
+        unresolved_remaining: unresolved.into_inner(),
+        errors: errs.len(),
+    };
+    if summary {
+        print_summary(&stats, &errs, args.dry_run, args.quiet);
+    }
+    Ok(stats)
+}
 
+fn print_preamble(dirs: &[PathBuf], dry_run: bool) {
+    println!(
+        "{} {}",
+        "Scanning directories:".blue().bold(),
+        dirs.iter()
+            .map(|d| d.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    if dry_run {
+        println!("{}", "(Dry run - no files will be modified)".yellow());
+    }
     println!();
-    if args.dry_run {
+}
+
+fn print_summary(stats: &Stats, errs: &[String], dry_run: bool, quiet: bool) {
+    // Cleaned and normalized together, as before the split
+    let changed = (stats.markers_cleaned + stats.markers_normalized).to_string();
+    let modified = stats.files_modified.to_string();
+
+    if !quiet {
+        println!();
+    }
+    if dry_run {
         println!(
             "{} {} markers in {} files would be cleaned",
             "Dry run:".yellow().bold(),
-            cleaned.to_string().green(),
-            modified.to_string().green()
+            changed.green(),
+            modified.green()
         );
     } else {
         println!(
             "{} {} markers in {} files",
             "Cleaned".green().bold(),
-            cleaned.to_string().green(),
-            modified.to_string().green()
+            changed.green(),
+            modified.green()
         );
     }
+    println!(
+        "{} {} resolved, {} normalized, {} unresolved remaining",
+        "Markers:".blue().bold(),
+        stats.markers_cleaned,
+        stats.markers_normalized,
+        stats.unresolved_remaining
+    );
 
     if !errs.is_empty() {
         println!();
@@ -173,8 +306,6 @@ fn main() -> Result<()> {
             println!("  ... and {} more", errs.len() - 10);
         }
     }
-
-    Ok(())
 }
 
 fn process_file(
@@ -183,15 +314,14 @@ fn process_file(
     existing_files: &HashSet<String>,
     dry_run: bool,
     verbose: bool,
-) -> Result<(bool, usize)> {
+) -> Result<FileOutcome> {
     let content = fs::read_to_string(path).context("Failed to read file")?;
 
-    let mut cleaned_count = 0;
-    let mut modified = false;
+    let mut outcome = FileOutcome::default();
 
     // Check if there are any ?[[ markers first (quick check)
     if !content.contains("?[[") {
-        return Ok((false, 0));
+        return Ok(outcome);
     }
 
     let new_content = pattern.replace_all(&content, |caps: &Captures| {
@@ -205,8 +335,8 @@ fn process_file(
         // Check if target exists
         if existing_files.contains(link_name) {
             // Target exists - remove the ? prefix(es)
-            cleaned_count += 1;
-            modified = true;
+            outcome.cleaned += 1;
+            outcome.modified = true;
             if verbose {
                 println!(
                     "  {} {}[[{}]] -> [[{}]] in {}",
@@ -220,10 +350,11 @@ fn process_file(
             format!("[[{}{}]]", link_name_raw, suffix)
         } else {
             // Target doesn't exist - keep the marker (but normalize to single ?)
+            outcome.unresolved += 1;
             if question_marks.len() > 1 {
                 // Normalize multiple ?'s to single ?
-                cleaned_count += 1;
-                modified = true;
+                outcome.normalized += 1;
+                outcome.modified = true;
                 if verbose {
                     println!(
                         "  {} {}[[{}]] -> ?[[{}]] in {}",
@@ -242,9 +373,47 @@ fn process_file(
         }
     });
 
-    if modified && !dry_run {
+    if outcome.modified && !dry_run {
         fs::write(path, new_content.as_ref()).context("Failed to write file")?;
     }
 
-    Ok((modified, cleaned_count))
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(content: &str, existing: &[&str]) -> FileOutcome {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("note.md");
+        fs::write(&path, content).unwrap();
+        let pattern = Regex::new(r"(\?+)\[\[([^\]|#]+)([^\]]*)\]\]").unwrap();
+        let existing: HashSet<String> = existing.iter().map(|s| s.to_string()).collect();
+        process_file(&path, &pattern, &existing, true, false).unwrap()
+    }
+
+    #[test]
+    fn counts_each_kind_of_marker() {
+        let content = "?[[Exists]] ??[[Exists|alias]] ?[[Missing]] ???[[Missing#Heading]] [[Plain]]";
+        assert_eq!(
+            outcome(content, &["Exists"]),
+            FileOutcome { modified: true, cleaned: 2, normalized: 1, unresolved: 2 }
+        );
+    }
+
+    #[test]
+    fn untouched_markers_still_count_as_unresolved() {
+        assert_eq!(
+            outcome("?[[Missing]] and ?[[Also missing]]", &[]),
+            FileOutcome { modified: false, cleaned: 0, normalized: 0, unresolved: 2 }
+        );
+    }
+
+    #[test]
+    fn exit_codes_for_check_mode() {
+        assert_eq!(Stats::default().exit_code(), 0);
+        assert_eq!(Stats { files_modified: 1, ..Stats::default() }.exit_code(), 1);
+        assert_eq!(Stats { files_modified: 1, errors: 1, ..Stats::default() }.exit_code(), 2);
+    }
 }
@@ -0,0 +1,100 @@
+//! Runs the built binary on a scratch vault to pin down `--stats-json` and
+//! `--check-mode`.
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+fn run(vault: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_wiki-resolve-batch"))
+        .arg("--dirs")
+        .arg(vault)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stats(output: &Output) -> serde_json::Value {
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+fn vault(notes: &[(&str, &[u8])]) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    for (name, content) in notes {
+        fs::write(dir.path().join(name), content).unwrap();
+    }
+    dir
+}
+
+#[test]
+fn clean_vault_exits_zero() {
+    let vault = vault(&[("Index.md", b"[[Exists]] ?[[Missing]]"), ("Exists.md", b"")]);
+    let output = run(vault.path(), &["--check-mode", "--stats-json", "-"]);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        stats(&output),
+        serde_json::json!({
+            "files_scanned": 2,
+            "files_modified": 0,
+            "markers_cleaned": 0,
+            "markers_normalized": 0,
+            "unresolved_remaining": 1,
+            "errors": 0,
+        })
+    );
+}
+
+#[test]
+fn changes_exit_one_and_are_written() {
+    let vault = vault(&[("Index.md", b"?[[Exists]] ??[[Missing]] ?[[Missing]]"), ("Exists.md", b"")]);
+    let json_path = vault.path().join("stats.json");
+    let output = run(vault.path(), &["--check-mode", "--quiet", "--stats-json", json_path.to_str().unwrap()]);
+
+    assert_eq!(output.status.code(), Some(1));
+    let stats: serde_json::Value = serde_json::from_str(&fs::read_to_string(&json_path).unwrap()).unwrap();
+    assert_eq!(stats["files_modified"], 1);
+    assert_eq!(stats["markers_cleaned"], 1);
+    assert_eq!(stats["markers_normalized"], 1);
+    assert_eq!(stats["unresolved_remaining"], 2);
+    assert_eq!(stats["errors"], 0);
+    assert_eq!(
+        fs::read_to_string(vault.path().join("Index.md")).unwrap(),
+        "[[Exists]] ?[[Missing]] ?[[Missing]]"
+    );
+
+    // Quiet leaves only the final counts
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("Scanning directories"));
+    assert!(stdout.contains("Cleaned 2 markers in 1 files"));
+
+    // Nothing left to do on the second run
+    assert_eq!(run(vault.path(), &["--check-mode", "--quiet"]).status.code(), Some(0));
+}
+
+#[test]
+fn dry_run_reports_changes_without_making_them() {
+    let vault = vault(&[("Index.md", b"?[[Exists]]"), ("Exists.md", b"")]);
+    let output = run(vault.path(), &["--check-mode", "--dry-run", "--stats-json", "-"]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stats(&output)["markers_cleaned"], 1);
+    assert_eq!(fs::read_to_string(vault.path().join("Index.md")).unwrap(), "?[[Exists]]");
+}
+
+#[test]
+fn unreadable_file_exits_two() {
+    let vault = vault(&[("Index.md", b"?[[Exists]]"), ("Exists.md", b""), ("Broken.md", b"?[[\xff\xfe]]")]);
+    let output = run(vault.path(), &["--check-mode", "--stats-json", "-"]);
+
+    assert_eq!(output.status.code(), Some(2));
+    let stats = stats(&output);
+    assert_eq!(stats["errors"], 1);
+    assert_eq!(stats["files_modified"], 1);
+}
+
+#[test]
+fn exit_codes_need_check_mode() {
+    let vault = vault(&[("Index.md", b"?[[Exists]]"), ("Exists.md", b""), ("Broken.md", b"\xff")]);
+    assert_eq!(run(vault.path(), &[]).status.code(), Some(0));
+}
@@ -0,0 +1,259 @@
+use anyhow::Result;
+
+const SAVED_MARK: &str = "✓ saved";
+const FAILED_MARK: &str = "✗ failed:";
+
+/// How a single line of a batch file should be treated.
+#[derive(Debug, PartialEq)]
+enum Line<'a> {
+    /// Anything that isn't a URL (blank, comment, frontmatter, prose):
+    /// copied through untouched
+    Ignored,
+    /// Already marked as saved on a previous run
+    Saved,
+    /// A URL to process
+    Url(&'a str),
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Summary {
+    pub saved: usize,
+    pub failed: usize,
+    pub skipped: usize,
+}
+
+/// Split off a status suffix left by a previous run, returning the original
+/// line content and whether it was marked saved.
+fn strip_status(line: &str) -> (&str, bool) {
+    if let Some(idx) = line.rfind(SAVED_MARK) {
+        if line[idx + SAVED_MARK.len()..].trim().is_empty() {
+            return (line[..idx].trim_end(), true);
+        }
+    }
+    if let Some(idx) = line.find(FAILED_MARK) {
+        return (line[..idx].trim_end(), false);
+    }
+    (line, false)
+}
+
+fn classify(line: &str) -> Line<'_> {
+    let (base, saved) = strip_status(line);
+    let text = base.trim();
+    let text = text.strip_prefix("- ").unwrap_or(text).trim();
+    let url = text.split_whitespace().next().unwrap_or("");
+    if !url.starts_with("https://") && !url.starts_with("http://") {
+        return Line::Ignored;
+    }
+    if saved {
+        Line::Saved
+    } else {
+        Line::Url(url)
+    }
+}
+
+/// Replace any previous status on `line` with `status`.
+fn annotate(line: &str, status: &str) -> String {
+    let (base, _) = strip_status(line);
+    format!("{base} {status}")
+}
+
+/// Keep failure reasons to a single line so the annotation stays on the
+/// URL's line.
+fn failure_reason(err: &anyhow::Error) -> String {
+    let reason = err.to_string();
+    let first = reason.lines().map(str::trim).find(|l| !l.is_empty()).unwrap_or("unknown error");
+    first.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Process every pending URL in `content` with `process`, returning the
+/// annotated file content and a summary. Lines keep their order; anything
+/// that isn't a URL line (comments, blanks, prose, a leading frontmatter
+/// block) is passed through byte for byte.
+pub fn run(content: &str, mut process: impl FnMut(&str) -> Result<()>) -> (String, Summary) {
+    let mut summary = Summary::default();
+    let mut out = String::with_capacity(content.len());
+    let mut in_frontmatter = false;
+
+    let pending = content
+        .lines()
+        .filter(|l| matches!(classify(l), Line::Url(_)))
+        .count();
+    let mut done = 0;
+
+    for (i, raw) in content.split_inclusive('\n').enumerate() {
+        // Keep each line's own ending, so CRLF files come back as they were
+        let line = raw.trim_end_matches(['\r', '\n']);
+        let ending = &raw[line.len()..];
+
+        if i == 0 && line.trim() == "---" {
+            in_frontmatter = true;
+            out.push_str(raw);
+            continue;
+        }
+        if in_frontmatter {
+            if line.trim() == "---" {
+                in_frontmatter = false;
+            }
+            out.push_str(raw);
+            continue;
+        }
+
+        let status = match classify(line) {
+            Line::Ignored => None,
+            Line::Saved => {
+                summary.skipped += 1;
+                None
+            }
+            Line::Url(url) => {
+                done += 1;
+                eprintln!("\n--- Video {done}/{pending} ---");
+                match process(url) {
+                    Ok(()) => {
+                        summary.saved += 1;
+                        Some(SAVED_MARK.to_string())
+                    }
+                    Err(e) => {
+                        eprintln!("Error: {e:#}");
+                        summary.failed += 1;
+                        Some(format!("{FAILED_MARK} {}", failure_reason(&e)))
+                    }
+                }
+            }
+        };
+        match status {
+            Some(status) => {
+                out.push_str(&annotate(line, &status));
+                out.push_str(ending);
+            }
+            None => out.push_str(raw),
+        }
+    }
+
+    (out, summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ytdlp::{VideoMetadata, VideoSource};
+    use std::cell::RefCell;
+    use std::path::{Path, PathBuf};
+
+    /// Stands in for yt-dlp: serves canned metadata and a one-line json3
+    /// transcript, failing for any URL containing "broken".
+    struct StubSource {
+        calls: RefCell<Vec<String>>,
+    }
+
+    impl VideoSource for StubSource {
+        fn fetch_metadata(&self, url: &str) -> Result<VideoMetadata> {
+            self.calls.borrow_mut().push(url.to_string());
+            if url.contains("broken") {
+                anyhow::bail!("yt-dlp metadata failed: ERROR: Video unavailable\nThis video is private");
+            }
+            let id = url.rsplit(['=', '/']).next().unwrap().to_string();
+            Ok(VideoMetadata {
+                title: format!("Video {id}"),
+                channel: Some("Stub Channel".into()),
+                uploader: None,
+                upload_date: Some("20260301".into()),
+                webpage_url: url.to_string(),
+                duration: None,
+                duration_string: None,
+                description: None,
+                id,
+            })
+        }
+
+        fn download_subtitles(&self, _url: &str, _lang: &str, tmp_dir: &Path) -> Result<PathBuf> {
+            let path = tmp_dir.join("subs.en.json3");
+            std::fs::write(&path, r#"{"events":[{"tStartMs":0,"segs":[{"utf8":"hello there"}]}]}"#)?;
+            Ok(path)
+        }
+    }
+
+    fn fixture() -> String {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/batch-list.md");
+        std::fs::read_to_string(path).unwrap()
+    }
+
+    #[test]
+    fn classifies_lines() {
+        assert_eq!(classify(""), Line::Ignored);
+        assert_eq!(classify("# heading"), Line::Ignored);
+        assert_eq!(classify("- https://youtu.be/abc"), Line::Url("https://youtu.be/abc"));
+        assert_eq!(classify("https://youtu.be/abc  "), Line::Url("https://youtu.be/abc"));
+        assert_eq!(classify("- https://youtu.be/abc ✓ saved"), Line::Saved);
+        assert_eq!(classify("- https://youtu.be/abc ✗ failed: timeout"), Line::Url("https://youtu.be/abc"));
+        assert_eq!(classify("- watch this later"), Line::Ignored);
+        assert_eq!(classify("# done ✓ saved"), Line::Ignored);
+        assert_eq!(classify("note ✗ failed: nothing"), Line::Ignored);
+    }
+
+    #[test]
+    fn annotation_replaces_previous_status() {
+        assert_eq!(annotate("- https://youtu.be/abc", SAVED_MARK), "- https://youtu.be/abc ✓ saved");
+        assert_eq!(
+            annotate("- https://youtu.be/abc ✗ failed: timeout", SAVED_MARK),
+            "- https://youtu.be/abc ✓ saved"
+        );
+    }
+
+    #[test]
+    fn batch_processes_fixture_and_annotates() {
+        let out_dir = tempfile::tempdir().unwrap();
+        let source = StubSource { calls: RefCell::new(Vec::new()) };
+
+        let (annotated, summary) = run(&fixture(), |url| {
+            crate::process_single(&source, url, false, "en", Some(out_dir.path()), false, 1500)
+        });
+
+        assert_eq!(
+            *source.calls.borrow(),
+            vec![
+                "https://www.youtube.com/watch?v=new1",
+                "https://youtu.be/new2",
+                "https://www.youtube.com/watch?v=broken",
+            ]
+        );
+        assert_eq!(summary, Summary { saved: 2, failed: 1, skipped: 1 });
+
+        let expected = "\
+---
+title: Watch later
+---
+# Week 12
+
+- https://www.youtube.com/watch?v=done1 ✓ saved
+- https://www.youtube.com/watch?v=new1 ✓ saved
+https://youtu.be/new2 ✓ saved
+
+# Retry these
+- https://www.youtube.com/watch?v=broken ✗ failed: yt-dlp metadata failed: ERROR: Video unavailable
+- that talk Sam mentioned
+";
+        assert_eq!(annotated, expected);
+
+        assert!(out_dir.path().join("2026-03-01-video-new1.md").exists());
+        assert!(out_dir.path().join("2026-03-01-video-new2.md").exists());
+
+        // A second run only retries the failures
+        source.calls.borrow_mut().clear();
+        let (again, _) = run(&annotated, |url| {
+            crate::process_single(&source, url, false, "en", Some(out_dir.path()), false, 1500)
+        });
+        assert_eq!(*source.calls.borrow(), vec!["https://www.youtube.com/watch?v=broken"]);
+        assert_eq!(again, annotated);
+    }
+
+    #[test]
+    fn non_url_lines_are_kept_byte_for_byte() {
+        let content = "# To watch  \r\n\r\n- https://youtu.be/abc\r\n  - ask Sam about the talk\t\r\nno newline";
+        let (annotated, summary) = run(content, |_| Ok(()));
+        assert_eq!(
+            annotated,
+            "# To watch  \r\n\r\n- https://youtu.be/abc ✓ saved\r\n  - ask Sam about the talk\t\r\nno newline"
+        );
+        assert_eq!(summary, Summary { saved: 1, failed: 0, skipped: 0 });
+    }
+}
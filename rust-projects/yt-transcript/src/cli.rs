@@ -21,6 +21,23 @@ pub struct Cli {
     /// Output directory (default: ~/Media/transcripts)
     #[arg(long, short)]
     pub output_dir: Option<std::path::PathBuf>,
+
+    /// Nest output as <channel>/<date>-<title>.md
+    #[arg(long)]
+    pub organize: bool,
+
+    /// Netscape-format cookies file for members-only or age-gated videos
+    #[arg(long, global = true, value_name = "FILE")]
+    pub cookies: Option<std::path::PathBuf>,
+
+    /// Load cookies from a browser profile (e.g. firefox, chrome)
+    #[arg(long, global = true, value_name = "NAME")]
+    pub cookies_from_browser: Option<String>,
+
+    /// Characters of the video description to keep (0 = omit it). Links
+    /// past the cut are still listed.
+    #[arg(long, global = true, default_value = "1500", value_name = "CHARS")]
+    pub description_limit: usize,
 }
 
 #[derive(Subcommand)]
@@ -41,5 +58,31 @@ pub enum Command {
         /// Output directory
         #[arg(long, short)]
         output_dir: Option<std::path::PathBuf>,
+
+        /// Nest output as <channel>/<date>-<title>.md
+        #[arg(long)]
+        organize: bool,
+    },
+
+    /// Fetch transcripts for every URL listed in a file, one per line
+    Batch {
+        /// File of URLs (blank lines, # comments and "- " list prefixes are ignored)
+        file: std::path::PathBuf,
+
+        /// Preferred subtitle language
+        #[arg(long, default_value = "en")]
+        lang: String,
+
+        /// Output directory
+        #[arg(long, short)]
+        output_dir: Option<std::path::PathBuf>,
+
+        /// Nest output as <channel>/<date>-<title>.md
+        #[arg(long)]
+        organize: bool,
+
+        /// Don't rewrite the file with per-line status markers
+        #[arg(long)]
+        no_annotate: bool,
     },
 }
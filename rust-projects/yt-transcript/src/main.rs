@@ -1,12 +1,17 @@
+mod batch;
 mod cli;
 mod output;
 mod transcript;
 mod ytdlp;
 
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use clap::Parser;
 fn main() -> Result<()> {
     let cli = cli::Cli::parse();
+    let ytdlp = ytdlp::YtDlp {
+        cookies: cli.cookies.clone(),
+        cookies_from_browser: cli.cookies_from_browser.clone(),
+    };
 
     match cli.command {
         Some(cli::Command::Channel {
@@ -14,7 +19,24 @@ fn main() -> Result<()> {
             limit,
             lang,
             output_dir,
-        }) => process_channel(&url, limit, &lang, output_dir.as_deref()),
+            organize,
+        }) => process_channel(&ytdlp, &url, limit, &lang, output_dir.as_deref(), organize, cli.description_limit),
+
+        Some(cli::Command::Batch {
+            file,
+            lang,
+            output_dir,
+            organize,
+            no_annotate,
+        }) => process_batch(
+            &ytdlp,
+            &file,
+            &lang,
+            output_dir.as_deref(),
+            organize,
+            no_annotate,
+            cli.description_limit,
+        ),
 
         None => {
             let url = cli.url.as_deref().unwrap_or_else(|| {
@@ -22,23 +44,34 @@ fn main() -> Result<()> {
                 eprintln!("Usage: yt-transcript <URL> [--stdout] [--lang LANG]");
                 std::process::exit(1);
             });
-            process_single(url, cli.stdout, &cli.lang, cli.output_dir.as_deref())
+            process_single(
+                &ytdlp,
+                url,
+                cli.stdout,
+                &cli.lang,
+                cli.output_dir.as_deref(),
+                cli.organize,
+                cli.description_limit,
+            )
         }
     }
 }
 
 fn process_single(
+    source: &dyn ytdlp::VideoSource,
     url: &str,
     to_stdout: bool,
     lang: &str,
     output_dir: Option<&std::path::Path>,
+    organize: bool,
+    description_limit: usize,
 ) -> Result<()> {
-    let meta = ytdlp::fetch_metadata(url)?;
+    let meta = source.fetch_metadata(url)?;
     eprintln!("Title: {}", meta.title);
     eprintln!("Channel: {}", meta.channel_name());
 
     let tmp = tempfile::tempdir()?;
-    let sub_path = ytdlp::download_subtitles(url, lang, tmp.path())?;
+    let sub_path = source.download_subtitles(url, lang, tmp.path())?;
 
     // Detect if auto-generated (yt-dlp puts "auto" in the filename)
     let is_auto = sub_path
@@ -52,12 +85,12 @@ fn process_single(
         bail!("Transcript was empty after processing");
     }
 
-    let markdown = output::format_markdown(&meta, &transcript_text, is_auto);
+    let markdown = output::format_markdown(&meta, &transcript_text, is_auto, description_limit);
 
     if to_stdout {
         print!("{markdown}");
     } else {
-        let out_path = output::output_path(&meta, output_dir)?;
+        let out_path = output::output_path(&meta, output_dir, organize)?;
         std::fs::write(&out_path, &markdown)?;
         eprintln!("Saved: {}", out_path.display());
     }
@@ -66,19 +99,22 @@ fn process_single(
 }
 
 fn process_channel(
+    ytdlp: &ytdlp::YtDlp,
     channel_url: &str,
     limit: usize,
     lang: &str,
     output_dir: Option<&std::path::Path>,
+    organize: bool,
+    description_limit: usize,
 ) -> Result<()> {
-    let video_urls = ytdlp::list_channel_videos(channel_url, limit)?;
+    let video_urls = ytdlp.list_channel_videos(channel_url, limit)?;
 
     let mut successes = 0;
     let mut failures = 0;
 
     for (i, url) in video_urls.iter().enumerate() {
         eprintln!("\n--- Video {}/{} ---", i + 1, video_urls.len());
-        match process_single(url, false, lang, output_dir) {
+        match process_single(ytdlp, url, false, lang, output_dir, organize, description_limit) {
             Ok(()) => successes += 1,
             Err(e) => {
                 eprintln!("Error: {e:#}");
@@ -90,3 +126,31 @@ fn process_channel(
     eprintln!("\nDone: {successes} saved, {failures} failed");
     Ok(())
 }
+
+fn process_batch(
+    ytdlp: &ytdlp::YtDlp,
+    file: &std::path::Path,
+    lang: &str,
+    output_dir: Option<&std::path::Path>,
+    organize: bool,
+    no_annotate: bool,
+    description_limit: usize,
+) -> Result<()> {
+    let content = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read batch file: {}", file.display()))?;
+
+    let (annotated, summary) = batch::run(&content, |url| {
+        process_single(ytdlp, url, false, lang, output_dir, organize, description_limit)
+    });
+
+    if !no_annotate && annotated != content {
+        std::fs::write(file, &annotated)
+            .with_context(|| format!("Failed to annotate batch file: {}", file.display()))?;
+    }
+
+    eprintln!(
+        "\nDone: {} saved, {} failed, {} already saved",
+        summary.saved, summary.failed, summary.skipped
+    );
+    Ok(())
+}
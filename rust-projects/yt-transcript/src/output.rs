@@ -2,8 +2,9 @@ use crate::ytdlp::VideoMetadata;
 use anyhow::{Context, Result};
 use std::path::{Path, PathBuf};
 
-/// Generate markdown with YAML frontmatter.
-pub fn format_markdown(meta: &VideoMetadata, transcript: &str, is_auto: bool) -> String {
+/// Generate markdown with YAML frontmatter. The video description, cut to
+/// `description_limit` characters (0 = left out), goes above the transcript.
+pub fn format_markdown(meta: &VideoMetadata, transcript: &str, is_auto: bool, description_limit: usize) -> String {
     let date = meta.formatted_date().unwrap_or_else(|| "unknown".into());
     let duration = meta
         .duration_string
@@ -24,15 +25,75 @@ pub fn format_markdown(meta: &VideoMetadata, transcript: &str, is_auto: bool) ->
     out.push_str(&format!("transcript_type: {transcript_type}\n"));
     out.push_str("---\n\n");
     out.push_str(&format!("# {}\n\n", meta.title));
+
+    let description = meta.description.as_deref().unwrap_or("");
+    if description_limit > 0 && !description.trim().is_empty() {
+        let description = truncate_description(description, description_limit);
+        out.push_str("## Description\n\n");
+        out.push_str(&description.text);
+        out.push_str("\n\n");
+        if !description.links.is_empty() {
+            out.push_str("### Links\n\n");
+            for link in &description.links {
+                out.push_str(&format!("- {link}\n"));
+            }
+            out.push('\n');
+        }
+        out.push_str("## Transcript\n\n");
+    }
+
     out.push_str(transcript);
     out.push('\n');
 
     out
 }
 
+/// A description cut down to size, with the link lines that fell past the cut
+#[derive(Debug, PartialEq)]
+pub struct Description {
+    pub text: String,
+    pub links: Vec<String>,
+}
+
+/// Keep the first `limit` characters of `description`, ending on a whole
+/// word with "…". Lines carrying a URL are never lost: any past the cut
+/// (or straddling it) come back in `links`, without list markers.
+pub fn truncate_description(description: &str, limit: usize) -> Description {
+    let description = description.trim();
+    let Some((cut, _)) = description.char_indices().nth(limit) else {
+        return Description { text: description.to_string(), links: Vec::new() };
+    };
+
+    let line_start = description[..cut].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = description[cut..].find('\n').map_or(description.len(), |i| cut + i);
+
+    let (head, rest_start) = if is_url_line(&description[line_start..line_end]) {
+        // Don't leave half a URL in the prose
+        (&description[..line_start], line_start)
+    } else if description[cut..].starts_with(char::is_whitespace) {
+        (&description[..cut], line_end)
+    } else {
+        let word_start = description[..cut].rfind(char::is_whitespace).unwrap_or(0);
+        (&description[..word_start], line_end)
+    };
+
+    let links = description[rest_start..]
+        .lines()
+        .filter(|line| is_url_line(line))
+        .map(|line| line.trim().trim_start_matches(['-', '*', '•']).trim().to_string())
+        .collect();
+
+    Description { text: format!("{}…", head.trim_end()), links }
+}
+
+fn is_url_line(line: &str) -> bool {
+    line.contains("https://") || line.contains("http://")
+}
+
 /// Determine the output file path: ~/Media/transcripts/YYYY-MM-DD-slugified-title.md
-pub fn output_path(meta: &VideoMetadata, output_dir: Option<&Path>) -> Result<PathBuf> {
-    let dir = match output_dir {
+/// With `organize`, files are nested per channel: <dir>/<channel-slug>/YYYY-MM-DD-title.md
+pub fn output_path(meta: &VideoMetadata, output_dir: Option<&Path>, organize: bool) -> Result<PathBuf> {
+    let mut dir = match output_dir {
         Some(d) => d.to_path_buf(),
         None => {
             let home = dirs::home_dir().context("Could not determine home directory")?;
@@ -40,6 +101,10 @@ pub fn output_path(meta: &VideoMetadata, output_dir: Option<&Path>) -> Result<Pa
         }
     };
 
+    if organize {
+        dir.push(channel_dir_name(meta.channel_name()));
+    }
+
     std::fs::create_dir_all(&dir)
         .with_context(|| format!("Failed to create output directory: {}", dir.display()))?;
 
@@ -56,3 +121,71 @@ pub fn output_path(meta: &VideoMetadata, output_dir: Option<&Path>) -> Result<Pa
     let filename = format!("{date_prefix}-{title_slug}.md");
     Ok(dir.join(filename))
 }
+
+/// Sanitize a channel name into a single path component
+fn channel_dir_name(channel: &str) -> String {
+    let channel_slug = slug::slugify(channel);
+    if channel_slug.is_empty() {
+        "unknown-channel".to_string()
+    } else {
+        channel_slug
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_description_kept_whole() {
+        let text = "Talk notes.\nSlides: https://example.com/slides\n";
+        assert_eq!(
+            truncate_description(text, 1500),
+            Description { text: text.trim().to_string(), links: Vec::new() }
+        );
+    }
+
+    #[test]
+    fn links_after_the_cut_are_listed() {
+        let description = format!(
+            "{}\n\nReferences:\n- Meditations: https://example.com/meditations\n\
+             • Letters https://example.com/letters\nThanks for watching!\n",
+            "Marcus Aurelius wrote for himself. ".repeat(10)
+        );
+        let cut = truncate_description(&description, 40);
+        assert_eq!(cut.text, "Marcus Aurelius wrote for himself.…");
+        assert_eq!(
+            cut.links,
+            vec!["Meditations: https://example.com/meditations", "Letters https://example.com/letters"]
+        );
+    }
+
+    #[test]
+    fn cut_inside_a_url_line_moves_it_to_links() {
+        let description = "Intro line.\nSource: https://example.com/a-very-long-path\nMore prose.";
+        let cut = truncate_description(description, 25);
+        assert_eq!(cut.text, "Intro line.…");
+        assert_eq!(cut.links, vec!["Source: https://example.com/a-very-long-path"]);
+    }
+
+    #[test]
+    fn description_section_in_markdown() {
+        let meta = VideoMetadata {
+            title: "Talk".into(),
+            channel: None,
+            uploader: None,
+            upload_date: None,
+            webpage_url: "https://youtu.be/x".into(),
+            duration: None,
+            duration_string: None,
+            description: Some("About the talk.\nhttps://example.com/more".into()),
+            id: "x".into(),
+        };
+        let markdown = format_markdown(&meta, "Hello.", false, 20);
+        assert!(markdown.ends_with(
+            "# Talk\n\n## Description\n\nAbout the talk.…\n\n### Links\n\n\
+             - https://example.com/more\n\n## Transcript\n\nHello.\n"
+        ));
+        assert!(format_markdown(&meta, "Hello.", false, 0).ends_with("# Talk\n\nHello.\n"));
+    }
+}
@@ -1,6 +1,6 @@
 use anyhow::{bail, Context, Result};
 use serde::Deserialize;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 #[derive(Debug, Deserialize)]
@@ -13,6 +13,7 @@ pub struct VideoMetadata {
     pub webpage_url: String,
     pub duration: Option<f64>,
     pub duration_string: Option<String>,
+    pub description: Option<String>,
     pub id: String,
 }
 
@@ -35,80 +36,207 @@ impl VideoMetadata {
     }
 }
 
-/// Fetch video metadata via yt-dlp --dump-json
-pub fn fetch_metadata(url: &str) -> Result<VideoMetadata> {
-    eprintln!("Fetching metadata...");
-    let output = Command::new("yt-dlp")
-        .args(["--dump-json", "--no-download", url])
-        .output()
-        .context("Failed to run yt-dlp — is it installed?")?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        bail!("yt-dlp metadata failed: {}", stderr.trim());
+/// The yt-dlp calls needed to save a single video. Batch runs go through
+/// this so they can be exercised without touching the network.
+pub trait VideoSource {
+    fn fetch_metadata(&self, url: &str) -> Result<VideoMetadata>;
+    fn download_subtitles(&self, url: &str, lang: &str, tmp_dir: &Path) -> Result<std::path::PathBuf>;
+}
+
+/// The real source: shells out to yt-dlp, passing through any cookie
+/// options for members-only or age-gated videos.
+#[derive(Default)]
+pub struct YtDlp {
+    pub cookies: Option<PathBuf>,
+    pub cookies_from_browser: Option<String>,
+}
+
+impl VideoSource for YtDlp {
+    /// Fetch video metadata via yt-dlp --dump-json
+    fn fetch_metadata(&self, url: &str) -> Result<VideoMetadata> {
+        eprintln!("Fetching metadata...");
+        let output = self
+            .command()
+            .args(["--dump-json", "--no-download", url])
+            .output()
+            .context("Failed to run yt-dlp — is it installed?")?;
+
+        if !output.status.success() {
+            bail!(self.failure("yt-dlp metadata failed", &output.stderr));
+        }
+
+        serde_json::from_slice(&output.stdout).context("Failed to parse yt-dlp JSON metadata")
     }
 
-    serde_json::from_slice(&output.stdout).context("Failed to parse yt-dlp JSON metadata")
+    /// Download subtitles to a temp directory, returning the path to the json3 file.
+    /// Prefers manual captions; falls back to auto-generated.
+    fn download_subtitles(&self, url: &str, lang: &str, tmp_dir: &Path) -> Result<std::path::PathBuf> {
+        eprintln!("Downloading subtitles...");
+
+        // Try manual captions first
+        let manual_result = self.try_download_subs(url, lang, tmp_dir, false);
+        if let Ok(path) = manual_result {
+            eprintln!("Using manual captions");
+            return Ok(path);
+        }
+
+        // Fall back to auto-generated
+        let auto_result = self.try_download_subs(url, lang, tmp_dir, true);
+        if let Ok(path) = auto_result {
+            eprintln!("Using auto-generated captions");
+            return Ok(path);
+        }
+
+        // A login wall isn't a missing-subtitles problem; say so
+        for err in [manual_result, auto_result].into_iter().filter_map(Result::err) {
+            if is_auth_error(&err.to_string()) {
+                return Err(err);
+            }
+        }
+
+        bail!("No subtitles available for this video (tried manual and auto-generated, language: {lang})")
+    }
 }
 
-/// Download subtitles to a temp directory, returning the path to the json3 file.
-/// Prefers manual captions; falls back to auto-generated.
-pub fn download_subtitles(url: &str, lang: &str, tmp_dir: &Path) -> Result<std::path::PathBuf> {
-    eprintln!("Downloading subtitles...");
+impl YtDlp {
+    /// A yt-dlp command with the cookie options already applied
+    fn command(&self) -> Command {
+        let mut cmd = Command::new("yt-dlp");
+        cmd.args(self.cookie_args());
+        cmd
+    }
 
-    // Try manual captions first
-    let manual_result = try_download_subs(url, lang, tmp_dir, false);
-    if let Ok(path) = manual_result {
-        eprintln!("Using manual captions");
-        return Ok(path);
+    fn cookie_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(ref path) = self.cookies {
+            args.push("--cookies".to_string());
+            args.push(path.to_string_lossy().to_string());
+        }
+        if let Some(ref browser) = self.cookies_from_browser {
+            args.push("--cookies-from-browser".to_string());
+            args.push(browser.clone());
+        }
+        args
     }
 
-    // Fall back to auto-generated
-    let auto_result = try_download_subs(url, lang, tmp_dir, true);
-    if let Ok(path) = auto_result {
-        eprintln!("Using auto-generated captions");
-        return Ok(path);
+    fn has_cookies(&self) -> bool {
+        self.cookies.is_some() || self.cookies_from_browser.is_some()
     }
 
-    bail!("No subtitles available for this video (tried manual and auto-generated, language: {lang})")
-}
+    /// Error message for a failed yt-dlp run, with a hint when the video
+    /// needs a login
+    fn failure(&self, what: &str, stderr: &[u8]) -> String {
+        let stderr = String::from_utf8_lossy(stderr);
+        let mut message = format!("{what}: {}", stderr.trim());
+        if is_auth_error(&stderr) {
+            message.push_str("\n\n");
+            message.push_str(auth_hint(self.has_cookies()));
+        }
+        message
+    }
 
-fn try_download_subs(
-    url: &str,
-    lang: &str,
-    tmp_dir: &Path,
-    auto_subs: bool,
-) -> Result<std::path::PathBuf> {
-    let mut args = vec![
-        "--skip-download".to_string(),
-        "--sub-format".to_string(),
-        "json3".to_string(),
-        "--sub-langs".to_string(),
-        lang.to_string(),
-        "-o".to_string(),
-        tmp_dir.join("subs.%(ext)s").to_string_lossy().to_string(),
-    ];
-
-    if auto_subs {
-        args.push("--write-auto-subs".to_string());
-    } else {
-        args.push("--write-subs".to_string());
+    fn try_download_subs(
+        &self,
+        url: &str,
+        lang: &str,
+        tmp_dir: &Path,
+        auto_subs: bool,
+    ) -> Result<std::path::PathBuf> {
+        let mut args = vec![
+            "--skip-download".to_string(),
+            "--sub-format".to_string(),
+            "json3".to_string(),
+            "--sub-langs".to_string(),
+            lang.to_string(),
+            "-o".to_string(),
+            tmp_dir.join("subs.%(ext)s").to_string_lossy().to_string(),
+        ];
+
+        if auto_subs {
+            args.push("--write-auto-subs".to_string());
+        } else {
+            args.push("--write-subs".to_string());
+        }
+
+        args.push(url.to_string());
+
+        let output = self
+            .command()
+            .args(&args)
+            .output()
+            .context("Failed to run yt-dlp for subtitles")?;
+
+        if !output.status.success() {
+            bail!(self.failure("yt-dlp subtitle download failed", &output.stderr));
+        }
+
+        // Find the json3 file in tmp_dir
+        find_json3_file(tmp_dir)
     }
 
-    args.push(url.to_string());
+    /// List video URLs from a channel, up to `limit`.
+    pub fn list_channel_videos(&self, channel_url: &str, limit: usize) -> Result<Vec<String>> {
+        eprintln!("Listing channel videos (limit {limit})...");
+        let output = self
+            .command()
+            .args([
+                "--flat-playlist",
+                "--print",
+                "url",
+                "--playlist-end",
+                &limit.to_string(),
+                channel_url,
+            ])
+            .output()
+            .context("Failed to run yt-dlp for channel listing")?;
+
+        if !output.status.success() {
+            bail!(self.failure("yt-dlp channel listing failed", &output.stderr));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let urls: Vec<String> = stdout
+            .lines()
+            .map(|l| l.trim().to_string())
+            .filter(|l| !l.is_empty())
+            .collect();
 
-    let output = Command::new("yt-dlp")
-        .args(&args)
-        .output()
-        .context("Failed to run yt-dlp for subtitles")?;
+        if urls.is_empty() {
+            bail!("No videos found for channel: {channel_url}");
+        }
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        bail!("yt-dlp subtitle download failed: {}", stderr.trim());
+        eprintln!("Found {} videos", urls.len());
+        Ok(urls)
     }
+}
+
+/// yt-dlp error text that means the video needs a logged-in session
+const AUTH_ERROR_MARKERS: &[&str] = &[
+    "sign in to confirm your age",
+    "sign in to confirm you",
+    "members-only",
+    "members only",
+    "join this channel",
+    "available to this channel's members",
+    "private video",
+    "login required",
+    "requires authentication",
+    "use --cookies",
+];
 
-    // Find the json3 file in tmp_dir
-    find_json3_file(tmp_dir)
+fn is_auth_error(stderr: &str) -> bool {
+    let lower = stderr.to_lowercase();
+    AUTH_ERROR_MARKERS.iter().any(|marker| lower.contains(marker))
+}
+
+fn auth_hint(has_cookies: bool) -> &'static str {
+    if has_cookies {
+        "This video needs a logged-in session and the supplied cookies weren't accepted. \
+         They may have expired or belong to an account without access; re-export them and try again."
+    } else {
+        "This video needs a logged-in session (members-only, private or age-gated). \
+         Pass --cookies FILE (Netscape format) or --cookies-from-browser NAME (e.g. firefox, chrome)."
+    }
 }
 
 fn find_json3_file(dir: &Path) -> Result<std::path::PathBuf> {
@@ -122,37 +250,40 @@ fn find_json3_file(dir: &Path) -> Result<std::path::PathBuf> {
     bail!("No json3 subtitle file found in {}", dir.display())
 }
 
-/// List video URLs from a channel, up to `limit`.
-pub fn list_channel_videos(channel_url: &str, limit: usize) -> Result<Vec<String>> {
-    eprintln!("Listing channel videos (limit {limit})...");
-    let output = Command::new("yt-dlp")
-        .args([
-            "--flat-playlist",
-            "--print",
-            "url",
-            "--playlist-end",
-            &limit.to_string(),
-            channel_url,
-        ])
-        .output()
-        .context("Failed to run yt-dlp for channel listing")?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        bail!("yt-dlp channel listing failed: {}", stderr.trim());
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cookie_args_passed_through() {
+        let ytdlp = YtDlp {
+            cookies: Some(PathBuf::from("/tmp/cookies.txt")),
+            cookies_from_browser: Some("firefox".to_string()),
+        };
+        assert_eq!(
+            ytdlp.cookie_args(),
+            vec!["--cookies", "/tmp/cookies.txt", "--cookies-from-browser", "firefox"]
+        );
+        assert!(YtDlp::default().cookie_args().is_empty());
     }
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let urls: Vec<String> = stdout
-        .lines()
-        .map(|l| l.trim().to_string())
-        .filter(|l| !l.is_empty())
-        .collect();
+    #[test]
+    fn auth_errors_get_a_cookie_hint() {
+        let stderr = b"ERROR: [youtube] abc123: Join this channel to get access to members-only content like this video";
+        let message = YtDlp::default().failure("yt-dlp metadata failed", stderr);
+        assert!(message.contains("--cookies-from-browser"));
 
-    if urls.is_empty() {
-        bail!("No videos found for channel: {channel_url}");
+        let with_cookies = YtDlp {
+            cookies: Some(PathBuf::from("c.txt")),
+            ..Default::default()
+        };
+        let message = with_cookies.failure("yt-dlp metadata failed", stderr);
+        assert!(message.contains("weren't accepted"));
     }
 
-    eprintln!("Found {} videos", urls.len());
-    Ok(urls)
+    #[test]
+    fn other_errors_have_no_hint() {
+        let message = YtDlp::default().failure("yt-dlp metadata failed", b"ERROR: Unable to download webpage: timed out");
+        assert_eq!(message, "yt-dlp metadata failed: ERROR: Unable to download webpage: timed out");
+    }
 }